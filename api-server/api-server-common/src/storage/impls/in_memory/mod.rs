@@ -15,10 +15,14 @@
 
 pub mod transactional;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use common::{
-    chain::{Block, ChainConfig, GenBlock, SignedTransaction, Transaction},
+    chain::{
+        tokens::{TokenId, TokenIssuance},
+        Block, ChainConfig, Destination, GenBlock, SignedTransaction, Transaction, TxOutput,
+        UtxoOutPoint,
+    },
     primitives::{BlockHeight, Id},
 };
 
@@ -32,6 +36,9 @@ struct ApiServerInMemoryStorage {
     block_aux_data_table: BTreeMap<Id<Block>, BlockAuxData>,
     main_chain_blocks_table: BTreeMap<BlockHeight, Id<Block>>,
     transaction_table: BTreeMap<Id<Transaction>, (Option<Id<Block>>, SignedTransaction)>,
+    utxo_table: BTreeMap<UtxoOutPoint, (TxOutput, Destination)>,
+    address_utxos_table: BTreeMap<Destination, BTreeSet<UtxoOutPoint>>,
+    token_issuance_table: BTreeMap<TokenId, TokenIssuance>,
     best_block: (BlockHeight, Id<GenBlock>),
     storage_version: u32,
 }
@@ -43,6 +50,9 @@ impl ApiServerInMemoryStorage {
             block_aux_data_table: BTreeMap::new(),
             main_chain_blocks_table: BTreeMap::new(),
             transaction_table: BTreeMap::new(),
+            utxo_table: BTreeMap::new(),
+            address_utxos_table: BTreeMap::new(),
+            token_issuance_table: BTreeMap::new(),
             best_block: (0.into(), chain_config.genesis_block_id()),
             storage_version: super::CURRENT_STORAGE_VERSION,
         };
@@ -111,6 +121,31 @@ impl ApiServerInMemoryStorage {
         };
         Ok(Some(*block_id))
     }
+
+    fn get_address_utxos(
+        &self,
+        address: &Destination,
+    ) -> Result<Vec<(UtxoOutPoint, TxOutput)>, ApiServerStorageError> {
+        let outpoints = match self.address_utxos_table.get(address) {
+            Some(outpoints) => outpoints,
+            None => return Ok(Vec::new()),
+        };
+        let utxos = outpoints
+            .iter()
+            .map(|outpoint| {
+                let (output, _) = self.utxo_table.get(outpoint).expect("utxo must exist");
+                (outpoint.clone(), output.clone())
+            })
+            .collect();
+        Ok(utxos)
+    }
+
+    fn get_token_issuance(
+        &self,
+        token_id: TokenId,
+    ) -> Result<Option<TokenIssuance>, ApiServerStorageError> {
+        Ok(self.token_issuance_table.get(&token_id).cloned())
+    }
 }
 
 impl ApiServerInMemoryStorage {
@@ -178,4 +213,44 @@ impl ApiServerInMemoryStorage {
         self.main_chain_blocks_table.remove(&block_height);
         Ok(())
     }
+
+    fn set_address_utxo(
+        &mut self,
+        outpoint: UtxoOutPoint,
+        output: &TxOutput,
+        address: &Destination,
+    ) -> Result<(), ApiServerStorageError> {
+        self.del_address_utxo(outpoint.clone())?;
+        self.address_utxos_table
+            .entry(address.clone())
+            .or_default()
+            .insert(outpoint.clone());
+        self.utxo_table.insert(outpoint, (output.clone(), address.clone()));
+        Ok(())
+    }
+
+    fn del_address_utxo(&mut self, outpoint: UtxoOutPoint) -> Result<(), ApiServerStorageError> {
+        if let Some((_, address)) = self.utxo_table.remove(&outpoint) {
+            let outpoints = self.address_utxos_table.get_mut(&address).expect("address must exist");
+            outpoints.remove(&outpoint);
+            if outpoints.is_empty() {
+                self.address_utxos_table.remove(&address);
+            }
+        }
+        Ok(())
+    }
+
+    fn set_token_issuance(
+        &mut self,
+        token_id: TokenId,
+        issuance: &TokenIssuance,
+    ) -> Result<(), ApiServerStorageError> {
+        self.token_issuance_table.insert(token_id, issuance.clone());
+        Ok(())
+    }
+
+    fn del_token_issuance(&mut self, token_id: TokenId) -> Result<(), ApiServerStorageError> {
+        self.token_issuance_table.remove(&token_id);
+        Ok(())
+    }
 }
@@ -13,6 +13,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use common::chain::ChainConfig;
@@ -80,13 +82,13 @@ impl<'t> ApiServerTransactionRw for ApiServerInMemoryStorageTransactionalRw<'t>
 }
 
 pub struct TransactionalApiServerInMemoryStorage {
-    storage: RwLock<ApiServerInMemoryStorage>,
+    storage: Arc<RwLock<ApiServerInMemoryStorage>>,
 }
 
 impl TransactionalApiServerInMemoryStorage {
     pub fn new(chain_config: &ChainConfig) -> Self {
         Self {
-            storage: RwLock::new(ApiServerInMemoryStorage::new(chain_config)),
+            storage: Arc::new(RwLock::new(ApiServerInMemoryStorage::new(chain_config))),
         }
     }
 
@@ -100,7 +102,15 @@ impl TransactionalApiServerInMemoryStorage {
 
     pub async fn clone_storage(&self) -> Self {
         Self {
-            storage: self.storage.read().await.clone().into(),
+            storage: Arc::new(self.storage.read().await.clone().into()),
+        }
+    }
+
+    /// Another handle to the same data, so that the changes made through one handle are visible
+    /// through the other
+    pub fn share(&self) -> Self {
+        Self {
+            storage: Arc::clone(&self.storage),
         }
     }
 }
@@ -14,7 +14,10 @@
 // limitations under the License.
 
 use common::{
-    chain::{Block, GenBlock, SignedTransaction, Transaction},
+    chain::{
+        tokens::{TokenId, TokenIssuance},
+        Block, Destination, GenBlock, SignedTransaction, Transaction, TxOutput, UtxoOutPoint,
+    },
     primitives::{BlockHeight, Id},
 };
 
@@ -62,4 +65,18 @@ impl<'t> ApiServerStorageRead for ApiServerInMemoryStorageTransactionalRo<'t> {
     ) -> Result<Option<Id<Block>>, ApiServerStorageError> {
         self.transaction.get_main_chain_block_id(block_height)
     }
+
+    async fn get_address_utxos(
+        &self,
+        address: &Destination,
+    ) -> Result<Vec<(UtxoOutPoint, TxOutput)>, ApiServerStorageError> {
+        self.transaction.get_address_utxos(address)
+    }
+
+    async fn get_token_issuance(
+        &self,
+        token_id: TokenId,
+    ) -> Result<Option<TokenIssuance>, ApiServerStorageError> {
+        self.transaction.get_token_issuance(token_id)
+    }
 }
@@ -14,7 +14,11 @@
 // limitations under the License.
 
 use common::{
-    chain::{Block, ChainConfig, GenBlock, SignedTransaction, Transaction},
+    chain::{
+        tokens::{TokenId, TokenIssuance},
+        Block, ChainConfig, Destination, GenBlock, SignedTransaction, Transaction, TxOutput,
+        UtxoOutPoint,
+    },
     primitives::{BlockHeight, Id},
 };
 
@@ -81,6 +85,34 @@ impl<'t> ApiServerStorageWrite for ApiServerInMemoryStorageTransactionalRw<'t> {
     ) -> Result<(), ApiServerStorageError> {
         self.transaction.del_main_chain_block_id(block_height)
     }
+
+    async fn set_address_utxo(
+        &mut self,
+        outpoint: UtxoOutPoint,
+        output: &TxOutput,
+        address: &Destination,
+    ) -> Result<(), ApiServerStorageError> {
+        self.transaction.set_address_utxo(outpoint, output, address)
+    }
+
+    async fn del_address_utxo(
+        &mut self,
+        outpoint: UtxoOutPoint,
+    ) -> Result<(), ApiServerStorageError> {
+        self.transaction.del_address_utxo(outpoint)
+    }
+
+    async fn set_token_issuance(
+        &mut self,
+        token_id: TokenId,
+        issuance: &TokenIssuance,
+    ) -> Result<(), ApiServerStorageError> {
+        self.transaction.set_token_issuance(token_id, issuance)
+    }
+
+    async fn del_token_issuance(&mut self, token_id: TokenId) -> Result<(), ApiServerStorageError> {
+        self.transaction.del_token_issuance(token_id)
+    }
 }
 
 #[async_trait::async_trait]
@@ -121,4 +153,18 @@ impl<'t> ApiServerStorageRead for ApiServerInMemoryStorageTransactionalRw<'t> {
     ) -> Result<Option<(Option<Id<Block>>, SignedTransaction)>, ApiServerStorageError> {
         self.transaction.get_transaction(transaction_id)
     }
+
+    async fn get_address_utxos(
+        &self,
+        address: &Destination,
+    ) -> Result<Vec<(UtxoOutPoint, TxOutput)>, ApiServerStorageError> {
+        self.transaction.get_address_utxos(address)
+    }
+
+    async fn get_token_issuance(
+        &self,
+        token_id: TokenId,
+    ) -> Result<Option<TokenIssuance>, ApiServerStorageError> {
+        self.transaction.get_token_issuance(token_id)
+    }
 }
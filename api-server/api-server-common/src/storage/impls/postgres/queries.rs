@@ -17,7 +17,11 @@ use bb8_postgres::{bb8::PooledConnection, PostgresConnectionManager};
 use serialization::{DecodeAll, Encode};
 
 use common::{
-    chain::{Block, ChainConfig, GenBlock, SignedTransaction, Transaction},
+    chain::{
+        tokens::{TokenId, TokenIssuance},
+        Block, ChainConfig, Destination, GenBlock, SignedTransaction, Transaction, TxOutput,
+        UtxoOutPoint,
+    },
     primitives::{BlockHeight, Id},
 };
 use tokio_postgres::NoTls;
@@ -211,6 +215,26 @@ impl<'a, 'b> QueryFromConnection<'a, 'b> {
         )
         .await?;
 
+        self.just_execute(
+            "CREATE TABLE ml_utxo (
+                    outpoint bytea PRIMARY KEY,
+                    address bytea NOT NULL,
+                    utxo bytea NOT NULL
+                );",
+        )
+        .await?;
+
+        self.just_execute("CREATE INDEX ml_utxo_address_index ON ml_utxo (address);")
+            .await?;
+
+        self.just_execute(
+            "CREATE TABLE ml_token_issuance (
+                    token_id bytea PRIMARY KEY,
+                    issuance bytea NOT NULL
+                );",
+        )
+        .await?;
+
         logging::log::info!("Done creating database tables");
 
         Ok(())
@@ -470,4 +494,142 @@ impl<'a, 'b> QueryFromConnection<'a, 'b> {
 
         Ok(())
     }
+
+    pub async fn get_address_utxos(
+        &mut self,
+        address: &Destination,
+    ) -> Result<Vec<(UtxoOutPoint, TxOutput)>, ApiServerStorageError> {
+        let rows = self
+            .tx
+            .query(
+                "SELECT outpoint, utxo FROM ml_utxo WHERE address = $1;",
+                &[&address.encode()],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let outpoint: Vec<u8> = row.get(0);
+                let utxo: Vec<u8> = row.get(1);
+
+                let outpoint = UtxoOutPoint::decode_all(&mut outpoint.as_slice()).map_err(|e| {
+                    ApiServerStorageError::DeserializationError(format!(
+                        "Outpoint deserialization failed: {}",
+                        e
+                    ))
+                })?;
+                let utxo = TxOutput::decode_all(&mut utxo.as_slice()).map_err(|e| {
+                    ApiServerStorageError::DeserializationError(format!(
+                        "Utxo for outpoint {:?} deserialization failed: {}",
+                        outpoint, e
+                    ))
+                })?;
+
+                Ok((outpoint, utxo))
+            })
+            .collect()
+    }
+
+    pub async fn set_address_utxo(
+        &mut self,
+        outpoint: UtxoOutPoint,
+        output: &TxOutput,
+        address: &Destination,
+    ) -> Result<(), ApiServerStorageError> {
+        logging::log::debug!("Inserting utxo {:?} for address {:?}", outpoint, address);
+
+        self.tx
+            .execute(
+                "INSERT INTO ml_utxo (outpoint, address, utxo) VALUES ($1, $2, $3)
+                    ON CONFLICT (outpoint) DO UPDATE
+                    SET address = $2, utxo = $3;",
+                &[&outpoint.encode(), &address.encode(), &output.encode()],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    pub async fn del_address_utxo(
+        &mut self,
+        outpoint: UtxoOutPoint,
+    ) -> Result<(), ApiServerStorageError> {
+        self.tx
+            .execute(
+                "DELETE FROM ml_utxo
+                WHERE outpoint = $1;",
+                &[&outpoint.encode()],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    pub async fn get_token_issuance(
+        &mut self,
+        token_id: TokenId,
+    ) -> Result<Option<TokenIssuance>, ApiServerStorageError> {
+        let row = self
+            .tx
+            .query_opt(
+                "SELECT issuance FROM ml_token_issuance WHERE token_id = $1;",
+                &[&token_id.encode()],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        let data = match row {
+            Some(d) => d,
+            None => return Ok(None),
+        };
+
+        let issuance: Vec<u8> = data.get(0);
+        let issuance = TokenIssuance::decode_all(&mut issuance.as_slice()).map_err(|e| {
+            ApiServerStorageError::DeserializationError(format!(
+                "Token issuance for token id {} deserialization failed: {}",
+                token_id, e
+            ))
+        })?;
+
+        Ok(Some(issuance))
+    }
+
+    pub async fn set_token_issuance(
+        &mut self,
+        token_id: TokenId,
+        issuance: &TokenIssuance,
+    ) -> Result<(), ApiServerStorageError> {
+        logging::log::debug!("Inserting token issuance for token id {}", token_id);
+
+        self.tx
+            .execute(
+                "INSERT INTO ml_token_issuance (token_id, issuance) VALUES ($1, $2)
+                    ON CONFLICT (token_id) DO UPDATE
+                    SET issuance = $2;",
+                &[&token_id.encode(), &issuance.encode()],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    pub async fn del_token_issuance(
+        &mut self,
+        token_id: TokenId,
+    ) -> Result<(), ApiServerStorageError> {
+        self.tx
+            .execute(
+                "DELETE FROM ml_token_issuance
+                WHERE token_id = $1;",
+                &[&token_id.encode()],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        Ok(())
+    }
 }
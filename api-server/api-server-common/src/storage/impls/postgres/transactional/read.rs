@@ -106,4 +106,30 @@ impl<'a> ApiServerStorageRead for ApiServerPostgresTransactionalRo<'a> {
 
         Ok(res)
     }
+
+    async fn get_address_utxos(
+        &self,
+        address: &common::chain::Destination,
+    ) -> Result<
+        Vec<(common::chain::UtxoOutPoint, common::chain::TxOutput)>,
+        crate::storage::storage_api::ApiServerStorageError,
+    > {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_address_utxos(address).await?;
+
+        Ok(res)
+    }
+
+    async fn get_token_issuance(
+        &self,
+        token_id: common::chain::tokens::TokenId,
+    ) -> Result<
+        Option<common::chain::tokens::TokenIssuance>,
+        crate::storage::storage_api::ApiServerStorageError,
+    > {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_token_issuance(token_id).await?;
+
+        Ok(res)
+    }
 }
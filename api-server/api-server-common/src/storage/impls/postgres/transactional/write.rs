@@ -14,7 +14,11 @@
 // limitations under the License.
 
 use common::{
-    chain::{Block, ChainConfig, GenBlock, SignedTransaction, Transaction},
+    chain::{
+        tokens::{TokenId, TokenIssuance},
+        Block, ChainConfig, Destination, GenBlock, SignedTransaction, Transaction, TxOutput,
+        UtxoOutPoint,
+    },
     primitives::{BlockHeight, Id},
 };
 
@@ -105,6 +109,46 @@ impl<'a> ApiServerStorageWrite for ApiServerPostgresTransactionalRw<'a> {
 
         Ok(())
     }
+
+    async fn set_address_utxo(
+        &mut self,
+        outpoint: UtxoOutPoint,
+        output: &TxOutput,
+        address: &Destination,
+    ) -> Result<(), ApiServerStorageError> {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        conn.set_address_utxo(outpoint, output, address).await?;
+
+        Ok(())
+    }
+
+    async fn del_address_utxo(
+        &mut self,
+        outpoint: UtxoOutPoint,
+    ) -> Result<(), ApiServerStorageError> {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        conn.del_address_utxo(outpoint).await?;
+
+        Ok(())
+    }
+
+    async fn set_token_issuance(
+        &mut self,
+        token_id: TokenId,
+        issuance: &TokenIssuance,
+    ) -> Result<(), ApiServerStorageError> {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        conn.set_token_issuance(token_id, issuance).await?;
+
+        Ok(())
+    }
+
+    async fn del_token_issuance(&mut self, token_id: TokenId) -> Result<(), ApiServerStorageError> {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        conn.del_token_issuance(token_id).await?;
+
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -166,4 +210,24 @@ impl<'a> ApiServerStorageRead for ApiServerPostgresTransactionalRw<'a> {
 
         Ok(res)
     }
+
+    async fn get_address_utxos(
+        &self,
+        address: &Destination,
+    ) -> Result<Vec<(UtxoOutPoint, TxOutput)>, ApiServerStorageError> {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_address_utxos(address).await?;
+
+        Ok(res)
+    }
+
+    async fn get_token_issuance(
+        &self,
+        token_id: TokenId,
+    ) -> Result<Option<TokenIssuance>, ApiServerStorageError> {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_token_issuance(token_id).await?;
+
+        Ok(res)
+    }
 }
@@ -14,7 +14,11 @@
 // limitations under the License.
 
 use common::{
-    chain::{Block, ChainConfig, GenBlock, SignedTransaction, Transaction},
+    chain::{
+        tokens::{TokenId, TokenIssuance},
+        Block, ChainConfig, Destination, GenBlock, SignedTransaction, Transaction, TxOutput,
+        UtxoOutPoint,
+    },
     primitives::{BlockHeight, Id},
 };
 
@@ -69,6 +73,18 @@ pub trait ApiServerStorageRead: Sync {
         &self,
         transaction_id: Id<Transaction>,
     ) -> Result<Option<(Option<Id<Block>>, SignedTransaction)>, ApiServerStorageError>;
+
+    /// Returns the unspent outputs that belong to the given address.
+    async fn get_address_utxos(
+        &self,
+        address: &Destination,
+    ) -> Result<Vec<(UtxoOutPoint, TxOutput)>, ApiServerStorageError>;
+
+    /// Returns the issuance data of a fungible token.
+    async fn get_token_issuance(
+        &self,
+        token_id: TokenId,
+    ) -> Result<Option<TokenIssuance>, ApiServerStorageError>;
 }
 
 #[async_trait::async_trait]
@@ -113,6 +129,27 @@ pub trait ApiServerStorageWrite: ApiServerStorageRead {
         &mut self,
         block_height: BlockHeight,
     ) -> Result<(), ApiServerStorageError>;
+
+    async fn set_address_utxo(
+        &mut self,
+        outpoint: UtxoOutPoint,
+        output: &TxOutput,
+        address: &Destination,
+    ) -> Result<(), ApiServerStorageError>;
+
+    /// Removes a spent output, removing an output that isn't indexed is OK.
+    async fn del_address_utxo(
+        &mut self,
+        outpoint: UtxoOutPoint,
+    ) -> Result<(), ApiServerStorageError>;
+
+    async fn set_token_issuance(
+        &mut self,
+        token_id: TokenId,
+        issuance: &TokenIssuance,
+    ) -> Result<(), ApiServerStorageError>;
+
+    async fn del_token_issuance(&mut self, token_id: TokenId) -> Result<(), ApiServerStorageError>;
 }
 
 #[async_trait::async_trait]
@@ -15,11 +15,15 @@
 
 use crate::sync::local_state::LocalBlockchainState;
 use api_server_common::storage::storage_api::{
-    ApiServerStorage, ApiServerStorageError, ApiServerStorageRead, ApiServerStorageWrite,
-    ApiServerTransactionRw,
+    block_aux_data::BlockAuxData, ApiServerStorage, ApiServerStorageError, ApiServerStorageRead,
+    ApiServerStorageWrite, ApiServerTransactionRw,
 };
 use common::{
-    chain::{Block, GenBlock},
+    chain::{
+        output_value::OutputValue,
+        tokens::{token_id, TokenData, TokenIssuance},
+        Block, Destination, GenBlock, OutPointSourceId, TxInput, TxOutput, UtxoOutPoint,
+    },
     primitives::{id::WithId, BlockHeight, Id, Idable},
 };
 
@@ -27,6 +31,10 @@ use common::{
 pub enum BlockchainStateError {
     #[error("Unexpected storage error: {0}")]
     StorageError(#[from] ApiServerStorageError),
+    #[error("Main chain block not found at height {0}")]
+    MainChainBlockNotFound(BlockHeight),
+    #[error("Block not found: {0}")]
+    BlockNotFound(Id<Block>),
 }
 
 pub struct BlockchainState<S: ApiServerStorage> {
@@ -64,7 +72,7 @@ impl<S: ApiServerStorage + Send + Sync> LocalBlockchainState for BlockchainState
         while db_tx.get_best_block().await?.0 > common_block_height {
             let current_best = db_tx.get_best_block().await?;
             logging::log::info!("Disconnecting block: {:?}", current_best);
-            db_tx.del_main_chain_block_id(current_best.0).await?;
+            disconnect_block(&mut db_tx, current_best.0).await?;
         }
 
         // Connect the new blocks in the new chain
@@ -78,9 +86,42 @@ impl<S: ApiServerStorage + Send + Sync> LocalBlockchainState for BlockchainState
                 db_tx
                     .set_transaction(tx.transaction().get_id(), Some(block.get_id()), tx)
                     .await?;
+
+                for input in tx.inputs() {
+                    match input {
+                        TxInput::Utxo(outpoint) => {
+                            db_tx.del_address_utxo(outpoint.clone()).await?;
+                        }
+                        TxInput::Account(_) => {}
+                    }
+                }
+
+                update_address_utxos(&mut db_tx, tx.transaction().get_id().into(), tx.outputs())
+                    .await?;
+
+                // A transaction can issue at most one token
+                if let (Some(issuance), Some(token_id)) = (
+                    tx.outputs().iter().find_map(token_issuance),
+                    token_id(tx.transaction()),
+                ) {
+                    db_tx.set_token_issuance(token_id, issuance).await?;
+                }
             }
 
+            update_address_utxos(
+                &mut db_tx,
+                block.get_id().into(),
+                block.block_reward().outputs(),
+            )
+            .await?;
+
             db_tx.set_block(block.get_id(), &block).await?;
+            db_tx
+                .set_block_aux_data(
+                    block.get_id(),
+                    &BlockAuxData::new(block.get_id(), block_height),
+                )
+                .await?;
             db_tx.set_best_block(block_height, block.get_id().into()).await?;
         }
 
@@ -91,3 +132,135 @@ impl<S: ApiServerStorage + Send + Sync> LocalBlockchainState for BlockchainState
         Ok(())
     }
 }
+
+/// Removes the main chain block at the given height and reverts its changes of the address outputs
+async fn disconnect_block<T: ApiServerStorageWrite + Send>(
+    db_tx: &mut T,
+    block_height: BlockHeight,
+) -> Result<(), BlockchainStateError> {
+    let block_id = db_tx
+        .get_main_chain_block_id(block_height)
+        .await?
+        .ok_or(BlockchainStateError::MainChainBlockNotFound(block_height))?;
+    let block = db_tx
+        .get_block(block_id)
+        .await?
+        .ok_or(BlockchainStateError::BlockNotFound(block_id))?;
+
+    remove_address_utxos(db_tx, block_id.into(), block.block_reward().outputs()).await?;
+
+    // Undo the transactions in the reverse order, so an output that was created and spent
+    // in the same block ends up removed
+    for tx in block.transactions().iter().rev() {
+        remove_address_utxos(db_tx, tx.transaction().get_id().into(), tx.outputs()).await?;
+
+        if tx.outputs().iter().any(|output| token_issuance(output).is_some()) {
+            if let Some(token_id) = token_id(tx.transaction()) {
+                db_tx.del_token_issuance(token_id).await?;
+            }
+        }
+
+        for input in tx.inputs() {
+            match input {
+                TxInput::Utxo(outpoint) => restore_address_utxo(db_tx, outpoint).await?,
+                TxInput::Account(_) => {}
+            }
+        }
+    }
+
+    db_tx.del_main_chain_block_id(block_height).await?;
+    let prev_height = block_height.prev_height().expect("the genesis block is never disconnected");
+    db_tx.set_best_block(prev_height, block.prev_block_id()).await?;
+
+    Ok(())
+}
+
+async fn remove_address_utxos<T: ApiServerStorageWrite + Send>(
+    db_tx: &mut T,
+    source_id: OutPointSourceId,
+    outputs: &[TxOutput],
+) -> Result<(), ApiServerStorageError> {
+    for index in 0..outputs.len() {
+        let outpoint = UtxoOutPoint::new(source_id.clone(), index as u32);
+        db_tx.del_address_utxo(outpoint).await?;
+    }
+    Ok(())
+}
+
+/// Indexes again an output that was spent by a disconnected block
+async fn restore_address_utxo<T: ApiServerStorageWrite + Send>(
+    db_tx: &mut T,
+    outpoint: &UtxoOutPoint,
+) -> Result<(), ApiServerStorageError> {
+    let index = outpoint.output_index() as usize;
+    let output = match outpoint.source_id() {
+        OutPointSourceId::Transaction(tx_id) => db_tx
+            .get_transaction(tx_id)
+            .await?
+            .and_then(|(_, tx)| tx.outputs().get(index).cloned()),
+        // The genesis outputs are not indexed, so they are not found here either
+        OutPointSourceId::BlockReward(block_id) => db_tx
+            .get_block(Id::new(block_id.to_hash()))
+            .await?
+            .and_then(|block| block.block_reward().outputs().get(index).cloned()),
+    };
+
+    if let Some(output) = output {
+        if let Some(destination) = address_utxo_destination(&output) {
+            db_tx.set_address_utxo(outpoint.clone(), &output, destination).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Indexes the outputs that belong to an address, so the address balance can be queried
+async fn update_address_utxos<T: ApiServerStorageWrite + Send>(
+    db_tx: &mut T,
+    source_id: OutPointSourceId,
+    outputs: &[TxOutput],
+) -> Result<(), ApiServerStorageError> {
+    for (index, output) in outputs.iter().enumerate() {
+        if let Some(destination) = address_utxo_destination(output) {
+            let outpoint = UtxoOutPoint::new(source_id.clone(), index as u32);
+            db_tx.set_address_utxo(outpoint, output, destination).await?;
+        }
+    }
+    Ok(())
+}
+
+/// The address of an output that counts towards the address balance
+fn address_utxo_destination(output: &TxOutput) -> Option<&Destination> {
+    match output {
+        TxOutput::Transfer(_, destination) | TxOutput::LockThenTransfer(_, destination, _) => {
+            Some(destination)
+        }
+        TxOutput::Burn(_)
+        | TxOutput::CreateStakePool(_, _)
+        | TxOutput::ProduceBlockFromStake(_, _)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::DelegateStaking(_, _) => None,
+    }
+}
+
+fn token_issuance(output: &TxOutput) -> Option<&TokenIssuance> {
+    let value = match output {
+        TxOutput::Transfer(value, _)
+        | TxOutput::LockThenTransfer(value, _, _)
+        | TxOutput::Burn(value) => value,
+        TxOutput::CreateStakePool(_, _)
+        | TxOutput::ProduceBlockFromStake(_, _)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::DelegateStaking(_, _) => return None,
+    };
+
+    match value {
+        OutputValue::Token(token_data) => match token_data.as_ref() {
+            TokenData::TokenIssuance(issuance) => Some(issuance.as_ref()),
+            TokenData::TokenTransfer(_)
+            | TokenData::NftIssuance(_)
+            | TokenData::TokenFreezeV1(_)
+            | TokenData::TokenUnfreezeV1(_) => None,
+        },
+        OutputValue::Coin(_) => None,
+    }
+}
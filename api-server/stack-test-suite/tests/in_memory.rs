@@ -63,7 +63,12 @@ async fn bad_request() {
     let (task, response) = spawn_webserver("/non-existent-url").await;
 
     assert_eq!(response.status(), 400);
-    assert_eq!(response.text().await.unwrap(), r#"{"error":"Bad request"}"#);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["error"].as_str().unwrap(), "Bad request");
+    assert_eq!(body["code"].as_str().unwrap(), "bad_request");
 
     task.abort();
 }
@@ -0,0 +1,288 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api_server_common::storage::storage_api::ApiServerStorageRead;
+use chainstate_test_framework::{empty_witness, TransactionBuilder};
+use common::{
+    address::{pubkeyhash::PublicKeyHash, Address},
+    chain::{OutPointSourceId, TxInput, UtxoOutPoint},
+};
+use crypto::key::{KeyKind, PrivateKey};
+
+use super::*;
+
+#[tokio::test]
+async fn invalid_address() {
+    let (task, response) = spawn_webserver("/api/v1/address/invalid-address/balance").await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["error"].as_str().unwrap(), "Invalid address");
+    assert_eq!(body["code"].as_str().unwrap(), "invalid_address");
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn ok(#[case] seed: Seed) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let task = tokio::spawn({
+        async move {
+            let web_server_state = {
+                let mut rng = make_seedable_rng(seed);
+
+                let chain_config = create_unit_test_config();
+
+                let (_, public_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+                let pkh_destination = Destination::PublicKeyHash(PublicKeyHash::from(&public_key));
+                let unfunded_destination = {
+                    let (_, public_key) =
+                        PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+                    Destination::PublicKeyHash(PublicKeyHash::from(&public_key))
+                };
+
+                let [spent_amount, anyonecanspend_amount, pkh_amount1] =
+                    std::array::from_fn(|_| Amount::from_atoms(rng.gen_range(1000..100_000)));
+                let pkh_amount2 = Amount::from_atoms(rng.gen_range(1..=spent_amount.into_atoms()));
+
+                let chainstate_blocks = {
+                    let mut tf = TestFramework::builder(&mut rng)
+                        .with_chain_config(chain_config.clone())
+                        .build();
+
+                    // Fund both addresses
+                    let transaction1 = TransactionBuilder::new()
+                        .add_input(
+                            TxInput::from_utxo(
+                                OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                                0,
+                            ),
+                            empty_witness(&mut rng),
+                        )
+                        .add_output(TxOutput::Transfer(
+                            OutputValue::Coin(spent_amount),
+                            Destination::AnyoneCanSpend,
+                        ))
+                        .add_output(TxOutput::Transfer(
+                            OutputValue::Coin(anyonecanspend_amount),
+                            Destination::AnyoneCanSpend,
+                        ))
+                        .add_output(TxOutput::Transfer(
+                            OutputValue::Coin(pkh_amount1),
+                            pkh_destination.clone(),
+                        ))
+                        .build();
+                    let transaction1_id = transaction1.transaction().get_id();
+                    let block1 = tf.make_block_builder().add_transaction(transaction1).build();
+                    tf.process_block(block1.clone(), BlockSource::Local).unwrap();
+
+                    // Spend one of the outputs to the other address
+                    let transaction2 = TransactionBuilder::new()
+                        .add_input(
+                            TxInput::from_utxo(transaction1_id.into(), 0),
+                            empty_witness(&mut rng),
+                        )
+                        .add_output(TxOutput::LockThenTransfer(
+                            OutputValue::Coin(pkh_amount2),
+                            pkh_destination.clone(),
+                            OutputTimeLock::ForBlockCount(10),
+                        ))
+                        .build();
+                    let block2 = tf.make_block_builder().add_transaction(transaction2).build();
+                    tf.process_block(block2.clone(), BlockSource::Local).unwrap();
+
+                    vec![block1, block2]
+                };
+
+                _ = tx.send([
+                    (
+                        Address::new(&chain_config, &Destination::AnyoneCanSpend).unwrap(),
+                        json!({ "coins": anyonecanspend_amount, "tokens": {} }),
+                    ),
+                    (
+                        Address::new(&chain_config, &pkh_destination).unwrap(),
+                        json!({
+                            "coins": (pkh_amount1 + pkh_amount2).unwrap(),
+                            "tokens": {},
+                        }),
+                    ),
+                    (
+                        Address::new(&chain_config, &unfunded_destination).unwrap(),
+                        json!({ "coins": Amount::ZERO, "tokens": {} }),
+                    ),
+                ]);
+
+                let storage = {
+                    let mut storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+                    let mut db_tx = storage.transaction_rw().await.unwrap();
+                    db_tx.initialize_storage(&chain_config).await.unwrap();
+                    db_tx.commit().await.unwrap();
+
+                    storage
+                };
+
+                let mut local_node = BlockchainState::new(storage);
+                local_node.scan_blocks(BlockHeight::new(0), chainstate_blocks).await.unwrap();
+
+                ApiServerWebServerState {
+                    db: Arc::new(local_node.storage().clone_storage().await),
+                    chain_config: Arc::new(chain_config),
+                }
+            };
+
+            web_server(listener, web_server_state).await
+        }
+    });
+
+    for (address, expected_balance) in rx.await.unwrap() {
+        let url = format!("/api/v1/address/{address}/balance");
+
+        // Given that the listener port is open, this will block until a
+        // response is made (by the web server, which takes the listener
+        // over)
+        let response = reqwest::get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+
+        let body = response.text().await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(body, expected_balance);
+    }
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn reorg(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let chain_config = create_unit_test_config();
+
+    let (_, public_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+    let pkh_destination = Destination::PublicKeyHash(PublicKeyHash::from(&public_key));
+
+    let [spent_amount, pkh_amount1] =
+        std::array::from_fn(|_| Amount::from_atoms(rng.gen_range(1000..100_000)));
+    let pkh_amount2 = Amount::from_atoms(rng.gen_range(1..=spent_amount.into_atoms()));
+
+    let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config.clone()).build();
+
+    let transaction1 = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(
+                OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                0,
+            ),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(spent_amount),
+            Destination::AnyoneCanSpend,
+        ))
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(pkh_amount1),
+            pkh_destination.clone(),
+        ))
+        .build();
+    let transaction1_id = transaction1.transaction().get_id();
+    let block1 = tf.make_block_builder().add_transaction(transaction1).build();
+    tf.process_block(block1.clone(), BlockSource::Local).unwrap();
+
+    // Spend one of the outputs and create another one in the same block
+    let transaction2 = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(transaction1_id.into(), 0),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(pkh_amount2),
+            Destination::AnyoneCanSpend,
+        ))
+        .build();
+    let transaction2_id = transaction2.transaction().get_id();
+    let transaction3 = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(transaction2_id.into(), 0),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(pkh_amount2),
+            pkh_destination.clone(),
+        ))
+        .build();
+    let block2 = tf
+        .make_block_builder()
+        .with_transactions(vec![transaction2, transaction3])
+        .build();
+    tf.process_block(block2.clone(), BlockSource::Local).unwrap();
+
+    // A competing block at the same height, without any transactions
+    let block2_alt = tf.make_block_builder().with_parent(block1.get_id().into()).build();
+
+    let storage = {
+        let mut storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+        let mut db_tx = storage.transaction_rw().await.unwrap();
+        db_tx.initialize_storage(&chain_config).await.unwrap();
+        db_tx.commit().await.unwrap();
+
+        storage
+    };
+
+    let mut local_node = BlockchainState::new(storage);
+    local_node.scan_blocks(BlockHeight::new(0), vec![block1, block2]).await.unwrap();
+    local_node
+        .scan_blocks(BlockHeight::new(1), vec![block2_alt.clone()])
+        .await
+        .unwrap();
+
+    let db_tx = local_node.storage().transaction_ro().await.unwrap();
+
+    assert_eq!(
+        db_tx.get_best_block().await.unwrap(),
+        (BlockHeight::new(2), block2_alt.get_id().into())
+    );
+    assert_eq!(
+        db_tx.get_address_utxos(&Destination::AnyoneCanSpend).await.unwrap(),
+        vec![(
+            UtxoOutPoint::new(transaction1_id.into(), 0),
+            TxOutput::Transfer(OutputValue::Coin(spent_amount), Destination::AnyoneCanSpend),
+        )]
+    );
+    assert_eq!(
+        db_tx.get_address_utxos(&pkh_destination).await.unwrap(),
+        vec![(
+            UtxoOutPoint::new(transaction1_id.into(), 1),
+            TxOutput::Transfer(OutputValue::Coin(pkh_amount1), pkh_destination.clone()),
+        )]
+    );
+}
@@ -25,6 +25,7 @@ async fn invalid_block_id() {
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(body["error"].as_str().unwrap(), "Invalid block Id");
+    assert_eq!(body["code"].as_str().unwrap(), "invalid_block_id");
 
     task.abort();
 }
@@ -36,12 +37,13 @@ async fn block_not_found() {
     )
     .await;
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 404);
 
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(body["error"].as_str().unwrap(), "Block not found");
+    assert_eq!(body["code"].as_str().unwrap(), "block_not_found");
 
     task.abort();
 }
@@ -25,6 +25,7 @@ async fn invalid_block_id() {
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(body["error"].as_str().unwrap(), "Invalid block Id");
+    assert_eq!(body["code"].as_str().unwrap(), "invalid_block_id");
 
     task.abort();
 }
@@ -34,12 +35,13 @@ async fn block_not_found() {
     let (task, response) = spawn_webserver(
     "/api/v1/block/0000000000000000000000000000000000000000000000000000000000000001/transaction-ids").await;
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 404);
 
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(body["error"].as_str().unwrap(), "Block not found");
+    assert_eq!(body["code"].as_str().unwrap(), "block_not_found");
 
     task.abort();
 }
@@ -135,3 +137,168 @@ async fn ok(#[case] seed: Seed) {
 
     task.abort();
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn paging(#[case] seed: Seed) {
+    use api_web_server::api::v1::{PAGINATION_MAX_LIMIT, TOTAL_COUNT_HEADER};
+    use chainstate_test_framework::{empty_witness, TransactionBuilder};
+    use common::chain::{OutPointSourceId, TxInput};
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let task = tokio::spawn({
+        async move {
+            let web_server_state = {
+                let mut rng = make_seedable_rng(seed);
+                // More transactions than can be returned at once
+                let n_transactions = PAGINATION_MAX_LIMIT + rng.gen_range(1..50);
+
+                let chain_config = create_unit_test_config();
+
+                let chainstate_blocks = {
+                    let mut tf = TestFramework::builder(&mut rng)
+                        .with_chain_config(chain_config.clone())
+                        .build();
+
+                    // Split the genesis output, so the next block can have many transactions
+                    let split_tx = (0..n_transactions)
+                        .fold(
+                            TransactionBuilder::new().add_input(
+                                TxInput::from_utxo(
+                                    OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                                    0,
+                                ),
+                                empty_witness(&mut rng),
+                            ),
+                            |builder, _| {
+                                builder.add_output(TxOutput::Transfer(
+                                    OutputValue::Coin(Amount::from_atoms(100)),
+                                    Destination::AnyoneCanSpend,
+                                ))
+                            },
+                        )
+                        .build();
+                    let split_tx_id = split_tx.transaction().get_id();
+                    let split_block = tf.make_block_builder().add_transaction(split_tx).build();
+                    tf.process_block(split_block.clone(), BlockSource::Local).unwrap();
+
+                    let transactions = (0..n_transactions)
+                        .map(|i| {
+                            TransactionBuilder::new()
+                                .add_input(
+                                    TxInput::from_utxo(split_tx_id.into(), i as u32),
+                                    empty_witness(&mut rng),
+                                )
+                                .add_output(TxOutput::Transfer(
+                                    OutputValue::Coin(Amount::from_atoms(100)),
+                                    Destination::AnyoneCanSpend,
+                                ))
+                                .build()
+                        })
+                        .collect::<Vec<_>>();
+                    let block = tf.make_block_builder().with_transactions(transactions).build();
+                    tf.process_block(block.clone(), BlockSource::Local).unwrap();
+
+                    _ = tx.send((
+                        block.get_id().to_hash().encode_hex::<String>(),
+                        block
+                            .transactions()
+                            .iter()
+                            .map(|tx| tx.transaction().get_id())
+                            .collect::<Vec<_>>(),
+                    ));
+
+                    vec![split_block, block]
+                };
+
+                let storage = {
+                    let mut storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+                    let mut db_tx = storage.transaction_rw().await.unwrap();
+                    db_tx.initialize_storage(&chain_config).await.unwrap();
+                    db_tx.commit().await.unwrap();
+
+                    storage
+                };
+
+                let mut local_node = BlockchainState::new(storage);
+                local_node.scan_blocks(BlockHeight::new(0), chainstate_blocks).await.unwrap();
+
+                ApiServerWebServerState {
+                    db: Arc::new(local_node.storage().clone_storage().await),
+                    chain_config: Arc::new(chain_config),
+                }
+            };
+
+            web_server(listener, web_server_state).await
+        }
+    });
+
+    let (block_id, transaction_ids) = rx.await.unwrap();
+    let total = transaction_ids.len();
+
+    let get = |query: String| {
+        let url = format!(
+            "http://{}:{}/api/v1/block/{block_id}/transaction-ids{query}",
+            addr.ip(),
+            addr.port()
+        );
+        async move { reqwest::get(url).await.unwrap() }
+    };
+
+    for (query, expected_range) in [
+        (String::new(), 0..PAGINATION_MAX_LIMIT),
+        ("?offset=0&limit=10".to_owned(), 0..10),
+        (
+            "?offset=10".to_owned(),
+            10..(PAGINATION_MAX_LIMIT + 10).min(total),
+        ),
+        (format!("?offset={}&limit=10", total - 5), total - 5..total),
+        (format!("?offset={total}"), total..total),
+        (format!("?offset={}&limit=10", total + 100), total..total),
+        (
+            format!("?limit={}", PAGINATION_MAX_LIMIT + 100),
+            0..PAGINATION_MAX_LIMIT,
+        ),
+        ("?offset=10&limit=0".to_owned(), 10..10),
+    ] {
+        let response = get(query).await;
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers()[TOTAL_COUNT_HEADER].to_str().unwrap(),
+            total.to_string()
+        );
+
+        let body = response.text().await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(body, json!(transaction_ids[expected_range]));
+    }
+
+    for query in ["?offset=-1", "?limit=abc"] {
+        let response = get(query.to_owned()).await;
+
+        assert_eq!(response.status(), 400);
+
+        let body = response.text().await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(
+            body["error"].as_str().unwrap(),
+            "Invalid pagination parameter"
+        );
+        assert_eq!(
+            body["code"].as_str().unwrap(),
+            "invalid_pagination_parameter"
+        );
+    }
+
+    task.abort();
+}
@@ -25,6 +25,7 @@ async fn invalid_height() {
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(body["error"].as_str().unwrap(), "Invalid block height");
+    assert_eq!(body["code"].as_str().unwrap(), "invalid_block_height");
 
     task.abort();
 }
@@ -33,7 +34,7 @@ async fn invalid_height() {
 async fn height_zero() {
     let (task, response) = spawn_webserver("/api/v1/chain/0").await;
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 404);
 
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
@@ -42,6 +43,7 @@ async fn height_zero() {
         body["error"].as_str().unwrap(),
         "No block found at supplied height"
     );
+    assert_eq!(body["code"].as_str().unwrap(), "no_block_at_height");
 
     task.abort();
 }
@@ -50,7 +52,7 @@ async fn height_zero() {
 async fn height_past_tip() {
     let (task, response) = spawn_webserver("/api/v1/chain/1337").await;
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 404);
 
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
@@ -59,6 +61,7 @@ async fn height_past_tip() {
         body["error"].as_str().unwrap(),
         "No block found at supplied height"
     );
+    assert_eq!(body["code"].as_str().unwrap(), "no_block_at_height");
 
     task.abort();
 }
@@ -13,12 +13,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod address_balance;
 mod block;
 mod block_header;
 mod block_reward;
 mod block_transaction_ids;
 mod chain_at_height;
 mod chain_tip;
+mod subscribe;
+mod token;
 mod transaction;
 mod transaction_merkle_path;
 
@@ -0,0 +1,111 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+use super::*;
+
+async fn expect_tip(
+    ws: &mut WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>,
+    expected_tip: serde_json::Value,
+) {
+    let message = tokio::time::timeout(Duration::from_secs(10), ws.next())
+        .await
+        .expect("tip notification must arrive")
+        .expect("subscription must be open")
+        .unwrap();
+
+    let body: serde_json::Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+    assert_eq!(body, expected_tip);
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn new_tip(#[case] seed: Seed) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = create_unit_test_config();
+
+    let storage = {
+        let mut storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+        let mut db_tx = storage.transaction_rw().await.unwrap();
+        db_tx.initialize_storage(&chain_config).await.unwrap();
+        db_tx.commit().await.unwrap();
+
+        storage
+    };
+
+    // The web server sees the blocks scanned by the local node
+    let web_server_state = ApiServerWebServerState {
+        db: Arc::new(storage.share()),
+        chain_config: Arc::new(chain_config.clone()),
+    };
+    let mut local_node = BlockchainState::new(storage);
+
+    let task = tokio::spawn(web_server(listener, web_server_state));
+
+    // The subscriptions share the storage poller
+    let url = format!("ws://{}:{}/api/v1/subscribe", addr.ip(), addr.port());
+    let (mut ws, _) = connect_async(url.as_str()).await.unwrap();
+    let (mut other_ws, _) = connect_async(url.as_str()).await.unwrap();
+
+    // The current tip is sent right away
+    let genesis_tip = json!({
+        "block_height": 0,
+        "block_id": chain_config.genesis_block_id(),
+    });
+    expect_tip(&mut ws, genesis_tip.clone()).await;
+    expect_tip(&mut other_ws, genesis_tip).await;
+
+    let n_blocks = rng.gen_range(1..10);
+    let chainstate_blocks = {
+        let mut tf =
+            TestFramework::builder(&mut rng).with_chain_config(chain_config.clone()).build();
+
+        let chainstate_block_ids = tf
+            .create_chain_return_ids(&tf.genesis().get_id().into(), n_blocks, &mut rng)
+            .unwrap();
+
+        chainstate_block_ids
+            .iter()
+            .map(|id| tf.block(tf.to_chain_block_id(id)))
+            .collect::<Vec<_>>()
+    };
+    let tip_id = chainstate_blocks.last().unwrap().get_id();
+
+    local_node.scan_blocks(BlockHeight::new(0), chainstate_blocks).await.unwrap();
+
+    let new_tip = json!({
+        "block_height": n_blocks,
+        "block_id": tip_id,
+    });
+    expect_tip(&mut ws, new_tip.clone()).await;
+    expect_tip(&mut other_ws, new_tip).await;
+
+    // Unsubscribe
+    ws.send(Message::Close(None)).await.unwrap();
+    let reply = tokio::time::timeout(Duration::from_secs(10), ws.next()).await.unwrap();
+    assert!(matches!(reply, Some(Ok(Message::Close(_))) | None));
+
+    task.abort();
+}
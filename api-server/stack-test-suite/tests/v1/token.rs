@@ -0,0 +1,241 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api_server_common::storage::storage_api::ApiServerStorageRead;
+use chainstate_test_framework::TransactionBuilder;
+use common::chain::{
+    signature::inputsig::InputWitness,
+    tokens::{token_id, TokenIssuance},
+    OutPointSourceId, TxInput,
+};
+use test_utils::random_string;
+
+use super::*;
+
+#[tokio::test]
+async fn invalid_token_id() {
+    let (task, response) = spawn_webserver("/api/v1/token/invalid-token-id").await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["error"].as_str().unwrap(), "Invalid token Id");
+    assert_eq!(body["code"].as_str().unwrap(), "invalid_token_id");
+
+    task.abort();
+}
+
+#[tokio::test]
+async fn token_not_found() {
+    let (task, response) = spawn_webserver(
+        "/api/v1/token/0000000000000000000000000000000000000000000000000000000000000001",
+    )
+    .await;
+
+    assert_eq!(response.status(), 404);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["error"].as_str().unwrap(), "Token not found");
+    assert_eq!(body["code"].as_str().unwrap(), "token_not_found");
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn ok(#[case] seed: Seed) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let task = tokio::spawn(async move {
+        let web_server_state = {
+            let mut rng = make_seedable_rng(seed);
+
+            let chain_config = create_unit_test_config();
+
+            let chainstate_blocks = {
+                let mut tf = TestFramework::builder(&mut rng)
+                    .with_chain_config(chain_config.clone())
+                    .build();
+
+                let issuance = TokenIssuance {
+                    token_ticker: random_string(&mut rng, 1..5).as_bytes().to_vec(),
+                    amount_to_issue: Amount::from_atoms(
+                        rng.gen_range(1..=chain_config.token_max_supply()),
+                    ),
+                    number_of_decimals: rng.gen_range(1..18),
+                    metadata_uri: random_string(&mut rng, 1..1024).as_bytes().to_vec(),
+                };
+
+                let transaction = TransactionBuilder::new()
+                    .add_input(
+                        TxInput::from_utxo(
+                            OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                            0,
+                        ),
+                        InputWitness::NoSignature(None),
+                    )
+                    .add_output(TxOutput::Transfer(
+                        issuance.clone().into(),
+                        Destination::AnyoneCanSpend,
+                    ))
+                    .add_output(TxOutput::Burn(OutputValue::Coin(
+                        chain_config.token_min_issuance_fee(),
+                    )))
+                    .build();
+                let token_id = token_id(transaction.transaction()).unwrap();
+
+                let block = tf.make_block_builder().add_transaction(transaction).build();
+                tf.process_block(block.clone(), BlockSource::Local).unwrap();
+
+                _ = tx.send((
+                    token_id.to_hash().encode_hex::<String>(),
+                    json!({
+                        "token_id": token_id,
+                        "token_ticker": String::from_utf8(issuance.token_ticker).unwrap(),
+                        "number_of_decimals": issuance.number_of_decimals,
+                        "total_supply": issuance.amount_to_issue,
+                        "metadata_uri": String::from_utf8(issuance.metadata_uri).unwrap(),
+                    }),
+                ));
+
+                vec![block]
+            };
+
+            let storage = {
+                let mut storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+                let mut db_tx = storage.transaction_rw().await.unwrap();
+                db_tx.initialize_storage(&chain_config).await.unwrap();
+                db_tx.commit().await.unwrap();
+
+                storage
+            };
+
+            let mut local_node = BlockchainState::new(storage);
+            local_node.scan_blocks(BlockHeight::new(0), chainstate_blocks).await.unwrap();
+
+            ApiServerWebServerState {
+                db: Arc::new(local_node.storage().clone_storage().await),
+                chain_config: Arc::new(chain_config),
+            }
+        };
+
+        web_server(listener, web_server_state).await
+    });
+
+    let (token_id, expected_token) = rx.await.unwrap();
+    let url = format!("/api/v1/token/{token_id}");
+
+    // Given that the listener port is open, this will block until a
+    // response is made (by the web server, which takes the listener
+    // over)
+    let response = reqwest::get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body, expected_token);
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn reorg(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let chain_config = create_unit_test_config();
+
+    let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config.clone()).build();
+
+    let issuance = TokenIssuance {
+        token_ticker: random_string(&mut rng, 1..5).as_bytes().to_vec(),
+        amount_to_issue: Amount::from_atoms(rng.gen_range(1..=chain_config.token_max_supply())),
+        number_of_decimals: rng.gen_range(1..18),
+        metadata_uri: random_string(&mut rng, 1..1024).as_bytes().to_vec(),
+    };
+
+    let transaction = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(
+                OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                0,
+            ),
+            InputWitness::NoSignature(None),
+        )
+        .add_output(TxOutput::Transfer(
+            issuance.clone().into(),
+            Destination::AnyoneCanSpend,
+        ))
+        .add_output(TxOutput::Burn(OutputValue::Coin(
+            chain_config.token_min_issuance_fee(),
+        )))
+        .build();
+    let token_id = token_id(transaction.transaction()).unwrap();
+
+    let block1 = tf.make_block_builder().add_transaction(transaction).build();
+    tf.process_block(block1.clone(), BlockSource::Local).unwrap();
+
+    // A competing block at the same height, without the issuance
+    let genesis_id = tf.genesis().get_id();
+    let block1_alt = tf.make_block_builder().with_parent(genesis_id.into()).build();
+
+    let storage = {
+        let mut storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+        let mut db_tx = storage.transaction_rw().await.unwrap();
+        db_tx.initialize_storage(&chain_config).await.unwrap();
+        db_tx.commit().await.unwrap();
+
+        storage
+    };
+
+    let mut local_node = BlockchainState::new(storage);
+    local_node.scan_blocks(BlockHeight::new(0), vec![block1]).await.unwrap();
+    {
+        let db_tx = local_node.storage().transaction_ro().await.unwrap();
+        assert_eq!(
+            db_tx.get_token_issuance(token_id).await.unwrap(),
+            Some(issuance)
+        );
+    }
+
+    local_node
+        .scan_blocks(BlockHeight::new(0), vec![block1_alt.clone()])
+        .await
+        .unwrap();
+
+    let db_tx = local_node.storage().transaction_ro().await.unwrap();
+    assert_eq!(
+        db_tx.get_best_block().await.unwrap(),
+        (BlockHeight::new(1), block1_alt.get_id().into())
+    );
+    assert_eq!(db_tx.get_token_issuance(token_id).await.unwrap(), None);
+}
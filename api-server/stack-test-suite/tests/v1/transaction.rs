@@ -25,6 +25,7 @@ async fn invalid_transaction_id() {
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(body["error"].as_str().unwrap(), "Invalid transaction Id");
+    assert_eq!(body["code"].as_str().unwrap(), "invalid_transaction_id");
 
     task.abort();
 }
@@ -36,12 +37,13 @@ async fn transaction_not_found() {
     )
     .await;
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 404);
 
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(body["error"].as_str().unwrap(), "Transaction not found");
+    assert_eq!(body["code"].as_str().unwrap(), "transaction_not_found");
 
     task.abort();
 }
@@ -83,6 +85,7 @@ async fn ok(#[case] seed: Seed) {
 
                 let expected_transaction = json!({
                 "block_id": block_id.to_hash().encode_hex::<String>(),
+                "block_height": block_height,
                 "version_byte": transaction.version_byte(),
                 "is_replaceable": transaction.is_replaceable(),
                 "flags": transaction.flags(),
@@ -141,6 +144,10 @@ async fn ok(#[case] seed: Seed) {
     let body = body.as_object().unwrap();
 
     assert_eq!(body.get("block_id").unwrap(), &block_id);
+    assert_eq!(
+        body.get("block_height").unwrap(),
+        &expected_transaction["block_height"]
+    );
     assert_eq!(
         body.get("version_byte").unwrap(),
         &expected_transaction["version_byte"]
@@ -31,6 +31,7 @@ async fn get_transaction_failed() {
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(body["error"].as_str().unwrap(), "Invalid transaction Id");
+    assert_eq!(body["code"].as_str().unwrap(), "invalid_transaction_id");
 
     task.abort();
 }
@@ -135,13 +136,14 @@ async fn get_block_failed(#[case] seed: Seed) {
         .await
         .unwrap();
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 404);
 
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
     let body = body.as_object().unwrap();
 
     assert_eq!(body["error"].as_str().unwrap(), "Block not found");
+    assert_eq!(body["code"].as_str().unwrap(), "block_not_found");
 
     task.abort();
 }
@@ -245,6 +247,10 @@ async fn transaction_not_part_of_block(#[case] seed: Seed) {
         body["error"].as_str().unwrap(),
         "Transaction not part of any block"
     );
+    assert_eq!(
+        body["code"].as_str().unwrap(),
+        "transaction_not_part_of_block"
+    );
 
     task.abort();
 }
@@ -363,6 +369,10 @@ async fn cannot_find_transaction_in_block(#[case] seed: Seed) {
         body["error"].as_str().unwrap(),
         "Cannot find transaction in block"
     );
+    assert_eq!(
+        body["code"].as_str().unwrap(),
+        "cannot_find_transaction_in_block"
+    );
 
     task.abort();
 }
@@ -18,24 +18,78 @@ use crate::error::{
 };
 use api_server_common::storage::storage_api::{ApiServerStorage, ApiServerStorageRead};
 use axum::{
-    extract::{Path, State},
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
     response::IntoResponse,
     routing::get,
-    Json, Router,
+    Extension, Json, Router,
 };
 use common::{
-    chain::{Block, SignedTransaction, Transaction},
-    primitives::{BlockHeight, Id, Idable, H256},
+    address::Address,
+    chain::{
+        output_value::OutputValue,
+        tokens::{token_id, TokenData, TokenId},
+        Block, Destination, GenBlock, SignedTransaction, Transaction, TxOutput, UtxoOutPoint,
+    },
+    primitives::{Amount, BlockHeight, Id, Idable, H256},
 };
 use crypto::random::{make_true_rng, Rng};
 use hex::ToHex;
+use logging::log;
+use serde::Deserialize;
 use serde_json::json;
-use std::{str::FromStr, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::broadcast;
 
 use crate::ApiServerWebServerState;
 
 pub const API_VERSION: &str = "1.0.0";
 
+/// The maximum number of items returned by a paginated endpoint, larger limits are clamped.
+pub const PAGINATION_MAX_LIMIT: usize = 1000;
+
+/// The header that contains the total number of items of a paginated endpoint.
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+/// How often the tip subscriptions check for a new tip.
+pub const TIP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How many tip notifications a slow subscription can fall behind before it skips them.
+const TIP_CHANNEL_CAPACITY: usize = 16;
+
+#[derive(Debug, Deserialize)]
+pub struct PaginationParams {
+    offset: Option<String>,
+    limit: Option<String>,
+}
+
+impl PaginationParams {
+    /// Returns the range of the items to return, clamped to the total number of items.
+    fn range(&self, total: usize) -> Result<std::ops::Range<usize>, ApiServerWebServerError> {
+        let parse = |value: &Option<String>, default: usize| {
+            value.as_ref().map_or(Ok(default), |value| {
+                value.parse::<usize>().map_err(|_| {
+                    ApiServerWebServerError::ClientError(
+                        ApiServerWebServerClientError::InvalidPaginationParameter,
+                    )
+                })
+            })
+        };
+
+        let offset = parse(&self.offset, 0)?.min(total);
+        let limit = parse(&self.limit, PAGINATION_MAX_LIMIT)?.min(PAGINATION_MAX_LIMIT);
+
+        Ok(offset..offset.saturating_add(limit).min(total))
+    }
+}
+
 pub fn routes<T: ApiServerStorage + Send + Sync + 'static>(
 ) -> Router<ApiServerWebServerState<Arc<T>>> {
     let router = Router::new();
@@ -55,6 +109,10 @@ pub fn routes<T: ApiServerStorage + Send + Sync + 'static>(
         .route("/transaction/:id", get(transaction))
         .route("/transaction/:id/merkle-path", get(transaction_merkle_path));
 
+    let router = router.route("/address/:address/balance", get(address_balance));
+
+    let router = router.route("/token/:id", get(token));
+
     let router = router
         .route(
             "/destination/address/:public_key_hash",
@@ -73,6 +131,11 @@ pub fn routes<T: ApiServerStorage + Send + Sync + 'static>(
             get(destination_multisig),
         );
 
+    let router = router.route(
+        "/subscribe",
+        get(subscribe).layer(Extension(Arc::new(TipSubscriptions::default()))),
+    );
+
     router.route("/pool/:id", get(pool))
 }
 
@@ -161,17 +224,21 @@ pub async fn block_reward<T: ApiServerStorage>(
 #[allow(clippy::unused_async)]
 pub async fn block_transaction_ids<T: ApiServerStorage>(
     Path(block_id): Path<String>,
+    Query(pagination): Query<PaginationParams>,
     State(state): State<ApiServerWebServerState<Arc<T>>>,
 ) -> Result<impl IntoResponse, ApiServerWebServerError> {
     let block = get_block(&block_id, &state).await?;
 
-    let transaction_ids = block
-        .transactions()
+    let total = block.transactions().len();
+    let transaction_ids = block.transactions()[pagination.range(total)?]
         .iter()
         .map(|tx| tx.transaction().get_id())
         .collect::<Vec<_>>();
 
-    Ok(Json(json!(transaction_ids)))
+    Ok((
+        [(TOTAL_COUNT_HEADER, total.to_string())],
+        Json(json!(transaction_ids)),
+    ))
 }
 
 //
@@ -222,11 +289,10 @@ pub async fn chain_at_height<T: ApiServerStorage>(
     }
 }
 
-#[allow(clippy::unused_async)]
-pub async fn chain_tip<T: ApiServerStorage>(
-    State(state): State<ApiServerWebServerState<Arc<T>>>,
-) -> Result<impl IntoResponse, ApiServerWebServerError> {
-    let best_block = state
+async fn get_best_block(
+    state: &ApiServerWebServerState<Arc<impl ApiServerStorage>>,
+) -> Result<(BlockHeight, Id<GenBlock>), ApiServerWebServerError> {
+    state
         .db
         .transaction_ro()
         .await
@@ -237,7 +303,14 @@ pub async fn chain_tip<T: ApiServerStorage>(
         .await
         .map_err(|_| {
             ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
-        })?;
+        })
+}
+
+#[allow(clippy::unused_async)]
+pub async fn chain_tip<T: ApiServerStorage>(
+    State(state): State<ApiServerWebServerState<Arc<T>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    let best_block = get_best_block(&state).await?;
 
     Ok(Json(json!({
       "block_height": best_block.0,
@@ -278,6 +351,29 @@ async fn get_transaction(
         ))
 }
 
+/// The height of the block if it's on the main chain
+async fn main_chain_block_height(
+    block_id: Id<Block>,
+    state: &ApiServerWebServerState<Arc<impl ApiServerStorage>>,
+) -> Result<Option<BlockHeight>, ApiServerWebServerError> {
+    let db_tx = state.db.transaction_ro().await.map_err(|_| {
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+
+    let block_height = match db_tx.get_block_aux_data(block_id).await.map_err(|_| {
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })? {
+        Some(aux_data) => aux_data.block_height(),
+        None => return Ok(None),
+    };
+
+    let main_chain_block_id = db_tx.get_main_chain_block_id(block_height).await.map_err(|_| {
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+
+    Ok((main_chain_block_id == Some(block_id)).then_some(block_height))
+}
+
 #[allow(clippy::unused_async)]
 pub async fn transaction<T: ApiServerStorage>(
     Path(transaction_id): Path<String>,
@@ -285,8 +381,14 @@ pub async fn transaction<T: ApiServerStorage>(
 ) -> Result<impl IntoResponse, ApiServerWebServerError> {
     let (block_id, transaction) = get_transaction(&transaction_id, &state).await?;
 
+    let block_height = match block_id {
+        Some(block_id) => main_chain_block_height(block_id, &state).await?,
+        None => None,
+    };
+
     Ok(Json(json!({
     "block_id": block_id.map_or("".to_string(), |b| b.to_hash().encode_hex::<String>()),
+    "block_height": block_height,
     "version_byte": transaction.version_byte(),
     "is_replaceable": transaction.is_replaceable(),
     "flags": transaction.flags(),
@@ -354,6 +456,103 @@ pub async fn transaction_merkle_path<T: ApiServerStorage>(
     })))
 }
 
+//
+// address/
+//
+
+/// Returns the id of the token issued in the transaction that created the outpoint
+async fn issued_token_id(
+    outpoint: &UtxoOutPoint,
+    state: &ApiServerWebServerState<Arc<impl ApiServerStorage>>,
+) -> Result<TokenId, ApiServerWebServerError> {
+    let transaction_id =
+        outpoint
+            .source_id()
+            .get_tx_id()
+            .copied()
+            .ok_or(ApiServerWebServerError::ServerError(
+                ApiServerWebServerServerError::InternalServerError,
+            ))?;
+    let (_, transaction) =
+        get_transaction(&transaction_id.to_hash().encode_hex::<String>(), state).await?;
+
+    token_id(transaction.transaction()).ok_or(ApiServerWebServerError::ServerError(
+        ApiServerWebServerServerError::InternalServerError,
+    ))
+}
+
+#[allow(clippy::unused_async)]
+pub async fn address_balance<T: ApiServerStorage>(
+    Path(address): Path<String>,
+    State(state): State<ApiServerWebServerState<Arc<T>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    let address = Address::<Destination>::from_str(&state.chain_config, &address)
+        .and_then(|address| address.decode_object(&state.chain_config))
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidAddress)
+        })?;
+
+    let utxos = state
+        .db
+        .transaction_ro()
+        .await
+        .map_err(|_| {
+            ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+        })?
+        .get_address_utxos(&address)
+        .await
+        .map_err(|_| {
+            ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+        })?;
+
+    let overflow_error =
+        || ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError);
+
+    let mut coins = Amount::ZERO;
+    let mut tokens = BTreeMap::<TokenId, Amount>::new();
+
+    for (outpoint, output) in utxos {
+        let value = match &output {
+            TxOutput::Transfer(value, _) | TxOutput::LockThenTransfer(value, _, _) => value,
+            TxOutput::Burn(_)
+            | TxOutput::CreateStakePool(_, _)
+            | TxOutput::ProduceBlockFromStake(_, _)
+            | TxOutput::CreateDelegationId(_, _)
+            | TxOutput::DelegateStaking(_, _) => continue,
+        };
+
+        match value {
+            OutputValue::Coin(amount) => {
+                coins = (coins + *amount).ok_or_else(overflow_error)?;
+            }
+            OutputValue::Token(token_data) => {
+                let (token_id, amount) = match token_data.as_ref() {
+                    TokenData::TokenTransfer(transfer) => (transfer.token_id, transfer.amount),
+                    TokenData::TokenIssuance(issuance) => (
+                        issued_token_id(&outpoint, &state).await?,
+                        issuance.amount_to_issue,
+                    ),
+                    TokenData::NftIssuance(_) => (
+                        issued_token_id(&outpoint, &state).await?,
+                        Amount::from_atoms(1),
+                    ),
+                    TokenData::TokenFreezeV1(_) | TokenData::TokenUnfreezeV1(_) => continue,
+                };
+                let balance = tokens.entry(token_id).or_insert(Amount::ZERO);
+                *balance = (*balance + amount).ok_or_else(overflow_error)?;
+            }
+        }
+    }
+
+    Ok(Json(json!({
+        "coins": coins,
+        "tokens": tokens
+            .into_iter()
+            .map(|(token_id, amount)| (token_id.to_hash().encode_hex::<String>(), amount))
+            .collect::<BTreeMap<_, _>>(),
+    })))
+}
+
 //
 // destination/
 //
@@ -477,3 +676,175 @@ pub async fn pool(
         }).collect::<Vec<_>>(),
     })))
 }
+
+//
+// token/
+//
+
+#[allow(clippy::unused_async)]
+pub async fn token<T: ApiServerStorage>(
+    Path(token_id): Path<String>,
+    State(state): State<ApiServerWebServerState<Arc<T>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    let token_id: TokenId = H256::from_str(&token_id)
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidTokenId)
+        })?
+        .into();
+
+    let issuance = state
+        .db
+        .transaction_ro()
+        .await
+        .map_err(|_| {
+            ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+        })?
+        .get_token_issuance(token_id)
+        .await
+        .map_err(|_| {
+            ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+        })?
+        .ok_or(ApiServerWebServerError::ClientError(
+            ApiServerWebServerClientError::TokenNotFound,
+        ))?;
+
+    Ok(Json(json!({
+    "token_id": token_id,
+    "token_ticker": String::from_utf8_lossy(&issuance.token_ticker),
+    "number_of_decimals": issuance.number_of_decimals,
+    "total_supply": issuance.amount_to_issue,
+    "metadata_uri": String::from_utf8_lossy(&issuance.metadata_uri),
+    })))
+}
+
+//
+// subscribe/
+//
+
+type Tip = (BlockHeight, Id<GenBlock>);
+
+/// The tip notifications shared by all the subscriptions, so the storage is polled once
+/// regardless of the number of clients. The poller runs only while there are subscriptions.
+#[derive(Default)]
+pub struct TipSubscriptions {
+    sender: Mutex<Option<broadcast::Sender<Tip>>>,
+}
+
+impl TipSubscriptions {
+    /// Subscribe to the new tips, starting the poller if it isn't running
+    fn subscribe<T: ApiServerStorage + Send + Sync + 'static>(
+        self: &Arc<Self>,
+        state: &ApiServerWebServerState<Arc<T>>,
+    ) -> broadcast::Receiver<Tip> {
+        let mut sender = self.sender.lock().expect("poisoned mutex");
+        match sender.as_ref() {
+            Some(sender) => sender.subscribe(),
+            None => {
+                let (new_sender, receiver) = broadcast::channel(TIP_CHANNEL_CAPACITY);
+                tokio::spawn(Arc::clone(self).poll_tip(state.clone(), new_sender.clone()));
+                *sender = Some(new_sender);
+                receiver
+            }
+        }
+    }
+
+    async fn poll_tip<T: ApiServerStorage>(
+        self: Arc<Self>,
+        state: ApiServerWebServerState<Arc<T>>,
+        sender: broadcast::Sender<Tip>,
+    ) {
+        let mut poll_interval = tokio::time::interval(TIP_POLL_INTERVAL);
+        poll_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut last_tip = None;
+
+        loop {
+            poll_interval.tick().await;
+
+            {
+                // Checked under the lock, so a new subscription either gets this sender
+                // before the poller stops or starts a new poller
+                let mut current_sender = self.sender.lock().expect("poisoned mutex");
+                if sender.receiver_count() == 0 {
+                    *current_sender = None;
+                    break;
+                }
+            }
+
+            let tip = match get_best_block(&state).await {
+                Ok(tip) => tip,
+                Err(e) => {
+                    log::error!("Failed to get the tip for the subscriptions: {e}");
+                    continue;
+                }
+            };
+
+            if last_tip != Some(tip) {
+                // Sending fails only if all the subscriptions have ended since the check
+                _ = sender.send(tip);
+                last_tip = Some(tip);
+            }
+        }
+    }
+}
+
+pub async fn subscribe<T: ApiServerStorage + Send + Sync + 'static>(
+    ws: WebSocketUpgrade,
+    State(state): State<ApiServerWebServerState<Arc<T>>>,
+    Extension(tip_subscriptions): Extension<Arc<TipSubscriptions>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| tip_subscription(socket, state, tip_subscriptions))
+}
+
+/// Sends the current tip when the client connects and then every new tip, until the client
+/// disconnects. The scanner runs in a separate process, so the tip is polled from the storage
+/// by a poller that is shared by all the subscriptions.
+async fn tip_subscription<T: ApiServerStorage + Send + Sync + 'static>(
+    mut socket: WebSocket,
+    state: ApiServerWebServerState<Arc<T>>,
+    tip_subscriptions: Arc<TipSubscriptions>,
+) {
+    // Subscribe before reading the current tip, so no new tip is missed in between
+    let mut new_tips = tip_subscriptions.subscribe(&state);
+
+    let mut last_tip = match get_best_block(&state).await {
+        Ok(tip) => tip,
+        Err(e) => {
+            log::error!("Failed to get the tip for a subscription: {e}");
+            return;
+        }
+    };
+    if send_tip(&mut socket, last_tip).await.is_err() {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            tip = new_tips.recv() => match tip {
+                Ok(tip) => {
+                    if tip != last_tip {
+                        if send_tip(&mut socket, tip).await.is_err() {
+                            break;
+                        }
+                        last_tip = tip;
+                    }
+                }
+                // The skipped tips are outdated anyway, the next one is sent
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                // Pings are answered by axum and nothing else is expected from the client
+                Some(Ok(_)) => {}
+            }
+        }
+    }
+}
+
+async fn send_tip(socket: &mut WebSocket, tip: Tip) -> Result<(), axum::Error> {
+    let message = json!({
+      "block_height": tip.0,
+      "block_id": tip.1,
+    });
+    socket.send(Message::Text(message.to_string())).await
+}
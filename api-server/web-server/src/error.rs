@@ -35,16 +35,24 @@ pub enum ApiServerWebServerError {
 pub enum ApiServerWebServerClientError {
     #[error("Bad request")]
     BadRequest,
+    #[error("Invalid address")]
+    InvalidAddress,
     #[error("Block not found")]
     BlockNotFound,
     #[error("Invalid block height")]
     InvalidBlockHeight,
     #[error("Invalid block Id")]
     InvalidBlockId,
+    #[error("Invalid pagination parameter")]
+    InvalidPaginationParameter,
+    #[error("Invalid token Id")]
+    InvalidTokenId,
     #[error("Invalid transaction Id")]
     InvalidTransactionId,
     #[error("No block found at supplied height")]
     NoBlockAtHeight,
+    #[error("Token not found")]
+    TokenNotFound,
     #[error("Transaction not found")]
     TransactionNotFound,
     #[error("Transaction not part of any block")]
@@ -66,17 +74,94 @@ pub enum ApiServerWebServerServerError {
     TransactionIndexOverflow,
 }
 
-impl IntoResponse for ApiServerWebServerError {
-    fn into_response(self) -> Response {
-        let (status, message) = match self {
-            ApiServerWebServerError::ClientError(error) => {
-                (StatusCode::BAD_REQUEST, error.to_string())
+impl ApiServerWebServerClientError {
+    /// Missing resources are reported as 404, everything else is a malformed request
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            ApiServerWebServerClientError::BlockNotFound
+            | ApiServerWebServerClientError::NoBlockAtHeight
+            | ApiServerWebServerClientError::TokenNotFound
+            | ApiServerWebServerClientError::TransactionNotFound => StatusCode::NOT_FOUND,
+            ApiServerWebServerClientError::BadRequest
+            | ApiServerWebServerClientError::InvalidAddress
+            | ApiServerWebServerClientError::InvalidBlockHeight
+            | ApiServerWebServerClientError::InvalidBlockId
+            | ApiServerWebServerClientError::InvalidPaginationParameter
+            | ApiServerWebServerClientError::InvalidTokenId
+            | ApiServerWebServerClientError::InvalidTransactionId
+            | ApiServerWebServerClientError::TransactionNotPartOfBlock => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    /// A stable, machine readable identifier of the error
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiServerWebServerClientError::BadRequest => "bad_request",
+            ApiServerWebServerClientError::InvalidAddress => "invalid_address",
+            ApiServerWebServerClientError::BlockNotFound => "block_not_found",
+            ApiServerWebServerClientError::InvalidBlockHeight => "invalid_block_height",
+            ApiServerWebServerClientError::InvalidBlockId => "invalid_block_id",
+            ApiServerWebServerClientError::InvalidPaginationParameter => {
+                "invalid_pagination_parameter"
+            }
+            ApiServerWebServerClientError::InvalidTokenId => "invalid_token_id",
+            ApiServerWebServerClientError::InvalidTransactionId => "invalid_transaction_id",
+            ApiServerWebServerClientError::NoBlockAtHeight => "no_block_at_height",
+            ApiServerWebServerClientError::TokenNotFound => "token_not_found",
+            ApiServerWebServerClientError::TransactionNotFound => "transaction_not_found",
+            ApiServerWebServerClientError::TransactionNotPartOfBlock => {
+                "transaction_not_part_of_block"
+            }
+        }
+    }
+}
+
+impl ApiServerWebServerServerError {
+    /// A stable, machine readable identifier of the error
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiServerWebServerServerError::CannotFindTransactionInBlock => {
+                "cannot_find_transaction_in_block"
+            }
+            ApiServerWebServerServerError::ErrorCalculatingMerklePath => {
+                "error_calculating_merkle_path"
             }
-            ApiServerWebServerError::ServerError(error) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+            ApiServerWebServerServerError::ErrorCalculatingMerkleTree => {
+                "error_calculating_merkle_tree"
             }
+            ApiServerWebServerServerError::InternalServerError => "internal_server_error",
+            ApiServerWebServerServerError::TransactionIndexOverflow => "transaction_index_overflow",
+        }
+    }
+}
+
+impl ApiServerWebServerError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            ApiServerWebServerError::ClientError(error) => error.status_code(),
+            ApiServerWebServerError::ServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiServerWebServerError::ClientError(error) => error.code(),
+            ApiServerWebServerError::ServerError(error) => error.code(),
+        }
+    }
+}
+
+impl IntoResponse for ApiServerWebServerError {
+    fn into_response(self) -> Response {
+        let message = match &self {
+            ApiServerWebServerError::ClientError(error) => error.to_string(),
+            ApiServerWebServerError::ServerError(error) => error.to_string(),
         };
 
-        (status, Json(json!({ "error": message }))).into_response()
+        (
+            self.status_code(),
+            Json(json!({ "error": message, "code": self.code() })),
+        )
+            .into_response()
     }
 }
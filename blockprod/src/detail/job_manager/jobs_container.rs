@@ -23,7 +23,7 @@ use common::{
 use logging::log;
 use tokio::sync::oneshot;
 
-use super::{JobHandle, JobKey, JobManagerError, NewJobEvent};
+use super::{JobHandle, JobInfo, JobKey, JobManagerError, NewJobEvent};
 
 #[derive(Default)]
 pub struct JobsContainer {
@@ -36,10 +36,26 @@ impl JobsContainer {
         _ = result_sender.send(self.jobs.len());
     }
 
+    pub fn handle_list_jobs(&self, result_sender: oneshot::Sender<Vec<JobInfo>>) {
+        let jobs = self
+            .jobs
+            .iter()
+            .map(|(job_key, job_handle)| JobInfo {
+                job_key: job_key.clone(),
+                job_type: job_handle.job_type,
+                start_time: job_handle.start_time,
+            })
+            .collect();
+
+        _ = result_sender.send(jobs);
+    }
+
     pub fn handle_add_job(&mut self, event: NewJobEvent) {
         let NewJobEvent {
             custom_id,
             current_tip_id,
+            job_type,
+            start_time,
             cancel_sender,
             result_sender,
         } = event;
@@ -51,7 +67,14 @@ impl JobsContainer {
                 log::error!("Error sending new job exists error: {e:?}");
             }
         } else {
-            self.jobs.insert(job_key.clone(), JobHandle { cancel_sender });
+            self.jobs.insert(
+                job_key.clone(),
+                JobHandle {
+                    cancel_sender,
+                    job_type,
+                    start_time,
+                },
+            );
 
             let last_used_block_timestamp =
                 self.last_used_block_timestamps.get(&custom_id).copied();
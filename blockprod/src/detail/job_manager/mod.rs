@@ -23,7 +23,9 @@ use chainstate::{ChainstateEvent, ChainstateHandle};
 use common::{
     chain::{block::timestamp::BlockTimestamp, GenBlock},
     primitives::Id,
+    time_getter::TimeGetter,
 };
+use consensus::GenerateBlockInputData;
 use logging::log;
 use serialization::{Decode, Encode};
 use tokio::sync::{
@@ -52,10 +54,56 @@ pub enum JobManagerError {
     FailedToSendUpdateLastTimestampSecondsUsed,
     #[error("Error updating last timestamp secconds used")]
     FailedToUpdateLastTimestampSecondsUsed,
+    #[error("Error sending list jobs event")]
+    FailedToSendListJobsEvent,
+    #[error("Error listing jobs")]
+    FailedToListJobs,
 }
 
 pub struct JobHandle {
     cancel_sender: UnboundedSender<()>,
+    job_type: JobType,
+    start_time: BlockTimestamp,
+}
+
+/// The consensus under which a job is producing a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum JobType {
+    IgnoreConsensus,
+    PoW,
+    PoS,
+}
+
+impl From<&GenerateBlockInputData> for JobType {
+    fn from(input_data: &GenerateBlockInputData) -> Self {
+        match input_data {
+            GenerateBlockInputData::None => JobType::IgnoreConsensus,
+            GenerateBlockInputData::PoW(_) => JobType::PoW,
+            GenerateBlockInputData::PoS(_) => JobType::PoS,
+        }
+    }
+}
+
+/// A snapshot of a running job, as seen by the job manager
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobInfo {
+    job_key: JobKey,
+    job_type: JobType,
+    start_time: BlockTimestamp,
+}
+
+impl JobInfo {
+    pub fn job_key(&self) -> &JobKey {
+        &self.job_key
+    }
+
+    pub fn job_type(&self) -> JobType {
+        self.job_type
+    }
+
+    pub fn start_time(&self) -> BlockTimestamp {
+        self.start_time
+    }
 }
 
 #[derive(
@@ -99,13 +147,17 @@ impl JobKey {
 pub struct NewJobEvent {
     custom_id: CustomId,
     current_tip_id: Id<GenBlock>,
+    job_type: JobType,
+    start_time: BlockTimestamp,
     cancel_sender: UnboundedSender<()>,
     result_sender: oneshot::Sender<Result<(JobKey, Option<BlockTimestamp>), JobManagerError>>,
 }
 
 pub struct JobManager {
     chainstate_handle: Option<ChainstateHandle>,
+    time_getter: TimeGetter,
     get_job_count_sender: UnboundedSender<oneshot::Sender<usize>>,
+    list_jobs_sender: UnboundedSender<oneshot::Sender<Vec<JobInfo>>>,
     last_used_block_timestamp_sender:
         UnboundedSender<(CustomId, BlockTimestamp, oneshot::Sender<()>)>,
     new_job_sender: UnboundedSender<NewJobEvent>,
@@ -117,10 +169,13 @@ pub struct JobManager {
 pub trait JobManagerInterface: Send + Sync {
     async fn get_job_count(&self) -> Result<usize, JobManagerError>;
 
+    async fn list_jobs(&self) -> Result<Vec<JobInfo>, JobManagerError>;
+
     async fn add_job(
         &self,
         custom_id: CustomId,
         block_id: Id<GenBlock>,
+        job_type: JobType,
     ) -> Result<(JobKey, Option<BlockTimestamp>, UnboundedReceiver<()>), JobManagerError>;
 
     async fn stop_all_jobs(&mut self) -> Result<usize, JobManagerError>;
@@ -150,9 +205,9 @@ pub struct JobManagerImpl {
 }
 
 impl JobManagerImpl {
-    pub fn new(chainstate_handle: Option<ChainstateHandle>) -> Self {
+    pub fn new(chainstate_handle: Option<ChainstateHandle>, time_getter: TimeGetter) -> Self {
         Self {
-            job_manager: JobManager::new(chainstate_handle),
+            job_manager: JobManager::new(chainstate_handle, time_getter),
         }
     }
 }
@@ -163,12 +218,17 @@ impl JobManagerInterface for JobManagerImpl {
         self.job_manager.get_job_count().await
     }
 
+    async fn list_jobs(&self) -> Result<Vec<JobInfo>, JobManagerError> {
+        self.job_manager.list_jobs().await
+    }
+
     async fn add_job(
         &self,
         custom_id: CustomId,
         block_id: Id<GenBlock>,
+        job_type: JobType,
     ) -> Result<(JobKey, Option<BlockTimestamp>, UnboundedReceiver<()>), JobManagerError> {
-        self.job_manager.add_job(custom_id, block_id).await
+        self.job_manager.add_job(custom_id, block_id, job_type).await
     }
 
     async fn stop_all_jobs(&mut self) -> Result<usize, JobManagerError> {
@@ -204,9 +264,10 @@ fn event_then<T>(ev: Option<T>, f: impl FnOnce(T)) {
 }
 
 impl JobManager {
-    pub fn new(chainstate_handle: Option<ChainstateHandle>) -> JobManager {
+    pub fn new(chainstate_handle: Option<ChainstateHandle>, time_getter: TimeGetter) -> JobManager {
         let (chainstate_sender, chainstate_receiver) = mpsc::unbounded_channel::<Id<GenBlock>>();
         let (get_job_count_sender, get_job_count_receiver) = mpsc::unbounded_channel();
+        let (list_jobs_sender, list_jobs_receiver) = mpsc::unbounded_channel();
         let (new_job_sender, new_job_receiver) = mpsc::unbounded_channel::<NewJobEvent>();
         let (stop_job_sender, stop_job_receiver) = mpsc::unbounded_channel();
         let (shutdown_sender, shutdown_receiver) = mpsc::unbounded_channel();
@@ -216,7 +277,9 @@ impl JobManager {
 
         let mut job_manager = JobManager {
             chainstate_handle: chainstate_handle.clone(),
+            time_getter,
             get_job_count_sender,
+            list_jobs_sender,
             last_used_block_timestamp_sender,
             new_job_sender,
             stop_job_sender,
@@ -230,6 +293,7 @@ impl JobManager {
         job_manager.run(
             chainstate_receiver,
             get_job_count_receiver,
+            list_jobs_receiver,
             last_used_block_timestamp_receiver,
             new_job_receiver,
             stop_job_receiver,
@@ -243,6 +307,7 @@ impl JobManager {
         &mut self,
         mut chainstate_receiver: UnboundedReceiver<Id<GenBlock>>,
         mut get_job_count_receiver: UnboundedReceiver<oneshot::Sender<usize>>,
+        mut list_jobs_receiver: UnboundedReceiver<oneshot::Sender<Vec<JobInfo>>>,
         mut last_used_block_timestamp_receiver: UnboundedReceiver<(
             CustomId,
             BlockTimestamp,
@@ -260,6 +325,9 @@ impl JobManager {
                     event = get_job_count_receiver.recv()
                         => event_then(event, |result_sender| jobs.handle_job_count(result_sender)),
 
+                    event = list_jobs_receiver.recv()
+                        => event_then(event, |result_sender| jobs.handle_list_jobs(result_sender)),
+
                     tip_id = chainstate_receiver.recv()
                         => event_then(tip_id, |id| jobs.handle_chainstate_event(id)),
 
@@ -317,10 +385,22 @@ impl JobManager {
         result_receiver.await.map_err(|_| JobManagerError::FailedToReadJobCount)
     }
 
+    pub async fn list_jobs(&self) -> Result<Vec<JobInfo>, JobManagerError> {
+        let (result_sender, result_receiver) = oneshot::channel();
+
+        ensure!(
+            self.list_jobs_sender.send(result_sender).is_ok(),
+            JobManagerError::FailedToSendListJobsEvent
+        );
+
+        result_receiver.await.map_err(|_| JobManagerError::FailedToListJobs)
+    }
+
     pub async fn add_job(
         &self,
         custom_id: CustomId,
         block_id: Id<GenBlock>,
+        job_type: JobType,
     ) -> Result<(JobKey, Option<BlockTimestamp>, UnboundedReceiver<()>), JobManagerError> {
         let (result_sender, result_receiver) = oneshot::channel();
         let (cancel_sender, cancel_receiver) = unbounded_channel::<()>();
@@ -328,6 +408,8 @@ impl JobManager {
         let job = NewJobEvent {
             custom_id,
             current_tip_id: block_id,
+            job_type,
+            start_time: BlockTimestamp::from_time(self.time_getter.get_time()),
             cancel_sender,
             result_sender,
         };
@@ -431,10 +513,13 @@ pub mod tests {
         impl JobManagerInterface for JobManager {
             async fn get_job_count(&self) -> Result<usize, JobManagerError>;
 
+            async fn list_jobs(&self) -> Result<Vec<JobInfo>, JobManagerError>;
+
             async fn add_job(
                 &self,
                 custom_id: CustomId,
                 block_id: Id<GenBlock>,
+                job_type: JobType,
             ) -> Result<(JobKey, Option<BlockTimestamp>, UnboundedReceiver<()>), JobManagerError>;
 
             async fn stop_all_jobs(&mut self) -> Result<usize, JobManagerError>;
@@ -53,7 +53,7 @@ use utils::once_destructor::OnceDestructor;
 
 use crate::{
     config::BlockProdConfig,
-    detail::job_manager::{JobKey, JobManagerHandle, JobManagerImpl},
+    detail::job_manager::{JobInfo, JobKey, JobManagerHandle, JobManagerImpl, JobType},
     BlockProductionError,
 };
 
@@ -128,7 +128,10 @@ impl BlockProduction {
         time_getter: TimeGetter,
         mining_thread_pool: Arc<slave_pool::ThreadPool>,
     ) -> Result<Self, BlockProductionError> {
-        let job_manager_handle = Box::new(JobManagerImpl::new(Some(chainstate_handle.clone())));
+        let job_manager_handle = Box::new(JobManagerImpl::new(
+            Some(chainstate_handle.clone()),
+            time_getter.clone(),
+        ));
 
         let block_production = Self {
             chain_config,
@@ -164,6 +167,10 @@ impl BlockProduction {
         Ok(self.job_manager_handle.stop_job(job_key).await? == 1)
     }
 
+    pub async fn list_jobs(&self) -> Result<Vec<JobInfo>, BlockProductionError> {
+        Ok(self.job_manager_handle.list_jobs().await?)
+    }
+
     pub async fn update_last_used_block_timestamp(
         &self,
         custom_id: CustomId,
@@ -184,11 +191,14 @@ impl BlockProduction {
         transaction_ids: Vec<Id<Transaction>>,
         packing_strategy: PackingStrategy,
     ) -> Result<Box<dyn TransactionAccumulator>, BlockProductionError> {
-        let mut accumulator = Box::new(DefaultTxAccumulator::new(
-            self.chain_config.max_block_size_from_std_scripts(),
-            current_tip,
-            min_block_timestamp,
-        ));
+        let mut accumulator = Box::new(
+            DefaultTxAccumulator::new(
+                self.chain_config.max_block_size_from_std_scripts(),
+                current_tip,
+                min_block_timestamp,
+            )
+            .with_max_tx_count(self.chain_config.max_block_tx_count()),
+        );
 
         for transaction in transactions.into_iter() {
             let transaction_id = transaction.transaction().get_id();
@@ -212,6 +222,7 @@ impl BlockProduction {
         &self,
         input_data: GenerateBlockInputData,
         time_getter: TimeGetter,
+        total_fees: Amount,
     ) -> Result<
         (
             ConsensusData,
@@ -271,6 +282,7 @@ impl BlockProduction {
                         input_data.clone(),
                         BlockTimestamp::from_time(time_getter.get_time()),
                         block_height,
+                        total_fees,
                         get_ancestor,
                     )?;
 
@@ -326,6 +338,22 @@ impl BlockProduction {
         transaction_ids: Vec<Id<Transaction>>,
         packing_strategy: PackingStrategy,
     ) -> Result<(Block, oneshot::Receiver<usize>), BlockProductionError> {
+        let (block, _total_fees, job_finished_receiver) = self
+            .produce_block_with_fees(input_data, transactions, transaction_ids, packing_strategy)
+            .await?;
+
+        Ok((block, job_finished_receiver))
+    }
+
+    /// Same as `produce_block`, but also returns the total fees of the
+    /// transactions included in the block.
+    pub async fn produce_block_with_fees(
+        &self,
+        input_data: GenerateBlockInputData,
+        transactions: Vec<SignedTransaction>,
+        transaction_ids: Vec<Id<Transaction>>,
+        packing_strategy: PackingStrategy,
+    ) -> Result<(Block, Amount, oneshot::Receiver<usize>), BlockProductionError> {
         self.produce_block_with_custom_id(
             input_data,
             transactions,
@@ -343,7 +371,7 @@ impl BlockProduction {
         transaction_ids: Vec<Id<Transaction>>,
         packing_strategy: PackingStrategy,
         custom_id_maybe: Option<Vec<u8>>,
-    ) -> Result<(Block, oneshot::Receiver<usize>), BlockProductionError> {
+    ) -> Result<(Block, Amount, oneshot::Receiver<usize>), BlockProductionError> {
         if !self.blockprod_config.skip_ibd_check {
             let is_initial_block_download = self
                 .chainstate_handle
@@ -378,7 +406,11 @@ impl BlockProduction {
 
         let (job_key, previous_last_used_block_timestamp, mut cancel_receiver) = self
             .job_manager_handle
-            .add_job(custom_id.clone(), tip_at_start.block_id())
+            .add_job(
+                custom_id.clone(),
+                tip_at_start.block_id(),
+                JobType::from(&input_data),
+            )
             .await?;
 
         // This destructor ensures that the job manager cleans up its
@@ -439,8 +471,29 @@ impl BlockProduction {
                     .await?;
             }
 
-            let (consensus_data, block_reward, current_tip_index, finalize_block_data) =
-                self.pull_consensus_data(input_data.clone(), self.time_getter.clone()).await?;
+            // We conservatively use the minimum timestamp here in order to figure out
+            // which transactions are valid for the block.
+            // TODO: Alternatively, we can construct the transaction sequence from
+            // scratch every time a different timestamp is attempted. That is more costly
+            // in terms of computational resources but will allow the node to include more
+            // transactions since the passing time may release some time locks.
+            //
+            // The transactions are collected first because the reward has to include their fees.
+            let accumulator = self
+                .collect_transactions(
+                    tip_at_start.block_id(),
+                    min_constructed_block_timestamp,
+                    transactions.clone(),
+                    transaction_ids.clone(),
+                    packing_strategy,
+                )
+                .await?;
+
+            let total_fees: Amount = accumulator.total_fees().into();
+
+            let (consensus_data, block_reward, current_tip_index, finalize_block_data) = self
+                .pull_consensus_data(input_data.clone(), self.time_getter.clone(), total_fees)
+                .await?;
 
             if current_tip_index.block_id() != tip_at_start.block_id() {
                 log::info!(
@@ -458,25 +511,7 @@ impl BlockProduction {
                 ));
             }
 
-            // We conservatively use the minimum timestamp here in order to figure out
-            // which transactions are valid for the block.
-            // TODO: Alternatively, we can construct the transaction sequence from
-            // scratch every time a different timestamp is attempted. That is more costly
-            // in terms of computational resources but will allow the node to include more
-            // transactions since the passing time may release some time locks.
-            let collected_transactions = self
-                .collect_transactions(
-                    current_tip_index.block_id(),
-                    min_constructed_block_timestamp,
-                    transactions.clone(),
-                    transaction_ids.clone(),
-                    packing_strategy,
-                )
-                .await?
-                .transactions()
-                .to_vec();
-
-            let block_body = BlockBody::new(block_reward, collected_transactions);
+            let block_body = BlockBody::new(block_reward, accumulator.transactions().to_vec());
 
             // A synchronous channel that sends only when the mining/staking is done
             let (ended_sender, ended_receiver) = mpsc::channel::<()>();
@@ -516,12 +551,64 @@ impl BlockProduction {
                     };
 
                     let block = Block::new_from_header(signed_block_header, block_body.clone())?;
-                    return Ok((block, job_finished_receiver));
+                    return Ok((block, total_fees, job_finished_receiver));
                 }
             }
         }
     }
 
+    /// Assemble a block the same way `produce_block` would, without
+    /// solving its header and without registering a job.
+    ///
+    /// Returns the block together with the total fees of its transactions.
+    /// Because the consensus data is not finalized, the returned block is
+    /// not valid and is meant to be inspected only.
+    pub async fn assemble_block(
+        &self,
+        input_data: GenerateBlockInputData,
+        transactions: Vec<SignedTransaction>,
+        transaction_ids: Vec<Id<Transaction>>,
+        packing_strategy: PackingStrategy,
+    ) -> Result<(Block, Amount), BlockProductionError> {
+        let tip_at_start = self.pull_best_block_index().await?;
+        let block_timestamp = BlockTimestamp::from_time(self.time_getter().get_time());
+
+        let accumulator = self
+            .collect_transactions(
+                tip_at_start.block_id(),
+                block_timestamp,
+                transactions,
+                transaction_ids,
+                packing_strategy,
+            )
+            .await?;
+
+        let total_fees: Amount = accumulator.total_fees().into();
+
+        let (consensus_data, block_reward, current_tip_index, _finalize_block_data) = self
+            .pull_consensus_data(input_data, self.time_getter.clone(), total_fees)
+            .await?;
+
+        if current_tip_index.block_id() != tip_at_start.block_id() {
+            return Err(BlockProductionError::TipChanged(
+                tip_at_start.block_id(),
+                tip_at_start.block_height(),
+                current_tip_index.block_id(),
+                current_tip_index.block_height(),
+            ));
+        }
+
+        let block = Block::new(
+            accumulator.transactions().to_vec(),
+            tip_at_start.block_id(),
+            block_timestamp,
+            consensus_data,
+            block_reward,
+        )?;
+
+        Ok((block, total_fees))
+    }
+
     // TODO: here, `block_timestamp_seconds` is a scary thing because, by being AcqRel, it might
     // imply that we perform thread synchronization through it. Which would be a bad thing
     // to do, because thread synchronization via atomics is too low-level and non-trivial
@@ -57,7 +57,7 @@ use utils::once_destructor::OnceDestructor;
 
 use crate::{
     detail::{
-        job_manager::{tests::MockJobManager, JobManagerError, JobManagerImpl},
+        job_manager::{tests::MockJobManager, JobManagerError, JobManagerImpl, JobType},
         CustomId, GenerateBlockInputData,
     },
     prepare_thread_pool, test_blockprod_config,
@@ -435,7 +435,7 @@ mod produce_block {
                 mock_job_manager
                     .expect_add_job()
                     .times(1)
-                    .returning(|_, _| Err(JobManagerError::FailedToSendNewJobEvent));
+                    .returning(|_, _, _| Err(JobManagerError::FailedToSendNewJobEvent));
 
                 block_production.set_job_manager(mock_job_manager);
 
@@ -1035,7 +1035,7 @@ mod produce_block {
 
                 let mut mock_job_manager = Box::<MockJobManager>::default();
 
-                mock_job_manager.expect_add_job().times(1).returning(move |_, _| {
+                mock_job_manager.expect_add_job().times(1).returning(move |_, _, _| {
                     let (_, cancel_receiver) = unbounded_channel::<()>();
                     let mut rng = make_seedable_rng(seed);
                     let job_key = JobKey::new(
@@ -1361,7 +1361,8 @@ mod produce_block {
                 )
                 .expect("Error initializing blockprod");
 
-                let no_chainstate_job_manager = Box::new(JobManagerImpl::new(None));
+                let no_chainstate_job_manager =
+                    Box::new(JobManagerImpl::new(None, Default::default()));
                 block_production.set_job_manager(no_chainstate_job_manager);
 
                 let mut kernel_input = TxInput::from_utxo(
@@ -1640,7 +1641,7 @@ mod process_block_with_custom_id {
 
                 let jobs_finished_iter = produce_results.into_iter().map(|r| r.unwrap());
 
-                for (_block, job) in jobs_finished_iter {
+                for (_block, _total_fees, job) in jobs_finished_iter {
                     job.await.unwrap();
                 }
 
@@ -1778,6 +1779,7 @@ mod stop_all_jobs {
                 .add_job(
                     CustomId::new_from_entropy(),
                     Id::<GenBlock>::new(H256::random_using(&mut rng)),
+                    JobType::IgnoreConsensus,
                 )
                 .await
                 .unwrap();
@@ -1788,6 +1790,7 @@ mod stop_all_jobs {
                 .add_job(
                     CustomId::new_from_entropy(),
                     Id::<GenBlock>::new(H256::random_using(&mut rng)),
+                    JobType::IgnoreConsensus,
                 )
                 .await
                 .unwrap();
@@ -1904,6 +1907,7 @@ mod stop_job {
                 .add_job(
                     CustomId::new_from_entropy(),
                     Id::<GenBlock>::new(H256::random_using(&mut rng)),
+                    JobType::IgnoreConsensus,
                 )
                 .await
                 .unwrap();
@@ -1914,6 +1918,7 @@ mod stop_job {
                 .add_job(
                     CustomId::new_from_entropy(),
                     Id::<GenBlock>::new(H256::random_using(&mut rng)),
+                    JobType::IgnoreConsensus,
                 )
                 .await
                 .unwrap();
@@ -1955,6 +1960,7 @@ mod stop_job {
                     .add_job(
                         CustomId::new_from_entropy(),
                         Id::<GenBlock>::new(H256::random_using(&mut rng)),
+                        JobType::IgnoreConsensus,
                     )
                     .await
                     .unwrap();
@@ -2010,6 +2016,7 @@ mod stop_job {
                 .add_job(
                     CustomId::new_from_entropy(),
                     Id::<GenBlock>::new(H256::random_using(&mut rng)),
+                    JobType::IgnoreConsensus,
                 )
                 .await
                 .unwrap();
@@ -2062,6 +2069,118 @@ mod stop_job {
     }
 }
 
+mod list_jobs {
+    use utils::atomics::SeqCstAtomicU64;
+
+    use super::*;
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn started_job_listed_until_stopped(#[case] seed: Seed) {
+        let (_manager, chain_config, chainstate, mempool, p2p) = setup_blockprod_test(None, None);
+
+        let mut rng = make_seedable_rng(seed);
+        let start_time = rng.gen_range(1..u32::MAX as u64);
+
+        let mut block_production = BlockProduction::new(
+            chain_config,
+            Arc::new(test_blockprod_config()),
+            chainstate,
+            mempool,
+            p2p,
+            mocked_time_getter_seconds(Arc::new(SeqCstAtomicU64::new(start_time))),
+            prepare_thread_pool(1),
+        )
+        .expect("Error initializing blockprod");
+
+        assert_eq!(block_production.list_jobs().await, Ok(vec![]));
+
+        let (job_key, _last_used_block_timestamp, _job_cancel_receiver) = block_production
+            .job_manager_handle
+            .add_job(
+                CustomId::new_from_entropy(),
+                Id::<GenBlock>::new(H256::random_using(&mut rng)),
+                JobType::PoS,
+            )
+            .await
+            .unwrap();
+
+        let jobs = block_production.list_jobs().await.unwrap();
+        assert_eq!(jobs.len(), 1, "Started job is not listed");
+        assert_eq!(jobs[0].job_key(), &job_key);
+        assert_eq!(jobs[0].job_type(), JobType::PoS);
+        assert_eq!(
+            jobs[0].start_time(),
+            BlockTimestamp::from_int_seconds(start_time)
+        );
+
+        let job_stopped = block_production.stop_job(job_key).await.unwrap();
+        assert!(job_stopped, "Failed to stop job");
+
+        assert_eq!(block_production.list_jobs().await, Ok(vec![]));
+    }
+}
+
+mod assemble_block {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn does_not_touch_job_state() {
+        let (manager, chain_config, chainstate, mempool, p2p) = setup_blockprod_test(None, None);
+
+        let join_handle = tokio::spawn({
+            let shutdown_trigger = manager.make_shutdown_trigger();
+            async move {
+                // Ensure a shutdown signal will be sent by the end of the scope
+                let _shutdown_signal = OnceDestructor::new(move || {
+                    shutdown_trigger.initiate();
+                });
+
+                let mut block_production = BlockProduction::new(
+                    chain_config.clone(),
+                    Arc::new(test_blockprod_config()),
+                    chainstate.clone(),
+                    mempool.clone(),
+                    p2p,
+                    Default::default(),
+                    prepare_thread_pool(1),
+                )
+                .expect("Error initializing blockprod");
+
+                let mut mock_job_manager = Box::<MockJobManager>::default();
+                mock_job_manager.expect_add_job().times(0);
+                mock_job_manager.expect_update_last_used_block_timestamp().times(0);
+                mock_job_manager.expect_make_job_stopper_function().times(0);
+                block_production.set_job_manager(mock_job_manager);
+
+                let (block, total_fees) = block_production
+                    .assemble_block(
+                        GenerateBlockInputData::None,
+                        vec![],
+                        vec![],
+                        PackingStrategy::LeaveEmptySpace,
+                    )
+                    .await
+                    .expect("Failed to assemble a block");
+
+                assert_eq!(block.prev_block_id(), chain_config.genesis_block_id());
+                assert!(block.transactions().is_empty());
+                assert_eq!(total_fees, Amount::ZERO);
+
+                // The best block is left untouched
+                let best_block_id =
+                    chainstate.call(|this| this.get_best_block_id()).await.unwrap().unwrap();
+                assert_eq!(best_block_id, chain_config.genesis_block_id());
+            }
+        });
+
+        manager.main().await;
+        join_handle.await.unwrap();
+    }
+}
+
 async fn assert_job_count(block_production: &BlockProduction, expected_jobs_count: usize) {
     // try for a sufficient amount of time before giving up with an error
     for _ in 1..100 {
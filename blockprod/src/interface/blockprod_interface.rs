@@ -13,10 +13,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{detail::job_manager::JobKey, BlockProductionError};
+use crate::{
+    detail::job_manager::{JobInfo, JobKey},
+    BlockProductionError,
+};
 use common::{
     chain::{Block, SignedTransaction, Transaction},
-    primitives::Id,
+    primitives::{Amount, Id},
 };
 use consensus::GenerateBlockInputData;
 use mempool::tx_accumulator::PackingStrategy;
@@ -31,6 +34,9 @@ pub trait BlockProductionInterface: Send + Sync {
     /// to the specified job to stop running
     async fn stop_job(&mut self, job_id: JobKey) -> Result<bool, BlockProductionError>;
 
+    /// List the jobs that are currently running, without affecting them
+    async fn list_jobs(&self) -> Result<Vec<JobInfo>, BlockProductionError>;
+
     /// Generate a block with the given transactions
     ///
     /// There are 3 levels of priority for transactions to be included
@@ -47,4 +53,26 @@ pub trait BlockProductionInterface: Send + Sync {
         transaction_ids: Vec<Id<Transaction>>,
         packing_strategy: PackingStrategy,
     ) -> Result<Block, BlockProductionError>;
+
+    /// Same as `generate_block`, but also returns the total fees of the
+    /// transactions included in the block
+    async fn generate_block_with_fees(
+        &mut self,
+        input_data: GenerateBlockInputData,
+        transactions: Vec<SignedTransaction>,
+        transaction_ids: Vec<Id<Transaction>>,
+        packing_strategy: PackingStrategy,
+    ) -> Result<(Block, Amount), BlockProductionError>;
+
+    /// Assemble a block with the given transactions, without solving it
+    /// and without starting a job
+    ///
+    /// Returns the block and the total fees of its transactions
+    async fn assemble_block(
+        &self,
+        input_data: GenerateBlockInputData,
+        transactions: Vec<SignedTransaction>,
+        transaction_ids: Vec<Id<Transaction>>,
+        packing_strategy: PackingStrategy,
+    ) -> Result<(Block, Amount), BlockProductionError>;
 }
@@ -14,12 +14,15 @@
 // limitations under the License.
 
 use crate::{
-    detail::{job_manager::JobKey, BlockProduction},
+    detail::{
+        job_manager::{JobInfo, JobKey},
+        BlockProduction,
+    },
     BlockProductionError,
 };
 use common::{
     chain::{Block, SignedTransaction, Transaction},
-    primitives::Id,
+    primitives::{Amount, Id},
 };
 use consensus::GenerateBlockInputData;
 use mempool::tx_accumulator::PackingStrategy;
@@ -36,6 +39,10 @@ impl BlockProductionInterface for BlockProduction {
         self.stop_job(job_id).await
     }
 
+    async fn list_jobs(&self) -> Result<Vec<JobInfo>, BlockProductionError> {
+        self.list_jobs().await
+    }
+
     async fn generate_block(
         &mut self,
         input_data: GenerateBlockInputData,
@@ -43,14 +50,39 @@ impl BlockProductionInterface for BlockProduction {
         transaction_ids: Vec<Id<Transaction>>,
         packing_strategy: PackingStrategy,
     ) -> Result<Block, BlockProductionError> {
-        let (block, end_receiver) = self
-            .produce_block(input_data, transactions, transaction_ids, packing_strategy)
+        let (block, _total_fees) = self
+            .generate_block_with_fees(input_data, transactions, transaction_ids, packing_strategy)
+            .await?;
+
+        Ok(block)
+    }
+
+    async fn generate_block_with_fees(
+        &mut self,
+        input_data: GenerateBlockInputData,
+        transactions: Vec<SignedTransaction>,
+        transaction_ids: Vec<Id<Transaction>>,
+        packing_strategy: PackingStrategy,
+    ) -> Result<(Block, Amount), BlockProductionError> {
+        let (block, total_fees, end_receiver) = self
+            .produce_block_with_fees(input_data, transactions, transaction_ids, packing_strategy)
             .await?;
 
         // The only error that can happen is if the channel is closed. We don't care about that here.
         let _finished = end_receiver.await;
 
-        Ok(block)
+        Ok((block, total_fees))
+    }
+
+    async fn assemble_block(
+        &self,
+        input_data: GenerateBlockInputData,
+        transactions: Vec<SignedTransaction>,
+        transaction_ids: Vec<Id<Transaction>>,
+        packing_strategy: PackingStrategy,
+    ) -> Result<(Block, Amount), BlockProductionError> {
+        self.assemble_block(input_data, transactions, transaction_ids, packing_strategy)
+            .await
     }
 }
 
@@ -253,6 +253,7 @@ mod tests {
 
         let mempool = mempool::make_mempool(
             Arc::clone(&chain_config),
+            mempool::MempoolConfig::default(),
             subsystem::Handle::clone(&chainstate),
             time_getter.clone(),
         );
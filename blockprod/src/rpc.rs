@@ -16,16 +16,79 @@
 //! Block production subsystem RPC handler
 
 use common::{
-    chain::Block,
+    chain::{block::timestamp::BlockTimestamp, Block},
     chain::{SignedTransaction, Transaction},
-    primitives::Id,
+    primitives::{Amount, Id, Idable},
 };
 use consensus::GenerateBlockInputData;
 use mempool::tx_accumulator::PackingStrategy;
 use rpc::Result as RpcResult;
-use serialization::hex_encoded::HexEncoded;
+use serialization::{hex_encoded::HexEncoded, Encode};
 
-use crate::detail::job_manager::JobKey;
+use crate::detail::job_manager::{JobInfo, JobKey, JobType};
+
+/// A running block production job, as reported by `list_jobs`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RpcJobInfo {
+    pub job_id: HexEncoded<JobKey>,
+    pub job_type: JobType,
+    pub start_time: BlockTimestamp,
+}
+
+impl From<JobInfo> for RpcJobInfo {
+    fn from(job_info: JobInfo) -> Self {
+        Self {
+            job_id: job_info.job_key().clone().into(),
+            job_type: job_info.job_type(),
+            start_time: job_info.start_time(),
+        }
+    }
+}
+
+/// A block assembled without being produced, as reported by `assemble_block`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RpcAssembledBlock {
+    pub block: HexEncoded<Block>,
+    pub total_fees: Amount,
+    pub size: usize,
+}
+
+/// A produced block, as reported by `generate_block`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RpcGeneratedBlock {
+    pub block: HexEncoded<Block>,
+    /// Ids of all the transactions included in the block
+    pub included_transaction_ids: Vec<Id<Transaction>>,
+    /// Ids of the requested mempool transactions that didn't make it into the block
+    pub skipped_transaction_ids: Vec<Id<Transaction>>,
+    pub total_fees: Amount,
+}
+
+impl RpcGeneratedBlock {
+    fn new(
+        block: Block,
+        requested_transaction_ids: Vec<Id<Transaction>>,
+        total_fees: Amount,
+    ) -> Self {
+        let included_transaction_ids = block
+            .transactions()
+            .iter()
+            .map(|tx| tx.transaction().get_id())
+            .collect::<Vec<_>>();
+
+        let skipped_transaction_ids = requested_transaction_ids
+            .into_iter()
+            .filter(|tx_id| !included_transaction_ids.contains(tx_id))
+            .collect();
+
+        Self {
+            block: block.into(),
+            included_transaction_ids,
+            skipped_transaction_ids,
+            total_fees,
+        }
+    }
+}
 
 #[rpc::rpc(server, client, namespace = "blockprod")]
 trait BlockProductionRpc {
@@ -39,6 +102,11 @@ trait BlockProductionRpc {
     #[method(name = "stop_job")]
     async fn stop_job(&self, job_id: HexEncoded<JobKey>) -> RpcResult<bool>;
 
+    /// List the currently running jobs, so that a specific one can be
+    /// passed to `stop_job`
+    #[method(name = "list_jobs")]
+    async fn list_jobs(&self) -> RpcResult<Vec<RpcJobInfo>>;
+
     /// Generate a block with the given transactions
     ///
     /// If `transactions` is `None`, the block will be generated with
@@ -50,7 +118,20 @@ trait BlockProductionRpc {
         transactions: Vec<HexEncoded<SignedTransaction>>,
         transaction_ids: Vec<Id<Transaction>>,
         packing_strategy: PackingStrategy,
-    ) -> RpcResult<HexEncoded<Block>>;
+    ) -> RpcResult<RpcGeneratedBlock>;
+
+    /// Assemble a block with the given transactions without producing it
+    ///
+    /// No job is started and the block header is not solved, so the
+    /// returned block can only be inspected, not submitted
+    #[method(name = "assemble_block")]
+    async fn assemble_block(
+        &self,
+        input_data: HexEncoded<GenerateBlockInputData>,
+        transactions: Vec<HexEncoded<SignedTransaction>>,
+        transaction_ids: Vec<Id<Transaction>>,
+        packing_strategy: PackingStrategy,
+    ) -> RpcResult<RpcAssembledBlock>;
 }
 
 #[async_trait::async_trait]
@@ -68,6 +149,12 @@ impl BlockProductionRpcServer for super::BlockProductionHandle {
         )
     }
 
+    async fn list_jobs(&self) -> rpc::Result<Vec<RpcJobInfo>> {
+        let jobs = rpc::handle_result(self.call_async(|this| this.list_jobs()).await)?;
+
+        Ok(jobs.into_iter().map(RpcJobInfo::from).collect())
+    }
+
     /// Generate a block with the given transactions.
     ///
     /// Parameters:
@@ -85,12 +172,39 @@ impl BlockProductionRpcServer for super::BlockProductionHandle {
         transactions: Vec<HexEncoded<SignedTransaction>>,
         transaction_ids: Vec<Id<Transaction>>,
         packing_strategy: PackingStrategy,
-    ) -> rpc::Result<HexEncoded<Block>> {
+    ) -> rpc::Result<RpcGeneratedBlock> {
         let transactions = transactions.into_iter().map(HexEncoded::take).collect::<Vec<_>>();
 
-        let block: Block = rpc::handle_result(
-            self.call_async_mut(move |this| {
-                this.generate_block(
+        let (block, total_fees) = rpc::handle_result(
+            self.call_async_mut({
+                let transaction_ids = transaction_ids.clone();
+                move |this| {
+                    this.generate_block_with_fees(
+                        input_data.take(),
+                        transactions,
+                        transaction_ids,
+                        packing_strategy,
+                    )
+                }
+            })
+            .await,
+        )?;
+
+        Ok(RpcGeneratedBlock::new(block, transaction_ids, total_fees))
+    }
+
+    async fn assemble_block(
+        &self,
+        input_data: HexEncoded<GenerateBlockInputData>,
+        transactions: Vec<HexEncoded<SignedTransaction>>,
+        transaction_ids: Vec<Id<Transaction>>,
+        packing_strategy: PackingStrategy,
+    ) -> rpc::Result<RpcAssembledBlock> {
+        let transactions = transactions.into_iter().map(HexEncoded::take).collect::<Vec<_>>();
+
+        let (block, total_fees) = rpc::handle_result(
+            self.call_async(move |this| {
+                this.assemble_block(
                     input_data.take(),
                     transactions,
                     transaction_ids,
@@ -100,6 +214,59 @@ impl BlockProductionRpcServer for super::BlockProductionHandle {
             .await,
         )?;
 
-        Ok(block.into())
+        Ok(RpcAssembledBlock {
+            size: block.encoded_size(),
+            block: block.into(),
+            total_fees,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        chain::{
+            block::{BlockReward, ConsensusData},
+            GenBlock,
+        },
+        primitives::H256,
+    };
+
+    use super::*;
+
+    fn make_tx(flags: u128) -> SignedTransaction {
+        SignedTransaction::new(Transaction::new(flags, vec![], vec![]).unwrap(), vec![]).unwrap()
+    }
+
+    #[test]
+    fn generated_block_reports_skipped_transactions() {
+        let included_tx = make_tx(0);
+        let mempool_tx = make_tx(1);
+        let skipped_tx = make_tx(2);
+
+        let block = Block::new(
+            vec![included_tx.clone(), mempool_tx.clone()],
+            Id::<GenBlock>::new(H256::zero()),
+            BlockTimestamp::from_int_seconds(1),
+            ConsensusData::None,
+            BlockReward::new(vec![]),
+        )
+        .unwrap();
+
+        let generated = RpcGeneratedBlock::new(
+            block,
+            vec![included_tx.transaction().get_id(), skipped_tx.transaction().get_id()],
+            Amount::from_atoms(123),
+        );
+
+        assert_eq!(
+            generated.included_transaction_ids,
+            vec![included_tx.transaction().get_id(), mempool_tx.transaction().get_id()]
+        );
+        assert_eq!(
+            generated.skipped_transaction_ids,
+            vec![skipped_tx.transaction().get_id()]
+        );
+        assert_eq!(generated.total_fees, Amount::from_atoms(123));
     }
 }
@@ -22,6 +22,7 @@ use chainstate::ChainstateConfig;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StorageBackendConfig {
     Lmdb,
+    /// Ephemeral storage, the data is lost on shutdown
     InMemory,
 }
 
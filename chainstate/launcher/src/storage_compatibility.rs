@@ -37,6 +37,16 @@ fn check_storage_version(
         .map_err(StorageCompatibilityCheckError::StorageError)?
         .ok_or(StorageCompatibilityCheckError::StorageVersionMissing)?;
 
+    // The database written by a newer node may be in a format we don't understand,
+    // so it must not be touched
+    ensure!(
+        storage_version <= ChainstateStorageVersion::CURRENT,
+        StorageCompatibilityCheckError::ChainstateStorageVersionTooNew(
+            storage_version,
+            ChainstateStorageVersion::CURRENT
+        )
+    );
+
     ensure!(
         storage_version == ChainstateStorageVersion::CURRENT,
         StorageCompatibilityCheckError::ChainstateStorageVersionMismatch(
@@ -88,3 +98,55 @@ fn check_chain_type(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chainstate_storage::{BlockchainStorageWrite, Store};
+    use common::chain::config::create_unit_test_config;
+
+    fn check_with_version(
+        version: ChainstateStorageVersion,
+    ) -> Result<(), StorageCompatibilityCheckError> {
+        let chain_config = create_unit_test_config();
+        let mut storage = Store::new(storage_inmemory::InMemory::new(), &chain_config).unwrap();
+        storage.set_storage_version(version).unwrap();
+        check_storage_compatibility(&storage, &chain_config)
+    }
+
+    #[test]
+    fn storage_version_matches() {
+        assert_eq!(
+            check_with_version(ChainstateStorageVersion::CURRENT),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn storage_version_older() {
+        let version = ChainstateStorageVersion::new(0);
+        assert_eq!(
+            check_with_version(version),
+            Err(
+                StorageCompatibilityCheckError::ChainstateStorageVersionMismatch(
+                    version,
+                    ChainstateStorageVersion::CURRENT
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn storage_version_newer() {
+        let version = ChainstateStorageVersion::new(u32::MAX);
+        assert_eq!(
+            check_with_version(version),
+            Err(
+                StorageCompatibilityCheckError::ChainstateStorageVersionTooNew(
+                    version,
+                    ChainstateStorageVersion::CURRENT
+                )
+            )
+        );
+    }
+}
@@ -32,6 +32,10 @@ make_config_setting!(
 );
 make_config_setting!(TxIndexEnabled, bool, false);
 make_config_setting!(MaxTipAge, Duration, Duration::from_secs(60 * 60 * 24));
+make_config_setting!(CommitBatchSize, usize, 1);
+make_config_setting!(PruneDepth, Option<u64>, None);
+make_config_setting!(ReindexTxIndex, bool, false);
+make_config_setting!(MaxBlocksPrunedAtOnce, u64, 1000);
 
 /// The chainstate subsystem configuration.
 #[derive(Debug, Clone, Default)]
@@ -47,6 +51,19 @@ pub struct ChainstateConfig {
     /// The initial block download is finished if the difference between the current time and the
     /// tip time is less than this value.
     pub max_tip_age: MaxTipAge,
+    /// The maximum number of blocks committed to the storage in a single transaction during
+    /// the initial block download. Blocks that weren't committed before a crash are downloaded
+    /// again.
+    pub commit_batch_size: CommitBatchSize,
+    /// If set, the bodies of the mainchain blocks buried under this many blocks are removed from
+    /// the storage once they can no longer be affected by a reorg. Block indices are kept.
+    pub prune_depth: PruneDepth,
+    /// If the transaction index is enabled in the config but missing in the storage (i.e. it was
+    /// turned on for an already synced node), build it from the stored mainchain blocks on startup.
+    pub reindex_tx_index: ReindexTxIndex,
+    /// The maximum number of block bodies removed when a block is connected, so enabling pruning
+    /// for an existing database doesn't remove the whole history in a single transaction.
+    pub max_blocks_pruned_at_once: MaxBlocksPrunedAtOnce,
 }
 
 impl ChainstateConfig {
@@ -77,4 +94,24 @@ impl ChainstateConfig {
         self.tx_index_enabled = tx_index_enabled.into();
         self
     }
+
+    pub fn with_commit_batch_size(mut self, commit_batch_size: usize) -> Self {
+        self.commit_batch_size = commit_batch_size.into();
+        self
+    }
+
+    pub fn with_prune_depth(mut self, prune_depth: Option<u64>) -> Self {
+        self.prune_depth = prune_depth.into();
+        self
+    }
+
+    pub fn with_reindex_tx_index(mut self, reindex_tx_index: bool) -> Self {
+        self.reindex_tx_index = reindex_tx_index.into();
+        self
+    }
+
+    pub fn with_max_blocks_pruned_at_once(mut self, max_blocks_pruned_at_once: u64) -> Self {
+        self.max_blocks_pruned_at_once = max_blocks_pruned_at_once.into();
+        self
+    }
 }
@@ -59,6 +59,7 @@ impl BanScore for BlockError {
             BlockError::BlockProofCalculationError(_) => 100,
             BlockError::TransactionVerifierError(err) => err.ban_score(),
             BlockError::TxIndexConfigError => 0,
+            BlockError::TxIndexRebuildBlockMissing(_) => 0,
             BlockError::TxIndexConstructionError(_) => 100,
             BlockError::PoSAccountingError(err) => err.ban_score(),
             BlockError::EpochSealError(err) => err.ban_score(),
@@ -84,6 +85,7 @@ impl BanScore for OrphanCheckError {
             OrphanCheckError::StorageError(_) => 0,
             OrphanCheckError::PrevBlockIndexNotFound(_) => 100,
             OrphanCheckError::LocalOrphan => 0,
+            OrphanCheckError::BootstrapFileOrphan => 0,
         }
     }
 }
@@ -107,6 +109,7 @@ impl BanScore for ConnectTransactionError {
             ConnectTransactionError::InvariantErrorHeaderCouldNotBeLoaded(_) => 100,
             ConnectTransactionError::FailedToAddAllFeesOfBlock(_) => 100,
             ConnectTransactionError::RewardAdditionError(_) => 100,
+            ConnectTransactionError::RewardOutputsTotalOverflow(_) => 100,
             ConnectTransactionError::TimeLockViolation(_) => 100,
             ConnectTransactionError::MissingBlockUndo(_) => 0,
             ConnectTransactionError::MissingBlockRewardUndo(_) => 0,
@@ -137,12 +140,14 @@ impl BanScore for ConnectTransactionError {
             ConnectTransactionError::DelegationRewardOverflow(_, _, _, _) => 100,
             ConnectTransactionError::DistributedDelegationsRewardExceedTotal(_, _, _, _) => 100,
             ConnectTransactionError::BlockRewardInputOutputMismatch(_, _) => 100,
+            ConnectTransactionError::BlockRewardAmountMismatch(_, _) => 100,
             ConnectTransactionError::TotalDelegationBalanceZero(_) => 0,
             ConnectTransactionError::DelegationDataNotFound(_) => 0,
             ConnectTransactionError::DelegationBalanceNotFound(_) => 0,
             ConnectTransactionError::DestinationRetrievalError(err) => err.ban_score(),
             ConnectTransactionError::OutputTimelockError(err) => err.ban_score(),
             ConnectTransactionError::NotEnoughPledgeToCreateStakePool(_, _, _) => 100,
+            ConnectTransactionError::StakePoolVrfKeyAlreadyInUse(_, _) => 100,
             ConnectTransactionError::NonceIsNotIncremental(..) => 100,
             ConnectTransactionError::AttemptToCreateStakePoolFromAccounts => 100,
             ConnectTransactionError::AttemptToCreateDelegationFromAccounts => 100,
@@ -245,6 +250,7 @@ impl BanScore for CheckBlockError {
             CheckBlockError::BlockTimeOrderInvalid(_, _) => 100,
             CheckBlockError::BlockFromTheFuture => 100,
             CheckBlockError::BlockSizeError(err) => err.ban_score(),
+            CheckBlockError::TooManyTransactions(_, _) => 100,
             CheckBlockError::CheckTransactionFailed(err) => err.ban_score(),
             CheckBlockError::ConsensusVerificationFailed(err) => err.ban_score(),
             CheckBlockError::InvalidBlockRewardOutputType(_) => 100,
@@ -275,6 +281,15 @@ impl BanScore for TokensError {
             TokensError::TokensInBlockReward => 100,
             TokensError::InvariantBrokenUndoIssuanceOnNonexistentToken(_) => 100,
             TokensError::InvariantBrokenRegisterIssuanceWithDuplicateId(_) => 100,
+            TokensError::FrozenStateChangeOfNonexistentToken(_) => 100,
+            TokensError::FrozenStateChangeNotAuthorized(_, _) => 100,
+            TokensError::TokenAlreadyFrozen(_) => 100,
+            TokensError::TokenNotFrozen(_) => 100,
+            TokensError::TransferOfFrozenToken(_, _) => 100,
+            TokensError::TokenFreezeNotActivated(_, _) => 100,
+            TokensError::TokensNotConserved(_, _, _) => 100,
+            TokensError::FungibleTokenIssuanceDisabled(_, _) => 100,
+            TokensError::NftIssuanceDisabled(_, _) => 100,
         }
     }
 }
@@ -319,6 +334,7 @@ impl BanScore for ConsensusPoWError {
             ConsensusPoWError::PreviousBitsDecodingFailed(_) => 0,
             ConsensusPoWError::InvalidTargetBits(_, _) => 100,
             ConsensusPoWError::InvalidBlockRewardMaturityDistance(_) => 0,
+            ConsensusPoWError::BlockRewardOverflow(_, _) => 0,
         }
     }
 }
@@ -329,6 +345,7 @@ impl BanScore for BlockSizeError {
             BlockSizeError::Header(_, _) => 100,
             BlockSizeError::SizeOfTxs(_, _) => 100,
             BlockSizeError::SizeOfSmartContracts(_, _) => 100,
+            BlockSizeError::SerializedSize(_, _) => 100,
         }
     }
 }
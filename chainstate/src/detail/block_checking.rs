@@ -45,4 +45,15 @@ impl<'a, S: BlockchainStorage, V: TransactionVerificationStrategy> BlockChecker<
         chainstate_ref.check_block_header(&header).log_err()?;
         Ok(())
     }
+
+    pub fn preliminary_header_check_batch(
+        &self,
+        headers: &[SignedBlockHeader],
+    ) -> Result<(), BlockError> {
+        let chainstate_ref = self.chainstate.make_db_tx_ro().map_err(BlockError::from)?;
+        for header in headers {
+            chainstate_ref.check_block_header_consistency(header).log_err()?;
+        }
+        Ok(())
+    }
 }
@@ -20,7 +20,7 @@ use chainstate_types::{BlockIndex, PropertyQueryError};
 use common::{chain::Block, primitives::id::WithId};
 use serialization::{Decode, Encode};
 
-use crate::{BlockError, ChainstateConfig};
+use crate::{BlockError, BlockSource, ChainstateConfig};
 
 use super::{query::ChainstateQuery, tx_verification_strategy::TransactionVerificationStrategy};
 
@@ -34,6 +34,8 @@ pub enum BootstrapError {
     BlockProcessing(#[from] BlockError),
     #[error("Block import error: {0}")]
     FailedToReadProperty(#[from] PropertyQueryError),
+    #[error("The node prunes old blocks, the blockchain can't be exported")]
+    BlocksPruned,
 }
 
 impl From<std::io::Error> for BootstrapError {
@@ -49,7 +51,7 @@ pub fn import_bootstrap_stream<P, S: std::io::Read>(
     chainstate_config: &ChainstateConfig,
 ) -> Result<(), BootstrapError>
 where
-    P: FnMut(WithId<Block>) -> Result<Option<BlockIndex>, BlockError>,
+    P: FnMut(WithId<Block>, BlockSource) -> Result<Option<BlockIndex>, BlockError>,
 {
     // min: The smallest buffer size, after which another read is triggered from the bootstrap file
     // max: The largest buffer size, after which reading the file is stopped
@@ -77,7 +79,7 @@ where
             None => break,
         };
         let block_len = block.encoded_size();
-        process_block_func(block.into())?;
+        process_block_func(block.into(), BlockSource::BootstrapFile)?;
 
         // consume the buffer from the front
         buffer_queue = buffer_queue[expected_magic_bytes.len() + block_len..].to_vec();
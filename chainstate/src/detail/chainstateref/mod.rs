@@ -17,7 +17,10 @@ mod epoch_seal;
 mod in_memory_reorg;
 mod tx_verifier_storage;
 
-use std::{cmp::max, collections::BTreeSet};
+use std::{
+    cmp::{max, min},
+    collections::{BTreeMap, BTreeSet},
+};
 use thiserror::Error;
 
 use chainstate_storage::{
@@ -34,11 +37,13 @@ use common::{
             signed_block_header::SignedBlockHeader, timestamp::BlockTimestamp, BlockReward,
             ConsensusData,
         },
+        check_duplicate_inputs,
         config::EpochIndex,
         tokens::TokenAuxiliaryData,
         tokens::{get_tokens_issuance_count, TokenId},
         AccountNonce, AccountType, Block, ChainConfig, GenBlock, GenBlockId, OutPointSourceId,
-        SignedTransaction, Transaction, TxMainChainIndex, TxOutput, UtxoOutPoint,
+        SignedTransaction, StrictMedianTimePast, Transaction, TxMainChainIndex, TxOutput,
+        UtxoOutPoint,
     },
     primitives::{id::WithId, time::Time, BlockDistance, BlockHeight, Id, Idable},
     time_getter::TimeGetter,
@@ -46,6 +51,7 @@ use common::{
 };
 use logging::log;
 use pos_accounting::{PoSAccountingDB, PoSAccountingDelta, PoSAccountingView};
+use serialization::Encode;
 use tx_verifier::transaction_verifier::{config::TransactionVerifierConfig, TransactionVerifier};
 use utils::{ensure, tap_error_log::LogError};
 use utxo::{UtxosCache, UtxosDB, UtxosView};
@@ -186,6 +192,10 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.db_tx.get_block_index(block_id).map_err(PropertyQueryError::from)
     }
 
+    pub fn has_block_index(&self, block_id: &Id<Block>) -> Result<bool, PropertyQueryError> {
+        self.db_tx.has_block_index(block_id).map_err(PropertyQueryError::from)
+    }
+
     pub fn get_existing_block_index(
         &self,
         block_id: &Id<Block>,
@@ -252,6 +262,29 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         Ok(self.db_tx.get_mainchain_tx_by_position(position)?)
     }
 
+    pub fn get_transaction_block(
+        &self,
+        id: Id<Transaction>,
+    ) -> Result<Option<(Id<Block>, BlockHeight)>, PropertyQueryError> {
+        if !self.get_is_transaction_index_enabled()? {
+            return Err(PropertyQueryError::TransactionIndexDisabled);
+        }
+        log::trace!("Loading transaction index with id: {}", id);
+        let tx_index =
+            match self.db_tx.get_mainchain_tx_index(&OutPointSourceId::Transaction(id))? {
+                Some(tx_index) => tx_index,
+                None => return Ok(None),
+            };
+        let block_id = match tx_index.position() {
+            common::chain::SpendablePosition::Transaction(pos) => *pos.block_id(),
+            common::chain::SpendablePosition::BlockReward(_) => {
+                panic!("In get_transaction_block(), a tx id led to a block reward")
+            }
+        };
+        let height = self.get_existing_block_index(&block_id)?.block_height();
+        Ok(Some((block_id, height)))
+    }
+
     pub fn get_block_id_by_height(
         &self,
         height: &BlockHeight,
@@ -364,6 +397,12 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.db_tx.get_token_aux_data(token_id).map_err(PropertyQueryError::from)
     }
 
+    pub fn get_tokens_aux_data(
+        &self,
+    ) -> Result<BTreeMap<TokenId, TokenAuxiliaryData>, PropertyQueryError> {
+        self.db_tx.get_tokens_aux_data().map_err(PropertyQueryError::from)
+    }
+
     pub fn get_token_id(
         &self,
         tx_id: &Id<Transaction>,
@@ -583,12 +622,26 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         .map_err(CheckBlockError::ConsensusVerificationFailed)
         .log_err()?;
 
-        // This enforces the minimum accepted timestamp for the block. Depending on the consensus algorithm,
-        // there might be extra checks. For example, PoS requires the timestamp to be greater the previous
-        // block's timestamp.
+        // This enforces the minimum accepted timestamp for the block, which must be greater
+        // than the median time past of its ancestors (or equal to it before the upgrade).
+        // Depending on the consensus algorithm, there might be extra checks. For example,
+        // PoS requires the timestamp to be greater the previous block's timestamp.
         let median_time_past = calculate_median_time_past(self, header.prev_block_id());
+        let block_height = self
+            .get_previous_block_index_for_check_block(header)?
+            .block_height()
+            .next_height();
+        let timestamp_valid = match self
+            .chain_config
+            .chainstate_upgrades()
+            .chainstate_upgrade_at_height(block_height)
+            .strict_median_time_past()
+        {
+            StrictMedianTimePast::Yes => header.timestamp() > median_time_past,
+            StrictMedianTimePast::No => header.timestamp() >= median_time_past,
+        };
         ensure!(
-            header.timestamp() >= median_time_past,
+            timestamp_valid,
             CheckBlockError::BlockTimeOrderInvalid(header.timestamp(), median_time_past),
         );
 
@@ -602,6 +655,24 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         Ok(())
     }
 
+    /// Perform the header checks that don't depend on the header's position in the chain,
+    /// so they can be done for headers whose parents are not known yet.
+    pub fn check_block_header_consistency(
+        &self,
+        header: &SignedBlockHeader,
+    ) -> Result<(), CheckBlockError> {
+        self.check_header_size(header).log_err()?;
+
+        let max_future_offset = self.chain_config.max_future_block_time_offset();
+        let current_time = self.current_time().as_duration_since_epoch();
+        ensure!(
+            header.timestamp().as_duration_since_epoch() <= current_time + *max_future_offset,
+            CheckBlockError::BlockFromTheFuture,
+        );
+
+        Ok(())
+    }
+
     fn check_block_reward_maturity_settings(&self, block: &Block) -> Result<(), CheckBlockError> {
         block
             .block_reward()
@@ -693,6 +764,12 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
             )
         );
 
+        let serialized_size = block.encoded_size();
+        ensure!(
+            serialized_size <= self.chain_config.max_block_size(),
+            BlockSizeError::SerializedSize(serialized_size, self.chain_config.max_block_size())
+        );
+
         Ok(())
     }
 
@@ -718,15 +795,13 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
                     ),
                 );
             }
-            let mut tx_inputs = BTreeSet::new();
+            check_duplicate_inputs(tx.inputs()).map_err(|_| {
+                CheckBlockTransactionsError::DuplicateInputInTransaction(
+                    tx.transaction().get_id(),
+                    block.get_id(),
+                )
+            })?;
             for input in tx.inputs() {
-                ensure!(
-                    tx_inputs.insert(input),
-                    CheckBlockTransactionsError::DuplicateInputInTransaction(
-                        tx.transaction().get_id(),
-                        block.get_id()
-                    )
-                );
                 ensure!(
                     block_inputs.insert(input),
                     CheckBlockTransactionsError::DuplicateInputInBlock(block.get_id())
@@ -736,7 +811,11 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         Ok(())
     }
 
-    fn check_tokens_txs(&self, block: &Block) -> Result<(), CheckBlockTransactionsError> {
+    fn check_tokens_txs(
+        &self,
+        block: &Block,
+        block_height: BlockHeight,
+    ) -> Result<(), CheckBlockTransactionsError> {
         for tx in block.transactions() {
             // We can't issue multiple tokens in a single tx
             let issuance_count = get_tokens_issuance_count(tx.outputs());
@@ -745,7 +824,7 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
                 CheckBlockTransactionsError::TokensError(
                     TokensError::MultipleTokenIssuanceInTransaction(
                         tx.transaction().get_id(),
-                        block.get_id()
+                        Some(block.get_id())
                     ),
                 )
             );
@@ -768,6 +847,7 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
                         token_data,
                         tx.transaction(),
                         block.get_id(),
+                        block_height,
                     )
                 })
                 .map_err(CheckBlockTransactionsError::TokensError)
@@ -798,11 +878,15 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         Ok(())
     }
 
-    fn check_transactions(&self, block: &Block) -> Result<(), CheckBlockTransactionsError> {
+    fn check_transactions(
+        &self,
+        block: &Block,
+        block_height: BlockHeight,
+    ) -> Result<(), CheckBlockTransactionsError> {
         // Note: duplicate txs are detected through duplicate inputs
         self.check_witness_count(block).log_err()?;
         self.check_duplicate_inputs(block).log_err()?;
-        self.check_tokens_txs(block).log_err()?;
+        self.check_tokens_txs(block, block_height).log_err()?;
         self.check_no_signature_size(block).log_err()?;
         Ok(())
     }
@@ -817,6 +901,13 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
     pub fn check_block(&self, block: &WithId<Block>) -> Result<(), CheckBlockError> {
         self.check_block_header(block.header()).log_err()?;
 
+        // Checked before anything that iterates over the transactions
+        let tx_count = block.transactions().len();
+        ensure!(
+            tx_count <= self.chain_config.max_block_tx_count(),
+            CheckBlockError::TooManyTransactions(tx_count, self.chain_config.max_block_tx_count())
+        );
+
         self.check_block_size(block)
             .map_err(CheckBlockError::BlockSizeError)
             .log_err()?;
@@ -846,7 +937,11 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
             );
         }
 
-        self.check_transactions(block)
+        let block_height = self
+            .get_previous_block_index_for_check_block(block.header())?
+            .block_height()
+            .next_height();
+        self.check_transactions(block, block_height)
             .map_err(CheckBlockError::CheckTransactionFailed)
             .log_err()?;
 
@@ -1286,6 +1381,80 @@ impl<'a, S: BlockchainStorageWrite, V: TransactionVerificationStrategy> Chainsta
         Ok(())
     }
 
+    /// If pruning is enabled, remove the bodies of the mainchain blocks that are buried under
+    /// the configured depth and can no longer be disconnected by a reorg. Block indices are kept.
+    pub fn prune_block_bodies(&mut self) -> Result<(), BlockError> {
+        let prune_depth = match *self.chainstate_config.prune_depth {
+            Some(prune_depth) => prune_depth,
+            None => return Ok(()),
+        };
+
+        let current_tip_height = self
+            .get_best_block_index()
+            .map_err(BlockError::BestBlockIndexQueryError)?
+            .block_height();
+        let min_height_with_allowed_reorg = self
+            .get_min_height_with_allowed_reorg()
+            .map_err(BlockError::MinHeightForReorgQueryError)?;
+
+        let prune_height = match (
+            u64::from(current_tip_height).checked_sub(prune_depth),
+            min_height_with_allowed_reorg.prev_height(),
+        ) {
+            (Some(height), Some(max_height)) => min(BlockHeight::new(height), max_height),
+            _ => return Ok(()),
+        };
+
+        // Bodies are always removed from the bottom of the mainchain upwards, so the pruned blocks
+        // form a contiguous range starting right above genesis. Find its end with a binary search.
+        let mut low = 1;
+        let mut high = u64::from(prune_height) + 1;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.mainchain_block_body_exists(BlockHeight::new(mid))? {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        // Limit the number of removed bodies, so that a long unpruned history is removed gradually
+        // over the next blocks rather than in a single transaction.
+        let end_height = min(
+            u64::from(prune_height),
+            low.saturating_add(*self.chainstate_config.max_blocks_pruned_at_once)
+                .saturating_sub(1),
+        );
+        for height in low..=end_height {
+            match self.get_mainchain_block_id_at_height(BlockHeight::new(height))? {
+                Some(block_id) => self.db_tx.del_block(block_id)?,
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_mainchain_block_id_at_height(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Option<Id<Block>>, BlockError> {
+        let block_id = self.db_tx.get_block_id_by_height(&height)?.and_then(|id| {
+            match id.classify(self.chain_config) {
+                GenBlockId::Block(block_id) => Some(block_id),
+                GenBlockId::Genesis(_) => None,
+            }
+        });
+        Ok(block_id)
+    }
+
+    fn mainchain_block_body_exists(&self, height: BlockHeight) -> Result<bool, BlockError> {
+        match self.get_mainchain_block_id_at_height(height)? {
+            Some(block_id) => Ok(self.db_tx.get_block(block_id)?.is_some()),
+            None => Ok(false),
+        }
+    }
+
     fn post_connect_tip(&mut self, tip_index: &BlockIndex, tip: &Block) -> Result<(), BlockError> {
         let tip_height = tip_index.block_height();
         epoch_seal::update_epoch_seal(
@@ -33,6 +33,7 @@ use common::{
     },
     primitives::{Amount, Id},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{
     AccountingBlockUndo, DelegationData, DeltaMergeUndo, FlushablePoSAccountingView,
     PoSAccountingDB, PoSAccountingDeltaData, PoSAccountingView, PoolData,
@@ -341,6 +342,14 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> PoSAccoun
         PoSAccountingDB::<_, TipStorageTag>::new(&self.db_tx).get_pool_data(pool_id)
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, pos_accounting::Error> {
+        PoSAccountingDB::<_, TipStorageTag>::new(&self.db_tx)
+            .get_pool_id_by_vrf_public_key(vrf_public_key)
+    }
+
     fn get_delegation_balance(
         &self,
         delegation_id: DelegationId,
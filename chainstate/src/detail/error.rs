@@ -63,6 +63,8 @@ pub enum BlockError {
     TransactionVerifierError(#[from] TransactionVerifierStorageError),
     #[error("Changing tx index state is not implemented for existing DB")]
     TxIndexConfigError,
+    #[error("Block {0} is missing while rebuilding the transaction index (was it pruned?)")]
+    TxIndexRebuildBlockMissing(Id<Block>),
     #[error("Transaction index construction error: {0}")]
     TxIndexConstructionError(#[from] TxIndexError),
     #[error("PoS accounting error: {0}")]
@@ -120,12 +122,14 @@ pub enum CheckBlockError {
     PrevBlockNotFound(Id<GenBlock>, Id<Block>),
     #[error("Block {0} not found in database")]
     BlockNotFound(Id<GenBlock>),
-    #[error("Block time ({0:?}) must be equal or higher than the median of its ancestors ({1:?})")]
+    #[error("Block time ({0:?}) is too early compared to the median of its ancestors ({1:?})")]
     BlockTimeOrderInvalid(BlockTimestamp, BlockTimestamp),
     #[error("Block time too far into the future")]
     BlockFromTheFuture,
     #[error("Block size is too large: {0}")]
     BlockSizeError(#[from] BlockSizeError),
+    #[error("Block has too many transactions (count: {0}, limit: {1})")]
+    TooManyTransactions(usize, usize),
     #[error("Check transaction failed: {0}")]
     CheckTransactionFailed(CheckBlockTransactionsError),
     #[error("Consensus verification failed: {0}")]
@@ -174,6 +178,8 @@ pub enum OrphanCheckError {
     PrevBlockIndexNotFound(PropertyQueryError),
     #[error("Orphan that was submitted legitimately through a local source")]
     LocalOrphan,
+    #[error("Orphan block found in a bootstrap file")]
+    BootstrapFileOrphan,
 }
 
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
@@ -184,6 +190,8 @@ pub enum BlockSizeError {
     SizeOfTxs(usize, usize),
     #[error("Block smart contracts component size too large (current: {0}, limit: {1})")]
     SizeOfSmartContracts(usize, usize),
+    #[error("Block serialized size too large (current: {0}, limit: {1})")]
+    SerializedSize(usize, usize),
 }
 
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
@@ -216,6 +224,10 @@ pub enum StorageCompatibilityCheckError {
         "Node cannot load chainstate database because the versions mismatch: db `{0:?}`, app `{1:?}`"
     )]
     ChainstateStorageVersionMismatch(ChainstateStorageVersion, ChainstateStorageVersion),
+    #[error(
+        "Chainstate database was created by a newer version of the node (db `{0:?}`, app `{1:?}`), please upgrade the node"
+    )]
+    ChainstateStorageVersionTooNew(ChainstateStorageVersion, ChainstateStorageVersion),
     #[error(
         "Chain's config magic bytes do not match the one from database : expected `{0:?}`, actual `{1:?}`"
     )]
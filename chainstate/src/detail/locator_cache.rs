@@ -0,0 +1,104 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chainstate_types::Locator;
+use common::{chain::GenBlock, primitives::Id};
+use utils::sync::Mutex;
+
+/// Remembers the locator of the most recent tip.
+///
+/// The locator only depends on the tip it has been built from, so a cached value stays valid
+/// until the tip changes. Peers request headers often, especially during the initial block
+/// download, and this spares walking the chain every time.
+#[derive(Default)]
+pub struct LocatorCache {
+    cached: Mutex<Option<(Id<GenBlock>, Locator)>>,
+}
+
+impl LocatorCache {
+    /// Return the locator for the given tip, calling `compute` only if it's not cached yet
+    pub fn get_or_compute<E>(
+        &self,
+        tip: Id<GenBlock>,
+        compute: impl FnOnce() -> Result<Locator, E>,
+    ) -> Result<Locator, E> {
+        let mut cached = self.cached.lock().expect("poisoned mutex");
+
+        match cached.as_ref() {
+            Some((cached_tip, locator)) if *cached_tip == tip => Ok(locator.clone()),
+            _ => {
+                let locator = compute()?;
+                *cached = Some((tip, locator.clone()));
+                Ok(locator)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::primitives::H256;
+    use std::convert::Infallible;
+
+    fn make_locator(tip: Id<GenBlock>) -> Locator {
+        Locator::new(vec![tip, Id::new(H256::zero())])
+    }
+
+    #[test]
+    fn recompute_on_tip_change() {
+        let cache = LocatorCache::default();
+        let mut computed = 0;
+        let mut get_locator = |tip| {
+            cache
+                .get_or_compute(tip, || {
+                    computed += 1;
+                    Ok::<_, Infallible>(make_locator(tip))
+                })
+                .unwrap()
+        };
+
+        let tip1 = Id::new(H256::from_low_u64_be(1));
+        let tip2 = Id::new(H256::from_low_u64_be(2));
+
+        assert_eq!(get_locator(tip1), make_locator(tip1));
+        assert_eq!(get_locator(tip1), make_locator(tip1));
+        assert_eq!(get_locator(tip2), make_locator(tip2));
+        assert_eq!(get_locator(tip2), make_locator(tip2));
+        assert_eq!(get_locator(tip1), make_locator(tip1));
+        drop(get_locator);
+
+        assert_eq!(computed, 3);
+    }
+
+    #[test]
+    fn failure_is_not_cached() {
+        let cache = LocatorCache::default();
+        let tip = Id::new(H256::from_low_u64_be(1));
+
+        assert_eq!(
+            cache.get_or_compute(tip, || Err("db error")),
+            Err("db error")
+        );
+        assert_eq!(
+            cache.get_or_compute(tip, || Ok::<_, &str>(make_locator(tip))),
+            Ok(make_locator(tip))
+        );
+        assert_eq!(
+            cache.get_or_compute(tip, || Err("not called")),
+            Ok(make_locator(tip))
+        );
+    }
+}
@@ -16,6 +16,7 @@
 mod chainstateref;
 mod error;
 mod info;
+mod locator_cache;
 mod median_time;
 mod orphan_blocks;
 
@@ -24,6 +25,7 @@ pub mod block_checking;
 pub mod block_invalidation;
 pub mod bootstrap;
 pub mod query;
+pub mod reader;
 pub mod tokens;
 pub mod tx_verification_strategy;
 
@@ -34,8 +36,10 @@ use thiserror::Error;
 
 use self::{
     block_invalidation::BlockInvalidator,
+    locator_cache::LocatorCache,
     orphan_blocks::{OrphanBlocksMut, OrphansProxy},
     query::ChainstateQuery,
+    reader::ChainstateReader,
     tx_verification_strategy::TransactionVerificationStrategy,
 };
 use crate::{ChainstateConfig, ChainstateEvent};
@@ -45,11 +49,14 @@ use chainstate_storage::{
 };
 use chainstate_types::{
     pos_randomness::PoSRandomness, BlockIndex, BlockStatus, BlockValidationStage, EpochData,
-    EpochStorageWrite, PropertyQueryError,
+    EpochStorageWrite, GenBlockIndex, PropertyQueryError,
 };
 use chainstateref::{ChainstateRef, ReorgError};
 use common::{
-    chain::{block::timestamp::BlockTimestamp, config::ChainConfig, Block, GenBlock, TxOutput},
+    chain::{
+        block::timestamp::BlockTimestamp, calculate_tx_offsets_in_block, config::ChainConfig,
+        signature::Signable, Block, GenBlock, Spender, TxInput, TxMainChainIndex, TxOutput,
+    },
     primitives::{id::WithId, BlockHeight, Id, Idable},
     time_getter::TimeGetter,
 };
@@ -68,17 +75,21 @@ pub use self::{
     error::*,
     info::ChainInfo,
     median_time::calculate_median_time_past,
-    tokens::{check_nft_issuance_data, check_tokens_issuance_data, is_rfc3986_valid_symbol},
+    tokens::{
+        check_nft_issuance_data, check_tokens_issuance_data, check_uri, is_rfc3986_valid_symbol,
+        validate_media_hash,
+    },
 };
 pub use chainstate_types::Locator;
 pub use error::{
-    BlockError, CheckBlockError, CheckBlockTransactionsError, DbCommittingContext,
+    BlockError, BlockSizeError, CheckBlockError, CheckBlockTransactionsError, DbCommittingContext,
     InitializationError, OrphanCheckError, StorageCompatibilityCheckError,
 };
 pub use orphan_blocks::OrphanBlocksRef;
 pub use transaction_verifier::{
     error::{
         ConnectTransactionError, SpendStakeError, TokenIssuanceError, TokensError, TxIndexError,
+        UriError,
     },
     storage::TransactionVerifierStorageError,
     IOPolicyError,
@@ -90,23 +101,31 @@ type ChainstateEventHandler = EventHandler<ChainstateEvent>;
 
 pub type OrphanErrorHandler = dyn Fn(&BlockError) + Send + Sync;
 
+/// How many blocks are indexed in a single DB transaction when rebuilding the transaction index
+const TX_INDEX_REBUILD_BATCH_SIZE: u64 = 1000;
+
 #[must_use]
 pub struct Chainstate<S, V> {
     chain_config: Arc<ChainConfig>,
     chainstate_config: ChainstateConfig,
     chainstate_storage: S,
-    tx_verification_strategy: V,
+    tx_verification_strategy: Arc<V>,
     orphan_blocks: OrphansProxy,
     custom_orphan_error_hook: Option<Arc<OrphanErrorHandler>>,
     events_controller: EventsController<ChainstateEvent>,
     time_getter: TimeGetter,
     is_initial_block_download_finished: SetFlag,
+    locator_cache: LocatorCache,
 }
 
 #[derive(Copy, Clone, Eq, Debug, PartialEq)]
 pub enum BlockSource {
+    /// The block was received from a peer
     Peer,
+    /// The block was submitted locally (e.g. produced by this node or submitted via RPC)
     Local,
+    /// The block was read from a bootstrap file during import
+    BootstrapFile,
 }
 
 impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V> {
@@ -120,7 +139,7 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         Ok(chainstateref::ChainstateRef::new_rw(
             &self.chain_config,
             &self.chainstate_config,
-            &self.tx_verification_strategy,
+            &*self.tx_verification_strategy,
             db_tx,
             &self.time_getter,
         ))
@@ -133,7 +152,7 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         Ok(chainstateref::ChainstateRef::new_ro(
             &self.chain_config,
             &self.chainstate_config,
-            &self.tx_verification_strategy,
+            &*self.tx_verification_strategy,
             db_tx,
             &self.time_getter,
         ))
@@ -143,6 +162,27 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         self.make_db_tx_ro().map(ChainstateQuery::new).map_err(PropertyQueryError::from)
     }
 
+    /// Get the locator of the current tip, reusing the previous result if the tip hasn't changed
+    pub fn get_locator(&self) -> Result<Locator, PropertyQueryError> {
+        let query = self.query()?;
+        let best_block_id = query.get_best_block_id()?;
+        self.locator_cache.get_or_compute(best_block_id, || query.get_locator())
+    }
+
+    /// Create a reader that queries the same storage independently of this object
+    pub fn make_reader(&self) -> ChainstateReader<S, V>
+    where
+        S: Clone,
+    {
+        ChainstateReader::new(
+            Arc::clone(&self.chain_config),
+            self.chainstate_config.clone(),
+            self.chainstate_storage.clone(),
+            Arc::clone(&self.tx_verification_strategy),
+            self.time_getter.clone(),
+        )
+    }
+
     pub fn subscribe_to_events(&mut self, handler: ChainstateEventHandler) {
         self.events_controller.subscribe_to_events(handler);
     }
@@ -202,12 +242,13 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
             chain_config,
             chainstate_config,
             chainstate_storage,
-            tx_verification_strategy,
+            tx_verification_strategy: Arc::new(tx_verification_strategy),
             orphan_blocks,
             custom_orphan_error_hook,
             events_controller: EventsController::new(),
             time_getter,
             is_initial_block_download_finished: SetFlag::new(),
+            locator_cache: LocatorCache::default(),
         }
     }
 
@@ -224,10 +265,12 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         let block1_id = dbtx
             .get_block_id_by_height(&BlockHeight::new(1))?
             .ok_or(InitializationError::Block1Missing)?;
-        let block1 = dbtx
-            .get_block(Id::new(block1_id.to_hash()))?
+        // Note: the block index is used instead of the block itself, because the block body
+        // may have been pruned.
+        let block1_index = dbtx
+            .get_block_index(&Id::new(block1_id.to_hash()))?
             .ok_or(InitializationError::Block1Missing)?;
-        let stored_genesis_id = block1.prev_block_id();
+        let stored_genesis_id = *block1_index.prev_block_id();
 
         // Check storage genesis ID matches chain config genesis ID
         ensure!(
@@ -252,8 +295,17 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
             .log_err()?;
 
         if let Some(tx_index_enabled) = tx_index_enabled {
+            if !tx_index_enabled
+                && *self.chainstate_config.tx_index_enabled
+                && *self.chainstate_config.reindex_tx_index
+            {
+                // The index was enabled for an existing DB, build it from the stored blocks.
+                db_tx.abort();
+                return self.rebuild_tx_index();
+            }
+
             // Make sure DB indexing state is same as in the config.
-            // TODO: Allow changing state (creating new or deleting existing index).
+            // TODO: Allow deleting an existing index.
             ensure!(
                 *self.chainstate_config.tx_index_enabled == tx_index_enabled,
                 BlockError::TxIndexConfigError
@@ -271,6 +323,123 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         Ok(())
     }
 
+    /// Build the transaction index from the mainchain blocks that are already in the storage.
+    ///
+    /// The blocks are not validated again, only the index entries for the transactions and
+    /// block rewards are written and the outputs spent by them are marked accordingly.
+    fn rebuild_tx_index(&mut self) -> Result<(), BlockError> {
+        let best_block_height = self
+            .make_db_tx_ro()
+            .map_err(BlockError::from)?
+            .get_best_block_index()
+            .map_err(BlockError::BestBlockIndexQueryError)?
+            .block_height();
+
+        log::info!("Rebuilding the transaction index up to height {best_block_height}");
+
+        let mut db_tx = self
+            .chainstate_storage
+            .transaction_rw(None)
+            .map_err(BlockError::from)
+            .log_err()?;
+
+        let genesis_id = self.chain_config.genesis_block_id();
+        let genesis_index = common::chain::TxMainChainIndex::new(
+            genesis_id.into(),
+            self.chain_config.genesis_block().utxos().len() as u32,
+        )
+        .expect("Genesis not constructed correctly");
+        db_tx
+            .set_mainchain_tx_index(&genesis_id.into(), &genesis_index)
+            .map_err(BlockError::StorageError)?;
+
+        let mut height = BlockHeight::new(1);
+        while height <= best_block_height {
+            let block_id = db_tx
+                .get_block_id_by_height(&height)
+                .map_err(BlockError::StorageError)?
+                .expect("Mainchain block id must exist up to the best height");
+            let block_id = Id::<Block>::new(block_id.to_hash());
+            let block = db_tx
+                .get_block(block_id)
+                .map_err(BlockError::StorageError)?
+                .ok_or(BlockError::TxIndexRebuildBlockMissing(block_id))?;
+
+            let reward_index = TxMainChainIndex::new(
+                block_id.into(),
+                block
+                    .block_reward()
+                    .outputs()
+                    .len()
+                    .try_into()
+                    .map_err(|_| TxIndexError::InvalidOutputCount)?,
+            )
+            .map_err(TxIndexError::from)?;
+            db_tx
+                .set_mainchain_tx_index(&block_id.into(), &reward_index)
+                .map_err(BlockError::StorageError)?;
+            Self::spend_in_tx_index(
+                &mut db_tx,
+                block.block_reward_transactable().inputs().unwrap_or(&[]),
+                Spender::BlockInput(block_id.into()),
+            )?;
+
+            let tx_indices = calculate_tx_offsets_in_block(&block).map_err(TxIndexError::from)?;
+            for (tx, tx_index) in block.transactions().iter().zip(tx_indices) {
+                let tx_id = tx.transaction().get_id();
+                Self::spend_in_tx_index(&mut db_tx, tx.inputs(), Spender::RegularInput(tx_id))?;
+                db_tx
+                    .set_mainchain_tx_index(&tx_id.into(), &tx_index)
+                    .map_err(BlockError::StorageError)?;
+            }
+
+            if height.into_int() % TX_INDEX_REBUILD_BATCH_SIZE == 0 {
+                db_tx.commit().map_err(BlockError::StorageError)?;
+                log::info!("Transaction index rebuilt up to height {height}/{best_block_height}");
+                db_tx = self
+                    .chainstate_storage
+                    .transaction_rw(None)
+                    .map_err(BlockError::from)
+                    .log_err()?;
+            }
+
+            height = height.next_height();
+        }
+
+        db_tx
+            .set_is_mainchain_tx_index_enabled(true)
+            .map_err(BlockError::StorageError)?;
+        db_tx.commit().map_err(BlockError::StorageError)?;
+
+        log::info!("Transaction index rebuilt up to height {best_block_height}");
+
+        Ok(())
+    }
+
+    fn spend_in_tx_index(
+        db_tx: &mut impl BlockchainStorageWrite,
+        inputs: &[TxInput],
+        spender: Spender,
+    ) -> Result<(), BlockError> {
+        for outpoint in inputs.iter().filter_map(|input| match input {
+            TxInput::Utxo(outpoint) => Some(outpoint),
+            TxInput::Account(_) => None,
+        }) {
+            let source_id = outpoint.source_id();
+            let mut tx_index = db_tx
+                .get_mainchain_tx_index(&source_id)
+                .map_err(BlockError::StorageError)?
+                .ok_or(TxIndexError::MissingOutputOrSpent)?;
+            tx_index
+                .spend(outpoint.output_index(), spender.clone())
+                .map_err(TxIndexError::from)?;
+            db_tx
+                .set_mainchain_tx_index(&source_id, &tx_index)
+                .map_err(BlockError::StorageError)?;
+        }
+        Ok(())
+    }
+
     fn broadcast_new_tip_event(&self, new_block_index: &Option<BlockIndex>) {
         match new_block_index {
             Some(ref new_block_index) => {
@@ -363,6 +532,10 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
             .update_min_height_with_allowed_reorg()
             .map_err(BlockIntegrationError::OtherNonValidationError)?;
 
+        chainstate_ref
+            .prune_block_bodies()
+            .map_err(BlockIntegrationError::OtherNonValidationError)?;
+
         result.map_err(|err| match err {
             ReorgError::ConnectTipFailed(block_id, block_err) => {
                 BlockIntegrationError::ConnectBlockErrorDuringReorg(
@@ -561,10 +734,11 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
 
         if let Some(ref bi) = result {
             log::info!(
-                "New tip in chainstate {} with height {}, timestamp: {}",
+                "New tip in chainstate {} with height {}, timestamp: {}, source: {:?}",
                 bi.block_id(),
                 bi.block_height(),
                 bi.block_timestamp(),
+                block_source,
             );
 
             self.update_initial_block_download_flag()
@@ -583,6 +757,116 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         self.process_block_and_related_orphans(block, block_source)
     }
 
+    /// Process the blocks, which must be ordered so that each block's parent is either already
+    /// known or precedes it in the batch. All the blocks are committed in a single db transaction.
+    /// If the batch can't be processed as a whole (e.g. one of the blocks is invalid or an orphan),
+    /// nothing is committed and the blocks are processed one by one instead.
+    ///
+    /// Returns the block index of the new tip.
+    pub fn process_block_batch(
+        &mut self,
+        blocks: Vec<WithId<Block>>,
+        block_source: BlockSource,
+    ) -> Result<Option<BlockIndex>, BlockError> {
+        if blocks.len() > 1 {
+            match self.attempt_to_process_block_batch(&blocks) {
+                Ok(new_tip) => {
+                    let last_block_id = blocks.last().expect("blocks can't be empty").get_id();
+                    let new_tip = self.process_orphans_of(&last_block_id)?.or(new_tip);
+                    self.broadcast_new_tip_event(&new_tip);
+                    if let Some(ref bi) = new_tip {
+                        log::info!(
+                            "New tip in chainstate {} with height {} after processing {} blocks, source: {:?}",
+                            bi.block_id(),
+                            bi.block_height(),
+                            blocks.len(),
+                            block_source,
+                        );
+
+                        self.update_initial_block_download_flag()
+                            .map_err(BlockError::BestBlockIdQueryError)?;
+                    }
+                    return Ok(new_tip);
+                }
+                Err(err) => {
+                    log::debug!(
+                        "Failed to process a batch of blocks, processing them one by one: {err}"
+                    );
+                }
+            }
+        }
+
+        let mut new_tip = None;
+        for block in blocks {
+            new_tip = self.process_block(block, block_source)?.or(new_tip);
+        }
+        Ok(new_tip)
+    }
+
+    /// Integrate all the blocks in a single db transaction.
+    /// On success, return Some(best_block_index) if the tip has changed, otherwise return None.
+    fn attempt_to_process_block_batch(
+        &mut self,
+        blocks: &[WithId<Block>],
+    ) -> Result<Option<BlockIndex>, BlockError> {
+        let last_block_id = blocks.last().expect("blocks can't be empty").get_id();
+
+        let tip_changed = self.with_rw_tx(
+            |chainstate_ref| {
+                let mut tip_changed = false;
+                for block in blocks {
+                    let block_id = block.get_id();
+                    if get_block_index(chainstate_ref, &block_id)?.is_some() {
+                        return Err(BlockError::BlockAlreadyProcessed(block_id));
+                    }
+
+                    let block_index = chainstate_ref
+                        .create_block_index_for_new_block(block, BlockStatus::new())?;
+                    tip_changed |= Self::integrate_block(chainstate_ref, block, block_index)
+                        .map_err(|err| match err {
+                            BlockIntegrationError::ConnectBlockErrorDuringReorg(err, _, _)
+                            | BlockIntegrationError::OtherValidationError(err, _)
+                            | BlockIntegrationError::OtherNonValidationError(err) => err,
+                            BlockIntegrationError::BlockCommitError(block_id, attempts, err) => {
+                                BlockError::DbCommitError(
+                                    attempts,
+                                    err,
+                                    DbCommittingContext::Block(block_id),
+                                )
+                            }
+                        })?;
+                }
+                Ok(tip_changed)
+            },
+            |attempt_number| {
+                log::info!(
+                    "Processing {} blocks up to {last_block_id}, attempt #{attempt_number}",
+                    blocks.len()
+                );
+            },
+            |attempts_count, db_err| {
+                BlockError::DbCommitError(
+                    attempts_count,
+                    db_err,
+                    DbCommittingContext::Block(last_block_id),
+                )
+            },
+        )?;
+
+        if !tip_changed {
+            return Ok(None);
+        }
+
+        let chainstate_ref = self.make_db_tx_ro().map_err(BlockError::from).log_err()?;
+        match chainstate_ref
+            .get_best_block_index()
+            .map_err(BlockError::BestBlockIdQueryError)?
+        {
+            GenBlockIndex::Block(block_index) => Ok(Some(block_index)),
+            GenBlockIndex::Genesis(_) => Ok(None),
+        }
+    }
+
     /// Initialize chainstate with genesis block
     pub fn process_genesis(&mut self) -> Result<(), BlockError> {
         // Gather information about genesis.
@@ -722,9 +1006,17 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
 
         drop(chainstate_ref);
 
-        if block_source == BlockSource::Local && !block_index_found {
-            self.new_orphan_block(block).log_err()?;
-            return Err(OrphanCheckError::LocalOrphan);
+        if !block_index_found {
+            match block_source {
+                BlockSource::Peer => {}
+                BlockSource::Local => {
+                    self.new_orphan_block(block).log_err()?;
+                    return Err(OrphanCheckError::LocalOrphan);
+                }
+                // Blocks in a bootstrap file are ordered by height, so an orphan means that
+                // the file is incomplete; there is no point in keeping it.
+                BlockSource::BootstrapFile => return Err(OrphanCheckError::BootstrapFileOrphan),
+            }
         }
         Ok(block)
     }
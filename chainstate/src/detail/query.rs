@@ -14,10 +14,12 @@
 // limitations under the License.
 
 use chainstate_storage::BlockchainStorageRead;
-use chainstate_types::{BlockIndex, GenBlockIndex, Locator, PropertyQueryError};
+use chainstate_types::{
+    BlockIndex, BlockIndexHistoryIterator, GenBlockIndex, Locator, PropertyQueryError,
+};
 use common::{
     chain::{
-        block::{signed_block_header::SignedBlockHeader, BlockReward},
+        block::{signed_block_header::SignedBlockHeader, timestamp::BlockTimestamp, BlockReward},
         tokens::{
             RPCFungibleTokenInfo, RPCNonFungibleTokenInfo, RPCTokenInfo, TokenAuxiliaryData,
             TokenData, TokenId,
@@ -102,9 +104,10 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
                             panic!("genesis block received at non-zero height {from}")
                         }
                         common::chain::GenBlockId::Block(block_id) => {
-                            let block = self.get_block(block_id)?.unwrap_or_else(|| {
-                                panic!("can't find block {block_id} at height {from}")
-                            });
+                            // The block body may be missing if the node prunes old blocks
+                            let block = self
+                                .get_block(block_id)?
+                                .ok_or(PropertyQueryError::BlockNotFound(block_id))?;
                             res.push(block);
                         }
                     }
@@ -117,6 +120,62 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         Ok(res)
     }
 
+    /// Returns the headers of the mainchain blocks with heights in `[from, to]`, in ascending
+    /// height order. The range may extend beyond the tip, but not contain more than
+    /// `max_count` heights.
+    pub fn get_mainchain_headers_in_range(
+        &self,
+        from: BlockHeight,
+        to: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<SignedBlockHeader>, PropertyQueryError> {
+        utils::ensure!(
+            from != BlockHeight::zero() && from <= to,
+            PropertyQueryError::InvalidBlockHeightRange(from, to)
+        );
+
+        let range_len = (to - from)
+            .and_then(|distance| usize::try_from(distance.to_int()).ok())
+            .and_then(|distance| distance.checked_add(1));
+        utils::ensure!(
+            range_len.map_or(false, |len| len <= max_count),
+            PropertyQueryError::BlockHeightRangeTooLong(from, to, max_count)
+        );
+
+        let mut res = Vec::new();
+        let mut height = from;
+        while height <= to {
+            let block_id = match self.get_block_id_from_height(&height)? {
+                Some(block_id) => block_id,
+                None => break,
+            };
+            let header = match block_id.classify(self.chainstate_ref.chain_config()) {
+                common::chain::GenBlockId::Genesis(_) => {
+                    panic!("genesis block received at non-zero height {height}")
+                }
+                common::chain::GenBlockId::Block(block_id) => self
+                    .get_block_header(block_id)?
+                    .ok_or(PropertyQueryError::BlockIndexNotFound(block_id.into()))?,
+            };
+            res.push(header);
+            height = height.next_height();
+        }
+
+        Ok(res)
+    }
+
+    pub fn get_last_block_timestamps(
+        &self,
+        count: usize,
+    ) -> Result<Vec<BlockTimestamp>, PropertyQueryError> {
+        let best_block_id = self.chainstate_ref.get_best_block_id()?;
+        let timestamps = BlockIndexHistoryIterator::new(best_block_id, &self.chainstate_ref)
+            .take(count)
+            .map(|block_index| block_index.block_timestamp())
+            .collect();
+        Ok(timestamps)
+    }
+
     pub fn get_block_index(
         &self,
         id: &Id<Block>,
@@ -124,6 +183,10 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.chainstate_ref.get_block_index(id)
     }
 
+    pub fn has_block(&self, id: &Id<Block>) -> Result<bool, PropertyQueryError> {
+        self.chainstate_ref.has_block_index(id)
+    }
+
     pub fn get_gen_block_index(
         &self,
         id: &Id<GenBlock>,
@@ -154,6 +217,13 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.chainstate_ref.get_transaction_in_block(id)
     }
 
+    pub fn get_transaction_block(
+        &self,
+        id: Id<Transaction>,
+    ) -> Result<Option<(Id<Block>, BlockHeight)>, PropertyQueryError> {
+        self.chainstate_ref.get_transaction_block(id)
+    }
+
     pub fn get_locator(&self) -> Result<Locator, PropertyQueryError> {
         let best_block_index = self.chainstate_ref.get_best_block_index()?;
         let height = best_block_index.block_height();
@@ -187,6 +257,15 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.chainstate_ref.get_block_height_in_main_chain(id)
     }
 
+    pub fn get_block_heights_in_main_chain(
+        &self,
+        ids: &[Id<GenBlock>],
+    ) -> Result<Vec<Option<BlockHeight>>, PropertyQueryError> {
+        ids.iter()
+            .map(|id| self.chainstate_ref.get_block_height_in_main_chain(id))
+            .collect()
+    }
+
     fn get_mainchain_headers_higher_than(
         &self,
         height: BlockHeight,
@@ -271,48 +350,35 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         token_id: TokenId,
     ) -> Result<Option<RPCTokenInfo>, PropertyQueryError> {
         let token_aux_data = self.get_token_aux_data(&token_id)?;
-        let token_aux_data = match token_aux_data {
-            Some(data) => data,
-            None => return Ok(None),
-        };
+        Ok(token_aux_data.and_then(|data| make_token_info_for_rpc(token_id, &data)))
+    }
 
-        Ok(token_aux_data
-            .issuance_tx()
-            .outputs()
-            .iter()
-            // Filter tokens
-            .filter_map(|output| match output {
-                TxOutput::Transfer(v, _)
-                | TxOutput::LockThenTransfer(v, _, _)
-                | TxOutput::Burn(v) => v.token_data(),
-                TxOutput::CreateStakePool(_, _)
-                | TxOutput::ProduceBlockFromStake(_, _)
-                | TxOutput::CreateDelegationId(_, _)
-                | TxOutput::DelegateStaking(_, _) => None,
+    /// Returns the infos of the tokens issued in the main chain, ordered by the height of the
+    /// issuance block (tokens issued in the same block are ordered by id), skipping the first
+    /// `offset` tokens and returning at most `limit` of them.
+    pub fn get_tokens_info_for_rpc_by_issuance(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<RPCTokenInfo>, PropertyQueryError> {
+        let tokens_aux_data = self.chainstate_ref.get_tokens_aux_data()?;
+
+        let mut tokens = tokens_aux_data
+            .into_iter()
+            .map(|(token_id, aux_data)| {
+                let block_index =
+                    self.chainstate_ref.get_existing_block_index(&aux_data.issuance_block_id())?;
+                Ok((block_index.block_height(), token_id, aux_data))
             })
-            // Find issuance data and return RPCTokenInfo
-            .find_map(|token_data| match token_data {
-                TokenData::TokenIssuance(issuance) => {
-                    Some(RPCTokenInfo::new_fungible(RPCFungibleTokenInfo::new(
-                        token_id,
-                        token_aux_data.issuance_tx().get_id(),
-                        token_aux_data.issuance_block_id(),
-                        issuance.token_ticker.clone(),
-                        issuance.amount_to_issue,
-                        issuance.number_of_decimals,
-                        issuance.metadata_uri.clone(),
-                    )))
-                }
-                TokenData::NftIssuance(nft) => {
-                    Some(RPCTokenInfo::new_nonfungible(RPCNonFungibleTokenInfo::new(
-                        token_id,
-                        token_aux_data.issuance_tx().get_id(),
-                        token_aux_data.issuance_block_id(),
-                        &nft.metadata,
-                    )))
-                }
-                TokenData::TokenTransfer(_) => None,
-            }))
+            .collect::<Result<Vec<_>, PropertyQueryError>>()?;
+        tokens.sort_unstable_by_key(|(height, token_id, _)| (*height, *token_id));
+
+        Ok(tokens
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .filter_map(|(_, token_id, aux_data)| make_token_info_for_rpc(token_id, &aux_data))
+            .collect())
     }
 
     pub fn get_token_aux_data(
@@ -337,3 +403,48 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.chainstate_ref.get_block_id_tree_as_list()
     }
 }
+
+fn make_token_info_for_rpc(
+    token_id: TokenId,
+    token_aux_data: &TokenAuxiliaryData,
+) -> Option<RPCTokenInfo> {
+    token_aux_data
+        .issuance_tx()
+        .outputs()
+        .iter()
+        // Filter tokens
+        .filter_map(|output| match output {
+            TxOutput::Transfer(v, _) | TxOutput::LockThenTransfer(v, _, _) | TxOutput::Burn(v) => {
+                v.token_data()
+            }
+            TxOutput::CreateStakePool(_, _)
+            | TxOutput::ProduceBlockFromStake(_, _)
+            | TxOutput::CreateDelegationId(_, _)
+            | TxOutput::DelegateStaking(_, _) => None,
+        })
+        // Find issuance data and return RPCTokenInfo
+        .find_map(|token_data| match token_data {
+            TokenData::TokenIssuance(issuance) => {
+                Some(RPCTokenInfo::new_fungible(RPCFungibleTokenInfo::new(
+                    token_id,
+                    token_aux_data.issuance_tx().get_id(),
+                    token_aux_data.issuance_block_id(),
+                    issuance.token_ticker.clone(),
+                    issuance.amount_to_issue,
+                    issuance.number_of_decimals,
+                    issuance.metadata_uri.clone(),
+                )))
+            }
+            TokenData::NftIssuance(nft) => {
+                Some(RPCTokenInfo::new_nonfungible(RPCNonFungibleTokenInfo::new(
+                    token_id,
+                    token_aux_data.issuance_tx().get_id(),
+                    token_aux_data.issuance_block_id(),
+                    &nft.metadata,
+                )))
+            }
+            TokenData::TokenTransfer(_)
+            | TokenData::TokenFreezeV1(_)
+            | TokenData::TokenUnfreezeV1(_) => None,
+        })
+}
@@ -0,0 +1,83 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use chainstate_storage::{BlockchainStorage, Transactional};
+use chainstate_types::PropertyQueryError;
+use common::{chain::ChainConfig, time_getter::TimeGetter};
+
+use super::{
+    chainstateref::ChainstateRef, query::ChainstateQuery,
+    tx_verification_strategy::TransactionVerificationStrategy, TxRo,
+};
+use crate::ChainstateConfig;
+
+/// Read-only access to the chainstate storage that doesn't go through the `Chainstate` object.
+///
+/// The queries are performed on snapshots of the storage (read-only storage transactions),
+/// so readers can run concurrently with block processing.
+pub struct ChainstateReader<S, V> {
+    chain_config: Arc<ChainConfig>,
+    chainstate_config: ChainstateConfig,
+    chainstate_storage: S,
+    tx_verification_strategy: Arc<V>,
+    time_getter: TimeGetter,
+}
+
+impl<S: BlockchainStorage, V: TransactionVerificationStrategy> ChainstateReader<S, V> {
+    pub fn new(
+        chain_config: Arc<ChainConfig>,
+        chainstate_config: ChainstateConfig,
+        chainstate_storage: S,
+        tx_verification_strategy: Arc<V>,
+        time_getter: TimeGetter,
+    ) -> Self {
+        Self {
+            chain_config,
+            chainstate_config,
+            chainstate_storage,
+            tx_verification_strategy,
+            time_getter,
+        }
+    }
+
+    pub fn query(&self) -> Result<ChainstateQuery<TxRo<'_, S>, V>, PropertyQueryError> {
+        let db_tx = self.chainstate_storage.transaction_ro().map_err(PropertyQueryError::from)?;
+        Ok(ChainstateQuery::new(ChainstateRef::new_ro(
+            &self.chain_config,
+            &self.chainstate_config,
+            &*self.tx_verification_strategy,
+            db_tx,
+            &self.time_getter,
+        )))
+    }
+
+    /// Open a snapshot that serves all the queries made through it from the same storage state.
+    pub fn snapshot(&self) -> Result<ChainstateSnapshot<'_, S, V>, PropertyQueryError> {
+        self.query().map(|query| ChainstateSnapshot { query })
+    }
+}
+
+/// A consistent read-only view of the chainstate, see `ChainstateReader::snapshot`.
+pub struct ChainstateSnapshot<'a, S: BlockchainStorage + 'a, V> {
+    query: ChainstateQuery<'a, TxRo<'a, S>, V>,
+}
+
+impl<'a, S: BlockchainStorage + 'a, V> ChainstateSnapshot<'a, S, V> {
+    pub fn query(&self) -> &ChainstateQuery<'a, TxRo<'a, S>, V> {
+        &self.query
+    }
+}
@@ -14,14 +14,13 @@
 // limitations under the License.
 
 use common::chain::ChainConfig;
-use tx_verifier::error::TokenIssuanceError;
+use tx_verifier::error::{TokenIssuanceError, UriError};
 use utils::ensure;
 
-fn check_is_text_alphanumeric(str: &[u8]) -> bool {
-    match String::from_utf8(str.to_vec()) {
-        Ok(text) => text.chars().all(char::is_alphanumeric),
-        Err(_) => false,
-    }
+/// Find the first character that is not alphanumeric.
+/// Invalid UTF-8 sequences are reported as the replacement character.
+fn find_non_alphanumeric_char(text: &[u8]) -> Option<char> {
+    String::from_utf8_lossy(text).chars().find(|ch| !ch.is_alphanumeric())
 }
 
 pub fn is_rfc3986_valid_symbol(ch: char) -> bool {
@@ -29,24 +28,60 @@ pub fn is_rfc3986_valid_symbol(ch: char) -> bool {
     "%:/?#[]@!$&\'()*+,;=-._~".chars().any(|rfc1738_ch| ch == rfc1738_ch)
 }
 
-pub fn is_uri_valid(uri: &[u8]) -> bool {
-    match String::from_utf8(uri.to_vec()) {
-        Ok(uri) => uri
-            .chars()
-            // TODO: this is probably an invalid way to validate URLs. Find the proper way to do this in rust.
-            .all(|ch| ch.is_alphanumeric() || is_rfc3986_valid_symbol(ch)),
-        Err(_) => false,
+fn is_uri_scheme_valid(scheme: &str) -> bool {
+    // scheme = ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )
+    // See https://www.rfc-editor.org/rfc/rfc3986#section-3.1
+    let mut chars = scheme.chars();
+    chars.next().map_or(false, |ch| ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || "+-.".contains(ch))
+}
+
+pub fn check_uri(chain_config: &ChainConfig, uri: &[u8]) -> Result<(), UriError> {
+    ensure!(
+        uri.len() <= chain_config.token_max_uri_len(),
+        UriError::TooLong(uri.len(), chain_config.token_max_uri_len())
+    );
+
+    let uri = std::str::from_utf8(uri).map_err(|_| UriError::InvalidUtf8)?;
+
+    // TODO: this is probably an invalid way to validate URLs. Find the proper way to do this in rust.
+    let illegal_char =
+        uri.chars().find(|ch| !ch.is_alphanumeric() && !is_rfc3986_valid_symbol(*ch));
+    if let Some(ch) = illegal_char {
+        return Err(UriError::IllegalCharacter(ch));
+    }
+
+    // The scheme is optional, but if there is a colon before the path, the query or the fragment,
+    // whatever precedes it must be a valid scheme
+    if let Some((scheme, _)) = uri
+        .find(|ch| matches!(ch, ':' | '/' | '?' | '#'))
+        .filter(|pos| uri[*pos..].starts_with(':'))
+        .map(|pos| uri.split_at(pos))
+    {
+        ensure!(
+            is_uri_scheme_valid(scheme),
+            UriError::BadScheme(scheme.to_owned())
+        );
     }
+
+    Ok(())
 }
 
-pub fn check_media_hash(chain_config: &ChainConfig, hash: &[u8]) -> Result<(), TokenIssuanceError> {
+/// Check that the length of an NFT media hash is within the limits of the chain, see
+/// [ChainConfig::min_hash_len] and [ChainConfig::max_hash_len].
+///
+/// Wallets may use this to validate a hash before building an issuance transaction.
+pub fn validate_media_hash(
+    chain_config: &ChainConfig,
+    hash: &[u8],
+) -> Result<(), TokenIssuanceError> {
     ensure!(
         hash.len() >= chain_config.min_hash_len(),
-        TokenIssuanceError::MediaHashTooShort
+        TokenIssuanceError::MediaHashTooShort(hash.len(), chain_config.min_hash_len())
     );
     ensure!(
         hash.len() <= chain_config.max_hash_len(),
-        TokenIssuanceError::MediaHashTooLong
+        TokenIssuanceError::MediaHashTooLong(hash.len(), chain_config.max_hash_len())
     );
     Ok(())
 }
@@ -58,14 +93,18 @@ pub fn check_token_ticker(
     // Check length
     ensure!(
         ticker.len() <= chain_config.token_max_ticker_len() && !ticker.is_empty(),
-        TokenIssuanceError::IssueErrorInvalidTickerLength
+        TokenIssuanceError::IssueErrorInvalidTickerLength(
+            ticker.len(),
+            chain_config.token_max_ticker_len()
+        )
     );
 
     // Check is ticker has alphanumeric chars
-    ensure!(
-        check_is_text_alphanumeric(ticker),
-        TokenIssuanceError::IssueErrorTickerHasNoneAlphaNumericChar
-    );
+    if let Some(ch) = find_non_alphanumeric_char(ticker) {
+        return Err(TokenIssuanceError::IssueErrorTickerHasNoneAlphaNumericChar(
+            ch,
+        ));
+    }
     Ok(())
 }
 
@@ -73,14 +112,18 @@ pub fn check_nft_name(chain_config: &ChainConfig, name: &[u8]) -> Result<(), Tok
     // Check length
     ensure!(
         name.len() <= chain_config.token_max_name_len() && !name.is_empty(),
-        TokenIssuanceError::IssueErrorInvalidNameLength
+        TokenIssuanceError::IssueErrorInvalidNameLength(
+            name.len(),
+            chain_config.token_max_name_len()
+        )
     );
 
     // Check is name has alphanumeric chars
-    ensure!(
-        check_is_text_alphanumeric(name),
-        TokenIssuanceError::IssueErrorNameHasNoneAlphaNumericChar
-    );
+    if let Some(ch) = find_non_alphanumeric_char(name) {
+        return Err(TokenIssuanceError::IssueErrorNameHasNoneAlphaNumericChar(
+            ch,
+        ));
+    }
     Ok(())
 }
 
@@ -91,13 +134,183 @@ pub fn check_nft_description(
     // Check length
     ensure!(
         description.len() <= chain_config.token_max_description_len() && !description.is_empty(),
-        TokenIssuanceError::IssueErrorInvalidDescriptionLength
+        TokenIssuanceError::IssueErrorInvalidDescriptionLength(
+            description.len(),
+            chain_config.token_max_description_len()
+        )
     );
 
     // Check is description has alphanumeric chars
-    ensure!(
-        check_is_text_alphanumeric(description),
-        TokenIssuanceError::IssueErrorDescriptionHasNoneAlphaNumericChar
-    );
+    if let Some(ch) = find_non_alphanumeric_char(description) {
+        return Err(TokenIssuanceError::IssueErrorDescriptionHasNoneAlphaNumericChar(ch));
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::chain::config::create_unit_test_config;
+
+    #[test]
+    fn valid_uris() {
+        let chain_config = create_unit_test_config();
+        for uri in [
+            "",
+            "https://some_site.some",
+            "ipfs://QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o",
+            "some_site.some/path?query=1#fragment",
+            "urn:isbn:0451450523",
+        ] {
+            assert_eq!(check_uri(&chain_config, uri.as_bytes()), Ok(()), "{uri}");
+        }
+    }
+
+    #[test]
+    fn too_long() {
+        let chain_config = create_unit_test_config();
+        let max_len = chain_config.token_max_uri_len();
+        let uri = "a".repeat(max_len + 1);
+        assert_eq!(
+            check_uri(&chain_config, uri.as_bytes()),
+            Err(UriError::TooLong(max_len + 1, max_len))
+        );
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let chain_config = create_unit_test_config();
+        assert_eq!(
+            check_uri(&chain_config, &[b'a', 0xff, b'b']),
+            Err(UriError::InvalidUtf8)
+        );
+    }
+
+    #[test]
+    fn illegal_character() {
+        let chain_config = create_unit_test_config();
+        assert_eq!(
+            check_uri(&chain_config, "https://some site.some".as_bytes()),
+            Err(UriError::IllegalCharacter(' '))
+        );
+        assert_eq!(
+            check_uri(&chain_config, "https://💖🚁🌭.🦠🚀🚖🚧".as_bytes()),
+            Err(UriError::IllegalCharacter('💖'))
+        );
+    }
+
+    #[test]
+    fn bad_scheme() {
+        let chain_config = create_unit_test_config();
+        for (uri, scheme) in [
+            ("://some_site.some", ""),
+            ("1https://some_site.some", "1https"),
+            ("ht_tp://some_site.some", "ht_tp"),
+        ] {
+            assert_eq!(
+                check_uri(&chain_config, uri.as_bytes()),
+                Err(UriError::BadScheme(scheme.to_owned())),
+                "{uri}"
+            );
+        }
+    }
+
+    #[test]
+    fn ticker_errors_report_offending_value() {
+        let chain_config = create_unit_test_config();
+        let max_len = chain_config.token_max_ticker_len();
+
+        assert_eq!(
+            check_token_ticker(&chain_config, &[]),
+            Err(TokenIssuanceError::IssueErrorInvalidTickerLength(
+                0, max_len
+            ))
+        );
+        assert_eq!(
+            check_token_ticker(&chain_config, "A".repeat(max_len + 1).as_bytes()),
+            Err(TokenIssuanceError::IssueErrorInvalidTickerLength(
+                max_len + 1,
+                max_len
+            ))
+        );
+        assert_eq!(
+            check_token_ticker(&chain_config, b"AB-C"),
+            Err(TokenIssuanceError::IssueErrorTickerHasNoneAlphaNumericChar(
+                '-'
+            ))
+        );
+        assert_eq!(
+            check_token_ticker(&chain_config, &[b'A', 0xff]),
+            Err(TokenIssuanceError::IssueErrorTickerHasNoneAlphaNumericChar(
+                char::REPLACEMENT_CHARACTER
+            ))
+        );
+    }
+
+    #[test]
+    fn nft_text_errors_report_offending_value() {
+        let chain_config = create_unit_test_config();
+        let max_name_len = chain_config.token_max_name_len();
+        let max_description_len = chain_config.token_max_description_len();
+
+        assert_eq!(
+            check_nft_name(&chain_config, "a".repeat(max_name_len + 1).as_bytes()),
+            Err(TokenIssuanceError::IssueErrorInvalidNameLength(
+                max_name_len + 1,
+                max_name_len
+            ))
+        );
+        assert_eq!(
+            check_nft_name(&chain_config, b"some name"),
+            Err(TokenIssuanceError::IssueErrorNameHasNoneAlphaNumericChar(
+                ' '
+            ))
+        );
+        assert_eq!(
+            check_nft_description(&chain_config, &[]),
+            Err(TokenIssuanceError::IssueErrorInvalidDescriptionLength(
+                0,
+                max_description_len
+            ))
+        );
+        assert_eq!(
+            check_nft_description(&chain_config, b"description!"),
+            Err(TokenIssuanceError::IssueErrorDescriptionHasNoneAlphaNumericChar('!'))
+        );
+    }
+
+    #[test]
+    fn media_hash_errors_report_offending_value() {
+        let chain_config = create_unit_test_config();
+        let min_len = chain_config.min_hash_len();
+        let max_len = chain_config.max_hash_len();
+
+        assert_eq!(
+            validate_media_hash(&chain_config, &vec![0; min_len - 1]),
+            Err(TokenIssuanceError::MediaHashTooShort(min_len - 1, min_len))
+        );
+        assert_eq!(
+            validate_media_hash(&chain_config, &vec![0; max_len + 1]),
+            Err(TokenIssuanceError::MediaHashTooLong(max_len + 1, max_len))
+        );
+    }
+
+    #[test]
+    fn media_hash_boundary_lengths() {
+        let chain_config = common::chain::config::Builder::test_chain()
+            .token_min_hash_len(20)
+            .token_max_hash_len(64)
+            .build();
+
+        assert_eq!(
+            validate_media_hash(&chain_config, &[0; 19]),
+            Err(TokenIssuanceError::MediaHashTooShort(19, 20))
+        );
+        assert_eq!(validate_media_hash(&chain_config, &[0; 20]), Ok(()));
+        assert_eq!(validate_media_hash(&chain_config, &[0; 64]), Ok(()));
+        assert_eq!(
+            validate_media_hash(&chain_config, &[0; 65]),
+            Err(TokenIssuanceError::MediaHashTooLong(65, 64))
+        );
+    }
+}
@@ -13,23 +13,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use self::check_utils::check_media_hash;
-
 use super::transaction_verifier::error::TokensError;
 use common::{
     chain::{
         tokens::{NftIssuance, TokenData},
-        Block, ChainConfig, Transaction,
+        Block, ChainConfig, NftMetadataSizeLimit, TokenMaxSupplyLimit, Transaction,
     },
-    primitives::{Amount, Id, Idable},
+    primitives::{Amount, BlockHeight, Id, Idable},
 };
 use serialization::{DecodeAll, Encode};
-use tx_verifier::error::TokenIssuanceError;
+use tx_verifier::error::{TokenIssuanceError, UriError};
 use utils::ensure;
 
 mod check_utils;
-pub use check_utils::is_rfc3986_valid_symbol;
-use check_utils::{check_nft_description, check_nft_name, check_token_ticker, is_uri_valid};
+use check_utils::{check_nft_description, check_nft_name, check_token_ticker};
+pub use check_utils::{check_uri, is_rfc3986_valid_symbol, validate_media_hash};
 
 pub fn check_tokens_transfer_data(
     source_block_id: Id<Block>,
@@ -48,51 +46,51 @@ pub fn check_tokens_transfer_data(
 pub fn check_nft_issuance_data(
     chain_config: &ChainConfig,
     issuance: &NftIssuance,
+    block_height: BlockHeight,
 ) -> Result<(), TokenIssuanceError> {
+    // Check the total size first, so oversized payloads are rejected before anything else
+    match chain_config
+        .chainstate_upgrades()
+        .chainstate_upgrade_at_height(block_height)
+        .nft_metadata_size_limit()
+    {
+        NftMetadataSizeLimit::Yes => ensure!(
+            issuance.metadata.encoded_size() <= chain_config.token_max_nft_metadata_size(),
+            TokenIssuanceError::IssueErrorMetadataTooLarge(
+                issuance.metadata.encoded_size(),
+                chain_config.token_max_nft_metadata_size()
+            )
+        ),
+        NftMetadataSizeLimit::No => {}
+    }
+
     check_token_ticker(chain_config, &issuance.metadata.ticker)?;
     check_nft_name(chain_config, &issuance.metadata.name)?;
     check_nft_description(chain_config, &issuance.metadata.description)?;
 
     let icon_uri = Vec::<u8>::decode_all(&mut issuance.metadata.icon_uri.encode().as_slice())
-        .map_err(|_| TokenIssuanceError::IssueErrorIncorrectIconURI)?;
+        .map_err(|_| TokenIssuanceError::IssueErrorIncorrectIconURI(UriError::Undecodable))?;
     if !icon_uri.is_empty() {
-        ensure!(
-            icon_uri.len() <= chain_config.token_max_uri_len(),
-            TokenIssuanceError::IssueErrorIncorrectIconURI
-        );
-        ensure!(
-            is_uri_valid(&icon_uri),
-            TokenIssuanceError::IssueErrorIncorrectIconURI
-        );
+        check_uri(chain_config, &icon_uri)
+            .map_err(TokenIssuanceError::IssueErrorIncorrectIconURI)?;
     }
 
-    let additional_metadata_uri =
-        Vec::<u8>::decode_all(&mut issuance.metadata.additional_metadata_uri.encode().as_slice())
-            .map_err(|_| TokenIssuanceError::IssueErrorIncorrectMetadataURI)?;
+    let additional_metadata_uri = Vec::<u8>::decode_all(
+        &mut issuance.metadata.additional_metadata_uri.encode().as_slice(),
+    )
+    .map_err(|_| TokenIssuanceError::IssueErrorIncorrectMetadataURI(UriError::Undecodable))?;
     if !additional_metadata_uri.is_empty() {
-        ensure!(
-            additional_metadata_uri.len() <= chain_config.token_max_uri_len(),
-            TokenIssuanceError::IssueErrorIncorrectMetadataURI
-        );
-        ensure!(
-            is_uri_valid(&additional_metadata_uri),
-            TokenIssuanceError::IssueErrorIncorrectMetadataURI
-        );
+        check_uri(chain_config, &additional_metadata_uri)
+            .map_err(TokenIssuanceError::IssueErrorIncorrectMetadataURI)?;
     }
 
     let media_uri = Vec::<u8>::decode_all(&mut issuance.metadata.media_uri.encode().as_slice())
-        .map_err(|_| TokenIssuanceError::IssueErrorIncorrectMediaURI)?;
+        .map_err(|_| TokenIssuanceError::IssueErrorIncorrectMediaURI(UriError::Undecodable))?;
     if !media_uri.is_empty() {
-        ensure!(
-            media_uri.len() <= chain_config.token_max_uri_len(),
-            TokenIssuanceError::IssueErrorIncorrectMediaURI
-        );
-        ensure!(
-            is_uri_valid(&media_uri),
-            TokenIssuanceError::IssueErrorIncorrectMediaURI
-        );
+        check_uri(chain_config, &media_uri)
+            .map_err(TokenIssuanceError::IssueErrorIncorrectMediaURI)?;
     }
-    check_media_hash(chain_config, &issuance.metadata.media_hash)?;
+    validate_media_hash(chain_config, &issuance.metadata.media_hash)?;
     Ok(())
 }
 
@@ -102,6 +100,7 @@ pub fn check_tokens_issuance_data(
     amount_to_issue: &Amount,
     number_of_decimals: &u8,
     metadata_uri: &[u8],
+    block_height: BlockHeight,
 ) -> Result<(), TokenIssuanceError> {
     // Check token ticker
     check_token_ticker(chain_config, token_ticker)?;
@@ -113,19 +112,40 @@ pub fn check_tokens_issuance_data(
 
     // Check decimals
     if number_of_decimals > &chain_config.token_max_dec_count() {
-        return Err(TokenIssuanceError::IssueErrorTooManyDecimals);
+        return Err(TokenIssuanceError::IssueErrorTooManyDecimals(
+            *number_of_decimals,
+            chain_config.token_max_dec_count(),
+        ));
+    }
+
+    // Check supply. If the limit is not representable in atoms, any amount is within it.
+    match chain_config
+        .chainstate_upgrades()
+        .chainstate_upgrade_at_height(block_height)
+        .token_max_supply_limit()
+    {
+        TokenMaxSupplyLimit::Yes => {
+            let max_supply_atoms =
+                10u128.checked_pow(*number_of_decimals as u32).and_then(|atoms_per_unit| {
+                    chain_config.token_max_supply().checked_mul(atoms_per_unit)
+                });
+            if let Some(max_supply_atoms) = max_supply_atoms {
+                ensure!(
+                    amount_to_issue.into_atoms() <= max_supply_atoms,
+                    TokenIssuanceError::IssueAmountExceedsMaxSupply(
+                        *amount_to_issue,
+                        Amount::from_atoms(max_supply_atoms)
+                    )
+                );
+            }
+        }
+        TokenMaxSupplyLimit::No => {}
     }
 
     // Check URI
-    ensure!(
-        is_uri_valid(metadata_uri),
-        TokenIssuanceError::IssueErrorIncorrectMetadataURI
-    );
+    check_uri(chain_config, metadata_uri)
+        .map_err(TokenIssuanceError::IssueErrorIncorrectMetadataURI)?;
 
-    ensure!(
-        metadata_uri.len() <= chain_config.token_max_uri_len(),
-        TokenIssuanceError::IssueErrorIncorrectMetadataURI
-    );
     Ok(())
 }
 
@@ -134,20 +154,39 @@ pub fn check_tokens_data(
     token_data: &TokenData,
     tx: &Transaction,
     source_block_id: Id<Block>,
+    block_height: BlockHeight,
 ) -> Result<(), TokensError> {
     match token_data {
         TokenData::TokenTransfer(transfer) => {
             check_tokens_transfer_data(source_block_id, tx, &transfer.amount)
         }
-        TokenData::TokenIssuance(issuance) => check_tokens_issuance_data(
-            chain_config,
-            &issuance.token_ticker,
-            &issuance.amount_to_issue,
-            &issuance.number_of_decimals,
-            &issuance.metadata_uri,
-        )
-        .map_err(|err| TokensError::IssueError(err, tx.get_id(), source_block_id)),
-        TokenData::NftIssuance(issuance) => check_nft_issuance_data(chain_config, issuance)
-            .map_err(|err| TokensError::IssueError(err, tx.get_id(), source_block_id)),
+        TokenData::TokenIssuance(issuance) => {
+            ensure!(
+                chain_config.fungible_token_issuance_enabled(),
+                TokensError::FungibleTokenIssuanceDisabled(tx.get_id(), source_block_id)
+            );
+
+            check_tokens_issuance_data(
+                chain_config,
+                &issuance.token_ticker,
+                &issuance.amount_to_issue,
+                &issuance.number_of_decimals,
+                &issuance.metadata_uri,
+                block_height,
+            )
+            .map_err(|err| TokensError::IssueError(err, tx.get_id(), source_block_id))
+        }
+        TokenData::NftIssuance(issuance) => {
+            ensure!(
+                chain_config.nft_issuance_enabled(),
+                TokensError::NftIssuanceDisabled(tx.get_id(), source_block_id)
+            );
+
+            check_nft_issuance_data(chain_config, issuance, block_height)
+                .map_err(|err| TokensError::IssueError(err, tx.get_id(), source_block_id))
+        }
+        // The issuer's authorization and the current state of the token depend on the chain
+        // state, so they are checked by the transaction verifier
+        TokenData::TokenFreezeV1(_) | TokenData::TokenUnfreezeV1(_) => Ok(()),
     }
 }
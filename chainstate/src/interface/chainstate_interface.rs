@@ -16,7 +16,10 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use crate::{detail::BlockSource, ChainInfo, ChainstateConfig, ChainstateError, ChainstateEvent};
+use crate::{
+    detail::BlockSource, ChainInfo, ChainstateConfig, ChainstateError, ChainstateEvent,
+    ChainstateReadHandle,
+};
 use chainstate_types::{BlockIndex, EpochData, GenBlockIndex, Locator};
 use common::{
     chain::{
@@ -30,6 +33,7 @@ use common::{
     },
     primitives::{Amount, BlockHeight, Id},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{DelegationData, PoolData};
 use utils::eventhandler::EventHandler;
 use utxo::Utxo;
@@ -41,10 +45,23 @@ pub trait ChainstateInterface: Send + Sync {
         block: Block,
         source: BlockSource,
     ) -> Result<Option<BlockIndex>, ChainstateError>;
+    /// Process several blocks at once, committing them to the storage in a single transaction.
+    /// The blocks must be ordered so that each block's parent is either known or precedes it.
+    fn process_block_batch(
+        &mut self,
+        blocks: Vec<Block>,
+        source: BlockSource,
+    ) -> Result<Option<BlockIndex>, ChainstateError>;
     fn invalidate_block(&mut self, block_id: &Id<Block>) -> Result<(), ChainstateError>;
     fn reset_block_failure_flags(&mut self, block_id: &Id<Block>) -> Result<(), ChainstateError>;
     fn preliminary_block_check(&self, block: Block) -> Result<Block, ChainstateError>;
     fn preliminary_header_check(&self, header: SignedBlockHeader) -> Result<(), ChainstateError>;
+    /// Check the internal consistency of each of the headers; unlike `preliminary_header_check`,
+    /// this doesn't require the headers to be connected to the chain.
+    fn preliminary_header_check_batch(
+        &self,
+        headers: &[SignedBlockHeader],
+    ) -> Result<(), ChainstateError>;
     fn get_best_block_id(&self) -> Result<Id<GenBlock>, ChainstateError>;
     fn is_block_in_main_chain(&self, block_id: &Id<GenBlock>) -> Result<bool, ChainstateError>;
     fn get_min_height_with_allowed_reorg(&self) -> Result<BlockHeight, ChainstateError>;
@@ -52,6 +69,13 @@ pub trait ChainstateInterface: Send + Sync {
         &self,
         block_id: &Id<GenBlock>,
     ) -> Result<Option<BlockHeight>, ChainstateError>;
+
+    /// Same as `get_block_height_in_main_chain`, but for multiple blocks at once; the heights
+    /// are returned in the same order as the ids.
+    fn get_block_heights_in_main_chain(
+        &self,
+        block_ids: &[Id<GenBlock>],
+    ) -> Result<Vec<Option<BlockHeight>>, ChainstateError>;
     fn get_best_block_height(&self) -> Result<BlockHeight, ChainstateError>;
     fn get_best_block_header(&self) -> Result<SignedBlockHeader, ChainstateError>;
     fn get_block_id_from_height(
@@ -59,6 +83,11 @@ pub trait ChainstateInterface: Send + Sync {
         height: &BlockHeight,
     ) -> Result<Option<Id<GenBlock>>, ChainstateError>;
     fn get_block(&self, block_id: Id<Block>) -> Result<Option<Block>, ChainstateError>;
+    /// Returns up to `max_count` mainchain blocks starting from the height `from`.
+    ///
+    /// Note: if the node prunes old blocks (see `ChainstateConfig::prune_depth`), requesting
+    /// a pruned block results in `PropertyQueryError::BlockNotFound`. Clients that scan
+    /// the whole chain, like the wallet or the API server, need a node that doesn't prune.
     fn get_mainchain_blocks(
         &self,
         from: BlockHeight,
@@ -69,6 +98,23 @@ pub trait ChainstateInterface: Send + Sync {
         block_id: Id<Block>,
     ) -> Result<Option<SignedBlockHeader>, ChainstateError>;
 
+    /// Returns the headers of the mainchain blocks with heights in `[from, to]`, in ascending
+    /// height order. Fewer headers are returned if the tip is below `to`.
+    /// Fails if the range contains more than `max_count` heights.
+    fn get_mainchain_headers_in_range(
+        &self,
+        from: BlockHeight,
+        to: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<SignedBlockHeader>, ChainstateError>;
+
+    /// Returns the timestamps of up to `count` most recent mainchain blocks, starting from the
+    /// tip. Fewer timestamps are returned if the chain (including genesis) is shorter than that.
+    fn get_last_block_timestamps(
+        &self,
+        count: usize,
+    ) -> Result<Vec<BlockTimestamp>, ChainstateError>;
+
     /// Returns a list of block headers whose heights distances increase exponentially starting
     /// from the current tip.
     ///
@@ -106,12 +152,21 @@ pub trait ChainstateInterface: Send + Sync {
     ) -> Result<(Vec<SignedBlockHeader>, Vec<SignedBlockHeader>), ChainstateError>;
 
     fn get_block_index(&self, id: &Id<Block>) -> Result<Option<BlockIndex>, ChainstateError>;
+
+    /// Check whether the block is known, i.e. its index exists. This is cheaper than
+    /// `get_block_index` because the index is not loaded.
+    fn has_block(&self, id: &Id<Block>) -> Result<bool, ChainstateError>;
     fn get_gen_block_index(
         &self,
         id: &Id<GenBlock>,
     ) -> Result<Option<GenBlockIndex>, ChainstateError>;
     fn get_best_block_index(&self) -> Result<GenBlockIndex, ChainstateError>;
 
+    /// Returns a handle that reads the chainstate storage directly, bypassing this object.
+    ///
+    /// The handle can be used concurrently with block processing, e.g. from other threads.
+    fn read_handle(&self) -> ChainstateReadHandle;
+
     fn get_chain_config(&self) -> &Arc<ChainConfig>;
     fn get_chainstate_config(&self) -> ChainstateConfig;
     fn wait_for_all_events(&self);
@@ -129,6 +184,13 @@ pub trait ChainstateInterface: Send + Sync {
         &self,
         tx_id: &Id<Transaction>,
     ) -> Result<Option<SignedTransaction>, ChainstateError>;
+
+    /// Returns the id and height of the mainchain block containing the given transaction.
+    /// Requires the transaction index to be enabled.
+    fn get_transaction_block(
+        &self,
+        tx_id: &Id<Transaction>,
+    ) -> Result<Option<(Id<Block>, BlockHeight)>, ChainstateError>;
     fn is_already_an_orphan(&self, block_id: &Id<Block>) -> bool;
     fn orphans_count(&self) -> usize;
     fn get_ancestor(
@@ -162,6 +224,14 @@ pub trait ChainstateInterface: Send + Sync {
         &self,
         token_id: TokenId,
     ) -> Result<Option<RPCTokenInfo>, ChainstateError>;
+
+    /// Returns the infos of the tokens issued in the main chain ordered by issuance height,
+    /// skipping the first `offset` tokens and returning at most `limit` of them.
+    fn get_tokens_info_for_rpc_by_issuance(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<RPCTokenInfo>, ChainstateError>;
     fn get_token_aux_data(
         &self,
         token_id: TokenId,
@@ -194,6 +264,7 @@ pub trait ChainstateInterface: Send + Sync {
     /// The blocks in the stream can be used to resync the blockchain in another node.
     /// NOTE: `include_orphans` here means "include all blocks that are not on mainchain", rather than just
     /// "blocks without a parent".
+    /// Fails with `BootstrapError::BlocksPruned` if the node prunes old blocks.
     fn export_bootstrap_stream<'a>(
         &self,
         writer: std::io::BufWriter<Box<dyn std::io::Write + Send + 'a>>,
@@ -215,6 +286,13 @@ pub trait ChainstateInterface: Send + Sync {
     /// Get stake pool data. See [pos_accounting::PoSAccountingView::get_pool_data].
     fn get_stake_pool_data(&self, pool_id: PoolId) -> Result<Option<PoolData>, ChainstateError>;
 
+    /// Get the id of the stake pool that uses the given VRF public key.
+    /// See [pos_accounting::PoSAccountingView::get_pool_id_by_vrf_public_key].
+    fn get_stake_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, ChainstateError>;
+
     /// Get all delegation shares for given stake pool.
     /// See [pos_accounting::PoSAccountingView::get_pool_delegations_shares].
     fn get_stake_pool_delegations_shares(
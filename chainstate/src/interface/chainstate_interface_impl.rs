@@ -20,18 +20,22 @@ use crate::{
         self,
         block_checking::BlockChecker,
         block_invalidation::BlockInvalidator,
-        bootstrap::{export_bootstrap_stream, import_bootstrap_stream},
+        bootstrap::{export_bootstrap_stream, import_bootstrap_stream, BootstrapError},
         calculate_median_time_past,
         tx_verification_strategy::TransactionVerificationStrategy,
         BlockSource, OrphanBlocksRef,
     },
-    ChainInfo, ChainstateConfig, ChainstateError, ChainstateEvent, ChainstateInterface, Locator,
+    ChainInfo, ChainstateConfig, ChainstateError, ChainstateEvent, ChainstateInterface,
+    ChainstateReadHandle, Locator,
 };
 use chainstate_storage::BlockchainStorage;
 use chainstate_types::{BlockIndex, EpochData, GenBlockIndex, PropertyQueryError};
 use common::{
     chain::{
-        block::{signed_block_header::SignedBlockHeader, Block, BlockReward, GenBlock},
+        block::{
+            signed_block_header::SignedBlockHeader, timestamp::BlockTimestamp, Block, BlockReward,
+            GenBlock,
+        },
         config::ChainConfig,
         tokens::{RPCTokenInfo, TokenAuxiliaryData, TokenId},
         AccountNonce, AccountType, DelegationId, OutPointSourceId, PoolId, SignedTransaction,
@@ -39,6 +43,7 @@ use common::{
     },
     primitives::{id::WithId, Amount, BlockHeight, Id, Idable},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{DelegationData, PoSAccountingView, PoolData};
 use utils::eventhandler::EventHandler;
 use utxo::{Utxo, UtxosView};
@@ -55,8 +60,8 @@ impl<S, V> ChainstateInterfaceImpl<S, V> {
 
 impl<S, V> ChainstateInterface for ChainstateInterfaceImpl<S, V>
 where
-    S: BlockchainStorage + Sync,
-    V: TransactionVerificationStrategy + Sync,
+    S: BlockchainStorage + Clone + Sync + 'static,
+    V: TransactionVerificationStrategy + Sync + 'static,
 {
     fn subscribe_to_events(&mut self, handler: EventHandler<ChainstateEvent>) {
         self.chainstate.subscribe_to_events(handler)
@@ -72,6 +77,16 @@ where
             .map_err(ChainstateError::ProcessBlockError)
     }
 
+    fn process_block_batch(
+        &mut self,
+        blocks: Vec<Block>,
+        source: BlockSource,
+    ) -> Result<Option<BlockIndex>, ChainstateError> {
+        self.chainstate
+            .process_block_batch(blocks.into_iter().map(Into::into).collect(), source)
+            .map_err(ChainstateError::ProcessBlockError)
+    }
+
     fn invalidate_block(&mut self, block_id: &Id<Block>) -> Result<(), ChainstateError> {
         BlockInvalidator::new(&mut self.chainstate)
             .invalidate_block(block_id, detail::block_invalidation::IsExplicit::Yes)
@@ -90,6 +105,15 @@ where
             .map_err(ChainstateError::ProcessBlockError)
     }
 
+    fn preliminary_header_check_batch(
+        &self,
+        headers: &[SignedBlockHeader],
+    ) -> Result<(), ChainstateError> {
+        BlockChecker::new(&self.chainstate)
+            .preliminary_header_check_batch(headers)
+            .map_err(ChainstateError::ProcessBlockError)
+    }
+
     fn preliminary_block_check(&self, block: Block) -> Result<Block, ChainstateError> {
         let block = BlockChecker::new(&self.chainstate)
             .preliminary_block_check(block.into())
@@ -132,6 +156,17 @@ where
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
+    fn get_block_heights_in_main_chain(
+        &self,
+        block_ids: &[Id<GenBlock>],
+    ) -> Result<Vec<Option<BlockHeight>>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_block_heights_in_main_chain(block_ids)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
     fn get_block_id_from_height(
         &self,
         height: &BlockHeight,
@@ -143,6 +178,17 @@ where
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
+    fn get_last_block_timestamps(
+        &self,
+        count: usize,
+    ) -> Result<Vec<BlockTimestamp>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_last_block_timestamps(count)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
     fn get_block(&self, block_id: Id<Block>) -> Result<Option<Block>, ChainstateError> {
         self.chainstate
             .query()
@@ -174,14 +220,23 @@ where
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
-    fn get_locator(&self) -> Result<Locator, ChainstateError> {
+    fn get_mainchain_headers_in_range(
+        &self,
+        from: BlockHeight,
+        to: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<SignedBlockHeader>, ChainstateError> {
         self.chainstate
             .query()
             .map_err(ChainstateError::from)?
-            .get_locator()
+            .get_mainchain_headers_in_range(from, to, max_count)
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
+    fn get_locator(&self) -> Result<Locator, ChainstateError> {
+        self.chainstate.get_locator().map_err(ChainstateError::FailedToReadProperty)
+    }
+
     fn get_locator_from_height(&self, height: BlockHeight) -> Result<Locator, ChainstateError> {
         self.chainstate
             .query()
@@ -261,6 +316,10 @@ where
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
+    fn read_handle(&self) -> ChainstateReadHandle {
+        Arc::new(self.chainstate.make_reader())
+    }
+
     fn get_block_index(&self, block_id: &Id<Block>) -> Result<Option<BlockIndex>, ChainstateError> {
         self.chainstate
             .query()
@@ -269,6 +328,14 @@ where
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
+    fn has_block(&self, block_id: &Id<Block>) -> Result<bool, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .has_block(block_id)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
     fn get_gen_block_index(
         &self,
         id: &Id<GenBlock>,
@@ -402,6 +469,18 @@ where
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
+    fn get_tokens_info_for_rpc_by_issuance(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<RPCTokenInfo>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_tokens_info_for_rpc_by_issuance(offset, limit)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
     fn get_token_aux_data(
         &self,
         token_id: TokenId,
@@ -480,7 +559,8 @@ where
         // and the cost of cloning is small compared to the bootstrapping
         let chainstate_config = self.chainstate.chainstate_config().clone();
 
-        let mut block_processor = |block| self.chainstate.process_block(block, BlockSource::Local);
+        let mut block_processor =
+            |block, block_source| self.chainstate.process_block(block, block_source);
 
         import_bootstrap_stream(
             &magic_bytes,
@@ -497,6 +577,11 @@ where
         writer: std::io::BufWriter<Box<dyn std::io::Write + Send + 'a>>,
         include_orphans: bool,
     ) -> Result<(), ChainstateError> {
+        // The bodies of the pruned blocks are gone, so a complete stream can't be produced
+        if self.chainstate.chainstate_config().prune_depth.is_some() {
+            return Err(BootstrapError::BlocksPruned.into());
+        }
+
         let magic_bytes = self.chainstate.chain_config().magic_bytes();
         let mut writer = writer;
         export_bootstrap_stream(
@@ -547,6 +632,17 @@ where
             .map_err(|e| ChainstateError::ProcessBlockError(e.into()))
     }
 
+    fn get_stake_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, ChainstateError> {
+        self.chainstate
+            .make_db_tx_ro()
+            .map_err(|e| ChainstateError::FailedToReadProperty(e.into()))?
+            .get_pool_id_by_vrf_public_key(vrf_public_key)
+            .map_err(|e| ChainstateError::ProcessBlockError(e.into()))
+    }
+
     fn get_stake_pool_delegations_shares(
         &self,
         pool_id: PoolId,
@@ -640,6 +736,17 @@ where
             .get_transaction_in_block(*tx_id)
             .map_err(ChainstateError::from)
     }
+
+    fn get_transaction_block(
+        &self,
+        tx_id: &Id<Transaction>,
+    ) -> Result<Option<(Id<Block>, BlockHeight)>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_transaction_block(*tx_id)
+            .map_err(ChainstateError::from)
+    }
 }
 
 // TODO: remove this function. The value of an output cannot be generalized and exposed from ChainstateInterface in such way
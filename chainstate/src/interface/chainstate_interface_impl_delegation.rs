@@ -30,13 +30,14 @@ use common::{
     },
     primitives::{Amount, BlockHeight, Id},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{DelegationData, PoolData};
 use utils::eventhandler::EventHandler;
 use utxo::Utxo;
 
 use crate::{
     chainstate_interface::ChainstateInterface, BlockSource, ChainInfo, ChainstateConfig,
-    ChainstateError, ChainstateEvent,
+    ChainstateError, ChainstateEvent, ChainstateReadHandle,
 };
 
 impl<T: Deref + DerefMut + Send + Sync> ChainstateInterface for T
@@ -55,6 +56,14 @@ where
         self.deref_mut().process_block(block, source)
     }
 
+    fn process_block_batch(
+        &mut self,
+        blocks: Vec<Block>,
+        source: BlockSource,
+    ) -> Result<Option<BlockIndex>, ChainstateError> {
+        self.deref_mut().process_block_batch(blocks, source)
+    }
+
     fn invalidate_block(&mut self, block_id: &Id<Block>) -> Result<(), ChainstateError> {
         self.deref_mut().invalidate_block(block_id)
     }
@@ -71,6 +80,13 @@ where
         self.deref().preliminary_header_check(header)
     }
 
+    fn preliminary_header_check_batch(
+        &self,
+        headers: &[SignedBlockHeader],
+    ) -> Result<(), ChainstateError> {
+        self.deref().preliminary_header_check_batch(headers)
+    }
+
     fn get_best_block_id(&self) -> Result<Id<GenBlock>, ChainstateError> {
         self.deref().get_best_block_id()
     }
@@ -90,6 +106,13 @@ where
         self.deref().get_block_height_in_main_chain(block_id)
     }
 
+    fn get_block_heights_in_main_chain(
+        &self,
+        block_ids: &[Id<GenBlock>],
+    ) -> Result<Vec<Option<BlockHeight>>, ChainstateError> {
+        self.deref().get_block_heights_in_main_chain(block_ids)
+    }
+
     fn get_best_block_height(&self) -> Result<BlockHeight, ChainstateError> {
         self.deref().get_best_block_height()
     }
@@ -117,6 +140,22 @@ where
         self.deref().get_mainchain_blocks(from, max_count)
     }
 
+    fn get_mainchain_headers_in_range(
+        &self,
+        from: BlockHeight,
+        to: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<SignedBlockHeader>, ChainstateError> {
+        self.deref().get_mainchain_headers_in_range(from, to, max_count)
+    }
+
+    fn get_last_block_timestamps(
+        &self,
+        count: usize,
+    ) -> Result<Vec<BlockTimestamp>, ChainstateError> {
+        self.deref().get_last_block_timestamps(count)
+    }
+
     fn get_locator(&self) -> Result<Locator, ChainstateError> {
         self.deref().get_locator()
     }
@@ -153,6 +192,10 @@ where
         self.deref().get_block_index(id)
     }
 
+    fn has_block(&self, id: &Id<Block>) -> Result<bool, ChainstateError> {
+        self.deref().has_block(id)
+    }
+
     fn get_gen_block_index(
         &self,
         id: &Id<GenBlock>,
@@ -164,6 +207,10 @@ where
         self.deref().get_best_block_index()
     }
 
+    fn read_handle(&self) -> ChainstateReadHandle {
+        self.deref().read_handle()
+    }
+
     fn get_chain_config(&self) -> &Arc<ChainConfig> {
         self.deref().get_chain_config()
     }
@@ -244,6 +291,14 @@ where
         self.deref().get_token_info_for_rpc(token_id)
     }
 
+    fn get_tokens_info_for_rpc_by_issuance(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<RPCTokenInfo>, ChainstateError> {
+        self.deref().get_tokens_info_for_rpc_by_issuance(offset, limit)
+    }
+
     fn get_token_aux_data(
         &self,
         token_id: TokenId,
@@ -308,6 +363,13 @@ where
         self.deref().get_stake_pool_data(pool_id)
     }
 
+    fn get_stake_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, ChainstateError> {
+        self.deref().get_stake_pool_id_by_vrf_public_key(vrf_public_key)
+    }
+
     fn get_stake_pool_delegations_shares(
         &self,
         pool_id: PoolId,
@@ -365,6 +427,13 @@ where
     ) -> Result<Option<SignedTransaction>, ChainstateError> {
         self.deref().get_transaction(tx_id)
     }
+
+    fn get_transaction_block(
+        &self,
+        tx_id: &Id<Transaction>,
+    ) -> Result<Option<(Id<Block>, BlockHeight)>, ChainstateError> {
+        self.deref().get_transaction_block(tx_id)
+    }
 }
 
 #[cfg(test)]
@@ -416,6 +485,10 @@ mod tests {
                 min_max_bootstrap_import_buffer_sizes: Default::default(),
                 tx_index_enabled: Default::default(),
                 max_tip_age: Default::default(),
+                commit_batch_size: Default::default(),
+                prune_depth: Default::default(),
+                reindex_tx_index: Default::default(),
+                max_blocks_pruned_at_once: Default::default(),
             };
             let chainstate_storage = Store::new_empty().unwrap();
 
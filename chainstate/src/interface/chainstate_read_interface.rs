@@ -0,0 +1,149 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{chainstate_interface::ChainstateInterface, ChainstateError};
+use chainstate_types::{BlockIndex, GenBlockIndex, Locator};
+use common::{
+    chain::block::{signed_block_header::SignedBlockHeader, Block, GenBlock},
+    primitives::{BlockHeight, Id, Idable},
+};
+
+/// Source of read-only views of the chainstate that can be used from other threads while
+/// blocks are being processed, see `ChainstateInterface::read_handle`.
+pub trait ChainstateSnapshotSource: Send + Sync {
+    /// Open a view of the current state of the chainstate storage.
+    ///
+    /// All the queries made through the returned object observe the same state, so an operation
+    /// consisting of several queries should use a single snapshot. A snapshot should not be kept
+    /// for long, because the storage may have to retain the old data until it's dropped.
+    fn snapshot(&self) -> Result<Box<dyn ChainstateReadInterface + '_>, ChainstateError>;
+}
+
+/// Read-only subset of the chainstate interface.
+///
+/// It's implemented by `ChainstateInterface` itself and by the storage snapshots obtained
+/// from a `ChainstateSnapshotSource`.
+pub trait ChainstateReadInterface {
+    fn get_best_block_id(&self) -> Result<Id<GenBlock>, ChainstateError>;
+    fn is_block_in_main_chain(&self, block_id: &Id<GenBlock>) -> Result<bool, ChainstateError>;
+    fn get_block_height_in_main_chain(
+        &self,
+        block_id: &Id<GenBlock>,
+    ) -> Result<Option<BlockHeight>, ChainstateError>;
+
+    /// See `ChainstateInterface::get_block_heights_in_main_chain`.
+    fn get_block_heights_in_main_chain(
+        &self,
+        block_ids: &[Id<GenBlock>],
+    ) -> Result<Vec<Option<BlockHeight>>, ChainstateError>;
+    fn get_block_id_from_height(
+        &self,
+        height: &BlockHeight,
+    ) -> Result<Option<Id<GenBlock>>, ChainstateError>;
+    fn get_block(&self, block_id: Id<Block>) -> Result<Option<Block>, ChainstateError>;
+    fn get_block_index(&self, id: &Id<Block>) -> Result<Option<BlockIndex>, ChainstateError>;
+    fn get_gen_block_index(
+        &self,
+        id: &Id<GenBlock>,
+    ) -> Result<Option<GenBlockIndex>, ChainstateError>;
+
+    /// See `ChainstateInterface::get_mainchain_headers_by_locator`.
+    fn get_mainchain_headers_by_locator(
+        &self,
+        locator: &Locator,
+        header_count_limit: usize,
+    ) -> Result<Vec<SignedBlockHeader>, ChainstateError>;
+
+    /// See `ChainstateInterface::split_off_leading_known_headers`.
+    fn split_off_leading_known_headers(
+        &self,
+        headers: Vec<SignedBlockHeader>,
+    ) -> Result<(Vec<SignedBlockHeader>, Vec<SignedBlockHeader>), ChainstateError> {
+        let mut headers = headers;
+        let mut first_non_existing_block_idx = 0;
+        for header in headers.iter() {
+            if self.get_block_index(&header.get_id())?.is_none() {
+                break;
+            }
+            first_non_existing_block_idx += 1;
+        }
+
+        let non_existing_block_headers = headers.split_off(first_non_existing_block_idx);
+        Ok((headers, non_existing_block_headers))
+    }
+}
+
+/// Allows code that only needs to read the chainstate to be used both with
+/// `ChainstateInterface` and with a read handle.
+impl<'a> ChainstateReadInterface for dyn ChainstateInterface + 'a {
+    fn get_best_block_id(&self) -> Result<Id<GenBlock>, ChainstateError> {
+        ChainstateInterface::get_best_block_id(self)
+    }
+
+    fn is_block_in_main_chain(&self, block_id: &Id<GenBlock>) -> Result<bool, ChainstateError> {
+        ChainstateInterface::is_block_in_main_chain(self, block_id)
+    }
+
+    fn get_block_height_in_main_chain(
+        &self,
+        block_id: &Id<GenBlock>,
+    ) -> Result<Option<BlockHeight>, ChainstateError> {
+        ChainstateInterface::get_block_height_in_main_chain(self, block_id)
+    }
+
+    fn get_block_heights_in_main_chain(
+        &self,
+        block_ids: &[Id<GenBlock>],
+    ) -> Result<Vec<Option<BlockHeight>>, ChainstateError> {
+        ChainstateInterface::get_block_heights_in_main_chain(self, block_ids)
+    }
+
+    fn get_block_id_from_height(
+        &self,
+        height: &BlockHeight,
+    ) -> Result<Option<Id<GenBlock>>, ChainstateError> {
+        ChainstateInterface::get_block_id_from_height(self, height)
+    }
+
+    fn get_block(&self, block_id: Id<Block>) -> Result<Option<Block>, ChainstateError> {
+        ChainstateInterface::get_block(self, block_id)
+    }
+
+    fn get_block_index(&self, id: &Id<Block>) -> Result<Option<BlockIndex>, ChainstateError> {
+        ChainstateInterface::get_block_index(self, id)
+    }
+
+    fn get_gen_block_index(
+        &self,
+        id: &Id<GenBlock>,
+    ) -> Result<Option<GenBlockIndex>, ChainstateError> {
+        ChainstateInterface::get_gen_block_index(self, id)
+    }
+
+    fn get_mainchain_headers_by_locator(
+        &self,
+        locator: &Locator,
+        header_count_limit: usize,
+    ) -> Result<Vec<SignedBlockHeader>, ChainstateError> {
+        ChainstateInterface::get_mainchain_headers_by_locator(self, locator, header_count_limit)
+    }
+
+    fn split_off_leading_known_headers(
+        &self,
+        headers: Vec<SignedBlockHeader>,
+    ) -> Result<(Vec<SignedBlockHeader>, Vec<SignedBlockHeader>), ChainstateError> {
+        ChainstateInterface::split_off_leading_known_headers(self, headers)
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    chainstate_read_interface::{ChainstateReadInterface, ChainstateSnapshotSource},
+    detail::{
+        reader::{ChainstateReader, ChainstateSnapshot},
+        tx_verification_strategy::TransactionVerificationStrategy,
+    },
+    ChainstateError, Locator,
+};
+use chainstate_storage::BlockchainStorage;
+use chainstate_types::{BlockIndex, GenBlockIndex};
+use common::{
+    chain::block::{signed_block_header::SignedBlockHeader, Block, GenBlock},
+    primitives::{BlockHeight, Id},
+};
+
+impl<S, V> ChainstateSnapshotSource for ChainstateReader<S, V>
+where
+    S: BlockchainStorage + Sync,
+    V: TransactionVerificationStrategy + Sync,
+{
+    fn snapshot(&self) -> Result<Box<dyn ChainstateReadInterface + '_>, ChainstateError> {
+        let snapshot = ChainstateReader::snapshot(self).map_err(ChainstateError::from)?;
+        Ok(Box::new(snapshot))
+    }
+}
+
+impl<'a, S, V> ChainstateReadInterface for ChainstateSnapshot<'a, S, V>
+where
+    S: BlockchainStorage + 'a,
+    V: TransactionVerificationStrategy,
+{
+    fn get_best_block_id(&self) -> Result<Id<GenBlock>, ChainstateError> {
+        self.query().get_best_block_id().map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    fn is_block_in_main_chain(&self, block_id: &Id<GenBlock>) -> Result<bool, ChainstateError> {
+        self.query()
+            .is_block_in_main_chain(block_id)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    fn get_block_height_in_main_chain(
+        &self,
+        block_id: &Id<GenBlock>,
+    ) -> Result<Option<BlockHeight>, ChainstateError> {
+        self.query()
+            .get_block_height_in_main_chain(block_id)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    fn get_block_heights_in_main_chain(
+        &self,
+        block_ids: &[Id<GenBlock>],
+    ) -> Result<Vec<Option<BlockHeight>>, ChainstateError> {
+        self.query()
+            .get_block_heights_in_main_chain(block_ids)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    fn get_block_id_from_height(
+        &self,
+        height: &BlockHeight,
+    ) -> Result<Option<Id<GenBlock>>, ChainstateError> {
+        self.query()
+            .get_block_id_from_height(height)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    fn get_block(&self, block_id: Id<Block>) -> Result<Option<Block>, ChainstateError> {
+        self.query().get_block(block_id).map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    fn get_block_index(&self, block_id: &Id<Block>) -> Result<Option<BlockIndex>, ChainstateError> {
+        self.query()
+            .get_block_index(block_id)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    fn get_gen_block_index(
+        &self,
+        id: &Id<GenBlock>,
+    ) -> Result<Option<GenBlockIndex>, ChainstateError> {
+        self.query()
+            .get_gen_block_index(id)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    fn get_mainchain_headers_by_locator(
+        &self,
+        locator: &Locator,
+        header_count_limit: usize,
+    ) -> Result<Vec<SignedBlockHeader>, ChainstateError> {
+        self.query()
+            .get_mainchain_headers_by_locator(locator, header_count_limit)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+}
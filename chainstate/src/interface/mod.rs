@@ -16,3 +16,5 @@
 pub mod chainstate_interface;
 pub mod chainstate_interface_impl;
 pub mod chainstate_interface_impl_delegation;
+pub mod chainstate_read_interface;
+pub mod chainstate_read_interface_impl;
@@ -28,24 +28,27 @@ use common::{
     primitives::{BlockHeight, Id},
     time_getter::TimeGetter,
 };
-use detail::{bootstrap::BootstrapError, Chainstate};
+use detail::Chainstate;
 use interface::chainstate_interface_impl;
 use tx_verifier::transaction_verifier::storage::HasTxIndexDisabledError;
 
 pub use crate::{
     config::{ChainstateConfig, MaxTipAge},
     detail::{
-        ban_score, block_invalidation::BlockInvalidatorError, calculate_median_time_past,
-        check_nft_issuance_data, check_tokens_issuance_data, is_rfc3986_valid_symbol, BlockError,
-        BlockSource, ChainInfo, CheckBlockError, CheckBlockTransactionsError,
-        ConnectTransactionError, IOPolicyError, InitializationError, Locator, OrphanCheckError,
-        SpendStakeError, StorageCompatibilityCheckError, TokenIssuanceError, TokensError,
-        TransactionVerifierStorageError, TxIndexError,
+        ban_score, block_invalidation::BlockInvalidatorError, bootstrap::BootstrapError,
+        calculate_median_time_past, check_nft_issuance_data, check_tokens_issuance_data, check_uri,
+        is_rfc3986_valid_symbol, validate_media_hash, BlockError, BlockSizeError, BlockSource,
+        ChainInfo, CheckBlockError, CheckBlockTransactionsError, ConnectTransactionError,
+        IOPolicyError, InitializationError, Locator, OrphanCheckError, SpendStakeError,
+        StorageCompatibilityCheckError, TokenIssuanceError, TokensError,
+        TransactionVerifierStorageError, TxIndexError, UriError,
     },
 };
 pub use chainstate_types::{BlockIndex, GenBlockIndex, PropertyQueryError};
 pub use detail::tx_verification_strategy::*;
-pub use interface::{chainstate_interface, chainstate_interface_impl_delegation};
+pub use interface::{
+    chainstate_interface, chainstate_interface_impl_delegation, chainstate_read_interface,
+};
 pub use tx_verifier;
 
 #[derive(Debug, Clone)]
@@ -77,6 +80,8 @@ pub type ChainstateSubsystem = Box<dyn ChainstateInterface>;
 
 pub type ChainstateHandle = subsystem::Handle<dyn ChainstateInterface>;
 
+pub type ChainstateReadHandle = Arc<dyn chainstate_read_interface::ChainstateSnapshotSource>;
+
 pub fn make_chainstate<S, V>(
     chain_config: Arc<ChainConfig>,
     chainstate_config: ChainstateConfig,
@@ -86,7 +91,7 @@ pub fn make_chainstate<S, V>(
     time_getter: TimeGetter,
 ) -> Result<ChainstateSubsystem, ChainstateError>
 where
-    S: chainstate_storage::BlockchainStorage + Sync + 'static,
+    S: chainstate_storage::BlockchainStorage + Clone + Sync + 'static,
     V: TransactionVerificationStrategy + Sync + 'static,
 {
     let chainstate = Chainstate::new(
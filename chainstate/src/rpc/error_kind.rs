@@ -0,0 +1,94 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Classification of chainstate errors for reporting them to RPC clients
+
+use chainstate_types::{GetAncestorError, PropertyQueryError};
+use rpc::{ClassifyError, ErrorKind};
+
+use crate::{
+    ban_score::BanScore, BlockError, BlockInvalidatorError, BootstrapError, ChainstateError,
+};
+
+impl ClassifyError for ChainstateError {
+    fn error_kind(&self) -> ErrorKind {
+        match self {
+            ChainstateError::FailedToInitializeChainstate(_) => ErrorKind::Internal,
+            ChainstateError::ProcessBlockError(err) => block_error_kind(err),
+            ChainstateError::FailedToReadProperty(err) => property_query_error_kind(err),
+            ChainstateError::BootstrapError(err) => match err {
+                BootstrapError::File(_)
+                | BootstrapError::Deserialization(_)
+                | BootstrapError::BlocksPruned => ErrorKind::BadRequest,
+                BootstrapError::BlockProcessing(err) => block_error_kind(err),
+                BootstrapError::FailedToReadProperty(err) => property_query_error_kind(err),
+            },
+            ChainstateError::BlockInvalidatorError(err) => match err {
+                BlockInvalidatorError::BlockTooDeepToInvalidate(_) => ErrorKind::BadRequest,
+                BlockInvalidatorError::BlockIndicesForBranchQueryError(err)
+                | BlockInvalidatorError::IsBlockInMainChainQueryError(_, err)
+                | BlockInvalidatorError::MinHeightForReorgQueryError(err)
+                | BlockInvalidatorError::BestBlockIndexQueryError(err)
+                | BlockInvalidatorError::BlockIndexQueryError(_, err) => {
+                    property_query_error_kind(err)
+                }
+                BlockInvalidatorError::StorageError(_)
+                | BlockInvalidatorError::BestChainCandidatesError(_)
+                | BlockInvalidatorError::BlocksDisconnectionError { .. }
+                | BlockInvalidatorError::BlockStatusUpdateError(_, _)
+                | BlockInvalidatorError::GenericReorgError(_)
+                | BlockInvalidatorError::DbCommitError(_, _, _) => ErrorKind::Internal,
+            },
+        }
+    }
+}
+
+/// Blocks that would get a peer banned are invalid, so submitting them is a bad request
+fn block_error_kind(err: &BlockError) -> ErrorKind {
+    if err.ban_score() > 0 {
+        ErrorKind::BadRequest
+    } else {
+        ErrorKind::Internal
+    }
+}
+
+fn property_query_error_kind(err: &PropertyQueryError) -> ErrorKind {
+    match err {
+        PropertyQueryError::BlockNotFound(_)
+        | PropertyQueryError::EpochDataNotFound(_)
+        | PropertyQueryError::BlockIndexNotFound(_)
+        | PropertyQueryError::PrevBlockIndexNotFound(_)
+        | PropertyQueryError::BlockForHeightNotFound(_)
+        | PropertyQueryError::StakePoolDataNotFound(_)
+        | PropertyQueryError::PoolBalanceNotFound(_) => ErrorKind::NotFound,
+        PropertyQueryError::GenesisHeaderRequested
+        | PropertyQueryError::InvalidStartingBlockHeightForMainchainBlocks(_)
+        | PropertyQueryError::TransactionIndexDisabled
+        | PropertyQueryError::InvalidBlockHeightRange(_, _)
+        | PropertyQueryError::BlockHeightRangeTooLong(_, _, _)
+        | PropertyQueryError::BlockNoLongerInMainchain(_, _)
+        | PropertyQueryError::ZeroMaxBlockCount => ErrorKind::BadRequest,
+        PropertyQueryError::GetAncestorError(err) => match err {
+            GetAncestorError::InvalidAncestorHeight { .. } => ErrorKind::BadRequest,
+            GetAncestorError::PrevBlockIndexNotFound(_)
+            | GetAncestorError::StartingPointNotFound(_) => ErrorKind::NotFound,
+        },
+        PropertyQueryError::StorageError(_)
+        | PropertyQueryError::BestBlockNotFound
+        | PropertyQueryError::BestBlockIndexNotFound
+        | PropertyQueryError::StakePoolDataReadError(_)
+        | PropertyQueryError::PoolBalanceReadError(_) => ErrorKind::Internal,
+    }
+}
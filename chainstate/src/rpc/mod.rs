@@ -15,6 +15,7 @@
 
 //! Chainstate subsystem RPC handler
 
+mod error_kind;
 mod types;
 
 use std::{
@@ -24,19 +25,38 @@ use std::{
 };
 
 use crate::{Block, BlockSource, ChainInfo, GenBlock};
-use chainstate_types::BlockIndex;
+use chainstate_types::{BlockIndex, PropertyQueryError};
 use common::{
     address::dehexify::dehexify_all_addresses,
     chain::{
+        block::{signed_block_header::SignedBlockHeader, BlockReward},
         tokens::{RPCTokenInfo, TokenId},
-        ChainConfig, DelegationId, PoolId, SignedTransaction, Transaction,
+        ChainConfig, DelegationId, PoolId, SignedTransaction, Transaction, TxOutput, UtxoOutPoint,
     },
-    primitives::{Amount, BlockHeight, Id},
+    primitives::{Amount, BlockHeight, Id, Idable},
 };
 use rpc::Result as RpcResult;
-use serialization::{hex_encoded::HexEncoded, json_encoded::JsonEncoded};
+use serialization::{hex_encoded::HexEncoded, json_encoded::JsonEncoded, Encode};
 
-use self::types::{block::RpcBlock, signed_transaction::RpcSignedTransaction};
+use self::types::{
+    block::RpcBlock,
+    block_export::{RpcBlockExportPage, RpcBlockExportToken},
+    signed_transaction::RpcSignedTransaction,
+    transaction_info::RpcTransactionInfo,
+};
+
+/// The maximum number of token infos returned by a single `tokens_info_by_issuance` request
+const MAX_TOKENS_INFO_PAGE_SIZE: usize = 100;
+
+/// The maximum number of heights in a single `headers_range` request
+const MAX_HEADERS_RANGE_LEN: usize = 2000;
+
+/// The maximum number of blocks in a single `export_blocks` page
+const MAX_BLOCK_EXPORT_PAGE_LEN: usize = 100;
+
+/// The total encoded size of blocks in an `export_blocks` page is kept below this,
+/// unless the page consists of a single block, so the response stays reasonably small
+const MAX_BLOCK_EXPORT_PAGE_BYTES: usize = 4 * 1024 * 1024;
 
 #[rpc::rpc(server, client, namespace = "chainstate")]
 trait ChainstateRpc {
@@ -56,20 +76,42 @@ trait ChainstateRpc {
     #[method(name = "get_block_json")]
     async fn get_block_json(&self, id: Id<Block>) -> RpcResult<Option<String>>;
 
-    /// returns a hex-encoded transaction, assuming it's in the mainchain.
+    /// Returns the hex-encoded reward outputs of the block with the given id.
+    #[method(name = "get_block_reward")]
+    async fn get_block_reward(&self, id: Id<Block>)
+        -> RpcResult<Option<Vec<HexEncoded<TxOutput>>>>;
+
+    /// Returns the hex-encoded output of an unspent transaction output in the mainchain.
+    /// The outpoint is hex-encoded as well.
+    #[method(name = "get_utxo")]
+    async fn get_utxo(
+        &self,
+        outpoint: HexEncoded<UtxoOutPoint>,
+    ) -> RpcResult<Option<HexEncoded<TxOutput>>>;
+
+    /// Returns a hex-encoded transaction, assuming it's in the mainchain, along with the id
+    /// of the block containing it and its index in that block.
     /// Note: The transaction index must be enabled in the node.
     #[method(name = "get_transaction")]
-    async fn get_transaction(
-        &self,
-        id: Id<Transaction>,
-    ) -> RpcResult<Option<HexEncoded<SignedTransaction>>>;
+    async fn get_transaction(&self, id: Id<Transaction>) -> RpcResult<Option<RpcTransactionInfo>>;
 
     /// returns a json-encoded transaction, assuming it's in the mainchain.
     /// Note: The transaction index must be enabled in the node.
     #[method(name = "get_transaction_json")]
     async fn get_transaction_json(&self, id: Id<Transaction>) -> RpcResult<Option<String>>;
 
+    /// Returns the id and height of the mainchain block containing the given transaction.
+    /// Note: The transaction index must be enabled in the node.
+    #[method(name = "get_transaction_block")]
+    async fn get_transaction_block(
+        &self,
+        id: Id<Transaction>,
+    ) -> RpcResult<Option<(Id<Block>, BlockHeight)>>;
+
     /// Returns a hex-encoded serialized blocks from the mainchain starting from a given block height.
+    ///
+    /// Fails if some of the blocks have been pruned by the node, so wallets and other clients
+    /// that scan the whole chain can't be used with a pruning node.
     #[method(name = "get_mainchain_blocks")]
     async fn get_mainchain_blocks(
         &self,
@@ -77,6 +119,16 @@ trait ChainstateRpc {
         max_count: usize,
     ) -> RpcResult<Vec<HexEncoded<Block>>>;
 
+    /// Returns the hex-encoded headers of the mainchain blocks with heights in `[from, to]`,
+    /// in ascending height order. Fewer headers are returned if the tip is below `to`.
+    /// The range may contain at most 2000 heights.
+    #[method(name = "headers_range")]
+    async fn headers_range(
+        &self,
+        from: BlockHeight,
+        to: BlockHeight,
+    ) -> RpcResult<Vec<HexEncoded<SignedBlockHeader>>>;
+
     /// Submit a block to be included in the chain
     #[method(name = "submit_block")]
     async fn submit_block(&self, block_hex: HexEncoded<Block>) -> RpcResult<()>;
@@ -109,6 +161,15 @@ trait ChainstateRpc {
         second_block: Id<GenBlock>,
     ) -> RpcResult<Option<(Id<GenBlock>, BlockHeight)>>;
 
+    /// Returns last common block id and height of the main chain blocks at the given heights.
+    /// Returns an error if either height is above the current tip.
+    #[method(name = "last_common_ancestor_by_height")]
+    async fn last_common_ancestor_by_height(
+        &self,
+        first_height: BlockHeight,
+        second_height: BlockHeight,
+    ) -> RpcResult<Option<(Id<GenBlock>, BlockHeight)>>;
+
     #[method(name = "stake_pool_balance")]
     async fn stake_pool_balance(&self, pool_id: PoolId) -> RpcResult<Option<Amount>>;
 
@@ -126,6 +187,16 @@ trait ChainstateRpc {
     #[method(name = "token_info")]
     async fn token_info(&self, token_id: TokenId) -> RpcResult<Option<RPCTokenInfo>>;
 
+    /// Get information about the tokens issued in the main chain, ordered by issuance height.
+    /// Skips the first `offset` tokens and returns at most `limit` of them, the limit is capped
+    /// at 100. Each token info includes the id of its issuance transaction.
+    #[method(name = "tokens_info_by_issuance")]
+    async fn tokens_info_by_issuance(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> RpcResult<Vec<RPCTokenInfo>>;
+
     /// Write blocks to disk
     #[method(name = "export_bootstrap_file")]
     async fn export_bootstrap_file(
@@ -138,6 +209,21 @@ trait ChainstateRpc {
     #[method(name = "import_bootstrap_file")]
     async fn import_bootstrap_file(&self, file_path: &std::path::Path) -> RpcResult<()>;
 
+    /// Returns a page of hex-encoded mainchain blocks with heights in `[from, to]`,
+    /// in ascending height order, along with the token to request the next page with.
+    ///
+    /// A page contains at most `max_count` blocks (capped at 100) and is also limited
+    /// in its total size. If `continuation` is given, the export resumes right after
+    /// the last block of the previous page and `from` is ignored.
+    #[method(name = "export_blocks")]
+    async fn export_blocks(
+        &self,
+        from: BlockHeight,
+        to: BlockHeight,
+        max_count: usize,
+        continuation: Option<RpcBlockExportToken>,
+    ) -> RpcResult<RpcBlockExportPage>;
+
     /// Return information about the chain.
     #[method(name = "info")]
     async fn info(&self) -> RpcResult<ChainInfo>;
@@ -146,21 +232,23 @@ trait ChainstateRpc {
 #[async_trait::async_trait]
 impl ChainstateRpcServer for super::ChainstateHandle {
     async fn best_block_id(&self) -> RpcResult<Id<GenBlock>> {
-        rpc::handle_result(self.call(|this| this.get_best_block_id()).await)
+        rpc::handle_classified_result(self.call(|this| this.get_best_block_id()).await)
     }
 
     async fn block_id_at_height(&self, height: BlockHeight) -> RpcResult<Option<Id<GenBlock>>> {
-        rpc::handle_result(self.call(move |this| this.get_block_id_from_height(&height)).await)
+        rpc::handle_classified_result(
+            self.call(move |this| this.get_block_id_from_height(&height)).await,
+        )
     }
 
     async fn get_block(&self, id: Id<Block>) -> RpcResult<Option<HexEncoded<Block>>> {
         let block: Option<Block> =
-            rpc::handle_result(self.call(move |this| this.get_block(id)).await)?;
+            rpc::handle_classified_result(self.call(move |this| this.get_block(id)).await)?;
         Ok(block.map(HexEncoded::new))
     }
 
     async fn get_block_json(&self, id: Id<Block>) -> RpcResult<Option<String>> {
-        let both: Option<(Block, BlockIndex)> = rpc::handle_result(
+        let both: Option<(Block, BlockIndex)> = rpc::handle_classified_result(
             self.call(move |this| {
                 let block = this.get_block(id);
                 let block_index = this.get_block_index(&id);
@@ -188,18 +276,72 @@ impl ChainstateRpcServer for super::ChainstateHandle {
         Ok(result)
     }
 
-    async fn get_transaction(
+    async fn get_block_reward(
+        &self,
+        id: Id<Block>,
+    ) -> RpcResult<Option<Vec<HexEncoded<TxOutput>>>> {
+        let reward: Option<BlockReward> = rpc::handle_classified_result(
+            self.call(move |this| match this.get_block_index(&id)? {
+                Some(block_index) => this.get_block_reward(&block_index),
+                None => Ok(None),
+            })
+            .await,
+        )?;
+        Ok(reward.map(|reward| reward.outputs().iter().cloned().map(HexEncoded::new).collect()))
+    }
+
+    async fn get_utxo(
+        &self,
+        outpoint: HexEncoded<UtxoOutPoint>,
+    ) -> RpcResult<Option<HexEncoded<TxOutput>>> {
+        let outpoint = outpoint.take();
+        let output: Option<TxOutput> = rpc::handle_classified_result(
+            self.call(move |this| {
+                this.utxo(&outpoint).map(|utxo| utxo.map(|utxo| utxo.take_output()))
+            })
+            .await,
+        )?;
+        Ok(output.map(HexEncoded::new))
+    }
+
+    async fn get_transaction(&self, id: Id<Transaction>) -> RpcResult<Option<RpcTransactionInfo>> {
+        rpc::handle_classified_result(
+            self.call(move |this| {
+                let block_id = match this.get_transaction_block(&id)? {
+                    Some((block_id, _)) => block_id,
+                    None => return Ok(None),
+                };
+                // The block may be missing if it has been pruned
+                let block = match this.get_block(block_id)? {
+                    Some(block) => block,
+                    None => return Ok(None),
+                };
+                let info =
+                    block.transactions().iter().position(|tx| tx.transaction().get_id() == id).map(
+                        |index| {
+                            RpcTransactionInfo::new(
+                                block.transactions()[index].clone(),
+                                block_id,
+                                index as u32,
+                            )
+                        },
+                    );
+                Ok::<_, crate::ChainstateError>(info)
+            })
+            .await,
+        )
+    }
+
+    async fn get_transaction_block(
         &self,
         id: Id<Transaction>,
-    ) -> RpcResult<Option<HexEncoded<SignedTransaction>>> {
-        let tx: Option<SignedTransaction> =
-            rpc::handle_result(self.call(move |this| this.get_transaction(&id)).await)?;
-        Ok(tx.map(HexEncoded::new))
+    ) -> RpcResult<Option<(Id<Block>, BlockHeight)>> {
+        rpc::handle_classified_result(self.call(move |this| this.get_transaction_block(&id)).await)
     }
 
     async fn get_transaction_json(&self, id: Id<Transaction>) -> RpcResult<Option<String>> {
         let tx: Option<SignedTransaction> =
-            rpc::handle_result(self.call(move |this| this.get_transaction(&id)).await)?;
+            rpc::handle_classified_result(self.call(move |this| this.get_transaction(&id)).await)?;
         let rpc_tx = tx.map(RpcSignedTransaction::new);
 
         let chain_config: Arc<ChainConfig> = rpc::handle_result(
@@ -222,40 +364,56 @@ impl ChainstateRpcServer for super::ChainstateHandle {
         from: BlockHeight,
         max_count: usize,
     ) -> RpcResult<Vec<HexEncoded<Block>>> {
-        let blocks: Vec<Block> = rpc::handle_result(
+        let blocks: Vec<Block> = rpc::handle_classified_result(
             self.call(move |this| this.get_mainchain_blocks(from, max_count)).await,
         )?;
         Ok(blocks.into_iter().map(HexEncoded::new).collect())
     }
 
+    async fn headers_range(
+        &self,
+        from: BlockHeight,
+        to: BlockHeight,
+    ) -> RpcResult<Vec<HexEncoded<SignedBlockHeader>>> {
+        let headers: Vec<SignedBlockHeader> = rpc::handle_classified_result(
+            self.call(move |this| {
+                this.get_mainchain_headers_in_range(from, to, MAX_HEADERS_RANGE_LEN)
+            })
+            .await,
+        )?;
+        Ok(headers.into_iter().map(HexEncoded::new).collect())
+    }
+
     async fn submit_block(&self, block: HexEncoded<Block>) -> RpcResult<()> {
         let res = self
             .call_mut(move |this| this.process_block(block.take(), BlockSource::Local))
             .await;
         // remove the block index from the return value
         let res = res.map(|v| v.map(|_bi| ()));
-        rpc::handle_result(res)
+        rpc::handle_classified_result(res)
     }
 
     async fn invalidate_block(&self, id: Id<Block>) -> RpcResult<()> {
-        rpc::handle_result(self.call_mut(move |this| this.invalidate_block(&id)).await)
+        rpc::handle_classified_result(self.call_mut(move |this| this.invalidate_block(&id)).await)
     }
 
     async fn reset_block_failure_flags(&self, id: Id<Block>) -> RpcResult<()> {
-        rpc::handle_result(self.call_mut(move |this| this.reset_block_failure_flags(&id)).await)
+        rpc::handle_classified_result(
+            self.call_mut(move |this| this.reset_block_failure_flags(&id)).await,
+        )
     }
 
     async fn block_height_in_main_chain(
         &self,
         block_id: Id<GenBlock>,
     ) -> RpcResult<Option<BlockHeight>> {
-        rpc::handle_result(
+        rpc::handle_classified_result(
             self.call(move |this| this.get_block_height_in_main_chain(&block_id)).await,
         )
     }
 
     async fn best_block_height(&self) -> RpcResult<BlockHeight> {
-        rpc::handle_result(self.call(move |this| this.get_best_block_height()).await)
+        rpc::handle_classified_result(self.call(move |this| this.get_best_block_height()).await)
     }
 
     async fn last_common_ancestor_by_id(
@@ -263,18 +421,42 @@ impl ChainstateRpcServer for super::ChainstateHandle {
         first_block: Id<GenBlock>,
         second_block: Id<GenBlock>,
     ) -> RpcResult<Option<(Id<GenBlock>, BlockHeight)>> {
-        rpc::handle_result(
+        rpc::handle_classified_result(
             self.call(move |this| this.last_common_ancestor_by_id(&first_block, &second_block))
                 .await,
         )
     }
 
+    async fn last_common_ancestor_by_height(
+        &self,
+        first_height: BlockHeight,
+        second_height: BlockHeight,
+    ) -> RpcResult<Option<(Id<GenBlock>, BlockHeight)>> {
+        rpc::handle_classified_result(
+            self.call(move |this| {
+                let block_id_at = |height: BlockHeight| {
+                    this.get_block_id_from_height(&height)?.ok_or(
+                        crate::ChainstateError::FailedToReadProperty(
+                            PropertyQueryError::BlockForHeightNotFound(height),
+                        ),
+                    )
+                };
+                let first_block = block_id_at(first_height)?;
+                let second_block = block_id_at(second_height)?;
+                this.last_common_ancestor_by_id(&first_block, &second_block)
+            })
+            .await,
+        )
+    }
+
     async fn stake_pool_balance(&self, pool_id: PoolId) -> RpcResult<Option<Amount>> {
-        rpc::handle_result(self.call(move |this| this.get_stake_pool_balance(pool_id)).await)
+        rpc::handle_classified_result(
+            self.call(move |this| this.get_stake_pool_balance(pool_id)).await,
+        )
     }
 
     async fn stake_pool_pledge(&self, pool_id: PoolId) -> RpcResult<Option<Amount>> {
-        rpc::handle_result(
+        rpc::handle_classified_result(
             self.call(move |this| {
                 this.get_stake_pool_data(pool_id)
                     .map(|opt| opt.map(|data| data.pledge_amount()))
@@ -288,14 +470,28 @@ impl ChainstateRpcServer for super::ChainstateHandle {
         pool_id: PoolId,
         delegation_id: DelegationId,
     ) -> RpcResult<Option<Amount>> {
-        rpc::handle_result(
+        rpc::handle_classified_result(
             self.call(move |this| this.get_stake_pool_delegation_share(pool_id, delegation_id))
                 .await,
         )
     }
 
     async fn token_info(&self, token_id: TokenId) -> RpcResult<Option<RPCTokenInfo>> {
-        rpc::handle_result(self.call(move |this| this.get_token_info_for_rpc(token_id)).await)
+        rpc::handle_classified_result(
+            self.call(move |this| this.get_token_info_for_rpc(token_id)).await,
+        )
+    }
+
+    async fn tokens_info_by_issuance(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> RpcResult<Vec<RPCTokenInfo>> {
+        let limit = std::cmp::min(limit, MAX_TOKENS_INFO_PAGE_SIZE);
+        rpc::handle_classified_result(
+            self.call(move |this| this.get_tokens_info_for_rpc_by_issuance(offset, limit))
+                .await,
+        )
     }
 
     async fn export_bootstrap_file(
@@ -308,7 +504,7 @@ impl ChainstateRpcServer for super::ChainstateHandle {
         let writer: std::io::BufWriter<Box<dyn Write + Send>> =
             std::io::BufWriter::new(Box::new(file_obj));
 
-        rpc::handle_result(
+        rpc::handle_classified_result(
             self.call(move |this| this.export_bootstrap_stream(writer, include_orphans))
                 .await,
         )
@@ -320,11 +516,80 @@ impl ChainstateRpcServer for super::ChainstateHandle {
         let reader: std::io::BufReader<Box<dyn Read + Send>> =
             std::io::BufReader::new(Box::new(file_obj));
 
-        rpc::handle_result(self.call_mut(move |this| this.import_bootstrap_stream(reader)).await)
+        rpc::handle_classified_result(
+            self.call_mut(move |this| this.import_bootstrap_stream(reader)).await,
+        )
+    }
+
+    async fn export_blocks(
+        &self,
+        from: BlockHeight,
+        to: BlockHeight,
+        max_count: usize,
+        continuation: Option<RpcBlockExportToken>,
+    ) -> RpcResult<RpcBlockExportPage> {
+        let max_count = std::cmp::min(max_count, MAX_BLOCK_EXPORT_PAGE_LEN);
+        rpc::handle_classified_result(
+            self.call(move |this| -> Result<_, crate::ChainstateError> {
+                utils::ensure!(max_count > 0, PropertyQueryError::ZeroMaxBlockCount);
+
+                let start = match continuation {
+                    Some(token) => {
+                        // Make sure the previous page is still part of the mainchain
+                        let prev_height = token.next_height.prev_height().ok_or(
+                            PropertyQueryError::InvalidBlockHeightRange(token.next_height, to),
+                        )?;
+                        utils::ensure!(
+                            this.get_block_id_from_height(&prev_height)?
+                                == Some(token.prev_block_id),
+                            PropertyQueryError::BlockNoLongerInMainchain(
+                                prev_height,
+                                token.prev_block_id
+                            )
+                        );
+                        token.next_height
+                    }
+                    None => from,
+                };
+                utils::ensure!(
+                    start != BlockHeight::zero() && start <= to,
+                    PropertyQueryError::InvalidBlockHeightRange(start, to)
+                );
+
+                let mut blocks = Vec::new();
+                let mut page_bytes = 0;
+                let mut height = start;
+                while height <= to && blocks.len() < max_count {
+                    let block = match this.get_mainchain_blocks(height, 1)?.pop() {
+                        Some(block) => block,
+                        None => break,
+                    };
+                    page_bytes += block.encoded_size();
+                    if !blocks.is_empty() && page_bytes > MAX_BLOCK_EXPORT_PAGE_BYTES {
+                        break;
+                    }
+                    blocks.push(block);
+                    height = height.next_height();
+                }
+
+                let next = match blocks.last() {
+                    Some(last_block) if height <= to => Some(RpcBlockExportToken {
+                        next_height: height,
+                        prev_block_id: last_block.get_id().into(),
+                    }),
+                    Some(_) => None,
+                    // Nothing new since the previous page, it can be resumed later
+                    None => continuation,
+                };
+
+                Ok(RpcBlockExportPage::new(blocks, next))
+            })
+            .await,
+        )
     }
 
     async fn info(&self) -> RpcResult<ChainInfo> {
-        rpc::handle_result(self.call(move |this| this.info()).await)
+        rpc::handle_classified_result(self.call(move |this| this.info()).await)
     }
 }
 
@@ -332,15 +597,32 @@ impl ChainstateRpcServer for super::ChainstateHandle {
 mod test {
     use super::*;
     use crate::{ChainstateConfig, DefaultTransactionVerificationStrategy};
+    use common::{
+        chain::{
+            block::{timestamp::BlockTimestamp, ConsensusData},
+            config::create_unit_test_config,
+            output_value::OutputValue,
+            signature::inputsig::InputWitness,
+            timelock::OutputTimeLock,
+            Destination, OutPointSourceId, TxInput,
+        },
+        time_getter::TimeGetter,
+    };
     use serde_json::Value;
     use std::{future::Future, sync::Arc};
 
     async fn with_chainstate<F: 'static + Send + Future<Output = ()>>(
         proc: impl 'static + Send + FnOnce(crate::ChainstateHandle) -> F,
+    ) {
+        with_chainstate_config(ChainstateConfig::new(), proc).await
+    }
+
+    async fn with_chainstate_config<F: 'static + Send + Future<Output = ()>>(
+        chainstate_config: ChainstateConfig,
+        proc: impl 'static + Send + FnOnce(crate::ChainstateHandle) -> F,
     ) {
         let storage = chainstate_storage::inmemory::Store::new_empty().unwrap();
         let chain_config = Arc::new(common::chain::config::create_unit_test_config());
-        let chainstate_config = ChainstateConfig::new();
         let mut man = subsystem::Manager::new("rpctest");
         let shutdown = man.make_shutdown_trigger();
         let handle = man.add_subsystem(
@@ -356,7 +638,7 @@ mod test {
             .unwrap(),
         );
         let tester = tokio::spawn(async move {
-            proc(handle);
+            proc(handle).await;
             shutdown.initiate();
         });
         let _ = tokio::join!(man.main(), tester);
@@ -392,4 +674,335 @@ mod test {
         })
         .await
     }
+
+    fn error_code(err: rpc::Error) -> i32 {
+        match err {
+            rpc::Error::Call(jsonrpsee::types::error::CallError::Custom(obj)) => obj.code(),
+            err => panic!("unexpected error {err:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rpc_error_codes() {
+        with_chainstate(|handle| async move {
+            // Invalidating an unknown block is reported as missing
+            let res = handle.invalidate_block(Id::new(common::primitives::H256::zero())).await;
+            assert_eq!(error_code(res.unwrap_err()), rpc::NOT_FOUND_ERROR_CODE);
+
+            // Genesis can't be requested as a mainchain block which makes the request malformed
+            let res = handle.get_mainchain_blocks(BlockHeight::zero(), 1).await;
+            assert_eq!(
+                error_code(res.unwrap_err()),
+                jsonrpsee::types::error::INVALID_PARAMS_CODE
+            );
+
+            // The transaction index is disabled by default
+            let res = handle.get_transaction_block(Id::new(common::primitives::H256::zero())).await;
+            assert_eq!(
+                error_code(res.unwrap_err()),
+                jsonrpsee::types::error::INVALID_PARAMS_CODE
+            );
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn block_reward() {
+        with_chainstate(|handle| async move {
+            let chain_config = create_unit_test_config();
+            let reward_outputs = vec![TxOutput::LockThenTransfer(
+                OutputValue::Coin(Amount::from_atoms(1)),
+                Destination::AnyoneCanSpend,
+                OutputTimeLock::ForBlockCount(
+                    chain_config.empty_consensus_reward_maturity_distance().to_int() as u64,
+                ),
+            )];
+            let block = Block::new(
+                vec![],
+                chain_config.genesis_block_id(),
+                BlockTimestamp::from_time(TimeGetter::default().get_time()),
+                ConsensusData::None,
+                BlockReward::new(reward_outputs.clone()),
+            )
+            .unwrap();
+            let block_id = block.get_id();
+
+            handle.submit_block(HexEncoded::new(block)).await.unwrap();
+
+            let reward = handle.get_block_reward(block_id).await.unwrap().unwrap();
+            let reward: Vec<TxOutput> = reward.into_iter().map(HexEncoded::take).collect();
+            assert_eq!(reward, reward_outputs);
+
+            // Unknown blocks have no reward
+            let unknown_id = Id::new(common::primitives::H256::zero());
+            assert!(handle.get_block_reward(unknown_id).await.unwrap().is_none());
+
+            // The reward output is unspent
+            let outpoint = UtxoOutPoint::new(block_id.into(), 0);
+            let utxo = handle.get_utxo(outpoint.into()).await.unwrap().unwrap();
+            assert_eq!(utxo.take(), reward_outputs[0]);
+
+            let outpoint = UtxoOutPoint::new(block_id.into(), 1);
+            assert!(handle.get_utxo(outpoint.into()).await.unwrap().is_none());
+        })
+        .await
+    }
+
+    #[rstest::rstest]
+    #[case(true)]
+    #[case(false)]
+    #[tokio::test]
+    async fn get_transaction(#[case] tx_index_enabled: bool) {
+        let chainstate_config =
+            ChainstateConfig::new().with_whether_tx_index_enabled(tx_index_enabled);
+        with_chainstate_config(chainstate_config, move |handle| async move {
+            let chain_config = create_unit_test_config();
+            let tx = Transaction::new(
+                0,
+                vec![TxInput::from_utxo(
+                    OutPointSourceId::BlockReward(chain_config.genesis_block_id()),
+                    0,
+                )],
+                vec![TxOutput::Transfer(
+                    OutputValue::Coin(Amount::from_atoms(1)),
+                    Destination::AnyoneCanSpend,
+                )],
+            )
+            .unwrap();
+            let tx = SignedTransaction::new(tx, vec![InputWitness::NoSignature(None)]).unwrap();
+            let tx_id = tx.transaction().get_id();
+            let block = Block::new(
+                vec![tx.clone()],
+                chain_config.genesis_block_id(),
+                BlockTimestamp::from_time(TimeGetter::default().get_time()),
+                ConsensusData::None,
+                BlockReward::new(vec![]),
+            )
+            .unwrap();
+            let block_id = block.get_id();
+
+            handle.submit_block(HexEncoded::new(block)).await.unwrap();
+
+            let unknown_id = Id::new(common::primitives::H256::zero());
+            if tx_index_enabled {
+                let info = handle.get_transaction(tx_id).await.unwrap().unwrap();
+                assert_eq!(info.transaction.take(), tx);
+                assert_eq!(info.block_id, block_id);
+                assert_eq!(info.index_in_block, 0);
+
+                assert!(handle.get_transaction(unknown_id).await.unwrap().is_none());
+            } else {
+                // Transaction lookups are malformed requests when the index is off
+                for id in [tx_id, unknown_id] {
+                    let res = handle.get_transaction(id).await;
+                    assert_eq!(
+                        error_code(res.unwrap_err()),
+                        jsonrpsee::types::error::INVALID_PARAMS_CODE
+                    );
+                }
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn headers_range() {
+        with_chainstate(|handle| async move {
+            let chain_config = create_unit_test_config();
+            let mut prev_block_id = chain_config.genesis_block_id();
+            let mut block_ids = Vec::new();
+            for _ in 0..3 {
+                let block = Block::new(
+                    vec![],
+                    prev_block_id,
+                    BlockTimestamp::from_time(TimeGetter::default().get_time()),
+                    ConsensusData::None,
+                    BlockReward::new(vec![]),
+                )
+                .unwrap();
+                prev_block_id = block.get_id().into();
+                block_ids.push(block.get_id());
+                handle.submit_block(HexEncoded::new(block)).await.unwrap();
+            }
+
+            let header_ids = |headers: Vec<HexEncoded<SignedBlockHeader>>| {
+                headers.into_iter().map(|header| header.take().block_id()).collect::<Vec<_>>()
+            };
+
+            // Headers are returned in ascending height order, up to the tip
+            let headers = handle.headers_range(1.into(), 10.into()).await.unwrap();
+            assert_eq!(header_ids(headers), block_ids);
+
+            let headers = handle.headers_range(2.into(), 3.into()).await.unwrap();
+            assert_eq!(header_ids(headers), block_ids[1..3].to_vec());
+
+            let headers = handle.headers_range(2.into(), 2.into()).await.unwrap();
+            assert_eq!(header_ids(headers), block_ids[1..2].to_vec());
+
+            // Ranges that are empty, contain genesis or are too long are malformed requests
+            let max_to = BlockHeight::new(MAX_HEADERS_RANGE_LEN as u64);
+            let headers = handle.headers_range(1.into(), max_to).await.unwrap();
+            assert_eq!(header_ids(headers), block_ids);
+
+            for (from, to) in [
+                (BlockHeight::new(3), BlockHeight::new(2)),
+                (BlockHeight::new(0), BlockHeight::new(2)),
+                (BlockHeight::new(1), max_to.next_height()),
+            ] {
+                let res = handle.headers_range(from, to).await;
+                assert_eq!(
+                    error_code(res.unwrap_err()),
+                    jsonrpsee::types::error::INVALID_PARAMS_CODE
+                );
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn last_common_ancestor_by_height() {
+        with_chainstate(|handle| async move {
+            let chain_config = create_unit_test_config();
+            let mut prev_block_id = chain_config.genesis_block_id();
+            let mut block_ids = vec![prev_block_id];
+            for _ in 0..3 {
+                let block = Block::new(
+                    vec![],
+                    prev_block_id,
+                    BlockTimestamp::from_time(TimeGetter::default().get_time()),
+                    ConsensusData::None,
+                    BlockReward::new(vec![]),
+                )
+                .unwrap();
+                prev_block_id = block.get_id().into();
+                block_ids.push(prev_block_id);
+                handle.submit_block(HexEncoded::new(block)).await.unwrap();
+            }
+
+            for (first, second, expected) in [(1, 3, 1), (3, 2, 2), (2, 2, 2), (0, 3, 0)] {
+                let res = handle
+                    .last_common_ancestor_by_height(
+                        BlockHeight::new(first),
+                        BlockHeight::new(second),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    res,
+                    Some((block_ids[expected], BlockHeight::new(expected as u64)))
+                );
+
+                // Same result as when querying by id
+                let res_by_id = handle
+                    .last_common_ancestor_by_id(
+                        block_ids[first as usize],
+                        block_ids[second as usize],
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(res, res_by_id);
+            }
+
+            // Heights above the tip are an error rather than an unknown ancestor
+            for (first, second) in [(1, 4), (4, 1), (5, 6)] {
+                let res = handle
+                    .last_common_ancestor_by_height(
+                        BlockHeight::new(first),
+                        BlockHeight::new(second),
+                    )
+                    .await;
+                assert_eq!(error_code(res.unwrap_err()), rpc::NOT_FOUND_ERROR_CODE);
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn export_blocks_paging() {
+        with_chainstate(|handle| async move {
+            let chain_config = create_unit_test_config();
+            let mut prev_block_id = chain_config.genesis_block_id();
+            let mut block_ids = vec![prev_block_id];
+            for _ in 0..5 {
+                let block = Block::new(
+                    vec![],
+                    prev_block_id,
+                    BlockTimestamp::from_time(TimeGetter::default().get_time()),
+                    ConsensusData::None,
+                    BlockReward::new(vec![]),
+                )
+                .unwrap();
+                prev_block_id = block.get_id().into();
+                block_ids.push(prev_block_id);
+                handle.submit_block(HexEncoded::new(block)).await.unwrap();
+            }
+
+            let page_ids = |page: &RpcBlockExportPage| {
+                page.blocks
+                    .iter()
+                    .map(|block| Id::<GenBlock>::from(block.as_ref().get_id()))
+                    .collect::<Vec<_>>()
+            };
+
+            // Export a range below the tip in pages of two blocks
+            let page = handle.export_blocks(1.into(), 4.into(), 2, None).await.unwrap();
+            assert_eq!(page_ids(&page), block_ids[1..3].to_vec());
+            let token = page.next.unwrap();
+            assert_eq!(
+                token,
+                RpcBlockExportToken {
+                    next_height: 3.into(),
+                    prev_block_id: block_ids[2],
+                }
+            );
+
+            // The token can be reused, e.g. after a disconnect, and `from` is ignored with it
+            for _ in 0..2 {
+                let page = handle.export_blocks(1.into(), 4.into(), 2, Some(token)).await.unwrap();
+                assert_eq!(page_ids(&page), block_ids[3..5].to_vec());
+                assert_eq!(page.next, None);
+            }
+
+            // A range beyond the tip ends with a token to resume once more blocks arrive
+            let page = handle.export_blocks(4.into(), 10.into(), 5, None).await.unwrap();
+            assert_eq!(page_ids(&page), block_ids[4..6].to_vec());
+            let token = page.next.unwrap();
+            assert_eq!(token.next_height, 6.into());
+            let page = handle.export_blocks(4.into(), 10.into(), 5, Some(token)).await.unwrap();
+            assert!(page.blocks.is_empty());
+            assert_eq!(page.next, Some(token));
+
+            // Malformed ranges and tokens of blocks that aren't in the mainchain are rejected
+            let stale_token = RpcBlockExportToken {
+                next_height: 3.into(),
+                prev_block_id: block_ids[1],
+            };
+            for (from, to, continuation) in [
+                (0, 4, None),
+                (3, 2, None),
+                (1, 4, Some(stale_token)),
+                (
+                    1,
+                    4,
+                    Some(RpcBlockExportToken {
+                        next_height: BlockHeight::zero(),
+                        prev_block_id: block_ids[0],
+                    }),
+                ),
+            ] {
+                let res = handle.export_blocks(from.into(), to.into(), 2, continuation).await;
+                assert_eq!(
+                    error_code(res.unwrap_err()),
+                    jsonrpsee::types::error::INVALID_PARAMS_CODE
+                );
+            }
+
+            let res = handle.export_blocks(1.into(), 4.into(), 0, None).await;
+            assert_eq!(
+                error_code(res.unwrap_err()),
+                jsonrpsee::types::error::INVALID_PARAMS_CODE
+            );
+        })
+        .await
+    }
 }
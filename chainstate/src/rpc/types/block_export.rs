@@ -0,0 +1,52 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    chain::{Block, GenBlock},
+    primitives::{BlockHeight, Id},
+};
+use serialization::hex_encoded::HexEncoded;
+
+/// Identifies where a paged block export continues.
+///
+/// The token doesn't refer to any state kept by the node, so an export can be resumed with it
+/// at any time, e.g. after reconnecting. If the last exported block has been reorged out
+/// of the mainchain in the meantime, resuming fails instead of returning blocks from
+/// a different chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RpcBlockExportToken {
+    /// The height of the next block to export
+    pub next_height: BlockHeight,
+    /// The id of the last exported block, at the height just below `next_height`
+    pub prev_block_id: Id<GenBlock>,
+}
+
+/// A page of mainchain blocks returned by a block export.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RpcBlockExportPage {
+    /// The hex-encoded blocks, in ascending height order
+    pub blocks: Vec<HexEncoded<Block>>,
+    /// The token to request the next page with, or None if there are no more blocks
+    pub next: Option<RpcBlockExportToken>,
+}
+
+impl RpcBlockExportPage {
+    pub fn new(blocks: Vec<Block>, next: Option<RpcBlockExportToken>) -> Self {
+        Self {
+            blocks: blocks.into_iter().map(HexEncoded::new).collect(),
+            next,
+        }
+    }
+}
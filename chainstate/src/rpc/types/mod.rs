@@ -14,4 +14,6 @@
 // limitations under the License.
 
 pub mod block;
+pub mod block_export;
 pub mod signed_transaction;
+pub mod transaction_info;
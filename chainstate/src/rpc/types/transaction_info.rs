@@ -0,0 +1,41 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    chain::{Block, SignedTransaction},
+    primitives::Id,
+};
+use serialization::hex_encoded::HexEncoded;
+
+/// A mainchain transaction along with its location in the chain.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RpcTransactionInfo {
+    /// The hex-encoded transaction
+    pub transaction: HexEncoded<SignedTransaction>,
+    /// The id of the mainchain block containing the transaction
+    pub block_id: Id<Block>,
+    /// The index of the transaction in the block's transaction list
+    pub index_in_block: u32,
+}
+
+impl RpcTransactionInfo {
+    pub fn new(transaction: SignedTransaction, block_id: Id<Block>, index_in_block: u32) -> Self {
+        Self {
+            transaction: HexEncoded::new(transaction),
+            block_id,
+            index_in_block,
+        }
+    }
+}
@@ -29,6 +29,7 @@ use common::{
     },
     primitives::{Amount, BlockHeight, Id},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{
     AccountingBlockUndo, DelegationData, DeltaMergeUndo, PoSAccountingDeltaData,
     PoSAccountingStorageRead, PoSAccountingStorageWrite, PoolData,
@@ -55,11 +56,15 @@ impl<B: storage::Backend> Store<B> {
     pub fn new(backend: B, chain_config: &ChainConfig) -> crate::Result<Self> {
         let storage = Self::from_backend(backend)?;
 
-        // Set defaults if missing
+        // Set defaults if missing, upgrade the storage written by older versions
         let mut db_tx = storage.transaction_rw(None)?;
 
-        if db_tx.get_storage_version()?.is_none() {
-            db_tx.set_storage_version(ChainstateStorageVersion::CURRENT)?;
+        match db_tx.get_storage_version()? {
+            None => db_tx.set_storage_version(ChainstateStorageVersion::CURRENT)?,
+            Some(version) if version < ChainstateStorageVersion::CURRENT => {
+                db_tx.migrate(version)?
+            }
+            Some(_) => {}
         }
 
         if db_tx.get_magic_bytes()?.is_none() {
@@ -234,6 +239,7 @@ impl<B: storage::Backend> BlockchainStorageRead for Store<B> {
         fn get_chain_type(&self) -> crate::Result<Option<String>>;
         fn get_best_block_id(&self) -> crate::Result<Option<Id<GenBlock>>>;
         fn get_block_index(&self, id: &Id<Block>) -> crate::Result<Option<BlockIndex>>;
+        fn has_block_index(&self, id: &Id<Block>) -> crate::Result<bool>;
         fn get_block(&self, id: Id<Block>) -> crate::Result<Option<Block>>;
         fn get_block_header(&self, id: Id<Block>) -> crate::Result<Option<SignedBlockHeader>>;
         fn get_block_reward(&self, block_index: &BlockIndex) -> crate::Result<Option<BlockReward>>;
@@ -260,6 +266,8 @@ impl<B: storage::Backend> BlockchainStorageRead for Store<B> {
 
         fn get_token_id(&self, tx_id: &Id<Transaction>) -> crate::Result<Option<TokenId>>;
 
+        fn get_tokens_aux_data(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>>;
+
         fn get_block_tree_by_height(
             &self,
             start_from: BlockHeight,
@@ -311,6 +319,16 @@ impl<B: storage::Backend> PoSAccountingStorageRead<TipStorageTag> for Store<B> {
         let tx = self.transaction_ro()?;
         PoSAccountingStorageRead::<TipStorageTag>::get_pool_data(&tx, pool_id)
     }
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> crate::Result<Option<PoolId>> {
+        let tx = self.transaction_ro()?;
+        PoSAccountingStorageRead::<TipStorageTag>::get_pool_id_by_vrf_public_key(
+            &tx,
+            vrf_public_key,
+        )
+    }
     fn get_delegation_balance(&self, delegation_id: DelegationId) -> crate::Result<Option<Amount>> {
         let tx = self.transaction_ro()?;
         PoSAccountingStorageRead::<TipStorageTag>::get_delegation_balance(&tx, delegation_id)
@@ -352,6 +370,16 @@ impl<B: storage::Backend> PoSAccountingStorageRead<SealedStorageTag> for Store<B
         let tx = self.transaction_ro()?;
         PoSAccountingStorageRead::<SealedStorageTag>::get_pool_data(&tx, pool_id)
     }
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> crate::Result<Option<PoolId>> {
+        let tx = self.transaction_ro()?;
+        PoSAccountingStorageRead::<SealedStorageTag>::get_pool_id_by_vrf_public_key(
+            &tx,
+            vrf_public_key,
+        )
+    }
     fn get_delegation_balance(&self, delegation_id: DelegationId) -> crate::Result<Option<Amount>> {
         let tx = self.transaction_ro()?;
         PoSAccountingStorageRead::<SealedStorageTag>::get_delegation_balance(&tx, delegation_id)
@@ -0,0 +1,107 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Upgrades of the storage written by older versions of the node
+
+use common::{
+    chain::{tokens::TokenAuxiliaryData, Block, Transaction},
+    primitives::Id,
+};
+use serialization::{Decode, DecodeAll, Encode};
+
+use crate::{schema as db, BlockchainStorageWrite, ChainstateStorageVersion};
+
+use super::StoreTxRw;
+
+/// Token auxiliary data as stored by `ChainstateStorageVersion::V1`
+#[derive(Encode, Decode)]
+struct TokenAuxiliaryDataV1 {
+    issuance_tx: Transaction,
+    issuance_block_id: Id<Block>,
+}
+
+impl<'st, B: storage::Backend> StoreTxRw<'st, B> {
+    /// Bring the storage from the given version to the current one.
+    ///
+    /// Versions for which no migration exists are left intact, so that the storage compatibility
+    /// check can reject them.
+    pub(crate) fn migrate(&mut self, from: ChainstateStorageVersion) -> crate::Result<()> {
+        let mut version = from;
+
+        if version == ChainstateStorageVersion::V1 {
+            self.migrate_v1_to_v2()?;
+            version = ChainstateStorageVersion::V2;
+            self.set_storage_version(version)?;
+        }
+
+        if version == ChainstateStorageVersion::V2 {
+            self.migrate_v2_to_v3()?;
+            version = ChainstateStorageVersion::V3;
+            self.set_storage_version(version)?;
+        }
+
+        Ok(())
+    }
+
+    /// Add the frozen flag to the token auxiliary data; none of the existing tokens are frozen
+    fn migrate_v1_to_v2(&mut self) -> crate::Result<()> {
+        let entries = self
+            .0
+            .get_mut::<db::DBTokensAuxData, _>()
+            .prefix_iter_raw(&())?
+            .collect::<Vec<_>>();
+
+        for (token_id, value) in entries {
+            let old_data =
+                TokenAuxiliaryDataV1::decode_all(&mut value.as_slice()).map_err(|e| {
+                    crate::Error::Migration(format!("Token aux data is not in the V1 format: {e}"))
+                })?;
+            let new_data =
+                TokenAuxiliaryData::new(old_data.issuance_tx, old_data.issuance_block_id);
+            self.write::<db::DBTokensAuxData, _, _, _>(token_id, &new_data)?;
+        }
+
+        Ok(())
+    }
+
+    /// Index the existing tip and sealed pool data by the VRF public key
+    fn migrate_v2_to_v3(&mut self) -> crate::Result<()> {
+        let tip_pools = self
+            .0
+            .get::<db::DBAccountingPoolDataTip, _>()
+            .prefix_iter_decoded(&())?
+            .collect::<Vec<_>>();
+        for (pool_id, pool_data) in tip_pools {
+            self.write::<db::DBAccountingPoolVrfKeysTip, _, _, _>(
+                (pool_data.vrf_public_key().clone(), pool_id),
+                (),
+            )?;
+        }
+
+        let sealed_pools = self
+            .0
+            .get::<db::DBAccountingPoolDataSealed, _>()
+            .prefix_iter_decoded(&())?
+            .collect::<Vec<_>>();
+        for (pool_id, pool_data) in sealed_pools {
+            self.write::<db::DBAccountingPoolVrfKeysSealed, _, _, _>(
+                (pool_data.vrf_public_key().clone(), pool_id),
+                (),
+            )?;
+        }
+
+        Ok(())
+    }
+}
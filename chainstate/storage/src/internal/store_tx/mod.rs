@@ -13,6 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod migration;
 mod read_impls;
 mod write_impls;
 
@@ -75,6 +76,17 @@ impl<'st, B: storage::Backend> StoreTxRo<'st, B> {
         map.get(key).map_err(crate::Error::from).map(|x| x.map(|x| x.decode()))
     }
 
+    // Check whether the database contains the key, without decoding the value
+    fn contains<DbMap, I, K>(&self, key: K) -> crate::Result<bool>
+    where
+        DbMap: schema::DbMap,
+        Schema: schema::HasDbMap<DbMap, I>,
+        K: EncodeLike<DbMap::Key>,
+    {
+        let map = self.0.get::<DbMap, I>();
+        map.get(key).map_err(crate::Error::from).map(|x| x.is_some())
+    }
+
     // Read a value for a well-known entry
     fn read_value<E: well_known::Entry>(&self) -> crate::Result<Option<E::Value>> {
         self.read::<db::DBValue, _, _>(E::KEY).map(|x| {
@@ -97,6 +109,17 @@ impl<'st, B: storage::Backend> StoreTxRw<'st, B> {
         map.get(key).map_err(crate::Error::from).map(|x| x.map(|x| x.decode()))
     }
 
+    // Check whether the database contains the key, without decoding the value
+    fn contains<DbMap, I, K>(&self, key: K) -> crate::Result<bool>
+    where
+        DbMap: schema::DbMap,
+        Schema: schema::HasDbMap<DbMap, I>,
+        K: EncodeLike<DbMap::Key>,
+    {
+        let map = self.0.get::<DbMap, I>();
+        map.get(key).map_err(crate::Error::from).map(|x| x.is_some())
+    }
+
     // Read a value for a well-known entry
     fn read_value<E: well_known::Entry>(&self) -> crate::Result<Option<E::Value>> {
         self.read::<db::DBValue, _, _>(E::KEY).map(|x| {
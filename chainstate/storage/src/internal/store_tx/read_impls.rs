@@ -27,6 +27,7 @@ use common::{
     },
     primitives::{Amount, BlockHeight, Id, H256},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{
     AccountingBlockUndo, DelegationData, DeltaMergeUndo, PoSAccountingDeltaData,
     PoSAccountingStorageRead, PoolData,
@@ -118,6 +119,10 @@ impl<'st, B: storage::Backend> BlockchainStorageRead for super::StoreTxRo<'st, B
         self.read::<db::DBBlockIndex, _, _>(id)
     }
 
+    fn has_block_index(&self, id: &Id<Block>) -> crate::Result<bool> {
+        self.contains::<db::DBBlockIndex, _, _>(id)
+    }
+
     /// Get the hash of the best block
     fn get_best_block_id(&self) -> crate::Result<Option<Id<GenBlock>>> {
         self.read_value::<well_known::BestBlockId>()
@@ -174,6 +179,11 @@ impl<'st, B: storage::Backend> BlockchainStorageRead for super::StoreTxRo<'st, B
         self.read::<db::DBIssuanceTxVsTokenId, _, _>(&issuance_tx_id)
     }
 
+    fn get_tokens_aux_data(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>> {
+        let map = self.0.get::<db::DBTokensAuxData, _>();
+        Ok(map.prefix_iter_decoded(&())?.collect())
+    }
+
     fn get_block_tree_by_height(
         &self,
         start_from: BlockHeight,
@@ -248,6 +258,15 @@ impl<'st, B: storage::Backend> PoSAccountingStorageRead<TipStorageTag>
         self.read::<db::DBAccountingPoolDataTip, _, _>(pool_id)
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> crate::Result<Option<PoolId>> {
+        let db_map = self.0.get::<db::DBAccountingPoolVrfKeysTip, _>();
+        let mut pool_ids = db_map.prefix_iter_keys(&(vrf_public_key.clone(),))?;
+        Ok(pool_ids.next().map(|(_, pool_id)| pool_id))
+    }
+
     fn get_delegation_balance(&self, delegation_id: DelegationId) -> crate::Result<Option<Amount>> {
         self.read::<db::DBAccountingDelegationBalancesTip, _, _>(delegation_id)
     }
@@ -288,6 +307,15 @@ impl<'st, B: storage::Backend> PoSAccountingStorageRead<SealedStorageTag>
         self.read::<db::DBAccountingPoolDataSealed, _, _>(pool_id)
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> crate::Result<Option<PoolId>> {
+        let db_map = self.0.get::<db::DBAccountingPoolVrfKeysSealed, _>();
+        let mut pool_ids = db_map.prefix_iter_keys(&(vrf_public_key.clone(),))?;
+        Ok(pool_ids.next().map(|(_, pool_id)| pool_id))
+    }
+
     fn get_delegation_balance(&self, delegation_id: DelegationId) -> crate::Result<Option<Amount>> {
         self.read::<db::DBAccountingDelegationBalancesSealed, _, _>(delegation_id)
     }
@@ -335,6 +363,10 @@ impl<'st, B: storage::Backend> BlockchainStorageRead for super::StoreTxRw<'st, B
         self.read::<db::DBBlockIndex, _, _>(id)
     }
 
+    fn has_block_index(&self, id: &Id<Block>) -> crate::Result<bool> {
+        self.contains::<db::DBBlockIndex, _, _>(id)
+    }
+
     /// Get the hash of the best block
     fn get_best_block_id(&self) -> crate::Result<Option<Id<GenBlock>>> {
         self.read_value::<well_known::BestBlockId>()
@@ -391,6 +423,11 @@ impl<'st, B: storage::Backend> BlockchainStorageRead for super::StoreTxRw<'st, B
         self.read::<db::DBIssuanceTxVsTokenId, _, _>(&issuance_tx_id)
     }
 
+    fn get_tokens_aux_data(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>> {
+        let map = self.0.get::<db::DBTokensAuxData, _>();
+        Ok(map.prefix_iter_decoded(&())?.collect())
+    }
+
     fn get_block_tree_by_height(
         &self,
         start_from: BlockHeight,
@@ -465,6 +502,15 @@ impl<'st, B: storage::Backend> PoSAccountingStorageRead<TipStorageTag>
         self.read::<db::DBAccountingPoolDataTip, _, _>(pool_id)
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> crate::Result<Option<PoolId>> {
+        let db_map = self.0.get::<db::DBAccountingPoolVrfKeysTip, _>();
+        let mut pool_ids = db_map.prefix_iter_keys(&(vrf_public_key.clone(),))?;
+        Ok(pool_ids.next().map(|(_, pool_id)| pool_id))
+    }
+
     fn get_delegation_balance(&self, delegation_id: DelegationId) -> crate::Result<Option<Amount>> {
         self.read::<db::DBAccountingDelegationBalancesTip, _, _>(delegation_id)
     }
@@ -505,6 +551,15 @@ impl<'st, B: storage::Backend> PoSAccountingStorageRead<SealedStorageTag>
         self.read::<db::DBAccountingPoolDataSealed, _, _>(pool_id)
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> crate::Result<Option<PoolId>> {
+        let db_map = self.0.get::<db::DBAccountingPoolVrfKeysSealed, _>();
+        let mut pool_ids = db_map.prefix_iter_keys(&(vrf_public_key.clone(),))?;
+        Ok(pool_ids.next().map(|(_, pool_id)| pool_id))
+    }
+
     fn get_delegation_balance(&self, delegation_id: DelegationId) -> crate::Result<Option<Amount>> {
         self.read::<db::DBAccountingDelegationBalancesSealed, _, _>(delegation_id)
     }
@@ -221,10 +221,25 @@ impl<'st, B: storage::Backend> PoSAccountingStorageWrite<TipStorageTag> for Stor
     }
 
     fn set_pool_data(&mut self, pool_id: PoolId, pool_data: &PoolData) -> crate::Result<()> {
+        // The VRF key of a pool isn't expected to change, but the index must not go stale
+        if let Some(old_data) = self.read::<db::DBAccountingPoolDataTip, _, _>(pool_id)? {
+            self.0
+                .get_mut::<db::DBAccountingPoolVrfKeysTip, _>()
+                .del((old_data.vrf_public_key().clone(), pool_id))?;
+        }
+        self.write::<db::DBAccountingPoolVrfKeysTip, _, _, _>(
+            (pool_data.vrf_public_key().clone(), pool_id),
+            (),
+        )?;
         self.write::<db::DBAccountingPoolDataTip, _, _, _>(pool_id, pool_data)
     }
 
     fn del_pool_data(&mut self, pool_id: PoolId) -> crate::Result<()> {
+        if let Some(pool_data) = self.read::<db::DBAccountingPoolDataTip, _, _>(pool_id)? {
+            self.0
+                .get_mut::<db::DBAccountingPoolVrfKeysTip, _>()
+                .del((pool_data.vrf_public_key().clone(), pool_id))?;
+        }
         self.0
             .get_mut::<db::DBAccountingPoolDataTip, _>()
             .del(pool_id)
@@ -298,10 +313,24 @@ impl<'st, B: storage::Backend> PoSAccountingStorageWrite<SealedStorageTag> for S
     }
 
     fn set_pool_data(&mut self, pool_id: PoolId, pool_data: &PoolData) -> crate::Result<()> {
+        if let Some(old_data) = self.read::<db::DBAccountingPoolDataSealed, _, _>(pool_id)? {
+            self.0
+                .get_mut::<db::DBAccountingPoolVrfKeysSealed, _>()
+                .del((old_data.vrf_public_key().clone(), pool_id))?;
+        }
+        self.write::<db::DBAccountingPoolVrfKeysSealed, _, _, _>(
+            (pool_data.vrf_public_key().clone(), pool_id),
+            (),
+        )?;
         self.write::<db::DBAccountingPoolDataSealed, _, _, _>(pool_id, pool_data)
     }
 
     fn del_pool_data(&mut self, pool_id: PoolId) -> crate::Result<()> {
+        if let Some(pool_data) = self.read::<db::DBAccountingPoolDataSealed, _, _>(pool_id)? {
+            self.0
+                .get_mut::<db::DBAccountingPoolVrfKeysSealed, _>()
+                .del((pool_data.vrf_public_key().clone(), pool_id))?;
+        }
         self.0
             .get_mut::<db::DBAccountingPoolDataSealed, _>()
             .del(pool_id)
@@ -17,9 +17,10 @@ use super::*;
 use common::chain::output_value::OutputValue;
 use common::chain::transaction::signed_transaction::SignedTransaction;
 use common::chain::{Block, Destination, TxOutput};
-use common::primitives::{Amount, Idable, H256};
+use common::primitives::{per_thousand::PerThousand, Amount, Idable, H256};
 use crypto::key::{KeyKind, PrivateKey};
 use crypto::random::{CryptoRng, Rng};
+use crypto::vrf::{VRFKeyKind, VRFPrivateKey};
 use rstest::rstest;
 use serialization::Encode;
 use test_utils::random::{make_seedable_rng, Seed};
@@ -421,3 +422,183 @@ fn utxo_db_impl_test(#[case] seed: Seed) {
     assert!(db_interface.del_undo_data(block_id).is_ok());
     assert_eq!(db_interface.get_undo_data(block_id), Ok(None));
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn migrate_token_aux_data_from_v1(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let token_id = TokenId::random_using(&mut rng);
+        let issuance_tx = Transaction::new(rng.gen(), vec![], vec![]).unwrap();
+        let issuance_block_id: Id<Block> = Id::new(H256::random_using(&mut rng));
+
+        // The token aux data as written by a node using the V1 format, without the frozen flag
+        let map_id = storage::raw::DbMapId::<Schema>::new::<db::DBTokensAuxData, _>();
+        let v1_data = (issuance_tx.clone(), issuance_block_id).encode();
+        let dump = [(map_id, [(token_id.encode(), v1_data)].into_iter().collect())]
+            .into_iter()
+            .collect();
+        let store = Store(
+            storage::Storage::new_from_dump(storage::inmemory::InMemory::new(), dump).unwrap(),
+        );
+
+        let mut db_tx = store.transaction_rw(None).unwrap();
+        db_tx.migrate(ChainstateStorageVersion::V1).unwrap();
+        db_tx.commit().unwrap();
+
+        assert_eq!(
+            store.get_storage_version().unwrap(),
+            Some(ChainstateStorageVersion::CURRENT)
+        );
+        let aux_data = store.get_token_aux_data(&token_id).unwrap().unwrap();
+        assert_eq!(
+            aux_data,
+            TokenAuxiliaryData::new(issuance_tx, issuance_block_id)
+        );
+        assert!(!aux_data.is_frozen());
+    })
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn migrate_corrupted_token_aux_data_from_v1(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let token_id = TokenId::random_using(&mut rng);
+
+        let map_id = storage::raw::DbMapId::<Schema>::new::<db::DBTokensAuxData, _>();
+        let invalid_data: Vec<u8> = vec![rng.gen()];
+        let dump = [(
+            map_id,
+            [(token_id.encode(), invalid_data)].into_iter().collect(),
+        )]
+        .into_iter()
+        .collect();
+        let store = Store(
+            storage::Storage::new_from_dump(storage::inmemory::InMemory::new(), dump).unwrap(),
+        );
+
+        let mut db_tx = store.transaction_rw(None).unwrap();
+        assert!(matches!(
+            db_tx.migrate(ChainstateStorageVersion::V1),
+            Err(crate::Error::Migration(_))
+        ));
+    })
+}
+
+fn create_rand_pool_data(rng: &mut (impl Rng + CryptoRng)) -> PoolData {
+    let (_, vrf_pk) = VRFPrivateKey::new_from_rng(rng, VRFKeyKind::Schnorrkel);
+    PoolData::new(
+        Destination::AnyoneCanSpend,
+        Amount::from_atoms(rng.gen_range(1..1000)),
+        vrf_pk,
+        PerThousand::new(rng.gen_range(0..=1000)).unwrap(),
+        Amount::from_atoms(rng.gen_range(0..1000)),
+    )
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn pool_vrf_key_index(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let pool_id_1 = PoolId::new(H256::random_using(&mut rng));
+        let pool_id_2 = PoolId::new(H256::random_using(&mut rng));
+        let pool_data_1 = create_rand_pool_data(&mut rng);
+        let pool_data_2 = create_rand_pool_data(&mut rng);
+        let vrf_pk_1 = pool_data_1.vrf_public_key().clone();
+        let vrf_pk_2 = pool_data_2.vrf_public_key().clone();
+
+        let mut store = TestStore::new_empty().unwrap();
+        let mut db_tx = store.transaction_rw(None).unwrap();
+        PoSAccountingStorageWrite::<TipStorageTag>::set_pool_data(
+            &mut db_tx,
+            pool_id_1,
+            &pool_data_1,
+        )
+        .unwrap();
+        PoSAccountingStorageWrite::<TipStorageTag>::set_pool_data(
+            &mut db_tx,
+            pool_id_2,
+            &pool_data_2,
+        )
+        .unwrap();
+        db_tx.commit().unwrap();
+
+        assert_eq!(
+            PoSAccountingStorageRead::<TipStorageTag>::get_pool_id_by_vrf_public_key(
+                &store, &vrf_pk_1
+            ),
+            Ok(Some(pool_id_1))
+        );
+        assert_eq!(
+            PoSAccountingStorageRead::<TipStorageTag>::get_pool_id_by_vrf_public_key(
+                &store, &vrf_pk_2
+            ),
+            Ok(Some(pool_id_2))
+        );
+        // The sealed storage is indexed separately
+        assert_eq!(
+            PoSAccountingStorageRead::<SealedStorageTag>::get_pool_id_by_vrf_public_key(
+                &store, &vrf_pk_1
+            ),
+            Ok(None)
+        );
+
+        PoSAccountingStorageWrite::<TipStorageTag>::del_pool_data(&mut store, pool_id_1).unwrap();
+        assert_eq!(
+            PoSAccountingStorageRead::<TipStorageTag>::get_pool_id_by_vrf_public_key(
+                &store, &vrf_pk_1
+            ),
+            Ok(None)
+        );
+        assert_eq!(
+            PoSAccountingStorageRead::<TipStorageTag>::get_pool_id_by_vrf_public_key(
+                &store, &vrf_pk_2
+            ),
+            Ok(Some(pool_id_2))
+        );
+    })
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn migrate_pool_vrf_keys_from_v2(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let pool_id = PoolId::new(H256::random_using(&mut rng));
+        let pool_data = create_rand_pool_data(&mut rng);
+
+        // The pool data as written by a node using the V2 format, without the VRF key index
+        let map_id = storage::raw::DbMapId::<Schema>::new::<db::DBAccountingPoolDataSealed, _>();
+        let dump = [(
+            map_id,
+            [(pool_id.encode(), pool_data.encode())].into_iter().collect(),
+        )]
+        .into_iter()
+        .collect();
+        let store = Store(
+            storage::Storage::new_from_dump(storage::inmemory::InMemory::new(), dump).unwrap(),
+        );
+
+        let mut db_tx = store.transaction_rw(None).unwrap();
+        db_tx.migrate(ChainstateStorageVersion::V2).unwrap();
+        db_tx.commit().unwrap();
+
+        assert_eq!(
+            store.get_storage_version().unwrap(),
+            Some(ChainstateStorageVersion::CURRENT)
+        );
+        assert_eq!(
+            PoSAccountingStorageRead::<SealedStorageTag>::get_pool_id_by_vrf_public_key(
+                &store,
+                pool_data.vrf_public_key()
+            ),
+            Ok(Some(pool_id))
+        );
+    })
+}
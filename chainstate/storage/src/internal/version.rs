@@ -15,11 +15,17 @@
 
 use serialization::{Decode, Encode};
 
-#[derive(Debug, Encode, Decode, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Encode, Decode, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
 pub struct ChainstateStorageVersion(u32);
 
 impl ChainstateStorageVersion {
-    pub const CURRENT: Self = Self(1);
+    /// The version before token freezing, without the frozen flag in the token auxiliary data
+    pub const V1: Self = Self(1);
+    /// The version before the pool data was indexed by the VRF public key
+    pub const V2: Self = Self(2);
+    pub const V3: Self = Self(3);
+
+    pub const CURRENT: Self = Self::V3;
 
     pub fn new(value: u32) -> Self {
         Self(value)
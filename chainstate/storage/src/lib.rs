@@ -77,6 +77,9 @@ pub trait BlockchainStorageRead:
 
     fn get_block_index(&self, block_id: &Id<Block>) -> crate::Result<Option<BlockIndex>>;
 
+    /// Check whether the block index exists, without loading it
+    fn has_block_index(&self, block_id: &Id<Block>) -> crate::Result<bool>;
+
     fn get_block_reward(&self, block_index: &BlockIndex) -> crate::Result<Option<BlockReward>>;
 
     /// Get block by its hash
@@ -110,6 +113,9 @@ pub trait BlockchainStorageRead:
     /// Get token id by id of the creation tx
     fn get_token_id(&self, tx_id: &Id<Transaction>) -> crate::Result<Option<TokenId>>;
 
+    /// Get auxiliary data of all tokens
+    fn get_tokens_aux_data(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>>;
+
     /// Get block tree as height vs ids
     fn get_block_tree_by_height(
         &self,
@@ -29,6 +29,7 @@ use common::{
     },
     primitives::{Amount, BlockHeight, Id},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{
     AccountingBlockUndo, DelegationData, DeltaMergeUndo, PoSAccountingDeltaData, PoolData,
 };
@@ -51,6 +52,7 @@ mockall::mock! {
         fn get_chain_type(&self) -> crate::Result<Option<String>>;
         fn get_best_block_id(&self) -> crate::Result<Option<Id<GenBlock>>>;
         fn get_block_index(&self, id: &Id<Block>) -> crate::Result<Option<BlockIndex>>;
+        fn has_block_index(&self, id: &Id<Block>) -> crate::Result<bool>;
         fn get_block(&self, id: Id<Block>) -> crate::Result<Option<Block>>;
         fn get_block_reward(&self, block_index: &BlockIndex) -> crate::Result<Option<BlockReward>>;
         fn get_block_header(&self, id: Id<Block>) -> crate::Result<Option<SignedBlockHeader>>;
@@ -77,6 +79,8 @@ mockall::mock! {
 
         fn get_token_id(&self, tx_id: &Id<Transaction>) -> crate::Result<Option<TokenId>>;
 
+        fn get_tokens_aux_data(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>>;
+
         fn get_block_tree_by_height(
             &self,
             start_from: BlockHeight,
@@ -111,6 +115,10 @@ mockall::mock! {
     impl PoSAccountingStorageReadTip for Store {
         fn get_pool_balance_tip(&self, pool_id: PoolId) -> crate::Result<Option<Amount>>;
         fn get_pool_data_tip(&self, pool_id: PoolId) -> crate::Result<Option<PoolData>>;
+        fn get_pool_id_by_vrf_public_key_tip(
+            &self,
+            vrf_public_key: &VRFPublicKey,
+        ) -> crate::Result<Option<PoolId>>;
         fn get_delegation_balance_tip(
             &self,
             delegation_id: DelegationId,
@@ -133,6 +141,10 @@ mockall::mock! {
     impl PoSAccountingStorageReadSealed for Store {
         fn get_pool_balance_sealed(&self, pool_id: PoolId) -> crate::Result<Option<Amount>>;
         fn get_pool_data_sealed(&self, pool_id: PoolId) -> crate::Result<Option<PoolData>>;
+        fn get_pool_id_by_vrf_public_key_sealed(
+            &self,
+            vrf_public_key: &VRFPublicKey,
+        ) -> crate::Result<Option<PoolId>>;
         fn get_delegation_balance_sealed(
             &self,
             delegation_id: DelegationId,
@@ -312,6 +324,7 @@ mockall::mock! {
         fn get_chain_type(&self) -> crate::Result<Option<String>>;
         fn get_best_block_id(&self) -> crate::Result<Option<Id<GenBlock>>>;
         fn get_block_index(&self, id: &Id<Block>) -> crate::Result<Option<BlockIndex>>;
+        fn has_block_index(&self, id: &Id<Block>) -> crate::Result<bool>;
         fn get_block(&self, id: Id<Block>) -> crate::Result<Option<Block>>;
         fn get_block_reward(&self, block_index: &BlockIndex) -> crate::Result<Option<BlockReward>>;
         fn get_block_header(&self, id: Id<Block>) -> crate::Result<Option<SignedBlockHeader>>;
@@ -336,6 +349,7 @@ mockall::mock! {
 
         fn get_token_aux_data(&self, token_id: &TokenId) -> crate::Result<Option<TokenAuxiliaryData>>;
         fn get_token_id(&self, tx_id: &Id<Transaction>) -> crate::Result<Option<TokenId>>;
+        fn get_tokens_aux_data(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>>;
         fn get_block_tree_by_height(
             &self,
             start_from: BlockHeight,
@@ -370,6 +384,10 @@ mockall::mock! {
     impl PoSAccountingStorageReadTip for StoreTxRo {
         fn get_pool_balance_tip(&self, pool_id: PoolId) -> crate::Result<Option<Amount>>;
         fn get_pool_data_tip(&self, pool_id: PoolId) -> crate::Result<Option<PoolData>>;
+        fn get_pool_id_by_vrf_public_key_tip(
+            &self,
+            vrf_public_key: &VRFPublicKey,
+        ) -> crate::Result<Option<PoolId>>;
         fn get_delegation_balance_tip(
             &self,
             delegation_id: DelegationId,
@@ -392,6 +410,10 @@ mockall::mock! {
     impl PoSAccountingStorageReadSealed for StoreTxRo {
         fn get_pool_balance_sealed(&self, pool_id: PoolId) -> crate::Result<Option<Amount>>;
         fn get_pool_data_sealed(&self, pool_id: PoolId) -> crate::Result<Option<PoolData>>;
+        fn get_pool_id_by_vrf_public_key_sealed(
+            &self,
+            vrf_public_key: &VRFPublicKey,
+        ) -> crate::Result<Option<PoolId>>;
         fn get_delegation_balance_sealed(
             &self,
             delegation_id: DelegationId,
@@ -429,6 +451,7 @@ mockall::mock! {
         fn get_best_block_id(&self) -> crate::Result<Option<Id<GenBlock>>>;
         fn get_block(&self, id: Id<Block>) -> crate::Result<Option<Block>>;
         fn get_block_index(&self, id: &Id<Block>) -> crate::Result<Option<BlockIndex>>;
+        fn has_block_index(&self, id: &Id<Block>) -> crate::Result<bool>;
         fn get_block_reward(&self, block_index: &BlockIndex) -> crate::Result<Option<BlockReward>>;
         fn get_block_header(&self, id: Id<Block>) -> crate::Result<Option<SignedBlockHeader>>;
 
@@ -452,6 +475,7 @@ mockall::mock! {
 
         fn get_token_aux_data(&self, token_id: &TokenId) -> crate::Result<Option<TokenAuxiliaryData>>;
         fn get_token_id(&self, tx_id: &Id<Transaction>) -> crate::Result<Option<TokenId>>;
+        fn get_tokens_aux_data(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>>;
         fn get_block_tree_by_height(
             &self,
             start_from: BlockHeight,
@@ -486,6 +510,10 @@ mockall::mock! {
     impl PoSAccountingStorageReadTip for StoreTxRw {
         fn get_pool_balance_tip(&self, pool_id: PoolId) -> crate::Result<Option<Amount>>;
         fn get_pool_data_tip(&self, pool_id: PoolId) -> crate::Result<Option<PoolData>>;
+        fn get_pool_id_by_vrf_public_key_tip(
+            &self,
+            vrf_public_key: &VRFPublicKey,
+        ) -> crate::Result<Option<PoolId>>;
         fn get_delegation_balance_tip(
             &self,
             delegation_id: DelegationId,
@@ -508,6 +536,10 @@ mockall::mock! {
     impl PoSAccountingStorageReadSealed for StoreTxRw {
         fn get_pool_balance_sealed(&self, pool_id: PoolId) -> crate::Result<Option<Amount>>;
         fn get_pool_data_sealed(&self, pool_id: PoolId) -> crate::Result<Option<PoolData>>;
+        fn get_pool_id_by_vrf_public_key_sealed(
+            &self,
+            vrf_public_key: &VRFPublicKey,
+        ) -> crate::Result<Option<PoolId>>;
         fn get_delegation_balance_sealed(
             &self,
             delegation_id: DelegationId,
@@ -21,6 +21,7 @@ use common::{
     chain::{DelegationId, PoolId},
     primitives::Amount,
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{
     DelegationData, PoSAccountingStorageRead, PoSAccountingStorageWrite, PoolData,
 };
@@ -30,6 +31,10 @@ use super::{MockStore, MockStoreTxRo, MockStoreTxRw};
 pub trait PoSAccountingStorageReadTip {
     fn get_pool_balance_tip(&self, pool_id: PoolId) -> crate::Result<Option<Amount>>;
     fn get_pool_data_tip(&self, pool_id: PoolId) -> crate::Result<Option<PoolData>>;
+    fn get_pool_id_by_vrf_public_key_tip(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> crate::Result<Option<PoolId>>;
     fn get_delegation_balance_tip(
         &self,
         delegation_id: DelegationId,
@@ -52,6 +57,10 @@ pub trait PoSAccountingStorageReadTip {
 pub trait PoSAccountingStorageReadSealed {
     fn get_pool_balance_sealed(&self, pool_id: PoolId) -> crate::Result<Option<Amount>>;
     fn get_pool_data_sealed(&self, pool_id: PoolId) -> crate::Result<Option<PoolData>>;
+    fn get_pool_id_by_vrf_public_key_sealed(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> crate::Result<Option<PoolId>>;
     fn get_delegation_balance_sealed(
         &self,
         delegation_id: DelegationId,
@@ -151,6 +160,12 @@ macro_rules! impl_sealed_read_ops {
             fn get_pool_data(&self, pool_id: PoolId) -> crate::Result<Option<PoolData>> {
                 self.get_pool_data_sealed(pool_id)
             }
+            fn get_pool_id_by_vrf_public_key(
+                &self,
+                vrf_public_key: &VRFPublicKey,
+            ) -> crate::Result<Option<PoolId>> {
+                self.get_pool_id_by_vrf_public_key_sealed(vrf_public_key)
+            }
             fn get_delegation_balance(
                 &self,
                 delegation_target: DelegationId,
@@ -189,6 +204,12 @@ macro_rules! impl_tip_read_ops {
             fn get_pool_data(&self, pool_id: PoolId) -> crate::Result<Option<PoolData>> {
                 self.get_pool_data_tip(pool_id)
             }
+            fn get_pool_id_by_vrf_public_key(
+                &self,
+                vrf_public_key: &VRFPublicKey,
+            ) -> crate::Result<Option<PoolId>> {
+                self.get_pool_id_by_vrf_public_key_tip(vrf_public_key)
+            }
             fn get_delegation_balance(
                 &self,
                 delegation_target: DelegationId,
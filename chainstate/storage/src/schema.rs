@@ -25,6 +25,7 @@ use common::{
     },
     primitives::{Amount, BlockHeight, Id},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{
     AccountingBlockUndo, DelegationData, DeltaMergeUndo, PoSAccountingDeltaData, PoolData,
 };
@@ -69,6 +70,8 @@ storage::decl_schema! {
 
         /// Store for tip accounting pool data
         pub DBAccountingPoolDataTip: Map<PoolId, PoolData>,
+        /// Index of the tip accounting pool data by the VRF public key
+        pub DBAccountingPoolVrfKeysTip: Map<(VRFPublicKey, PoolId), ()>,
         /// Store for tip accounting pool balances
         pub DBAccountingPoolBalancesTip: Map<PoolId, Amount>,
         /// Store for tip accounting delegation data
@@ -80,6 +83,8 @@ storage::decl_schema! {
 
         /// Store for sealed accounting pool data
         pub DBAccountingPoolDataSealed: Map<PoolId, PoolData>,
+        /// Index of the sealed accounting pool data by the VRF public key
+        pub DBAccountingPoolVrfKeysSealed: Map<(VRFPublicKey, PoolId), ()>,
         /// Store for sealed accounting pool balances
         pub DBAccountingPoolBalancesSealed: Map<PoolId, Amount>,
         /// Store for sealed accounting delegation data
@@ -43,7 +43,7 @@ pub struct BlockBuilder<'f> {
     framework: &'f mut TestFramework,
     transactions: Vec<SignedTransaction>,
     prev_block_hash: Id<GenBlock>,
-    timestamp: BlockTimestamp,
+    timestamp: Option<BlockTimestamp>,
     consensus_data: ConsensusData,
     reward: BlockReward,
     block_source: BlockSource,
@@ -56,7 +56,6 @@ impl<'f> BlockBuilder<'f> {
     pub fn new(framework: &'f mut TestFramework) -> Self {
         let transactions = Vec::new();
         let prev_block_hash = framework.chainstate.get_best_block_id().unwrap();
-        let timestamp = BlockTimestamp::from_time(framework.time_getter.get_time());
         let consensus_data = ConsensusData::None;
         let reward = BlockReward::new(Vec::new());
         let block_source = BlockSource::Local;
@@ -66,7 +65,7 @@ impl<'f> BlockBuilder<'f> {
             framework,
             transactions,
             prev_block_hash,
-            timestamp,
+            timestamp: None,
             consensus_data,
             reward,
             block_source,
@@ -200,9 +199,10 @@ impl<'f> BlockBuilder<'f> {
         self
     }
 
-    /// Overrides the timestamp that is equal to the current time by default.
+    /// Overrides the timestamp that is equal to the current time by default
+    /// (or to the median time past of the parent plus one second if the current time isn't above it).
     pub fn with_timestamp(mut self, timestamp: BlockTimestamp) -> Self {
-        self.timestamp = timestamp;
+        self.timestamp = Some(timestamp);
         self
     }
 
@@ -223,14 +223,37 @@ impl<'f> BlockBuilder<'f> {
         self
     }
 
+    /// The block timestamp must be strictly above the median time past of the parent,
+    /// which may not be the case for the current time if it wasn't progressed between blocks.
+    fn default_timestamp(&self) -> BlockTimestamp {
+        let current_time = BlockTimestamp::from_time(self.framework.time_getter.get_time());
+        let parent_known = self
+            .framework
+            .chainstate
+            .get_gen_block_index(&self.prev_block_hash)
+            .unwrap()
+            .is_some();
+        if !parent_known {
+            return current_time;
+        }
+
+        let median_time_past = self
+            .framework
+            .chainstate
+            .calculate_median_time_past(&self.prev_block_hash)
+            .unwrap();
+        std::cmp::max(current_time, median_time_past.add_int_seconds(1).unwrap())
+    }
+
     fn build_impl(self) -> (Block, &'f mut TestFramework) {
+        let timestamp = self.timestamp.unwrap_or_else(|| self.default_timestamp());
         let block_body = BlockBody::new(self.reward, self.transactions);
         let merkle_proxy = block_body.merkle_tree_proxy().unwrap();
         let unsigned_header = BlockHeader::new(
             self.prev_block_hash,
             merkle_proxy.merkle_tree().root(),
             merkle_proxy.witness_merkle_tree().root(),
-            self.timestamp,
+            timestamp,
             self.consensus_data,
         );
 
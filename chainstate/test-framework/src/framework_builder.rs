@@ -57,6 +57,7 @@ impl TestFrameworkBuilder {
     pub fn new(rng: &mut (impl Rng + CryptoRng)) -> Self {
         let chain_config = ChainConfigBuilder::new(ChainType::Mainnet)
             .net_upgrades(NetUpgrades::unit_tests())
+            .chainstate_upgrades(NetUpgrades::latest_chainstate_upgrades())
             .genesis_unittest(Destination::AnyoneCanSpend)
             .build();
         let chainstate_config = ChainstateConfig {
@@ -65,6 +66,10 @@ impl TestFrameworkBuilder {
             min_max_bootstrap_import_buffer_sizes: Default::default(),
             tx_index_enabled: rng.gen::<bool>().into(),
             max_tip_age: Default::default(),
+            commit_batch_size: Default::default(),
+            prune_depth: Default::default(),
+            reindex_tx_index: Default::default(),
+            max_blocks_pruned_at_once: Default::default(),
         };
         let chainstate_storage = TestStore::new_empty().unwrap();
         let time_getter = None;
@@ -106,6 +106,7 @@ pub fn create_utxo_data(
             TokenData::NftIssuance(_issuance) => {
                 new_token_transfer_output(chainstate, &outsrc, Amount::from_atoms(1))
             }
+            TokenData::TokenFreezeV1(_) | TokenData::TokenUnfreezeV1(_) => return None,
         },
     };
 
@@ -233,6 +234,7 @@ pub fn create_multiple_utxo_data(
                     vec![new_token_transfer_output(chainstate, &outsrc, Amount::from_atoms(1))]
                 }
             }
+            TokenData::TokenFreezeV1(_) | TokenData::TokenUnfreezeV1(_) => return None,
         },
     };
 
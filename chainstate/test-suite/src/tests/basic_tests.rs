@@ -14,7 +14,8 @@
 // limitations under the License.
 
 use chainstate_test_framework::TestFramework;
-use common::primitives::Idable;
+use common::chain::GenBlock;
+use common::primitives::{Id, Idable, H256};
 use rstest::rstest;
 use test_utils::random::make_seedable_rng;
 use test_utils::random::Seed;
@@ -61,3 +62,57 @@ fn test_is_block_in_main_chain(#[case] seed: Seed) {
     let block3_id = tf.index_at(3).block_id();
     assert!(!tf.chainstate.is_block_in_main_chain(&(*block3_id).into()).unwrap());
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn test_get_block_heights_in_main_chain(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let mut tf = TestFramework::builder(&mut rng).build();
+
+    tf.create_chain(&tf.genesis().get_id().into(), 2, &mut rng).unwrap();
+    tf.create_chain(&tf.genesis().get_id().into(), 1, &mut rng).unwrap();
+
+    let genesis_id: Id<GenBlock> = tf.genesis().get_id().into();
+    let block1_id: Id<GenBlock> = (*tf.index_at(1).block_id()).into();
+    let block2_id: Id<GenBlock> = (*tf.index_at(2).block_id()).into();
+    let block3_id: Id<GenBlock> = (*tf.index_at(3).block_id()).into();
+
+    let heights = tf
+        .chainstate
+        .get_block_heights_in_main_chain(&[block2_id, block3_id, genesis_id, block1_id])
+        .unwrap();
+    assert_eq!(
+        heights,
+        vec![Some(2.into()), None, Some(0.into()), Some(1.into())]
+    );
+
+    let heights = tf.chainstate.get_block_heights_in_main_chain(&[]).unwrap();
+    assert!(heights.is_empty());
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn test_has_block(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let mut tf = TestFramework::builder(&mut rng).build();
+
+    tf.create_chain(&tf.genesis().get_id().into(), 2, &mut rng).unwrap();
+    tf.create_chain(&tf.genesis().get_id().into(), 1, &mut rng).unwrap();
+
+    // Both mainchain and stale blocks are known
+    let known_ids: Vec<_> = (1..=3).map(|idx| *tf.index_at(idx).block_id()).collect();
+    // A block that has been built but never processed is not known
+    let unknown_block = tf.make_block_builder().build();
+    let unknown_ids = [unknown_block.get_id(), Id::new(H256::random_using(&mut rng))];
+
+    for id in known_ids {
+        assert!(tf.chainstate.has_block(&id).unwrap());
+        assert!(tf.chainstate.get_block_index(&id).unwrap().is_some());
+    }
+    for id in unknown_ids {
+        assert!(!tf.chainstate.has_block(&id).unwrap());
+        assert!(tf.chainstate.get_block_index(&id).unwrap().is_none());
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::{max, min};
+
+use chainstate::{BootstrapError, ChainstateConfig, ChainstateError};
+use chainstate_test_framework::{TestFramework, TestStore};
+use chainstate_types::PropertyQueryError;
+use common::{
+    chain::{
+        config::{Builder as ChainConfigBuilder, ChainType},
+        Block, ChainConfig, GenBlockId, NetUpgrades,
+    },
+    primitives::{BlockDistance, BlockHeight, Id, Idable},
+};
+use crypto::random::Rng;
+use rstest::rstest;
+use test_utils::random::{make_seedable_rng, Seed};
+
+const MAX_DEPTH_FOR_REORG: u64 = 5;
+
+fn make_chain_config() -> ChainConfig {
+    ChainConfigBuilder::new(ChainType::Regtest)
+        .net_upgrades(NetUpgrades::unit_tests())
+        .max_depth_for_reorg(BlockDistance::new(MAX_DEPTH_FOR_REORG as i64))
+        .build()
+}
+
+fn make_test_framework(rng: &mut impl Rng, prune_depth: u64) -> TestFramework {
+    TestFramework::builder(rng)
+        .with_chain_config(make_chain_config())
+        .with_chainstate_config(ChainstateConfig::new().with_prune_depth(Some(prune_depth)))
+        .build()
+}
+
+fn mainchain_block_id(tf: &TestFramework, height: u64) -> Id<Block> {
+    match tf.block_id(height).classify(tf.chainstate.get_chain_config()) {
+        GenBlockId::Block(id) => id,
+        GenBlockId::Genesis(_) => panic!("genesis at non-zero height"),
+    }
+}
+
+// Bodies of the blocks buried deep enough are removed, while the indices stay and the chain can
+// still be extended and reorged within the allowed depth.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn pruned_blocks_keep_chain_valid(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let prune_depth = rng.gen_range(1..10);
+        let mut tf = make_test_framework(&mut rng, prune_depth);
+
+        let chain_length = rng.gen_range(15..30);
+        let genesis_id = tf.genesis().get_id().into();
+        tf.create_chain(&genesis_id, chain_length, &mut rng).unwrap();
+
+        // Blocks above the reorg limit are never pruned
+        let chain_length = chain_length as u64;
+        let last_pruned_height = min(
+            chain_length - prune_depth,
+            chain_length - MAX_DEPTH_FOR_REORG - 1,
+        );
+
+        for height in 1..=chain_length {
+            let block_id = mainchain_block_id(&tf, height);
+            assert!(tf.chainstate.get_block_index(&block_id).unwrap().is_some());
+            let block = tf.chainstate.get_block(block_id).unwrap();
+            assert_eq!(block.is_none(), height <= last_pruned_height);
+        }
+
+        assert_eq!(
+            tf.chainstate.get_mainchain_blocks(BlockHeight::new(1), 1).unwrap_err(),
+            ChainstateError::FailedToReadProperty(PropertyQueryError::BlockNotFound(
+                mainchain_block_id(&tf, 1)
+            ))
+        );
+
+        // The blockchain can't be exported
+        let writer: std::io::BufWriter<Box<dyn std::io::Write + Send>> =
+            std::io::BufWriter::new(Box::new(Vec::new()));
+        assert_eq!(
+            tf.chainstate.export_bootstrap_stream(writer, false).unwrap_err(),
+            ChainstateError::BootstrapError(BootstrapError::BlocksPruned)
+        );
+
+        // A reorg within the allowed depth still works
+        let fork_point = tf.block_id(chain_length - 2);
+        let fork_tip = tf.create_chain(&fork_point, 3, &mut rng).unwrap();
+        assert_eq!(tf.best_block_id(), fork_tip);
+        assert_eq!(
+            tf.best_block_index().block_height(),
+            BlockHeight::new(chain_length + 1)
+        );
+
+        // And the chain can be extended further
+        tf.create_chain(&fork_tip, 1, &mut rng).unwrap();
+        assert_eq!(
+            tf.best_block_index().block_height(),
+            BlockHeight::new(chain_length + 2)
+        );
+    });
+}
+
+// Enabling pruning for an existing database removes the old bodies gradually, starting from the
+// oldest ones, no more than the configured number per connected block.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn pruning_enabled_for_existing_chain(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let storage = TestStore::new_empty().unwrap();
+
+        let chain_length = rng.gen_range(30..50);
+        {
+            let mut tf = TestFramework::builder(&mut rng)
+                .with_chain_config(make_chain_config())
+                .with_storage(storage.clone())
+                .build();
+            let genesis_id = tf.genesis().get_id().into();
+            tf.create_chain(&genesis_id, chain_length, &mut rng).unwrap();
+        }
+
+        let prune_depth = rng.gen_range(1..10);
+        let max_blocks_pruned_at_once = rng.gen_range(2..5);
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(make_chain_config())
+            .with_chainstate_config(
+                ChainstateConfig::new()
+                    .with_prune_depth(Some(prune_depth))
+                    .with_max_blocks_pruned_at_once(max_blocks_pruned_at_once),
+            )
+            .with_storage(storage)
+            .build();
+
+        let mut tip_height = chain_length as u64;
+        let mut pruned_count = 0;
+        while pruned_count < tip_height - max(prune_depth, MAX_DEPTH_FOR_REORG + 1) {
+            let tip_id = tf.best_block_id();
+            tf.create_chain(&tip_id, 1, &mut rng).unwrap();
+            tip_height += 1;
+
+            let last_pruned_height = min(
+                tip_height - prune_depth,
+                tip_height - MAX_DEPTH_FOR_REORG - 1,
+            );
+            pruned_count = min(pruned_count + max_blocks_pruned_at_once, last_pruned_height);
+
+            for height in 1..=tip_height {
+                let block = tf.chainstate.get_block(mainchain_block_id(&tf, height)).unwrap();
+                assert_eq!(block.is_none(), height <= pruned_count);
+            }
+        }
+    });
+}
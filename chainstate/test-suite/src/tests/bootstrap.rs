@@ -18,7 +18,7 @@ use std::collections::BTreeSet;
 use std::io::BufWriter;
 
 use chainstate::chainstate_interface::ChainstateInterface;
-use chainstate::ChainstateConfig;
+use chainstate::{BlockError, BootstrapError, ChainstateConfig, ChainstateError, OrphanCheckError};
 use chainstate_test_framework::TestFramework;
 use common::chain::Block;
 use common::primitives::Id;
@@ -274,3 +274,44 @@ fn bootstrap_tests(#[case] seed: Seed) {
         }
     });
 }
+
+// Blocks from a bootstrap file are processed with `BlockSource::BootstrapFile`, so a missing block
+// in the file aborts the import without the orphans being stored (unlike local orphans).
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn bootstrap_with_missing_block(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf1 = TestFramework::builder(&mut rng).build();
+        let genesis_id = tf1.genesis().get_id();
+        tf1.create_chain(&genesis_id.into(), 5, &mut rng).unwrap();
+
+        // Make a bootstrap file without the second block
+        let mainchain_blocks = tf1.chainstate.get_mainchain_blocks_list().unwrap();
+        let magic_bytes = tf1.chain_config().magic_bytes().to_vec();
+        let mut bootstrap = Vec::new();
+        for block_id in mainchain_blocks.iter().take(1).chain(mainchain_blocks.iter().skip(2)) {
+            bootstrap.extend_from_slice(&magic_bytes);
+            bootstrap.extend(tf1.chainstate.get_block(*block_id).unwrap().unwrap().encode());
+        }
+
+        let mut tf2 = TestFramework::builder(&mut rng).build();
+        let reader: std::io::BufReader<Box<dyn std::io::Read + Send>> =
+            std::io::BufReader::new(Box::new(bootstrap.as_slice()));
+
+        assert_eq!(
+            tf2.chainstate.import_bootstrap_stream(reader).unwrap_err(),
+            ChainstateError::BootstrapError(BootstrapError::BlockProcessing(
+                BlockError::OrphanCheckFailed(OrphanCheckError::BootstrapFileOrphan)
+            ))
+        );
+
+        // The blocks before the gap are imported, the orphan is not kept
+        assert_eq!(
+            tf2.chainstate.get_mainchain_blocks_list().unwrap(),
+            mainchain_blocks[..1].to_vec()
+        );
+        assert_eq!(tf2.chainstate.orphans_count(), 0);
+    });
+}
@@ -128,6 +128,7 @@ fn store_token(#[case] seed: Seed) {
         let storage = Store::new_empty().unwrap();
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).with_storage(storage.clone()).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
 
         // issue a token
         let tx = TransactionBuilder::new()
@@ -141,7 +142,7 @@ fn store_token(#[case] seed: Seed) {
             .add_output(TxOutput::Transfer(
                 TokenIssuance {
                     token_ticker: "XXXX".as_bytes().to_vec(),
-                    amount_to_issue: Amount::from_atoms(rng.gen_range(1..u128::MAX)),
+                    amount_to_issue: Amount::from_atoms(rng.gen_range(1..=token_max_supply)),
                     number_of_decimals: rng.gen_range(1..18),
                     metadata_uri: "http://uri".as_bytes().to_vec(),
                 }
@@ -386,6 +387,7 @@ fn reorg_store_token(#[case] seed: Seed) {
         let storage = Store::new_empty().unwrap();
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).with_storage(storage.clone()).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
         let genesis_id = tf.genesis().get_id();
 
         // create block
@@ -400,7 +402,7 @@ fn reorg_store_token(#[case] seed: Seed) {
             .add_output(TxOutput::Transfer(
                 TokenIssuance {
                     token_ticker: "AAAA".as_bytes().to_vec(),
-                    amount_to_issue: Amount::from_atoms(rng.gen_range(1..u128::MAX)),
+                    amount_to_issue: Amount::from_atoms(rng.gen_range(1..=token_max_supply)),
                     number_of_decimals: rng.gen_range(1..18),
                     metadata_uri: "http://uri".as_bytes().to_vec(),
                 }
@@ -422,7 +424,7 @@ fn reorg_store_token(#[case] seed: Seed) {
         tf.process_block(block_1, BlockSource::Local).unwrap();
 
         // create parallel chain
-        let bbbb_tokens_amount = Amount::from_atoms(rng.gen_range(1..u128::MAX));
+        let bbbb_tokens_amount = Amount::from_atoms(rng.gen_range(1..=token_max_supply));
         let tx_2 = TransactionBuilder::new()
             .add_input(
                 TxInput::from_utxo(
@@ -718,6 +720,10 @@ fn reorg_store_coin_no_tx_index(#[case] seed: Seed, #[case] tx_index_enabled: bo
                 max_orphan_blocks: Default::default(),
                 min_max_bootstrap_import_buffer_sizes: Default::default(),
                 max_tip_age: Default::default(),
+                commit_batch_size: Default::default(),
+                prune_depth: Default::default(),
+                reindex_tx_index: Default::default(),
+                max_blocks_pruned_at_once: Default::default(),
             };
 
             let tf_build_error = TestFramework::builder(&mut rng)
@@ -736,3 +742,220 @@ fn reorg_store_coin_no_tx_index(#[case] seed: Seed, #[case] tx_index_enabled: bo
         }
     });
 }
+
+// Process a batch of blocks and check that all of them are committed to the storage at once,
+// then reopen the chainstate on the same storage and check that the tip is the last block.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn process_block_batch_persists_all_blocks(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+
+        let mut tf1 = TestFramework::builder(&mut rng).build();
+        let blocks_count = rng.gen_range(2..20);
+        let mut blocks = Vec::with_capacity(blocks_count);
+        for _ in 0..blocks_count {
+            let block =
+                tf1.make_block_builder().add_test_transaction_from_best_block(&mut rng).build();
+            tf1.process_block(block.clone(), BlockSource::Local).unwrap();
+            blocks.push(block);
+        }
+        let last_block_id = blocks.last().unwrap().get_id();
+
+        let storage = Store::new_empty().unwrap();
+        {
+            let mut tf2 = TestFramework::builder(&mut rng).with_storage(storage.clone()).build();
+            let new_tip =
+                tf2.chainstate.process_block_batch(blocks.clone(), BlockSource::Peer).unwrap();
+            assert_eq!(new_tip.map(|bi| *bi.block_id()), Some(last_block_id));
+        }
+
+        {
+            let db_tx = storage.transaction_ro().unwrap();
+            assert_eq!(
+                db_tx.get_best_block_id().unwrap(),
+                Some(Id::<GenBlock>::from(last_block_id))
+            );
+            for block in &blocks {
+                assert!(db_tx.get_block_index(&block.get_id()).unwrap().is_some());
+                assert_eq!(
+                    db_tx.get_block(block.get_id()).unwrap().as_ref(),
+                    Some(block)
+                );
+            }
+        }
+
+        let tf3 = TestFramework::builder(&mut rng).with_storage(storage).build();
+        assert_eq!(tf3.best_block_id(), Id::<GenBlock>::from(last_block_id));
+    });
+}
+
+// Look up the block containing a transaction with and without tx index.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy(), true)]
+#[case(Seed::from_entropy(), false)]
+fn get_transaction_block(#[case] seed: Seed, #[case] tx_index_enabled: bool) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let config = chainstate::ChainstateConfig {
+            tx_index_enabled: tx_index_enabled.into(),
+            ..Default::default()
+        };
+        let mut tf = TestFramework::builder(&mut rng).with_chainstate_config(config).build();
+
+        let tx = TransactionBuilder::new()
+            .add_input(
+                TxInput::from_utxo(
+                    OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                    0,
+                ),
+                empty_witness(&mut rng),
+            )
+            .add_output(TxOutput::Transfer(
+                OutputValue::Coin(Amount::from_atoms(100)),
+                anyonecanspend_address(),
+            ))
+            .build();
+        let tx_id = tx.transaction().get_id();
+
+        let block = tf.make_block_builder().add_transaction(tx).build();
+        let block_id = block.get_id();
+        tf.process_block(block, BlockSource::Local).unwrap();
+
+        let unknown_tx_id =
+            Id::<Transaction>::new(common::primitives::H256::random_using(&mut rng));
+
+        if tx_index_enabled {
+            assert_eq!(
+                tf.chainstate.get_transaction_block(&tx_id).unwrap(),
+                Some((block_id, BlockHeight::new(1)))
+            );
+            assert_eq!(
+                tf.chainstate.get_transaction_block(&unknown_tx_id).unwrap(),
+                None
+            );
+        } else {
+            for id in [tx_id, unknown_tx_id] {
+                assert_eq!(
+                    tf.chainstate.get_transaction_block(&id),
+                    Err(chainstate::ChainstateError::FailedToReadProperty(
+                        chainstate::PropertyQueryError::TransactionIndexDisabled
+                    ))
+                );
+            }
+        }
+    });
+}
+
+// Enable the tx index for a chain that was built without it and check that the rebuilt index is
+// the same as the one maintained by a node that had it enabled from the start.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn rebuild_tx_index(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let storage = Store::new_empty().unwrap();
+        let mut rng = make_seedable_rng(seed);
+
+        let config_no_index = chainstate::ChainstateConfig {
+            tx_index_enabled: false.into(),
+            ..Default::default()
+        };
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chainstate_config(config_no_index)
+            .with_storage(storage.clone())
+            .build();
+        let genesis_id = tf.genesis().get_id();
+
+        let tx_1 = TransactionBuilder::new()
+            .add_input(
+                TxInput::from_utxo(OutPointSourceId::BlockReward(genesis_id.into()), 0),
+                empty_witness(&mut rng),
+            )
+            .add_output(TxOutput::Transfer(
+                OutputValue::Coin(Amount::from_atoms(300)),
+                anyonecanspend_address(),
+            ))
+            .build();
+        let tx_1_id = tx_1.transaction().get_id();
+        let block_1 = tf.make_block_builder().add_transaction(tx_1).build();
+        let block_1_id = block_1.get_id();
+        tf.process_block(block_1.clone(), BlockSource::Local).unwrap();
+
+        let tx_2 = TransactionBuilder::new()
+            .add_input(
+                TxInput::from_utxo(OutPointSourceId::Transaction(tx_1_id), 0),
+                empty_witness(&mut rng),
+            )
+            .add_output(TxOutput::Transfer(
+                OutputValue::Coin(Amount::from_atoms(100)),
+                anyonecanspend_address(),
+            ))
+            .build();
+        let tx_2_id = tx_2.transaction().get_id();
+        let block_2 = tf.make_block_builder().add_transaction(tx_2).build();
+        let block_2_id = block_2.get_id();
+        tf.process_block(block_2.clone(), BlockSource::Local).unwrap();
+        drop(tf);
+
+        let config_index = chainstate::ChainstateConfig {
+            tx_index_enabled: true.into(),
+            ..Default::default()
+        };
+
+        // Enabling the index without the reindex option is still an error
+        let tf_build_error = TestFramework::builder(&mut rng)
+            .with_chainstate_config(config_index.clone())
+            .with_storage(storage.clone())
+            .try_build()
+            .err()
+            .expect("fail");
+        assert_eq!(
+            tf_build_error,
+            chainstate::ChainstateError::ProcessBlockError(
+                chainstate::BlockError::TxIndexConfigError
+            )
+        );
+
+        // The reference node maintains the index from the start
+        let reference_storage = Store::new_empty().unwrap();
+        let mut tf_reference = TestFramework::builder(&mut rng)
+            .with_chainstate_config(config_index.clone())
+            .with_storage(reference_storage.clone())
+            .build();
+        tf_reference.process_block(block_1, BlockSource::Local).unwrap();
+        tf_reference.process_block(block_2, BlockSource::Local).unwrap();
+
+        let tf = TestFramework::builder(&mut rng)
+            .with_chainstate_config(config_index.with_reindex_tx_index(true))
+            .with_storage(storage.clone())
+            .build();
+        assert_eq!(
+            tf.chainstate.get_transaction_block(&tx_2_id).unwrap(),
+            Some((block_2_id, BlockHeight::new(2)))
+        );
+
+        assert_eq!(
+            storage.get_is_mainchain_tx_index_enabled().unwrap(),
+            Some(true)
+        );
+        let db_tx = storage.transaction_ro().unwrap();
+        let reference_db_tx = reference_storage.transaction_ro().unwrap();
+        for source_id in [
+            OutPointSourceId::BlockReward(genesis_id.into()),
+            OutPointSourceId::BlockReward(block_1_id.into()),
+            OutPointSourceId::BlockReward(block_2_id.into()),
+            OutPointSourceId::Transaction(tx_1_id),
+            OutPointSourceId::Transaction(tx_2_id),
+        ] {
+            let tx_index = db_tx.get_mainchain_tx_index(&source_id).unwrap();
+            assert!(tx_index.is_some());
+            assert_eq!(
+                tx_index,
+                reference_db_tx.get_mainchain_tx_index(&source_id).unwrap()
+            );
+        }
+    });
+}
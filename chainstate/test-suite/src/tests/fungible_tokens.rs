@@ -13,24 +13,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::vec;
+use std::{sync::Arc, vec};
 
 use chainstate::{
     BlockError, BlockSource, ChainstateError, CheckBlockError, CheckBlockTransactionsError,
     ConnectTransactionError, TokensError,
 };
-use chainstate_test_framework::{get_output_value, TestFramework, TransactionBuilder};
-use common::chain::tokens::{Metadata, NftIssuance, TokenIssuance, TokenTransfer};
+use chainstate_test_framework::{get_output_value, TestFramework, TestStore, TransactionBuilder};
+use common::chain::tokens::{Metadata, NftIssuance, RPCTokenInfo, TokenIssuance, TokenTransfer};
 use common::chain::UtxoOutPoint;
 use common::primitives::{id, Id};
 use common::{
     chain::{
+        block::timestamp::BlockTimestamp,
         output_value::OutputValue,
         signature::inputsig::InputWitness,
         tokens::{token_id, TokenData, TokenId},
-        Destination, OutPointSourceId, TxInput, TxOutput,
+        ChainstateUpgrade, Destination, ExactPoWReward, NetUpgrades, NftMetadataSizeLimit,
+        OutPointSourceId, StrictMedianTimePast, TokenFreezeActivated, TokenMaxSupplyLimit,
+        TokensConservation, TxInput, TxOutput, UniquePoolVrfKeys,
     },
-    primitives::{Amount, Idable},
+    primitives::{Amount, BlockHeight, Idable},
 };
 use crypto::{hash::StreamHasher, random::Rng};
 use expect_test::expect;
@@ -38,10 +41,18 @@ use rstest::rstest;
 use serialization::extras::non_empty_vec::DataOrNoVec;
 use test_utils::{
     gen_text_with_non_ascii,
+    nft_utils::{random_nft_issuance, random_token_issuance},
     random::{make_seedable_rng, Seed},
     random_string,
 };
-use tx_verifier::error::TokenIssuanceError;
+use tx_verifier::{
+    error::{TokenIssuanceError, UriError},
+    transaction_verifier::{
+        config::TransactionVerifierConfig, TransactionSourceForConnect, TransactionVerifier,
+    },
+};
+
+use super::helpers::in_memory_storage_wrapper::InMemoryStorageWrapper;
 
 #[rstest]
 #[trace]
@@ -50,6 +61,7 @@ fn token_issue_test(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
         let outpoint_source_id: OutPointSourceId = tf.genesis().get_id().into();
 
         let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
@@ -68,7 +80,9 @@ fn token_issue_test(#[case] seed: Seed) {
                             token_ticker: random_string(&mut rng, 10..u16::MAX as usize)
                                 .as_bytes()
                                 .to_vec(),
-                            amount_to_issue: Amount::from_atoms(rng.gen_range(1..u128::MAX)),
+                            amount_to_issue: Amount::from_atoms(
+                                rng.gen_range(1..=token_max_supply),
+                            ),
                             number_of_decimals: rng.gen_range(1..18),
                             metadata_uri: random_string(&mut rng, 1..1024).as_bytes().to_vec(),
                         }
@@ -85,7 +99,7 @@ fn token_issue_test(#[case] seed: Seed) {
             Err(ChainstateError::ProcessBlockError(
                 BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                     CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                        TokenIssuanceError::IssueErrorInvalidTickerLength,
+                        TokenIssuanceError::IssueErrorInvalidTickerLength(_, _),
                         _,
                         _
                     ))
@@ -105,7 +119,9 @@ fn token_issue_test(#[case] seed: Seed) {
                     .add_output(TxOutput::Transfer(
                         TokenIssuance {
                             token_ticker: b"".to_vec(),
-                            amount_to_issue: Amount::from_atoms(rng.gen_range(1..u128::MAX)),
+                            amount_to_issue: Amount::from_atoms(
+                                rng.gen_range(1..=token_max_supply),
+                            ),
                             number_of_decimals: rng.gen_range(1..18),
                             metadata_uri: random_string(&mut rng, 1..1024).as_bytes().to_vec(),
                         }
@@ -122,7 +138,7 @@ fn token_issue_test(#[case] seed: Seed) {
             Err(ChainstateError::ProcessBlockError(
                 BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                     CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                        TokenIssuanceError::IssueErrorInvalidTickerLength,
+                        TokenIssuanceError::IssueErrorInvalidTickerLength(_, _),
                         _,
                         _
                     ))
@@ -157,7 +173,7 @@ fn token_issue_test(#[case] seed: Seed) {
                                 TokenIssuance {
                                     token_ticker,
                                     amount_to_issue: Amount::from_atoms(
-                                        rng.gen_range(1..u128::MAX),
+                                        rng.gen_range(1..=token_max_supply),
                                     ),
                                     number_of_decimals: rng.gen_range(1..18),
                                     metadata_uri: random_string(&mut rng, 1..1024)
@@ -177,7 +193,7 @@ fn token_issue_test(#[case] seed: Seed) {
                     Err(ChainstateError::ProcessBlockError(
                         BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                             CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                                TokenIssuanceError::IssueErrorTickerHasNoneAlphaNumericChar,
+                                TokenIssuanceError::IssueErrorTickerHasNoneAlphaNumericChar(_),
                                 _,
                                 _
                             ))
@@ -239,7 +255,9 @@ fn token_issue_test(#[case] seed: Seed) {
                         .add_output(TxOutput::Transfer(
                             TokenIssuance {
                                 token_ticker: random_string(&mut rng, 1..5).as_bytes().to_vec(),
-                                amount_to_issue: Amount::from_atoms(rng.gen_range(1..u128::MAX)),
+                                amount_to_issue: Amount::from_atoms(
+                                    rng.gen_range(1..=token_max_supply),
+                                ),
                                 number_of_decimals: decimals_count_to_use,
                                 metadata_uri: random_string(&mut rng, 1..1024).as_bytes().to_vec(),
                             }
@@ -251,17 +269,18 @@ fn token_issue_test(#[case] seed: Seed) {
                 )
                 .build_and_process();
 
+            let max_decimals = tf.chainstate.get_chain_config().token_max_dec_count();
             assert!(matches!(
                 result,
                 Err(ChainstateError::ProcessBlockError(
                     BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                         CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                            TokenIssuanceError::IssueErrorTooManyDecimals,
+                            TokenIssuanceError::IssueErrorTooManyDecimals(decimals, max),
                             _,
                             _
                         ))
                     ))
-                ))
+                )) if decimals == decimals_count_to_use && max == max_decimals
             ));
         }
 
@@ -281,7 +300,9 @@ fn token_issue_test(#[case] seed: Seed) {
                         .add_output(TxOutput::Transfer(
                             TokenIssuance {
                                 token_ticker: random_string(&mut rng, 1..5).as_bytes().to_vec(),
-                                amount_to_issue: Amount::from_atoms(rng.gen_range(1..u128::MAX)),
+                                amount_to_issue: Amount::from_atoms(
+                                    rng.gen_range(1..=token_max_supply),
+                                ),
                                 number_of_decimals: rng.gen_range(1..18),
                                 metadata_uri: random_string(&mut rng, uri_len_range_to_use)
                                     .as_bytes()
@@ -300,7 +321,10 @@ fn token_issue_test(#[case] seed: Seed) {
                 Err(ChainstateError::ProcessBlockError(
                     BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                         CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                            TokenIssuanceError::IssueErrorIncorrectMetadataURI,
+                            TokenIssuanceError::IssueErrorIncorrectMetadataURI(UriError::TooLong(
+                                _,
+                                _
+                            )),
                             _,
                             _
                         ))
@@ -321,7 +345,9 @@ fn token_issue_test(#[case] seed: Seed) {
                     .add_output(TxOutput::Transfer(
                         TokenIssuance {
                             token_ticker: random_string(&mut rng, 1..5).as_bytes().to_vec(),
-                            amount_to_issue: Amount::from_atoms(rng.gen_range(1..u128::MAX)),
+                            amount_to_issue: Amount::from_atoms(
+                                rng.gen_range(1..=token_max_supply),
+                            ),
                             number_of_decimals: rng.gen_range(1..18),
                             metadata_uri: "https://💖🚁🌭.🦠🚀🚖🚧".as_bytes().to_vec(),
                         }
@@ -338,7 +364,9 @@ fn token_issue_test(#[case] seed: Seed) {
             Err(ChainstateError::ProcessBlockError(
                 BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                     CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                        TokenIssuanceError::IssueErrorIncorrectMetadataURI,
+                        TokenIssuanceError::IssueErrorIncorrectMetadataURI(
+                            UriError::IllegalCharacter('💖')
+                        ),
                         _,
                         _
                     ))
@@ -349,7 +377,7 @@ fn token_issue_test(#[case] seed: Seed) {
         // Valid case
         let output_value = TokenIssuance {
             token_ticker: random_string(&mut rng, 1..5).as_bytes().to_vec(),
-            amount_to_issue: Amount::from_atoms(rng.gen_range(1..u128::MAX)),
+            amount_to_issue: Amount::from_atoms(rng.gen_range(1..=token_max_supply)),
             number_of_decimals: rng.gen_range(1..18),
             metadata_uri: random_string(&mut rng, 1..1024).as_bytes().to_vec(),
         };
@@ -387,8 +415,9 @@ fn token_transfer_test(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
         // To have possibility to send exceed tokens amount than we have, let's limit the max issuance tokens amount
-        let total_funds = Amount::from_atoms(rng.gen_range(1..u128::MAX - 1));
+        let total_funds = Amount::from_atoms(rng.gen_range(1..token_max_supply));
         let genesis_outpoint_id: OutPointSourceId = tf.genesis().get_id().into();
 
         let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
@@ -573,7 +602,8 @@ fn multiple_token_issuance_in_one_tx(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
-        let total_funds = Amount::from_atoms(rng.gen_range(1..u128::MAX));
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+        let total_funds = Amount::from_atoms(rng.gen_range(1..=token_max_supply));
         let genesis_outpoint_id: OutPointSourceId = tf.genesis().get_id().into();
 
         let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
@@ -651,7 +681,8 @@ fn token_issuance_with_insufficient_fee(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
-        let total_funds = Amount::from_atoms(rng.gen_range(1..u128::MAX));
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+        let total_funds = Amount::from_atoms(rng.gen_range(1..=token_max_supply));
         let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
 
         let coins_value =
@@ -733,8 +764,9 @@ fn transfer_split_and_combine_tokens(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
         // Due to transfer a piece of funds, let's limit the start range value
-        let total_funds = Amount::from_atoms(rng.gen_range(4..u128::MAX - 1));
+        let total_funds = Amount::from_atoms(rng.gen_range(4..token_max_supply));
         let quarter_funds = (total_funds / 4).unwrap();
 
         let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
@@ -839,11 +871,12 @@ fn burn_tokens(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
         // Due to burn a piece of funds, let's limit the start range value
-        let total_funds = Amount::from_atoms(rng.gen_range(4..u128::MAX - 1));
+        let total_funds = Amount::from_atoms(rng.gen_range(4..token_max_supply));
         // Round down
         let half_funds = (total_funds / 2).unwrap();
-        let quarter_funds = (total_funds / 4).unwrap();
+        let quarter_funds = (half_funds / 2).unwrap();
 
         let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
 
@@ -907,7 +940,39 @@ fn burn_tokens(#[case] seed: Seed) {
             ))
         ));
 
-        // Valid case: Burn 25% with burn data, and burn 25% by not specifying an output, and transfer the remaining 50%
+        // Try to burn 50% by not specifying an output, and transfer the remaining 50%
+        let result = tf
+            .make_block_builder()
+            .add_transaction(
+                TransactionBuilder::new()
+                    .add_input(
+                        TxInput::from_utxo(issuance_outpoint_id.clone(), 0),
+                        InputWitness::NoSignature(None),
+                    )
+                    .add_output(TxOutput::Transfer(
+                        TokenData::TokenTransfer(TokenTransfer {
+                            token_id,
+                            amount: half_funds,
+                        })
+                        .into(),
+                        Destination::AnyoneCanSpend,
+                    ))
+                    .build(),
+            )
+            .build_and_process();
+
+        assert_eq!(
+            result.unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(
+                ConnectTransactionError::TokensError(TokensError::TokensNotConserved(
+                    token_id,
+                    total_funds,
+                    half_funds
+                ))
+            ))
+        );
+
+        // Valid case: Burn 50% with burn data and transfer the remaining 50%
         let block_index = tf
             .make_block_builder()
             .add_transaction(
@@ -919,7 +984,7 @@ fn burn_tokens(#[case] seed: Seed) {
                     .add_output(TxOutput::Burn(
                         TokenTransfer {
                             token_id,
-                            amount: quarter_funds,
+                            amount: (total_funds - half_funds).unwrap(),
                         }
                         .into(),
                     ))
@@ -952,7 +1017,7 @@ fn burn_tokens(#[case] seed: Seed) {
                     .add_output(TxOutput::Burn(
                         TokenTransfer {
                             token_id,
-                            amount: quarter_funds,
+                            amount: (half_funds - quarter_funds).unwrap(),
                         }
                         .into(),
                     ))
@@ -1048,7 +1113,8 @@ fn reorg_and_try_to_double_spend_tokens(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
-        let total_funds = Amount::from_atoms(rng.gen_range(1..u128::MAX));
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+        let total_funds = Amount::from_atoms(rng.gen_range(1..=token_max_supply));
         // Issue a new token
         let genesis_outpoint_id = OutPointSourceId::BlockReward(tf.genesis().get_id().into());
         let issuance_data = TokenIssuance {
@@ -1338,8 +1404,8 @@ fn attempt_to_print_tokens_one_output(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
-        // To avoid CoinOrTokenOverflow, random value can't be more than u128::MAX / 2
-        let total_funds = Amount::from_atoms(rng.gen_range(1..u128::MAX / 2));
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+        let total_funds = Amount::from_atoms(rng.gen_range(1..=token_max_supply));
 
         let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
 
@@ -1436,8 +1502,8 @@ fn attempt_to_print_tokens_two_outputs(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
-        // To avoid CoinOrTokenOverflow, random value can't be more than u128::MAX / 2
-        let total_funds = Amount::from_atoms(rng.gen_range(1..u128::MAX / 2));
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+        let total_funds = Amount::from_atoms(rng.gen_range(1..=token_max_supply));
 
         let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
 
@@ -1535,6 +1601,166 @@ fn attempt_to_print_tokens_two_outputs(#[case] seed: Seed) {
     });
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn token_amounts_must_be_conserved(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+        let total_funds = Amount::from_atoms(rng.gen_range(2..=token_max_supply));
+        let amount_to_transfer = Amount::from_atoms(rng.gen_range(1..total_funds.into_atoms()));
+        let amount_to_burn = (total_funds - amount_to_transfer).unwrap();
+
+        let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
+
+        // Issue a new token
+        let genesis_outpoint_id = OutPointSourceId::BlockReward(tf.genesis().get_id().into());
+        let issuance_tx = TransactionBuilder::new()
+            .add_input(
+                TxInput::from_utxo(genesis_outpoint_id, 0),
+                InputWitness::NoSignature(None),
+            )
+            .add_output(TxOutput::Transfer(
+                TokenIssuance {
+                    token_ticker: random_string(&mut rng, 1..5).as_bytes().to_vec(),
+                    amount_to_issue: total_funds,
+                    number_of_decimals: rng.gen_range(1..18),
+                    metadata_uri: random_string(&mut rng, 1..1024).as_bytes().to_vec(),
+                }
+                .into(),
+                Destination::AnyoneCanSpend,
+            ))
+            .add_output(TxOutput::Burn(OutputValue::Coin(token_min_issuance_fee)))
+            .build();
+        let issuance_outpoint_id: OutPointSourceId = issuance_tx.transaction().get_id().into();
+        let token_id = token_id(issuance_tx.transaction()).unwrap();
+        tf.make_block_builder()
+            .add_transaction(issuance_tx)
+            .build_and_process()
+            .unwrap()
+            .unwrap();
+
+        // Outputs exceed inputs
+        let result = tf
+            .make_block_builder()
+            .add_transaction(
+                TransactionBuilder::new()
+                    .add_input(
+                        TxInput::from_utxo(issuance_outpoint_id.clone(), 0),
+                        InputWitness::NoSignature(None),
+                    )
+                    .add_output(TxOutput::Transfer(
+                        TokenTransfer {
+                            token_id,
+                            amount: amount_to_transfer,
+                        }
+                        .into(),
+                        Destination::AnyoneCanSpend,
+                    ))
+                    .add_output(TxOutput::Transfer(
+                        TokenTransfer {
+                            token_id,
+                            amount: total_funds,
+                        }
+                        .into(),
+                        Destination::AnyoneCanSpend,
+                    ))
+                    .build(),
+            )
+            .build_and_process();
+        assert!(matches!(
+            result,
+            Err(ChainstateError::ProcessBlockError(
+                BlockError::StateUpdateFailed(ConnectTransactionError::AttemptToPrintMoney(_, _))
+            ))
+        ));
+
+        // Inputs exceed outputs and the difference is not burned
+        let result = tf
+            .make_block_builder()
+            .add_transaction(
+                TransactionBuilder::new()
+                    .add_input(
+                        TxInput::from_utxo(issuance_outpoint_id.clone(), 0),
+                        InputWitness::NoSignature(None),
+                    )
+                    .add_output(TxOutput::Transfer(
+                        TokenTransfer {
+                            token_id,
+                            amount: amount_to_transfer,
+                        }
+                        .into(),
+                        Destination::AnyoneCanSpend,
+                    ))
+                    .build(),
+            )
+            .build_and_process();
+        assert_eq!(
+            result.unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(
+                ConnectTransactionError::TokensError(TokensError::TokensNotConserved(
+                    token_id,
+                    total_funds,
+                    amount_to_transfer
+                ))
+            ))
+        );
+
+        // Valid case: the difference between inputs and transferred outputs is burned
+        let transfer_tx = TransactionBuilder::new()
+            .add_input(
+                TxInput::from_utxo(issuance_outpoint_id, 0),
+                InputWitness::NoSignature(None),
+            )
+            .add_output(TxOutput::Transfer(
+                TokenTransfer {
+                    token_id,
+                    amount: amount_to_transfer,
+                }
+                .into(),
+                Destination::AnyoneCanSpend,
+            ))
+            .add_output(TxOutput::Burn(
+                TokenTransfer {
+                    token_id,
+                    amount: amount_to_burn,
+                }
+                .into(),
+            ))
+            .build();
+        let transfer_outpoint_id: OutPointSourceId = transfer_tx.transaction().get_id().into();
+        tf.make_block_builder()
+            .add_transaction(transfer_tx)
+            .build_and_process()
+            .unwrap()
+            .unwrap();
+
+        // Valid case: outputs match inputs exactly
+        tf.make_block_builder()
+            .add_transaction(
+                TransactionBuilder::new()
+                    .add_input(
+                        TxInput::from_utxo(transfer_outpoint_id, 0),
+                        InputWitness::NoSignature(None),
+                    )
+                    .add_output(TxOutput::Transfer(
+                        TokenTransfer {
+                            token_id,
+                            amount: amount_to_transfer,
+                        }
+                        .into(),
+                        Destination::AnyoneCanSpend,
+                    ))
+                    .build(),
+            )
+            .build_and_process()
+            .unwrap()
+            .unwrap();
+    });
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -1542,8 +1768,9 @@ fn spend_different_token_than_one_in_input(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
         // To have possibility to send exceed tokens amount than we have, let's limit the max issuance tokens amount
-        let total_funds = Amount::from_atoms(rng.gen_range(1..u128::MAX - 1));
+        let total_funds = Amount::from_atoms(rng.gen_range(1..token_max_supply));
         // Issuance a few different tokens
         let genesis_outpoint_id = OutPointSourceId::BlockReward(tf.genesis().get_id().into());
         let output_value = TokenIssuance {
@@ -1680,13 +1907,14 @@ fn tokens_reorgs_and_cleanup_data(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
 
         let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
 
         // Issue a new token
         let issuance_value = TokenIssuance {
             token_ticker: random_string(&mut rng, 1..5).as_bytes().to_vec(),
-            amount_to_issue: Amount::from_atoms(rng.gen_range(1..u128::MAX)),
+            amount_to_issue: Amount::from_atoms(rng.gen_range(1..=token_max_supply)),
             number_of_decimals: rng.gen_range(1..18),
             metadata_uri: random_string(&mut rng, 1..1024).as_bytes().to_vec(),
         };
@@ -1776,7 +2004,8 @@ fn token_issuance_in_block_reward(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
-        let total_funds = Amount::from_atoms(rng.gen_range(1..u128::MAX));
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+        let total_funds = Amount::from_atoms(rng.gen_range(1..=token_max_supply));
         let (_, pub_key) =
             crypto::key::PrivateKey::new_from_rng(&mut rng, crypto::key::KeyKind::Secp256k1Schnorr);
 
@@ -1907,8 +2136,11 @@ fn issue_and_transfer_in_the_same_block(#[case] seed: Seed) {
     utils::concurrency::model(move || {
         let mut rng = make_seedable_rng(seed);
         let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
 
         let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
+        let amount_to_issue = Amount::from_atoms(rng.gen_range(100_000..=token_max_supply));
+        let amount_to_transfer = Amount::from_atoms(rng.gen_range(1..100_000));
 
         let tx_1 = TransactionBuilder::new()
             .add_input(
@@ -1921,7 +2153,7 @@ fn issue_and_transfer_in_the_same_block(#[case] seed: Seed) {
             .add_output(TxOutput::Transfer(
                 TokenIssuance {
                     token_ticker: "XXXX".as_bytes().to_vec(),
-                    amount_to_issue: Amount::from_atoms(rng.gen_range(100_000..u128::MAX)),
+                    amount_to_issue,
                     number_of_decimals: rng.gen_range(1..18),
                     metadata_uri: "http://uri".as_bytes().to_vec(),
                 }
@@ -1931,6 +2163,7 @@ fn issue_and_transfer_in_the_same_block(#[case] seed: Seed) {
             .add_output(TxOutput::Burn(OutputValue::Coin(token_min_issuance_fee)))
             .build();
 
+        let token_id = token_id(tx_1.transaction()).unwrap();
         let tx_2 = TransactionBuilder::new()
             .add_input(
                 TxInput::from_utxo(
@@ -1941,8 +2174,16 @@ fn issue_and_transfer_in_the_same_block(#[case] seed: Seed) {
             )
             .add_output(TxOutput::Transfer(
                 TokenData::TokenTransfer(TokenTransfer {
-                    token_id: token_id(tx_1.transaction()).unwrap(),
-                    amount: Amount::from_atoms(rng.gen_range(1..100_000)),
+                    token_id,
+                    amount: amount_to_transfer,
+                })
+                .into(),
+                Destination::AnyoneCanSpend,
+            ))
+            .add_output(TxOutput::Transfer(
+                TokenData::TokenTransfer(TokenTransfer {
+                    token_id,
+                    amount: (amount_to_issue - amount_to_transfer).unwrap(),
                 })
                 .into(),
                 Destination::AnyoneCanSpend,
@@ -1957,3 +2198,368 @@ fn issue_and_transfer_in_the_same_block(#[case] seed: Seed) {
             .unwrap();
     })
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn tokens_info_by_issuance(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+
+        let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
+        let mut coins = get_output_value(&tf.genesis().utxos()[0]).unwrap().coin_amount().unwrap();
+        let mut outpoint_source_id: OutPointSourceId =
+            OutPointSourceId::BlockReward(tf.genesis().get_id().into());
+
+        // Issue one token per block, with empty blocks in between
+        let mut token_ids = Vec::new();
+        for _ in 0..5 {
+            coins = (coins - token_min_issuance_fee).unwrap();
+            let tx = TransactionBuilder::new()
+                .add_input(
+                    TxInput::from_utxo(outpoint_source_id, 0),
+                    InputWitness::NoSignature(None),
+                )
+                .add_output(TxOutput::Transfer(
+                    OutputValue::Coin(coins),
+                    Destination::AnyoneCanSpend,
+                ))
+                .add_output(TxOutput::Transfer(
+                    TokenIssuance {
+                        token_ticker: random_string(&mut rng, 1..5).as_bytes().to_vec(),
+                        amount_to_issue: Amount::from_atoms(rng.gen_range(1..=token_max_supply)),
+                        number_of_decimals: rng.gen_range(1..18),
+                        metadata_uri: "http://uri".as_bytes().to_vec(),
+                    }
+                    .into(),
+                    Destination::AnyoneCanSpend,
+                ))
+                .add_output(TxOutput::Burn(OutputValue::Coin(token_min_issuance_fee)))
+                .build();
+            outpoint_source_id = OutPointSourceId::Transaction(tx.transaction().get_id());
+            token_ids.push(token_id(tx.transaction()).unwrap());
+
+            tf.make_block_builder().add_transaction(tx).build_and_process().unwrap();
+            tf.make_block_builder().build_and_process().unwrap();
+        }
+
+        let listed_ids = |tf: &TestFramework, offset, limit| -> Vec<TokenId> {
+            tf.chainstate
+                .get_tokens_info_for_rpc_by_issuance(offset, limit)
+                .unwrap()
+                .into_iter()
+                .map(|info| match info {
+                    RPCTokenInfo::FungibleToken(info) => info.token_id,
+                    RPCTokenInfo::NonFungibleToken(info) => info.token_id,
+                })
+                .collect()
+        };
+
+        assert_eq!(listed_ids(&tf, 0, 10), token_ids);
+        assert_eq!(listed_ids(&tf, 1, 2), token_ids[1..3]);
+        assert_eq!(listed_ids(&tf, 3, 10), token_ids[3..]);
+        assert!(listed_ids(&tf, 5, 10).is_empty());
+        assert!(listed_ids(&tf, 0, 0).is_empty());
+
+        // Tokens issued in blocks that got reorged out are not listed anymore
+        let kept_tokens = rng.gen_range(1..token_ids.len());
+        let fork_height = BlockHeight::new(kept_tokens as u64 * 2);
+        let fork_point = tf.chainstate.get_block_id_from_height(&fork_height).unwrap().unwrap();
+        tf.create_chain(&fork_point, 15, &mut rng).unwrap();
+        assert_eq!(listed_ids(&tf, 0, 10), token_ids[..kept_tokens]);
+    })
+}
+
+// Mempool transactions aren't checked as a part of a block,
+// so the number of issuances must also be checked when they are connected
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn issuance_count_in_mempool_tx(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let storage = TestStore::new_empty().unwrap();
+        let tf = TestFramework::builder(&mut rng).with_storage(storage.clone()).build();
+        let chain_config = Arc::clone(tf.chain_config());
+        let storage = InMemoryStorageWrapper::new(storage, chain_config.as_ref().clone());
+
+        let genesis_input = TxInput::from_utxo(tf.genesis().get_id().into(), 0);
+        let fee = chain_config.token_min_issuance_fee();
+        let token_issuance = TxOutput::Transfer(
+            random_token_issuance(Arc::clone(&chain_config), &mut rng).into(),
+            Destination::AnyoneCanSpend,
+        );
+        let nft_issuance = TxOutput::Transfer(
+            random_nft_issuance(Arc::clone(&chain_config), &mut rng).into(),
+            Destination::AnyoneCanSpend,
+        );
+
+        let connect_tx_with_issuances = |issuances: &[&TxOutput]| {
+            let tx = issuances
+                .iter()
+                .fold(
+                    TransactionBuilder::new()
+                        .add_input(genesis_input.clone(), InputWitness::NoSignature(None))
+                        .add_output(TxOutput::Burn(OutputValue::Coin(fee))),
+                    |builder, issuance| builder.add_output((*issuance).clone()),
+                )
+                .build();
+
+            let mut verifier = TransactionVerifier::new(
+                &storage,
+                chain_config.as_ref(),
+                TransactionVerifierConfig::new(true),
+            );
+            verifier
+                .connect_transaction(
+                    &TransactionSourceForConnect::for_mempool(&tf.best_block_index()),
+                    &tx,
+                    &BlockTimestamp::from_time(tf.current_time()),
+                    None,
+                )
+                .map(|_| ())
+        };
+
+        assert_eq!(connect_tx_with_issuances(&[]), Ok(()));
+        assert_eq!(connect_tx_with_issuances(&[&token_issuance]), Ok(()));
+        assert_eq!(connect_tx_with_issuances(&[&nft_issuance]), Ok(()));
+
+        for issuances in [
+            [&token_issuance, &token_issuance],
+            [&token_issuance, &nft_issuance],
+            [&nft_issuance, &nft_issuance],
+        ] {
+            assert!(matches!(
+                connect_tx_with_issuances(&issuances),
+                Err(ConnectTransactionError::TokensError(
+                    TokensError::MultipleTokenIssuanceInTransaction(_, None)
+                ))
+            ));
+        }
+    });
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn issue_amount_at_max_supply(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let max_supply = rng.gen_range(1..1_000_000_000);
+        let chain_config = common::chain::config::Builder::test_chain()
+            .token_max_supply(max_supply)
+            .build();
+        let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+
+        let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
+        let number_of_decimals =
+            rng.gen_range(0..=tf.chainstate.get_chain_config().token_max_dec_count());
+        let max_supply_atoms = max_supply * 10u128.pow(number_of_decimals as u32);
+        let genesis_input = TxInput::from_utxo(tf.genesis().get_id().into(), 0);
+
+        let mut process_issuance = |tf: &mut TestFramework, amount_to_issue| {
+            tf.make_block_builder()
+                .add_transaction(
+                    TransactionBuilder::new()
+                        .add_input(genesis_input.clone(), InputWitness::NoSignature(None))
+                        .add_output(TxOutput::Transfer(
+                            TokenIssuance {
+                                token_ticker: random_string(&mut rng, 1..5).as_bytes().to_vec(),
+                                amount_to_issue: Amount::from_atoms(amount_to_issue),
+                                number_of_decimals,
+                                metadata_uri: random_string(&mut rng, 1..1024).as_bytes().to_vec(),
+                            }
+                            .into(),
+                            Destination::AnyoneCanSpend,
+                        ))
+                        .add_output(TxOutput::Burn(OutputValue::Coin(token_min_issuance_fee)))
+                        .build(),
+                )
+                .build_and_process()
+        };
+
+        // One atom above the cap
+        let result = process_issuance(&mut tf, max_supply_atoms + 1);
+        assert!(matches!(
+            result,
+            Err(ChainstateError::ProcessBlockError(
+                BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
+                    CheckBlockTransactionsError::TokensError(TokensError::IssueError(
+                        TokenIssuanceError::IssueAmountExceedsMaxSupply(amount, max),
+                        _,
+                        _
+                    ))
+                ))
+            )) if amount == Amount::from_atoms(max_supply_atoms + 1)
+                && max == Amount::from_atoms(max_supply_atoms)
+        ));
+
+        // Exactly at the cap
+        assert!(process_issuance(&mut tf, max_supply_atoms).is_ok());
+    })
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn issue_amount_above_max_supply_activation(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let max_supply = rng.gen_range(1..1_000_000_000);
+        let chain_config = common::chain::config::Builder::test_chain()
+            .token_max_supply(max_supply)
+            .chainstate_upgrades(
+                NetUpgrades::initialize(vec![
+                    (
+                        BlockHeight::zero(),
+                        ChainstateUpgrade::new(
+                            TokenFreezeActivated::Yes,
+                            UniquePoolVrfKeys::Yes,
+                            ExactPoWReward::Yes,
+                            TokensConservation::Yes,
+                            NftMetadataSizeLimit::Yes,
+                            TokenMaxSupplyLimit::No,
+                            StrictMedianTimePast::Yes,
+                        ),
+                    ),
+                    (
+                        BlockHeight::new(2),
+                        ChainstateUpgrade::new(
+                            TokenFreezeActivated::Yes,
+                            UniquePoolVrfKeys::Yes,
+                            ExactPoWReward::Yes,
+                            TokensConservation::Yes,
+                            NftMetadataSizeLimit::Yes,
+                            TokenMaxSupplyLimit::Yes,
+                            StrictMedianTimePast::Yes,
+                        ),
+                    ),
+                ])
+                .unwrap(),
+            )
+            .build();
+        let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+
+        let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
+        let number_of_decimals =
+            rng.gen_range(0..=tf.chainstate.get_chain_config().token_max_dec_count());
+        let amount_to_issue =
+            Amount::from_atoms(max_supply * 10u128.pow(number_of_decimals as u32) + 1);
+
+        let mut issuance_tx_builder = |input: TxInput| {
+            TransactionBuilder::new()
+                .add_input(input, InputWitness::NoSignature(None))
+                .add_output(TxOutput::Transfer(
+                    TokenIssuance {
+                        token_ticker: random_string(&mut rng, 1..5).as_bytes().to_vec(),
+                        amount_to_issue,
+                        number_of_decimals,
+                        metadata_uri: random_string(&mut rng, 1..1024).as_bytes().to_vec(),
+                    }
+                    .into(),
+                    Destination::AnyoneCanSpend,
+                ))
+                .add_output(TxOutput::Burn(OutputValue::Coin(token_min_issuance_fee)))
+        };
+
+        // Block 1 is before the activation
+        let tx = issuance_tx_builder(TxInput::from_utxo(tf.genesis().get_id().into(), 0))
+            .add_output(TxOutput::Transfer(
+                OutputValue::Coin(token_min_issuance_fee),
+                Destination::AnyoneCanSpend,
+            ))
+            .build();
+        let change_input = TxInput::from_utxo(tx.transaction().get_id().into(), 2);
+        tf.make_block_builder().add_transaction(tx).build_and_process().unwrap();
+
+        // Block 2 is after the activation
+        let tx = issuance_tx_builder(change_input).build();
+        let result = tf.make_block_builder().add_transaction(tx).build_and_process();
+        assert!(matches!(
+            result,
+            Err(ChainstateError::ProcessBlockError(
+                BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
+                    CheckBlockTransactionsError::TokensError(TokensError::IssueError(
+                        TokenIssuanceError::IssueAmountExceedsMaxSupply(amount, _),
+                        _,
+                        _
+                    ))
+                ))
+            )) if amount == amount_to_issue
+        ));
+    })
+}
+
+// Fungible token and NFT issuance can be disabled independently of each other
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy(), true, false)]
+#[case(Seed::from_entropy(), false, true)]
+fn issuance_disabled_by_chain_config(
+    #[case] seed: Seed,
+    #[case] fungible_token_issuance_enabled: bool,
+    #[case] nft_issuance_enabled: bool,
+) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = common::chain::config::Builder::test_chain()
+            .fungible_token_issuance_enabled(fungible_token_issuance_enabled)
+            .nft_issuance_enabled(nft_issuance_enabled)
+            .build();
+        let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+        let chain_config = Arc::clone(tf.chainstate.get_chain_config());
+        let token_min_issuance_fee = chain_config.token_min_issuance_fee();
+
+        let fungible_issuance: OutputValue =
+            random_token_issuance(Arc::clone(&chain_config), &mut rng).into();
+        let nft_issuance: OutputValue =
+            random_nft_issuance(Arc::clone(&chain_config), &mut rng).into();
+
+        let (disabled_issuance, enabled_issuance) = if fungible_token_issuance_enabled {
+            (nft_issuance, fungible_issuance)
+        } else {
+            (fungible_issuance, nft_issuance)
+        };
+
+        let genesis_outpoint_id: OutPointSourceId = tf.genesis().get_id().into();
+        let make_issuance_tx = |issuance: OutputValue| {
+            TransactionBuilder::new()
+                .add_input(
+                    TxInput::from_utxo(genesis_outpoint_id.clone(), 0),
+                    InputWitness::NoSignature(None),
+                )
+                .add_output(TxOutput::Transfer(issuance, Destination::AnyoneCanSpend))
+                .add_output(TxOutput::Burn(OutputValue::Coin(token_min_issuance_fee)))
+                .build()
+        };
+
+        // The disabled kind of issuance is rejected
+        let tx = make_issuance_tx(disabled_issuance);
+        let tx_id = tx.transaction().get_id();
+        let block = tf.make_block_builder().add_transaction(tx).build();
+        let block_id = block.get_id();
+
+        let expected_error = if fungible_token_issuance_enabled {
+            TokensError::NftIssuanceDisabled(tx_id, block_id)
+        } else {
+            TokensError::FungibleTokenIssuanceDisabled(tx_id, block_id)
+        };
+        assert_eq!(
+            tf.process_block(block, BlockSource::Local),
+            Err(ChainstateError::ProcessBlockError(
+                BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
+                    CheckBlockTransactionsError::TokensError(expected_error)
+                ))
+            ))
+        );
+
+        // The other kind of issuance is still accepted
+        tf.make_block_builder()
+            .add_transaction(make_issuance_tx(enabled_issuance))
+            .build_and_process()
+            .unwrap()
+            .unwrap();
+    })
+}
@@ -28,6 +28,7 @@ use common::{
     },
     primitives::{Amount, Id},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{DelegationData, PoSAccountingDB, PoSAccountingView, PoolData};
 use utxo::UtxosStorageRead;
 
@@ -143,6 +144,14 @@ impl PoSAccountingView for InMemoryStorageWrapper {
         PoSAccountingDB::<_, TipStorageTag>::new(&self.storage).get_pool_data(pool_id)
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, pos_accounting::Error> {
+        PoSAccountingDB::<_, TipStorageTag>::new(&self.storage)
+            .get_pool_id_by_vrf_public_key(vrf_public_key)
+    }
+
     fn get_delegation_balance(
         &self,
         delegation_id: DelegationId,
@@ -18,7 +18,9 @@ use chainstate_test_framework::TestFramework;
 use common::{
     chain::{
         block::timestamp::BlockTimestamp, output_value::OutputValue, stakelock::StakePoolData,
-        CoinUnit, Destination, Genesis, RequiredConsensus, TxOutput,
+        ChainstateUpgrade, CoinUnit, Destination, ExactPoWReward, Genesis, NetUpgrades,
+        NftMetadataSizeLimit, RequiredConsensus, StrictMedianTimePast, TokenFreezeActivated,
+        TokenMaxSupplyLimit, TokensConservation, TxOutput, UniquePoolVrfKeys,
     },
     primitives::{per_thousand::PerThousand, Amount, BlockHeight, Compact, H256},
 };
@@ -77,6 +79,23 @@ pub fn create_stake_pool_data_with_all_reward_to_owner(
     )
 }
 
+/// Chainstate upgrades for the tests that create pools sharing the VRF key of the genesis pool
+pub fn chainstate_upgrades_with_shared_vrf_keys() -> NetUpgrades<ChainstateUpgrade> {
+    NetUpgrades::initialize(vec![(
+        BlockHeight::zero(),
+        ChainstateUpgrade::new(
+            TokenFreezeActivated::Yes,
+            UniquePoolVrfKeys::No,
+            ExactPoWReward::Yes,
+            TokensConservation::Yes,
+            NftMetadataSizeLimit::Yes,
+            TokenMaxSupplyLimit::Yes,
+            StrictMedianTimePast::Yes,
+        ),
+    )])
+    .expect("valid net upgrades")
+}
+
 pub fn create_custom_genesis_with_stake_pool(
     staker_pk: PublicKey,
     vrf_pk: VRFPublicKey,
@@ -20,6 +20,7 @@ use chainstate_storage::Transactional;
 use chainstate_test_framework::TestFramework;
 use chainstate_types::BlockIndexHistoryIterator;
 use common::primitives::{Id, Idable, H256};
+use crypto::random::Rng;
 use rstest::rstest;
 use test_utils::random::{make_seedable_rng, Seed};
 
@@ -78,3 +79,38 @@ fn history_iteration(#[case] seed: Seed) {
         }
     });
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn last_block_timestamps(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let genesis_timestamp = tf.genesis().timestamp();
+        assert_eq!(
+            tf.chainstate.get_last_block_timestamps(11).unwrap(),
+            vec![genesis_timestamp]
+        );
+
+        let mut timestamps = vec![genesis_timestamp];
+        for _ in 0..5 {
+            tf.progress_time_seconds_since_epoch(rng.gen_range(1..=120));
+            let block = tf.make_block_builder().build();
+            timestamps.push(block.timestamp());
+            tf.process_block(block, BlockSource::Local).unwrap();
+        }
+        timestamps.reverse();
+
+        assert_eq!(tf.chainstate.get_last_block_timestamps(0).unwrap(), vec![]);
+        assert_eq!(
+            tf.chainstate.get_last_block_timestamps(3).unwrap(),
+            timestamps[..3]
+        );
+        assert_eq!(
+            tf.chainstate.get_last_block_timestamps(11).unwrap(),
+            timestamps
+        );
+    });
+}
@@ -22,7 +22,9 @@ use common::{
     chain::{
         output_value::OutputValue,
         tokens::{token_id, TokenData, TokenIssuance, TokenTransfer},
-        Destination, OutPointSourceId, TxInput, TxOutput,
+        ChainstateUpgrade, Destination, ExactPoWReward, NetUpgrades, NftMetadataSizeLimit,
+        OutPointSourceId, StrictMedianTimePast, TokenFreezeActivated, TokenMaxSupplyLimit,
+        TokensConservation, TxInput, TxOutput, UniquePoolVrfKeys,
     },
     primitives::{Amount, Idable},
 };
@@ -139,6 +141,124 @@ fn tokens_homomorphism(#[case] seed: Seed) {
             .with_tx_verification_strategy(TxVerificationStrategy::Disposable)
             .build();
 
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+        let amount_to_issue = Amount::from_atoms(rng.gen_range(100_000..=token_max_supply));
+        let amount_to_transfer = Amount::from_atoms(rng.gen_range(1..100_000));
+
+        let tx_1 = TransactionBuilder::new()
+            .add_input(
+                TxInput::from_utxo(
+                    OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                    0,
+                ),
+                empty_witness(&mut rng),
+            )
+            .add_output(TxOutput::Transfer(
+                TokenIssuance {
+                    token_ticker: "XXXX".as_bytes().to_vec(),
+                    amount_to_issue,
+                    number_of_decimals: rng.gen_range(1..18),
+                    metadata_uri: "http://uri".as_bytes().to_vec(),
+                }
+                .into(),
+                Destination::AnyoneCanSpend,
+            ))
+            .add_output(TxOutput::Burn(OutputValue::Coin(
+                tf.chainstate.get_chain_config().token_min_issuance_fee(),
+            )))
+            .build();
+        let token_id = token_id(tx_1.transaction()).unwrap();
+
+        let tx_2 = TransactionBuilder::new()
+            .add_input(
+                TxInput::from_utxo(
+                    OutPointSourceId::Transaction(tx_1.transaction().get_id()),
+                    0,
+                ),
+                InputWitness::NoSignature(None),
+            )
+            .add_output(TxOutput::Transfer(
+                TokenData::TokenTransfer(TokenTransfer {
+                    token_id,
+                    amount: amount_to_transfer,
+                })
+                .into(),
+                Destination::AnyoneCanSpend,
+            ))
+            .add_output(TxOutput::Transfer(
+                TokenData::TokenTransfer(TokenTransfer {
+                    token_id,
+                    amount: (amount_to_issue - amount_to_transfer).unwrap(),
+                })
+                .into(),
+                Destination::AnyoneCanSpend,
+            ))
+            .build();
+
+        tf.make_block_builder()
+            .add_transaction(tx_1.clone())
+            .add_transaction(tx_2.clone())
+            .build_and_process()
+            .unwrap()
+            .unwrap();
+
+        tf2.make_block_builder()
+            .add_transaction(tx_1)
+            .add_transaction(tx_2)
+            .build_and_process()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(storage1.dump_raw(), storage2.dump_raw());
+    });
+}
+
+// Before the tokens conservation upgrade the tokens that are not transferred are implicitly burned
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn tokens_homomorphism_before_conservation(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+
+        let chain_config = common::chain::config::Builder::test_chain()
+            .chainstate_upgrades(
+                NetUpgrades::initialize(vec![(
+                    BlockHeight::zero(),
+                    ChainstateUpgrade::new(
+                        TokenFreezeActivated::Yes,
+                        UniquePoolVrfKeys::Yes,
+                        ExactPoWReward::Yes,
+                        TokensConservation::No,
+                        NftMetadataSizeLimit::Yes,
+                        TokenMaxSupplyLimit::Yes,
+                        StrictMedianTimePast::Yes,
+                    ),
+                )])
+                .unwrap(),
+            )
+            .build();
+
+        let storage1 = TestStore::new_empty().unwrap();
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.clone())
+            .with_storage(storage1.clone())
+            .with_tx_verification_strategy(TxVerificationStrategy::Default)
+            .build();
+
+        // Sync randomized `tx_index_enabled` value between two TestFrameworks
+        let chainstate_config = tf.chainstate.get_chainstate_config();
+
+        let storage2 = TestStore::new_empty().unwrap();
+        let mut tf2 = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config)
+            .with_chainstate_config(chainstate_config)
+            .with_storage(storage2.clone())
+            .with_tx_verification_strategy(TxVerificationStrategy::Disposable)
+            .build();
+
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+
         let tx_1 = TransactionBuilder::new()
             .add_input(
                 TxInput::from_utxo(
@@ -150,7 +270,7 @@ fn tokens_homomorphism(#[case] seed: Seed) {
             .add_output(TxOutput::Transfer(
                 TokenIssuance {
                     token_ticker: "XXXX".as_bytes().to_vec(),
-                    amount_to_issue: Amount::from_atoms(rng.gen_range(100_000..u128::MAX)),
+                    amount_to_issue: Amount::from_atoms(rng.gen_range(100_000..=token_max_supply)),
                     number_of_decimals: rng.gen_range(1..18),
                     metadata_uri: "http://uri".as_bytes().to_vec(),
                 }
@@ -18,12 +18,19 @@ use chainstate::ChainstateError;
 use chainstate_test_framework::TestFramework;
 use common::{
     chain::{
-        config::{Builder as ChainConfigBuilder, ChainType},
-        NetUpgrades,
+        config::{
+            regtest::GenesisStakingSettings,
+            regtest_options::{regtest_chain_config, ChainConfigOptions},
+            Builder as ChainConfigBuilder, ChainType,
+        },
+        output_value::OutputValue,
+        Destination, NetUpgrades, OutPointSourceId, TxOutput, UtxoOutPoint,
     },
-    primitives::Id,
+    primitives::{Amount, Id, Idable},
 };
 use crypto::random::CryptoRng;
+use serialization::hex::HexEncode;
+use utxo::UtxoSource;
 
 #[cfg(not(loom))]
 fn genesis_check_ok(num_blocks: u64, rng: &mut (impl Rng + CryptoRng)) {
@@ -60,6 +67,10 @@ fn genesis_check_ok(num_blocks: u64, rng: &mut (impl Rng + CryptoRng)) {
         min_max_bootstrap_import_buffer_sizes: Default::default(),
         tx_index_enabled: tx_index_enabled.map(Into::into).unwrap_or_default(),
         max_tip_age: Default::default(),
+        commit_batch_size: Default::default(),
+        prune_depth: Default::default(),
+        reindex_tx_index: Default::default(),
+        max_blocks_pruned_at_once: Default::default(),
     };
 
     // Initialize a different test framework with given storage.
@@ -120,6 +131,10 @@ fn genesis_check_err(num_blocks: u64, rng: &mut (impl Rng + CryptoRng)) {
         min_max_bootstrap_import_buffer_sizes: Default::default(),
         tx_index_enabled: tx_index_enabled.map(Into::into).unwrap_or_default(),
         max_tip_age: Default::default(),
+        commit_batch_size: Default::default(),
+        prune_depth: Default::default(),
+        reindex_tx_index: Default::default(),
+        max_blocks_pruned_at_once: Default::default(),
     };
 
     // Start another chain with different genesis using the previous storage
@@ -176,3 +191,57 @@ fn genesis_check_err_nonempty_chain(#[case] seed: Seed) {
     let mut rng = make_seedable_rng(seed);
     genesis_check_err(rng.gen_range(2..100), &mut rng);
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn custom_regtest_genesis_from_file(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let prefunded = TxOutput::Transfer(
+        OutputValue::Coin(Amount::from_atoms(rng.gen_range(1..1_000_000_000))),
+        Destination::AnyoneCanSpend,
+    );
+    let genesis_dir = tempfile::TempDir::new().unwrap();
+    let genesis_file = genesis_dir.path().join("genesis.json");
+    std::fs::write(
+        &genesis_file,
+        format!(
+            r#"{{"fun_message": "custom", "utxos": ["{}"]}}"#,
+            prefunded.hex_encode()
+        ),
+    )
+    .unwrap();
+
+    let options = ChainConfigOptions {
+        chain_magic_bytes: None,
+        chain_max_future_block_time_offset: None,
+        software_version: None,
+        chain_target_block_spacing: None,
+        chain_coin_decimals: None,
+        chain_emission_schedule: None,
+        chain_max_block_header_size: None,
+        chain_max_block_size_with_standard_txs: None,
+        chain_max_block_size_with_smart_contracts: None,
+        chain_max_block_size: None,
+        chain_max_block_tx_count: None,
+        chain_initial_difficulty: None,
+        chain_pos_netupgrades: None,
+        chain_pos_netupgrades_v0_to_v1: None,
+        chain_genesis_block_timestamp: None,
+        chain_genesis_staking_settings: GenesisStakingSettings::default(),
+        chain_genesis_file: Some(genesis_file),
+    };
+    let chain_config = regtest_chain_config(&options).unwrap();
+    assert_eq!(chain_config.genesis_block().fun_message(), "custom");
+    assert_eq!(chain_config.genesis_block().utxos(), [prefunded.clone()]);
+
+    let tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+    let outpoint = UtxoOutPoint::new(
+        OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+        0,
+    );
+    let utxo = tf.chainstate.utxo(&outpoint).unwrap().unwrap();
+    assert_eq!(utxo.output(), &prefunded);
+    assert_eq!(utxo.source(), &UtxoSource::Blockchain(BlockHeight::zero()));
+}
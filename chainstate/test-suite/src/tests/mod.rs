@@ -28,6 +28,7 @@ use test_utils::random::{make_seedable_rng, Seed};
 
 mod basic_tests;
 mod block_invalidation;
+mod block_pruning;
 mod block_status;
 mod bootstrap;
 mod chainstate_accounting_storage_tests;
@@ -50,10 +51,12 @@ mod pos_maturity_settings;
 mod pos_processing_tests;
 mod pos_retargeting_tests;
 mod processing_tests;
+mod read_handle_tests;
 mod reorgs_tests;
 mod signature_tests;
 mod stake_pool_tests;
 mod syncing_tests;
+mod token_freeze;
 mod tx_verification_simulation;
 mod tx_verifier_among_threads;
 mod tx_verifier_disconnect;
@@ -24,19 +24,31 @@ use common::chain::OutPointSourceId;
 use common::chain::{
     signature::inputsig::InputWitness,
     tokens::{Metadata, NftIssuance, TokenData},
-    Destination, TxInput, TxOutput,
+    ChainstateUpgrade, Destination, ExactPoWReward, NetUpgrades, NftMetadataSizeLimit,
+    StrictMedianTimePast, TokenFreezeActivated, TokenMaxSupplyLimit, TokensConservation, TxInput,
+    TxOutput, UniquePoolVrfKeys,
 };
-use common::primitives::Idable;
+use common::primitives::{BlockHeight, Idable};
 use crypto::random::{CryptoRng, Rng};
 use rstest::rstest;
-use serialization::extras::non_empty_vec::DataOrNoVec;
+use serialization::{extras::non_empty_vec::DataOrNoVec, Encode};
 use test_utils::{
     gen_text_with_non_ascii,
     nft_utils::random_creator,
     random::{make_seedable_rng, Seed},
     random_string,
 };
-use tx_verifier::error::TokenIssuanceError;
+use tx_verifier::error::{TokenIssuanceError, UriError};
+
+// A single byte that is not allowed in a URI is either an illegal ASCII character
+// or makes the URI an invalid UTF-8 string
+fn expected_uri_error(c: u8) -> UriError {
+    if c.is_ascii() {
+        UriError::IllegalCharacter(char::from(c))
+    } else {
+        UriError::InvalidUtf8
+    }
+}
 
 #[rstest]
 #[trace]
@@ -88,7 +100,7 @@ fn nft_name_too_long(#[case] seed: Seed) {
             Err(ChainstateError::ProcessBlockError(
                 BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                     CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                        TokenIssuanceError::IssueErrorInvalidNameLength,
+                        TokenIssuanceError::IssueErrorInvalidNameLength(_, _),
                         _,
                         _
                     ))
@@ -143,7 +155,7 @@ fn nft_empty_name(#[case] seed: Seed) {
             Err(ChainstateError::ProcessBlockError(
                 BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                     CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                        TokenIssuanceError::IssueErrorInvalidNameLength,
+                        TokenIssuanceError::IssueErrorInvalidNameLength(_, _),
                         _,
                         _
                     ))
@@ -210,7 +222,7 @@ fn nft_invalid_name(#[case] seed: Seed) {
                 Err(ChainstateError::ProcessBlockError(
                     BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                         CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                            TokenIssuanceError::IssueErrorNameHasNoneAlphaNumericChar,
+                            TokenIssuanceError::IssueErrorNameHasNoneAlphaNumericChar(_),
                             _,
                             _
                         ))
@@ -272,7 +284,7 @@ fn nft_ticker_too_long(#[case] seed: Seed) {
             Err(ChainstateError::ProcessBlockError(
                 BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                     CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                        TokenIssuanceError::IssueErrorInvalidTickerLength,
+                        TokenIssuanceError::IssueErrorInvalidTickerLength(_, _),
                         _,
                         _
                     ))
@@ -328,7 +340,7 @@ fn nft_empty_ticker(#[case] seed: Seed) {
             Err(ChainstateError::ProcessBlockError(
                 BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                     CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                        TokenIssuanceError::IssueErrorInvalidTickerLength,
+                        TokenIssuanceError::IssueErrorInvalidTickerLength(_, _),
                         _,
                         _
                     ))
@@ -395,7 +407,7 @@ fn nft_invalid_ticker(#[case] seed: Seed) {
                 Err(ChainstateError::ProcessBlockError(
                     BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                         CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                            TokenIssuanceError::IssueErrorTickerHasNoneAlphaNumericChar,
+                            TokenIssuanceError::IssueErrorTickerHasNoneAlphaNumericChar(_),
                             _,
                             _
                         ))
@@ -457,7 +469,7 @@ fn nft_description_too_long(#[case] seed: Seed) {
             Err(ChainstateError::ProcessBlockError(
                 BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                     CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                        TokenIssuanceError::IssueErrorInvalidDescriptionLength,
+                        TokenIssuanceError::IssueErrorInvalidDescriptionLength(_, _),
                         _,
                         _
                     ))
@@ -513,7 +525,7 @@ fn nft_empty_description(#[case] seed: Seed) {
             Err(ChainstateError::ProcessBlockError(
                 BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                     CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                        TokenIssuanceError::IssueErrorInvalidDescriptionLength,
+                        TokenIssuanceError::IssueErrorInvalidDescriptionLength(_, _),
                         _,
                         _
                     ))
@@ -579,7 +591,7 @@ fn nft_invalid_description(#[case] seed: Seed) {
                 Err(ChainstateError::ProcessBlockError(
                     BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                         CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                            TokenIssuanceError::IssueErrorDescriptionHasNoneAlphaNumericChar,
+                            TokenIssuanceError::IssueErrorDescriptionHasNoneAlphaNumericChar(_),
                             _,
                             _
                         ))
@@ -640,7 +652,7 @@ fn nft_icon_uri_too_long(#[case] seed: Seed) {
             Err(ChainstateError::ProcessBlockError(
                 BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                     CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                        TokenIssuanceError::IssueErrorIncorrectIconURI,
+                        TokenIssuanceError::IssueErrorIncorrectIconURI(UriError::TooLong(_, _)),
                         _,
                         _
                     ))
@@ -771,12 +783,12 @@ fn nft_icon_uri_invalid(#[case] seed: Seed) {
                 Err(ChainstateError::ProcessBlockError(
                     BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                         CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                            TokenIssuanceError::IssueErrorIncorrectIconURI,
+                            TokenIssuanceError::IssueErrorIncorrectIconURI(ref err),
                             _,
                             _
                         ))
                     ))
-                ))
+                )) if *err == expected_uri_error(c)
             ));
         }
     })
@@ -833,7 +845,7 @@ fn nft_metadata_uri_too_long(#[case] seed: Seed) {
             Err(ChainstateError::ProcessBlockError(
                 BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                     CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                        TokenIssuanceError::IssueErrorIncorrectMetadataURI,
+                        TokenIssuanceError::IssueErrorIncorrectMetadataURI(UriError::TooLong(_, _)),
                         _,
                         _
                     ))
@@ -963,12 +975,12 @@ fn nft_metadata_uri_invalid(#[case] seed: Seed) {
                 Err(ChainstateError::ProcessBlockError(
                     BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                         CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                            TokenIssuanceError::IssueErrorIncorrectMetadataURI,
+                            TokenIssuanceError::IssueErrorIncorrectMetadataURI(ref err),
                             _,
                             _
                         ))
                     ))
-                ))
+                )) if *err == expected_uri_error(c)
             ));
         }
     })
@@ -1025,7 +1037,7 @@ fn nft_media_uri_too_long(#[case] seed: Seed) {
             Err(ChainstateError::ProcessBlockError(
                 BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                     CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                        TokenIssuanceError::IssueErrorIncorrectMediaURI,
+                        TokenIssuanceError::IssueErrorIncorrectMediaURI(UriError::TooLong(_, _)),
                         _,
                         _
                     ))
@@ -1157,12 +1169,12 @@ fn nft_media_uri_invalid(#[case] seed: Seed) {
                 Err(ChainstateError::ProcessBlockError(
                     BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                         CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                            TokenIssuanceError::IssueErrorIncorrectMediaURI,
+                            TokenIssuanceError::IssueErrorIncorrectMediaURI(ref err),
                             _,
                             _
                         ))
                     ))
-                ))
+                )) if *err == expected_uri_error(c)
             ));
         }
     })
@@ -1237,7 +1249,7 @@ fn nft_media_hash_too_short(#[case] seed: Seed) {
                 Err(ChainstateError::ProcessBlockError(
                     BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                         CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                            TokenIssuanceError::MediaHashTooShort,
+                            TokenIssuanceError::MediaHashTooShort(_, _),
                             _,
                             _
                         ))
@@ -1272,7 +1284,7 @@ fn nft_media_hash_too_long(#[case] seed: Seed) {
                 Err(ChainstateError::ProcessBlockError(
                     BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
                         CheckBlockTransactionsError::TokensError(TokensError::IssueError(
-                            TokenIssuanceError::MediaHashTooLong,
+                            TokenIssuanceError::MediaHashTooLong(_, _),
                             _,
                             _
                         ))
@@ -1397,3 +1409,204 @@ fn nft_valid_case(#[case] seed: Seed) {
         );
     })
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn nft_metadata_size_limit(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let outpoint_source_id = OutPointSourceId::BlockReward(tf.genesis().get_id().into());
+
+        let chain_config = tf.chainstate.get_chain_config().clone();
+        let max_metadata_size = chain_config.token_max_nft_metadata_size();
+        let max_uri_len = chain_config.token_max_uri_len();
+        let token_min_issuance_fee = chain_config.token_min_issuance_fee();
+
+        // All the fields are within their individual limits, the size is tuned via the media URI
+        let metadata_base = Metadata {
+            creator: Some(random_creator(&mut rng)),
+            name: random_string(&mut rng, 1..chain_config.token_max_name_len()).into_bytes(),
+            description: random_string(&mut rng, 1..chain_config.token_max_description_len())
+                .into_bytes(),
+            ticker: random_string(&mut rng, 1..chain_config.token_max_ticker_len()).into_bytes(),
+            icon_uri: DataOrNoVec::from(Some(
+                random_string(&mut rng, max_uri_len..=max_uri_len).into_bytes(),
+            )),
+            additional_metadata_uri: DataOrNoVec::from(Some(
+                random_string(&mut rng, max_uri_len..=max_uri_len).into_bytes(),
+            )),
+            media_uri: DataOrNoVec::from(None),
+            media_hash: vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0],
+        };
+        let metadata_with_media_uri_len = |len: usize| Metadata {
+            media_uri: DataOrNoVec::from(Some(vec![b'a'; len])),
+            ..metadata_base.clone()
+        };
+        // Media URI lengths used below all have the same length prefix size
+        let size_without_media_uri = metadata_with_media_uri_len(64).encoded_size() - 64;
+        let media_uri_len_at_limit = max_metadata_size - size_without_media_uri;
+        assert!((64..=max_uri_len).contains(&media_uri_len_at_limit));
+
+        let process_issuance = |tf: &mut TestFramework, metadata: Metadata| {
+            tf.make_block_builder()
+                .add_transaction(
+                    TransactionBuilder::new()
+                        .add_input(
+                            TxInput::from_utxo(outpoint_source_id.clone(), 0),
+                            InputWitness::NoSignature(None),
+                        )
+                        .add_output(TxOutput::Transfer(
+                            NftIssuance { metadata }.into(),
+                            Destination::AnyoneCanSpend,
+                        ))
+                        .add_output(TxOutput::Burn(OutputValue::Coin(token_min_issuance_fee)))
+                        .build(),
+                )
+                .build_and_process()
+        };
+
+        // Just over the limit
+        let metadata = metadata_with_media_uri_len(media_uri_len_at_limit + 1);
+        assert_eq!(metadata.encoded_size(), max_metadata_size + 1);
+        let result = process_issuance(&mut tf, metadata);
+        assert!(matches!(
+            result,
+            Err(ChainstateError::ProcessBlockError(
+                BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
+                    CheckBlockTransactionsError::TokensError(TokensError::IssueError(
+                        TokenIssuanceError::IssueErrorMetadataTooLarge(_, _),
+                        _,
+                        _
+                    ))
+                ))
+            ))
+        ));
+
+        // Right at the limit
+        let metadata = metadata_with_media_uri_len(media_uri_len_at_limit);
+        assert_eq!(metadata.encoded_size(), max_metadata_size);
+        assert!(process_issuance(&mut tf, metadata).is_ok());
+    })
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn nft_metadata_size_limit_activation(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = common::chain::config::Builder::test_chain()
+            .chainstate_upgrades(
+                NetUpgrades::initialize(vec![
+                    (
+                        BlockHeight::zero(),
+                        ChainstateUpgrade::new(
+                            TokenFreezeActivated::Yes,
+                            UniquePoolVrfKeys::Yes,
+                            ExactPoWReward::Yes,
+                            TokensConservation::Yes,
+                            NftMetadataSizeLimit::No,
+                            TokenMaxSupplyLimit::Yes,
+                            StrictMedianTimePast::Yes,
+                        ),
+                    ),
+                    (
+                        BlockHeight::new(2),
+                        ChainstateUpgrade::new(
+                            TokenFreezeActivated::Yes,
+                            UniquePoolVrfKeys::Yes,
+                            ExactPoWReward::Yes,
+                            TokensConservation::Yes,
+                            NftMetadataSizeLimit::Yes,
+                            TokenMaxSupplyLimit::Yes,
+                            StrictMedianTimePast::Yes,
+                        ),
+                    ),
+                ])
+                .unwrap(),
+            )
+            .build();
+        let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+
+        let chain_config = tf.chainstate.get_chain_config().clone();
+        let max_uri_len = chain_config.token_max_uri_len();
+        let token_min_issuance_fee = chain_config.token_min_issuance_fee();
+
+        // All the fields are within their individual limits, but the total size is not
+        let metadata = Metadata {
+            creator: Some(random_creator(&mut rng)),
+            name: random_string(&mut rng, 1..chain_config.token_max_name_len()).into_bytes(),
+            description: random_string(&mut rng, 1..chain_config.token_max_description_len())
+                .into_bytes(),
+            ticker: random_string(&mut rng, 1..chain_config.token_max_ticker_len()).into_bytes(),
+            icon_uri: DataOrNoVec::from(Some(
+                random_string(&mut rng, max_uri_len..=max_uri_len).into_bytes(),
+            )),
+            additional_metadata_uri: DataOrNoVec::from(Some(
+                random_string(&mut rng, max_uri_len..=max_uri_len).into_bytes(),
+            )),
+            media_uri: DataOrNoVec::from(Some(
+                random_string(&mut rng, max_uri_len..=max_uri_len).into_bytes(),
+            )),
+            media_hash: vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0],
+        };
+        assert!(metadata.encoded_size() > chain_config.token_max_nft_metadata_size());
+
+        // Block 1 is before the activation
+        let tx = TransactionBuilder::new()
+            .add_input(
+                TxInput::from_utxo(
+                    OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                    0,
+                ),
+                InputWitness::NoSignature(None),
+            )
+            .add_output(TxOutput::Transfer(
+                NftIssuance {
+                    metadata: metadata.clone(),
+                }
+                .into(),
+                Destination::AnyoneCanSpend,
+            ))
+            .add_output(TxOutput::Burn(OutputValue::Coin(token_min_issuance_fee)))
+            .add_output(TxOutput::Transfer(
+                OutputValue::Coin(token_min_issuance_fee),
+                Destination::AnyoneCanSpend,
+            ))
+            .build();
+        let change_outpoint_id: OutPointSourceId = tx.transaction().get_id().into();
+        tf.make_block_builder().add_transaction(tx).build_and_process().unwrap();
+
+        // Block 2 is after the activation
+        let result = tf
+            .make_block_builder()
+            .add_transaction(
+                TransactionBuilder::new()
+                    .add_input(
+                        TxInput::from_utxo(change_outpoint_id, 2),
+                        InputWitness::NoSignature(None),
+                    )
+                    .add_output(TxOutput::Transfer(
+                        NftIssuance { metadata }.into(),
+                        Destination::AnyoneCanSpend,
+                    ))
+                    .add_output(TxOutput::Burn(OutputValue::Coin(token_min_issuance_fee)))
+                    .build(),
+            )
+            .build_and_process();
+        assert!(matches!(
+            result,
+            Err(ChainstateError::ProcessBlockError(
+                BlockError::CheckBlockFailed(CheckBlockError::CheckTransactionFailed(
+                    CheckBlockTransactionsError::TokensError(TokensError::IssueError(
+                        TokenIssuanceError::IssueErrorMetadataTooLarge(_, _),
+                        _,
+                        _
+                    ))
+                ))
+            ))
+        ));
+    })
+}
@@ -17,7 +17,10 @@ use itertools::Itertools;
 use std::num::NonZeroU64;
 
 use super::helpers::{
-    new_pub_key_destination, pos::create_stake_pool_data_with_all_reward_to_owner,
+    new_pub_key_destination,
+    pos::{
+        chainstate_upgrades_with_shared_vrf_keys, create_stake_pool_data_with_all_reward_to_owner,
+    },
 };
 
 use accounting::{DataDelta, DeltaAmountCollection, DeltaDataCollection};
@@ -313,6 +316,7 @@ fn in_memory_reorg_disconnect_produce_pool(#[case] seed: Seed) {
         pool_id,
         stake_pool_data,
     )
+    .chainstate_upgrades(chainstate_upgrades_with_shared_vrf_keys())
     .build();
     let target_block_time =
         chainstate_test_framework::get_target_block_time(&chain_config, BlockHeight::new(1));
@@ -410,6 +414,7 @@ fn in_memory_reorg_disconnect_create_pool(#[case] seed: Seed) {
         pool_id,
         stake_pool_data,
     )
+    .chainstate_upgrades(chainstate_upgrades_with_shared_vrf_keys())
     .build();
     let target_block_time =
         chainstate_test_framework::get_target_block_time(&chain_config, BlockHeight::new(1));
@@ -13,7 +13,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::helpers::pos::create_custom_genesis_with_stake_pool;
+use super::helpers::pos::{
+    chainstate_upgrades_with_shared_vrf_keys, create_custom_genesis_with_stake_pool,
+};
 
 use chainstate::{BlockError, ChainstateError, ConnectTransactionError};
 use chainstate_test_framework::{empty_witness, TestFramework, TransactionBuilder};
@@ -70,6 +72,7 @@ fn decommission_maturity_setting_follows_netupgrade(#[case] seed: Seed) {
     let genesis = create_custom_genesis_with_stake_pool(staking_pk, vrf_pk.clone());
     let chain_config = ConfigBuilder::test_chain()
         .net_upgrades(net_upgrades)
+        .chainstate_upgrades(chainstate_upgrades_with_shared_vrf_keys())
         .genesis_custom(genesis)
         .build();
     let target_block_time =
@@ -19,9 +19,9 @@ use super::helpers::new_pub_key_destination;
 
 use chainstate::chainstate_interface::ChainstateInterface;
 use chainstate::{
-    make_chainstate, BlockError, BlockSource, ChainstateConfig, ChainstateError, CheckBlockError,
-    CheckBlockTransactionsError, ConnectTransactionError, DefaultTransactionVerificationStrategy,
-    OrphanCheckError,
+    make_chainstate, BlockError, BlockSizeError, BlockSource, ChainstateConfig, ChainstateError,
+    CheckBlockError, CheckBlockTransactionsError, ConnectTransactionError,
+    DefaultTransactionVerificationStrategy, OrphanCheckError,
 };
 use chainstate_test_framework::{
     anyonecanspend_address, empty_witness, get_output_value, TestFramework, TestStore,
@@ -38,8 +38,10 @@ use common::{
         config::{create_unit_test_config, Builder as ConfigBuilder},
         output_value::OutputValue,
         timelock::OutputTimeLock,
-        Block, ConsensusUpgrade, Destination, GenBlock, NetUpgrades, OutPointSourceId,
-        OutputSpentState, PoolId, TxInput, TxOutput, UpgradeVersion,
+        Block, ChainstateUpgrade, ConsensusUpgrade, Destination, ExactPoWReward, GenBlock,
+        NetUpgrades, NftMetadataSizeLimit, OutPointSourceId, OutputSpentState, PoolId,
+        StrictMedianTimePast, TokenFreezeActivated, TokenMaxSupplyLimit, TokensConservation,
+        TxInput, TxOutput, UniquePoolVrfKeys, UpgradeVersion,
     },
     primitives::{per_thousand::PerThousand, Amount, BlockHeight, Compact, Id, Idable, H256},
     Uint256,
@@ -51,6 +53,7 @@ use crypto::{
     vrf::{VRFKeyKind, VRFPrivateKey},
 };
 use rstest::rstest;
+use serialization::Encode;
 use test_utils::mock_time_getter::mocked_time_getter_seconds;
 use test_utils::random::{make_seedable_rng, Seed};
 use tx_verifier::timelock_check::OutputMaturityError;
@@ -777,15 +780,22 @@ fn consensus_type(#[case] seed: Seed) {
     for i in 5..10 {
         let (_, pub_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
         let prev_block = tf.block(*tf.index_at(i - 1).block_id());
+        let transactions = tf
+            .make_block_builder()
+            .add_test_transaction_from_block(&prev_block, &mut rng)
+            .build()
+            .transactions()
+            .to_vec();
+        let reward = pow_reward_amount(&tf, BlockHeight::new(i as u64), &transactions);
         let mined_block = tf
             .make_block_builder()
             .with_parent(prev_block.get_id().into())
             .with_reward(vec![TxOutput::LockThenTransfer(
-                OutputValue::Coin(Amount::from_atoms(10)),
+                OutputValue::Coin(reward),
                 Destination::PublicKey(pub_key),
                 OutputTimeLock::ForBlockCount(reward_lock_distance as u64),
             )])
-            .add_test_transaction_from_block(&prev_block, &mut rng)
+            .with_transactions(transactions)
             .build();
         let mut block_header = mined_block.header().clone();
         let bits = min_difficulty.into();
@@ -864,15 +874,22 @@ fn consensus_type(#[case] seed: Seed) {
     for i in 15..20 {
         let (_, pub_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
         let prev_block = tf.block(*tf.index_at(i - 1).block_id());
+        let transactions = tf
+            .make_block_builder()
+            .add_test_transaction_from_block(&prev_block, &mut rng)
+            .build()
+            .transactions()
+            .to_vec();
+        let reward = pow_reward_amount(&tf, BlockHeight::new(i as u64), &transactions);
         let mined_block = tf
             .make_block_builder()
             .with_parent(prev_block.get_id().into())
             .with_reward(vec![TxOutput::LockThenTransfer(
-                OutputValue::Coin(Amount::from_atoms(10)),
+                OutputValue::Coin(reward),
                 Destination::PublicKey(pub_key),
                 OutputTimeLock::ForBlockCount(reward_lock_distance as u64),
             )])
-            .add_test_transaction_from_block(&prev_block, &mut rng)
+            .with_transactions(transactions)
             .build();
         let bits = min_difficulty.into();
         let mut block_header = mined_block.header().clone();
@@ -932,14 +949,21 @@ fn pow(#[case] seed: Seed) {
     // Let's create a block with random (invalid) PoW data and see that it fails the consensus
     // checks
     let (_, pub_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+    let transactions = tf
+        .make_block_builder()
+        .add_test_transaction_from_best_block(&mut rng)
+        .build()
+        .transactions()
+        .to_vec();
+    let reward = pow_reward_amount(&tf, BlockHeight::new(1), &transactions);
     let mut random_invalid_block = tf
         .make_block_builder()
         .with_reward(vec![TxOutput::LockThenTransfer(
-            OutputValue::Coin(Amount::from_atoms(10)),
+            OutputValue::Coin(reward),
             Destination::PublicKey(pub_key),
             OutputTimeLock::ForBlockCount(reward_lock_distance as u64),
         )])
-        .add_test_transaction_from_best_block(&mut rng)
+        .with_transactions(transactions)
         .build();
     make_invalid_pow_block(&mut random_invalid_block, u128::MAX, difficulty.into())
         .expect("generate invalid block");
@@ -970,6 +994,138 @@ fn pow(#[case] seed: Seed) {
     tf.process_block(valid_block, BlockSource::Local).unwrap();
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy(), ExactPoWReward::Yes)]
+#[case(Seed::from_entropy(), ExactPoWReward::No)]
+fn pow_block_reward_must_match_subsidy_and_fees(
+    #[case] seed: Seed,
+    #[case] exact_pow_reward: ExactPoWReward,
+) {
+    let mut rng = make_seedable_rng(seed);
+    let difficulty =
+        Uint256([0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0x0FFFFFFFFFFFFFFF]);
+
+    let upgrades = vec![
+        (
+            BlockHeight::new(0),
+            UpgradeVersion::ConsensusUpgrade(ConsensusUpgrade::IgnoreConsensus),
+        ),
+        (
+            BlockHeight::new(1),
+            UpgradeVersion::ConsensusUpgrade(ConsensusUpgrade::PoW {
+                initial_difficulty: difficulty.into(),
+            }),
+        ),
+    ];
+
+    let net_upgrades = NetUpgrades::initialize(upgrades).expect("valid net-upgrades");
+    let chainstate_upgrades = NetUpgrades::initialize(vec![(
+        BlockHeight::zero(),
+        ChainstateUpgrade::new(
+            TokenFreezeActivated::Yes,
+            UniquePoolVrfKeys::Yes,
+            exact_pow_reward,
+            TokensConservation::Yes,
+            NftMetadataSizeLimit::Yes,
+            TokenMaxSupplyLimit::Yes,
+            StrictMedianTimePast::Yes,
+        ),
+    )])
+    .expect("valid net-upgrades");
+    let chain_config = ConfigBuilder::test_chain()
+        .net_upgrades(net_upgrades)
+        .chainstate_upgrades(chainstate_upgrades)
+        .build();
+    let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+
+    let reward_lock_distance: i64 = tf
+        .chainstate
+        .get_chain_config()
+        .get_proof_of_work_config()
+        .reward_maturity_distance()
+        .into();
+
+    let transactions = tf
+        .make_block_builder()
+        .add_test_transaction_from_best_block(&mut rng)
+        .build()
+        .transactions()
+        .to_vec();
+    let expected_reward = pow_reward_amount(&tf, BlockHeight::new(1), &transactions);
+    let under_claimed = Amount::from_atoms(rng.gen_range(0..expected_reward.into_atoms()));
+    let over_claimed = (expected_reward + Amount::from_atoms(rng.gen_range(1..1000))).unwrap();
+
+    let mut make_mined_block = |tf: &mut TestFramework, reward: Amount| {
+        let (_, pub_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+        let block = tf
+            .make_block_builder()
+            .with_reward(vec![TxOutput::LockThenTransfer(
+                OutputValue::Coin(reward),
+                Destination::PublicKey(pub_key),
+                OutputTimeLock::ForBlockCount(reward_lock_distance as u64),
+            )])
+            .with_transactions(transactions.clone())
+            .build();
+        let mut block_header = block.header().clone();
+        assert_eq!(
+            consensus::mine(
+                block_header.header_mut().unwrap(),
+                u128::MAX,
+                difficulty.into(),
+                Arc::new(false.into())
+            )
+            .expect("Unexpected conversion error"),
+            consensus::MiningResult::Success
+        );
+        Block::new_from_header(block_header, block.body().clone()).unwrap()
+    };
+
+    // Over-claiming the reward
+    let block = make_mined_block(&mut tf, over_claimed);
+    let expected_error = match exact_pow_reward {
+        ExactPoWReward::Yes => {
+            ConnectTransactionError::BlockRewardAmountMismatch(expected_reward, over_claimed)
+        }
+        ExactPoWReward::No => {
+            ConnectTransactionError::AttemptToPrintMoney(expected_reward, over_claimed)
+        }
+    };
+    assert_eq!(
+        tf.process_block(block, BlockSource::Local).unwrap_err(),
+        ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(expected_error))
+    );
+    assert_eq!(tf.best_block_id(), tf.genesis().get_id());
+
+    // Under-claiming the reward is only allowed before the activation
+    let block = make_mined_block(&mut tf, under_claimed);
+    match exact_pow_reward {
+        ExactPoWReward::Yes => {
+            assert_eq!(
+                tf.process_block(block, BlockSource::Local).unwrap_err(),
+                ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(
+                    ConnectTransactionError::BlockRewardAmountMismatch(
+                        expected_reward,
+                        under_claimed
+                    )
+                ))
+            );
+        }
+        ExactPoWReward::No => {
+            let block_id = block.get_id();
+            tf.process_block(block, BlockSource::Local).unwrap();
+            assert_eq!(tf.best_block_id(), <Id<GenBlock>>::from(block_id));
+            return;
+        }
+    }
+
+    // Claiming exactly the subsidy plus the fees
+    let block = make_mined_block(&mut tf, expected_reward);
+    let block_id = block.get_id();
+    tf.process_block(block, BlockSource::Local).unwrap();
+    assert_eq!(tf.best_block_id(), <Id<GenBlock>>::from(block_id));
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -1008,10 +1164,24 @@ fn read_block_reward_from_storage(#[case] seed: Seed) {
         .reward_maturity_distance()
         .into();
 
-    let block_reward_output_count = rng.gen::<usize>() % 20;
+    let transactions = tf
+        .make_block_builder()
+        .add_test_transaction_from_best_block(&mut rng)
+        .build()
+        .transactions()
+        .to_vec();
+
+    // Split the reward randomly between the outputs, the total has to match exactly
+    let mut remaining_reward = pow_reward_amount(&tf, BlockHeight::new(1), &transactions);
+    let block_reward_output_count = 1 + rng.gen::<usize>() % 20;
     let expected_block_reward = (0..block_reward_output_count)
-        .map(|_| {
-            let amount = Amount::from_atoms(rng.gen::<u128>() % 50);
+        .map(|i| {
+            let amount = if i + 1 == block_reward_output_count {
+                remaining_reward
+            } else {
+                Amount::from_atoms(rng.gen_range(0..=remaining_reward.into_atoms()))
+            };
+            remaining_reward = (remaining_reward - amount).unwrap();
             let pub_key = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr).1;
             TxOutput::LockThenTransfer(
                 OutputValue::Coin(amount),
@@ -1026,7 +1196,7 @@ fn read_block_reward_from_storage(#[case] seed: Seed) {
         let mut random_invalid_block = tf
             .make_block_builder()
             .with_reward(expected_block_reward.clone())
-            .add_test_transaction_from_best_block(&mut rng)
+            .with_transactions(transactions)
             .build();
         make_invalid_pow_block(&mut random_invalid_block, u128::MAX, difficulty.into())
             .expect("generate invalid block");
@@ -1141,6 +1311,134 @@ fn blocks_from_the_future(#[case] seed: Seed) {
     });
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn block_timestamp_against_median_time_past(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let config = create_unit_test_config();
+
+        let genesis_timestamp = config.genesis_block().timestamp().as_int_seconds();
+        let current_time = Arc::new(SeqCstAtomicU64::new(genesis_timestamp));
+        let time_getter = mocked_time_getter_seconds(Arc::clone(&current_time));
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(config)
+            .with_time_getter(time_getter)
+            .build();
+
+        // Build a chain with increasing timestamps so that the median time past is below the tip
+        let blocks_count = rng.gen_range(1..20);
+        for i in 1..=blocks_count {
+            tf.make_block_builder()
+                .with_timestamp(BlockTimestamp::from_int_seconds(genesis_timestamp + i))
+                .build_and_process()
+                .unwrap()
+                .unwrap();
+        }
+        current_time.store(genesis_timestamp + blocks_count);
+
+        let median_time_past =
+            tf.chainstate.calculate_median_time_past(&tf.best_block_id()).unwrap();
+
+        // A block with the timestamp equal to the median time past is rejected
+        assert_eq!(
+            tf.make_block_builder()
+                .with_timestamp(median_time_past)
+                .build_and_process()
+                .unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::CheckBlockFailed(
+                CheckBlockError::BlockTimeOrderInvalid(median_time_past, median_time_past)
+            ))
+        );
+
+        // A block too far in the future is rejected with a distinct error
+        let max_future_offset =
+            tf.chainstate.get_chain_config().max_future_block_time_offset().as_secs();
+        assert_eq!(
+            tf.make_block_builder()
+                .with_timestamp(BlockTimestamp::from_int_seconds(
+                    current_time.load() + max_future_offset + 1
+                ))
+                .build_and_process()
+                .unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::CheckBlockFailed(
+                CheckBlockError::BlockFromTheFuture
+            ))
+        );
+
+        // A block with the timestamp above the median time past is accepted
+        let block_id = tf
+            .make_block_builder()
+            .with_timestamp(median_time_past.add_int_seconds(1).unwrap())
+            .build_and_process()
+            .unwrap()
+            .unwrap()
+            .block_id();
+        assert_eq!(tf.best_block_id(), <Id<GenBlock>>::from(block_id));
+    });
+}
+
+// Before the upgrade, a block timestamp equal to the median time past is accepted
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn strict_median_time_past_activation(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let make_upgrade = |strict_median_time_past| {
+            ChainstateUpgrade::new(
+                TokenFreezeActivated::Yes,
+                UniquePoolVrfKeys::Yes,
+                ExactPoWReward::Yes,
+                TokensConservation::Yes,
+                NftMetadataSizeLimit::Yes,
+                TokenMaxSupplyLimit::Yes,
+                strict_median_time_past,
+            )
+        };
+        let chain_config = common::chain::config::Builder::test_chain()
+            .chainstate_upgrades(
+                NetUpgrades::initialize(vec![
+                    (BlockHeight::zero(), make_upgrade(StrictMedianTimePast::No)),
+                    (BlockHeight::new(3), make_upgrade(StrictMedianTimePast::Yes)),
+                ])
+                .unwrap(),
+            )
+            .build();
+        let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+
+        for _ in 0..2 {
+            let median_time_past =
+                tf.chainstate.calculate_median_time_past(&tf.best_block_id()).unwrap();
+            tf.make_block_builder()
+                .with_timestamp(median_time_past)
+                .build_and_process()
+                .unwrap()
+                .unwrap();
+        }
+
+        let median_time_past =
+            tf.chainstate.calculate_median_time_past(&tf.best_block_id()).unwrap();
+        assert_eq!(
+            tf.make_block_builder()
+                .with_timestamp(median_time_past)
+                .build_and_process()
+                .unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::CheckBlockFailed(
+                CheckBlockError::BlockTimeOrderInvalid(median_time_past, median_time_past)
+            ))
+        );
+
+        tf.make_block_builder()
+            .with_timestamp(median_time_past.add_int_seconds(1).unwrap())
+            .build_and_process()
+            .unwrap()
+            .unwrap();
+        assert_eq!(tf.best_block_index().block_height(), BlockHeight::new(3));
+    });
+}
+
 #[test]
 fn mainnet_initialization() {
     let chain_config = Arc::new(common::chain::config::create_mainnet());
@@ -1157,6 +1455,115 @@ fn mainnet_initialization() {
     .unwrap();
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn block_serialized_size_limit(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+
+        let block = {
+            let mut tf = TestFramework::builder(&mut rng)
+                .with_chain_config(ConfigBuilder::test_chain().build())
+                .build();
+            tf.make_block_builder().add_test_transaction_from_best_block(&mut rng).build()
+        };
+        let block_size = block.encoded_size();
+
+        // The block is one byte over the limit
+        let chain_config = ConfigBuilder::test_chain().max_block_size(block_size - 1).build();
+        let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+        assert_eq!(
+            tf.process_block(block.clone(), BlockSource::Local).unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::CheckBlockFailed(
+                CheckBlockError::BlockSizeError(BlockSizeError::SerializedSize(
+                    block_size,
+                    block_size - 1
+                ))
+            ))
+        );
+        assert_eq!(tf.best_block_id(), tf.genesis().get_id());
+
+        // The block is exactly at the limit
+        let chain_config = ConfigBuilder::test_chain().max_block_size(block_size).build();
+        let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+        tf.process_block(block.clone(), BlockSource::Local).unwrap();
+        assert_eq!(tf.best_block_id(), <Id<GenBlock>>::from(block.get_id()));
+    });
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn block_tx_count_limit(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = ConfigBuilder::test_chain().max_block_tx_count(1).build();
+        let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+
+        // Transaction that spends the genesis reward
+        let tx1 = SignedTransaction::new(
+            Transaction::new(
+                0,
+                vec![TxInput::from_utxo(tf.genesis().get_id().into(), 0)],
+                vec![TxOutput::Transfer(
+                    get_output_value(&tf.genesis().utxos()[0]).unwrap(),
+                    anyonecanspend_address(),
+                )],
+            )
+            .unwrap(),
+            vec![empty_witness(&mut rng)],
+        )
+        .expect("invalid witness count");
+        let tx1_id = tx1.transaction().get_id();
+
+        // Transaction that spends tx1
+        let tx2 = SignedTransaction::new(
+            Transaction::new(
+                0,
+                vec![TxInput::from_utxo(tx1_id.into(), 0)],
+                vec![TxOutput::Transfer(
+                    get_output_value(&tx1.transaction().outputs()[0]).unwrap(),
+                    anyonecanspend_address(),
+                )],
+            )
+            .unwrap(),
+            vec![empty_witness(&mut rng)],
+        )
+        .expect("invalid witness count");
+
+        let block = tf
+            .make_block_builder()
+            .add_transaction(tx1.clone())
+            .add_transaction(tx2.clone())
+            .build();
+        let too_many_txs_error = ChainstateError::ProcessBlockError(BlockError::CheckBlockFailed(
+            CheckBlockError::TooManyTransactions(2, 1),
+        ));
+        assert_eq!(
+            tf.process_block(block.clone(), BlockSource::Local).unwrap_err(),
+            too_many_txs_error
+        );
+
+        // The transaction count is checked first if the block is also too large
+        let chain_config = ConfigBuilder::test_chain()
+            .max_block_tx_count(1)
+            .max_block_size(block.encoded_size() - 1)
+            .build();
+        let mut tf_small_blocks =
+            TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+        assert_eq!(
+            tf_small_blocks.process_block(block, BlockSource::Local).unwrap_err(),
+            too_many_txs_error
+        );
+
+        // The same transactions are accepted one per block
+        tf.make_block_builder().add_transaction(tx1).build_and_process().unwrap();
+        tf.make_block_builder().add_transaction(tx2).build_and_process().unwrap();
+        assert_eq!(tf.best_block_index().block_height(), BlockHeight::new(2));
+    });
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -1254,6 +1661,39 @@ fn burn_inputs_in_tx(#[case] seed: Seed) {
     });
 }
 
+// The amount a PoW block reward has to claim: the subsidy plus the fees of the block's transactions
+fn pow_reward_amount(
+    tf: &TestFramework,
+    height: BlockHeight,
+    transactions: &[SignedTransaction],
+) -> Amount {
+    let coins = |output: &TxOutput| match get_output_value(output) {
+        Some(OutputValue::Coin(amount)) => amount,
+        _ => Amount::ZERO,
+    };
+    let fees = transactions
+        .iter()
+        .map(|tx| {
+            let inputs_total = tx
+                .transaction()
+                .inputs()
+                .iter()
+                .map(|input| {
+                    let outpoint = input.utxo_outpoint().unwrap();
+                    coins(tf.chainstate.utxo(outpoint).unwrap().unwrap().output())
+                })
+                .sum::<Option<Amount>>()
+                .unwrap();
+            let outputs_total =
+                tx.transaction().outputs().iter().map(coins).sum::<Option<Amount>>().unwrap();
+            (inputs_total - outputs_total).unwrap()
+        })
+        .sum::<Option<Amount>>()
+        .unwrap();
+    let subsidy = tf.chainstate.get_chain_config().block_subsidy_at_height(&height);
+    (subsidy + fees).unwrap()
+}
+
 fn make_invalid_pow_block(
     block: &mut Block,
     max_nonce: u128,
@@ -0,0 +1,99 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc,
+};
+
+use chainstate::BlockSource;
+use chainstate_test_framework::TestFramework;
+use common::chain::GenBlock;
+use common::primitives::{BlockHeight, Id};
+use rstest::rstest;
+use test_utils::random::{make_seedable_rng, Seed};
+
+// Query the chainstate via a read handle from another thread while blocks are being processed.
+// Block processing must not be held up by the reads and the reads must always see a consistent
+// (and non-decreasing) tip. Two queries made through one snapshot must see the same tip.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn concurrent_reads_with_process_block(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let reader = tf.chainstate.read_handle();
+        let blocks_count = 10;
+
+        let stop = AtomicBool::new(false);
+        let (started_sender, started_receiver) = mpsc::channel();
+
+        let (reads_count, last_seen_tip) = std::thread::scope(|s| {
+            let (stop, reader) = (&stop, &reader);
+            let reader_thread = s.spawn(move || {
+                let mut reads_count = 0;
+                let mut last_height = BlockHeight::zero();
+                let mut last_seen_tip: Option<Id<GenBlock>> = None;
+
+                loop {
+                    // Check the flag before reading, so that the final read happens after
+                    // all the blocks have been processed.
+                    let stopped = stop.load(Ordering::Acquire);
+
+                    let snapshot = reader.snapshot().unwrap();
+                    let best_block_id = snapshot.get_best_block_id().unwrap();
+                    let index = snapshot.get_gen_block_index(&best_block_id).unwrap().unwrap();
+                    let height = snapshot.get_block_height_in_main_chain(&best_block_id).unwrap();
+                    drop(snapshot);
+                    assert_eq!(height, Some(index.block_height()));
+                    assert!(index.block_height() >= last_height);
+                    last_height = index.block_height();
+                    last_seen_tip = Some(best_block_id);
+                    reads_count += 1;
+
+                    if reads_count == 1 {
+                        started_sender.send(()).unwrap();
+                    }
+                    if stopped {
+                        break;
+                    }
+                }
+
+                (reads_count, last_seen_tip)
+            });
+
+            started_receiver.recv().unwrap();
+            for _ in 0..blocks_count {
+                let block = tf.make_block_builder().build();
+                tf.process_block(block, BlockSource::Local).unwrap();
+            }
+            stop.store(true, Ordering::Release);
+
+            reader_thread.join().unwrap()
+        });
+
+        assert!(reads_count > 1);
+        assert_eq!(last_seen_tip, Some(tf.best_block_id()));
+        assert_eq!(
+            reader
+                .snapshot()
+                .unwrap()
+                .get_block_height_in_main_chain(&tf.best_block_id())
+                .unwrap(),
+            Some(BlockHeight::new(blocks_count))
+        );
+    });
+}
@@ -23,6 +23,7 @@ use chainstate_test_framework::{
 };
 use common::{
     chain::{
+        config::Builder as ConfigBuilder,
         output_value::OutputValue,
         signature::{
             inputsig::{standard_signature::StandardInputSignature, InputWitness},
@@ -31,10 +32,12 @@ use common::{
         stakelock::StakePoolData,
         timelock::OutputTimeLock,
         tokens::{TokenData, TokenTransfer},
-        Destination, GenBlock, OutPointSourceId, SignedTransaction, TxInput, TxOutput,
-        UtxoOutPoint,
+        ChainstateUpgrade, Destination, ExactPoWReward, GenBlock, NetUpgrades,
+        NftMetadataSizeLimit, OutPointSourceId, SignedTransaction, StrictMedianTimePast,
+        TokenFreezeActivated, TokenMaxSupplyLimit, TokensConservation, TxInput, TxOutput,
+        UniquePoolVrfKeys, UtxoOutPoint,
     },
-    primitives::{per_thousand::PerThousand, Amount, Id, Idable},
+    primitives::{per_thousand::PerThousand, Amount, BlockDistance, BlockHeight, Id, Idable},
 };
 use crypto::{
     key::{KeyKind, PrivateKey},
@@ -678,3 +681,275 @@ fn decommission_from_stake_pool_with_staker_key(#[case] seed: Seed) {
             .unwrap();
     });
 }
+
+// Create a pool, then try to create another pool with the same VRF key: it must be rejected
+// while the first pool is active and accepted once the first pool is decommissioned.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn stake_pool_reuse_vrf_key(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let genesis_id = tf.genesis().get_id().into();
+        let (_, vrf_pk) = VRFPrivateKey::new_from_rng(&mut rng, VRFKeyKind::Schnorrkel);
+        let min_pledge = tf.chainstate.get_chain_config().min_stake_pool_pledge();
+
+        let genesis_outpoint = UtxoOutPoint::new(OutPointSourceId::BlockReward(genesis_id), 0);
+        let pool_id_1 = pos_accounting::make_pool_id(&genesis_outpoint);
+        let (stake_pool_data_1, _) =
+            create_stake_pool_data_with_all_reward_to_owner(&mut rng, min_pledge, vrf_pk.clone());
+        let tx1 = TransactionBuilder::new()
+            .add_input(genesis_outpoint.into(), empty_witness(&mut rng))
+            .add_output(TxOutput::CreateStakePool(
+                pool_id_1,
+                Box::new(stake_pool_data_1),
+            ))
+            .add_output(TxOutput::Transfer(
+                OutputValue::Coin(min_pledge),
+                anyonecanspend_address(),
+            ))
+            .build();
+        let tx1_id = tx1.transaction().get_id();
+        tf.make_block_builder().add_transaction(tx1).build_and_process().unwrap();
+
+        let transfer_outpoint = UtxoOutPoint::new(tx1_id.into(), 1);
+        let pool_id_2 = pos_accounting::make_pool_id(&transfer_outpoint);
+        let (stake_pool_data_2, _) =
+            create_stake_pool_data_with_all_reward_to_owner(&mut rng, min_pledge, vrf_pk);
+        let tx2 = TransactionBuilder::new()
+            .add_input(transfer_outpoint.into(), empty_witness(&mut rng))
+            .add_output(TxOutput::CreateStakePool(
+                pool_id_2,
+                Box::new(stake_pool_data_2),
+            ))
+            .build();
+
+        let result = tf.make_block_builder().add_transaction(tx2.clone()).build_and_process();
+        assert_eq!(
+            result.unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(
+                ConnectTransactionError::StakePoolVrfKeyAlreadyInUse(pool_id_2, pool_id_1)
+            ))
+        );
+
+        // decommission the first pool, which frees its VRF key
+        let decommission_tx = TransactionBuilder::new()
+            .add_input(
+                UtxoOutPoint::new(tx1_id.into(), 0).into(),
+                empty_witness(&mut rng),
+            )
+            .add_output(TxOutput::LockThenTransfer(
+                OutputValue::Coin(min_pledge),
+                anyonecanspend_address(),
+                OutputTimeLock::ForBlockCount(1),
+            ))
+            .build();
+        tf.make_block_builder()
+            .add_transaction(decommission_tx)
+            .build_and_process()
+            .unwrap();
+
+        tf.make_block_builder().add_transaction(tx2).build_and_process().unwrap();
+        assert!(
+            PoSAccountingStorageRead::<TipStorageTag>::get_pool_data(&tf.storage, pool_id_2)
+                .unwrap()
+                .is_some()
+        );
+    });
+}
+
+// Pools sharing a VRF key can be created before the rule is activated, but not after
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn stake_pool_reuse_vrf_key_before_activation(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = ConfigBuilder::test_chain()
+            .chainstate_upgrades(
+                NetUpgrades::initialize(vec![
+                    (
+                        BlockHeight::zero(),
+                        ChainstateUpgrade::new(
+                            TokenFreezeActivated::Yes,
+                            UniquePoolVrfKeys::No,
+                            ExactPoWReward::Yes,
+                            TokensConservation::Yes,
+                            NftMetadataSizeLimit::Yes,
+                            TokenMaxSupplyLimit::Yes,
+                            StrictMedianTimePast::Yes,
+                        ),
+                    ),
+                    (BlockHeight::new(2), ChainstateUpgrade::latest()),
+                ])
+                .unwrap(),
+            )
+            .build();
+        let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+        let genesis_id = tf.genesis().get_id().into();
+        let (_, vrf_pk) = VRFPrivateKey::new_from_rng(&mut rng, VRFKeyKind::Schnorrkel);
+        let min_pledge = tf.chainstate.get_chain_config().min_stake_pool_pledge();
+
+        let genesis_outpoint = UtxoOutPoint::new(OutPointSourceId::BlockReward(genesis_id), 0);
+        let pool_id_1 = pos_accounting::make_pool_id(&genesis_outpoint);
+        let (stake_pool_data_1, _) =
+            create_stake_pool_data_with_all_reward_to_owner(&mut rng, min_pledge, vrf_pk.clone());
+        let tx1 = TransactionBuilder::new()
+            .add_input(genesis_outpoint.into(), empty_witness(&mut rng))
+            .add_output(TxOutput::CreateStakePool(
+                pool_id_1,
+                Box::new(stake_pool_data_1),
+            ))
+            .add_output(TxOutput::Transfer(
+                OutputValue::Coin(min_pledge),
+                anyonecanspend_address(),
+            ))
+            .add_output(TxOutput::Transfer(
+                OutputValue::Coin(min_pledge),
+                anyonecanspend_address(),
+            ))
+            .build();
+        let tx1_id = tx1.transaction().get_id();
+
+        let transfer_outpoint = UtxoOutPoint::new(tx1_id.into(), 1);
+        let pool_id_2 = pos_accounting::make_pool_id(&transfer_outpoint);
+        let (stake_pool_data_2, _) =
+            create_stake_pool_data_with_all_reward_to_owner(&mut rng, min_pledge, vrf_pk.clone());
+        let tx2 = TransactionBuilder::new()
+            .add_input(transfer_outpoint.into(), empty_witness(&mut rng))
+            .add_output(TxOutput::CreateStakePool(
+                pool_id_2,
+                Box::new(stake_pool_data_2),
+            ))
+            .build();
+
+        tf.make_block_builder()
+            .with_transactions(vec![tx1, tx2])
+            .build_and_process()
+            .unwrap();
+
+        for pool_id in [pool_id_1, pool_id_2] {
+            assert!(
+                PoSAccountingStorageRead::<TipStorageTag>::get_pool_data(&tf.storage, pool_id)
+                    .unwrap()
+                    .is_some()
+            );
+        }
+
+        // The rule is activated at height 2
+        let transfer_outpoint = UtxoOutPoint::new(tx1_id.into(), 2);
+        let pool_id_3 = pos_accounting::make_pool_id(&transfer_outpoint);
+        let (stake_pool_data_3, _) =
+            create_stake_pool_data_with_all_reward_to_owner(&mut rng, min_pledge, vrf_pk);
+        let tx3 = TransactionBuilder::new()
+            .add_input(transfer_outpoint.into(), empty_witness(&mut rng))
+            .add_output(TxOutput::CreateStakePool(
+                pool_id_3,
+                Box::new(stake_pool_data_3),
+            ))
+            .build();
+
+        let result = tf.make_block_builder().add_transaction(tx3).build_and_process();
+        assert!(matches!(
+            result.unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(
+                ConnectTransactionError::StakePoolVrfKeyAlreadyInUse(pool_id, existing_pool_id)
+            )) if pool_id == pool_id_3 && [pool_id_1, pool_id_2].contains(&existing_pool_id)
+        ));
+    });
+}
+
+// Decommission a pool and check that the decommissioned coins must be locked for the maturity
+// distance from the chain config and cannot be spent until it has passed.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn decommission_output_maturity(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let maturity: u64 = rng.gen_range(2..20);
+        let chain_config = ConfigBuilder::test_chain()
+            .empty_consensus_reward_maturity_distance(BlockDistance::new(maturity as i64))
+            .build();
+        let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+
+        let (_, vrf_pk) = VRFPrivateKey::new_from_rng(&mut rng, VRFKeyKind::Schnorrkel);
+        let amount_to_stake = tf.chainstate.get_chain_config().min_stake_pool_pledge();
+        let (stake_pool_data, _) =
+            create_stake_pool_data_with_all_reward_to_owner(&mut rng, amount_to_stake, vrf_pk);
+        let genesis_outpoint = UtxoOutPoint::new(
+            OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+            0,
+        );
+        let pool_id = pos_accounting::make_pool_id(&genesis_outpoint);
+        let tx1 = TransactionBuilder::new()
+            .add_input(genesis_outpoint.into(), empty_witness(&mut rng))
+            .add_output(TxOutput::CreateStakePool(
+                pool_id,
+                Box::new(stake_pool_data),
+            ))
+            .build();
+        let stake_pool_outpoint = UtxoOutPoint::new(tx1.transaction().get_id().into(), 0);
+        tf.make_block_builder().add_transaction(tx1).build_and_process().unwrap();
+
+        let make_decommission_tx = |rng: &mut _, block_count| {
+            TransactionBuilder::new()
+                .add_input(stake_pool_outpoint.clone().into(), empty_witness(rng))
+                .add_output(TxOutput::LockThenTransfer(
+                    OutputValue::Coin(amount_to_stake),
+                    anyonecanspend_address(),
+                    OutputTimeLock::ForBlockCount(block_count),
+                ))
+                .build()
+        };
+
+        // the decommissioned coins must be locked for at least the maturity distance
+        let tx = make_decommission_tx(&mut rng, maturity - 1);
+        let tx_id = tx.transaction().get_id();
+        let result = tf.make_block_builder().add_transaction(tx).build_and_process();
+        assert_eq!(
+            result.unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(
+                ConnectTransactionError::IOPolicyError(
+                    IOPolicyError::AttemptToPrintMoneyOrViolateTimelockConstraints,
+                    tx_id.into()
+                )
+            ))
+        );
+
+        let decommission_tx = make_decommission_tx(&mut rng, maturity);
+        let decommissioned_outpoint =
+            UtxoOutPoint::new(decommission_tx.transaction().get_id().into(), 0);
+        tf.make_block_builder()
+            .add_transaction(decommission_tx)
+            .build_and_process()
+            .unwrap();
+
+        let spend_tx = TransactionBuilder::new()
+            .add_input(
+                decommissioned_outpoint.clone().into(),
+                empty_witness(&mut rng),
+            )
+            .add_output(TxOutput::Transfer(
+                OutputValue::Coin(amount_to_stake),
+                anyonecanspend_address(),
+            ))
+            .build();
+
+        // the last block before maturity cannot spend the decommissioned coins
+        for _ in 0..maturity - 2 {
+            tf.make_block_builder().build_and_process().unwrap();
+        }
+        let result = tf.make_block_builder().add_transaction(spend_tx.clone()).build_and_process();
+        assert_eq!(
+            result.unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(
+                ConnectTransactionError::TimeLockViolation(decommissioned_outpoint)
+            ))
+        );
+
+        tf.make_block_builder().build_and_process().unwrap();
+        tf.make_block_builder().add_transaction(spend_tx).build_and_process().unwrap();
+    });
+}
@@ -62,6 +62,7 @@ fn get_locator(#[case] seed: Seed) {
 
             // Check the locator length.
             let locator = btf.chainstate.get_locator().unwrap();
+            assert_eq!(btf.chainstate.get_locator().unwrap(), locator);
             assert_eq!(
                 locator.len(),
                 blocks.next_power_of_two().trailing_zeros() as usize + 1
@@ -623,6 +624,10 @@ fn initial_block_download(#[case] seed: Seed) {
                 min_max_bootstrap_import_buffer_sizes: Default::default(),
                 tx_index_enabled: Default::default(),
                 max_tip_age: Duration::from_secs(1).into(),
+                commit_batch_size: Default::default(),
+                prune_depth: Default::default(),
+                reindex_tx_index: Default::default(),
+                max_blocks_pruned_at_once: Default::default(),
             })
             .with_initial_time_since_genesis(2)
             .build();
@@ -670,6 +675,10 @@ fn header_check_for_orphan(#[case] seed: Seed) {
                 min_max_bootstrap_import_buffer_sizes: Default::default(),
                 tx_index_enabled: Default::default(),
                 max_tip_age: Default::default(),
+                commit_batch_size: Default::default(),
+                prune_depth: Default::default(),
+                reindex_tx_index: Default::default(),
+                max_blocks_pruned_at_once: Default::default(),
             })
             .build();
 
@@ -708,3 +717,48 @@ fn header_check_for_orphan(#[case] seed: Seed) {
         );
     });
 }
+
+// A header with an invalid timestamp deep in the batch is rejected by the batch check,
+// even though the batch is not connected to the chain.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn header_check_batch(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let max_future_offset =
+            tf.chainstate.get_chain_config().max_future_block_time_offset().as_secs();
+        let current_time = tf.current_time().as_secs_since_epoch();
+
+        let block_1 = tf.make_block_builder().make_orphan(&mut rng).build();
+        let block_2 = tf.make_block_builder().with_parent(block_1.get_id().into()).build();
+        let block_3 = tf
+            .make_block_builder()
+            .with_parent(block_2.get_id().into())
+            .with_timestamp(BlockTimestamp::from_int_seconds(
+                current_time + max_future_offset + 1,
+            ))
+            .build();
+        let block_4 = tf.make_block_builder().with_parent(block_3.get_id().into()).build();
+
+        let headers = [&block_1, &block_2]
+            .into_iter()
+            .map(|block| block.header().clone())
+            .collect::<Vec<_>>();
+        tf.chainstate.preliminary_header_check_batch(&headers).unwrap();
+
+        let headers = [&block_1, &block_2, &block_3, &block_4]
+            .into_iter()
+            .map(|block| block.header().clone())
+            .collect::<Vec<_>>();
+        let err = tf.chainstate.preliminary_header_check_batch(&headers).unwrap_err();
+        assert_eq!(
+            err,
+            ChainstateError::ProcessBlockError(chainstate::BlockError::CheckBlockFailed(
+                chainstate::CheckBlockError::BlockFromTheFuture
+            ))
+        );
+    });
+}
@@ -0,0 +1,454 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chainstate::{BlockError, BlockSource, ChainstateError, ConnectTransactionError, TokensError};
+use chainstate_test_framework::{get_output_value, TestFramework, TransactionBuilder};
+use common::{
+    chain::{
+        output_value::OutputValue,
+        signature::inputsig::{standard_signature::StandardInputSignature, InputWitness},
+        signed_transaction::SignedTransaction,
+        tokens::{token_id, TokenFreeze, TokenId, TokenIssuance, TokenTransfer, TokenUnfreeze},
+        ChainstateUpgrade, Destination, ExactPoWReward, NetUpgrades, NftMetadataSizeLimit,
+        OutPointSourceId, StrictMedianTimePast, TokenFreezeActivated, TokenMaxSupplyLimit,
+        TokensConservation, Transaction, TxInput, TxOutput, UniquePoolVrfKeys,
+    },
+    primitives::{Amount, BlockHeight, Idable},
+};
+use crypto::{
+    key::{KeyKind, PrivateKey},
+    random::{CryptoRng, Rng},
+};
+use rstest::rstest;
+use test_utils::{
+    random::{make_seedable_rng, Seed},
+    random_string,
+};
+
+// The owner of the issued tokens, `None` key means the outputs are anyone-can-spend
+struct Issuer {
+    key: Option<PrivateKey>,
+    destination: Destination,
+}
+
+impl Issuer {
+    fn new(rng: &mut (impl Rng + CryptoRng)) -> Self {
+        let (sk, pk) = PrivateKey::new_from_rng(rng, KeyKind::Secp256k1Schnorr);
+        Self {
+            key: Some(sk),
+            destination: Destination::PublicKey(pk),
+        }
+    }
+
+    fn anyone() -> Self {
+        Self {
+            key: None,
+            destination: Destination::AnyoneCanSpend,
+        }
+    }
+
+    // Sign the only input of the transaction, which spends `utxo` belonging to the issuer
+    fn sign(&self, tx: Transaction, utxo: &TxOutput) -> SignedTransaction {
+        let witness = match &self.key {
+            Some(key) => InputWitness::Standard(
+                StandardInputSignature::produce_uniparty_signature_for_input(
+                    key,
+                    Default::default(),
+                    self.destination.clone(),
+                    &tx,
+                    &[Some(utxo)],
+                    0,
+                )
+                .unwrap(),
+            ),
+            None => InputWitness::NoSignature(None),
+        };
+        SignedTransaction::new(tx, vec![witness]).unwrap()
+    }
+}
+
+struct IssuedToken {
+    token_id: TokenId,
+    // The issuance transaction; its output 0 holds the issued tokens
+    outpoint_id: OutPointSourceId,
+    issuance_output: TxOutput,
+    // The amount of coins in each of the outputs 1 and 2 of the issuance transaction
+    change: Amount,
+}
+
+// Issue a token to `issuer`. The remaining coins are split between the issuer (output 1)
+// and an anyone-can-spend output (output 2).
+fn issue_token(
+    tf: &mut TestFramework,
+    rng: &mut impl Rng,
+    issuer: &Issuer,
+    amount: Amount,
+) -> IssuedToken {
+    let genesis_outpoint_id = OutPointSourceId::BlockReward(tf.genesis().get_id().into());
+    let genesis_amount = get_output_value(&tf.genesis().utxos()[0]).unwrap().coin_amount().unwrap();
+    let token_min_issuance_fee = tf.chainstate.get_chain_config().token_min_issuance_fee();
+    let change = ((genesis_amount - token_min_issuance_fee).unwrap() / 2).unwrap();
+
+    let issuance_output = TxOutput::Transfer(
+        TokenIssuance {
+            token_ticker: random_string(rng, 1..5).as_bytes().to_vec(),
+            amount_to_issue: amount,
+            number_of_decimals: rng.gen_range(1..18),
+            metadata_uri: "https://some_site.some".as_bytes().to_vec(),
+        }
+        .into(),
+        issuer.destination.clone(),
+    );
+    let tx = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(genesis_outpoint_id, 0),
+            InputWitness::NoSignature(None),
+        )
+        .add_output(issuance_output.clone())
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(change),
+            issuer.destination.clone(),
+        ))
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(change),
+            Destination::AnyoneCanSpend,
+        ))
+        .add_output(TxOutput::Burn(OutputValue::Coin(token_min_issuance_fee)))
+        .build();
+    let token_id = token_id(tx.transaction()).unwrap();
+    let outpoint_id: OutPointSourceId = tx.transaction().get_id().into();
+
+    tf.make_block_builder().add_transaction(tx).build_and_process().unwrap();
+
+    IssuedToken {
+        token_id,
+        outpoint_id,
+        issuance_output,
+        change,
+    }
+}
+
+// Pass the coins of `owner` at `coin_outpoint` through and attach the freeze/unfreeze operation
+fn make_frozen_state_change_tx(
+    owner: &Issuer,
+    coin_outpoint: (OutPointSourceId, u32),
+    coin_amount: Amount,
+    operation: OutputValue,
+) -> SignedTransaction {
+    let utxo = TxOutput::Transfer(OutputValue::Coin(coin_amount), owner.destination.clone());
+    let tx = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(coin_outpoint.0, coin_outpoint.1),
+            InputWitness::NoSignature(None),
+        )
+        .add_output(utxo.clone())
+        .add_output(TxOutput::Burn(operation))
+        .build()
+        .transaction()
+        .clone();
+    owner.sign(tx, &utxo)
+}
+
+// Send all the issued tokens to an anyone-can-spend output
+fn make_transfer_tx(issuer: &Issuer, token: &IssuedToken, amount: Amount) -> SignedTransaction {
+    let tx = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(token.outpoint_id.clone(), 0),
+            InputWitness::NoSignature(None),
+        )
+        .add_output(TxOutput::Transfer(
+            TokenTransfer {
+                token_id: token.token_id,
+                amount,
+            }
+            .into(),
+            Destination::AnyoneCanSpend,
+        ))
+        .build()
+        .transaction()
+        .clone();
+    issuer.sign(tx, &token.issuance_output)
+}
+
+fn tokens_error(err: TokensError) -> ChainstateError {
+    ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(
+        ConnectTransactionError::TokensError(err),
+    ))
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn freeze_then_unfreeze(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+
+        let issuer = Issuer::new(&mut rng);
+        let amount = Amount::from_atoms(rng.gen_range(1..=token_max_supply));
+        let token = issue_token(&mut tf, &mut rng, &issuer, amount);
+        let token_id = token.token_id;
+
+        // Unfreezing a token that is not frozen is an error
+        let tx = make_frozen_state_change_tx(
+            &issuer,
+            (token.outpoint_id.clone(), 1),
+            token.change,
+            TokenUnfreeze { token_id }.into(),
+        );
+        let result = tf.make_block_builder().add_transaction(tx).build_and_process();
+        assert_eq!(
+            result.unwrap_err(),
+            tokens_error(TokensError::TokenNotFrozen(token_id))
+        );
+
+        // Freeze
+        let tx = make_frozen_state_change_tx(
+            &issuer,
+            (token.outpoint_id.clone(), 1),
+            token.change,
+            TokenFreeze { token_id }.into(),
+        );
+        let freeze_outpoint_id: OutPointSourceId = tx.transaction().get_id().into();
+        tf.make_block_builder().add_transaction(tx).build_and_process().unwrap();
+
+        // Transfers are blocked while the token is frozen
+        let tx = make_transfer_tx(&issuer, &token, amount);
+        let tx_id = tx.transaction().get_id();
+        let result = tf.make_block_builder().add_transaction(tx).build_and_process();
+        assert_eq!(
+            result.unwrap_err(),
+            tokens_error(TokensError::TransferOfFrozenToken(token_id, tx_id))
+        );
+
+        // Freezing the token again is an error
+        let tx = make_frozen_state_change_tx(
+            &issuer,
+            (freeze_outpoint_id.clone(), 0),
+            token.change,
+            TokenFreeze { token_id }.into(),
+        );
+        let result = tf.make_block_builder().add_transaction(tx).build_and_process();
+        assert_eq!(
+            result.unwrap_err(),
+            tokens_error(TokensError::TokenAlreadyFrozen(token_id))
+        );
+
+        // Unfreeze
+        let tx = make_frozen_state_change_tx(
+            &issuer,
+            (freeze_outpoint_id, 0),
+            token.change,
+            TokenUnfreeze { token_id }.into(),
+        );
+        tf.make_block_builder().add_transaction(tx).build_and_process().unwrap();
+
+        // Transfers are possible again
+        let tx = make_transfer_tx(&issuer, &token, amount);
+        tf.make_block_builder().add_transaction(tx).build_and_process().unwrap();
+    });
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn freeze_not_authorized(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+
+        let issuer = Issuer::new(&mut rng);
+        let amount = Amount::from_atoms(rng.gen_range(1..=token_max_supply));
+        let token = issue_token(&mut tf, &mut rng, &issuer, amount);
+        let token_id = token.token_id;
+
+        // The freezing transaction doesn't spend anything that belongs to the issuer
+        let tx = make_frozen_state_change_tx(
+            &Issuer::anyone(),
+            (token.outpoint_id.clone(), 2),
+            token.change,
+            TokenFreeze { token_id }.into(),
+        );
+        let tx_id = tx.transaction().get_id();
+        let result = tf.make_block_builder().add_transaction(tx).build_and_process();
+        assert_eq!(
+            result.unwrap_err(),
+            tokens_error(TokensError::FrozenStateChangeNotAuthorized(token_id, tx_id))
+        );
+
+        // Freezing a token that doesn't exist
+        let unknown_token_id = TokenId::random_using(&mut rng);
+        let tx = make_frozen_state_change_tx(
+            &issuer,
+            (token.outpoint_id, 1),
+            token.change,
+            TokenFreeze {
+                token_id: unknown_token_id,
+            }
+            .into(),
+        );
+        let result = tf.make_block_builder().add_transaction(tx).build_and_process();
+        assert_eq!(
+            result.unwrap_err(),
+            tokens_error(TokensError::FrozenStateChangeOfNonexistentToken(
+                unknown_token_id
+            ))
+        );
+    });
+}
+
+// A token issued to an anyone-can-spend destination has no freezing authority
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn freeze_anyone_can_spend_issuance(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+
+        let issuer = Issuer::anyone();
+        let amount = Amount::from_atoms(rng.gen_range(1..=token_max_supply));
+        let token = issue_token(&mut tf, &mut rng, &issuer, amount);
+        let token_id = token.token_id;
+
+        let tx = make_frozen_state_change_tx(
+            &issuer,
+            (token.outpoint_id, 1),
+            token.change,
+            TokenFreeze { token_id }.into(),
+        );
+        let tx_id = tx.transaction().get_id();
+        let result = tf.make_block_builder().add_transaction(tx).build_and_process();
+        assert_eq!(
+            result.unwrap_err(),
+            tokens_error(TokensError::FrozenStateChangeNotAuthorized(token_id, tx_id))
+        );
+    });
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn freeze_before_activation(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = common::chain::config::Builder::test_chain()
+            .chainstate_upgrades(
+                NetUpgrades::initialize(vec![
+                    (
+                        BlockHeight::zero(),
+                        ChainstateUpgrade::new(
+                            TokenFreezeActivated::No,
+                            UniquePoolVrfKeys::Yes,
+                            ExactPoWReward::Yes,
+                            TokensConservation::Yes,
+                            NftMetadataSizeLimit::Yes,
+                            TokenMaxSupplyLimit::Yes,
+                            StrictMedianTimePast::Yes,
+                        ),
+                    ),
+                    (
+                        BlockHeight::new(3),
+                        ChainstateUpgrade::new(
+                            TokenFreezeActivated::Yes,
+                            UniquePoolVrfKeys::Yes,
+                            ExactPoWReward::Yes,
+                            TokensConservation::Yes,
+                            NftMetadataSizeLimit::Yes,
+                            TokenMaxSupplyLimit::Yes,
+                            StrictMedianTimePast::Yes,
+                        ),
+                    ),
+                ])
+                .unwrap(),
+            )
+            .build();
+        let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+
+        let issuer = Issuer::new(&mut rng);
+        let amount = Amount::from_atoms(rng.gen_range(1..=token_max_supply));
+        let token = issue_token(&mut tf, &mut rng, &issuer, amount);
+        let token_id = token.token_id;
+
+        // Block 2 is before the activation
+        let tx = make_frozen_state_change_tx(
+            &issuer,
+            (token.outpoint_id.clone(), 1),
+            token.change,
+            TokenFreeze { token_id }.into(),
+        );
+        let tx_id = tx.transaction().get_id();
+        let result = tf.make_block_builder().add_transaction(tx).build_and_process();
+        assert_eq!(
+            result.unwrap_err(),
+            tokens_error(TokensError::TokenFreezeNotActivated(token_id, tx_id))
+        );
+
+        // Block 3 activates the freezing
+        tf.make_block_builder().build_and_process().unwrap();
+        let tx = make_frozen_state_change_tx(
+            &issuer,
+            (token.outpoint_id, 1),
+            token.change,
+            TokenFreeze { token_id }.into(),
+        );
+        tf.make_block_builder().add_transaction(tx).build_and_process().unwrap();
+    });
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn freeze_reverted_on_reorg(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let token_max_supply = tf.chainstate.get_chain_config().token_max_supply();
+
+        let issuer = Issuer::new(&mut rng);
+        let amount = Amount::from_atoms(rng.gen_range(1..=token_max_supply));
+        let token = issue_token(&mut tf, &mut rng, &issuer, amount);
+        let issuance_block_id = tf.best_block_id();
+
+        let tx = make_frozen_state_change_tx(
+            &issuer,
+            (token.outpoint_id.clone(), 1),
+            token.change,
+            TokenFreeze {
+                token_id: token.token_id,
+            }
+            .into(),
+        );
+        tf.make_block_builder().add_transaction(tx).build_and_process().unwrap();
+
+        // Disconnect the freezing block by building a longer chain without it
+        let alt_block = tf.make_block_builder().with_parent(issuance_block_id).build();
+        let alt_block_id = alt_block.get_id();
+        tf.process_block(alt_block, BlockSource::Local).unwrap();
+        tf.make_block_builder()
+            .with_parent(alt_block_id.into())
+            .build_and_process()
+            .unwrap()
+            .unwrap();
+        assert_eq!(tf.best_block_index().block_height(), 3.into());
+
+        let tx = make_transfer_tx(&issuer, &token, amount);
+        tf.make_block_builder().add_transaction(tx).build_and_process().unwrap();
+    });
+}
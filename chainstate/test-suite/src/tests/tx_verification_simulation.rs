@@ -35,6 +35,10 @@ fn simulation(
                 max_orphan_blocks: Default::default(),
                 min_max_bootstrap_import_buffer_sizes: Default::default(),
                 max_tip_age: Default::default(),
+                commit_batch_size: Default::default(),
+                prune_depth: Default::default(),
+                reindex_tx_index: Default::default(),
+                max_blocks_pruned_at_once: Default::default(),
             })
             .with_tx_verification_strategy(TxVerificationStrategy::Randomized(seed))
             .build();
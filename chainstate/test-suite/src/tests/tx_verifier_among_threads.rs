@@ -72,6 +72,13 @@ impl PoSAccountingView for EmptyAccountingView {
         Ok(None)
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        _vrf_public_key: &crypto::vrf::VRFPublicKey,
+    ) -> Result<Option<PoolId>, pos_accounting::Error> {
+        Ok(None)
+    }
+
     fn get_pool_delegations_shares(
         &self,
         _pool_id: PoolId,
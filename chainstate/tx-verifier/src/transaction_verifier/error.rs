@@ -62,6 +62,8 @@ pub enum ConnectTransactionError {
     AttemptToPrintMoney(Amount, Amount),
     #[error("Block reward inputs and outputs value mismatch (total inputs: `{0:?}` vs total outputs `{1:?}`")]
     BlockRewardInputOutputMismatch(Amount, Amount),
+    #[error("Block reward amount mismatch (expected subsidy plus fees: `{0:?}` vs total outputs `{1:?}`")]
+    BlockRewardAmountMismatch(Amount, Amount),
     #[error("Fee calculation failed (total inputs: `{0:?}` vs total outputs `{1:?}`")]
     TxFeeTotalCalcFailed(Amount, Amount),
     #[error("Signature verification failed in transaction: {0}")]
@@ -80,6 +82,8 @@ pub enum ConnectTransactionError {
     FailedToAddAllFeesOfBlock(Id<Block>),
     #[error("Block reward addition error for block {0}")]
     RewardAdditionError(Id<Block>),
+    #[error("Sum of block reward outputs overflows in block {0}")]
+    RewardOutputsTotalOverflow(Id<Block>),
     #[error("Timelock rules violated in output {0:?}")]
     TimeLockViolation(UtxoOutPoint),
     #[error("Utxo error: {0}")]
@@ -146,6 +150,8 @@ pub enum ConnectTransactionError {
         "Transaction {0} has not enough pledge to create a stake pool: giver {1:?}, required {2:?}"
     )]
     NotEnoughPledgeToCreateStakePool(Id<Transaction>, Amount, Amount),
+    #[error("VRF public key of stake pool {0} is already used by stake pool {1}")]
+    StakePoolVrfKeyAlreadyInUse(PoolId, PoolId),
     #[error("Attempt to create stake pool from accounting inputs")]
     AttemptToCreateStakePoolFromAccounts,
     #[error("Attempt to create delegation from accounting inputs")]
@@ -217,34 +223,53 @@ impl From<TxMainChainIndexError> for TxIndexError {
     }
 }
 
+/// The reason a URI in token metadata was rejected
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum UriError {
+    #[error("URI length {0} exceeds the limit of {1}")]
+    TooLong(usize, usize),
+    #[error("URI data can't be decoded")]
+    Undecodable,
+    #[error("URI is not a valid UTF-8 string")]
+    InvalidUtf8,
+    #[error("Illegal character {0:?} in URI")]
+    IllegalCharacter(char),
+    #[error("Invalid URI scheme {0:?}")]
+    BadScheme(String),
+}
+
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
 pub enum TokenIssuanceError {
-    #[error("Invalid name length")]
-    IssueErrorInvalidNameLength,
-    #[error("Invalid ticker length")]
-    IssueErrorInvalidTickerLength,
-    #[error("Invalid ticker length")]
-    IssueErrorInvalidDescriptionLength,
-    #[error("Invalid character in token ticker")]
-    IssueErrorTickerHasNoneAlphaNumericChar,
-    #[error("Invalid character in token name")]
-    IssueErrorNameHasNoneAlphaNumericChar,
-    #[error("Invalid character in token description")]
-    IssueErrorDescriptionHasNoneAlphaNumericChar,
+    #[error("Invalid name length {0}, must be between 1 and {1}")]
+    IssueErrorInvalidNameLength(usize, usize),
+    #[error("Invalid ticker length {0}, must be between 1 and {1}")]
+    IssueErrorInvalidTickerLength(usize, usize),
+    #[error("Invalid description length {0}, must be between 1 and {1}")]
+    IssueErrorInvalidDescriptionLength(usize, usize),
+    #[error("Invalid character {0:?} in token ticker")]
+    IssueErrorTickerHasNoneAlphaNumericChar(char),
+    #[error("Invalid character {0:?} in token name")]
+    IssueErrorNameHasNoneAlphaNumericChar(char),
+    #[error("Invalid character {0:?} in token description")]
+    IssueErrorDescriptionHasNoneAlphaNumericChar(char),
     #[error("Incorrect amount")]
     IssueAmountIsZero,
-    #[error("Too many decimals")]
-    IssueErrorTooManyDecimals,
-    #[error("Incorrect metadata URI")]
-    IssueErrorIncorrectMetadataURI,
-    #[error("Incorrect icon URI")]
-    IssueErrorIncorrectIconURI,
-    #[error("Incorrect media URI")]
-    IssueErrorIncorrectMediaURI,
-    #[error("The media hash is too short")]
-    MediaHashTooShort,
-    #[error("The media hash is too long")]
-    MediaHashTooLong,
+    #[error("Too many decimals {0}, the maximum is {1}")]
+    IssueErrorTooManyDecimals(u8, u8),
+    #[error("Issued amount {0:?} exceeds the maximum token supply {1:?}")]
+    IssueAmountExceedsMaxSupply(Amount, Amount),
+    #[error("Incorrect metadata URI: {0}")]
+    IssueErrorIncorrectMetadataURI(UriError),
+    #[error("Incorrect icon URI: {0}")]
+    IssueErrorIncorrectIconURI(UriError),
+    #[error("Incorrect media URI: {0}")]
+    IssueErrorIncorrectMediaURI(UriError),
+    #[error("The media hash length {0} is below the minimum of {1}")]
+    MediaHashTooShort(usize, usize),
+    #[error("The media hash length {0} exceeds the maximum of {1}")]
+    MediaHashTooLong(usize, usize),
+    #[error("The NFT metadata size {0} exceeds the maximum of {1}")]
+    IssueErrorMetadataTooLarge(usize, usize),
 }
 
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
@@ -253,8 +278,8 @@ pub enum TokensError {
     StorageError(#[from] chainstate_storage::Error),
     #[error("Issuance error {0} in transaction {1} in block {2}")]
     IssueError(TokenIssuanceError, Id<Transaction>, Id<Block>),
-    #[error("Too many tokens issuance in transaction {0} in block {1}")]
-    MultipleTokenIssuanceInTransaction(Id<Transaction>, Id<Block>),
+    #[error("Too many tokens issuance in transaction {0} in block {1:?}")]
+    MultipleTokenIssuanceInTransaction(Id<Transaction>, Option<Id<Block>>),
     #[error("Coin or token overflow")]
     CoinOrTokenOverflow,
     #[error("Insufficient token issuance fee in transaction {0} in block {1}")]
@@ -269,6 +294,26 @@ pub enum TokensError {
     InvariantBrokenUndoIssuanceOnNonexistentToken(TokenId),
     #[error("Invariant broken - attempt register issuance on non-existent token {0}")]
     InvariantBrokenRegisterIssuanceWithDuplicateId(TokenId),
+    #[error("Attempt to freeze or unfreeze non-existent token {0}")]
+    FrozenStateChangeOfNonexistentToken(TokenId),
+    #[error("Freezing or unfreezing token {0} in transaction {1} is not authorized by the issuer")]
+    FrozenStateChangeNotAuthorized(TokenId, Id<Transaction>),
+    #[error("Token {0} is already frozen")]
+    TokenAlreadyFrozen(TokenId),
+    #[error("Token {0} is not frozen")]
+    TokenNotFrozen(TokenId),
+    #[error("Attempt to transfer frozen token {0} in transaction {1}")]
+    TransferOfFrozenToken(TokenId, Id<Transaction>),
+    #[error("Attempt to freeze or unfreeze token {0} in transaction {1} before the activation")]
+    TokenFreezeNotActivated(TokenId, Id<Transaction>),
+    #[error(
+        "Token {0} amounts are not conserved (total inputs: `{1:?}` vs total outputs `{2:?}`)"
+    )]
+    TokensNotConserved(TokenId, Amount, Amount),
+    #[error("Fungible token issuance is disabled, attempted in transaction {0} in block {1}")]
+    FungibleTokenIssuanceDisabled(Id<Transaction>, Id<Block>),
+    #[error("NFT issuance is disabled, attempted in transaction {0} in block {1}")]
+    NftIssuanceDisabled(Id<Transaction>, Id<Block>),
 }
 
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
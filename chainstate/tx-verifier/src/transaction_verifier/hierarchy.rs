@@ -33,6 +33,7 @@ use common::{
     },
     primitives::{Amount, Id},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{
     AccountingBlockUndo, DelegationData, DeltaMergeUndo, FlushablePoSAccountingView,
     PoSAccountingDeltaData, PoSAccountingView, PoolData,
@@ -309,6 +310,15 @@ impl<C, S: TransactionVerifierStorageRef, U: UtxosView, A: PoSAccountingView> Po
         self.accounting_delta_adapter.accounting_delta().get_pool_data(pool_id)
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, Self::Error> {
+        self.accounting_delta_adapter
+            .accounting_delta()
+            .get_pool_id_by_vrf_public_key(vrf_public_key)
+    }
+
     fn get_delegation_balance(
         &self,
         delegation_id: DelegationId,
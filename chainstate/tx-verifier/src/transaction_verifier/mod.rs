@@ -21,6 +21,7 @@ mod input_output_policy;
 mod optional_tx_index_cache;
 mod reward_distribution;
 mod signature_check;
+mod token_freeze_check;
 mod token_issuance_cache;
 mod transferred_amount_check;
 mod tx_index_cache;
@@ -69,12 +70,13 @@ use common::{
         signed_transaction::SignedTransaction,
         tokens::{get_tokens_issuance_count, TokenId},
         AccountNonce, AccountOutPoint, AccountSpending, AccountType, Block, ChainConfig,
-        DelegationId, GenBlock, OutPointSourceId, PoolId, Transaction, TxInput, TxMainChainIndex,
-        TxOutput, UtxoOutPoint,
+        DelegationId, GenBlock, OutPointSourceId, PoolId, TokenFreezeActivated, Transaction,
+        TxInput, TxMainChainIndex, TxOutput, UniquePoolVrfKeys, UtxoOutPoint,
     },
     primitives::{id::WithId, Amount, BlockHeight, Id, Idable, H256},
 };
 use consensus::ConsensusPoSError;
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{
     PoSAccountingDelta, PoSAccountingDeltaData, PoSAccountingOperations, PoSAccountingUndo,
     PoSAccountingView, PoolData,
@@ -223,6 +225,16 @@ where
             return Ok(());
         }
 
+        // All the issuances in a tx would get the same token id, derived from the first input.
+        // Blocks are checked for this separately, but mempool transactions only go through here.
+        ensure!(
+            issuance_count == 1,
+            ConnectTransactionError::TokensError(TokensError::MultipleTokenIssuanceInTransaction(
+                tx.get_id(),
+                *block_id,
+            ))
+        );
+
         let total_burned = tx
             .outputs()
             .iter()
@@ -250,6 +262,38 @@ where
         Ok(())
     }
 
+    /// Pools sharing a VRF key would produce identical VRF outputs, which is why a VRF key can be
+    /// used by a single active pool at a time once [UniquePoolVrfKeys] is activated.
+    fn check_stake_pool_vrf_key_is_unique(
+        &self,
+        block_height: BlockHeight,
+        pool_id: PoolId,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<(), ConnectTransactionError> {
+        match self
+            .chain_config
+            .as_ref()
+            .chainstate_upgrades()
+            .chainstate_upgrade_at_height(block_height)
+            .unique_pool_vrf_keys()
+        {
+            UniquePoolVrfKeys::Yes => {}
+            UniquePoolVrfKeys::No => return Ok(()),
+        }
+
+        match self
+            .accounting_delta_adapter
+            .accounting_delta()
+            .get_pool_id_by_vrf_public_key(vrf_public_key)?
+        {
+            Some(existing_pool_id) => Err(ConnectTransactionError::StakePoolVrfKeyAlreadyInUse(
+                pool_id,
+                existing_pool_id,
+            )),
+            None => Ok(()),
+        }
+    }
+
     fn get_pool_data_from_output_in_reward(
         &self,
         output: &TxOutput,
@@ -359,6 +403,12 @@ where
 
         let block_subsidy_at_height =
             Subsidy(self.chain_config.as_ref().block_subsidy_at_height(&block_height));
+        let exact_pow_reward = self
+            .chain_config
+            .as_ref()
+            .chainstate_upgrades()
+            .chainstate_upgrade_at_height(block_height)
+            .exact_pow_reward();
         check_transferred_amount_in_reward(
             &self.utxo_cache,
             &self.accounting_delta_adapter.accounting_delta(),
@@ -367,6 +417,7 @@ where
             block.consensus_data(),
             total_fees,
             block_subsidy_at_height,
+            exact_pow_reward,
         )
     }
 
@@ -443,6 +494,7 @@ where
     fn connect_pos_accounting_outputs(
         &mut self,
         tx_source: TransactionSource,
+        block_height: BlockHeight,
         tx: &Transaction,
     ) -> Result<(), ConnectTransactionError> {
         // TODO: this should also collect all the delegations after the pool decommissioning;
@@ -479,10 +531,17 @@ where
                         let expected_pool_id = pos_accounting::make_pool_id(input_utxo_outpoint);
                         let res = if expected_pool_id == *pool_id {
                             if data.value() >= self.chain_config.as_ref().min_stake_pool_pledge() {
-                                self.accounting_delta_adapter
-                                    .operations(tx_source)
-                                    .create_pool(*pool_id, data.as_ref().clone().into())
-                                    .map_err(ConnectTransactionError::PoSAccountingError)
+                                self.check_stake_pool_vrf_key_is_unique(
+                                    block_height,
+                                    *pool_id,
+                                    data.vrf_public_key(),
+                                )
+                                .and_then(|()| {
+                                    self.accounting_delta_adapter
+                                        .operations(tx_source)
+                                        .create_pool(*pool_id, data.as_ref().clone().into())
+                                        .map_err(ConnectTransactionError::PoSAccountingError)
+                                })
                             } else {
                                 Err(ConnectTransactionError::NotEnoughPledgeToCreateStakePool(
                                     tx.get_id(),
@@ -631,6 +690,80 @@ where
         Ok(())
     }
 
+    fn connect_token_frozen_state_changes(
+        &mut self,
+        tx: &Transaction,
+        block_height: BlockHeight,
+    ) -> Result<(), ConnectTransactionError> {
+        token_freeze_check::check_no_frozen_tokens_transferred(
+            &self.utxo_cache,
+            tx,
+            |token_id| {
+                self.get_token_aux_data(token_id)
+                    .map_err(|_| ConnectTransactionError::TxVerifierStorage)
+            },
+            |tx_id| {
+                self.get_token_id_from_issuance_tx(*tx_id)
+                    .map_err(|_| ConnectTransactionError::TxVerifierStorage)
+            },
+        )?;
+
+        let token_freeze_activated = self
+            .chain_config
+            .chainstate_upgrades()
+            .chainstate_upgrade_at_height(block_height)
+            .token_freeze_activated();
+
+        for (token_id, frozen) in token_freeze_check::frozen_state_changes(tx) {
+            match token_freeze_activated {
+                TokenFreezeActivated::Yes => {}
+                TokenFreezeActivated::No => {
+                    return Err(ConnectTransactionError::TokensError(
+                        TokensError::TokenFreezeNotActivated(token_id, tx.get_id()),
+                    ))
+                }
+            }
+
+            let aux_data = self
+                .get_token_aux_data(&token_id)
+                .map_err(|_| ConnectTransactionError::TxVerifierStorage)?
+                .ok_or(TokensError::FrozenStateChangeOfNonexistentToken(token_id))?;
+
+            token_freeze_check::check_frozen_state_change(
+                &SignatureDestinationGetter::new_for_transaction(
+                    &self.accounting_delta_adapter.accounting_delta(),
+                    &self.utxo_cache,
+                ),
+                tx,
+                token_id,
+                &aux_data,
+                frozen,
+            )?;
+
+            self.token_issuance_cache
+                .set_token_aux_data(&token_id, aux_data.with_frozen(frozen))?;
+        }
+
+        Ok(())
+    }
+
+    fn disconnect_token_frozen_state_changes(
+        &mut self,
+        tx: &Transaction,
+    ) -> Result<(), ConnectTransactionError> {
+        for (token_id, frozen) in token_freeze_check::frozen_state_changes(tx).rev() {
+            let aux_data = self
+                .get_token_aux_data(&token_id)
+                .map_err(|_| ConnectTransactionError::TxVerifierStorage)?
+                .ok_or(TokensError::FrozenStateChangeOfNonexistentToken(token_id))?;
+
+            self.token_issuance_cache
+                .set_token_aux_data(&token_id, aux_data.with_frozen(!frozen))?;
+        }
+
+        Ok(())
+    }
+
     pub fn connect_transaction(
         &mut self,
         tx_source: &TransactionSourceForConnect,
@@ -657,12 +790,20 @@ where
                     .map_err(|_| ConnectTransactionError::TxVerifierStorage)
             };
 
+        let tokens_conservation = self
+            .chain_config
+            .as_ref()
+            .chainstate_upgrades()
+            .chainstate_upgrade_at_height(tx_source.expected_block_height())
+            .tokens_conservation();
+
         // check for attempted money printing
         let fee = check_transferred_amounts_and_get_fee(
             &self.utxo_cache,
             &self.accounting_delta_adapter.accounting_delta(),
             tx.transaction(),
             issuance_token_id_getter,
+            tokens_conservation,
         )?;
 
         input_output_policy::check_tx_inputs_outputs_policy(
@@ -679,6 +820,12 @@ where
         // Register tokens if tx has issuance data
         self.token_issuance_cache.register(block_id, tx.transaction())?;
 
+        // Reject transfers of frozen tokens and apply freeze/unfreeze operations
+        self.connect_token_frozen_state_changes(
+            tx.transaction(),
+            tx_source.expected_block_height(),
+        )?;
+
         // check timelocks of the outputs and make sure there's no premature spending
         timelock_check::check_timelocks(
             &self.storage,
@@ -700,7 +847,11 @@ where
             ),
         )?;
 
-        self.connect_pos_accounting_outputs(tx_source.into(), tx.transaction())?;
+        self.connect_pos_accounting_outputs(
+            tx_source.into(),
+            tx_source.expected_block_height(),
+            tx.transaction(),
+        )?;
 
         // spend utxos
         let tx_undo = self
@@ -921,6 +1072,9 @@ where
             tx.transaction(),
         )?;
 
+        // Revert freeze/unfreeze operations
+        self.disconnect_token_frozen_state_changes(tx.transaction())?;
+
         // Remove issued tokens
         self.token_issuance_cache.unregister(tx.transaction())?;
 
@@ -29,6 +29,7 @@ use common::{
     },
     primitives::{Amount, Id},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{
     DelegationData, DeltaMergeUndo, FlushablePoSAccountingView, PoSAccountingDeltaData,
     PoSAccountingView, PoolData,
@@ -154,6 +155,10 @@ mockall::mock! {
         fn pool_exists(&self, pool_id: PoolId) -> Result<bool, pos_accounting::Error>;
         fn get_pool_balance(&self, pool_id: PoolId) -> Result<Option<Amount>, pos_accounting::Error>;
         fn get_pool_data(&self, pool_id: PoolId) -> Result<Option<PoolData>, pos_accounting::Error>;
+        fn get_pool_id_by_vrf_public_key(
+            &self,
+            vrf_public_key: &VRFPublicKey,
+        ) -> Result<Option<PoolId>, pos_accounting::Error>;
         fn get_delegation_balance(
             &self,
             delegation_id: DelegationId,
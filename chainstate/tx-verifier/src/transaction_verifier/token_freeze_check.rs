@@ -0,0 +1,209 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+
+use common::{
+    chain::{
+        tokens::{TokenAuxiliaryData, TokenData, TokenId},
+        Destination, OutPointSourceId, Transaction, TxInput, TxOutput,
+    },
+    primitives::{Id, Idable},
+};
+use utils::ensure;
+use utxo::UtxosView;
+
+use super::{
+    error::{ConnectTransactionError, TokensError},
+    signature_destination_getter::SignatureDestinationGetter,
+};
+
+/// Freeze/unfreeze operations of the transaction in the order of appearance.
+/// The bool is the new frozen state of the token.
+pub fn frozen_state_changes(
+    tx: &Transaction,
+) -> impl DoubleEndedIterator<Item = (TokenId, bool)> + '_ {
+    tx.outputs()
+        .iter()
+        .filter_map(|output| match output {
+            TxOutput::Transfer(v, _) | TxOutput::LockThenTransfer(v, _, _) | TxOutput::Burn(v) => {
+                v.token_data()
+            }
+            TxOutput::CreateStakePool(_, _)
+            | TxOutput::ProduceBlockFromStake(_, _)
+            | TxOutput::CreateDelegationId(_, _)
+            | TxOutput::DelegateStaking(_, _) => None,
+        })
+        .filter_map(TokenData::frozen_state_change)
+}
+
+/// Collect the ids of the tokens that are spent by the inputs or sent to the outputs of the transaction
+fn transferred_tokens<U, IssuanceTokenIdGetterFunc>(
+    utxo_view: &U,
+    tx: &Transaction,
+    issuance_token_id_getter: IssuanceTokenIdGetterFunc,
+) -> Result<BTreeSet<TokenId>, ConnectTransactionError>
+where
+    U: UtxosView,
+    IssuanceTokenIdGetterFunc:
+        Fn(&Id<Transaction>) -> Result<Option<TokenId>, ConnectTransactionError>,
+{
+    let mut token_ids = BTreeSet::new();
+
+    for input in tx.inputs() {
+        let outpoint = match input {
+            TxInput::Utxo(outpoint) => outpoint,
+            TxInput::Account(_) => continue,
+        };
+        let utxo = utxo_view.utxo(outpoint).map_err(|_| utxo::Error::ViewRead)?.ok_or(
+            ConnectTransactionError::MissingOutputOrSpent(outpoint.clone()),
+        )?;
+        let token_data = match utxo.output() {
+            TxOutput::Transfer(v, _) | TxOutput::LockThenTransfer(v, _, _) => v.token_data(),
+            TxOutput::Burn(_)
+            | TxOutput::CreateStakePool(_, _)
+            | TxOutput::ProduceBlockFromStake(_, _)
+            | TxOutput::CreateDelegationId(_, _)
+            | TxOutput::DelegateStaking(_, _) => None,
+        };
+        match token_data {
+            Some(TokenData::TokenTransfer(transfer)) => {
+                token_ids.insert(transfer.token_id);
+            }
+            Some(TokenData::TokenIssuance(_) | TokenData::NftIssuance(_)) => {
+                if let OutPointSourceId::Transaction(tx_id) = outpoint.source_id() {
+                    if let Some(token_id) = issuance_token_id_getter(&tx_id)? {
+                        token_ids.insert(token_id);
+                    }
+                }
+            }
+            Some(TokenData::TokenFreezeV1(_) | TokenData::TokenUnfreezeV1(_)) | None => {}
+        }
+    }
+
+    for output in tx.outputs() {
+        let token_data = match output {
+            TxOutput::Transfer(v, _) | TxOutput::LockThenTransfer(v, _, _) | TxOutput::Burn(v) => {
+                v.token_data()
+            }
+            TxOutput::CreateStakePool(_, _)
+            | TxOutput::ProduceBlockFromStake(_, _)
+            | TxOutput::CreateDelegationId(_, _)
+            | TxOutput::DelegateStaking(_, _) => None,
+        };
+        if let Some(TokenData::TokenTransfer(transfer)) = token_data {
+            token_ids.insert(transfer.token_id);
+        }
+    }
+
+    Ok(token_ids)
+}
+
+/// Make sure the transaction doesn't move any frozen tokens
+pub fn check_no_frozen_tokens_transferred<U, TokenAuxDataGetterFunc, IssuanceTokenIdGetterFunc>(
+    utxo_view: &U,
+    tx: &Transaction,
+    token_aux_data_getter: TokenAuxDataGetterFunc,
+    issuance_token_id_getter: IssuanceTokenIdGetterFunc,
+) -> Result<(), ConnectTransactionError>
+where
+    U: UtxosView,
+    TokenAuxDataGetterFunc:
+        Fn(&TokenId) -> Result<Option<TokenAuxiliaryData>, ConnectTransactionError>,
+    IssuanceTokenIdGetterFunc:
+        Fn(&Id<Transaction>) -> Result<Option<TokenId>, ConnectTransactionError>,
+{
+    for token_id in transferred_tokens(utxo_view, tx, issuance_token_id_getter)? {
+        let is_frozen = token_aux_data_getter(&token_id)?.map_or(false, |data| data.is_frozen());
+        ensure!(
+            !is_frozen,
+            ConnectTransactionError::TokensError(TokensError::TransferOfFrozenToken(
+                token_id,
+                tx.get_id()
+            ))
+        );
+    }
+    Ok(())
+}
+
+/// The destination the token was issued to, if it can act as the freezing authority.
+///
+/// Only the destinations that require a signature qualify, otherwise anyone would be able
+/// to freeze the token.
+fn issuer_destination(aux_data: &TokenAuxiliaryData) -> Option<&Destination> {
+    aux_data
+        .issuance_tx()
+        .outputs()
+        .iter()
+        .find(|output| output.is_token_or_nft_issuance())
+        .and_then(|output| match output {
+            TxOutput::Transfer(_, d) | TxOutput::LockThenTransfer(_, d, _) => Some(d),
+            TxOutput::Burn(_)
+            | TxOutput::CreateStakePool(_, _)
+            | TxOutput::ProduceBlockFromStake(_, _)
+            | TxOutput::CreateDelegationId(_, _)
+            | TxOutput::DelegateStaking(_, _) => None,
+        })
+        .filter(|d| match d {
+            Destination::Address(_)
+            | Destination::PublicKey(_)
+            | Destination::ClassicMultisig(_) => true,
+            Destination::AnyoneCanSpend | Destination::ScriptHash(_) => false,
+        })
+}
+
+/// Check that the token can be switched to the new frozen state by the transaction.
+///
+/// The operation is authorized by spending an input that belongs to the issuer of the token;
+/// the signature of that input is checked along with all the other signatures of the transaction.
+/// Tokens issued to a destination that doesn't require a signature can't be frozen.
+pub fn check_frozen_state_change(
+    destination_getter: &SignatureDestinationGetter,
+    tx: &Transaction,
+    token_id: TokenId,
+    aux_data: &TokenAuxiliaryData,
+    frozen: bool,
+) -> Result<(), ConnectTransactionError> {
+    if frozen {
+        ensure!(
+            !aux_data.is_frozen(),
+            ConnectTransactionError::TokensError(TokensError::TokenAlreadyFrozen(token_id))
+        );
+    } else {
+        ensure!(
+            aux_data.is_frozen(),
+            ConnectTransactionError::TokensError(TokensError::TokenNotFrozen(token_id))
+        );
+    }
+
+    let mut authorized = false;
+    if let Some(issuer) = issuer_destination(aux_data) {
+        for input in tx.inputs() {
+            if destination_getter.call(input)? == *issuer {
+                authorized = true;
+                break;
+            }
+        }
+    }
+    ensure!(
+        authorized,
+        ConnectTransactionError::TokensError(TokensError::FrozenStateChangeNotAuthorized(
+            token_id,
+            tx.get_id()
+        ))
+    );
+
+    Ok(())
+}
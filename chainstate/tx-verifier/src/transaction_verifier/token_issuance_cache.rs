@@ -112,9 +112,18 @@ impl TokenIssuanceCache {
     ) -> Result<(), TokensError> {
         match self.data.entry(token_id) {
             Entry::Occupied(mut entry) => match entry.get() {
-                CachedOperation::Write(_) | CachedOperation::Read(_) => Err(
-                    TokensError::InvariantBrokenRegisterIssuanceWithDuplicateId(token_id),
-                ),
+                CachedOperation::Write(data) | CachedOperation::Read(data) => {
+                    // Updating the state of an existing token is fine, issuing it again is not
+                    let is_update = matches!(&new_op, CachedOperation::Write(new_data)
+                        if new_data.issuance_tx() == data.issuance_tx());
+                    if !is_update {
+                        return Err(TokensError::InvariantBrokenRegisterIssuanceWithDuplicateId(
+                            token_id,
+                        ));
+                    }
+                    entry.insert(new_op);
+                    Ok(())
+                }
                 CachedOperation::Erase => {
                     entry.insert(new_op);
                     Ok(())
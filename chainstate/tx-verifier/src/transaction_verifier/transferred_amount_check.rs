@@ -21,8 +21,11 @@ use common::{
         block::{BlockRewardTransactable, ConsensusData},
         output_value::OutputValue,
         signature::Signable,
-        tokens::{get_tokens_issuance_count, token_id, TokenData, TokenId},
-        AccountSpending, Block, OutPointSourceId, Transaction, TxInput, TxOutput,
+        tokens::{
+            get_tokens_issuance_count, token_id, TokenData, TokenFreeze, TokenId, TokenUnfreeze,
+        },
+        AccountSpending, Block, ExactPoWReward, OutPointSourceId, TokensConservation, Transaction,
+        TxInput, TxOutput,
     },
     primitives::{Amount, Id, Idable},
 };
@@ -56,6 +59,7 @@ fn get_total_fee(
 fn check_transferred_amount(
     inputs_total_map: &BTreeMap<CoinOrTokenId, Amount>,
     outputs_total_map: &BTreeMap<CoinOrTokenId, Amount>,
+    tokens_conservation: TokensConservation,
 ) -> Result<(), ConnectTransactionError> {
     for (coin_or_token_id, outputs_total) in outputs_total_map {
         // Does coin or token exist in inputs?
@@ -69,6 +73,27 @@ fn check_transferred_amount(
             ));
         }
     }
+
+    match tokens_conservation {
+        TokensConservation::Yes => {}
+        TokensConservation::No => return Ok(()),
+    }
+
+    // Tokens can't be used to pay fees, so whatever isn't burned explicitly must be transferred
+    for (coin_or_token_id, inputs_total) in inputs_total_map {
+        if let CoinOrTokenId::TokenId(token_id) = coin_or_token_id {
+            let outputs_total = outputs_total_map.get(coin_or_token_id).unwrap_or(&Amount::ZERO);
+            ensure!(
+                outputs_total == inputs_total,
+                ConnectTransactionError::TokensError(TokensError::TokensNotConserved(
+                    *token_id,
+                    *inputs_total,
+                    *outputs_total,
+                ))
+            );
+        }
+    }
+
     Ok(())
 }
 
@@ -77,6 +102,7 @@ pub fn check_transferred_amounts_and_get_fee<U, P, IssuanceTokenIdGetterFunc>(
     pos_accounting_view: &P,
     tx: &Transaction,
     issuance_token_id_getter: IssuanceTokenIdGetterFunc,
+    tokens_conservation: TokensConservation,
 ) -> Result<Fee, ConnectTransactionError>
 where
     U: UtxosView,
@@ -93,7 +119,7 @@ where
     )?;
     let outputs_total_map = calculate_total_outputs(pos_accounting_view, tx.outputs(), None)?;
 
-    check_transferred_amount(&inputs_total_map, &outputs_total_map)?;
+    check_transferred_amount(&inputs_total_map, &outputs_total_map, tokens_conservation)?;
     let total_fee = get_total_fee(&inputs_total_map, &outputs_total_map)?;
 
     Ok(total_fee)
@@ -231,6 +257,7 @@ fn get_output_token_id_and_amount(
                 }
                 None => None,
             },
+            TokenData::TokenFreezeV1(_) | TokenData::TokenUnfreezeV1(_) => None,
         },
     })
 }
@@ -259,6 +286,11 @@ where
                 .ok_or(ConnectTransactionError::TokensError(
                     TokensError::TokenIdCantBeCalculated,
                 ))?,
+            // Freeze/unfreeze operations carry no value
+            TokenData::TokenFreezeV1(TokenFreeze { token_id })
+            | TokenData::TokenUnfreezeV1(TokenUnfreeze { token_id }) => {
+                (CoinOrTokenId::TokenId(*token_id), Amount::ZERO)
+            }
         },
     })
 }
@@ -294,6 +326,7 @@ pub fn check_transferred_amount_in_reward<U: UtxosView, P: PoSAccountingView>(
     consensus_data: &ConsensusData,
     total_fees: Fee,
     block_subsidy_at_height: Subsidy,
+    exact_pow_reward: ExactPoWReward,
 ) -> Result<(), ConnectTransactionError> {
     let inputs = block_reward_transactable.inputs();
     let outputs = block_reward_transactable.outputs();
@@ -318,22 +351,45 @@ pub fn check_transferred_amount_in_reward<U: UtxosView, P: PoSAccountingView>(
 
             calculate_total_outputs(pos_accounting_view, outputs, None)
                 .map(|total| total.get(&CoinOrTokenId::Coin).cloned().unwrap_or(Amount::ZERO))
+                .map_err(|err| match err {
+                    ConnectTransactionError::TokensError(TokensError::CoinOrTokenOverflow) => {
+                        ConnectTransactionError::RewardOutputsTotalOverflow(block_id)
+                    }
+                    err => err,
+                })
         },
     )?;
 
+    let full_reward_total = || {
+        amount_sum!(inputs_total, block_subsidy_at_height.0, total_fees.0)
+            .ok_or_else(|| ConnectTransactionError::RewardAdditionError(block_id))
+    };
+    let check_reward_cap = || -> Result<(), ConnectTransactionError> {
+        let max_allowed_outputs_total = full_reward_total()?;
+        ensure!(
+            outputs_total <= max_allowed_outputs_total,
+            ConnectTransactionError::AttemptToPrintMoney(max_allowed_outputs_total, outputs_total)
+        );
+        Ok(())
+    };
+
     match consensus_data {
-        ConsensusData::None | ConsensusData::PoW(_) => {
-            let max_allowed_outputs_total =
-                amount_sum!(inputs_total, block_subsidy_at_height.0, total_fees.0)
-                    .ok_or_else(|| ConnectTransactionError::RewardAdditionError(block_id))?;
-            ensure!(
-                outputs_total <= max_allowed_outputs_total,
-                ConnectTransactionError::AttemptToPrintMoney(
-                    max_allowed_outputs_total,
-                    outputs_total
-                )
-            );
-        }
+        // Blocks without consensus only cap the reward
+        ConsensusData::None => check_reward_cap()?,
+        ConsensusData::PoW(_) => match exact_pow_reward {
+            ExactPoWReward::Yes => {
+                // The miner must claim exactly the subsidy plus the fees, neither more nor less
+                let expected_outputs_total = full_reward_total()?;
+                ensure!(
+                    outputs_total == expected_outputs_total,
+                    ConnectTransactionError::BlockRewardAmountMismatch(
+                        expected_outputs_total,
+                        outputs_total
+                    )
+                );
+            }
+            ExactPoWReward::No => check_reward_cap()?,
+        },
         ConsensusData::PoS(_) => {
             ensure!(
                 outputs_total == inputs_total,
@@ -388,7 +444,7 @@ mod tests {
         let pos_accounting_db = pos_accounting::PoSAccountingDB::new(&pos_accounting_store);
 
         let fee = Fee(Amount::from_atoms(rng.gen_range(0..100_000)));
-        let subsidy = Subsidy(Amount::from_atoms(rng.gen_range(0..100_000)));
+        let subsidy = Subsidy(Amount::from_atoms(rng.gen_range(1..100_000)));
         let total_input_value = ((input_amount + fee.0).unwrap() + subsidy.0).unwrap();
 
         let check = |output_value| {
@@ -407,49 +463,207 @@ mod tests {
                 &ConsensusData::PoW(Box::new(PoWData::new(Compact(1), 1))),
                 fee,
                 subsidy,
+                ExactPoWReward::Yes,
             )
         };
 
-        // invalid case min case
+        // over-claiming min case
         {
             let invalid_output_value = Amount::from_atoms(total_input_value.into_atoms() + 1);
             let result = check(invalid_output_value);
             assert_eq!(
                 result,
-                Err(ConnectTransactionError::AttemptToPrintMoney(
+                Err(ConnectTransactionError::BlockRewardAmountMismatch(
                     total_input_value,
                     invalid_output_value
                 ))
             );
         }
 
-        // invalid random case
+        // over-claiming random case
         {
             let invalid_output_value =
                 Amount::from_atoms(rng.gen_range((total_input_value.into_atoms() + 1)..u128::MAX));
             let result = check(invalid_output_value);
             assert_eq!(
                 result,
-                Err(ConnectTransactionError::AttemptToPrintMoney(
+                Err(ConnectTransactionError::BlockRewardAmountMismatch(
                     total_input_value,
                     invalid_output_value
                 ))
             );
         }
 
-        // valid max case
+        // exact case
         {
             let result = check(total_input_value);
             assert_eq!(result, Ok(()));
         }
 
-        // valid random case
+        // under-claiming max case
         {
-            let valid_output_value =
+            let invalid_output_value = Amount::from_atoms(total_input_value.into_atoms() - 1);
+            let result = check(invalid_output_value);
+            assert_eq!(
+                result,
+                Err(ConnectTransactionError::BlockRewardAmountMismatch(
+                    total_input_value,
+                    invalid_output_value
+                ))
+            );
+        }
+
+        // under-claiming random case
+        {
+            let invalid_output_value =
                 Amount::from_atoms(rng.gen_range(0..total_input_value.into_atoms()));
-            let result = check(valid_output_value);
+            let result = check(invalid_output_value);
+            assert_eq!(
+                result,
+                Err(ConnectTransactionError::BlockRewardAmountMismatch(
+                    total_input_value,
+                    invalid_output_value
+                ))
+            );
+        }
+    }
+
+    // Blocks without consensus and PoW blocks before the exact reward activation only cap the reward
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy(), ConsensusData::None, ExactPoWReward::Yes)]
+    #[case(
+        Seed::from_entropy(),
+        ConsensusData::PoW(Box::new(PoWData::new(Compact(1), 1))),
+        ExactPoWReward::No
+    )]
+    fn check_block_reward_capped(
+        #[case] seed: Seed,
+        #[case] consensus_data: ConsensusData,
+        #[case] exact_pow_reward: ExactPoWReward,
+    ) {
+        let mut rng = make_seedable_rng(seed);
+
+        let utxo_db =
+            utxo::UtxosDBInMemoryImpl::new(Id::<GenBlock>::new(H256::zero()), BTreeMap::new());
+        let pos_accounting_store = pos_accounting::InMemoryPoSAccounting::new();
+        let pos_accounting_db = pos_accounting::PoSAccountingDB::new(&pos_accounting_store);
+
+        let fee = Fee(Amount::from_atoms(rng.gen_range(0..100_000)));
+        let subsidy = Subsidy(Amount::from_atoms(rng.gen_range(0..100_000)));
+        let max_reward = (fee.0 + subsidy.0).unwrap();
+
+        let check = |output_value| {
+            let outputs = vec![TxOutput::LockThenTransfer(
+                OutputValue::Coin(output_value),
+                Destination::AnyoneCanSpend,
+                OutputTimeLock::ForBlockCount(1),
+            )];
+            let block_reward = BlockRewardTransactable::new(None, Some(&outputs), None);
+            check_transferred_amount_in_reward(
+                &utxo_db,
+                &pos_accounting_db,
+                &block_reward,
+                Id::<Block>::new(H256::zero()),
+                &consensus_data,
+                fee,
+                subsidy,
+                exact_pow_reward,
+            )
+        };
+
+        // over-claiming is rejected
+        {
+            let invalid_output_value = Amount::from_atoms(max_reward.into_atoms() + 1);
+            assert_eq!(
+                check(invalid_output_value),
+                Err(ConnectTransactionError::AttemptToPrintMoney(
+                    max_reward,
+                    invalid_output_value
+                ))
+            );
+        }
+
+        // exact and under-claiming rewards are accepted
+        assert_eq!(check(max_reward), Ok(()));
+        assert_eq!(
+            check(Amount::from_atoms(
+                rng.gen_range(0..=max_reward.into_atoms())
+            )),
+            Ok(())
+        );
+    }
+
+    // Fees and outputs close to the maximum amount must be rejected instead of wrapping around
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn check_block_reward_pow_overflow(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let utxo_db =
+            utxo::UtxosDBInMemoryImpl::new(Id::<GenBlock>::new(H256::zero()), BTreeMap::new());
+        let pos_accounting_store = pos_accounting::InMemoryPoSAccounting::new();
+        let pos_accounting_db = pos_accounting::PoSAccountingDB::new(&pos_accounting_store);
+        let block_id = Id::<Block>::new(H256::zero());
+
+        let check = |output_values: &[Amount], fee: Fee, subsidy: Subsidy| {
+            let outputs = output_values
+                .iter()
+                .map(|value| {
+                    TxOutput::LockThenTransfer(
+                        OutputValue::Coin(*value),
+                        Destination::AnyoneCanSpend,
+                        OutputTimeLock::ForBlockCount(1),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let block_reward = BlockRewardTransactable::new(None, Some(&outputs), None);
+            check_transferred_amount_in_reward(
+                &utxo_db,
+                &pos_accounting_db,
+                &block_reward,
+                block_id,
+                &ConsensusData::PoW(Box::new(PoWData::new(Compact(1), 1))),
+                fee,
+                subsidy,
+                ExactPoWReward::Yes,
+            )
+        };
+
+        // Fees plus subsidy overflow
+        {
+            let subsidy = Amount::from_atoms(rng.gen_range(1..100_000));
+            let fee = Amount::from_atoms(
+                rng.gen_range((u128::MAX - subsidy.into_atoms() + 1)..=u128::MAX),
+            );
+            let result = check(&[Amount::from_atoms(1)], Fee(fee), Subsidy(subsidy));
+            assert_eq!(
+                result,
+                Err(ConnectTransactionError::RewardAdditionError(block_id))
+            );
+        }
+
+        // Fees plus subsidy reach the maximum exactly
+        {
+            let subsidy = Amount::from_atoms(rng.gen_range(1..100_000));
+            let fee = (Amount::MAX - subsidy).unwrap();
+            let result = check(&[Amount::MAX], Fee(fee), Subsidy(subsidy));
             assert_eq!(result, Ok(()));
         }
+
+        // Sum of the outputs overflows; a wrapped sum would look like a small valid reward
+        {
+            let first = Amount::from_atoms(rng.gen_range((u128::MAX / 2 + 1)..=u128::MAX));
+            let second = Amount::from_atoms(u128::MAX - first.into_atoms() + 1);
+            let result = check(&[first, second], Fee(Amount::MAX), Subsidy(Amount::ZERO));
+            assert_eq!(
+                result,
+                Err(ConnectTransactionError::RewardOutputsTotalOverflow(
+                    block_id
+                ))
+            );
+        }
     }
 
     #[rstest]
@@ -505,6 +719,7 @@ mod tests {
             ))),
             fee,
             subsidy,
+            ExactPoWReward::Yes,
         )
         .unwrap();
     }
@@ -579,6 +794,7 @@ mod tests {
             ))),
             fee,
             subsidy,
+            ExactPoWReward::Yes,
         )
         .unwrap_err();
         assert_eq!(
@@ -626,11 +842,14 @@ mod tests {
             );
             let tx = Transaction::new(0, vec![input], vec![output]).unwrap();
 
-            let res =
-                check_transferred_amounts_and_get_fee(&utxo_db, &pos_accounting_db, &tx, |_id| {
-                    Ok(None)
-                })
-                .unwrap_err();
+            let res = check_transferred_amounts_and_get_fee(
+                &utxo_db,
+                &pos_accounting_db,
+                &tx,
+                |_id| Ok(None),
+                TokensConservation::Yes,
+            )
+            .unwrap_err();
             assert_eq!(
                 res,
                 ConnectTransactionError::AttemptToPrintMoney(delegated_amount, overspend_amount)
@@ -650,11 +869,14 @@ mod tests {
             );
             let tx = Transaction::new(0, vec![input], vec![output]).unwrap();
 
-            let res =
-                check_transferred_amounts_and_get_fee(&utxo_db, &pos_accounting_db, &tx, |_id| {
-                    Ok(None)
-                })
-                .unwrap_err();
+            let res = check_transferred_amounts_and_get_fee(
+                &utxo_db,
+                &pos_accounting_db,
+                &tx,
+                |_id| Ok(None),
+                TokensConservation::Yes,
+            )
+            .unwrap_err();
             assert_eq!(
                 res,
                 ConnectTransactionError::AttemptToPrintMoney(withdraw_amount, overspend_amount)
@@ -671,7 +893,13 @@ mod tests {
         );
         let tx = Transaction::new(0, vec![input], vec![output]).unwrap();
 
-        check_transferred_amounts_and_get_fee(&utxo_db, &pos_accounting_db, &tx, |_id| Ok(None))
-            .unwrap();
+        check_transferred_amounts_and_get_fee(
+            &utxo_db,
+            &pos_accounting_db,
+            &tx,
+            |_id| Ok(None),
+            TokensConservation::Yes,
+        )
+        .unwrap();
     }
 }
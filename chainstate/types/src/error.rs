@@ -54,6 +54,14 @@ pub enum PropertyQueryError {
     InvalidStartingBlockHeightForMainchainBlocks(BlockHeight),
     #[error("Transaction index must be enabled to retrieve transactions by id")]
     TransactionIndexDisabled,
+    #[error("Invalid block height range from {0} to {1}")]
+    InvalidBlockHeightRange(BlockHeight, BlockHeight),
+    #[error("Block height range from {0} to {1} is longer than the maximum of {2}")]
+    BlockHeightRangeTooLong(BlockHeight, BlockHeight, usize),
+    #[error("Block {1} is no longer in the mainchain at height {0}")]
+    BlockNoLongerInMainchain(BlockHeight, Id<GenBlock>),
+    #[error("The maximum number of blocks to return can't be zero")]
+    ZeroMaxBlockCount,
 }
 
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
@@ -18,6 +18,8 @@
 pub enum Error {
     #[error("Storage error: {0}")]
     Storage(storage::error::Recoverable),
+    #[error("Storage migration error: {0}")]
+    Migration(String),
 }
 
 impl From<storage::Error> for Error {
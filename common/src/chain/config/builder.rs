@@ -27,8 +27,10 @@ use crate::{
         },
         pos_initial_difficulty,
         pow::PoWChainConfigBuilder,
-        CoinUnit, ConsensusUpgrade, Destination, GenBlock, Genesis, NetUpgrades, PoSChainConfig,
-        PoSConsensusVersion, PoWChainConfig, UpgradeVersion,
+        ChainstateUpgrade, CoinUnit, ConsensusUpgrade, Destination, ExactPoWReward, GenBlock,
+        Genesis, NetUpgrades, NftMetadataSizeLimit, PoSChainConfig, PoSConsensusVersion,
+        PoWChainConfig, StrictMedianTimePast, TokenFreezeActivated, TokenMaxSupplyLimit,
+        TokensConservation, UniquePoolVrfKeys, UpgradeVersion,
     },
     primitives::{
         id::WithId, per_thousand::PerThousand, semver::SemVer, Amount, BlockDistance, BlockHeight,
@@ -115,6 +117,42 @@ impl ChainType {
             ChainType::Signet => NetUpgrades::unit_tests(),
         }
     }
+
+    fn default_chainstate_upgrades(&self) -> NetUpgrades<ChainstateUpgrade> {
+        match self {
+            ChainType::Mainnet | ChainType::Testnet => {
+                let upgrades = vec![
+                    (
+                        BlockHeight::new(0),
+                        ChainstateUpgrade::new(
+                            TokenFreezeActivated::No,
+                            UniquePoolVrfKeys::No,
+                            ExactPoWReward::No,
+                            TokensConservation::No,
+                            NftMetadataSizeLimit::No,
+                            TokenMaxSupplyLimit::No,
+                            StrictMedianTimePast::No,
+                        ),
+                    ),
+                    (
+                        // TODO: decide on proper height
+                        BlockHeight::new(9999999999),
+                        ChainstateUpgrade::new(
+                            TokenFreezeActivated::Yes,
+                            UniquePoolVrfKeys::Yes,
+                            ExactPoWReward::Yes,
+                            TokensConservation::Yes,
+                            NftMetadataSizeLimit::Yes,
+                            TokenMaxSupplyLimit::Yes,
+                            StrictMedianTimePast::Yes,
+                        ),
+                    ),
+                ];
+                NetUpgrades::initialize(upgrades).expect("net upgrades")
+            }
+            ChainType::Regtest | ChainType::Signet => NetUpgrades::latest_chainstate_upgrades(),
+        }
+    }
 }
 
 // Builder support types
@@ -155,22 +193,29 @@ pub struct Builder {
     max_block_header_size: usize,
     max_block_size_with_standard_txs: usize,
     max_block_size_with_smart_contracts: usize,
+    max_block_size: usize,
+    max_block_tx_count: usize,
     max_no_signature_data_size: usize,
     max_depth_for_reorg: BlockDistance,
     epoch_length: NonZeroU64,
     sealed_epoch_distance_from_tip: usize,
     initial_randomness: H256,
     net_upgrades: NetUpgrades<UpgradeVersion>,
+    chainstate_upgrades: NetUpgrades<ChainstateUpgrade>,
     genesis_block: GenesisBlockInit,
     emission_schedule: EmissionScheduleInit,
     token_min_issuance_fee: Amount,
     token_max_uri_len: usize,
     token_max_dec_count: u8,
+    token_max_supply: u128,
     token_max_ticker_len: usize,
     token_max_name_len: usize,
     token_max_description_len: usize,
     token_min_hash_len: usize,
     token_max_hash_len: usize,
+    token_max_nft_metadata_size: usize,
+    fungible_token_issuance_enabled: bool,
+    nft_issuance_enabled: bool,
     empty_consensus_reward_maturity_distance: BlockDistance,
     max_classic_multisig_public_keys_count: usize,
     min_stake_pool_pledge: Amount,
@@ -192,6 +237,8 @@ impl Builder {
             max_block_header_size: super::MAX_BLOCK_HEADER_SIZE,
             max_block_size_with_standard_txs: super::MAX_BLOCK_TXS_SIZE,
             max_block_size_with_smart_contracts: super::MAX_BLOCK_CONTRACTS_SIZE,
+            max_block_size: super::MAX_BLOCK_SIZE,
+            max_block_tx_count: super::MAX_BLOCK_TX_COUNT,
             max_no_signature_data_size: super::MAX_TX_NO_SIG_WITNESS_SIZE,
             max_future_block_time_offset: super::DEFAULT_MAX_FUTURE_BLOCK_TIME_OFFSET,
             max_depth_for_reorg: super::DEFAULT_MAX_DEPTH_FOR_REORG,
@@ -202,14 +249,19 @@ impl Builder {
             genesis_block: chain_type.default_genesis_init(),
             emission_schedule: EmissionScheduleInit::Mainnet,
             net_upgrades: chain_type.default_net_upgrades(),
+            chainstate_upgrades: chain_type.default_chainstate_upgrades(),
             token_min_issuance_fee: super::TOKEN_MIN_ISSUANCE_FEE,
             token_max_uri_len: super::TOKEN_MAX_URI_LEN,
             token_max_dec_count: super::TOKEN_MAX_DEC_COUNT,
+            token_max_supply: super::TOKEN_MAX_SUPPLY,
             token_max_ticker_len: super::TOKEN_MAX_TICKER_LEN,
             token_max_name_len: super::TOKEN_MAX_NAME_LEN,
             token_max_description_len: super::TOKEN_MAX_DESCRIPTION_LEN,
             token_min_hash_len: super::TOKEN_MIN_HASH_LEN,
             token_max_hash_len: super::TOKEN_MAX_HASH_LEN,
+            token_max_nft_metadata_size: super::TOKEN_MAX_NFT_METADATA_SIZE,
+            fungible_token_issuance_enabled: true,
+            nft_issuance_enabled: true,
             empty_consensus_reward_maturity_distance: BlockDistance::new(0),
             max_classic_multisig_public_keys_count: super::MAX_CLASSIC_MULTISIG_PUBLIC_KEYS_COUNT,
             min_stake_pool_pledge: super::MIN_STAKE_POOL_PLEDGE,
@@ -220,6 +272,7 @@ impl Builder {
     pub fn test_chain() -> Self {
         Self::new(ChainType::Mainnet)
             .net_upgrades(NetUpgrades::unit_tests())
+            .chainstate_upgrades(NetUpgrades::latest_chainstate_upgrades())
             .genesis_unittest(Destination::AnyoneCanSpend)
     }
 
@@ -237,6 +290,8 @@ impl Builder {
             max_block_header_size,
             max_block_size_with_standard_txs,
             max_block_size_with_smart_contracts,
+            max_block_size,
+            max_block_tx_count,
             max_future_block_time_offset,
             max_no_signature_data_size,
             max_depth_for_reorg,
@@ -247,14 +302,19 @@ impl Builder {
             genesis_block,
             emission_schedule,
             net_upgrades,
+            chainstate_upgrades,
             token_min_issuance_fee,
             token_max_uri_len,
             token_max_dec_count,
+            token_max_supply,
             token_max_ticker_len,
             token_max_name_len,
             token_max_description_len,
             token_min_hash_len,
             token_max_hash_len,
+            token_max_nft_metadata_size,
+            fungible_token_issuance_enabled,
+            nft_issuance_enabled,
             empty_consensus_reward_maturity_distance,
             max_classic_multisig_public_keys_count,
             min_stake_pool_pledge,
@@ -317,6 +377,8 @@ impl Builder {
             max_block_header_size,
             max_block_size_with_standard_txs,
             max_block_size_with_smart_contracts,
+            max_block_size,
+            max_block_tx_count,
             max_future_block_time_offset,
             max_no_signature_data_size,
             max_depth_for_reorg,
@@ -330,15 +392,20 @@ impl Builder {
             emission_schedule,
             final_supply,
             net_upgrades,
+            chainstate_upgrades,
             token_min_issuance_fee,
             token_max_uri_len,
             token_max_dec_count,
+            token_max_supply,
             token_max_ticker_len,
             empty_consensus_reward_maturity_distance,
             token_max_name_len,
             token_max_description_len,
             token_min_hash_len,
             token_max_hash_len,
+            token_max_nft_metadata_size,
+            fungible_token_issuance_enabled,
+            nft_issuance_enabled,
             max_classic_multisig_public_keys_count,
             min_stake_pool_pledge,
         }
@@ -365,14 +432,24 @@ impl Builder {
     builder_method!(software_version: SemVer);
     builder_method!(target_block_spacing: Duration);
     builder_method!(coin_decimals: u8);
+    builder_method!(token_max_supply: u128);
+    builder_method!(token_min_hash_len: usize);
+    builder_method!(token_max_hash_len: usize);
+    builder_method!(token_max_nft_metadata_size: usize);
     builder_method!(max_block_header_size: usize);
     builder_method!(max_block_size_with_standard_txs: usize);
     builder_method!(max_block_size_with_smart_contracts: usize);
+    builder_method!(max_block_size: usize);
+    builder_method!(max_block_tx_count: usize);
+    builder_method!(max_no_signature_data_size: usize);
     builder_method!(max_depth_for_reorg: BlockDistance);
     builder_method!(net_upgrades: NetUpgrades<UpgradeVersion>);
+    builder_method!(chainstate_upgrades: NetUpgrades<ChainstateUpgrade>);
     builder_method!(empty_consensus_reward_maturity_distance: BlockDistance);
     builder_method!(epoch_length: NonZeroU64);
     builder_method!(sealed_epoch_distance_from_tip: usize);
+    builder_method!(fungible_token_issuance_enabled: bool);
+    builder_method!(nft_issuance_enabled: bool);
 
     /// Set the genesis block to be the unit test version
     pub fn genesis_unittest(mut self, premine_destination: Destination) -> Self {
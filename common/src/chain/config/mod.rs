@@ -30,8 +30,8 @@ use crate::chain::block::timestamp::BlockTimestamp;
 use crate::chain::transaction::Destination;
 use crate::chain::upgrades::NetUpgrades;
 use crate::chain::TxOutput;
+use crate::chain::{ChainstateUpgrade, PoWChainConfig, UpgradeVersion};
 use crate::chain::{GenBlock, Genesis};
-use crate::chain::{PoWChainConfig, UpgradeVersion};
 use crate::primitives::id::{Id, Idable, WithId};
 use crate::primitives::per_thousand::PerThousand;
 use crate::primitives::semver::SemVer;
@@ -167,6 +167,7 @@ pub struct ChainConfig {
     bip44_coin_type: ChildNumber,
     height_checkpoint_data: Checkpoints,
     net_upgrades: NetUpgrades<UpgradeVersion>,
+    chainstate_upgrades: NetUpgrades<ChainstateUpgrade>,
     magic_bytes: [u8; 4],
     p2p_port: u16,
     default_rpc_port: u16,
@@ -181,6 +182,8 @@ pub struct ChainConfig {
     max_block_header_size: usize,
     max_block_size_with_standard_txs: usize,
     max_block_size_with_smart_contracts: usize,
+    max_block_size: usize,
+    max_block_tx_count: usize,
     max_no_signature_data_size: usize,
     max_depth_for_reorg: BlockDistance,
     pow_chain_config: PoWChainConfig,
@@ -190,11 +193,15 @@ pub struct ChainConfig {
     token_min_issuance_fee: Amount,
     token_max_uri_len: usize,
     token_max_dec_count: u8,
+    token_max_supply: u128,
     token_max_ticker_len: usize,
     token_max_name_len: usize,
     token_max_description_len: usize,
     token_min_hash_len: usize,
     token_max_hash_len: usize,
+    token_max_nft_metadata_size: usize,
+    fungible_token_issuance_enabled: bool,
+    nft_issuance_enabled: bool,
     empty_consensus_reward_maturity_distance: BlockDistance,
     max_classic_multisig_public_keys_count: usize,
     min_stake_pool_pledge: Amount,
@@ -291,6 +298,12 @@ impl ChainConfig {
         &self.net_upgrades
     }
 
+    /// Changes of the transaction validation rules, activated at the given heights
+    #[must_use]
+    pub fn chainstate_upgrades(&self) -> &NetUpgrades<ChainstateUpgrade> {
+        &self.chainstate_upgrades
+    }
+
     /// Checkpoints enforced by the chain, as in, a block id vs height that must be satisfied
     #[must_use]
     pub fn height_checkpoints(&self) -> &Checkpoints {
@@ -321,7 +334,10 @@ impl ChainConfig {
         self.coin_ticker
     }
 
-    /// The maximum size of data attached to NoSignature witness
+    /// The maximum size of data attached to NoSignature witness.
+    ///
+    /// This is a consensus rule: blocks with transactions that carry more data in a NoSignature
+    /// witness are rejected, so that the witness can't be used to cheaply bloat the chain.
     #[must_use]
     pub fn max_no_signature_data_size(&self) -> usize {
         self.max_no_signature_data_size
@@ -368,6 +384,20 @@ impl ChainConfig {
         self.max_block_size_with_smart_contracts
     }
 
+    /// The maximum serialized size of a whole block, including the header and the block reward.
+    /// This is a consensus rule, independent of the size limits of network messages.
+    #[must_use]
+    pub fn max_block_size(&self) -> usize {
+        self.max_block_size
+    }
+
+    /// The maximum number of transactions in a block.
+    /// Bounds the size of the merkle trees and the verification work spent on a single block.
+    #[must_use]
+    pub fn max_block_tx_count(&self) -> usize {
+        self.max_block_tx_count
+    }
+
     /// The maximum size of any transaction submitted to the node for the mempool
     pub fn max_tx_size_for_mempool(&self) -> usize {
         std::cmp::min(
@@ -435,6 +465,12 @@ impl ChainConfig {
         self.token_max_dec_count
     }
 
+    /// The maximum supply of a token in whole units, i.e. regardless of the number of decimals
+    #[must_use]
+    pub fn token_max_supply(&self) -> u128 {
+        self.token_max_supply
+    }
+
     /// The maximum length of a ticker of a token
     #[must_use]
     pub fn token_max_ticker_len(&self) -> usize {
@@ -458,18 +494,40 @@ impl ChainConfig {
         self.token_max_name_len
     }
 
-    /// The minimum length of a hash of a token
+    /// The minimum length, in bytes, of the media hash of an NFT
+    ///
+    /// The hashing algorithm is not prescribed, so any digest whose length lies between
+    /// this and [ChainConfig::max_hash_len] (inclusive) is accepted, e.g. a 32-byte SHA-256
+    /// or BLAKE2b-256 digest with the default limits.
     #[must_use]
     pub fn min_hash_len(&self) -> usize {
         self.token_min_hash_len
     }
 
-    /// The maximum length of a hash of a token
+    /// The maximum length, in bytes, of the media hash of an NFT
     #[must_use]
     pub fn max_hash_len(&self) -> usize {
         self.token_max_hash_len
     }
 
+    /// The maximum encoded size of the whole metadata of an NFT
+    #[must_use]
+    pub fn token_max_nft_metadata_size(&self) -> usize {
+        self.token_max_nft_metadata_size
+    }
+
+    /// Whether transactions are allowed to issue new fungible tokens
+    #[must_use]
+    pub fn fungible_token_issuance_enabled(&self) -> bool {
+        self.fungible_token_issuance_enabled
+    }
+
+    /// Whether transactions are allowed to issue new NFTs
+    #[must_use]
+    pub fn nft_issuance_enabled(&self) -> bool {
+        self.nft_issuance_enabled
+    }
+
     /// The minimum number of blocks required for a block reward to mature
     #[must_use]
     pub fn empty_consensus_reward_maturity_distance(&self) -> BlockDistance {
@@ -533,15 +591,21 @@ impl AsRef<ChainConfig> for ChainConfig {
 const MAX_BLOCK_HEADER_SIZE: usize = 1024;
 const MAX_BLOCK_TXS_SIZE: usize = 1_048_576;
 const MAX_BLOCK_CONTRACTS_SIZE: usize = 1_048_576;
+// Leaves room for the block reward and the encoding overhead on top of the component limits above
+const MAX_BLOCK_SIZE: usize = 2_200_000;
+// A transaction takes at least about 50 bytes, so blocks within the size limits never hit this one
+const MAX_BLOCK_TX_COUNT: usize = 25_000;
 const MAX_TX_NO_SIG_WITNESS_SIZE: usize = 128;
 const TOKEN_MIN_ISSUANCE_FEE: Amount = Amount::from_atoms(100 * CoinUnit::ATOMS_PER_COIN);
 const TOKEN_MAX_DEC_COUNT: u8 = 18;
+const TOKEN_MAX_SUPPLY: u128 = 1_000_000_000_000_000_000;
 const TOKEN_MAX_TICKER_LEN: usize = 5;
 const TOKEN_MIN_HASH_LEN: usize = 4;
 const TOKEN_MAX_HASH_LEN: usize = 32;
 const TOKEN_MAX_NAME_LEN: usize = 10;
 const TOKEN_MAX_DESCRIPTION_LEN: usize = 100;
 const TOKEN_MAX_URI_LEN: usize = 1024;
+const TOKEN_MAX_NFT_METADATA_SIZE: usize = 2560;
 const MAX_CLASSIC_MULTISIG_PUBLIC_KEYS_COUNT: usize = 16;
 const MIN_STAKE_POOL_PLEDGE: Amount = Amount::from_atoms(40_000 * CoinUnit::ATOMS_PER_COIN);
 
@@ -650,6 +714,7 @@ pub fn create_regtest() -> ChainConfig {
 pub fn create_unit_test_config() -> ChainConfig {
     Builder::new(ChainType::Mainnet)
         .net_upgrades(NetUpgrades::unit_tests())
+        .chainstate_upgrades(NetUpgrades::latest_chainstate_upgrades())
         .genesis_unittest(Destination::AnyoneCanSpend)
         .build()
 }
@@ -36,7 +36,7 @@ use crypto::{
 use hex::FromHex;
 use serialization::hex::HexEncode;
 
-const GENESIS_BLOCK_TIMESTAMP: u64 = 1639975460;
+pub(super) const GENESIS_BLOCK_TIMESTAMP: u64 = 1639975460;
 const GENESIS_POOL_ID: &str = "123c4c600097c513e088b9be62069f0c74c7671c523c8e3469a1c3f14b7ea2c4";
 const GENESIS_STAKE_PRIVATE_KEY: &str =
     "008717e6946febd3a33ccdc3f3a27629ec80c33461c33a0fc56b4836fcedd26638";
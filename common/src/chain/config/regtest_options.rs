@@ -13,30 +13,40 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{str::FromStr, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 
 use clap::Args;
 
 use crate::{
     chain::{
+        block::timestamp::BlockTimestamp,
         config::{
-            regtest::{create_regtest_pos_genesis, create_regtest_pow_genesis},
+            regtest::{
+                create_regtest_pos_genesis, create_regtest_pow_genesis, GENESIS_BLOCK_TIMESTAMP,
+            },
             Builder, ChainType, EmissionScheduleTabular,
         },
         pos::{
             DEFAULT_BLOCK_COUNT_TO_AVERAGE, DEFAULT_MATURITY_DISTANCE, DEFAULT_TARGET_BLOCK_TIME,
         },
-        pos_initial_difficulty, ConsensusUpgrade, Destination, NetUpgrades, PoSChainConfig,
-        PoSConsensusVersion, UpgradeVersion,
+        pos_initial_difficulty, ConsensusUpgrade, Destination, Genesis, NetUpgrades,
+        PoSChainConfig, PoSConsensusVersion, TxOutput, UpgradeVersion,
     },
-    primitives::{self, per_thousand::PerThousand, semver::SemVer, BlockHeight},
+    primitives::{self, per_thousand::PerThousand, semver::SemVer, Amount, BlockHeight},
     Uint256,
 };
 
 use super::{regtest::GenesisStakingSettings, ChainConfig};
 
-use anyhow::{anyhow, ensure, Result};
+use anyhow::{anyhow, ensure, Context, Result};
+use itertools::Itertools;
 use paste::paste;
+use serde::Deserialize;
+use serialization::hex_encoded::HexEncoded;
 
 #[derive(Args, Clone, Debug)]
 pub struct ChainConfigOptions {
@@ -75,6 +85,14 @@ pub struct ChainConfigOptions {
     #[clap(long)]
     pub chain_max_block_size_with_smart_contracts: Option<usize>,
 
+    /// The maximum serialized size of a whole block in bytes.
+    #[clap(long)]
+    pub chain_max_block_size: Option<usize>,
+
+    /// The maximum number of transactions in a block.
+    #[clap(long)]
+    pub chain_max_block_tx_count: Option<usize>,
+
     /// Initial difficulty for the chain in Compact representation.
     #[clap(long)]
     pub chain_initial_difficulty: Option<u32>,
@@ -95,9 +113,139 @@ pub struct ChainConfigOptions {
     /// PoS Genesis staking settings
     #[clap(long, default_value_t)]
     pub chain_genesis_staking_settings: GenesisStakingSettings,
+
+    /// Path to a genesis specification file (JSON, or TOML if the extension is `.toml`).
+    /// Overrides the default regtest genesis block.
+    #[clap(long)]
+    pub chain_genesis_file: Option<PathBuf>,
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ChainConfigOptionError {
+    #[error("Magic bytes must be 4 bytes long, got {0}")]
+    InvalidMagicBytesLength(usize),
+    #[error("Invalid software version {0}: {1}")]
+    InvalidSoftwareVersion(String, String),
+    #[error("Target block spacing must not be zero")]
+    ZeroTargetBlockSpacing,
+    #[error("Coin decimals {0} exceed the precision of coin amounts")]
+    TooManyCoinDecimals(u8),
+    #[error("Invalid emission schedule {0}: {1}")]
+    InvalidEmissionSchedule(String, String),
+    #[error("Maximum block header size must not be zero")]
+    ZeroMaxBlockHeaderSize,
+    #[error("Maximum block size with standard transactions must not be zero")]
+    ZeroMaxBlockSizeWithStandardTxs,
+    #[error("Maximum block size must not be zero")]
+    ZeroMaxBlockSize,
+    #[error("Maximum block transaction count must not be zero")]
+    ZeroMaxBlockTxCount,
+    #[error("Invalid initial difficulty {0}")]
+    InvalidInitialDifficulty(u32),
+    #[error("Initial difficulty is only used with the PoS V0 to V1 net upgrade")]
+    UnusedInitialDifficulty,
+    #[error("PoS V0 to V1 net upgrade height must be greater than 1, got {0}")]
+    InvalidPoSV0ToV1UpgradeHeight(u64),
+}
+
+/// All the problems found in a set of chain config options
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid chain config options: {}", .0.iter().join("; "))]
+pub struct ChainConfigOptionsErrors(Vec<ChainConfigOptionError>);
+
+impl ChainConfigOptionsErrors {
+    pub fn errors(&self) -> &[ChainConfigOptionError] {
+        &self.0
+    }
+}
+
+impl ChainConfigOptions {
+    /// Check the ranges of the options and their consistency with each other, reporting all the
+    /// problems at once rather than failing on the first one
+    pub fn validate(&self) -> Result<(), ChainConfigOptionsErrors> {
+        let mut errors = Vec::new();
+
+        if let Some(magic_bytes) = &self.chain_magic_bytes {
+            if magic_bytes.len() != 4 {
+                errors.push(ChainConfigOptionError::InvalidMagicBytesLength(
+                    magic_bytes.len(),
+                ));
+            }
+        }
+
+        if let Some(version) = &self.software_version {
+            if let Err(e) = SemVer::try_from(version.as_str()) {
+                errors.push(ChainConfigOptionError::InvalidSoftwareVersion(
+                    version.clone(),
+                    e.to_string(),
+                ));
+            }
+        }
+
+        if self.chain_target_block_spacing == Some(0) {
+            errors.push(ChainConfigOptionError::ZeroTargetBlockSpacing);
+        }
+
+        if let Some(decimals) = self.chain_coin_decimals {
+            if 10u128.checked_pow(decimals.into()).is_none() {
+                errors.push(ChainConfigOptionError::TooManyCoinDecimals(decimals));
+            }
+        }
+
+        if let Some(schedule) = &self.chain_emission_schedule {
+            if let Err(e) = EmissionScheduleTabular::from_str(schedule) {
+                errors.push(ChainConfigOptionError::InvalidEmissionSchedule(
+                    schedule.clone(),
+                    e.to_string(),
+                ));
+            }
+        }
+
+        if self.chain_max_block_header_size == Some(0) {
+            errors.push(ChainConfigOptionError::ZeroMaxBlockHeaderSize);
+        }
+
+        if self.chain_max_block_size_with_standard_txs == Some(0) {
+            errors.push(ChainConfigOptionError::ZeroMaxBlockSizeWithStandardTxs);
+        }
+
+        if self.chain_max_block_size == Some(0) {
+            errors.push(ChainConfigOptionError::ZeroMaxBlockSize);
+        }
+
+        if self.chain_max_block_tx_count == Some(0) {
+            errors.push(ChainConfigOptionError::ZeroMaxBlockTxCount);
+        }
+
+        if let Some(difficulty) = self.chain_initial_difficulty {
+            if Uint256::try_from(primitives::Compact(difficulty)).is_err() {
+                errors.push(ChainConfigOptionError::InvalidInitialDifficulty(difficulty));
+            }
+            if self.chain_pos_netupgrades_v0_to_v1.is_none() {
+                errors.push(ChainConfigOptionError::UnusedInitialDifficulty);
+            }
+        }
+
+        if let Some(height) = self.chain_pos_netupgrades_v0_to_v1 {
+            // Height 1 is where the PoS V0 consensus starts
+            if height <= 1 {
+                errors.push(ChainConfigOptionError::InvalidPoSV0ToV1UpgradeHeight(
+                    height,
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ChainConfigOptionsErrors(errors))
+        }
+    }
 }
 
 pub fn regtest_chain_config(options: &ChainConfigOptions) -> Result<ChainConfig> {
+    options.validate()?;
+
     let ChainConfigOptions {
         chain_magic_bytes,
         chain_max_future_block_time_offset,
@@ -108,11 +256,14 @@ pub fn regtest_chain_config(options: &ChainConfigOptions) -> Result<ChainConfig>
         chain_max_block_header_size,
         chain_max_block_size_with_standard_txs,
         chain_max_block_size_with_smart_contracts,
+        chain_max_block_size,
+        chain_max_block_tx_count,
         chain_pos_netupgrades,
         chain_pos_netupgrades_v0_to_v1,
         chain_initial_difficulty,
         chain_genesis_block_timestamp,
         chain_genesis_staking_settings,
+        chain_genesis_file,
     } = options;
 
     let mut builder = Builder::new(ChainType::Regtest);
@@ -157,6 +308,8 @@ pub fn regtest_chain_config(options: &ChainConfigOptions) -> Result<ChainConfig>
     update_builder!(max_block_header_size);
     update_builder!(max_block_size_with_standard_txs);
     update_builder!(max_block_size_with_smart_contracts);
+    update_builder!(max_block_size);
+    update_builder!(max_block_tx_count);
 
     if chain_pos_netupgrades.unwrap_or(false) {
         builder = builder.net_upgrades(NetUpgrades::regtest_with_pos()).genesis_custom(
@@ -229,5 +382,252 @@ pub fn regtest_chain_config(options: &ChainConfigOptions) -> Result<ChainConfig>
             ));
     }
 
+    if let Some(path) = chain_genesis_file {
+        let pos_enabled =
+            chain_pos_netupgrades.unwrap_or(false) || chain_pos_netupgrades_v0_to_v1.is_some();
+        let genesis = load_genesis_file(path, *chain_genesis_block_timestamp, pos_enabled)?;
+        builder = builder.genesis_custom(genesis);
+    }
+
     Ok(builder.build())
 }
+
+/// Contents of a custom regtest genesis file
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct GenesisSpec {
+    #[serde(default)]
+    fun_message: String,
+    /// Timestamp in seconds since UNIX epoch
+    timestamp: Option<u64>,
+    /// Hex-encoded genesis outputs
+    utxos: Vec<HexEncoded<TxOutput>>,
+}
+
+fn load_genesis_file(
+    path: &Path,
+    default_timestamp: Option<u64>,
+    pos_enabled: bool,
+) -> Result<Genesis> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read genesis file {}", path.display()))?;
+    let is_toml = path.extension().is_some_and(|ext| ext == "toml");
+    let spec = parse_genesis_spec(&contents, is_toml)
+        .with_context(|| format!("Invalid genesis file {}", path.display()))?;
+    make_genesis(spec, default_timestamp, pos_enabled)
+}
+
+fn parse_genesis_spec(contents: &str, is_toml: bool) -> Result<GenesisSpec> {
+    let spec = if is_toml {
+        toml::from_str(contents)?
+    } else {
+        serde_json::from_str(contents)?
+    };
+    Ok(spec)
+}
+
+fn make_genesis(
+    spec: GenesisSpec,
+    default_timestamp: Option<u64>,
+    pos_enabled: bool,
+) -> Result<Genesis> {
+    let GenesisSpec {
+        fun_message,
+        timestamp,
+        utxos,
+    } = spec;
+    let utxos: Vec<TxOutput> = utxos.into_iter().map(HexEncoded::take).collect();
+
+    ensure!(!utxos.is_empty(), "Genesis must have at least one output");
+
+    let mut total_coins = Amount::ZERO;
+    for output in &utxos {
+        let coins = match output {
+            TxOutput::Transfer(value, _) | TxOutput::LockThenTransfer(value, _, _) => value
+                .coin_amount()
+                .ok_or_else(|| anyhow!("Only coin outputs are supported in genesis: {output:?}"))?,
+            TxOutput::CreateStakePool(_, data) => data.value(),
+            TxOutput::Burn(_)
+            | TxOutput::ProduceBlockFromStake(_, _)
+            | TxOutput::CreateDelegationId(_, _)
+            | TxOutput::DelegateStaking(_, _) => {
+                return Err(anyhow!("Unsupported genesis output: {output:?}"))
+            }
+        };
+        total_coins =
+            (total_coins + coins).ok_or_else(|| anyhow!("Genesis coin amount overflow"))?;
+    }
+
+    if pos_enabled {
+        ensure!(
+            utxos.iter().any(|output| matches!(output, TxOutput::CreateStakePool(_, _))),
+            "Genesis must create a stake pool when PoS is enabled"
+        );
+    }
+
+    let timestamp = timestamp.or(default_timestamp).unwrap_or(GENESIS_BLOCK_TIMESTAMP);
+
+    Ok(Genesis::new(
+        fun_message,
+        BlockTimestamp::from_int_seconds(timestamp),
+        utxos,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use serialization::hex::HexEncode;
+
+    use super::*;
+    use crate::chain::output_value::OutputValue;
+
+    fn transfer_hex(amount: u128) -> String {
+        TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(amount)),
+            Destination::AnyoneCanSpend,
+        )
+        .hex_encode()
+    }
+
+    #[test]
+    fn parse_json_and_toml() {
+        let output = transfer_hex(1000);
+
+        let json =
+            format!(r#"{{"fun_message": "hello", "timestamp": 1234, "utxos": ["{output}"]}}"#);
+        let genesis = make_genesis(parse_genesis_spec(&json, false).unwrap(), None, false).unwrap();
+        assert_eq!(genesis.fun_message(), "hello");
+        assert_eq!(genesis.timestamp(), BlockTimestamp::from_int_seconds(1234));
+        assert_eq!(genesis.utxos().len(), 1);
+        assert_eq!(genesis.utxos()[0].hex_encode(), output);
+
+        let toml = format!("utxos = [\"{output}\"]\n");
+        let genesis =
+            make_genesis(parse_genesis_spec(&toml, true).unwrap(), Some(42), false).unwrap();
+        assert_eq!(genesis.fun_message(), "");
+        assert_eq!(genesis.timestamp(), BlockTimestamp::from_int_seconds(42));
+        assert_eq!(genesis.utxos()[0].hex_encode(), output);
+    }
+
+    #[test]
+    fn invalid_spec() {
+        assert!(parse_genesis_spec(r#"{"utxos": [], "foo": 1}"#, false).is_err());
+        assert!(parse_genesis_spec(r#"{"utxos": ["zz"]}"#, false).is_err());
+
+        let empty = parse_genesis_spec(r#"{"utxos": []}"#, false).unwrap();
+        assert!(make_genesis(empty, None, false).is_err());
+
+        let burn = TxOutput::Burn(OutputValue::Coin(Amount::from_atoms(1))).hex_encode();
+        let spec = parse_genesis_spec(&format!(r#"{{"utxos": ["{burn}"]}}"#), false).unwrap();
+        assert!(make_genesis(spec, None, false).is_err());
+
+        let overflow = format!(
+            r#"{{"utxos": ["{}", "{}"]}}"#,
+            transfer_hex(u128::MAX),
+            transfer_hex(1)
+        );
+        let spec = parse_genesis_spec(&overflow, false).unwrap();
+        assert!(make_genesis(spec, None, false).is_err());
+
+        let no_pool = format!(r#"{{"utxos": ["{}"]}}"#, transfer_hex(1));
+        let spec = parse_genesis_spec(&no_pool, false).unwrap();
+        assert!(make_genesis(spec, None, true).is_err());
+    }
+
+    fn empty_options() -> ChainConfigOptions {
+        ChainConfigOptions {
+            chain_magic_bytes: None,
+            chain_max_future_block_time_offset: None,
+            software_version: None,
+            chain_target_block_spacing: None,
+            chain_coin_decimals: None,
+            chain_emission_schedule: None,
+            chain_max_block_header_size: None,
+            chain_max_block_size_with_standard_txs: None,
+            chain_max_block_size_with_smart_contracts: None,
+            chain_max_block_size: None,
+            chain_max_block_tx_count: None,
+            chain_initial_difficulty: None,
+            chain_pos_netupgrades: None,
+            chain_pos_netupgrades_v0_to_v1: None,
+            chain_genesis_block_timestamp: None,
+            chain_genesis_staking_settings: GenesisStakingSettings::default(),
+            chain_genesis_file: None,
+        }
+    }
+
+    #[test]
+    fn validate_valid_options() {
+        empty_options().validate().unwrap();
+        regtest_chain_config(&empty_options()).unwrap();
+
+        let options = ChainConfigOptions {
+            chain_magic_bytes: Some("abcd".to_owned()),
+            software_version: Some("1.2.3".to_owned()),
+            chain_target_block_spacing: Some(60),
+            chain_coin_decimals: Some(11),
+            chain_emission_schedule: Some("1000+1,10000:+0".to_owned()),
+            chain_initial_difficulty: Some(419627008),
+            chain_pos_netupgrades_v0_to_v1: Some(200),
+            ..empty_options()
+        };
+        options.validate().unwrap();
+        regtest_chain_config(&options).unwrap();
+    }
+
+    #[test]
+    fn validate_reports_all_errors() {
+        let options = ChainConfigOptions {
+            chain_magic_bytes: Some("abc".to_owned()),
+            software_version: Some("1.x".to_owned()),
+            chain_target_block_spacing: Some(0),
+            chain_coin_decimals: Some(39),
+            chain_emission_schedule: Some(String::new()),
+            chain_max_block_header_size: Some(0),
+            chain_max_block_size_with_standard_txs: Some(0),
+            chain_max_block_size: Some(0),
+            chain_max_block_tx_count: Some(0),
+            chain_initial_difficulty: Some(0x01fedcba),
+            chain_pos_netupgrades_v0_to_v1: Some(1),
+            ..empty_options()
+        };
+
+        let errors = options.validate().unwrap_err();
+        assert_eq!(
+            errors.errors(),
+            [
+                ChainConfigOptionError::InvalidMagicBytesLength(3),
+                ChainConfigOptionError::InvalidSoftwareVersion(
+                    "1.x".to_owned(),
+                    SemVer::try_from("1.x").unwrap_err().to_owned()
+                ),
+                ChainConfigOptionError::ZeroTargetBlockSpacing,
+                ChainConfigOptionError::TooManyCoinDecimals(39),
+                ChainConfigOptionError::InvalidEmissionSchedule(
+                    String::new(),
+                    EmissionScheduleTabular::from_str("").unwrap_err().to_string()
+                ),
+                ChainConfigOptionError::ZeroMaxBlockHeaderSize,
+                ChainConfigOptionError::ZeroMaxBlockSizeWithStandardTxs,
+                ChainConfigOptionError::ZeroMaxBlockSize,
+                ChainConfigOptionError::ZeroMaxBlockTxCount,
+                ChainConfigOptionError::InvalidInitialDifficulty(0x01fedcba),
+                ChainConfigOptionError::InvalidPoSV0ToV1UpgradeHeight(1),
+            ]
+        );
+
+        let message = regtest_chain_config(&options).unwrap_err().to_string();
+        for error in errors.errors() {
+            assert!(message.contains(&error.to_string()));
+        }
+
+        let options = ChainConfigOptions {
+            chain_initial_difficulty: Some(419627008),
+            ..empty_options()
+        };
+        assert_eq!(
+            options.validate().unwrap_err().errors(),
+            [ChainConfigOptionError::UnusedInitialDifficulty]
+        );
+    }
+}
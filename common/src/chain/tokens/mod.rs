@@ -88,6 +88,7 @@ use super::{Block, ChainConfig, Transaction};
 pub struct TokenAuxiliaryData {
     issuance_tx: Transaction,
     issuance_block_id: Id<Block>,
+    frozen: bool,
 }
 
 impl TokenAuxiliaryData {
@@ -95,6 +96,7 @@ impl TokenAuxiliaryData {
         Self {
             issuance_tx,
             issuance_block_id,
+            frozen: false,
         }
     }
 
@@ -105,6 +107,15 @@ impl TokenAuxiliaryData {
     pub fn issuance_block_id(&self) -> Id<Block> {
         self.issuance_block_id
     }
+
+    /// Whether transfers of the token are currently frozen by the issuer
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    pub fn with_frozen(self, frozen: bool) -> Self {
+        Self { frozen, ..self }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, serde::Serialize)]
@@ -113,6 +124,16 @@ pub struct TokenTransfer {
     pub amount: Amount,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, serde::Serialize)]
+pub struct TokenFreeze {
+    pub token_id: TokenId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, serde::Serialize)]
+pub struct TokenUnfreeze {
+    pub token_id: TokenId,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, serde::Serialize)]
 pub struct TokenIssuance {
     pub token_ticker: Vec<u8>,
@@ -139,6 +160,25 @@ pub enum TokenData {
     //     token_id: TokenId,
     //     amount_to_issue: Amount,
     // },
+    /// Forbid any transfers of the token, must be authorized by the issuer
+    #[codec(index = 5)]
+    TokenFreezeV1(TokenFreeze),
+    /// Allow transfers of a previously frozen token, must be authorized by the issuer
+    #[codec(index = 6)]
+    TokenUnfreezeV1(TokenUnfreeze),
+}
+
+impl TokenData {
+    /// The id of the token whose state is changed by a freeze/unfreeze operation
+    pub fn frozen_state_change(&self) -> Option<(TokenId, bool)> {
+        match self {
+            TokenData::TokenFreezeV1(freeze) => Some((freeze.token_id, true)),
+            TokenData::TokenUnfreezeV1(unfreeze) => Some((unfreeze.token_id, false)),
+            TokenData::TokenTransfer(_)
+            | TokenData::TokenIssuance(_)
+            | TokenData::NftIssuance(_) => None,
+        }
+    }
 }
 
 impl From<NftIssuance> for TokenData {
@@ -152,3 +192,15 @@ impl From<TokenIssuance> for TokenData {
         Self::TokenIssuance(Box::new(d))
     }
 }
+
+impl From<TokenFreeze> for TokenData {
+    fn from(d: TokenFreeze) -> Self {
+        Self::TokenFreezeV1(d)
+    }
+}
+
+impl From<TokenUnfreeze> for TokenData {
+    fn from(d: TokenUnfreeze) -> Self {
+        Self::TokenUnfreezeV1(d)
+    }
+}
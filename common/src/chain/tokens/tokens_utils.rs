@@ -13,10 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::TokenId;
+use super::{TokenData, TokenId};
 use crate::{
-    chain::{Transaction, TxOutput},
-    primitives::id::hash_encoded,
+    chain::{output_value::OutputValue, Transaction, TxOutput},
+    primitives::{id::hash_encoded, Amount},
 };
 
 pub fn token_id(tx: &Transaction) -> Option<TokenId> {
@@ -26,3 +26,240 @@ pub fn token_id(tx: &Transaction) -> Option<TokenId> {
 pub fn get_tokens_issuance_count(outputs: &[TxOutput]) -> usize {
     outputs.iter().filter(|&output| output.is_token_or_nft_issuance()).count()
 }
+
+/// A single token-related effect of a transaction output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenOperation {
+    Issuance { token_id: TokenId, amount: Amount },
+    NftIssuance { token_id: TokenId },
+    Transfer { token_id: TokenId, amount: Amount },
+    Burn { token_id: TokenId, amount: Amount },
+    Freeze { token_id: TokenId },
+    Unfreeze { token_id: TokenId },
+}
+
+impl TokenOperation {
+    pub fn token_id(&self) -> TokenId {
+        match self {
+            TokenOperation::Issuance { token_id, .. }
+            | TokenOperation::NftIssuance { token_id }
+            | TokenOperation::Transfer { token_id, .. }
+            | TokenOperation::Burn { token_id, .. }
+            | TokenOperation::Freeze { token_id }
+            | TokenOperation::Unfreeze { token_id } => *token_id,
+        }
+    }
+}
+
+/// Collect the token operations performed by the outputs of a transaction, in output order.
+///
+/// Returns `None` if the transaction issues a token but its token id can't be calculated,
+/// i.e. the transaction has no inputs.
+pub fn get_token_operations(tx: &Transaction) -> Option<Vec<TokenOperation>> {
+    tx.outputs()
+        .iter()
+        .filter_map(|output| {
+            let (value, is_burn) = match output {
+                TxOutput::Transfer(v, _) | TxOutput::LockThenTransfer(v, _, _) => (v, false),
+                TxOutput::Burn(v) => (v, true),
+                TxOutput::CreateStakePool(_, _)
+                | TxOutput::ProduceBlockFromStake(_, _)
+                | TxOutput::CreateDelegationId(_, _)
+                | TxOutput::DelegateStaking(_, _) => return None,
+            };
+            let token_data = match value {
+                OutputValue::Token(token_data) => token_data,
+                OutputValue::Coin(_) => return None,
+            };
+
+            let operation = match token_data.as_ref() {
+                TokenData::TokenTransfer(transfer) if is_burn => Some(TokenOperation::Burn {
+                    token_id: transfer.token_id,
+                    amount: transfer.amount,
+                }),
+                TokenData::TokenTransfer(transfer) => Some(TokenOperation::Transfer {
+                    token_id: transfer.token_id,
+                    amount: transfer.amount,
+                }),
+                TokenData::TokenIssuance(issuance) => {
+                    token_id(tx).map(|token_id| TokenOperation::Issuance {
+                        token_id,
+                        amount: issuance.amount_to_issue,
+                    })
+                }
+                TokenData::NftIssuance(_) => {
+                    token_id(tx).map(|token_id| TokenOperation::NftIssuance { token_id })
+                }
+                TokenData::TokenFreezeV1(freeze) => Some(TokenOperation::Freeze {
+                    token_id: freeze.token_id,
+                }),
+                TokenData::TokenUnfreezeV1(unfreeze) => Some(TokenOperation::Unfreeze {
+                    token_id: unfreeze.token_id,
+                }),
+            };
+            Some(operation)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serialization::extras::non_empty_vec::DataOrNoVec;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    use super::*;
+    use crate::{
+        chain::{
+            tokens::{
+                Metadata, NftIssuance, TokenFreeze, TokenIssuance, TokenTransfer, TokenUnfreeze,
+            },
+            transaction::TxInput,
+            Destination, OutPointSourceId,
+        },
+        primitives::{Id, H256},
+    };
+    use crypto::random::Rng;
+
+    fn token_output(token_data: TokenData) -> OutputValue {
+        OutputValue::Token(Box::new(token_data))
+    }
+
+    fn random_input(rng: &mut (impl Rng + crypto::random::CryptoRng)) -> TxInput {
+        TxInput::from_utxo(
+            OutPointSourceId::Transaction(Id::new(H256::random_using(rng))),
+            rng.gen(),
+        )
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn mixed_token_operations(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let transferred_token_id = TokenId::random_using(&mut rng);
+        let burned_token_id = TokenId::random_using(&mut rng);
+        let frozen_token_id = TokenId::random_using(&mut rng);
+        let unfrozen_token_id = TokenId::random_using(&mut rng);
+        let transfer_amount = Amount::from_atoms(rng.gen_range(1..u128::MAX));
+        let burn_amount = Amount::from_atoms(rng.gen_range(1..u128::MAX));
+        let issue_amount = Amount::from_atoms(rng.gen_range(1..u128::MAX));
+
+        let outputs = vec![
+            TxOutput::Transfer(
+                OutputValue::Coin(Amount::from_atoms(rng.gen())),
+                Destination::AnyoneCanSpend,
+            ),
+            TxOutput::Transfer(
+                token_output(
+                    TokenIssuance {
+                        token_ticker: b"XXXX".to_vec(),
+                        amount_to_issue: issue_amount,
+                        number_of_decimals: rng.gen_range(1..18),
+                        metadata_uri: b"http://uri".to_vec(),
+                    }
+                    .into(),
+                ),
+                Destination::AnyoneCanSpend,
+            ),
+            TxOutput::LockThenTransfer(
+                token_output(TokenData::TokenTransfer(TokenTransfer {
+                    token_id: transferred_token_id,
+                    amount: transfer_amount,
+                })),
+                Destination::AnyoneCanSpend,
+                crate::chain::timelock::OutputTimeLock::ForBlockCount(rng.gen()),
+            ),
+            TxOutput::Burn(token_output(TokenData::TokenTransfer(TokenTransfer {
+                token_id: burned_token_id,
+                amount: burn_amount,
+            }))),
+            TxOutput::Burn(token_output(
+                TokenFreeze {
+                    token_id: frozen_token_id,
+                }
+                .into(),
+            )),
+            TxOutput::Burn(token_output(
+                TokenUnfreeze {
+                    token_id: unfrozen_token_id,
+                }
+                .into(),
+            )),
+            TxOutput::Burn(OutputValue::Coin(Amount::from_atoms(rng.gen()))),
+        ];
+        let tx = Transaction::new(0, vec![random_input(&mut rng)], outputs).unwrap();
+        let issued_token_id = token_id(&tx).unwrap();
+
+        assert_eq!(
+            get_token_operations(&tx),
+            Some(vec![
+                TokenOperation::Issuance {
+                    token_id: issued_token_id,
+                    amount: issue_amount,
+                },
+                TokenOperation::Transfer {
+                    token_id: transferred_token_id,
+                    amount: transfer_amount,
+                },
+                TokenOperation::Burn {
+                    token_id: burned_token_id,
+                    amount: burn_amount,
+                },
+                TokenOperation::Freeze {
+                    token_id: frozen_token_id,
+                },
+                TokenOperation::Unfreeze {
+                    token_id: unfrozen_token_id,
+                },
+            ])
+        );
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn nft_issuance(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let nft = NftIssuance {
+            metadata: Metadata {
+                creator: None,
+                name: b"name".to_vec(),
+                description: b"description".to_vec(),
+                ticker: b"NFT".to_vec(),
+                icon_uri: DataOrNoVec::from(None),
+                additional_metadata_uri: DataOrNoVec::from(None),
+                media_uri: DataOrNoVec::from(None),
+                media_hash: vec![0; 32],
+            },
+        };
+        let nft_output = TxOutput::Transfer(token_output(nft.into()), Destination::AnyoneCanSpend);
+
+        let tx =
+            Transaction::new(0, vec![random_input(&mut rng)], vec![nft_output.clone()]).unwrap();
+        assert_eq!(
+            get_token_operations(&tx),
+            Some(vec![TokenOperation::NftIssuance {
+                token_id: token_id(&tx).unwrap()
+            }])
+        );
+
+        // Without inputs the id of the issued token is unknown
+        let tx = Transaction::new(0, vec![], vec![nft_output]).unwrap();
+        assert_eq!(get_token_operations(&tx), None);
+
+        // Transactions without token outputs have no token operations
+        let tx = Transaction::new(
+            0,
+            vec![],
+            vec![TxOutput::Transfer(
+                OutputValue::Coin(Amount::from_atoms(rng.gen())),
+                Destination::AnyoneCanSpend,
+            )],
+        )
+        .unwrap();
+        assert_eq!(get_token_operations(&tx), Some(vec![]));
+    }
+}
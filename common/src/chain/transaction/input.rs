@@ -13,11 +13,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeSet;
+
 use serialization::{Decode, Encode};
+use thiserror::Error;
 
 use crate::chain::AccountNonce;
 
-use super::{AccountOutPoint, AccountSpending, OutPointSourceId, UtxoOutPoint};
+use super::{AccountOutPoint, AccountSpending, AccountType, OutPointSourceId, UtxoOutPoint};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DuplicateInputError {
+    #[error("Utxo {0:?} is spent by more than one input")]
+    DuplicateUtxo(UtxoOutPoint),
+    #[error("Nonce {1:?} of account {0:?} is spent by more than one input")]
+    DuplicateAccountNonce(AccountType, AccountNonce),
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Encode, Decode, serde::Serialize)]
 pub enum TxInput {
@@ -44,8 +55,104 @@ impl TxInput {
     }
 }
 
+/// Check that no two inputs spend the same thing (see CVE-2018-17144).
+///
+/// Utxo inputs are compared by the outpoint and account inputs by the account and the nonce,
+/// regardless of the spent amount.
+pub fn check_duplicate_inputs(inputs: &[TxInput]) -> Result<(), DuplicateInputError> {
+    let mut utxos = BTreeSet::new();
+    let mut account_nonces = BTreeSet::new();
+
+    for input in inputs {
+        match input {
+            TxInput::Utxo(outpoint) => {
+                if !utxos.insert(outpoint) {
+                    return Err(DuplicateInputError::DuplicateUtxo(outpoint.clone()));
+                }
+            }
+            TxInput::Account(outpoint) => {
+                let account = AccountType::from(*outpoint.account());
+                if !account_nonces.insert((account, outpoint.nonce())) {
+                    return Err(DuplicateInputError::DuplicateAccountNonce(
+                        account,
+                        outpoint.nonce(),
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 impl From<UtxoOutPoint> for TxInput {
     fn from(outpoint: UtxoOutPoint) -> TxInput {
         TxInput::Utxo(outpoint)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Rng, Seed};
+
+    use super::*;
+    use crate::{
+        chain::{DelegationId, Transaction},
+        primitives::{Amount, Id, H256},
+    };
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn duplicate_inputs(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let tx_id = Id::<Transaction>::new(H256::random_using(&mut rng));
+        let delegation_id = DelegationId::new(H256::random_using(&mut rng));
+        let nonce = AccountNonce::new(rng.gen_range(0..1000));
+        let account_input = |nonce: AccountNonce, amount: u128| {
+            TxInput::from_account(
+                nonce,
+                AccountSpending::Delegation(delegation_id, Amount::from_atoms(amount)),
+            )
+        };
+
+        // Distinct inputs
+        let inputs = vec![
+            TxInput::from_utxo(tx_id.into(), 0),
+            TxInput::from_utxo(tx_id.into(), 1),
+            account_input(nonce, 1),
+            account_input(nonce.increment().unwrap(), 1),
+        ];
+        assert_eq!(check_duplicate_inputs(&inputs), Ok(()));
+
+        // The same utxo spent twice
+        let inputs = vec![
+            TxInput::from_utxo(tx_id.into(), 1),
+            account_input(nonce, 1),
+            TxInput::from_utxo(tx_id.into(), 1),
+        ];
+        assert_eq!(
+            check_duplicate_inputs(&inputs),
+            Err(DuplicateInputError::DuplicateUtxo(UtxoOutPoint::new(
+                tx_id.into(),
+                1
+            )))
+        );
+
+        // The same account nonce spent twice, even with different amounts
+        let inputs = vec![
+            account_input(nonce, rng.gen_range(0..100)),
+            TxInput::from_utxo(tx_id.into(), 0),
+            account_input(nonce, rng.gen_range(100..200)),
+        ];
+        assert_eq!(
+            check_duplicate_inputs(&inputs),
+            Err(DuplicateInputError::DuplicateAccountNonce(
+                AccountType::Delegation(delegation_id),
+                nonce
+            ))
+        );
+    }
+}
@@ -34,6 +34,8 @@ pub use utxo_outpoint::*;
 
 pub mod signed_transaction;
 
+pub mod partially_signed_transaction;
+
 pub mod output;
 pub use output::*;
 
@@ -121,7 +121,9 @@ impl TxOutput {
                 match v {
                     OutputValue::Token(data) => match data.as_ref() {
                         TokenData::TokenIssuance(_) | TokenData::NftIssuance(_) => true,
-                        TokenData::TokenTransfer(_) => false,
+                        TokenData::TokenTransfer(_)
+                        | TokenData::TokenFreezeV1(_)
+                        | TokenData::TokenUnfreezeV1(_) => false,
                     },
                     OutputValue::Coin(_) => false,
                 }
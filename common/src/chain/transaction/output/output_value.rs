@@ -16,7 +16,9 @@
 use serialization::{Decode, Encode};
 
 use crate::{
-    chain::tokens::{NftIssuance, TokenData, TokenIssuance, TokenTransfer},
+    chain::tokens::{
+        NftIssuance, TokenData, TokenFreeze, TokenIssuance, TokenTransfer, TokenUnfreeze,
+    },
     primitives::Amount,
 };
 
@@ -65,3 +67,15 @@ impl From<TokenIssuance> for OutputValue {
         TokenData::TokenIssuance(Box::new(d)).into()
     }
 }
+
+impl From<TokenFreeze> for OutputValue {
+    fn from(d: TokenFreeze) -> Self {
+        TokenData::TokenFreezeV1(d).into()
+    }
+}
+
+impl From<TokenUnfreeze> for OutputValue {
+    fn from(d: TokenUnfreeze) -> Self {
+        TokenData::TokenUnfreezeV1(d).into()
+    }
+}
@@ -0,0 +1,462 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A transaction that is being signed, possibly by several parties.
+//!
+//! Along with the transaction itself, it carries the information the signers need but that
+//! isn't part of the transaction: the outputs spent by the inputs (required to compute the
+//! sighash) and the destinations the inputs have to be authorized by. The witnesses are
+//! collected one by one; partial classical multisig witnesses of the same input are combined.
+//! Once every input has a witness, the transaction is finalized into a [SignedTransaction].
+
+use serialization::{hex_encoded::HexEncoded, Decode, DecodeAll, Encode};
+use thiserror::Error;
+use utils::ensure;
+
+use super::{
+    signature::{
+        inputsig::{
+            classical_multisig::authorize_classical_multisig::{
+                AuthorizedClassicalMultisigSpend, ClassicalMultisigSigningError,
+            },
+            standard_signature::StandardInputSignature,
+            InputWitness,
+        },
+        verify_signature, TransactionSigError,
+    },
+    signed_transaction::SignedTransaction,
+    Destination, Transaction, TransactionCreationError, TxOutput,
+};
+use crate::chain::ChainConfig;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PartiallySignedTransactionError {
+    #[error("Witness count {0} does not match input count {1}")]
+    InvalidWitnessCount(usize, usize),
+    #[error("Input UTXO count {0} does not match input count {1}")]
+    InvalidInputUtxosCount(usize, usize),
+    #[error("Destination count {0} does not match input count {1}")]
+    InvalidDestinationsCount(usize, usize),
+    #[error("Invalid input index {0} (the transaction has {1} inputs)")]
+    InvalidInputIndex(usize, usize),
+    #[error("Input {0} already has a different witness")]
+    ConflictingWitness(usize),
+    #[error("Failed to combine the multisig signatures of input {0}: {1}")]
+    MultisigCombineFailed(usize, ClassicalMultisigSigningError),
+    #[error("Input {0} has no witness")]
+    MissingWitness(usize),
+    #[error("Invalid witness of input {0}: {1}")]
+    InvalidWitness(usize, TransactionSigError),
+    #[error("Transaction creation error: {0}")]
+    TransactionCreation(#[from] TransactionCreationError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode)]
+pub struct PartiallySignedTransaction {
+    tx: Transaction,
+    witnesses: Vec<Option<InputWitness>>,
+    input_utxos: Vec<Option<TxOutput>>,
+    destinations: Vec<Option<Destination>>,
+}
+
+impl PartiallySignedTransaction {
+    pub fn new(
+        tx: Transaction,
+        witnesses: Vec<Option<InputWitness>>,
+        input_utxos: Vec<Option<TxOutput>>,
+        destinations: Vec<Option<Destination>>,
+    ) -> Result<Self, PartiallySignedTransactionError> {
+        let inputs_count = tx.inputs().len();
+        ensure!(
+            witnesses.len() == inputs_count,
+            PartiallySignedTransactionError::InvalidWitnessCount(witnesses.len(), inputs_count)
+        );
+        ensure!(
+            input_utxos.len() == inputs_count,
+            PartiallySignedTransactionError::InvalidInputUtxosCount(
+                input_utxos.len(),
+                inputs_count
+            )
+        );
+        ensure!(
+            destinations.len() == inputs_count,
+            PartiallySignedTransactionError::InvalidDestinationsCount(
+                destinations.len(),
+                inputs_count
+            )
+        );
+
+        Ok(Self {
+            tx,
+            witnesses,
+            input_utxos,
+            destinations,
+        })
+    }
+
+    /// Create a partially signed transaction with no witnesses yet
+    pub fn new_unsigned(
+        tx: Transaction,
+        input_utxos: Vec<Option<TxOutput>>,
+        destinations: Vec<Option<Destination>>,
+    ) -> Result<Self, PartiallySignedTransactionError> {
+        let witnesses = vec![None; tx.inputs().len()];
+        Self::new(tx, witnesses, input_utxos, destinations)
+    }
+
+    pub fn tx(&self) -> &Transaction {
+        &self.tx
+    }
+
+    pub fn witnesses(&self) -> &[Option<InputWitness>] {
+        &self.witnesses
+    }
+
+    pub fn input_utxos(&self) -> &[Option<TxOutput>] {
+        &self.input_utxos
+    }
+
+    /// The input UTXOs in the form expected by the sighash functions
+    pub fn input_utxos_refs(&self) -> Vec<Option<&TxOutput>> {
+        self.input_utxos.iter().map(Option::as_ref).collect()
+    }
+
+    pub fn destinations(&self) -> &[Option<Destination>] {
+        &self.destinations
+    }
+
+    pub fn is_fully_signed(&self) -> bool {
+        self.witnesses.iter().all(Option::is_some)
+    }
+
+    /// Add the witness of the input with the given index.
+    ///
+    /// If the input already has a witness, the new one is accepted only if both are partial
+    /// classical multisig witnesses of the same challenge and sighash type, in which case
+    /// their signatures are combined, or if the witnesses are identical.
+    pub fn add_witness(
+        &mut self,
+        input_index: usize,
+        witness: InputWitness,
+    ) -> Result<(), PartiallySignedTransactionError> {
+        let inputs_count = self.witnesses.len();
+        let current = self.witnesses.get_mut(input_index).ok_or(
+            PartiallySignedTransactionError::InvalidInputIndex(input_index, inputs_count),
+        )?;
+
+        let combined = match current.as_ref() {
+            None => witness,
+            Some(existing) if *existing == witness => witness,
+            Some(existing) => combine_multisig_witnesses(input_index, existing, witness)?,
+        };
+        *current = Some(combined);
+
+        Ok(())
+    }
+
+    /// Produce the signed transaction once every input has a witness.
+    ///
+    /// The witnesses of the inputs whose destinations are known are verified, so an incomplete
+    /// classical multisig witness is rejected.
+    pub fn finalize(
+        self,
+        chain_config: &ChainConfig,
+    ) -> Result<SignedTransaction, PartiallySignedTransactionError> {
+        let witnesses = self
+            .witnesses
+            .into_iter()
+            .enumerate()
+            .map(|(index, witness)| {
+                witness.ok_or(PartiallySignedTransactionError::MissingWitness(index))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let signed_tx = SignedTransaction::new(self.tx, witnesses)?;
+
+        let input_utxos: Vec<_> = self.input_utxos.iter().map(Option::as_ref).collect();
+        for (index, destination) in self.destinations.iter().enumerate() {
+            if let Some(destination) = destination {
+                verify_signature(chain_config, destination, &signed_tx, &input_utxos, index)
+                    .map_err(|e| PartiallySignedTransactionError::InvalidWitness(index, e))?;
+            }
+        }
+
+        Ok(signed_tx)
+    }
+}
+
+fn decode_multisig_witness(
+    witness: &InputWitness,
+) -> Option<(&StandardInputSignature, AuthorizedClassicalMultisigSpend)> {
+    match witness {
+        InputWitness::NoSignature(_) => None,
+        InputWitness::Standard(sig) => {
+            let authorization =
+                AuthorizedClassicalMultisigSpend::decode_all(&mut sig.raw_signature()).ok()?;
+            Some((sig, authorization))
+        }
+    }
+}
+
+fn combine_multisig_witnesses(
+    input_index: usize,
+    existing: &InputWitness,
+    new: InputWitness,
+) -> Result<InputWitness, PartiallySignedTransactionError> {
+    let conflict = PartiallySignedTransactionError::ConflictingWitness(input_index);
+
+    let (existing_sig, mut authorization) =
+        decode_multisig_witness(existing).ok_or(conflict.clone())?;
+    let (new_sig, new_authorization) = decode_multisig_witness(&new).ok_or(conflict.clone())?;
+    ensure!(
+        existing_sig.sighash_type() == new_sig.sighash_type(),
+        conflict
+    );
+
+    authorization
+        .combine(new_authorization)
+        .map_err(|e| PartiallySignedTransactionError::MultisigCombineFailed(input_index, e))?;
+
+    Ok(InputWitness::Standard(StandardInputSignature::new(
+        existing_sig.sighash_type(),
+        authorization.encode(),
+    )))
+}
+
+impl Decode for PartiallySignedTransaction {
+    fn decode<I: serialization::Input>(input: &mut I) -> Result<Self, serialization::Error> {
+        let tx = Transaction::decode(input)?;
+        let witnesses = Vec::<Option<InputWitness>>::decode(input)?;
+        let input_utxos = Vec::<Option<TxOutput>>::decode(input)?;
+        let destinations = Vec::<Option<Destination>>::decode(input)?;
+        Self::new(tx, witnesses, input_utxos, destinations).map_err(|e| {
+            serialization::Error::from("Invalid partially signed transaction").chain(e.to_string())
+        })
+    }
+}
+
+impl serde::Serialize for PartiallySignedTransaction {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        HexEncoded::new(self).serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PartiallySignedTransaction {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HexEncoded::<Self>::deserialize(deserializer).map(HexEncoded::take)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU8;
+
+    use crypto::key::{KeyKind, PrivateKey};
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    use super::*;
+    use crate::{
+        address::pubkeyhash::PublicKeyHash,
+        chain::{
+            classic_multisig::ClassicMultisigChallenge,
+            config::create_mainnet,
+            output_value::OutputValue,
+            signature::{
+                inputsig::classical_multisig::authorize_classical_multisig::{
+                    sign_classical_multisig_spending, ClassicalMultisigCompletionStatus,
+                },
+                sighash::{sighashtype::SigHashType, signature_hash},
+            },
+            TxInput,
+        },
+        primitives::{Amount, Id, H256},
+    };
+
+    // A 2-of-2 multisig spending transaction and the keys of both parties
+    fn make_multisig_psbt(
+        rng: &mut impl test_utils::random::Rng,
+        chain_config: &ChainConfig,
+    ) -> (
+        PartiallySignedTransaction,
+        ClassicMultisigChallenge,
+        Vec<PrivateKey>,
+    ) {
+        let (priv_keys, pub_keys): (Vec<_>, Vec<_>) =
+            (0..2).map(|_| PrivateKey::new_from_rng(rng, KeyKind::Secp256k1Schnorr)).unzip();
+        let challenge =
+            ClassicMultisigChallenge::new(chain_config, NonZeroU8::new(2).unwrap(), pub_keys)
+                .unwrap();
+        let destination = Destination::ClassicMultisig(PublicKeyHash::from(&challenge));
+
+        let input_utxo = TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(rng.gen_range(100..1000))),
+            destination.clone(),
+        );
+        let tx = Transaction::new(
+            0,
+            vec![TxInput::from_utxo(Id::<Transaction>::new(H256::random_using(rng)).into(), 0)],
+            vec![TxOutput::Transfer(
+                OutputValue::Coin(Amount::from_atoms(50)),
+                Destination::AnyoneCanSpend,
+            )],
+        )
+        .unwrap();
+
+        let psbt = PartiallySignedTransaction::new_unsigned(
+            tx,
+            vec![Some(input_utxo)],
+            vec![Some(destination)],
+        )
+        .unwrap();
+
+        (psbt, challenge, priv_keys)
+    }
+
+    // Sign the only input of the transaction with one of the multisig keys
+    fn sign_multisig(
+        chain_config: &ChainConfig,
+        psbt: &PartiallySignedTransaction,
+        challenge: &ClassicMultisigChallenge,
+        key_index: u8,
+        private_key: &PrivateKey,
+    ) -> InputWitness {
+        let sighash_type = SigHashType::default();
+        let sighash = signature_hash(sighash_type, psbt.tx(), &psbt.input_utxos_refs(), 0).unwrap();
+        let status = sign_classical_multisig_spending(
+            chain_config,
+            key_index,
+            private_key,
+            challenge,
+            &sighash,
+            AuthorizedClassicalMultisigSpend::new_empty(challenge.clone()),
+        )
+        .unwrap();
+        assert!(matches!(
+            status,
+            ClassicalMultisigCompletionStatus::Incomplete(_)
+        ));
+
+        InputWitness::Standard(StandardInputSignature::new(
+            sighash_type,
+            status.take().encode(),
+        ))
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn two_party_signing(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = create_mainnet();
+
+        let (psbt, challenge, priv_keys) = make_multisig_psbt(&mut rng, &chain_config);
+
+        // Each party receives the transaction in the serialized form and signs it independently
+        let hex = serde_json::to_string(&psbt).unwrap();
+        let mut psbt1: PartiallySignedTransaction = serde_json::from_str(&hex).unwrap();
+        let mut psbt2: PartiallySignedTransaction = serde_json::from_str(&hex).unwrap();
+        assert_eq!(psbt1, psbt);
+
+        let witness1 = sign_multisig(&chain_config, &psbt1, &challenge, 0, &priv_keys[0]);
+        psbt1.add_witness(0, witness1).unwrap();
+        let witness2 = sign_multisig(&chain_config, &psbt2, &challenge, 1, &priv_keys[1]);
+        psbt2.add_witness(0, witness2.clone()).unwrap();
+
+        // A single signature isn't enough
+        assert!(psbt1.is_fully_signed());
+        assert_eq!(
+            psbt1.clone().finalize(&chain_config),
+            Err(PartiallySignedTransactionError::InvalidWitness(
+                0,
+                TransactionSigError::IncompleteClassicalMultisigSignature
+            ))
+        );
+
+        // Adding the same witness again changes nothing
+        let mut psbt2_again = psbt2.clone();
+        psbt2_again.add_witness(0, witness2).unwrap();
+        assert_eq!(psbt2_again, psbt2);
+
+        // Combine the signatures of both parties
+        let witness2 = psbt2.witnesses()[0].clone().unwrap();
+        psbt1.add_witness(0, witness2).unwrap();
+
+        let signed_tx = psbt1.finalize(&chain_config).unwrap();
+        assert_eq!(signed_tx.transaction(), psbt.tx());
+        let input_utxos = psbt.input_utxos_refs();
+        verify_signature(
+            &chain_config,
+            psbt.destinations()[0].as_ref().unwrap(),
+            &signed_tx,
+            &input_utxos,
+            0,
+        )
+        .unwrap();
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn missing_and_conflicting_witnesses(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = create_mainnet();
+
+        let (mut psbt, challenge, priv_keys) = make_multisig_psbt(&mut rng, &chain_config);
+
+        assert!(!psbt.is_fully_signed());
+        assert_eq!(
+            psbt.clone().finalize(&chain_config),
+            Err(PartiallySignedTransactionError::MissingWitness(0))
+        );
+
+        assert_eq!(
+            psbt.add_witness(1, InputWitness::NoSignature(None)),
+            Err(PartiallySignedTransactionError::InvalidInputIndex(1, 1))
+        );
+
+        let witness = sign_multisig(&chain_config, &psbt, &challenge, 0, &priv_keys[0]);
+        psbt.add_witness(0, witness).unwrap();
+        assert_eq!(
+            psbt.add_witness(0, InputWitness::NoSignature(None)),
+            Err(PartiallySignedTransactionError::ConflictingWitness(0))
+        );
+    }
+
+    #[test]
+    fn decode_invalid_counts() {
+        let tx = Transaction::new(
+            0,
+            vec![TxInput::from_utxo(Id::<Transaction>::new(H256::zero()).into(), 0)],
+            vec![],
+        )
+        .unwrap();
+
+        assert_eq!(
+            PartiallySignedTransaction::new(tx.clone(), vec![], vec![None], vec![None]),
+            Err(PartiallySignedTransactionError::InvalidWitnessCount(0, 1))
+        );
+
+        // Encode the fields directly to bypass the checks of the constructor
+        let encoded = (
+            tx,
+            Vec::<Option<InputWitness>>::new(),
+            vec![None::<TxOutput>],
+            vec![None::<Destination>],
+        )
+            .encode();
+        assert!(PartiallySignedTransaction::decode_all(&mut encoded.as_slice()).is_err());
+    }
+}
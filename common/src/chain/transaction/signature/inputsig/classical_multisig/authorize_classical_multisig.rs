@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crypto::key::Signature;
 use serialization::{Decode, DecodeAll, Encode};
@@ -23,7 +23,10 @@ use crate::{
     chain::{
         classic_multisig::{ClassicMultisigChallenge, ClassicMultisigChallengeError},
         signature::{
-            inputsig::classical_multisig::multisig_partial_signature::PartiallySignedMultisigChallenge,
+            inputsig::{
+                classical_multisig::multisig_partial_signature::PartiallySignedMultisigChallenge,
+                InputWitness,
+            },
             TransactionSigError,
         },
         ChainConfig,
@@ -113,6 +116,28 @@ impl AuthorizedClassicalMultisigSpend {
             challenge,
         }
     }
+
+    /// Add the signatures of another authorization of the same challenge, e.g. one that was
+    /// produced independently by another party. Signatures present in both must be identical.
+    pub fn combine(&mut self, other: Self) -> Result<(), ClassicalMultisigSigningError> {
+        if self.challenge != other.challenge {
+            return Err(ClassicalMultisigSigningError::AttemptedToCombineDifferentChallenges);
+        }
+
+        for (index, signature) in other.signatures {
+            match self.signatures.get(&index) {
+                Some(existing) if *existing != signature => {
+                    return Err(
+                        ClassicalMultisigSigningError::ClassicalMultisigIndexAlreadyExists(index),
+                    )
+                }
+                Some(_) => {}
+                None => self.add_signature(index, signature),
+            }
+        }
+
+        Ok(())
+    }
 }
 
 pub fn verify_classical_multisig_spending(
@@ -142,12 +167,110 @@ pub fn verify_classical_multisig_spending(
     }
 }
 
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ClassicalMultisigCheckError {
+    #[error("The input witness has no signature")]
+    NoSignature,
+    #[error("Invalid classical multisig signature encoding")]
+    InvalidSignatureEncoding,
+    #[error("The signatures were made for a different multisig challenge")]
+    ChallengeMismatch,
+    #[error("Public keys with indices {0} and {1} are the same and both have signatures")]
+    DuplicateSignature(u8, u8),
+    #[error("Invalid classical multisig authorization: {0}")]
+    InvalidClassicalMultisig(#[from] PartiallySignedMultisigStructureError),
+}
+
+/// The signatures of a classical multisig witness that do and don't match their public keys,
+/// identified by the indices of the public keys in the challenge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassicalMultisigSignaturesCheck {
+    matched: BTreeSet<u8>,
+    mismatched: BTreeSet<u8>,
+    min_required_signatures: u8,
+}
+
+impl ClassicalMultisigSignaturesCheck {
+    pub fn matched(&self) -> &BTreeSet<u8> {
+        &self.matched
+    }
+
+    pub fn mismatched(&self) -> &BTreeSet<u8> {
+        &self.mismatched
+    }
+
+    /// The number of valid signatures still needed to reach the threshold of the challenge
+    pub fn missing_signatures_count(&self) -> usize {
+        (self.min_required_signatures as usize).saturating_sub(self.matched.len())
+    }
+
+    /// Whether all the signatures are valid and there are enough of them to spend
+    pub fn is_complete(&self) -> bool {
+        self.mismatched.is_empty() && self.missing_signatures_count() == 0
+    }
+}
+
+/// Check the signatures of a possibly partially signed classical multisig witness against the
+/// declared challenge and report which of them match their public keys. Unlike
+/// [verify_classical_multisig_spending], this doesn't fail on incomplete or invalid signatures,
+/// so it can be used to give feedback while the signatures are being collected.
+/// Signatures by the same public key appearing more than once in the challenge are rejected,
+/// because they would let a single party count towards the threshold multiple times.
+pub fn check_classical_multisig_signatures(
+    chain_config: &ChainConfig,
+    challenge: &ClassicMultisigChallenge,
+    witness: &InputWitness,
+    sighash: &H256,
+) -> Result<ClassicalMultisigSignaturesCheck, ClassicalMultisigCheckError> {
+    let raw_signature = match witness {
+        InputWitness::NoSignature(_) => return Err(ClassicalMultisigCheckError::NoSignature),
+        InputWitness::Standard(sig) => sig.raw_signature(),
+    };
+    let authorization = AuthorizedClassicalMultisigSpend::decode_all(&mut &raw_signature[..])
+        .map_err(|_| ClassicalMultisigCheckError::InvalidSignatureEncoding)?;
+
+    if authorization.challenge() != challenge {
+        return Err(ClassicalMultisigCheckError::ChallengeMismatch);
+    }
+
+    let msg = sighash.encode();
+    // Checks the challenge, the signature indices and the threshold
+    PartiallySignedMultisigChallenge::from_partial(chain_config, &msg, &authorization)?;
+
+    let mut signers = BTreeMap::new();
+    let mut matched = BTreeSet::new();
+    let mut mismatched = BTreeSet::new();
+    for (index, signature) in authorization.iter() {
+        let public_key = &challenge.public_keys()[index as usize];
+        if let Some(other_index) = signers.insert(public_key, index) {
+            return Err(ClassicalMultisigCheckError::DuplicateSignature(
+                other_index,
+                index,
+            ));
+        }
+
+        if public_key.verify_message(signature, &msg) {
+            matched.insert(index);
+        } else {
+            mismatched.insert(index);
+        }
+    }
+
+    Ok(ClassicalMultisigSignaturesCheck {
+        matched,
+        mismatched,
+        min_required_signatures: challenge.min_required_signatures(),
+    })
+}
+
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum ClassicalMultisigSigningError {
     #[error("Attempted to sign a classical multisig with invalid challenge")]
     AttemptedToSignClassicalMultisigWithInvalidChallenge(ClassicMultisigChallengeError),
     #[error("Attempted to add a classical multisig with an index that already exists")]
     ClassicalMultisigIndexAlreadyExists(u8),
+    #[error("Attempted to combine classical multisig signatures made for different challenges")]
+    AttemptedToCombineDifferentChallenges,
     #[error("Attempted to sign a classical multisig that is already complete")]
     AttemptedToSignAlreadyCompleteClassicalMultisig,
     #[error("Invalid classical multisig key index: {0} (must be in range 0..{1})")]
@@ -928,4 +1051,196 @@ mod tests {
             );
         }
     }
+
+    fn make_multisig_witness(authorization: &AuthorizedClassicalMultisigSpend) -> InputWitness {
+        use crate::chain::signature::{
+            inputsig::standard_signature::StandardInputSignature, sighash::sighashtype::SigHashType,
+        };
+
+        let sighash_type = SigHashType::try_from(SigHashType::ALL).unwrap();
+        InputWitness::Standard(StandardInputSignature::new(
+            sighash_type,
+            authorization.encode(),
+        ))
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn check_signatures(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let chain_config = create_mainnet();
+        let min_required_signatures = (rng.gen::<u8>() % 10) + 2;
+        let total_parties = (rng.gen::<u8>() % 5) + min_required_signatures;
+        let (priv_keys, pub_keys): (Vec<_>, Vec<_>) = (0..total_parties)
+            .map(|_| PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr))
+            .unzip();
+        let challenge = ClassicMultisigChallenge::new(
+            &chain_config,
+            min_required_signatures.try_into().unwrap(),
+            pub_keys,
+        )
+        .unwrap();
+
+        let sighash = H256::random_using(&mut rng);
+        let msg = sighash.encode();
+
+        let mut indices: Vec<u8> = (0..total_parties).collect();
+        indices.shuffle(&mut rng);
+        let signed_indices = &indices[..min_required_signatures as usize];
+
+        // Valid M-of-N signatures
+        let signatures = signed_indices
+            .iter()
+            .map(|&i| (i, priv_keys[i as usize].sign_message(&msg).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        let authorization = AuthorizedClassicalMultisigSpend::new(signatures, challenge.clone());
+        let check = check_classical_multisig_signatures(
+            &chain_config,
+            &challenge,
+            &make_multisig_witness(&authorization),
+            &sighash,
+        )
+        .unwrap();
+        assert!(check.is_complete());
+        assert_eq!(
+            check.matched(),
+            &signed_indices.iter().copied().collect::<BTreeSet<_>>()
+        );
+        assert!(check.mismatched().is_empty());
+
+        // Insufficient signatures
+        let signatures_count = rng.gen_range(0..min_required_signatures as usize);
+        let signatures = signed_indices[..signatures_count]
+            .iter()
+            .map(|&i| (i, priv_keys[i as usize].sign_message(&msg).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        let authorization = AuthorizedClassicalMultisigSpend::new(signatures, challenge.clone());
+        let check = check_classical_multisig_signatures(
+            &chain_config,
+            &challenge,
+            &make_multisig_witness(&authorization),
+            &sighash,
+        )
+        .unwrap();
+        assert!(!check.is_complete());
+        assert_eq!(check.matched().len(), signatures_count);
+        assert_eq!(
+            check.missing_signatures_count(),
+            min_required_signatures as usize - signatures_count
+        );
+
+        // A signature made with a key different from the one at its index
+        let wrong_index = signed_indices[0];
+        let other_index = indices[min_required_signatures as usize..]
+            .first()
+            .copied()
+            .unwrap_or(signed_indices[1]);
+        let mut signatures = signed_indices
+            .iter()
+            .map(|&i| (i, priv_keys[i as usize].sign_message(&msg).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        signatures.insert(
+            wrong_index,
+            priv_keys[other_index as usize].sign_message(&msg).unwrap(),
+        );
+        let authorization = AuthorizedClassicalMultisigSpend::new(signatures, challenge.clone());
+        let check = check_classical_multisig_signatures(
+            &chain_config,
+            &challenge,
+            &make_multisig_witness(&authorization),
+            &sighash,
+        )
+        .unwrap();
+        assert!(!check.is_complete());
+        assert_eq!(check.mismatched(), &BTreeSet::from([wrong_index]));
+        assert_eq!(check.matched().len(), min_required_signatures as usize - 1);
+
+        // More signatures than the threshold
+        if total_parties > min_required_signatures {
+            let signatures = (0..=min_required_signatures)
+                .map(|i| (i, priv_keys[i as usize].sign_message(&msg).unwrap()))
+                .collect::<BTreeMap<_, _>>();
+            let authorization =
+                AuthorizedClassicalMultisigSpend::new(signatures, challenge.clone());
+            assert_eq!(
+                check_classical_multisig_signatures(
+                    &chain_config,
+                    &challenge,
+                    &make_multisig_witness(&authorization),
+                    &sighash,
+                ),
+                Err(ClassicalMultisigCheckError::InvalidClassicalMultisig(
+                    PartiallySignedMultisigStructureError::Overconstrained(
+                        min_required_signatures as usize + 1,
+                        min_required_signatures as usize
+                    )
+                ))
+            );
+        }
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn check_signatures_duplicate_key(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let chain_config = create_mainnet();
+        let (priv_key, pub_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+        let (_, other_pub_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+        let challenge = ClassicMultisigChallenge::new(
+            &chain_config,
+            NonZeroU8::new(2).unwrap(),
+            vec![pub_key.clone(), other_pub_key, pub_key],
+        )
+        .unwrap();
+
+        let sighash = H256::random_using(&mut rng);
+        let signature = priv_key.sign_message(&sighash.encode()).unwrap();
+
+        // The same party signs twice to reach the threshold on its own
+        let authorization = AuthorizedClassicalMultisigSpend::new(
+            BTreeMap::from([(0, signature.clone()), (2, signature)]),
+            challenge.clone(),
+        );
+        assert_eq!(
+            check_classical_multisig_signatures(
+                &chain_config,
+                &challenge,
+                &make_multisig_witness(&authorization),
+                &sighash,
+            ),
+            Err(ClassicalMultisigCheckError::DuplicateSignature(0, 2))
+        );
+
+        // The signatures must be for the declared challenge
+        let (_, another_pub_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+        let other_challenge = ClassicMultisigChallenge::new(
+            &chain_config,
+            NonZeroU8::new(1).unwrap(),
+            vec![another_pub_key],
+        )
+        .unwrap();
+        assert_eq!(
+            check_classical_multisig_signatures(
+                &chain_config,
+                &other_challenge,
+                &make_multisig_witness(&authorization),
+                &sighash,
+            ),
+            Err(ClassicalMultisigCheckError::ChallengeMismatch)
+        );
+
+        assert_eq!(
+            check_classical_multisig_signatures(
+                &chain_config,
+                &challenge,
+                &InputWitness::NoSignature(None),
+                &sighash,
+            ),
+            Err(ClassicalMultisigCheckError::NoSignature)
+        );
+    }
 }
@@ -13,9 +13,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::chain::{
-    classic_multisig::{ClassicMultisigChallenge, ClassicMultisigChallengeError},
-    ChainConfig,
+use crypto::key::{PrivateKey, Signature};
+use serialization::{Decode, Encode};
+
+use crate::{
+    chain::{
+        classic_multisig::{ClassicMultisigChallenge, ClassicMultisigChallengeError},
+        signature::{
+            inputsig::standard_signature::StandardInputSignature, sighash::sighashtype::SigHashType,
+        },
+        ChainConfig,
+    },
+    primitives::H256,
 };
 
 use super::authorize_classical_multisig::AuthorizedClassicalMultisigSpend;
@@ -146,6 +155,156 @@ pub enum SigsVerifyResult {
     Invalid,
 }
 
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum PartialMultisigSignatureError {
+    #[error("The challenge is structurally invalid and can never be used for signing: {0}")]
+    InvalidChallenge(#[from] ClassicMultisigChallengeError),
+    #[error("There is no public key with index {0} in the challenge")]
+    InvalidSignatureIndex(u8),
+    #[error("The signature for public key with index {0} is invalid")]
+    InvalidSignature(u8),
+    #[error("A different signature for public key with index {0} has already been added")]
+    ConflictingSignature(u8),
+    #[error("The partial signatures are for a different challenge or message")]
+    Mismatch,
+    #[error("Signing with the private key failed")]
+    SigningFailed,
+    #[error("Not enough signatures to finalize: {0} < {1}")]
+    Incomplete(usize, usize),
+}
+
+/// A classical multisig signature being collected from the parties of a challenge.
+///
+/// It can be encoded and passed between the signers, each of them adding their signature
+/// or merging the signatures collected by others, until the threshold of the challenge is
+/// reached and the final input signature can be produced with [PartialMultisigSignature::finalize].
+/// Every added signature is verified against the message, so an invalid contribution is
+/// rejected by the party receiving it rather than when the transaction is validated.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct PartialMultisigSignature {
+    sighash_type: SigHashType,
+    /// The signature hash of the input being spent, which is the message signed by each party.
+    sighash: H256,
+    signatures: AuthorizedClassicalMultisigSpend,
+}
+
+impl PartialMultisigSignature {
+    pub fn new(
+        chain_config: &ChainConfig,
+        challenge: ClassicMultisigChallenge,
+        sighash_type: SigHashType,
+        sighash: H256,
+    ) -> Result<Self, PartialMultisigSignatureError> {
+        challenge.is_valid(chain_config)?;
+
+        Ok(Self {
+            sighash_type,
+            sighash,
+            signatures: AuthorizedClassicalMultisigSpend::new_empty(challenge),
+        })
+    }
+
+    pub fn sighash_type(&self) -> SigHashType {
+        self.sighash_type
+    }
+
+    pub fn sighash(&self) -> &H256 {
+        &self.sighash
+    }
+
+    pub fn challenge(&self) -> &ClassicMultisigChallenge {
+        self.signatures.challenge()
+    }
+
+    pub fn signatures(&self) -> &AuthorizedClassicalMultisigSpend {
+        &self.signatures
+    }
+
+    /// Add the signature of the party whose public key has the given index in the challenge.
+    /// Adding the same signature again has no effect.
+    pub fn add_signature(
+        &mut self,
+        key_index: u8,
+        signature: Signature,
+    ) -> Result<(), PartialMultisigSignatureError> {
+        let public_key = self.challenge().public_keys().get(key_index as usize).ok_or(
+            PartialMultisigSignatureError::InvalidSignatureIndex(key_index),
+        )?;
+
+        if let Some(existing) = self.signatures.signatures().get(&key_index) {
+            return if *existing == signature {
+                Ok(())
+            } else {
+                Err(PartialMultisigSignatureError::ConflictingSignature(
+                    key_index,
+                ))
+            };
+        }
+
+        if !public_key.verify_message(&signature, &self.sighash.encode()) {
+            return Err(PartialMultisigSignatureError::InvalidSignature(key_index));
+        }
+
+        self.signatures.add_signature(key_index, signature);
+        Ok(())
+    }
+
+    /// Sign the message with the private key of the party whose public key has the given index
+    pub fn sign(
+        &mut self,
+        key_index: u8,
+        private_key: &PrivateKey,
+    ) -> Result<(), PartialMultisigSignatureError> {
+        let signature = private_key
+            .sign_message(&self.sighash.encode())
+            .map_err(|_| PartialMultisigSignatureError::SigningFailed)?;
+        self.add_signature(key_index, signature)
+    }
+
+    /// Add the signatures collected by another party for the same challenge and message
+    pub fn merge(&mut self, other: &Self) -> Result<(), PartialMultisigSignatureError> {
+        if self.sighash_type != other.sighash_type
+            || self.sighash != other.sighash
+            || self.challenge() != other.challenge()
+        {
+            return Err(PartialMultisigSignatureError::Mismatch);
+        }
+
+        other
+            .signatures
+            .iter()
+            .try_for_each(|(key_index, signature)| self.add_signature(key_index, signature.clone()))
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.signatures.available_signatures_count()
+            >= self.challenge().min_required_signatures() as usize
+    }
+
+    /// Produce the input signature spending the multisig output.
+    /// If more signatures than required have been collected, only those with the lowest
+    /// public key indices are used, because a multisig witness can't be overconstrained.
+    pub fn finalize(self) -> Result<StandardInputSignature, PartialMultisigSignatureError> {
+        let min_required_signatures = self.challenge().min_required_signatures() as usize;
+        let available_signatures_count = self.signatures.available_signatures_count();
+        if available_signatures_count < min_required_signatures {
+            return Err(PartialMultisigSignatureError::Incomplete(
+                available_signatures_count,
+                min_required_signatures,
+            ));
+        }
+
+        let challenge = self.signatures.challenge().clone();
+        let signatures = self.signatures.take().into_iter().take(min_required_signatures).collect();
+        let authorization = AuthorizedClassicalMultisigSpend::new(signatures, challenge);
+
+        Ok(StandardInputSignature::new(
+            self.sighash_type,
+            authorization.encode(),
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -438,4 +597,130 @@ mod tests {
             ClassicMultisigChallengeError::MinRequiredSignaturesIsZero,
         );
     }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn partial_signature_encode_decode(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let data = TestChallengeData::new_random(&mut rng);
+
+        let mut partial = PartialMultisigSignature::new(
+            &data.chain_config,
+            data.challenge.clone(),
+            SigHashType::try_from(SigHashType::ALL).unwrap(),
+            H256::decode_all(&mut data.message_bytes.as_slice()).unwrap(),
+        )
+        .unwrap();
+
+        let sig_count = rng.gen_range(0..=data.priv_keys.len());
+        for (index, signature) in data.signatures_map.iter().take(sig_count) {
+            partial.add_signature(*index, signature.clone()).unwrap();
+        }
+
+        let encoded = partial.encode();
+        let decoded = PartialMultisigSignature::decode_all(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, partial);
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn partial_signature_three_parties(#[case] seed: Seed) {
+        use crate::chain::signature::inputsig::classical_multisig::authorize_classical_multisig::verify_classical_multisig_spending;
+
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = create_mainnet();
+
+        let (priv_keys, pub_keys): (Vec<_>, Vec<_>) = (0..3)
+            .map(|_| PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr))
+            .unzip();
+        let challenge =
+            ClassicMultisigChallenge::new(&chain_config, NonZeroU8::new(2).unwrap(), pub_keys)
+                .unwrap();
+        let sighash = H256::random_using(&mut rng);
+        let sighash_type = SigHashType::try_from(SigHashType::ALL).unwrap();
+
+        // The coordinator creates the partial signature and passes it to the first signer
+        let partial =
+            PartialMultisigSignature::new(&chain_config, challenge.clone(), sighash_type, sighash)
+                .unwrap();
+        let mut first =
+            PartialMultisigSignature::decode_all(&mut partial.encode().as_slice()).unwrap();
+        first.sign(2, &priv_keys[2]).unwrap();
+        assert!(!first.is_complete());
+        assert_eq!(
+            first.clone().finalize().unwrap_err(),
+            PartialMultisigSignatureError::Incomplete(1, 2)
+        );
+
+        // Signing with the wrong key or a different signature for the same key is rejected
+        assert_eq!(
+            first.sign(1, &priv_keys[0]).unwrap_err(),
+            PartialMultisigSignatureError::InvalidSignature(1)
+        );
+        let conflicting_signature =
+            priv_keys[2].sign_message(&H256::random_using(&mut rng).encode()).unwrap();
+        assert_eq!(
+            first.add_signature(2, conflicting_signature.clone()).unwrap_err(),
+            PartialMultisigSignatureError::ConflictingSignature(2)
+        );
+        assert_eq!(
+            first.sign(3, &priv_keys[2]).unwrap_err(),
+            PartialMultisigSignatureError::InvalidSignatureIndex(3)
+        );
+
+        // The second signer works independently on another copy
+        let mut second =
+            PartialMultisigSignature::decode_all(&mut partial.encode().as_slice()).unwrap();
+        second.sign(0, &priv_keys[0]).unwrap();
+
+        // Merging the same signatures again has no effect
+        let mut merged =
+            PartialMultisigSignature::decode_all(&mut first.encode().as_slice()).unwrap();
+        merged.merge(&second).unwrap();
+        merged.merge(&first).unwrap();
+        assert!(merged.is_complete());
+        assert_eq!(merged.signatures().available_signatures_count(), 2);
+
+        // A conflicting contribution from a third party is rejected
+        let mut conflicting = partial.clone();
+        conflicting.signatures.add_signature(2, conflicting_signature);
+        assert_eq!(
+            merged.clone().merge(&conflicting).unwrap_err(),
+            PartialMultisigSignatureError::ConflictingSignature(2)
+        );
+
+        // The third party can't merge signatures for a different message
+        let mut third = partial.clone();
+        let other = PartialMultisigSignature::new(
+            &chain_config,
+            challenge.clone(),
+            sighash_type,
+            H256::random_using(&mut rng),
+        )
+        .unwrap();
+        assert_eq!(
+            third.merge(&other).unwrap_err(),
+            PartialMultisigSignatureError::Mismatch
+        );
+
+        // Extra signatures are dropped on finalization
+        third.sign(1, &priv_keys[1]).unwrap();
+        merged.add_signature(1, third.signatures().signatures()[&1].clone()).unwrap();
+        assert_eq!(merged.signatures().available_signatures_count(), 3);
+
+        let signature = merged.finalize().unwrap();
+        assert_eq!(signature.sighash_type(), sighash_type);
+        let authorization =
+            AuthorizedClassicalMultisigSpend::from_data(signature.raw_signature()).unwrap();
+        assert_eq!(authorization.available_signatures_count(), 2);
+        verify_classical_multisig_spending(
+            &chain_config,
+            &(&challenge).into(),
+            &authorization,
+            &sighash,
+        )
+        .unwrap();
+    }
 }
@@ -35,3 +35,60 @@ impl serde::Serialize for InputWitness {
         HexEncoded::new(self).serialize(serializer)
     }
 }
+
+impl<'de> serde::Deserialize<'de> for InputWitness {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HexEncoded::<Self>::deserialize(deserializer).map(HexEncoded::take)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chain::signature::sighash::sighashtype::SigHashType;
+    use rstest::rstest;
+    use test_utils::random::{Rng, Seed};
+
+    fn random_bytes(rng: &mut impl Rng) -> Vec<u8> {
+        let len = rng.gen_range(0..100);
+        (0..len).map(|_| rng.gen()).collect()
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn serde_roundtrip(#[case] seed: Seed) {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let witnesses = [
+            InputWitness::NoSignature(None),
+            InputWitness::NoSignature(Some(random_bytes(&mut rng))),
+            InputWitness::Standard(StandardInputSignature::new(
+                SigHashType::default(),
+                random_bytes(&mut rng),
+            )),
+        ];
+
+        for witness in witnesses {
+            let json = serde_json::to_string(&witness).unwrap();
+            assert_eq!(json, format!("\"{}\"", HexEncoded::new(&witness)));
+            let decoded: InputWitness = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, witness);
+        }
+    }
+
+    #[test]
+    fn deserialize_malformed() {
+        // Not a hex string
+        assert!(serde_json::from_str::<InputWitness>("\"xyz\"").is_err());
+        // Unknown variant index
+        assert!(serde_json::from_str::<InputWitness>("\"05\"").is_err());
+        // Trailing data
+        let mut encoded = InputWitness::NoSignature(None).encode();
+        encoded.push(0);
+        let json = format!("\"{}\"", hex::encode(encoded));
+        assert!(serde_json::from_str::<InputWitness>(&json).is_err());
+        // Not a string
+        assert!(serde_json::from_str::<InputWitness>("0").is_err());
+    }
+}
@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use serialization::Encode;
+use serialization::{Decode, Encode};
 
 use super::TransactionSigError;
 
@@ -65,6 +65,15 @@ impl TryFrom<u8> for SigHashType {
     }
 }
 
+impl Decode for SigHashType {
+    fn decode<I: serialization::Input>(input: &mut I) -> Result<Self, serialization::Error> {
+        input
+            .read_byte()?
+            .try_into()
+            .map_err(|_| serialization::Error::from("Invalid sighash byte"))
+    }
+}
+
 /// How inputs should be hashed
 #[derive(PartialEq, Eq, Debug)]
 pub enum InputsMode {
@@ -0,0 +1,150 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crypto::key::{KeyKind, PrivateKey};
+use crypto::random::{CryptoRng, Rng};
+use rstest::rstest;
+use test_utils::random::Seed;
+
+use super::utils::*;
+use crate::{
+    chain::{
+        config::create_mainnet,
+        signature::{sighash::sighashtype::SigHashType, verify_signature, TransactionSigError},
+        signed_transaction::SignedTransaction,
+        AccountNonce, AccountOutPoint, AccountSpending, Destination, TxInput, TxOutput,
+    },
+    primitives::Amount,
+};
+
+// Sign a transaction whose first input spends from an account
+fn sign_tx_with_account_input(
+    rng: &mut (impl Rng + CryptoRng),
+    private_key: &PrivateKey,
+    destination: &Destination,
+    sighash_type: SigHashType,
+) -> (SignedTransaction, Vec<Option<TxOutput>>) {
+    let (mut inputs_utxos, _priv_keys) = generate_inputs_utxos(rng, 3);
+    inputs_utxos[0] = None;
+    let inputs_utxos_refs = inputs_utxos.iter().map(|utxo| utxo.as_ref()).collect::<Vec<_>>();
+
+    let tx = generate_unsigned_tx(rng, destination, &inputs_utxos, 3).unwrap();
+    let signed_tx = sign_whole_tx(
+        tx,
+        &inputs_utxos_refs,
+        private_key,
+        sighash_type,
+        destination,
+    )
+    .unwrap();
+
+    (signed_tx, inputs_utxos)
+}
+
+fn replace_first_account_input(
+    tx: &SignedTransaction,
+    mutate: impl FnOnce(AccountNonce, AccountSpending) -> (AccountNonce, AccountSpending),
+) -> SignedTransaction {
+    let mut updater = MutableTransaction::from(tx);
+    let outpoint = match &updater.inputs[0] {
+        TxInput::Account(outpoint) => outpoint.clone(),
+        TxInput::Utxo(_) => panic!("expected an account input"),
+    };
+    let (nonce, account) = mutate(outpoint.nonce(), *outpoint.account());
+    updater.inputs[0] = TxInput::Account(AccountOutPoint::new(nonce, account));
+    updater.generate_tx().unwrap()
+}
+
+// Changing the withdrawn amount of a signed account input must invalidate its signature
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn tampered_account_amount(#[case] seed: Seed) {
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+    let chain_config = create_mainnet();
+
+    let (private_key, public_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+    let destination = Destination::PublicKey(public_key);
+
+    for sighash_type in sig_hash_types() {
+        let (tx, inputs_utxos) =
+            sign_tx_with_account_input(&mut rng, &private_key, &destination, sighash_type);
+        let inputs_utxos_refs = inputs_utxos.iter().map(|utxo| utxo.as_ref()).collect::<Vec<_>>();
+        assert_eq!(
+            verify_signature(&chain_config, &destination, &tx, &inputs_utxos_refs, 0),
+            Ok(())
+        );
+
+        let tampered_tx = replace_first_account_input(&tx, |nonce, account| {
+            let AccountSpending::Delegation(delegation_id, amount) = account;
+            let tampered_amount = loop {
+                let new_amount = Amount::from_atoms(rng.gen());
+                if new_amount != amount {
+                    break new_amount;
+                }
+            };
+            (
+                nonce,
+                AccountSpending::Delegation(delegation_id, tampered_amount),
+            )
+        });
+        assert_eq!(
+            verify_signature(
+                &chain_config,
+                &destination,
+                &tampered_tx,
+                &inputs_utxos_refs,
+                0
+            ),
+            Err(TransactionSigError::SignatureVerificationFailed),
+            "{sighash_type:?}"
+        );
+    }
+}
+
+// Changing the nonce of a signed account input must invalidate its signature,
+// so the signature can't be replayed for another withdrawal from the same account
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn tampered_account_nonce(#[case] seed: Seed) {
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+    let chain_config = create_mainnet();
+
+    let (private_key, public_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+    let destination = Destination::PublicKey(public_key);
+
+    for sighash_type in sig_hash_types() {
+        let (tx, inputs_utxos) =
+            sign_tx_with_account_input(&mut rng, &private_key, &destination, sighash_type);
+        let inputs_utxos_refs = inputs_utxos.iter().map(|utxo| utxo.as_ref()).collect::<Vec<_>>();
+
+        let tampered_tx = replace_first_account_input(&tx, |nonce, account| {
+            let new_nonce = nonce.increment().unwrap_or_else(|| nonce.decrement().unwrap());
+            (new_nonce, account)
+        });
+        assert_eq!(
+            verify_signature(
+                &chain_config,
+                &destination,
+                &tampered_tx,
+                &inputs_utxos_refs,
+                0
+            ),
+            Err(TransactionSigError::SignatureVerificationFailed),
+            "{sighash_type:?}"
+        );
+    }
+}
@@ -40,6 +40,7 @@ use crypto::{
 };
 use test_utils::random::Seed;
 
+mod account_inputs;
 mod mixed_sighash_types;
 mod sign_and_mutate;
 mod sign_and_verify;
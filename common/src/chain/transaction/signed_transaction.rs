@@ -15,12 +15,25 @@
 
 use super::{signature::inputsig::InputWitness, Transaction, TransactionSize, TxOutput};
 use crate::{
-    chain::{TransactionCreationError, TxInput},
+    chain::{check_duplicate_inputs, DuplicateInputError, TransactionCreationError, TxInput},
     primitives::id::{self, H256},
 };
 use serialization::{Decode, Encode};
+use thiserror::Error;
 use utils::ensure;
 
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TxStructureError {
+    #[error("The transaction has no inputs")]
+    EmptyInputs,
+    #[error("The transaction has no outputs")]
+    EmptyOutputs,
+    #[error("Duplicate input: {0}")]
+    DuplicateInput(#[from] DuplicateInputError),
+    #[error("Witness count {0} does not match input count {1}")]
+    InvalidWitnessCount(usize, usize),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Encode, serde::Serialize)]
 pub struct SignedTransaction {
     transaction: Transaction,
@@ -86,6 +99,20 @@ impl SignedTransaction {
     pub fn serialized_hash(&self) -> H256 {
         id::hash_encoded(self)
     }
+
+    /// Check the invariants of the transaction that don't depend on the chain state, so that
+    /// a malformed transaction can be rejected before it's broadcast or looked up further
+    pub fn check_structure(&self) -> Result<(), TxStructureError> {
+        ensure!(!self.inputs().is_empty(), TxStructureError::EmptyInputs);
+        ensure!(!self.outputs().is_empty(), TxStructureError::EmptyOutputs);
+        ensure!(
+            self.signatures.len() == self.inputs().len(),
+            TxStructureError::InvalidWitnessCount(self.signatures.len(), self.inputs().len())
+        );
+        check_duplicate_inputs(self.inputs())?;
+
+        Ok(())
+    }
 }
 
 impl Decode for SignedTransaction {
@@ -116,7 +143,7 @@ mod tests {
     use super::*;
 
     use crate::chain::output_value::OutputValue;
-    use crate::chain::TxInput;
+    use crate::chain::{AccountNonce, AccountSpending, DelegationId, TxInput};
     use crate::primitives::id::Id;
     use crate::primitives::H256;
 
@@ -244,4 +271,67 @@ mod tests {
             SignedTransaction::decode(&mut encoded.as_slice()).unwrap_err();
         }
     }
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn check_structure(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let utxo_input = TxInput::from_utxo(
+            Id::<Transaction>::new(H256::random_using(&mut rng)).into(),
+            rng.gen::<u32>() % 10,
+        );
+        let account_input = TxInput::from_account(
+            AccountNonce::new(rng.gen()),
+            AccountSpending::Delegation(
+                DelegationId::new(H256::random_using(&mut rng)),
+                Amount::from_atoms(rng.gen::<u128>()),
+            ),
+        );
+        let output = TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(rng.gen::<u128>())),
+            crate::chain::Destination::AnyoneCanSpend,
+        );
+        let make_signed_tx = |inputs: Vec<TxInput>, outputs: Vec<TxOutput>| {
+            let witnesses = vec![InputWitness::NoSignature(None); inputs.len()];
+            let tx = Transaction::new(0, inputs, outputs).unwrap();
+            SignedTransaction::new(tx, witnesses).unwrap()
+        };
+
+        // Valid transaction
+        make_signed_tx(
+            vec![utxo_input.clone(), account_input.clone()],
+            vec![output.clone()],
+        )
+        .check_structure()
+        .unwrap();
+
+        assert_eq!(
+            make_signed_tx(vec![], vec![output.clone()]).check_structure(),
+            Err(TxStructureError::EmptyInputs)
+        );
+        assert_eq!(
+            make_signed_tx(vec![utxo_input.clone()], vec![]).check_structure(),
+            Err(TxStructureError::EmptyOutputs)
+        );
+
+        assert!(matches!(
+            make_signed_tx(
+                vec![account_input.clone(), account_input],
+                vec![output.clone()]
+            )
+            .check_structure(),
+            Err(TxStructureError::DuplicateInput(_))
+        ));
+
+        // The constructor and the decoder reject this, so construct it manually
+        let signed_tx = SignedTransaction {
+            transaction: Transaction::new(0, vec![utxo_input], vec![output]).unwrap(),
+            signatures: vec![],
+        };
+        assert_eq!(
+            signed_tx.check_structure(),
+            Err(TxStructureError::InvalidWitnessCount(0, 1))
+        );
+    }
 }
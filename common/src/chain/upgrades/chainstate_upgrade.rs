@@ -0,0 +1,168 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::primitives::BlockHeight;
+
+use super::NetUpgrades;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
+pub enum TokenFreezeActivated {
+    Yes,
+    No,
+}
+
+/// Whether a new stake pool is rejected if its VRF public key is already used by another pool.
+///
+/// The VRF output is the source of randomness in the PoS kernel. Pools sharing a VRF key
+/// produce identical VRF outputs for the same epoch randomness and timestamp, so they are no
+/// longer independent lotteries: a single key holder can evaluate the kernel once for all of
+/// them and grind over which pool to stake with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
+pub enum UniquePoolVrfKeys {
+    Yes,
+    No,
+}
+
+/// Whether a PoW block reward must claim exactly the block subsidy plus the fees of the block's
+/// transactions, instead of at most that amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
+pub enum ExactPoWReward {
+    Yes,
+    No,
+}
+
+/// Whether the token amounts spent by a transaction must equal the token amounts in its outputs,
+/// instead of the difference being implicitly burned. Burning tokens then requires a burn output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
+pub enum TokensConservation {
+    Yes,
+    No,
+}
+
+/// Whether the total encoded size of NFT metadata is limited by
+/// `ChainConfig::token_max_nft_metadata_size`, in addition to the limits of the individual fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
+pub enum NftMetadataSizeLimit {
+    Yes,
+    No,
+}
+
+/// Whether the amount of a fungible token issuance is limited by `ChainConfig::token_max_supply`
+/// whole units, scaled by the token's number of decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
+pub enum TokenMaxSupplyLimit {
+    Yes,
+    No,
+}
+
+/// Whether a block timestamp must be strictly greater than the median time past of its ancestors,
+/// instead of greater or equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
+pub enum StrictMedianTimePast {
+    Yes,
+    No,
+}
+
+/// Changes of the transaction validation rules that are activated at some height
+/// without changing the consensus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
+pub struct ChainstateUpgrade {
+    token_freeze_activated: TokenFreezeActivated,
+    unique_pool_vrf_keys: UniquePoolVrfKeys,
+    exact_pow_reward: ExactPoWReward,
+    tokens_conservation: TokensConservation,
+    nft_metadata_size_limit: NftMetadataSizeLimit,
+    token_max_supply_limit: TokenMaxSupplyLimit,
+    strict_median_time_past: StrictMedianTimePast,
+}
+
+impl ChainstateUpgrade {
+    pub fn new(
+        token_freeze_activated: TokenFreezeActivated,
+        unique_pool_vrf_keys: UniquePoolVrfKeys,
+        exact_pow_reward: ExactPoWReward,
+        tokens_conservation: TokensConservation,
+        nft_metadata_size_limit: NftMetadataSizeLimit,
+        token_max_supply_limit: TokenMaxSupplyLimit,
+        strict_median_time_past: StrictMedianTimePast,
+    ) -> Self {
+        Self {
+            token_freeze_activated,
+            unique_pool_vrf_keys,
+            exact_pow_reward,
+            tokens_conservation,
+            nft_metadata_size_limit,
+            token_max_supply_limit,
+            strict_median_time_past,
+        }
+    }
+
+    /// All the upgrades are activated
+    pub fn latest() -> Self {
+        Self::new(
+            TokenFreezeActivated::Yes,
+            UniquePoolVrfKeys::Yes,
+            ExactPoWReward::Yes,
+            TokensConservation::Yes,
+            NftMetadataSizeLimit::Yes,
+            TokenMaxSupplyLimit::Yes,
+            StrictMedianTimePast::Yes,
+        )
+    }
+
+    pub fn token_freeze_activated(&self) -> TokenFreezeActivated {
+        self.token_freeze_activated
+    }
+
+    pub fn unique_pool_vrf_keys(&self) -> UniquePoolVrfKeys {
+        self.unique_pool_vrf_keys
+    }
+
+    pub fn exact_pow_reward(&self) -> ExactPoWReward {
+        self.exact_pow_reward
+    }
+
+    pub fn tokens_conservation(&self) -> TokensConservation {
+        self.tokens_conservation
+    }
+
+    pub fn nft_metadata_size_limit(&self) -> NftMetadataSizeLimit {
+        self.nft_metadata_size_limit
+    }
+
+    pub fn token_max_supply_limit(&self) -> TokenMaxSupplyLimit {
+        self.token_max_supply_limit
+    }
+
+    pub fn strict_median_time_past(&self) -> StrictMedianTimePast {
+        self.strict_median_time_past
+    }
+}
+
+impl NetUpgrades<ChainstateUpgrade> {
+    /// All the upgrades are activated at genesis
+    pub fn latest_chainstate_upgrades() -> Self {
+        Self::initialize(vec![(BlockHeight::zero(), ChainstateUpgrade::latest())])
+            .expect("valid net upgrades")
+    }
+
+    /// The chainstate upgrade that is in effect at the given height
+    pub fn chainstate_upgrade_at_height(&self, height: BlockHeight) -> &ChainstateUpgrade {
+        &self
+            .version_at_height(height)
+            .expect("Genesis must have a chainstate upgrade")
+            .1
+    }
+}
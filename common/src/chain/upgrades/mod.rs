@@ -13,8 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod chainstate_upgrade;
 mod netupgrade;
 
+pub use chainstate_upgrade::*;
 pub use netupgrade::*;
 
 pub enum NetUpgradeError {
@@ -31,17 +31,14 @@ use common::{
         timestamp::BlockTimestamp,
         BlockHeader, BlockReward, ConsensusData,
     },
-    chain::{
-        output_value::OutputValue, timelock::OutputTimeLock, ChainConfig, Destination,
-        RequiredConsensus, TxOutput,
-    },
-    primitives::BlockHeight,
+    chain::{ChainConfig, Destination, RequiredConsensus},
+    primitives::{Amount, BlockHeight},
 };
 use serialization::{Decode, Encode};
 use utils::atomics::{AcqRelAtomicU64, RelaxedAtomicBool};
 
 use crate::pos::input_data::generate_pos_consensus_data_and_reward;
-use crate::pow::input_data::generate_pow_consensus_data_and_reward;
+use crate::pow::input_data::{create_block_reward, generate_pow_consensus_data_and_reward};
 
 pub use crate::{
     error::ConsensusVerificationError,
@@ -107,6 +104,7 @@ pub fn generate_consensus_data_and_reward<G>(
     input_data: GenerateBlockInputData,
     block_timestamp: BlockTimestamp,
     block_height: BlockHeight,
+    total_fees: Amount,
     get_ancestor: G,
 ) -> Result<(ConsensusData, BlockReward), ConsensusCreationError>
 where
@@ -116,27 +114,13 @@ where
         RequiredConsensus::IgnoreConsensus => {
             let consensus_data = ConsensusData::None;
 
-            let time_lock = {
-                let block_distance = chain_config.empty_consensus_reward_maturity_distance();
-
-                let reward_maturity_distance_i64: i64 =
-                    block_distance.try_into().map_err(|_| {
-                        ConsensusPoWError::InvalidBlockRewardMaturityDistance(block_distance)
-                    })?;
-
-                let reward_maturity_distance_u64: u64 =
-                    reward_maturity_distance_i64.try_into().map_err(|_| {
-                        ConsensusPoWError::InvalidBlockRewardMaturityDistance(block_distance)
-                    })?;
-
-                OutputTimeLock::ForBlockCount(reward_maturity_distance_u64)
-            };
-
-            let block_reward = BlockReward::new(vec![TxOutput::LockThenTransfer(
-                OutputValue::Coin(chain_config.block_subsidy_at_height(&block_height)),
+            let block_reward = create_block_reward(
+                chain_config,
+                block_height,
+                total_fees,
+                chain_config.empty_consensus_reward_maturity_distance(),
                 Destination::AnyoneCanSpend,
-                time_lock,
-            )]);
+            )?;
 
             Ok((consensus_data, block_reward))
         }
@@ -163,6 +147,7 @@ where
                 get_ancestor,
                 *pow_input_data,
                 block_height,
+                total_fees,
             )
             .map_err(ConsensusCreationError::MiningError),
             GenerateBlockInputData::PoS(_) => Err(ConsensusCreationError::MiningError(
@@ -874,6 +874,127 @@ mod tests {
         assert_ne!(target, Compact::from(target_limit));
     }
 
+    fn add_block_with_required_target(
+        rng: &mut (impl Rng + CryptoRng),
+        chain_config: &ChainConfig,
+        block_index_handle: &mut TestBlockIndexHandle,
+        timestamp: u64,
+    ) -> Uint256 {
+        let (tip_height, tip_id) = match block_index_handle.blocks.last() {
+            Some((height, block_index)) => (*height, (*block_index.block_id()).into()),
+            None => (BlockHeight::zero(), chain_config.genesis_block_id()),
+        };
+        let height = tip_height.next_height();
+        let pos_status = get_pos_status(chain_config, height);
+        let target =
+            calculate_target_required(chain_config, &pos_status, tip_id, block_index_handle)
+                .unwrap();
+        let target = Uint256::try_from(target).unwrap();
+
+        let timestamp = BlockTimestamp::from_int_seconds(timestamp);
+        let block = make_block(rng, tip_id, timestamp, target);
+        let block_index = BlockIndex::new(
+            &block,
+            Uint256::ZERO,
+            tip_id,
+            height,
+            timestamp,
+            BlockStatus::new(),
+        );
+        block_index_handle.add_block_index(height, block_index);
+
+        target
+    }
+
+    // Produce a sequence of blocks faster than the target block time and then a sequence of
+    // slower blocks, every block using the required target. The target must go down (harder)
+    // while the blocks are fast and up (easier) while they are slow.
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn target_follows_block_times(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let target_block_time = 100;
+        let target_limit = Uint256::from_u64(rng.gen_range(1 << 40..1 << 60));
+        let pos_config = PoSChainConfigBuilder::new_for_unit_test()
+            .targe_limit(target_limit)
+            .targe_block_time(NonZeroU64::new(target_block_time).unwrap())
+            .block_count_to_average_for_blocktime(5)
+            .difficulty_change_limit(PerThousand::new(100).unwrap())
+            .build();
+        let upgrades = vec![(
+            BlockHeight::new(0),
+            UpgradeVersion::ConsensusUpgrade(ConsensusUpgrade::PoS {
+                initial_difficulty: None,
+                config: pos_config,
+            }),
+        )];
+        let net_upgrades = NetUpgrades::initialize(upgrades).expect("valid net-upgrades");
+        let chain_config = ConfigBuilder::test_chain()
+            .net_upgrades(net_upgrades)
+            .genesis_custom(Genesis::new(
+                "msg".to_owned(),
+                BlockTimestamp::from_int_seconds(0),
+                vec![],
+            ))
+            .build();
+
+        // The target limit isn't necessarily representable as `Compact` exactly
+        let target_limit = Uint256::try_from(Compact::from(target_limit)).unwrap();
+
+        let mut block_index_handle = TestBlockIndexHandle::new(&chain_config);
+        let mut timestamp = 0;
+
+        // The first block after genesis uses the target limit.
+        // Note that a block's target depends on the timestamps of the previous blocks only.
+        timestamp += rng.gen_range(1..target_block_time / 2);
+        let first_target = add_block_with_required_target(
+            &mut rng,
+            &chain_config,
+            &mut block_index_handle,
+            timestamp,
+        );
+        assert_eq!(first_target, target_limit);
+
+        // Fast blocks
+        let mut prev_target = first_target;
+        for _ in 0..20 {
+            timestamp += rng.gen_range(1..target_block_time / 2);
+            let target = add_block_with_required_target(
+                &mut rng,
+                &chain_config,
+                &mut block_index_handle,
+                timestamp,
+            );
+            assert!(target < prev_target);
+            prev_target = target;
+        }
+        let lowest_target = prev_target;
+
+        // Slow blocks, the target is never allowed to exceed the limit.
+        // The first slow block still gets its target from the fast blocks before it.
+        timestamp += rng.gen_range(target_block_time * 5..target_block_time * 10);
+        prev_target = add_block_with_required_target(
+            &mut rng,
+            &chain_config,
+            &mut block_index_handle,
+            timestamp,
+        );
+        for _ in 0..20 {
+            timestamp += rng.gen_range(target_block_time * 5..target_block_time * 10);
+            let target = add_block_with_required_target(
+                &mut rng,
+                &chain_config,
+                &mut block_index_handle,
+                timestamp,
+            );
+            assert!(target > prev_target || target == target_limit);
+            assert!(target <= target_limit);
+            prev_target = target;
+        }
+        assert!(prev_target > lowest_target);
+    }
+
     // The test can be enabled on demand to simulate the work of current DAA.
     // It will calculate and print block times for blocks that could be generated over 1_000_000 time slots
     // which is ideally 8333 blocks for 120s target time
@@ -22,7 +22,7 @@ use common::{
         timelock::OutputTimeLock,
         ChainConfig, Destination, PoWStatus, TxOutput,
     },
-    primitives::BlockHeight,
+    primitives::{Amount, BlockDistance, BlockHeight},
 };
 use serialization::{Decode, Encode};
 
@@ -50,6 +50,7 @@ pub fn generate_pow_consensus_data_and_reward<G>(
     get_ancestor: G,
     pow_input_data: PoWGenerateBlockInputData,
     block_height: BlockHeight,
+    total_fees: Amount,
 ) -> Result<(ConsensusData, BlockReward), ConsensusPoWError>
 where
     G: Fn(&BlockIndex, BlockHeight) -> Result<GenBlockIndex, PropertyQueryError>,
@@ -64,25 +65,38 @@ where
 
     let consensus_data = ConsensusData::PoW(Box::new(PoWData::new(work_required, 0)));
 
-    let time_lock = {
-        let block_distance = chain_config.get_proof_of_work_config().reward_maturity_distance();
-
-        let reward_maturity_distance_i64: i64 = block_distance
-            .try_into()
-            .map_err(|_| ConsensusPoWError::InvalidBlockRewardMaturityDistance(block_distance))?;
+    let block_reward = create_block_reward(
+        chain_config,
+        block_height,
+        total_fees,
+        chain_config.get_proof_of_work_config().reward_maturity_distance(),
+        pow_input_data.reward_destination().clone(),
+    )?;
 
-        let reward_maturity_distance_u64: u64 = reward_maturity_distance_i64
-            .try_into()
-            .map_err(|_| ConsensusPoWError::InvalidBlockRewardMaturityDistance(block_distance))?;
+    Ok((consensus_data, block_reward))
+}
 
-        OutputTimeLock::ForBlockCount(reward_maturity_distance_u64)
-    };
+/// The reward of a block without PoS, which claims the block subsidy plus the fees of the block's
+/// transactions and is locked for the given maturity distance.
+pub(crate) fn create_block_reward(
+    chain_config: &ChainConfig,
+    block_height: BlockHeight,
+    total_fees: Amount,
+    reward_maturity_distance: BlockDistance,
+    destination: Destination,
+) -> Result<BlockReward, ConsensusPoWError> {
+    let reward_maturity_blocks: u64 =
+        i64::from(reward_maturity_distance).try_into().map_err(|_| {
+            ConsensusPoWError::InvalidBlockRewardMaturityDistance(reward_maturity_distance)
+        })?;
 
-    let block_reward = BlockReward::new(vec![TxOutput::LockThenTransfer(
-        OutputValue::Coin(chain_config.block_subsidy_at_height(&block_height)),
-        pow_input_data.reward_destination().clone(),
-        time_lock,
-    )]);
+    let subsidy = chain_config.block_subsidy_at_height(&block_height);
+    let reward_amount = (subsidy + total_fees)
+        .ok_or(ConsensusPoWError::BlockRewardOverflow(subsidy, total_fees))?;
 
-    Ok((consensus_data, block_reward))
+    Ok(BlockReward::new(vec![TxOutput::LockThenTransfer(
+        OutputValue::Coin(reward_amount),
+        destination,
+        OutputTimeLock::ForBlockCount(reward_maturity_blocks),
+    )]))
 }
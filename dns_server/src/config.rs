@@ -19,6 +19,12 @@ use clap::Parser;
 use p2p::types::ip_or_socket_address::IpOrSocketAddress;
 use trust_dns_client::rr::Name;
 
+/// Default TTL of the A and AAAA records (in seconds)
+pub const DEFAULT_RECORD_TTL: u32 = 60;
+
+/// Default maximum number of A or AAAA records returned per query
+pub const DEFAULT_MAX_RECORDS: usize = 20;
+
 #[derive(clap::ValueEnum, Clone, Debug)]
 pub enum Network {
     Mainnet,
@@ -43,6 +49,10 @@ pub struct DnsServerConfig {
     #[clap(long)]
     pub reserved_node: Vec<IpOrSocketAddress>,
 
+    /// Don't advertise IPv6 addresses (no AAAA records will be returned).
+    #[clap(long)]
+    pub disable_ipv6: bool,
+
     /// Hostname of the DNS seed
     #[clap(long)]
     pub host: Name,
@@ -57,4 +67,13 @@ pub struct DnsServerConfig {
     /// If set, the SOA record will be added.
     #[clap(long)]
     pub mbox: Option<Name>,
+
+    /// TTL of the A and AAAA records (in seconds).
+    #[clap(long, default_value_t = DEFAULT_RECORD_TTL)]
+    pub record_ttl: u32,
+
+    /// Maximum number of A or AAAA records returned per query.
+    /// If more nodes are known, a random subset is returned.
+    #[clap(long, default_value_t = DEFAULT_MAX_RECORDS)]
+    pub max_records: usize,
 }
@@ -16,13 +16,14 @@
 use std::time::Duration;
 
 use common::primitives::time::Time;
+use p2p::types::services::Services;
 use utils::const_value::ConstValue;
 
 /// Address state transition
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AddressStateTransitionTo {
     Connecting,
-    Connected,
+    Connected { services: Services },
     Disconnecting,
     Disconnected,
 }
@@ -47,7 +48,10 @@ pub enum AddressState {
         was_reachable: bool,
     },
 
-    Connected {},
+    Connected {
+        /// Services advertised by the node
+        services: Services,
+    },
 
     Disconnecting {
         /// Same as above
@@ -98,7 +102,7 @@ impl AddressState {
                 fail_count,
                 was_reachable: _,
             } => *fail_count,
-            AddressState::Connected {} => 0,
+            AddressState::Connected { services: _ } => 0,
             AddressState::Disconnecting {
                 fail_count,
                 was_reachable: _,
@@ -122,7 +126,7 @@ impl AddressState {
                 fail_count: _,
                 was_reachable,
             } => *was_reachable,
-            AddressState::Connected {} => true,
+            AddressState::Connected { services: _ } => true,
             AddressState::Disconnecting {
                 fail_count: _,
                 was_reachable,
@@ -147,7 +151,7 @@ impl AddressState {
                 fail_count: _,
                 was_reachable: _,
             } => false,
-            AddressState::Connected {} => true,
+            AddressState::Connected { services: _ } => true,
             AddressState::Disconnecting {
                 fail_count: _,
                 was_reachable: _,
@@ -172,7 +176,7 @@ impl AddressState {
                 fail_count: _,
                 was_reachable,
             } => *was_reachable,
-            AddressState::Connected {} => true,
+            AddressState::Connected { services: _ } => true,
             AddressState::Disconnecting {
                 fail_count: _,
                 was_reachable,
@@ -195,7 +199,7 @@ impl AddressData {
     /// Returns true when it is time to attempt a new outbound connection
     pub fn connect_now(&self, now: Time) -> bool {
         match self.state {
-            AddressState::Connected {}
+            AddressState::Connected { services: _ }
             | AddressState::Connecting {
                 fail_count: _,
                 was_reachable: _,
@@ -268,10 +272,10 @@ impl AddressData {
                 };
             }
 
-            AddressStateTransitionTo::Connected => {
+            AddressStateTransitionTo::Connected { services } => {
                 assert!(matches!(self.state, AddressState::Connecting { .. }));
 
-                self.state = AddressState::Connected {};
+                self.state = AddressState::Connected { services };
             }
 
             AddressStateTransitionTo::Disconnecting => {
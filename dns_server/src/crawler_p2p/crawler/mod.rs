@@ -378,7 +378,9 @@ impl Crawler {
                 self.now,
                 &address,
                 address_data,
-                AddressStateTransitionTo::Connected,
+                AddressStateTransitionTo::Connected {
+                    services: peer_info.remote_services,
+                },
                 callback,
             );
         } else {
@@ -48,7 +48,9 @@ use crate::{dns_server::DnsServerCommand, error::DnsServerError};
 
 use self::storage::{DnsServerStorage, DnsServerStorageRead, DnsServerStorageWrite};
 
-use super::crawler::{Crawler, CrawlerCommand, CrawlerConfig, CrawlerEvent};
+use super::crawler::{
+    address_data::AddressState, Crawler, CrawlerCommand, CrawlerConfig, CrawlerEvent,
+};
 
 /// How often the server performs maintenance (tries to connect to new nodes)
 const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
@@ -64,6 +66,10 @@ pub struct CrawlerManagerConfig {
     /// the DNS server has no way to communicate port numbers,
     /// and hence we only add addresses that are reachable through this default port
     pub default_p2p_port: u16,
+
+    /// If set, IPv6 addresses are not added to DNS (which is useful when the nodes
+    /// that use the DNS seed only have IPv4 connectivity)
+    pub disable_ipv6: bool,
 }
 
 pub struct CrawlerManager<N: NetworkingService, S> {
@@ -271,18 +277,19 @@ where
         self.send_crawler_event(CrawlerEvent::Timer { period });
     }
 
-    fn get_dns_ip(address: &SocketAddress, default_p2p_port: u16) -> Option<IpAddr> {
+    fn get_dns_ip(address: &SocketAddress, config: &CrawlerManagerConfig) -> Option<IpAddr> {
         // Only add nodes listening on the default port to DNS
         match address.as_peer_address() {
             PeerAddress::Ip4(addr)
                 if Ipv4Addr::from(addr.ip).is_global_unicast_ip()
-                    && addr.port == default_p2p_port =>
+                    && addr.port == config.default_p2p_port =>
             {
                 Some(Ipv4Addr::from(addr.ip).into())
             }
             PeerAddress::Ip6(addr)
-                if Ipv6Addr::from(addr.ip).is_global_unicast_ip()
-                    && addr.port == default_p2p_port =>
+                if !config.disable_ipv6
+                    && Ipv6Addr::from(addr.ip).is_global_unicast_ip()
+                    && addr.port == config.default_p2p_port =>
             {
                 Some(Ipv6Addr::from(addr.ip).into())
             }
@@ -310,16 +317,16 @@ where
                 new_state,
             } => {
                 match (
-                    Self::get_dns_ip(&address, config.default_p2p_port),
+                    Self::get_dns_ip(&address, config),
                     old_state.is_reachable(),
-                    new_state.is_reachable(),
+                    &new_state,
                 ) {
-                    (Some(ip), false, true) => {
+                    (Some(ip), false, AddressState::Connected { services }) => {
                         dns_server_cmd_tx
-                            .send(DnsServerCommand::AddAddress(ip))
+                            .send(DnsServerCommand::AddAddress(ip, *services))
                             .expect("sending must succeed (AddAddress)");
                     }
-                    (Some(ip), true, false) => {
+                    (Some(ip), true, _) if !new_state.is_reachable() => {
                         dns_server_cmd_tx
                             .send(DnsServerCommand::DelAddress(ip))
                             .expect("sending must succeed (DelAddress)");
@@ -198,6 +198,7 @@ impl ConnectivityService<MockNetworkingService> for MockConnectivityHandle {
                     software_version: SemVer::new(1, 2, 3),
                     user_agent: mintlayer_core_user_agent(),
                     common_services: NodeType::DnsServer.into(),
+                    remote_services: NodeType::Full.into(),
                 };
                 let old = self.state.connected.lock().unwrap().insert(address, peer_id);
                 assert!(old.is_none());
@@ -264,6 +265,18 @@ pub fn test_crawler(
     MockStateRef,
     mpsc::UnboundedReceiver<DnsServerCommand>,
     P2pBasicTestTimeGetter,
+) {
+    test_crawler_with_ipv6_disabled(reserved_nodes, false)
+}
+
+pub fn test_crawler_with_ipv6_disabled(
+    reserved_nodes: Vec<SocketAddress>,
+    disable_ipv6: bool,
+) -> (
+    CrawlerManager<MockNetworkingService, DnsServerStorageImpl<storage::inmemory::InMemory>>,
+    MockStateRef,
+    mpsc::UnboundedReceiver<DnsServerCommand>,
+    P2pBasicTestTimeGetter,
 ) {
     let (conn_tx, conn_rx) = mpsc::unbounded_channel();
     let reserved_nodes = reserved_nodes
@@ -273,6 +286,7 @@ pub fn test_crawler(
     let crawler_mgr_config = CrawlerManagerConfig {
         reserved_nodes,
         default_p2p_port: 3031,
+        disable_ipv6,
     };
     let crawler_config = CrawlerConfig {
         ban_duration: BanDuration::default(),
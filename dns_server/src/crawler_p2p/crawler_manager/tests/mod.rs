@@ -19,7 +19,7 @@ use std::time::Duration;
 
 use chainstate::ban_score::BanScore;
 use p2p::{
-    config::{BanDuration, BanThreshold},
+    config::{BanDuration, BanThreshold, NodeType},
     types::socket_address::SocketAddress,
 };
 use p2p_test_utils::{expect_no_recv, expect_recv};
@@ -27,7 +27,7 @@ use p2p_test_utils::{expect_no_recv, expect_recv};
 use crate::{
     crawler_p2p::crawler_manager::tests::mock_manager::{
         advance_time, assert_banned_addresses, assert_known_addresses,
-        erratic_node_connection_error, test_crawler,
+        erratic_node_connection_error, test_crawler, test_crawler_with_ipv6_disabled,
     },
     dns_server::DnsServerCommand,
 };
@@ -42,7 +42,7 @@ async fn basic() {
     advance_time(&mut crawler, &time_getter, Duration::from_secs(60), 60).await;
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node1.socket_addr().ip())
+        DnsServerCommand::AddAddress(node1.socket_addr().ip(), NodeType::Full.into())
     );
 
     // Node goes offline, DNS record removed
@@ -54,6 +54,48 @@ async fn basic() {
     );
 }
 
+#[tokio::test]
+async fn ipv6_node() {
+    let node1: SocketAddress = "[2a00::1]:3031".parse().unwrap();
+    let (mut crawler, state, mut command_rx, time_getter) = test_crawler(vec![node1]);
+
+    // IPv6 node goes online, DNS record added
+    state.node_online(node1);
+    advance_time(&mut crawler, &time_getter, Duration::from_secs(60), 60).await;
+    let ip = node1.socket_addr().ip();
+    assert!(ip.is_ipv6());
+    assert_eq!(
+        expect_recv!(command_rx),
+        DnsServerCommand::AddAddress(ip, NodeType::Full.into())
+    );
+
+    // IPv6 node goes offline, DNS record removed
+    state.node_offline(node1);
+    advance_time(&mut crawler, &time_getter, Duration::from_secs(60), 60).await;
+    assert_eq!(expect_recv!(command_rx), DnsServerCommand::DelAddress(ip));
+}
+
+#[tokio::test]
+async fn ipv6_disabled() {
+    let node1: SocketAddress = "1.2.3.4:3031".parse().unwrap();
+    let node2: SocketAddress = "[2a00::1]:3031".parse().unwrap();
+    let (mut crawler, state, mut command_rx, time_getter) =
+        test_crawler_with_ipv6_disabled(vec![node1, node2], true);
+
+    // Both nodes are online, but only the IPv4 one is added to DNS
+    state.node_online(node1);
+    state.node_online(node2);
+    advance_time(&mut crawler, &time_getter, Duration::from_secs(60), 60).await;
+    assert_eq!(
+        expect_recv!(command_rx),
+        DnsServerCommand::AddAddress(node1.socket_addr().ip(), NodeType::Full.into())
+    );
+    expect_no_recv!(command_rx);
+
+    // The IPv6 node is still crawled
+    assert_known_addresses(&crawler, &[node1, node2]);
+}
+
 #[tokio::test]
 async fn long_offline() {
     let node1: SocketAddress = "1.2.3.4:3031".parse().unwrap();
@@ -73,7 +115,7 @@ async fn long_offline() {
     advance_time(&mut crawler, &time_getter, Duration::from_secs(60), 24 * 60).await;
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node1.socket_addr().ip())
+        DnsServerCommand::AddAddress(node1.socket_addr().ip(), NodeType::Full.into())
     );
 }
 
@@ -91,21 +133,21 @@ async fn announced_online() {
     advance_time(&mut crawler, &time_getter, Duration::from_secs(60), 60).await;
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node1.socket_addr().ip())
+        DnsServerCommand::AddAddress(node1.socket_addr().ip(), NodeType::Full.into())
     );
 
     state.announce_address(node1, node2);
     advance_time(&mut crawler, &time_getter, Duration::from_secs(60), 60).await;
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node2.socket_addr().ip())
+        DnsServerCommand::AddAddress(node2.socket_addr().ip(), NodeType::Full.into())
     );
 
     state.announce_address(node2, node3);
     advance_time(&mut crawler, &time_getter, Duration::from_secs(60), 60).await;
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node3.socket_addr().ip())
+        DnsServerCommand::AddAddress(node3.socket_addr().ip(), NodeType::Full.into())
     );
 
     assert_known_addresses(&crawler, &[node1, node2, node3]);
@@ -122,7 +164,7 @@ async fn announced_offline() {
     advance_time(&mut crawler, &time_getter, Duration::from_secs(60), 60).await;
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node1.socket_addr().ip())
+        DnsServerCommand::AddAddress(node1.socket_addr().ip(), NodeType::Full.into())
     );
     assert_eq!(state.connection_attempts.lock().unwrap().len(), 1);
 
@@ -137,7 +179,7 @@ async fn announced_offline() {
     advance_time(&mut crawler, &time_getter, Duration::from_secs(60), 24 * 60).await;
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node2.socket_addr().ip())
+        DnsServerCommand::AddAddress(node2.socket_addr().ip(), NodeType::Full.into())
     );
     assert_eq!(state.connection_attempts.lock().unwrap().len(), 3);
 }
@@ -165,11 +207,11 @@ async fn private_ip() {
     // Check that only nodes with public addresses and on the default port are added to DNS
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node1.socket_addr().ip())
+        DnsServerCommand::AddAddress(node1.socket_addr().ip(), NodeType::Full.into())
     );
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node2.socket_addr().ip())
+        DnsServerCommand::AddAddress(node2.socket_addr().ip(), NodeType::Full.into())
     );
     expect_no_recv!(command_rx);
 
@@ -203,11 +245,11 @@ async fn ban_unban() {
     // Only normal nodes are added to DNS
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node1.socket_addr().ip())
+        DnsServerCommand::AddAddress(node1.socket_addr().ip(), NodeType::Full.into())
     );
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node3.socket_addr().ip())
+        DnsServerCommand::AddAddress(node3.socket_addr().ip(), NodeType::Full.into())
     );
     expect_no_recv!(command_rx);
 
@@ -263,7 +305,7 @@ async fn ban_unban() {
     assert_banned_addresses(&crawler, &[(node1.as_bannable(), node1_ban_end_time)]);
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node2.socket_addr().ip())
+        DnsServerCommand::AddAddress(node2.socket_addr().ip(), NodeType::Full.into())
     );
 
     // Wait enough time for node1 to be unbanned.
@@ -275,6 +317,6 @@ async fn ban_unban() {
     assert_banned_addresses(&crawler, &[]);
     assert_eq!(
         expect_recv!(command_rx),
-        DnsServerCommand::AddAddress(node1.socket_addr().ip())
+        DnsServerCommand::AddAddress(node1.socket_addr().ip(), NodeType::Full.into())
     );
 }
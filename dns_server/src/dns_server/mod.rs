@@ -23,12 +23,13 @@ use std::{
 
 use crypto::random::{make_pseudo_rng, SliceRandom};
 use futures::never::Never;
+use p2p::types::services::Services;
 use tokio::{net::UdpSocket, sync::mpsc};
 use trust_dns_client::rr::{rdata::SOA, LowerName, Name, RData, RecordSet, RecordType, RrKey};
 use trust_dns_server::{
     authority::{
-        AuthLookup, Authority, Catalog, LookupError, LookupOptions, MessageRequest, UpdateResult,
-        ZoneType,
+        AuthLookup, Authority, Catalog, LookupError, LookupOptions, LookupRecords, MessageRequest,
+        UpdateResult, ZoneType,
     },
     server::RequestInfo,
     store::in_memory::InMemoryAuthority,
@@ -40,7 +41,8 @@ use crate::{config::DnsServerConfig, error::DnsServerError};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum DnsServerCommand {
-    AddAddress(IpAddr),
+    /// Add the address of a node that advertises the specified services
+    AddAddress(IpAddr, Services),
 
     DelAddress(IpAddr),
 }
@@ -60,16 +62,9 @@ const SOA_EXPIRE: i32 = 2592000;
 const SOA_MINIMUM: u32 = 604800;
 
 // Same values as in `https://github.com/sipa/bitcoin-seeder/blob/3ef602de83a76bc95a06867d4bfc239f13992140/dns.cpp`
-const TTL_IP: u32 = 3600;
 const TTL_NS: u32 = 21600;
 const TTL_SOA: u32 = 21600;
 
-// Maximum number of IPv4 addresses in result
-const MAX_IPV4_RECORDS: usize = 24;
-
-// Maximum number of IPv6 addresses in result
-const MAX_IPV6_RECORDS: usize = 14;
-
 impl DnsServer {
     pub async fn new(
         config: Arc<DnsServerConfig>,
@@ -82,6 +77,8 @@ impl DnsServer {
             host: config.host.clone(),
             nameserver: config.nameserver.clone(),
             mbox: config.mbox.clone(),
+            record_ttl: config.record_ttl,
+            max_records: config.max_records,
             inner,
             ip4: Default::default(),
             ip6: Default::default(),
@@ -128,14 +125,51 @@ impl DnsServer {
 }
 
 /// Wrapper for InMemoryAuthority that selects random addresses every second
+///
+/// Queries for subdomains of the form `x<hex>.<host>` are answered with the addresses of the nodes
+/// that advertise all the services from the hex-encoded bitmask (same as in Bitcoin DNS seeds).
 struct AuthorityImpl {
     serial: RelaxedAtomicU32,
     host: Name,
     nameserver: Option<Name>,
     mbox: Option<Name>,
+    record_ttl: u32,
+    max_records: usize,
     inner: InMemoryAuthority,
-    ip4: Mutex<Vec<Ipv4Addr>>,
-    ip6: Mutex<Vec<Ipv6Addr>>,
+    ip4: Mutex<Vec<(Ipv4Addr, Services)>>,
+    ip6: Mutex<Vec<(Ipv6Addr, Services)>>,
+}
+
+/// Randomly selects up to `count` addresses of the nodes that advertise all `required_services`
+fn choose_addresses<T: Copy>(
+    addresses: &[(T, Services)],
+    required_services: Services,
+    count: usize,
+) -> Vec<T> {
+    addresses
+        .iter()
+        .filter(|(_, services)| *services & required_services == required_services)
+        .map(|(address, _)| *address)
+        .collect::<Vec<_>>()
+        .choose_multiple(&mut make_pseudo_rng(), count)
+        .cloned()
+        .collect()
+}
+
+/// Returns the required services if `name` is a service filter subdomain of `host` (like `x9.<host>`)
+fn parse_service_filter(host: &LowerName, name: &LowerName) -> Option<Services> {
+    if name.num_labels() != host.num_labels() + 1 || !host.zone_of(name) {
+        return None;
+    }
+
+    let name = Name::from(name.clone());
+    let label = name.iter().next()?;
+    let hex = std::str::from_utf8(label.strip_prefix(b"x")?).ok()?;
+    if hex.is_empty() || !hex.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    u64::from_str_radix(hex, 16).ok().map(Services::from_u64)
 }
 
 impl AuthorityImpl {
@@ -149,21 +183,19 @@ impl AuthorityImpl {
             return;
         }
 
-        let ipv4 = self
-            .ip4
-            .lock()
-            .expect("mutex must be valid (refresh ipv4)")
-            .choose_multiple(&mut make_pseudo_rng(), MAX_IPV4_RECORDS)
-            .cloned()
-            .collect::<Vec<_>>();
-
-        let ipv6 = self
-            .ip6
-            .lock()
-            .expect("mutex must be valid (refresh ipv6)")
-            .choose_multiple(&mut make_pseudo_rng(), MAX_IPV6_RECORDS)
-            .cloned()
-            .collect::<Vec<_>>();
+        let no_services = Services::from_u64(0);
+
+        let ipv4 = choose_addresses(
+            &self.ip4.lock().expect("mutex must be valid (refresh ipv4)"),
+            no_services,
+            self.max_records,
+        );
+
+        let ipv6 = choose_addresses(
+            &self.ip6.lock().expect("mutex must be valid (refresh ipv6)"),
+            no_services,
+            self.max_records,
+        );
 
         let mut new_records = BTreeMap::new();
 
@@ -194,7 +226,7 @@ impl AuthorityImpl {
         }
 
         // A records
-        let mut ipv4_rec = RecordSet::with_ttl(self.host.clone(), RecordType::A, TTL_IP);
+        let mut ipv4_rec = RecordSet::with_ttl(self.host.clone(), RecordType::A, self.record_ttl);
         for ip in ipv4 {
             ipv4_rec.add_rdata(RData::A(ip));
         }
@@ -204,7 +236,8 @@ impl AuthorityImpl {
         );
 
         // AAAA records
-        let mut ipv6_rec = RecordSet::with_ttl(self.host.clone(), RecordType::AAAA, TTL_IP);
+        let mut ipv6_rec =
+            RecordSet::with_ttl(self.host.clone(), RecordType::AAAA, self.record_ttl);
         for ip in ipv6 {
             ipv6_rec.add_rdata(RData::AAAA(ip));
         }
@@ -215,6 +248,43 @@ impl AuthorityImpl {
 
         *self.inner.records_mut().await = new_records;
     }
+
+    /// Returns random addresses of the nodes that advertise all `required_services`
+    fn filtered_lookup(
+        &self,
+        name: &LowerName,
+        query_type: RecordType,
+        required_services: Services,
+        lookup_options: LookupOptions,
+    ) -> AuthLookup {
+        let mut records =
+            RecordSet::with_ttl(Name::from(name.clone()), query_type, self.record_ttl);
+        match query_type {
+            RecordType::A => {
+                let ipv4 = choose_addresses(
+                    &self.ip4.lock().expect("mutex must be valid (filtered lookup ipv4)"),
+                    required_services,
+                    self.max_records,
+                );
+                for ip in ipv4 {
+                    records.add_rdata(RData::A(ip));
+                }
+            }
+            RecordType::AAAA => {
+                let ipv6 = choose_addresses(
+                    &self.ip6.lock().expect("mutex must be valid (filtered lookup ipv6)"),
+                    required_services,
+                    self.max_records,
+                );
+                for ip in ipv6 {
+                    records.add_rdata(RData::AAAA(ip));
+                }
+            }
+            _ => return AuthLookup::default(),
+        }
+
+        AuthLookup::answers(LookupRecords::new(lookup_options, Arc::new(records)), None)
+    }
 }
 
 #[async_trait::async_trait]
@@ -243,6 +313,10 @@ impl Authority for AuthorityImpl {
         query_type: RecordType,
         lookup_options: LookupOptions,
     ) -> Result<Self::Lookup, LookupError> {
+        if let Some(required_services) = parse_service_filter(self.origin(), name) {
+            return Ok(self.filtered_lookup(name, query_type, required_services, lookup_options));
+        }
+
         self.refresh().await;
         self.inner.lookup(name, query_type, lookup_options).await
     }
@@ -252,6 +326,12 @@ impl Authority for AuthorityImpl {
         request_info: RequestInfo<'_>,
         lookup_options: LookupOptions,
     ) -> Result<Self::Lookup, LookupError> {
+        let name = request_info.query.name();
+        if let Some(required_services) = parse_service_filter(self.origin(), name) {
+            let query_type = request_info.query.query_type();
+            return Ok(self.filtered_lookup(name, query_type, required_services, lookup_options));
+        }
+
         self.refresh().await;
         self.inner.search(request_info, lookup_options).await
     }
@@ -267,23 +347,23 @@ impl Authority for AuthorityImpl {
 
 fn handle_command(auth: &AuthorityImpl, command: DnsServerCommand) {
     match command {
-        DnsServerCommand::AddAddress(IpAddr::V4(ip)) => {
-            auth.ip4.lock().expect("mutex must be valid (add ipv4)").push(ip);
+        DnsServerCommand::AddAddress(IpAddr::V4(ip), services) => {
+            auth.ip4.lock().expect("mutex must be valid (add ipv4)").push((ip, services));
         }
-        DnsServerCommand::AddAddress(IpAddr::V6(ip)) => {
-            auth.ip6.lock().expect("mutex must be valid (add ipv6)").push(ip);
+        DnsServerCommand::AddAddress(IpAddr::V6(ip), services) => {
+            auth.ip6.lock().expect("mutex must be valid (add ipv6)").push((ip, services));
         }
         DnsServerCommand::DelAddress(IpAddr::V4(ip)) => {
             auth.ip4
                 .lock()
                 .expect("mutex must be valid (remove ipv4)")
-                .retain(|val| *val != ip);
+                .retain(|(val, _)| *val != ip);
         }
         DnsServerCommand::DelAddress(IpAddr::V6(ip)) => {
             auth.ip6
                 .lock()
                 .expect("mutex must be valid (remove ipv6)")
-                .retain(|val| *val != ip);
+                .retain(|(val, _)| *val != ip);
         }
     };
 }
@@ -13,15 +13,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::net::{Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
-use trust_dns_client::rr::{Name, RData, RecordType};
+use p2p::{config::NodeType, types::services::Services};
+use trust_dns_client::rr::{LowerName, Name, RData, RecordType};
 use trust_dns_server::{
-    authority::{Authority, ZoneType},
+    authority::{Authority, LookupObject, ZoneType},
     store::in_memory::InMemoryAuthority,
 };
 
-use crate::dns_server::{handle_command, AuthorityImpl, DnsServerCommand};
+use crate::{
+    config::{DEFAULT_MAX_RECORDS, DEFAULT_RECORD_TTL},
+    dns_server::{handle_command, parse_service_filter, AuthorityImpl, DnsServerCommand},
+};
 
 #[tokio::test]
 async fn dns_server_basic() {
@@ -36,6 +40,8 @@ async fn dns_server_basic() {
         host: host.clone(),
         nameserver,
         mbox,
+        record_ttl: DEFAULT_RECORD_TTL,
+        max_records: DEFAULT_MAX_RECORDS,
         inner,
         ip4: Default::default(),
         ip6: Default::default(),
@@ -43,8 +49,14 @@ async fn dns_server_basic() {
 
     let ip1: Ipv4Addr = "1.2.3.4".parse().unwrap();
     let ip2: Ipv6Addr = "2a00::1".parse().unwrap();
-    handle_command(&auth, DnsServerCommand::AddAddress(ip1.into()));
-    handle_command(&auth, DnsServerCommand::AddAddress(ip2.into()));
+    handle_command(
+        &auth,
+        DnsServerCommand::AddAddress(ip1.into(), NodeType::Full.into()),
+    );
+    handle_command(
+        &auth,
+        DnsServerCommand::AddAddress(ip2.into(), NodeType::Full.into()),
+    );
     assert_eq!(auth.ip4.lock().unwrap().len(), 1);
     assert_eq!(auth.ip6.lock().unwrap().len(), 1);
 
@@ -75,3 +87,152 @@ async fn dns_server_basic() {
     assert_eq!(auth.ip4.lock().unwrap().len(), 0);
     assert_eq!(auth.ip6.lock().unwrap().len(), 0);
 }
+
+fn make_test_authority(host: &Name, record_ttl: u32, max_records: usize) -> AuthorityImpl {
+    let inner = InMemoryAuthority::empty(host.clone(), ZoneType::Primary, false);
+
+    AuthorityImpl {
+        serial: Default::default(),
+        host: host.clone(),
+        nameserver: None,
+        mbox: None,
+        record_ttl,
+        max_records,
+        inner,
+        ip4: Default::default(),
+        ip6: Default::default(),
+    }
+}
+
+async fn lookup_ips(auth: &AuthorityImpl, name: &Name, query_type: RecordType) -> Vec<RData> {
+    auth.lookup(&name.clone().into(), query_type, Default::default())
+        .await
+        .unwrap()
+        .iter()
+        .filter_map(|record| record.data().cloned())
+        .collect()
+}
+
+#[test]
+fn service_filter_parsing() {
+    let host: LowerName = Name::from_ascii("seed.mintlayer.org.").unwrap().into();
+    let parse = |name: &str| parse_service_filter(&host, &Name::from_ascii(name).unwrap().into());
+
+    assert_eq!(parse("x2.seed.mintlayer.org."), Some(Services::from_u64(2)));
+    assert_eq!(parse("X7.Seed.Mintlayer.org."), Some(Services::from_u64(7)));
+    assert_eq!(parse("seed.mintlayer.org."), None);
+    assert_eq!(parse("x.seed.mintlayer.org."), None);
+    assert_eq!(parse("xz.seed.mintlayer.org."), None);
+    assert_eq!(parse("y1.seed.mintlayer.org."), None);
+    assert_eq!(parse("x1.x1.seed.mintlayer.org."), None);
+    assert_eq!(parse("x1.other.mintlayer.org."), None);
+}
+
+#[tokio::test]
+async fn dns_server_service_filter() {
+    let host: Name = "seed.mintlayer.org.".parse().unwrap();
+    let auth = make_test_authority(&host, DEFAULT_RECORD_TTL, DEFAULT_MAX_RECORDS);
+
+    let full_ip4: Ipv4Addr = "1.2.3.4".parse().unwrap();
+    let blocks_only_ip4: Ipv4Addr = "1.2.3.5".parse().unwrap();
+    let full_ip6: Ipv6Addr = "2a00::1".parse().unwrap();
+    let blocks_only_ip6: Ipv6Addr = "2a00::2".parse().unwrap();
+    for (ip, node_type) in [
+        (IpAddr::from(full_ip4), NodeType::Full),
+        (IpAddr::from(blocks_only_ip4), NodeType::BlocksOnly),
+        (IpAddr::from(full_ip6), NodeType::Full),
+        (IpAddr::from(blocks_only_ip6), NodeType::BlocksOnly),
+    ] {
+        handle_command(&auth, DnsServerCommand::AddAddress(ip, node_type.into()));
+    }
+
+    // Unfiltered queries return all the nodes
+    let result_a = lookup_ips(&auth, &host, RecordType::A).await;
+    assert_eq!(result_a.len(), 2);
+    assert!(result_a.contains(&RData::A(full_ip4)));
+    assert!(result_a.contains(&RData::A(blocks_only_ip4)));
+    let result_aaaa = lookup_ips(&auth, &host, RecordType::AAAA).await;
+    assert_eq!(result_aaaa.len(), 2);
+    assert!(result_aaaa.contains(&RData::AAAA(full_ip6)));
+    assert!(result_aaaa.contains(&RData::AAAA(blocks_only_ip6)));
+
+    // Both node types advertise `Service::Blocks` (0x2)
+    let blocks_host: Name = "x2.seed.mintlayer.org.".parse().unwrap();
+    assert_eq!(
+        lookup_ips(&auth, &blocks_host, RecordType::A).await.len(),
+        2
+    );
+    assert_eq!(
+        lookup_ips(&auth, &blocks_host, RecordType::AAAA).await.len(),
+        2
+    );
+
+    // Only full nodes advertise `Service::Transactions` (0x1)
+    let txs_host: Name = "x1.seed.mintlayer.org.".parse().unwrap();
+    assert_eq!(
+        lookup_ips(&auth, &txs_host, RecordType::A).await,
+        vec![RData::A(full_ip4)]
+    );
+    assert_eq!(
+        lookup_ips(&auth, &txs_host, RecordType::AAAA).await,
+        vec![RData::AAAA(full_ip6)]
+    );
+
+    // No node advertises an unknown service
+    let unknown_host: Name = "x80.seed.mintlayer.org.".parse().unwrap();
+    assert!(lookup_ips(&auth, &unknown_host, RecordType::A).await.is_empty());
+    assert!(lookup_ips(&auth, &unknown_host, RecordType::AAAA).await.is_empty());
+}
+
+#[tokio::test]
+async fn dns_server_record_cap() {
+    let host: Name = "seed.mintlayer.org.".parse().unwrap();
+    let record_ttl = 123;
+    let max_records = 5;
+    let auth = make_test_authority(&host, record_ttl, max_records);
+
+    let nodes_count = 3 * max_records as u8;
+    let ipv4_all = (0..nodes_count).map(|i| Ipv4Addr::new(1, 2, 3, i)).collect::<Vec<_>>();
+    let ipv6_all = (0..nodes_count)
+        .map(|i| Ipv6Addr::new(0x2a00, 0, 0, 0, 0, 0, 0, i.into()))
+        .collect::<Vec<_>>();
+    for ip in ipv4_all.iter().map(IpAddr::from).chain(ipv6_all.iter().map(IpAddr::from)) {
+        handle_command(
+            &auth,
+            DnsServerCommand::AddAddress(ip, NodeType::Full.into()),
+        );
+    }
+
+    let filtered_host: Name = "x1.seed.mintlayer.org.".parse().unwrap();
+    for name in [&host, &filtered_host] {
+        for query_type in [RecordType::A, RecordType::AAAA] {
+            let records = auth
+                .lookup(&name.clone().into(), query_type, Default::default())
+                .await
+                .unwrap()
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>();
+
+            // Only a random subset of the nodes is returned
+            assert_eq!(records.len(), max_records);
+            assert!(records.iter().all(|record| record.ttl() == record_ttl));
+
+            let mut ips = records
+                .iter()
+                .map(|record| match record.data() {
+                    Some(RData::A(ip)) => IpAddr::from(*ip),
+                    Some(RData::AAAA(ip)) => IpAddr::from(*ip),
+                    data => panic!("unexpected record data: {data:?}"),
+                })
+                .collect::<Vec<_>>();
+            assert!(ips.iter().all(|ip| match ip {
+                IpAddr::V4(ip) => ipv4_all.contains(ip),
+                IpAddr::V6(ip) => ipv6_all.contains(ip),
+            }));
+            ips.sort();
+            ips.dedup();
+            assert_eq!(ips.len(), max_records);
+        }
+    }
+}
@@ -58,6 +58,7 @@ async fn run(config: Arc<DnsServerConfig>) -> Result<Never, error::DnsServerErro
         boot_nodes: Vec::new(),
         reserved_nodes: Vec::new(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -75,6 +76,12 @@ async fn run(config: Arc<DnsServerConfig>) -> Result<Never, error::DnsServerErro
         max_singular_unconnected_headers: Default::default(),
         sync_stalling_timeout: Default::default(),
         enable_block_relay_peers: Default::default(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
     });
 
     let transport = p2p::make_p2p_transport();
@@ -112,6 +119,7 @@ async fn run(config: Arc<DnsServerConfig>) -> Result<Never, error::DnsServerErro
     let crawler_mgr_config = CrawlerManagerConfig {
         reserved_nodes: config.reserved_node.clone(),
         default_p2p_port: chain_config.p2p_port(),
+        disable_ipv6: config.disable_ipv6,
     };
 
     let crawler_config = CrawlerConfig {
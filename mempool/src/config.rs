@@ -15,7 +15,9 @@
 
 use std::time::Duration;
 
-use common::primitives::BlockDistance;
+use common::primitives::{Amount, BlockDistance};
+
+use crate::pool::FeeRate;
 
 /// Mempool size configuration
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Clone, Copy)]
@@ -43,7 +45,59 @@ impl std::fmt::Display for MempoolMaxSize {
     }
 }
 
-pub const ENABLE_RBF: bool = false;
+/// Limits on the rate at which a single peer may submit transactions to the mempool
+///
+/// The limits are enforced using a token bucket, so a peer may send a burst of up to
+/// `burst_period` worth of transactions at once.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct PeerTxRateLimit {
+    /// Maximum sustained number of transactions per second
+    pub txs_per_sec: u32,
+
+    /// Maximum sustained number of transaction bytes per second
+    pub bytes_per_sec: usize,
+
+    /// The period the burst allowance is computed from
+    pub burst_period: Duration,
+}
+
+impl Default for PeerTxRateLimit {
+    fn default() -> Self {
+        Self {
+            txs_per_sec: DEFAULT_PEER_TXS_PER_SEC,
+            bytes_per_sec: DEFAULT_PEER_TX_BYTES_PER_SEC,
+            burst_period: DEFAULT_PEER_TX_BURST_PERIOD,
+        }
+    }
+}
+
+/// Mempool configuration
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct MempoolConfig {
+    /// Limits on the rate at which each peer may submit transactions
+    pub peer_tx_rate_limit: PeerTxRateLimit,
+
+    /// Whether transactions signaling replaceability may be replaced by conflicting ones
+    pub enable_rbf: bool,
+
+    /// By how much a replacement received from a peer has to exceed the fee rate of the
+    /// transactions it replaces
+    pub rbf_fee_rate_bump: FeeRate,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        Self {
+            peer_tx_rate_limit: PeerTxRateLimit::default(),
+            enable_rbf: DEFAULT_ENABLE_RBF,
+            rbf_fee_rate_bump: DEFAULT_RBF_FEE_RATE_BUMP,
+        }
+    }
+}
+
+pub const DEFAULT_ENABLE_RBF: bool = false;
+
+pub const DEFAULT_RBF_FEE_RATE_BUMP: FeeRate = FeeRate::new(Amount::from_atoms(1000));
 
 // Number of times we try to add transaction if the tip moves during validation
 pub const MAX_TX_ADDITION_ATTEMPTS: usize = 3;
@@ -72,3 +126,9 @@ pub const MAX_ORPHAN_ACCOUNT_GAP: u64 = 2;
 pub const FUTURE_TIMELOCK_TOLERANCE: Duration = Duration::from_secs(5 * 60);
 
 pub const FUTURE_TIMELOCK_TOLERANCE_BLOCKS: BlockDistance = BlockDistance::new(5);
+
+pub const DEFAULT_PEER_TXS_PER_SEC: u32 = 100;
+
+pub const DEFAULT_PEER_TX_BYTES_PER_SEC: usize = 1_000_000;
+
+pub const DEFAULT_PEER_TX_BURST_PERIOD: Duration = Duration::from_secs(10);
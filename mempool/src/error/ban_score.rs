@@ -46,7 +46,9 @@ impl MempoolBanScore for MempoolPolicyError {
             // Basic transaction integrity checks failed, ban peer.
             MempoolPolicyError::NoInputs => 100,
             MempoolPolicyError::NoOutputs => 100,
+            MempoolPolicyError::DuplicateInput(_) => 100,
             MempoolPolicyError::ExceedsMaxBlockSize => 100,
+            MempoolPolicyError::NoSignatureDataSizeTooLarge(_, _) => 100,
             MempoolPolicyError::RelayFeeOverflow => 100,
 
             // Errors to do with transaction conflicts and replacements are not punished since the
@@ -59,6 +61,7 @@ impl MempoolBanScore for MempoolPolicyError {
             MempoolPolicyError::TransactionFeeLowerThanConflictsWithDescendants => 0,
             MempoolPolicyError::ReplacementFeeLowerThanOriginal { .. } => 0,
             MempoolPolicyError::AdditionalFeesUnderflow => 0,
+            MempoolPolicyError::ReplacementFeeRateBumpTooLow { .. } => 0,
 
             // The peer should not pass transactions not meeting the minimal fee threshold
             MempoolPolicyError::InsufficientFeesToRelay { .. } => 100,
@@ -74,6 +77,10 @@ impl MempoolBanScore for MempoolPolicyError {
             MempoolPolicyError::FeeOverflow => 0,
             MempoolPolicyError::GetParentError => 0,
             MempoolPolicyError::DescendantOfExpiredTransaction => 0,
+
+            // The peer is flooding us with transactions. Penalize it, but not so much that
+            // a single burst gets it banned.
+            MempoolPolicyError::PeerRateLimitExceeded(_) => 20,
         }
     }
 }
@@ -138,6 +145,7 @@ impl MempoolBanScore for ConnectTransactionError {
             ConnectTransactionError::PoolOwnerRewardCannotExceedTotalReward(_, _, _, _) => 100,
             ConnectTransactionError::UnexpectedPoolId(_, _) => 100,
             ConnectTransactionError::NotEnoughPledgeToCreateStakePool(_, _, _) => 100,
+            ConnectTransactionError::StakePoolVrfKeyAlreadyInUse(_, _) => 0,
             ConnectTransactionError::AttemptToCreateStakePoolFromAccounts => 100,
             ConnectTransactionError::AttemptToCreateDelegationFromAccounts => 100,
             ConnectTransactionError::OutputTimelockError(err) => err.ban_score(),
@@ -151,6 +159,7 @@ impl MempoolBanScore for ConnectTransactionError {
             ConnectTransactionError::TxNumWrongInBlockOnConnect(_, _) => 0,
             ConnectTransactionError::TxNumWrongInBlockOnDisconnect(_, _) => 0,
             ConnectTransactionError::FailedToAddAllFeesOfBlock(_) => 0,
+            ConnectTransactionError::RewardOutputsTotalOverflow(_) => 0,
 
             // Internal errors, not peer's fault
             ConnectTransactionError::InvariantBrokenAlreadyUnspent => 0,
@@ -171,6 +180,7 @@ impl MempoolBanScore for ConnectTransactionError {
             ConnectTransactionError::DelegationRewardOverflow(_, _, _, _) => 0,
             ConnectTransactionError::DistributedDelegationsRewardExceedTotal(_, _, _, _) => 0,
             ConnectTransactionError::BlockRewardInputOutputMismatch(_, _) => 0,
+            ConnectTransactionError::BlockRewardAmountMismatch(_, _) => 0,
             ConnectTransactionError::TotalDelegationBalanceZero(_) => 0,
             ConnectTransactionError::DelegationDataNotFound(_) => 0,
             ConnectTransactionError::DelegationBalanceNotFound(_) => 0,
@@ -21,11 +21,14 @@ use subsystem::error::CallError;
 use thiserror::Error;
 
 use common::{
-    chain::{GenBlock, Transaction},
+    chain::{DuplicateInputError, GenBlock, Transaction},
     primitives::{Id, H256},
 };
 
-use crate::pool::fee::Fee;
+use crate::{
+    pool::{fee::Fee, FeeRate},
+    tx_accumulator::TxAccumulatorError,
+};
 
 /// Error related to the construction of transaction sequence for inclusion in a block
 #[derive(Debug, Clone, Error, PartialEq, Eq)]
@@ -40,6 +43,8 @@ pub enum BlockConstructionError {
     Call(#[from] subsystem::error::CallError),
     #[error("User-requested transaction {0} not found in mempool")]
     TxNotFound(Id<Transaction>),
+    #[error("Transaction accumulator error: {0}")]
+    Accumulator(#[from] TxAccumulatorError),
 }
 
 #[derive(Debug, Clone, Error, PartialEq, Eq)]
@@ -62,8 +67,12 @@ pub enum MempoolPolicyError {
     NoInputs,
     #[error("Transaction has no outputs.")]
     NoOutputs,
+    #[error("Transaction has duplicate inputs: {0}")]
+    DuplicateInput(#[from] DuplicateInputError),
     #[error("Transaction exceeds the maximum block size.")]
     ExceedsMaxBlockSize,
+    #[error("NoSignature witness data size {0} exceeds the maximum {1}")]
+    NoSignatureDataSizeTooLarge(usize, usize),
     #[error("Transaction already exists in the mempool.")]
     TransactionAlreadyInMempool,
     #[error("Replacement transaction has fee lower than the original. Replacement fee is {replacement_fee:?}, original fee {original_fee:?}")]
@@ -83,6 +92,11 @@ pub enum MempoolPolicyError {
     InsufficientFeesToRelay { tx_fee: Fee, relay_fee: Fee },
     #[error("Replacement transaction does not pay enough for its bandwidth.")]
     InsufficientFeesToRelayRBF,
+    #[error("Replacement transaction fee rate {replacement_fee_rate:?} is lower than the required {min_fee_rate:?}")]
+    ReplacementFeeRateBumpTooLow {
+        replacement_fee_rate: FeeRate,
+        min_fee_rate: FeeRate,
+    },
     #[error("Rolling fee threshold not met.")]
     RollingFeeThresholdNotMet { minimum_fee: Fee, tx_fee: Fee },
     #[error("Overflow encountered while computing fee with ancestors")]
@@ -97,6 +111,8 @@ pub enum MempoolPolicyError {
     DescendantOfExpiredTransaction,
     #[error("Relay fee overflow error")]
     RelayFeeOverflow,
+    #[error("Peer {0} exceeded the transaction admission rate limit")]
+    PeerRateLimitExceeded(p2p_types::PeerId),
 }
 
 #[derive(Debug, Clone, Error, PartialEq, Eq)]
@@ -29,23 +29,38 @@ pub struct TransactionProcessed {
     tx_id: Id<Transaction>,
     origin: TxOrigin,
     result: crate::Result<()>,
+    replaced: Vec<Id<Transaction>>,
 }
 
 impl TransactionProcessed {
-    fn new(tx_id: Id<Transaction>, origin: TxOrigin, result: crate::Result<()>) -> Self {
+    fn new(
+        tx_id: Id<Transaction>,
+        origin: TxOrigin,
+        result: crate::Result<()>,
+        replaced: Vec<Id<Transaction>>,
+    ) -> Self {
         Self {
             tx_id,
             origin,
             result,
+            replaced,
         }
     }
 
     pub fn accepted(tx_id: Id<Transaction>, origin: TxOrigin) -> Self {
-        Self::new(tx_id, origin, Ok(()))
+        Self::accepted_replacing(tx_id, origin, Vec::new())
+    }
+
+    pub fn accepted_replacing(
+        tx_id: Id<Transaction>,
+        origin: TxOrigin,
+        replaced: Vec<Id<Transaction>>,
+    ) -> Self {
+        Self::new(tx_id, origin, Ok(()), replaced)
     }
 
     pub fn rejected(tx_id: Id<Transaction>, err: Error, origin: TxOrigin) -> Self {
-        Self::new(tx_id, origin, Err(err))
+        Self::new(tx_id, origin, Err(err), Vec::new())
     }
 
     pub fn result(&self) -> &crate::Result<()> {
@@ -67,6 +82,11 @@ impl TransactionProcessed {
     pub fn origin(&self) -> TxOrigin {
         self.origin
     }
+
+    /// Transactions evicted from the mempool because this transaction replaced them
+    pub fn replaced_txs(&self) -> &[Id<Transaction>] {
+        &self.replaced
+    }
 }
 
 /// Event triggered when mempool has synced up to given tip
@@ -18,13 +18,13 @@ use crate::{
     event::MempoolEvent,
     tx_accumulator::{PackingStrategy, TransactionAccumulator},
     tx_origin::{LocalTxOrigin, RemoteTxOrigin},
-    FeeRate, MempoolMaxSize, TxStatus,
+    FeeRate, FeeRateEstimate, MempoolMaxSize, TxFeeRateInfo, TxStatus,
 };
 use common::{
     chain::{GenBlock, SignedTransaction, Transaction},
     primitives::Id,
 };
-use std::sync::Arc;
+use std::{num::NonZeroUsize, sync::Arc};
 
 pub trait MempoolInterface: Send + Sync {
     /// Add a transaction from remote peer to mempool
@@ -44,6 +44,13 @@ pub trait MempoolInterface: Send + Sync {
     /// Get all transactions from mempool
     fn get_all(&self) -> Vec<SignedTransaction>;
 
+    /// Get the number of transactions in the main mempool (non-orphan)
+    fn transaction_count(&self) -> usize;
+
+    /// Get all transactions from mempool in the order they would be put into a block, i.e. by
+    /// decreasing fee rate (taking the in-mempool ancestors into account), parents first
+    fn get_all_by_fee_rate(&self) -> Vec<SignedTransaction>;
+
     /// Get a specific transaction from the main mempool (non-orphan)
     fn transaction(&self, id: &Id<Transaction>) -> Option<SignedTransaction>;
 
@@ -83,6 +90,14 @@ pub trait MempoolInterface: Send + Sync {
     /// making it less likely to get rejected or trimmed in the case the mempool is full
     fn get_fee_rate(&self, in_top_x_mb: usize) -> Result<FeeRate, Error>;
 
+    /// Estimate the fee rate needed for a new transaction to get confirmed within the given
+    /// number of blocks. Falls back to the minimum relay fee rate if the mempool lacks data.
+    fn estimate_fee_rate(&self, target_blocks: NonZeroUsize) -> Result<FeeRateEstimate, Error>;
+
+    /// Get the fees of up to `count` transactions that would be put into a block first,
+    /// i.e. in the order of decreasing fee rate (taking the in-mempool ancestors into account)
+    fn get_top_fee_rates(&self, count: usize) -> Result<Vec<TxFeeRateInfo>, Error>;
+
     /// Notify mempool given peer has disconnected
     fn notify_peer_disconnected(&mut self, peer_id: p2p_types::PeerId);
 
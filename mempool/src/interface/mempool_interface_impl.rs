@@ -19,7 +19,8 @@ use crate::{
     pool::memory_usage_estimator::StoreMemoryUsageEstimator,
     tx_accumulator::{PackingStrategy, TransactionAccumulator},
     tx_origin::{LocalTxOrigin, RemoteTxOrigin},
-    FeeRate, MempoolInterface, MempoolMaxSize, TxStatus,
+    FeeRate, FeeRateEstimate, MempoolConfig, MempoolInterface, MempoolMaxSize, TxFeeRateInfo,
+    TxStatus,
 };
 use common::{
     chain::{ChainConfig, GenBlock, SignedTransaction, Transaction},
@@ -27,7 +28,7 @@ use common::{
     time_getter::TimeGetter,
 };
 use logging::log;
-use std::sync::Arc;
+use std::{num::NonZeroUsize, sync::Arc};
 use utils::tap_error_log::LogError;
 
 type Mempool = crate::pool::Mempool<StoreMemoryUsageEstimator>;
@@ -37,6 +38,7 @@ type Mempool = crate::pool::Mempool<StoreMemoryUsageEstimator>;
 /// Contains all the information required to spin up the mempool subsystem
 pub struct MempoolInit {
     chain_config: Arc<ChainConfig>,
+    mempool_config: MempoolConfig,
     chainstate_handle: chainstate::ChainstateHandle,
     time_getter: TimeGetter,
 }
@@ -44,11 +46,13 @@ pub struct MempoolInit {
 impl MempoolInit {
     fn new(
         chain_config: Arc<ChainConfig>,
+        mempool_config: MempoolConfig,
         chainstate_handle: chainstate::ChainstateHandle,
         time_getter: TimeGetter,
     ) -> Self {
         Self {
             chain_config,
+            mempool_config,
             chainstate_handle,
             time_getter,
         }
@@ -59,12 +63,20 @@ impl MempoolInit {
         this: subsystem::SubmitOnlyHandle<dyn MempoolInterface>,
     ) -> Result<MempoolImpl, subsystem::error::CallError> {
         log::info!("Starting mempool");
-        let mempool = Mempool::new(
+        let MempoolConfig {
+            peer_tx_rate_limit,
+            enable_rbf,
+            rbf_fee_rate_bump,
+        } = self.mempool_config;
+        let mut mempool = Mempool::new(
             self.chain_config,
             self.chainstate_handle,
             self.time_getter,
             StoreMemoryUsageEstimator,
         );
+        mempool.set_peer_tx_rate_limit(peer_tx_rate_limit);
+        mempool.set_rbf_enabled(enable_rbf);
+        mempool.set_rbf_fee_rate_bump(rbf_fee_rate_bump);
         let mempool = MempoolImpl::new(mempool);
 
         log::trace!("Subscribing to chainstate events");
@@ -147,6 +159,14 @@ impl MempoolInterface for MempoolImpl {
         self.mempool.get_all()
     }
 
+    fn transaction_count(&self) -> usize {
+        self.mempool.transaction_count()
+    }
+
+    fn get_all_by_fee_rate(&self) -> Vec<SignedTransaction> {
+        self.mempool.txs_by_ancestor_score().cloned().collect()
+    }
+
     fn contains_transaction(&self, tx_id: &Id<Transaction>) -> bool {
         self.mempool.contains_transaction(tx_id)
     }
@@ -196,6 +216,14 @@ impl MempoolInterface for MempoolImpl {
         Ok(self.mempool.get_fee_rate(in_top_x_mb)?)
     }
 
+    fn estimate_fee_rate(&self, target_blocks: NonZeroUsize) -> Result<FeeRateEstimate, Error> {
+        Ok(self.mempool.estimate_fee_rate(target_blocks)?)
+    }
+
+    fn get_top_fee_rates(&self, count: usize) -> Result<Vec<TxFeeRateInfo>, Error> {
+        Ok(self.mempool.top_txs_fee_rates(count)?)
+    }
+
     fn notify_peer_disconnected(&mut self, peer_id: p2p_types::PeerId) {
         self.mempool.on_peer_disconnected(peer_id);
         self.work_queue.remove_peer(peer_id);
@@ -229,8 +257,9 @@ impl subsystem::Subsystem for MempoolImpl {
 /// Mempool constructor
 pub fn make_mempool(
     chain_config: Arc<ChainConfig>,
+    mempool_config: MempoolConfig,
     chainstate_handle: chainstate::ChainstateHandle,
     time_getter: TimeGetter,
 ) -> MempoolInit {
-    MempoolInit::new(chain_config, chainstate_handle, time_getter)
+    MempoolInit::new(chain_config, mempool_config, chainstate_handle, time_getter)
 }
@@ -15,7 +15,7 @@
 
 #![deny(clippy::clone_on_ref_ptr)]
 
-pub use config::MempoolMaxSize;
+pub use config::{MempoolConfig, MempoolMaxSize, PeerTxRateLimit};
 pub use interface::{make_mempool, MempoolInterface};
 pub use mempool_types::{tx_origin, TxStatus};
 
@@ -27,7 +27,7 @@ mod pool;
 pub mod rpc;
 pub mod tx_accumulator;
 
-pub use pool::FeeRate;
+pub use pool::{FeeRate, FeeRateEstimate, TxFeeRateInfo};
 
 pub type MempoolHandle = subsystem::Handle<dyn MempoolInterface>;
 
@@ -15,20 +15,21 @@
 
 use crate::{
     error::{BlockConstructionError, TxValidationError},
-    pool::{tx_verifier, Mempool, TxMempoolEntry},
-    tx_accumulator::{PackingStrategy, TransactionAccumulator},
+    pool::{fee::Fee, store::MempoolStore, tx_verifier, Mempool, TxMempoolEntry},
+    tx_accumulator::{PackingStrategy, TransactionAccumulator, TxAccumulatorError},
 };
 
 use std::{
     cmp::Ordering,
-    collections::{binary_heap, btree_map, BTreeMap, BTreeSet, BinaryHeap},
+    collections::{btree_map, BTreeMap, BTreeSet, BinaryHeap},
+    iter::Peekable,
     ops::Deref,
 };
 
 use chainstate::tx_verifier::transaction_verifier::TransactionSourceForConnect;
 use common::{
     chain::transaction::Transaction,
-    primitives::{Id, Idable},
+    primitives::{Amount, Id, Idable},
 };
 use logging::log;
 use utils::{ensure, graph_traversals, shallow_clone::ShallowClone};
@@ -66,6 +67,116 @@ impl<'a> From<&'a TxMempoolEntry> for EntryByScore<'a> {
     }
 }
 
+/// Iterates over the mempool transactions in the descending order of the ancestor score, i.e.
+/// the fee rate of a transaction together with its in-mempool ancestors. A transaction is only
+/// emitted after all its in-mempool parents, so any prefix of the sequence is a valid order
+/// for a block.
+pub struct TxsByAncestorScore<'a> {
+    store: &'a MempoolStore,
+    by_score: Peekable<Box<dyn Iterator<Item = &'a TxMempoolEntry> + 'a>>,
+    // Transactions emitted so far
+    emitted: BTreeSet<&'a Id<Transaction>>,
+    // The transaction emitted last, its children are released on the next call unless rejected
+    last: Option<&'a TxMempoolEntry>,
+    // Transactions waiting for the given number of parents to be emitted
+    pending: BTreeMap<&'a Id<Transaction>, usize>,
+    // Transactions whose parents have all been emitted
+    ready: BinaryHeap<EntryByScore<'a>>,
+}
+
+impl<'a> TxsByAncestorScore<'a> {
+    pub fn new(store: &'a MempoolStore) -> Self {
+        let by_score = store
+            .txs_by_ancestor_score
+            .iter()
+            .rev()
+            .map(|(_score, tx_id)| store.txs_by_id[tx_id].deref());
+
+        Self::with_source(store, by_score, BTreeSet::new())
+    }
+
+    /// Iterate over the given transactions, which must be sorted by the ancestor score except
+    /// that parents may precede their children. The transactions in `emitted` count as
+    /// already emitted parents.
+    pub fn with_source(
+        store: &'a MempoolStore,
+        source: impl Iterator<Item = &'a TxMempoolEntry> + 'a,
+        emitted: BTreeSet<&'a Id<Transaction>>,
+    ) -> Self {
+        let by_score: Box<dyn Iterator<Item = &'a TxMempoolEntry> + 'a> = Box::new(source.fuse());
+
+        Self {
+            store,
+            by_score: by_score.peekable(),
+            emitted,
+            last: None,
+            pending: BTreeMap::new(),
+            ready: BinaryHeap::new(),
+        }
+    }
+
+    /// Don't count the transaction returned last as emitted, so none of its descendants
+    /// is returned either.
+    pub fn reject_last(&mut self) {
+        self.last = None;
+    }
+
+    fn release_children(&mut self, tx: &'a TxMempoolEntry) {
+        self.emitted.insert(tx.tx_id());
+
+        for child in tx.children() {
+            match self.pending.entry(child) {
+                btree_map::Entry::Vacant(_) => (),
+                btree_map::Entry::Occupied(mut c) => match c.get_mut() {
+                    0 => panic!("pending with 0 missing parents"),
+                    1 => {
+                        // This was the last missing parent, put the tx into the ready queue
+                        self.ready.push(self.store.txs_by_id[c.key()].deref().into());
+                        c.remove();
+                    }
+                    n => *n -= 1,
+                },
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for TxsByAncestorScore<'a> {
+    type Item = &'a TxMempoolEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(last) = self.last.take() {
+            self.release_children(last);
+        }
+
+        // Set aside the transactions that still miss a parent
+        while let Some(tx) = self.by_score.peek() {
+            let missing_parents = tx.parents().filter(|p| !self.emitted.contains(p)).count();
+            if missing_parents == 0 {
+                break;
+            }
+            self.pending.insert(tx.tx_id(), missing_parents);
+            let _ = self.by_score.next();
+        }
+
+        let next_tx = match (self.by_score.peek(), self.ready.peek()) {
+            (Some(store_tx), Some(ready_tx)) => {
+                if store_tx.ancestor_score() > ready_tx.ancestor_score() {
+                    self.by_score.next().expect("just checked")
+                } else {
+                    self.ready.pop().expect("just checked").entry
+                }
+            }
+            (Some(_store_tx), None) => self.by_score.next().expect("just checked"),
+            (None, Some(_ready_tx)) => self.ready.pop().expect("just checked").entry,
+            (None, None) => return None,
+        };
+
+        self.last = Some(next_tx);
+        Some(next_tx)
+    }
+}
+
 pub fn collect_txs<M>(
     mempool: &Mempool<M>,
     mut tx_accumulator: Box<dyn TransactionAccumulator>,
@@ -99,18 +210,23 @@ pub fn collect_txs<M>(
 
     // Use transactions already in the Accumulator to check for uniqueness and to update the
     // verifier state to update UTXOs they consume / provide.
-    let accum_ids = tx_accumulator
-        .transactions()
-        .iter()
-        .map(|transaction| {
-            let _fee =
-                tx_verifier.connect_transaction(&tx_source, transaction, &block_timestamp, None)?;
-            Ok(transaction.transaction().get_id())
-        })
-        .collect::<Result<Vec<_>, TxValidationError>>()?;
+    let mut accum_ids = Vec::with_capacity(tx_accumulator.transactions().len());
+    let mut accum_fees = Fee::new(Amount::ZERO);
+    for transaction in tx_accumulator.transactions() {
+        let fee: Fee = tx_verifier
+            .connect_transaction(&tx_source, transaction, &block_timestamp, None)
+            .map_err(TxValidationError::from)?
+            .into();
+        accum_fees =
+            (accum_fees + fee).ok_or(TxAccumulatorError::FeeAccumulationError(accum_fees, fee))?;
+        accum_ids.push(transaction.transaction().get_id());
+    }
+
+    // Transactions already in the Accumulator were added before their fees were known
+    tx_accumulator.add_fees(accum_fees)?;
 
     // Set of transactions already placed into the accumulator
-    let mut emitted: BTreeSet<_> = accum_ids.iter().collect();
+    let emitted: BTreeSet<_> = accum_ids.iter().collect();
     // Set of already processed transactions, for de-duplication
     let mut processed = emitted.clone();
 
@@ -140,54 +256,28 @@ pub fn collect_txs<M>(
     };
 
     // Put all the transaction IDs together
-    let mut tx_iter = given_txids
-        .chain(mempool_txids)
-        .filter_map(|tx_id| {
-            // If the transaction with this ID has already been processed, skip it
-            ensure!(processed.insert(tx_id));
-            let tx = mempool.store.txs_by_id.get(tx_id).expect("already checked").deref();
-            let timelock_check = chainstate::tx_verifier::timelock_check::check_timelocks(
-                &chainstate,
-                chain_config,
-                &utxo_view,
-                tx.transaction(),
-                &tx_source,
-                &block_timestamp,
-            );
-            ensure!(timelock_check.is_ok());
-            Some(tx)
-        })
-        .fuse()
-        .peekable();
+    let source_txs = given_txids.chain(mempool_txids).filter_map(|tx_id| {
+        // If the transaction with this ID has already been processed, skip it
+        ensure!(processed.insert(tx_id));
+        let tx = mempool.store.txs_by_id.get(tx_id).expect("already checked").deref();
+        let timelock_check = chainstate::tx_verifier::timelock_check::check_timelocks(
+            &chainstate,
+            chain_config,
+            &utxo_view,
+            tx.transaction(),
+            &tx_source,
+            &block_timestamp,
+        );
+        ensure!(timelock_check.is_ok());
+        Some(tx)
+    });
 
-    // Set of transaction waiting for one or more parents to be emitted
-    let mut pending = BTreeMap::new();
-    // A queue of transactions that can be emitted
-    let mut ready = BinaryHeap::<EntryByScore>::new();
+    let mut txs = TxsByAncestorScore::with_source(&mempool.store, source_txs, emitted);
 
     while !tx_accumulator.done() {
-        // Take out the transactions from tx_iter until there is one ready
-        while let Some(tx) = tx_iter.peek() {
-            let missing_parents: usize = tx.parents().filter(|p| !emitted.contains(p)).count();
-            if missing_parents == 0 {
-                break;
-            } else {
-                pending.insert(tx.tx_id(), missing_parents);
-                let _ = tx_iter.next();
-            }
-        }
-
-        let next_tx = match (tx_iter.peek(), ready.peek_mut()) {
-            (Some(store_tx), Some(ready_tx)) => {
-                if store_tx.ancestor_score() > ready_tx.ancestor_score() {
-                    tx_iter.next().expect("just checked")
-                } else {
-                    binary_heap::PeekMut::pop(ready_tx).entry
-                }
-            }
-            (Some(_store_tx), None) => tx_iter.next().expect("just checked"),
-            (None, Some(ready_tx)) => binary_heap::PeekMut::pop(ready_tx).entry,
-            (None, None) => break,
+        let next_tx = match txs.next() {
+            Some(tx) => tx,
+            None => break,
         };
 
         let verification_result = tx_verifier.connect_transaction(
@@ -204,6 +294,7 @@ pub fn collect_txs<M>(
                 "CRITICAL: Verifier and mempool do not agree on transaction deps for {}: {err}",
                 next_tx.tx_id()
             );
+            txs.reject_last();
             continue;
         }
 
@@ -214,24 +305,6 @@ pub fn collect_txs<M>(
             );
             break;
         }
-
-        emitted.insert(next_tx.tx_id());
-
-        // Release newly ready transactions
-        for child in next_tx.children() {
-            match pending.entry(child) {
-                btree_map::Entry::Vacant(_) => (),
-                btree_map::Entry::Occupied(mut c) => match c.get_mut() {
-                    0 => panic!("pending with 0 missing parents"),
-                    1 => {
-                        // This was the last missing parent, put the tx into the ready queue
-                        ready.push(mempool.store.txs_by_id[c.key()].deref().into());
-                        c.remove();
-                    }
-                    n => *n -= 1,
-                },
-            }
-        }
     }
 
     let final_chainstate_tip =
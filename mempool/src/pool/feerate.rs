@@ -16,7 +16,10 @@
 use serde::{Deserialize, Serialize};
 use std::num::NonZeroUsize;
 
-use common::primitives::amount::Amount;
+use common::{
+    chain::Transaction,
+    primitives::{amount::Amount, Id},
+};
 
 use crate::error::MempoolPolicyError;
 
@@ -60,6 +63,48 @@ impl FeeRate {
     }
 }
 
+/// Fee rate estimate for getting a transaction confirmed within a number of blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeRateEstimate {
+    fee_rate: FeeRate,
+    is_fallback: bool,
+}
+
+impl FeeRateEstimate {
+    pub fn from_mempool(fee_rate: FeeRate) -> Self {
+        Self {
+            fee_rate,
+            is_fallback: false,
+        }
+    }
+
+    /// There are not enough transactions in the mempool to fill the target blocks,
+    /// so the minimum relay fee rate is used instead
+    pub fn fallback(min_relay_fee_rate: FeeRate) -> Self {
+        Self {
+            fee_rate: min_relay_fee_rate,
+            is_fallback: true,
+        }
+    }
+
+    pub fn fee_rate(&self) -> FeeRate {
+        self.fee_rate
+    }
+
+    pub fn is_fallback(&self) -> bool {
+        self.is_fallback
+    }
+}
+
+/// The fee and the fee rate of a transaction in the mempool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxFeeRateInfo {
+    pub tx_id: Id<Transaction>,
+    pub size: usize,
+    pub fee: Amount,
+    pub fee_rate: FeeRate,
+}
+
 impl std::ops::Add for FeeRate {
     type Output = Option<Self>;
     fn add(self, other: Self) -> Self::Output {
@@ -26,7 +26,8 @@ use chainstate::{
 };
 use common::{
     chain::{
-        block::timestamp::BlockTimestamp, Block, ChainConfig, GenBlock, SignedTransaction,
+        block::timestamp::BlockTimestamp, check_duplicate_inputs,
+        signature::inputsig::InputWitness, Block, ChainConfig, GenBlock, SignedTransaction,
         Transaction,
     },
     primitives::{amount::Amount, time::Time, BlockHeight, Id},
@@ -36,13 +37,14 @@ use logging::log;
 use serialization::Encode;
 use utils::{ensure, eventhandler::EventsController, shallow_clone::ShallowClone};
 
-pub use self::feerate::FeeRate;
+pub use self::feerate::{FeeRate, FeeRateEstimate, TxFeeRateInfo};
 pub use self::memory_usage_estimator::MemoryUsageEstimator;
 use self::{
     entry::{TxDependency, TxEntry, TxEntryWithFee},
     fee::Fee,
     feerate::{INCREMENTAL_RELAY_FEE_RATE, INCREMENTAL_RELAY_THRESHOLD},
     orphans::{OrphanType, TxOrphanPool},
+    peer_rate_limit::PeerRateLimiter,
     rolling_fee_rate::RollingFeeRate,
     spends_unconfirmed::SpendsUnconfirmed,
     store::{Conflicts, MempoolRemovalReason, MempoolStore, TxMempoolEntry},
@@ -67,6 +69,7 @@ pub mod fee;
 mod feerate;
 pub mod memory_usage_estimator;
 mod orphans;
+mod peer_rate_limit;
 mod reorg;
 mod rolling_fee_rate;
 mod spends_unconfirmed;
@@ -94,6 +97,9 @@ pub struct Mempool<M> {
     events_controller: EventsController<MempoolEvent>,
     tx_verifier: tx_verifier::TransactionVerifier,
     orphans: TxOrphanPool,
+    peer_rate_limiter: PeerRateLimiter,
+    rbf_enabled: bool,
+    rbf_fee_rate_bump: FeeRate,
 }
 
 impl<M> std::fmt::Debug for Mempool<M> {
@@ -128,6 +134,9 @@ impl<M> Mempool<M> {
             events_controller: Default::default(),
             tx_verifier,
             orphans: TxOrphanPool::new(),
+            peer_rate_limiter: PeerRateLimiter::new(PeerTxRateLimit::default()),
+            rbf_enabled: DEFAULT_ENABLE_RBF,
+            rbf_fee_rate_bump: DEFAULT_RBF_FEE_RATE_BUMP,
         }
     }
 
@@ -155,7 +164,7 @@ impl<M> Mempool<M> {
         // Clear the orphan pool, returning the list of previous transactions.
         let orphan_txs = mem::replace(&mut self.orphans, TxOrphanPool::new()).into_transactions();
 
-        pool_txs.chain(orphan_txs.map(|entry| entry.map_origin(TxOrigin::from)))
+        pool_txs.chain(orphan_txs.map(|entry| entry.map_origin(TxOrigin::Orphan)))
     }
 
     pub fn best_block_id(&self) -> Id<GenBlock> {
@@ -166,6 +175,33 @@ impl<M> Mempool<M> {
     pub fn max_size(&self) -> MempoolMaxSize {
         self.max_size
     }
+
+    pub fn peer_tx_rate_limit(&self) -> PeerTxRateLimit {
+        self.peer_rate_limiter.limit()
+    }
+
+    pub fn set_peer_tx_rate_limit(&mut self, limit: PeerTxRateLimit) {
+        self.peer_rate_limiter.set_limit(limit);
+    }
+
+    /// Whether transactions in the mempool may be replaced by conflicting ones paying more fees
+    pub fn rbf_enabled(&self) -> bool {
+        self.rbf_enabled
+    }
+
+    pub fn set_rbf_enabled(&mut self, enabled: bool) {
+        self.rbf_enabled = enabled;
+    }
+
+    /// The minimum fee rate increase over the replaced transactions required from replacements
+    /// received from peers. Locally submitted replacements are not subject to this limit.
+    pub fn rbf_fee_rate_bump(&self) -> FeeRate {
+        self.rbf_fee_rate_bump
+    }
+
+    pub fn set_rbf_fee_rate_bump(&mut self, fee_rate_bump: FeeRate) {
+        self.rbf_fee_rate_bump = fee_rate_bump;
+    }
 }
 
 // Rolling-fee-related methods
@@ -330,7 +366,9 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
 
         self.check_preliminary_mempool_policy(&transaction)?;
 
-        let outcome = match self.verify_transaction(transaction)? {
+        let replaceable_conflicts = self.replaceable_conflicts_postorder(&transaction);
+
+        let outcome = match self.verify_transaction(transaction, &replaceable_conflicts)? {
             VerificationOutcome::Valid { transaction, delta } => {
                 let conflicts = self.check_mempool_policy(&transaction)?;
                 ValidationOutcome::Valid {
@@ -352,9 +390,14 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
     }
 
     /// Verify transaction according to consensus rules
+    ///
+    /// The transactions in `replaced` are disconnected before the new transaction is verified,
+    /// so that the inputs they spend are available to a potential replacement. They have to be
+    /// given in an order where descendants come before their ancestors.
     fn verify_transaction(
         &self,
         transaction: TxEntry,
+        replaced: &[Id<Transaction>],
     ) -> Result<VerificationOutcome, TxValidationError> {
         let chainstate_handle = self.blocking_chainstate_handle();
 
@@ -371,6 +414,14 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
 
             let mut tx_verifier = self.tx_verifier.derive_child();
 
+            for replaced_id in replaced {
+                let replaced_entry = self.store.get_entry(replaced_id).expect("entry for id");
+                tx_verifier.disconnect_transaction(
+                    &TransactionSource::Mempool,
+                    replaced_entry.transaction(),
+                )?;
+            }
+
             let verifier_time =
                 self.clock.get_time().saturating_duration_add(config::FUTURE_TIMELOCK_TOLERANCE);
             let effective_height = (current_best.block_height()
@@ -427,11 +478,27 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
             MempoolPolicyError::NoOutputs,
         );
 
+        check_duplicate_inputs(tx.transaction().inputs())?;
+
         ensure!(
             entry.size() <= self.chain_config.max_tx_size_for_mempool(),
             MempoolPolicyError::ExceedsMaxBlockSize,
         );
 
+        // Same as the consensus check, done early to reject the transaction before verification
+        let max_no_sig_data_size = self.chain_config.max_no_signature_data_size();
+        for witness in tx.signatures() {
+            if let InputWitness::NoSignature(Some(data)) = witness {
+                ensure!(
+                    data.len() <= max_no_sig_data_size,
+                    MempoolPolicyError::NoSignatureDataSizeTooLarge(
+                        data.len(),
+                        max_no_sig_data_size
+                    ),
+                );
+            }
+        }
+
         // TODO: Taken from the previous implementation. Is this correct?
         ensure!(
             !self.contains_transaction(tx_id),
@@ -449,7 +516,7 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
         self.pays_minimum_relay_fees(entry)?;
         self.pays_minimum_mempool_fee(entry)?;
 
-        if ENABLE_RBF {
+        if self.rbf_enabled {
             self.rbf_checks(entry)
         } else {
             // Without RBF enabled, any conflicting transaction results in an error
@@ -470,7 +537,7 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
         let transaction = transaction
             .try_map_origin(|origin| match origin {
                 TxOrigin::Local(o) => Err(OrphanPoolError::NotSupportedForLocalOrigin(o)),
-                TxOrigin::Remote(o) => Ok(o),
+                TxOrigin::Remote(o) | TxOrigin::Orphan(o) => Ok(o),
             })
             .map_err(|(_, e)| e)?;
 
@@ -541,6 +608,35 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
 
 // RBF checks
 impl<M: MemoryUsageEstimator> Mempool<M> {
+    /// If all the transactions the given one conflicts with are replaceable, return them together
+    /// with their descendants, ordered so that descendants come before their ancestors.
+    ///
+    /// Returns an empty list if RBF is disabled, if there are no conflicts or if some of them
+    /// cannot be replaced. In the latter cases, the transaction fails verification as if the
+    /// conflicts were not replaced at all.
+    fn replaceable_conflicts_postorder(&self, entry: &TxEntry) -> Vec<Id<Transaction>> {
+        if !self.rbf_enabled {
+            return Vec::new();
+        }
+
+        let conflicts = self
+            .conflicting_tx_ids(entry)
+            .map(|id_conflict| self.store.get_entry(id_conflict).expect("entry for id"))
+            .collect::<Vec<_>>();
+
+        if conflicts.is_empty() || self.all_conflicts_replaceable(&conflicts).is_err() {
+            return Vec::new();
+        }
+
+        let mut visited = BTreeSet::new();
+        conflicts
+            .iter()
+            .flat_map(|conflict| conflict.depth_postorder_descendants(&self.store))
+            .map(|entry| *entry.tx_id())
+            .filter(|tx_id| visited.insert(*tx_id))
+            .collect()
+    }
+
     fn rbf_checks(&self, tx: &TxEntryWithFee) -> Result<Conflicts, MempoolPolicyError> {
         let conflicts = self
             .conflicting_tx_ids(tx.tx_entry())
@@ -558,25 +654,13 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
         &self,
         tx: &TxEntry<O>,
     ) -> Result<(), OrphanPoolError> {
-        let mut conflicts = self.conflicting_tx_ids(tx).peekable();
-        if conflicts.peek().is_none() {
-            // Early exit if there are no conflicts
-            return Ok(());
-        }
-
-        if ENABLE_RBF {
-            // Note: Since RBF is currently disabled, the following is effectively dead code and
-            // completely untested. Needs to be reviewed when RBF is re-enabled.
-            let conflicts: Vec<_> = conflicts
-                .map(|id_conflict| self.store.get_entry(id_conflict).expect("entry for id"))
-                .collect();
-            self.all_conflicts_replaceable(&conflicts)?;
-            self.spends_no_new_unconfirmed_outputs(tx, &conflicts)?;
-            self.potential_replacements_within_limit(&conflicts)?;
-            Ok(())
-        } else {
-            Err(OrphanPoolError::MempoolConflict)
-        }
+        // The fee of an orphan is not known, so it cannot be determined whether it pays enough
+        // to replace the transactions it conflicts with.
+        ensure!(
+            self.conflicting_tx_ids(tx).next().is_none(),
+            OrphanPoolError::MempoolConflict,
+        );
+        Ok(())
     }
 
     fn do_rbf_checks(
@@ -593,6 +677,11 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
         // transactions that would need to be removed (direct conflicts and all descendants), check
         // that the replacement transaction pays more than its direct conflicts.
         self.pays_more_than_direct_conflicts(tx, conflicts)?;
+        // Replacements relayed by other nodes also have to bump the fee rate noticeably, so
+        // that peers cannot make us churn through slightly different versions of a transaction.
+        if tx.tx_entry().origin().remote_origin().is_some() {
+            self.pays_fee_rate_bump(tx, conflicts)?;
+        }
         // Enforce BIP125 Rule #2.
         self.spends_no_new_unconfirmed_outputs(tx.tx_entry(), conflicts)?;
         // Enforce BIP125 Rule #5.
@@ -679,7 +768,7 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
             unconfirmed && new
         });
         ensure!(
-            spends_new_unconfirmed,
+            !spends_new_unconfirmed,
             MempoolConflictError::SpendsNewUnconfirmed,
         );
         Ok(())
@@ -704,6 +793,38 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
         )
     }
 
+    fn pays_fee_rate_bump(
+        &self,
+        tx: &TxEntryWithFee,
+        conflicts: &[&TxMempoolEntry],
+    ) -> Result<(), MempoolPolicyError> {
+        let fee_rate_of = |fee: Fee, size: usize| {
+            FeeRate::from_total_tx_fee(
+                fee,
+                NonZeroUsize::new(size).expect("transaction cannot have zero size"),
+            )
+        };
+
+        let replacement_fee_rate = fee_rate_of(tx.fee(), tx.tx_entry().size())?;
+        let max_conflict_fee_rate = conflicts
+            .iter()
+            .map(|conflict| fee_rate_of(conflict.fee(), conflict.size()))
+            .try_fold(FeeRate::new(Amount::ZERO), |max, rate| {
+                rate.map(|rate| std::cmp::max(max, rate))
+            })?;
+        let min_fee_rate = (max_conflict_fee_rate + self.rbf_fee_rate_bump)
+            .ok_or(MempoolPolicyError::FeeOverflow)?;
+
+        ensure!(
+            replacement_fee_rate >= min_fee_rate,
+            MempoolPolicyError::ReplacementFeeRateBumpTooLow {
+                replacement_fee_rate,
+                min_fee_rate,
+            }
+        );
+        Ok(())
+    }
+
     fn potential_replacements_within_limit(
         &self,
         conflicts: &[&TxMempoolEntry],
@@ -864,6 +985,19 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
         work_queue: &mut WorkQueue,
     ) -> Result<TxStatus, Error> {
         let creation_time = self.clock.get_time();
+
+        // Transactions re-admitted from the orphan pool have already been charged for.
+        if let TxOrigin::Remote(remote_origin) = origin {
+            if let Err(err) = self.peer_rate_limiter.check_and_charge(
+                remote_origin.peer_id(),
+                tx.encoded_size(),
+                creation_time,
+            ) {
+                log::debug!("Transaction rejected: {}", err);
+                return Err(err.into());
+            }
+        }
+
         self.add_transaction_and_descendants(TxEntry::new(tx, creation_time, origin), work_queue)
     }
 
@@ -907,14 +1041,14 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
                 conflicts,
                 delta,
             }) => {
-                if ENABLE_RBF {
-                    self.store.drop_conflicts(conflicts);
-                }
+                // The replaced transactions have already been disconnected in the delta
+                let replaced = self.store.drop_conflicts(conflicts);
                 tx_verifier::flush_to_storage(&mut self.tx_verifier, delta)?;
                 self.finalize_tx(transaction)?;
                 self.store.assert_valid();
 
-                let event = event::TransactionProcessed::accepted(tx_id, origin);
+                let event =
+                    event::TransactionProcessed::accepted_replacing(tx_id, origin, replaced);
                 self.events_controller.broadcast(event.into());
 
                 Ok(TxStatus::InMempool)
@@ -945,6 +1079,10 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
         }
     }
 
+    pub fn transaction_count(&self) -> usize {
+        self.store.len()
+    }
+
     pub fn get_all(&self) -> Vec<SignedTransaction> {
         self.store
             .txs_by_descendant_score
@@ -953,6 +1091,30 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
             .collect()
     }
 
+    /// The transactions in the order of decreasing fee rate with ancestors, parents first
+    pub fn txs_by_ancestor_score(&self) -> impl Iterator<Item = &SignedTransaction> + '_ {
+        collect_txs::TxsByAncestorScore::new(&self.store).map(|entry| entry.transaction())
+    }
+
+    /// The fees of the first `count` transactions in the order of `txs_by_ancestor_score`
+    pub fn top_txs_fee_rates(
+        &self,
+        count: usize,
+    ) -> Result<Vec<TxFeeRateInfo>, MempoolPolicyError> {
+        collect_txs::TxsByAncestorScore::new(&self.store)
+            .take(count)
+            .map(|entry| {
+                let size = NonZeroUsize::new(entry.size()).expect("transaction has nonzero size");
+                Ok(TxFeeRateInfo {
+                    tx_id: *entry.tx_id(),
+                    size: entry.size(),
+                    fee: *entry.fee(),
+                    fee_rate: FeeRate::from_total_tx_fee(entry.fee(), size)?,
+                })
+            })
+            .collect()
+    }
+
     pub fn collect_txs(
         &self,
         tx_accumulator: Box<dyn TransactionAccumulator>,
@@ -995,6 +1157,7 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
 
     pub fn on_peer_disconnected(&mut self, peer_id: p2p_types::PeerId) {
         self.orphans.remove_by_origin(RemoteTxOrigin::new(peer_id));
+        self.peer_rate_limiter.remove_peer(peer_id);
     }
 
     pub fn get_fee_rate(&self, in_top_x_mb: usize) -> Result<FeeRate, MempoolPolicyError> {
@@ -1024,6 +1187,39 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
             .map(|feerate| std::cmp::max(feerate, INCREMENTAL_RELAY_FEE_RATE))
     }
 
+    /// Estimate the fee rate needed for a transaction to be included within `target_blocks`
+    /// blocks, assuming the blocks are filled with the best paying mempool transactions.
+    pub fn estimate_fee_rate(
+        &self,
+        target_blocks: NonZeroUsize,
+    ) -> Result<FeeRateEstimate, MempoolPolicyError> {
+        let min_relay_fee_rate = std::cmp::max(
+            self.rolling_fee_rate.read().rolling_minimum_fee_rate(),
+            INCREMENTAL_RELAY_FEE_RATE,
+        );
+        let target_size = self
+            .chain_config
+            .max_block_size_from_std_scripts()
+            .saturating_mul(target_blocks.get());
+
+        let mut total_size = 0;
+        let last_included =
+            self.store.txs_by_descendant_score.iter().rev().find(|(_score, tx_id)| {
+                total_size += self.store.txs_by_id.get(tx_id).map_or(0, |tx| tx.size());
+                total_size >= target_size
+            });
+
+        match last_included {
+            None => Ok(FeeRateEstimate::fallback(min_relay_fee_rate)),
+            Some((score, _tx_id)) => {
+                let amount = (Amount::from_atoms(score.into_atoms()) * 1000)
+                    .ok_or(MempoolPolicyError::FeeOverflow)?;
+                let fee_rate = std::cmp::max(FeeRate::new(amount), min_relay_fee_rate);
+                Ok(FeeRateEstimate::from_mempool(fee_rate))
+            }
+        }
+    }
+
     pub fn perform_work_unit(&mut self, work_queue: &mut WorkQueue) {
         log::trace!("Performing orphan processing work");
 
@@ -1042,7 +1238,7 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
 
             if orphan.is_ready() {
                 // Take the transaction out of orphan pool and pass it to mempool processing code.
-                let orphan = orphan.take().map_origin(TxOrigin::from);
+                let orphan = orphan.take().map_origin(TxOrigin::Orphan);
                 let result = self.add_transaction_entry(orphan);
                 log::debug!("Orphan tx {orphan_id:?} processed: {result:?}");
             } else {
@@ -47,6 +47,7 @@ impl OrphanType {
             | CTE::TxUndoWithDependency(_)
             | CTE::AttemptToPrintMoney(_, _)
             | CTE::BlockRewardInputOutputMismatch(_, _)
+            | CTE::BlockRewardAmountMismatch(_, _)
             | CTE::TxFeeTotalCalcFailed(_, _)
             | CTE::SignatureVerificationFailed(_)
             | CTE::BlockHeightArithmeticError
@@ -56,6 +57,7 @@ impl OrphanType {
             | CTE::BlockIndexCouldNotBeLoaded(_)
             | CTE::FailedToAddAllFeesOfBlock(_)
             | CTE::RewardAdditionError(_)
+            | CTE::RewardOutputsTotalOverflow(_)
             | CTE::TimeLockViolation(_)
             | CTE::UtxoError(_)
             | CTE::TokensError(_)
@@ -82,6 +84,7 @@ impl OrphanType {
             | CTE::DelegationDataNotFound(_)
             | CTE::OutputTimelockError(_)
             | CTE::NotEnoughPledgeToCreateStakePool(..)
+            | CTE::StakePoolVrfKeyAlreadyInUse(..)
             | CTE::DelegationBalanceNotFound(_)
             | CTE::MissingTransactionNonce(_)
             | CTE::AttemptToCreateStakePoolFromAccounts
@@ -0,0 +1,110 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common::primitives::time::Time;
+use p2p_types::PeerId;
+
+use crate::{config::PeerTxRateLimit, error::MempoolPolicyError};
+
+/// Token bucket of a single peer.
+///
+/// To avoid floating point arithmetic, the budgets are kept in "per mille" units, i.e. one
+/// transaction (or one byte) costs 1000 units and each millisecond refills the per-second rate.
+#[derive(Debug)]
+struct Bucket {
+    txs: u128,
+    bytes: u128,
+    last_update: Time,
+}
+
+/// Limits the rate at which transactions from individual peers are admitted to the mempool
+#[derive(Debug)]
+pub struct PeerRateLimiter {
+    limit: PeerTxRateLimit,
+    buckets: BTreeMap<PeerId, Bucket>,
+}
+
+impl PeerRateLimiter {
+    pub fn new(limit: PeerTxRateLimit) -> Self {
+        Self {
+            limit,
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    pub fn limit(&self) -> PeerTxRateLimit {
+        self.limit
+    }
+
+    pub fn set_limit(&mut self, limit: PeerTxRateLimit) {
+        self.limit = limit;
+        self.buckets.clear();
+    }
+
+    fn txs_capacity(&self) -> u128 {
+        self.limit.txs_per_sec as u128 * self.limit.burst_period.as_millis()
+    }
+
+    fn bytes_capacity(&self) -> u128 {
+        self.limit.bytes_per_sec as u128 * self.limit.burst_period.as_millis()
+    }
+
+    /// Charge the peer for a transaction of the given size, failing if the peer has exceeded
+    /// its budget. Nothing is charged on failure.
+    pub fn check_and_charge(
+        &mut self,
+        peer_id: PeerId,
+        tx_size: usize,
+        now: Time,
+    ) -> Result<(), MempoolPolicyError> {
+        let txs_capacity = self.txs_capacity();
+        let bytes_capacity = self.bytes_capacity();
+        let limit = self.limit;
+
+        let bucket = self.buckets.entry(peer_id).or_insert_with(|| Bucket {
+            txs: txs_capacity,
+            bytes: bytes_capacity,
+            last_update: now,
+        });
+
+        // The clock may go backwards, in which case nothing is refilled.
+        let elapsed_millis = (now - bucket.last_update).map_or(0, |elapsed| elapsed.as_millis());
+        bucket.txs = std::cmp::min(
+            bucket.txs.saturating_add(elapsed_millis * limit.txs_per_sec as u128),
+            txs_capacity,
+        );
+        bucket.bytes = std::cmp::min(
+            bucket.bytes.saturating_add(elapsed_millis * limit.bytes_per_sec as u128),
+            bytes_capacity,
+        );
+        bucket.last_update = now;
+
+        let tx_cost = 1000;
+        let bytes_cost = tx_size as u128 * 1000;
+        if bucket.txs < tx_cost || bucket.bytes < bytes_cost {
+            return Err(MempoolPolicyError::PeerRateLimitExceeded(peer_id));
+        }
+
+        bucket.txs -= tx_cost;
+        bucket.bytes -= bytes_cost;
+        Ok(())
+    }
+
+    pub fn remove_peer(&mut self, peer_id: PeerId) {
+        self.buckets.remove(&peer_id);
+    }
+}
@@ -156,6 +156,10 @@ impl MempoolStore {
         self.txs_by_id.is_empty()
     }
 
+    pub fn len(&self) -> usize {
+        self.txs_by_id.len()
+    }
+
     pub fn get_entry(&self, id: &Id<Transaction>) -> Option<&TxMempoolEntry> {
         self.txs_by_id.get(id).map(|tx| tx.deref())
     }
@@ -478,10 +482,16 @@ impl MempoolStore {
         assert_eq!(tx_id_seq, Some(*tx_id), "Inconsistent transaction seq nos");
     }
 
-    pub fn drop_conflicts(&mut self, conflicts: Conflicts) {
+    // Remove the replaced transactions. Return the IDs of the removed transactions
+    pub fn drop_conflicts(&mut self, conflicts: Conflicts) -> Vec<Id<Transaction>> {
+        let mut replaced = Vec::new();
         for conflict in conflicts.0 {
-            self.remove_tx(&conflict, MempoolRemovalReason::Replaced);
+            replaced.extend(
+                self.drop_tx_and_descendants(&conflict, MempoolRemovalReason::Replaced)
+                    .map(|entry| *entry.tx_id()),
+            );
         }
+        replaced
     }
 
     // Remove given transaction and its descendants. Return the IDs of the removed transactions
@@ -117,6 +117,51 @@ async fn transaction_order_respects_deps(#[case] seed: Seed) {
     assert_eq!(tx_ids, vec![tx0_id, tx1_id, tx2_id]);
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn max_tx_count(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let genesis_id = tf.genesis().get_id();
+
+    let tx0 = make_tx(&mut rng, &[(genesis_id.into(), 0)], &[900_000_000_000]);
+    let tx0_id = tx0.transaction().get_id();
+
+    let tx1 = make_tx(&mut rng, &[(tx0_id.into(), 0)], &[800_000_000_000]);
+    let tx1_id = tx1.transaction().get_id();
+
+    let tx2 = make_tx(&mut rng, &[(tx1_id.into(), 0)], &[500_000_000_000]);
+
+    let mut mempool = setup_with_chainstate(tf.chainstate());
+    assert_eq!(mempool.add_transaction_test(tx0), Ok(TxStatus::InMempool));
+    assert_eq!(mempool.add_transaction_test(tx1), Ok(TxStatus::InMempool));
+    assert_eq!(mempool.add_transaction_test(tx2), Ok(TxStatus::InMempool));
+
+    let accumulator = Box::new(
+        DefaultTxAccumulator::new(1_000_000, genesis_id.into(), DUMMY_TIMESTAMP)
+            .with_max_tx_count(2),
+    );
+    let accumulator = mempool
+        .collect_txs(accumulator, vec![], PackingStrategy::FillSpaceFromMempool)
+        .unwrap();
+    let tx_ids: Vec<_> =
+        accumulator.transactions().iter().map(|tx| tx.transaction().get_id()).collect();
+
+    assert_eq!(tx_ids, vec![tx0_id, tx1_id]);
+    assert!(accumulator.done());
+
+    let accumulator = Box::new(
+        DefaultTxAccumulator::new(1_000_000, genesis_id.into(), DUMMY_TIMESTAMP)
+            .with_max_tx_count(0),
+    );
+    let accumulator = mempool
+        .collect_txs(accumulator, vec![], PackingStrategy::FillSpaceFromMempool)
+        .unwrap();
+    assert!(accumulator.transactions().is_empty());
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -169,6 +214,139 @@ async fn transaction_graph_respects_deps(#[case] seed: Seed) {
     }
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn txs_by_fee_rate(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let genesis_id = tf.genesis().get_id();
+
+    let amount = 100_000_000_000;
+    let tx0 = make_tx(
+        &mut rng,
+        &[(genesis_id.into(), 0)],
+        &[amount, amount, amount],
+    );
+    let tx0_id = tx0.transaction().get_id();
+
+    // Independent transactions with varying fees
+    let tx_low = make_tx(&mut rng, &[(tx0_id.into(), 0)], &[amount - 1_000_000]);
+    let tx_low_id = tx_low.transaction().get_id();
+    let tx_high = make_tx(&mut rng, &[(tx0_id.into(), 1)], &[amount - 100_000_000]);
+    let tx_high_id = tx_high.transaction().get_id();
+    let tx_mid = make_tx(&mut rng, &[(tx0_id.into(), 2)], &[amount - 10_000_000]);
+    let tx_mid_id = tx_mid.transaction().get_id();
+
+    // A child paying a high fee has to wait for its low fee parent
+    let tx_child = make_tx(
+        &mut rng,
+        &[(tx_low_id.into(), 0)],
+        &[amount - 2_000_000_000],
+    );
+    let tx_child_id = tx_child.transaction().get_id();
+
+    let mut mempool = setup_with_chainstate(tf.chainstate());
+    for tx in [tx0, tx_low, tx_high, tx_mid, tx_child] {
+        assert_eq!(mempool.add_transaction_test(tx), Ok(TxStatus::InMempool));
+    }
+
+    let tx_ids: Vec<_> =
+        mempool.txs_by_ancestor_score().map(|tx| tx.transaction().get_id()).collect();
+    assert_eq!(
+        tx_ids,
+        vec![tx0_id, tx_high_id, tx_mid_id, tx_low_id, tx_child_id]
+    );
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn top_txs_fee_rates(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let genesis_id = tf.genesis().get_id();
+
+    let amount = 100_000_000_000;
+    let tx0 = make_tx(
+        &mut rng,
+        &[(genesis_id.into(), 0)],
+        &[amount, amount, amount, amount],
+    );
+    let tx0_id = tx0.transaction().get_id();
+
+    let fees = [1_000_000, 100_000_000, 10_000_000, 50_000_000];
+    let txs: Vec<_> = fees
+        .iter()
+        .enumerate()
+        .map(|(i, fee)| make_tx(&mut rng, &[(tx0_id.into(), i as u32)], &[amount - fee]))
+        .collect();
+
+    let mut mempool = setup_with_chainstate(tf.chainstate());
+    assert_eq!(mempool.add_transaction_test(tx0), Ok(TxStatus::InMempool));
+    for tx in &txs {
+        assert_eq!(
+            mempool.add_transaction_test(tx.clone()),
+            Ok(TxStatus::InMempool)
+        );
+    }
+
+    // Skip tx0, which has to go first as the parent of all others
+    let top = mempool.top_txs_fee_rates(4).unwrap();
+    assert_eq!(top.len(), 4);
+    assert_eq!(top[0].tx_id, tx0_id);
+
+    let expected_order = [1, 3, 2];
+    let top_ids: Vec<_> = top[1..].iter().map(|info| info.tx_id).collect();
+    let expected_ids: Vec<_> =
+        expected_order.iter().map(|i| txs[*i].transaction().get_id()).collect();
+    assert_eq!(top_ids, expected_ids);
+
+    for (info, i) in top[1..].iter().zip(expected_order) {
+        assert_eq!(info.fee, Amount::from_atoms(fees[i]));
+        assert_eq!(info.size, txs[i].encoded_size());
+    }
+    assert!(top[1..].windows(2).all(|w| w[0].fee_rate > w[1].fee_rate));
+
+    // Fewer transactions are returned if there aren't enough of them
+    assert_eq!(mempool.top_txs_fee_rates(100).unwrap().len(), 5);
+    assert_eq!(mempool.top_txs_fee_rates(0).unwrap(), vec![]);
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn txs_by_fee_rate_matches_collect_txs(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let genesis_id = tf.genesis().get_id();
+    let time = tf.genesis().timestamp();
+
+    let txs: Vec<_> = generate_transaction_graph(&mut rng, time.into_time()).take(15).collect();
+
+    let mut mempool = setup_with_chainstate(tf.chainstate());
+    for tx in &txs {
+        let res = mempool.add_transaction_test(tx.transaction().clone());
+        assert_eq!(res, Ok(TxStatus::InMempool));
+    }
+
+    let accumulator = Box::new(DefaultTxAccumulator::new(
+        10_000_000,
+        genesis_id.into(),
+        DUMMY_TIMESTAMP,
+    ));
+    let accumulator = mempool
+        .collect_txs(accumulator, vec![], PackingStrategy::FillSpaceFromMempool)
+        .unwrap();
+
+    let by_fee_rate: Vec<_> = mempool.txs_by_ancestor_score().cloned().collect();
+    assert_eq!(by_fee_rate.len(), txs.len());
+    assert_eq!(&by_fee_rate, accumulator.transactions());
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
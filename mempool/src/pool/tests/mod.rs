@@ -27,7 +27,7 @@ use common::{
         output_value::OutputValue,
         signature::inputsig::InputWitness,
         transaction::{Destination, TxInput, TxOutput},
-        OutPointSourceId, Transaction, UtxoOutPoint,
+        DuplicateInputError, OutPointSourceId, Transaction, UtxoOutPoint,
     },
     primitives::{Id, Idable, H256},
 };
@@ -36,6 +36,7 @@ use std::{collections::BTreeMap, ops::Deref, sync::Arc};
 mod accumulator;
 mod expiry;
 mod orphans;
+mod rate_limit;
 mod reorg;
 mod replacement;
 mod utils;
@@ -171,6 +172,61 @@ async fn txs_sorted(#[case] seed: Seed) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn estimate_fee_rate(#[case] seed: Seed) -> anyhow::Result<()> {
+    let mut rng = make_seedable_rng(seed);
+    let genesis_id = common::chain::config::create_unit_test_config().genesis_block_id();
+    let tx = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(OutPointSourceId::BlockReward(genesis_id), 0),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(1000)),
+            Destination::AnyoneCanSpend,
+        ))
+        .build();
+
+    // Blocks are just big enough to fit the transaction
+    let chain_config = Arc::new(
+        common::chain::config::Builder::test_chain()
+            .max_block_size_with_standard_txs(tx.encoded_size())
+            .build(),
+    );
+    let tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .build();
+    let mut mempool = Mempool::new(
+        chain_config,
+        start_chainstate(tf.chainstate()),
+        Default::default(),
+        StoreMemoryUsageEstimator,
+    );
+    let target_blocks = NonZeroUsize::new(1).unwrap();
+
+    // Nothing in the mempool, fall back to the minimum relay fee rate
+    let estimate = mempool.estimate_fee_rate(target_blocks)?;
+    assert!(estimate.is_fallback());
+    assert_eq!(estimate.fee_rate(), INCREMENTAL_RELAY_FEE_RATE);
+
+    mempool.add_transaction_test(tx)?.assert_in_mempool();
+
+    // The transaction fills the first block
+    let estimate = mempool.estimate_fee_rate(target_blocks)?;
+    assert!(!estimate.is_fallback());
+    assert!(estimate.fee_rate() >= INCREMENTAL_RELAY_FEE_RATE);
+
+    // But not the first two
+    let estimate = mempool.estimate_fee_rate(NonZeroUsize::new(2).unwrap())?;
+    assert!(estimate.is_fallback());
+
+    mempool.store.assert_valid();
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn tx_no_inputs() {
     let mut mempool = setup();
@@ -296,7 +352,9 @@ async fn tx_duplicate_inputs() -> anyhow::Result<()> {
 
     assert!(matches!(
         mempool.add_transaction_test(tx),
-        Err(Error::Validity(_)),
+        Err(Error::Policy(MempoolPolicyError::DuplicateInput(
+            DuplicateInputError::DuplicateUtxo(_)
+        ))),
     ));
     mempool.store.assert_valid();
     Ok(())
@@ -407,6 +465,49 @@ async fn tx_too_big(#[case] seed: Seed) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn no_signature_data_too_large() -> anyhow::Result<()> {
+    let mut mempool = setup();
+
+    let outpoint_source_id: OutPointSourceId = mempool.chain_config.genesis_block_id().into();
+    let max_data_size = mempool.chain_config.max_no_signature_data_size();
+    let relay_fee: Fee = Amount::from_atoms(get_relay_fee_from_tx_size(
+        TX_SPEND_INPUT_SIZE + max_data_size + 1,
+    ))
+    .into();
+
+    let input = TxInput::from_utxo(outpoint_source_id.clone(), 0);
+    let tx = tx_spend_input(
+        &mempool,
+        input,
+        InputWitness::NoSignature(Some(vec![0; max_data_size + 1])),
+        relay_fee,
+        0,
+    )
+    .await?;
+    assert_eq!(
+        mempool.add_transaction_test(tx),
+        Err(
+            MempoolPolicyError::NoSignatureDataSizeTooLarge(max_data_size + 1, max_data_size)
+                .into()
+        )
+    );
+
+    let input = TxInput::from_utxo(outpoint_source_id, 0);
+    let tx = tx_spend_input(
+        &mempool,
+        input,
+        InputWitness::NoSignature(Some(vec![0; max_data_size])),
+        relay_fee,
+        0,
+    )
+    .await?;
+    mempool.add_transaction_test(tx)?.assert_in_mempool();
+
+    mempool.store.assert_valid();
+    Ok(())
+}
+
 // To test our validation of BIP125 Rule#4 (replacement transaction pays for its own bandwidth), we need to know the necessary relay fee before creating the transaction. The relay fee depends on the size of the transaction. The usual way to get the size of a transaction is to call `tx.encoded_size` but we cannot do this until we have created the transaction itself. To get around this cycle, we have precomputed the size of all transaction created by `tx_spend_input`. This value will be the same for all transactions created by this function.
 const TX_SPEND_INPUT_SIZE: usize = 213;
 
@@ -481,9 +582,13 @@ async fn tx_spend_several_inputs<M>(
 
 #[rstest]
 #[trace]
-#[case(Seed::from_entropy())]
+#[case(Seed::from_entropy(), true)]
+#[case(Seed::from_entropy(), false)]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
-async fn one_ancestor_replaceability_signal_is_enough(#[case] seed: Seed) -> anyhow::Result<()> {
+async fn one_ancestor_replaceability_signal_is_enough(
+    #[case] seed: Seed,
+    #[case] rbf_enabled: bool,
+) -> anyhow::Result<()> {
     let mut rng = make_seedable_rng(seed);
     let tf = TestFramework::builder(&mut rng).build();
     let genesis = tf.genesis();
@@ -502,6 +607,7 @@ async fn one_ancestor_replaceability_signal_is_enough(#[case] seed: Seed) -> any
     let tx = tx_builder.build();
 
     let mut mempool = setup_with_chainstate(tf.chainstate());
+    mempool.set_rbf_enabled(rbf_enabled);
     mempool.add_transaction_test(tx.clone())?.assert_in_mempool();
 
     let flags_replaceable = 1;
@@ -573,7 +679,7 @@ async fn one_ancestor_replaceability_signal_is_enough(#[case] seed: Seed) -> any
     .expect("invalid witness count");
 
     let result = mempool.add_transaction_test(replacing_tx);
-    if ENABLE_RBF {
+    if rbf_enabled {
         assert_eq!(result, Ok(TxStatus::InMempool));
         assert!(!mempool.contains_transaction(&replaced_tx_id));
     } else {
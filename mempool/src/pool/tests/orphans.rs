@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::tx_origin::LocalTxOrigin;
+use crate::{event::MempoolEvent, tx_origin::LocalTxOrigin};
 use common::primitives::id::hash_encoded;
 
 use super::*;
@@ -459,3 +459,59 @@ async fn orphan_scheduling(#[case] seed: Seed) {
         assert!(mempool.contains_transaction(tx_id));
     }
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn orphan_keeps_remote_origin(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let genesis_id = tf.genesis().get_id();
+
+    let tx0 = make_tx(
+        &mut rng,
+        &[(OutPointSourceId::BlockReward(genesis_id.into()), 0)],
+        &[100_000_000],
+    );
+    let tx0_outpt = OutPointSourceId::Transaction(tx0.transaction().get_id());
+
+    let tx1 = make_tx(&mut rng, &[(tx0_outpt, 0)], &[80_000_000]);
+    let tx1_id = tx1.transaction().get_id();
+
+    let mut mempool = setup_with_chainstate(tf.chainstate());
+    let mut work_queue = WorkQueue::new();
+
+    let (events_tx, mut events_rx) = tokio::sync::mpsc::unbounded_channel();
+    mempool.subscribe_to_events(Arc::new(move |event| match event {
+        MempoolEvent::TransactionProcessed(tpe) => {
+            let _ = events_tx.send(tpe);
+        }
+        MempoolEvent::NewTip(_) => (),
+    }));
+
+    let orphan_origin = RemoteTxOrigin::new(p2p_types::PeerId::from_u64(1));
+    let parent_origin = RemoteTxOrigin::new(p2p_types::PeerId::from_u64(2));
+
+    mempool
+        .add_transaction(tx1, orphan_origin.into(), &mut work_queue)
+        .unwrap()
+        .assert_in_orphan_pool();
+    mempool
+        .add_transaction(tx0, parent_origin.into(), &mut work_queue)
+        .unwrap()
+        .assert_in_mempool();
+    mempool.perform_work_unit(&mut work_queue);
+    assert!(mempool.contains_transaction(&tx1_id));
+
+    let orphan_event = loop {
+        let event = events_rx.recv().await.unwrap();
+        if *event.tx_id() == tx1_id {
+            break event;
+        }
+    };
+    assert!(orphan_event.was_accepted());
+    assert_eq!(orphan_event.origin(), TxOrigin::Orphan(orphan_origin));
+    assert_eq!(orphan_event.origin().remote_origin(), Some(orphan_origin));
+    assert!(orphan_event.origin().should_propagate());
+}
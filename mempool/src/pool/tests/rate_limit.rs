@@ -0,0 +1,175 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::chain::output_value::OutputValue;
+
+use super::*;
+use crate::{config::PeerTxRateLimit, error::MempoolBanScore, tx_origin::LocalTxOrigin};
+use ::utils::atomics::SeqCstAtomicU64;
+
+// Create a mempool containing a transaction with the given number of outputs, each of which
+// can be spent independently.
+fn setup_with_parent(
+    rng: &mut (impl Rng + CryptoRng),
+    num_outputs: usize,
+    mock_time: &Arc<SeqCstAtomicU64>,
+) -> (Mempool<StoreMemoryUsageEstimator>, Id<Transaction>) {
+    let tf = TestFramework::builder(rng).build();
+    let genesis = tf.genesis();
+
+    let mut tx_builder = TransactionBuilder::new().add_input(
+        TxInput::from_utxo(OutPointSourceId::BlockReward(genesis.get_id().into()), 0),
+        empty_witness(rng),
+    );
+    for _ in 0..num_outputs {
+        tx_builder = tx_builder.add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(999_999_999_000)),
+            anyonecanspend_address(),
+        ));
+    }
+    let parent = tx_builder.build();
+    let parent_id = parent.transaction().get_id();
+
+    let chainstate = tf.chainstate();
+    let mut mempool = Mempool::new(
+        Arc::clone(chainstate.get_chain_config()),
+        start_chainstate(chainstate),
+        mocked_time_getter_seconds(Arc::clone(mock_time)),
+        StoreMemoryUsageEstimator,
+    );
+
+    let mut work_queue = WorkQueue::new();
+    mempool
+        .add_transaction(parent, LocalTxOrigin::Mempool.into(), &mut work_queue)
+        .unwrap()
+        .assert_in_mempool();
+
+    (mempool, parent_id)
+}
+
+async fn make_children(
+    mempool: &Mempool<StoreMemoryUsageEstimator>,
+    parent_id: Id<Transaction>,
+    num_outputs: usize,
+) -> Vec<SignedTransaction> {
+    let mut children = Vec::with_capacity(num_outputs);
+    for i in 0..num_outputs {
+        let child = tx_spend_input(
+            mempool,
+            TxInput::from_utxo(OutPointSourceId::Transaction(parent_id), i as u32),
+            InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec())),
+            None,
+            0,
+        )
+        .await
+        .unwrap();
+        children.push(child);
+    }
+    children
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn burst_is_throttled(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let mock_time = Arc::new(SeqCstAtomicU64::new(0));
+
+    let num_outputs = 6;
+    let (mut mempool, parent_id) = setup_with_parent(&mut rng, num_outputs, &mock_time);
+    mempool.set_peer_tx_rate_limit(PeerTxRateLimit {
+        txs_per_sec: 2,
+        bytes_per_sec: 1_000_000,
+        burst_period: Duration::from_secs(1),
+    });
+
+    let mut children = make_children(&mempool, parent_id, num_outputs).await.into_iter();
+    let peer1 = p2p_types::PeerId::from_u64(1);
+    let peer2 = p2p_types::PeerId::from_u64(2);
+    let mut work_queue = WorkQueue::new();
+    let mut add_remote = |mempool: &mut Mempool<StoreMemoryUsageEstimator>,
+                          tx: SignedTransaction,
+                          peer_id: p2p_types::PeerId| {
+        mempool.add_transaction(tx, RemoteTxOrigin::new(peer_id).into(), &mut work_queue)
+    };
+
+    // The burst allowance is used up by the first two transactions.
+    for _ in 0..2 {
+        add_remote(&mut mempool, children.next().unwrap(), peer1)
+            .unwrap()
+            .assert_in_mempool();
+    }
+    let throttled_tx = children.next().unwrap();
+    assert_eq!(
+        add_remote(&mut mempool, throttled_tx.clone(), peer1),
+        Err(MempoolPolicyError::PeerRateLimitExceeded(peer1).into())
+    );
+    assert!(!mempool.contains_transaction(&throttled_tx.transaction().get_id()));
+
+    // Other peers have their own budget.
+    add_remote(&mut mempool, children.next().unwrap(), peer2)
+        .unwrap()
+        .assert_in_mempool();
+
+    // Local transactions are not limited.
+    let mut local_work_queue = WorkQueue::new();
+    mempool
+        .add_transaction(
+            children.next().unwrap(),
+            LocalTxOrigin::P2p.into(),
+            &mut local_work_queue,
+        )
+        .unwrap()
+        .assert_in_mempool();
+
+    // The budget is refilled as time passes.
+    mock_time.store(1);
+    add_remote(&mut mempool, throttled_tx, peer1).unwrap().assert_in_mempool();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn byte_budget_is_enforced(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let mock_time = Arc::new(SeqCstAtomicU64::new(0));
+
+    let num_outputs = 2;
+    let (mut mempool, parent_id) = setup_with_parent(&mut rng, num_outputs, &mock_time);
+    let children = make_children(&mempool, parent_id, num_outputs).await;
+
+    // Only one transaction fits into the byte budget.
+    mempool.set_peer_tx_rate_limit(PeerTxRateLimit {
+        txs_per_sec: 100,
+        bytes_per_sec: children[0].encoded_size(),
+        burst_period: Duration::from_secs(1),
+    });
+
+    let peer = p2p_types::PeerId::from_u64(1);
+    let origin: TxOrigin = RemoteTxOrigin::new(peer).into();
+    let mut work_queue = WorkQueue::new();
+    mempool
+        .add_transaction(children[0].clone(), origin, &mut work_queue)
+        .unwrap()
+        .assert_in_mempool();
+    let result = mempool.add_transaction(children[1].clone(), origin, &mut work_queue);
+    assert_eq!(
+        result,
+        Err(MempoolPolicyError::PeerRateLimitExceeded(peer).into())
+    );
+    assert_eq!(result.unwrap_err().mempool_ban_score(), 20);
+}
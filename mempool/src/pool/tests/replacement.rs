@@ -17,6 +17,7 @@ use common::chain::output_value::OutputValue;
 use crypto::random::{CryptoRng, Rng};
 
 use super::*;
+use crate::{error::MempoolBanScore, tx_origin::LocalTxOrigin};
 
 async fn test_replace_tx(
     rng: &mut (impl Rng + CryptoRng),
@@ -332,3 +333,139 @@ async fn pays_more_than_conflicts_with_descendants(#[case] seed: Seed) -> anyhow
     mempool.store.assert_valid();
     Ok(())
 }
+
+// Create a mempool with a replaceable transaction spending the genesis reward and return the
+// outpoint it spends.
+async fn setup_with_replaceable_tx(
+    rng: &mut (impl Rng + CryptoRng),
+    original_fee: Fee,
+) -> (Mempool<StoreMemoryUsageEstimator>, TxInput, Id<Transaction>) {
+    let tf = TestFramework::builder(rng).build();
+    let genesis = tf.genesis();
+    let parent = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(OutPointSourceId::BlockReward(genesis.get_id().into()), 0),
+            empty_witness(rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(999_999_999_000)),
+            anyonecanspend_address(),
+        ))
+        .build();
+    let mut mempool = setup_with_chainstate(tf.chainstate());
+    mempool.set_rbf_enabled(true);
+    let parent_id = parent.transaction().get_id();
+    mempool.add_transaction_test(parent).unwrap().assert_in_mempool();
+
+    let input = TxInput::from_utxo(OutPointSourceId::Transaction(parent_id), 0);
+    let original = tx_spend_input(
+        &mempool,
+        input.clone(),
+        InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec())),
+        original_fee,
+        1,
+    )
+    .await
+    .unwrap();
+    let original_id = original.transaction().get_id();
+    mempool.add_transaction_test(original).unwrap().assert_in_mempool();
+
+    (mempool, input, original_id)
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn replaced_txs_reported_in_event(#[case] seed: Seed) -> anyhow::Result<()> {
+    let mut rng = make_seedable_rng(seed);
+    let (mut mempool, input, original_id) =
+        setup_with_replaceable_tx(&mut rng, Amount::from_atoms(1_000).into()).await;
+
+    let child = tx_spend_input(
+        &mempool,
+        TxInput::from_utxo(OutPointSourceId::Transaction(original_id), 0),
+        InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec())),
+        None,
+        0,
+    )
+    .await?;
+    let child_id = child.transaction().get_id();
+    mempool.add_transaction_test(child)?.assert_in_mempool();
+
+    let (events_tx, mut events_rx) = tokio::sync::mpsc::unbounded_channel();
+    mempool.subscribe_to_events(Arc::new(move |event| match event {
+        MempoolEvent::TransactionProcessed(tpe) => {
+            let _ = events_tx.send(tpe);
+        }
+        MempoolEvent::NewTip(_) => (),
+    }));
+
+    let replacement = tx_spend_input(
+        &mempool,
+        input,
+        InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec())),
+        Fee::new(Amount::from_atoms(100_000)),
+        0,
+    )
+    .await?;
+    let replacement_id = replacement.transaction().get_id();
+    mempool.add_transaction_test(replacement)?.assert_in_mempool();
+
+    assert!(mempool.contains_transaction(&replacement_id));
+    assert!(!mempool.contains_transaction(&original_id));
+    assert!(!mempool.contains_transaction(&child_id));
+    mempool.store.assert_valid();
+
+    let event = events_rx.recv().await.unwrap();
+    assert_eq!(*event.tx_id(), replacement_id);
+    assert!(event.was_accepted());
+    let replaced: BTreeSet<_> = event.replaced_txs().iter().copied().collect();
+    assert_eq!(replaced, BTreeSet::from([original_id, child_id]));
+
+    Ok(())
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remote_replacement_needs_fee_rate_bump(#[case] seed: Seed) -> anyhow::Result<()> {
+    let mut rng = make_seedable_rng(seed);
+    let (mut mempool, input, original_id) =
+        setup_with_replaceable_tx(&mut rng, Amount::from_atoms(1_000).into()).await;
+
+    let replacement = tx_spend_input(
+        &mempool,
+        input,
+        InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec())),
+        Fee::new(Amount::from_atoms(100_000)),
+        0,
+    )
+    .await?;
+    let replacement_id = replacement.transaction().get_id();
+
+    // The replacement pays enough to replace the original, but not enough to satisfy the bump
+    mempool.set_rbf_fee_rate_bump(FeeRate::new(Amount::from_atoms(1_000_000)));
+    let res = mempool.add_transaction_test(replacement.clone());
+    assert!(matches!(
+        res,
+        Err(Error::Policy(
+            MempoolPolicyError::ReplacementFeeRateBumpTooLow { .. }
+        ))
+    ));
+    assert_eq!(res.unwrap_err().mempool_ban_score(), 0);
+    assert!(mempool.contains_transaction(&original_id));
+    assert!(!mempool.contains_transaction(&replacement_id));
+
+    // Replacements submitted by the local node are not subject to the bump
+    let mut work_queue = WorkQueue::new();
+    mempool
+        .add_transaction(replacement, LocalTxOrigin::P2p.into(), &mut work_queue)?
+        .assert_in_mempool();
+    assert!(mempool.contains_transaction(&replacement_id));
+    assert!(!mempool.contains_transaction(&original_id));
+    mempool.store.assert_valid();
+
+    Ok(())
+}
@@ -31,6 +31,7 @@ use common::{
     },
     primitives::{Amount, Id},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::{AccountingBlockUndo, DelegationData, PoSAccountingView, PoolData};
 use subsystem::blocking::BlockingHandle;
 use utils::shallow_clone::ShallowClone;
@@ -148,6 +149,14 @@ impl PoSAccountingView for ChainstateHandle {
         self.call(move |c| c.get_stake_pool_data(pool_id))
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, Error> {
+        let vrf_public_key = vrf_public_key.clone();
+        self.call(move |c| c.get_stake_pool_id_by_vrf_public_key(&vrf_public_key))
+    }
+
     fn get_pool_delegations_shares(
         &self,
         pool_id: PoolId,
@@ -15,6 +15,8 @@
 
 //! Mempool subsystem RPC handler
 
+use std::num::NonZeroUsize;
+
 use common::{
     chain::{GenBlock, SignedTransaction, Transaction},
     primitives::Id,
@@ -23,7 +25,10 @@ use mempool_types::tx_origin::LocalTxOrigin;
 use serialization::hex_encoded::HexEncoded;
 use utils::tap_error_log::LogError;
 
-use crate::{FeeRate, MempoolMaxSize, TxStatus};
+use crate::{FeeRate, FeeRateEstimate, MempoolMaxSize, TxFeeRateInfo, TxStatus};
+
+/// The maximum number of transactions returned by `transactions_by_fee_rate`
+const MAX_TXS_BY_FEE_RATE: usize = 1000;
 
 use rpc::Result as RpcResult;
 
@@ -68,6 +73,15 @@ trait MempoolRpc {
 
     #[method(name = "get_fee_rate")]
     async fn get_fee_rate(&self, in_top_x_mb: usize) -> RpcResult<FeeRate>;
+
+    /// Estimate the fee rate needed to get a transaction confirmed within the target number of blocks
+    #[method(name = "estimate_fee_rate")]
+    async fn estimate_fee_rate(&self, target_blocks: NonZeroUsize) -> RpcResult<FeeRateEstimate>;
+
+    /// Get up to `count` transactions in the order of decreasing fee rate, with their
+    /// sizes and fees. At most 1000 transactions are returned.
+    #[method(name = "transactions_by_fee_rate")]
+    async fn transactions_by_fee_rate(&self, count: usize) -> RpcResult<Vec<TxFeeRateInfo>>;
 }
 
 #[async_trait::async_trait]
@@ -134,4 +148,13 @@ impl MempoolRpcServer for super::MempoolHandle {
     async fn get_fee_rate(&self, in_top_x_mb: usize) -> rpc::Result<FeeRate> {
         rpc::handle_result(self.call(move |this| this.get_fee_rate(in_top_x_mb)).await)
     }
+
+    async fn estimate_fee_rate(&self, target_blocks: NonZeroUsize) -> rpc::Result<FeeRateEstimate> {
+        rpc::handle_result(self.call(move |this| this.estimate_fee_rate(target_blocks)).await)
+    }
+
+    async fn transactions_by_fee_rate(&self, count: usize) -> rpc::Result<Vec<TxFeeRateInfo>> {
+        let count = std::cmp::min(count, MAX_TXS_BY_FEE_RATE);
+        rpc::handle_result(self.call(move |this| this.get_top_fee_rates(count)).await)
+    }
 }
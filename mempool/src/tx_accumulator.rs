@@ -39,6 +39,9 @@ pub trait TransactionAccumulator: Send {
     /// Meaning: If this call returns an error, the callee should guarantee that &self never changed
     // TODO: Add a test for this property, at least for DefaultTxAccumulator
     fn add_tx(&mut self, tx: SignedTransaction, tx_fee: Fee) -> Result<(), TxAccumulatorError>;
+    /// Account for the fees of transactions that were added before their fees were known,
+    /// such as user-provided transactions added with a zero fee ahead of mempool verification
+    fn add_fees(&mut self, fees: Fee) -> Result<(), TxAccumulatorError>;
     fn done(&self) -> bool;
     fn transactions(&self) -> &[SignedTransaction];
     fn total_fees(&self) -> Fee;
@@ -55,6 +58,7 @@ pub struct DefaultTxAccumulator {
     txs: Vec<SignedTransaction>,
     txs_size: usize,
     target_size: usize,
+    max_tx_count: usize,
     done: bool,
     total_fees: Fee,
     expected_tip: Id<GenBlock>,
@@ -67,6 +71,7 @@ impl DefaultTxAccumulator {
             txs: Vec::new(),
             txs_size: 0,
             target_size,
+            max_tx_count: usize::MAX,
             done: false,
             total_fees: Amount::ZERO.into(),
             expected_tip,
@@ -74,6 +79,13 @@ impl DefaultTxAccumulator {
         }
     }
 
+    /// Limit the number of transactions, the accumulator is done once the limit is reached
+    pub fn with_max_tx_count(mut self, max_tx_count: usize) -> Self {
+        self.max_tx_count = max_tx_count;
+        self.done |= self.txs.len() >= max_tx_count;
+        self
+    }
+
     pub fn total_size(&self) -> usize {
         Compact(self.transactions().len() as u64).encoded_size() + self.txs_size
     }
@@ -89,7 +101,9 @@ impl TransactionAccumulator for DefaultTxAccumulator {
         let tx_size = tx.encoded_size();
         let total_size_with_tx = self.total_size_with(tx_size);
 
-        if total_size_with_tx <= self.target_size {
+        if self.txs.len() >= self.max_tx_count {
+            self.done = true;
+        } else if total_size_with_tx <= self.target_size {
             self.txs_size += tx_size;
             self.total_fees = (self.total_fees + tx_fee).ok_or(
                 TxAccumulatorError::FeeAccumulationError(self.total_fees, tx_fee),
@@ -98,6 +112,8 @@ impl TransactionAccumulator for DefaultTxAccumulator {
 
             // Sanity check that total_size_with() and total_size() agree
             assert_eq!(total_size_with_tx, self.total_size());
+
+            self.done = self.txs.len() >= self.max_tx_count;
         } else {
             self.done = true;
         };
@@ -109,6 +125,13 @@ impl TransactionAccumulator for DefaultTxAccumulator {
         Ok(())
     }
 
+    fn add_fees(&mut self, fees: Fee) -> Result<(), TxAccumulatorError> {
+        self.total_fees = (self.total_fees + fees).ok_or(
+            TxAccumulatorError::FeeAccumulationError(self.total_fees, fees),
+        )?;
+        Ok(())
+    }
+
     fn done(&self) -> bool {
         self.done
     }
@@ -16,13 +16,22 @@
 use p2p_types::peer_id::PeerId;
 
 /// Tracks where a transaction originates
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+///
+/// The serialized form is externally tagged with the variant name, e.g. `{"Local":"P2p"}`
+/// or `{"Remote":5}` where the number is the peer id.
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, serde::Serialize, serde::Deserialize,
+)]
 pub enum TxOrigin {
     /// Transaction originates locally
     Local(LocalTxOrigin),
 
     /// Transaction was received from a peer.
     Remote(RemoteTxOrigin),
+
+    /// Transaction was received from a peer, kept in the orphan pool and re-admitted once
+    /// its missing dependencies became available.
+    Orphan(RemoteTxOrigin),
 }
 
 impl TxOrigin {
@@ -30,7 +39,15 @@ impl TxOrigin {
     pub fn should_propagate(self) -> bool {
         match self {
             Self::Local(origin) => origin.should_propagate(),
-            Self::Remote(origin) => origin.should_propagate(),
+            Self::Remote(origin) | Self::Orphan(origin) => origin.should_propagate(),
+        }
+    }
+
+    /// The peer the transaction has originally been received from, if any.
+    pub fn remote_origin(self) -> Option<RemoteTxOrigin> {
+        match self {
+            Self::Local(_) => None,
+            Self::Remote(origin) | Self::Orphan(origin) => Some(origin),
         }
     }
 }
@@ -48,7 +65,9 @@ impl From<RemoteTxOrigin> for TxOrigin {
 }
 
 /// Signifies transaction originates in our local node
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, serde::Serialize, serde::Deserialize,
+)]
 pub enum LocalTxOrigin {
     /// Transaction was submitted to local node's mempool. It should not be propagated further.
     Mempool,
@@ -74,7 +93,9 @@ impl LocalTxOrigin {
 ///
 /// If it eventually turns out to be valid, it should be propagated further to other peers.
 /// If it's not valid, the original peer should be penalized as appropriate.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, serde::Serialize, serde::Deserialize,
+)]
 pub struct RemoteTxOrigin(PeerId);
 
 impl RemoteTxOrigin {
@@ -96,6 +117,7 @@ impl std::fmt::Display for TxOrigin {
         match self {
             TxOrigin::Local(origin) => origin.fmt(f),
             TxOrigin::Remote(origin) => origin.fmt(f),
+            TxOrigin::Orphan(origin) => write!(f, "orphan pool ({origin})"),
         }
     }
 }
@@ -128,3 +150,48 @@ mod seal {
     impl IsOrigin for super::LocalTxOrigin {}
     impl IsOrigin for super::RemoteTxOrigin {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_origins() -> [TxOrigin; 5] {
+        let peer_id = "7".parse().unwrap();
+        [
+            LocalTxOrigin::Mempool.into(),
+            LocalTxOrigin::P2p.into(),
+            LocalTxOrigin::PastBlock.into(),
+            RemoteTxOrigin::new(peer_id).into(),
+            TxOrigin::Orphan(RemoteTxOrigin::new(peer_id)),
+        ]
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        for origin in all_origins() {
+            let json = serde_json::to_string(&origin).unwrap();
+            assert_eq!(serde_json::from_str::<TxOrigin>(&json).unwrap(), origin);
+        }
+    }
+
+    #[test]
+    fn serde_json_shape() {
+        let expected = [
+            r#"{"Local":"Mempool"}"#,
+            r#"{"Local":"P2p"}"#,
+            r#"{"Local":"PastBlock"}"#,
+            r#"{"Remote":7}"#,
+            r#"{"Orphan":7}"#,
+        ];
+        for (origin, expected) in all_origins().into_iter().zip(expected) {
+            assert_eq!(serde_json::to_string(&origin).unwrap(), expected);
+        }
+
+        // The peer id is serialized the same way as elsewhere
+        let peer_id: PeerId = "7".parse().unwrap();
+        assert_eq!(
+            serde_json::to_value(RemoteTxOrigin::new(peer_id)).unwrap(),
+            serde_json::to_value(peer_id).unwrap()
+        );
+    }
+}
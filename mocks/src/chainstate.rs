@@ -16,7 +16,8 @@
 use std::sync::Arc;
 
 use chainstate::{
-    BlockSource, ChainInfo, ChainstateConfig, ChainstateError, ChainstateEvent, Locator,
+    BlockSource, ChainInfo, ChainstateConfig, ChainstateError, ChainstateEvent,
+    ChainstateReadHandle, Locator,
 };
 use chainstate_types::{BlockIndex, EpochData, GenBlockIndex};
 use common::{
@@ -31,6 +32,7 @@ use common::{
     },
     primitives::{Amount, BlockHeight, Id},
 };
+use crypto::vrf::VRFPublicKey;
 use pos_accounting::PoolData;
 use utils::eventhandler::EventHandler;
 use utxo::Utxo;
@@ -43,10 +45,12 @@ mockall::mock! {
     impl ChainstateInterface for ChainstateInterface {
         fn subscribe_to_events(&mut self, handler: Arc<dyn Fn(ChainstateEvent) + Send + Sync>);
         fn process_block(&mut self, block: Block, source: BlockSource) -> Result<Option<BlockIndex>, ChainstateError>;
+        fn process_block_batch(&mut self, blocks: Vec<Block>, source: BlockSource) -> Result<Option<BlockIndex>, ChainstateError>;
         fn invalidate_block(&mut self, block_id: &Id<Block>) -> Result<(), ChainstateError>;
         fn reset_block_failure_flags(&mut self, block_id: &Id<Block>) -> Result<(), ChainstateError>;
         fn preliminary_block_check(&self, block: Block) -> Result<Block, ChainstateError>;
         fn preliminary_header_check(&self, header: SignedBlockHeader) -> Result<(), ChainstateError>;
+        fn preliminary_header_check_batch(&self, headers: &[SignedBlockHeader]) -> Result<(), ChainstateError>;
         fn get_best_block_id(&self) -> Result<Id<GenBlock>, ChainstateError>;
         fn get_best_block_height(&self) -> Result<BlockHeight, ChainstateError>;
         fn get_best_block_header(&self) -> Result<SignedBlockHeader, ChainstateError>;
@@ -56,6 +60,10 @@ mockall::mock! {
             &self,
             block_id: &Id<GenBlock>,
         ) -> Result<Option<BlockHeight>, ChainstateError>;
+        fn get_block_heights_in_main_chain(
+            &self,
+            block_ids: &[Id<GenBlock>],
+        ) -> Result<Vec<Option<BlockHeight>>, ChainstateError>;
         fn get_block_id_from_height(
             &self,
             height: &BlockHeight,
@@ -67,6 +75,13 @@ mockall::mock! {
             max_count: usize,
         ) -> Result<Vec<Block>, ChainstateError>;
         fn get_block_header(&self, block_id: Id<Block>) -> Result<Option<SignedBlockHeader>, ChainstateError>;
+        fn get_mainchain_headers_in_range(
+            &self,
+            from: BlockHeight,
+            to: BlockHeight,
+            max_count: usize,
+        ) -> Result<Vec<SignedBlockHeader>, ChainstateError>;
+        fn get_last_block_timestamps(&self, count: usize) -> Result<Vec<BlockTimestamp>, ChainstateError>;
         fn get_locator(&self) -> Result<Locator, ChainstateError>;
         fn get_locator_from_height(&self, height: BlockHeight) -> Result<Locator, ChainstateError>;
         fn get_mainchain_headers_by_locator(
@@ -87,6 +102,7 @@ mockall::mock! {
             &self,
             id: &Id<Block>
         ) -> Result<Option<BlockIndex>, ChainstateError>;
+        fn has_block(&self, id: &Id<Block>) -> Result<bool, ChainstateError>;
         fn get_gen_block_index(
             &self,
             id: &Id<GenBlock>,
@@ -124,6 +140,11 @@ mockall::mock! {
         ) -> Result<Option<BlockReward>, ChainstateError>;
         fn get_epoch_data(&self, epoch_index: u64) -> Result<Option<EpochData>, ChainstateError>;
         fn get_token_info_for_rpc(&self, token_id: TokenId) -> Result<Option<RPCTokenInfo>, ChainstateError>;
+        fn get_tokens_info_for_rpc_by_issuance(
+            &self,
+            offset: usize,
+            limit: usize,
+        ) -> Result<Vec<RPCTokenInfo>, ChainstateError>;
         fn get_token_aux_data(
             &self,
             token_id: TokenId,
@@ -152,6 +173,10 @@ mockall::mock! {
         fn stake_pool_exists(&self, pool_id: PoolId) -> Result<bool, ChainstateError>;
         fn get_stake_pool_balance(&self, pool_id: PoolId) -> Result<Option<Amount>, ChainstateError>;
         fn get_stake_pool_data(&self, pool_id: PoolId) -> Result<Option<PoolData>, ChainstateError>;
+        fn get_stake_pool_id_by_vrf_public_key(
+            &self,
+            vrf_public_key: &VRFPublicKey,
+        ) -> Result<Option<PoolId>, ChainstateError>;
         fn get_stake_pool_delegations_shares(
             &self,
             pool_id: PoolId,
@@ -175,10 +200,15 @@ mockall::mock! {
             account: AccountType,
         ) -> Result<Option<AccountNonce>, ChainstateError>;
         fn is_transaction_index_enabled(&self) -> Result<bool, ChainstateError>;
+        fn read_handle(&self) -> ChainstateReadHandle;
         fn get_transaction(
             &self,
             tx_id: &Id<Transaction>,
         ) -> Result<Option<SignedTransaction>, ChainstateError>;
+        fn get_transaction_block(
+            &self,
+            tx_id: &Id<Transaction>,
+        ) -> Result<Option<(Id<Block>, BlockHeight)>, ChainstateError>;
     }
 }
 
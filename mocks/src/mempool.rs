@@ -15,7 +15,7 @@
 
 #![allow(clippy::unwrap_used)]
 
-use std::sync::Arc;
+use std::{num::NonZeroUsize, sync::Arc};
 
 use common::{
     chain::{GenBlock, SignedTransaction, Transaction},
@@ -26,7 +26,7 @@ use mempool::{
     event::MempoolEvent,
     tx_accumulator::{PackingStrategy, TransactionAccumulator},
     tx_origin::{LocalTxOrigin, RemoteTxOrigin},
-    FeeRate, MempoolInterface, MempoolMaxSize, TxStatus,
+    FeeRate, FeeRateEstimate, MempoolInterface, MempoolMaxSize, TxFeeRateInfo, TxStatus,
 };
 
 mockall::mock! {
@@ -46,6 +46,8 @@ mockall::mock! {
         ) -> Result<TxStatus, Error>;
 
         fn get_all(&self) -> Vec<SignedTransaction>;
+        fn transaction_count(&self) -> usize;
+        fn get_all_by_fee_rate(&self) -> Vec<SignedTransaction>;
         fn transaction(&self, id: &Id<Transaction>) -> Option<SignedTransaction>;
         fn orphan_transaction(&self, id: &Id<Transaction>) -> Option<SignedTransaction>;
         fn contains_transaction(&self, tx: &Id<Transaction>) -> bool;
@@ -64,6 +66,8 @@ mockall::mock! {
         fn get_max_size(&self) -> MempoolMaxSize;
         fn set_max_size(&mut self, max_size: MempoolMaxSize) -> Result<(), Error>;
         fn get_fee_rate(&self, in_top_x_mb: usize) -> Result<FeeRate, Error>;
+        fn estimate_fee_rate(&self, target_blocks: NonZeroUsize) -> Result<FeeRateEstimate, Error>;
+        fn get_top_fee_rates(&self, count: usize) -> Result<Vec<TxFeeRateInfo>, Error>;
 
         fn notify_peer_disconnected(&mut self, peer_id: p2p_types::PeerId);
         fn notify_chainstate_event(&mut self, event: chainstate::ChainstateEvent);
@@ -17,8 +17,12 @@ pub async fn run() -> anyhow::Result<()> {
     let opts = node_lib::Options::from_args(std::env::args_os());
     logging::init_logging();
     logging::log::info!("Command line options: {opts:?}");
-    let node = node_lib::setup(opts).await?;
-    node.main().await;
+    match node_lib::setup(opts).await? {
+        node_lib::NodeSetupResult::Node(node) => node.main().await,
+        node_lib::NodeSetupResult::ConfigGenerated(path) => {
+            println!("Config file written to {}", path.display())
+        }
+    }
     Ok(())
 }
 
@@ -184,7 +184,9 @@ impl Backend {
         let handles_client = WalletHandlesClient::new(
             self.controller.chainstate.clone(),
             self.controller.mempool.clone(),
-            self.controller.block_prod.clone(),
+            self.controller.block_prod.clone().ok_or_else(|| {
+                BackendError::WalletError("Block production is disabled".to_owned())
+            })?,
             self.controller.p2p.clone(),
         )
         .await
@@ -368,7 +370,7 @@ impl Backend {
         // TODO: add support for utxo selection in the GUI
         self.synced_wallet_controller(wallet_id, account_id.account_index())
             .await?
-            .send_to_address(address, amount, vec![])
+            .send_to_address(address, amount, vec![], None)
             .await
             .map_err(|e| BackendError::WalletError(e.to_string()))?;
 
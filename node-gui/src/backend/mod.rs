@@ -86,7 +86,15 @@ pub async fn node_initialize(_time_getter: TimeGetter) -> anyhow::Result<Backend
     logging::init_logging();
     logging::log::info!("Command line options: {opts:?}");
 
-    let node = node_lib::setup(opts).await?;
+    let node = match node_lib::setup(opts).await? {
+        node_lib::NodeSetupResult::Node(node) => node,
+        node_lib::NodeSetupResult::ConfigGenerated(path) => {
+            anyhow::bail!(
+                "Config file written to {}, restart to run the node",
+                path.display()
+            )
+        }
+    };
 
     let controller = node.controller().clone();
 
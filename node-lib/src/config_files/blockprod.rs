@@ -20,6 +20,8 @@ use serde::{Deserialize, Serialize};
 #[must_use]
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct BlockProdConfigFile {
+    /// Whether the block production subsystem is started.
+    pub enabled: Option<bool>,
     /// Minimum number of connected peers to enable block production.
     pub min_peers_to_produce_blocks: Option<usize>,
     /// Skip initial block download check for block production.
@@ -36,6 +36,13 @@ pub struct ChainstateConfigFile {
     /// The initial block download is finished if the difference between the current time and the
     /// tip time is less than this value.
     pub max_tip_age: Option<u64>,
+    /// The maximum number of blocks committed to the storage in a single transaction during
+    /// the initial block download.
+    pub commit_batch_size: Option<usize>,
+    /// Remove the bodies of the blocks buried under this many blocks.
+    pub prune_depth: Option<u64>,
+    /// Build the transaction index from the stored blocks if it was enabled for a synced node.
+    pub reindex_tx_index: Option<bool>,
 }
 
 impl From<ChainstateConfigFile> for ChainstateConfig {
@@ -46,6 +53,10 @@ impl From<ChainstateConfigFile> for ChainstateConfig {
             min_max_bootstrap_import_buffer_sizes: c.min_max_bootstrap_import_buffer_sizes.into(),
             tx_index_enabled: c.tx_index_enabled.into(),
             max_tip_age: c.max_tip_age.map(Duration::from_secs).into(),
+            commit_batch_size: c.commit_batch_size.into(),
+            prune_depth: c.prune_depth.into(),
+            reindex_tx_index: c.reindex_tx_index.into(),
+            max_blocks_pruned_at_once: Default::default(),
         }
     }
 }
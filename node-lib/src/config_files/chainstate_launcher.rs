@@ -26,6 +26,8 @@ pub enum StorageBackendConfigFile {
     #[serde(rename = "lmdb")]
     #[default]
     Lmdb,
+    /// Keep the chainstate in memory only. Nothing is written to disk and all the data
+    /// is lost on shutdown, which is useful for throwaway test nodes.
     #[serde(rename = "inmemory", alias = "in-memory")]
     InMemory,
 }
@@ -0,0 +1,70 @@
+// Copyright (c) 2022 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common::primitives::Amount;
+use mempool::{FeeRate, MempoolConfig, PeerTxRateLimit};
+use serde::{Deserialize, Serialize};
+
+/// The mempool subsystem configuration.
+#[must_use]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct MempoolConfigFile {
+    /// Maximum sustained number of transactions per second accepted from a single peer.
+    pub peer_max_txs_per_sec: Option<u32>,
+    /// Maximum sustained number of transaction bytes per second accepted from a single peer.
+    pub peer_max_tx_bytes_per_sec: Option<usize>,
+    /// The period (in seconds) the per-peer transaction burst allowance is computed from.
+    pub peer_tx_burst_period: Option<u64>,
+    /// Whether transactions signaling replaceability may be replaced by conflicting ones.
+    pub enable_rbf: Option<bool>,
+    /// The minimum fee rate increase (in atoms per 1000 bytes) a replacement received from
+    /// a peer has to pay over the transactions it replaces.
+    pub rbf_fee_rate_bump: Option<u128>,
+}
+
+impl From<MempoolConfigFile> for MempoolConfig {
+    fn from(config: MempoolConfigFile) -> Self {
+        let MempoolConfigFile {
+            peer_max_txs_per_sec,
+            peer_max_tx_bytes_per_sec,
+            peer_tx_burst_period,
+            enable_rbf,
+            rbf_fee_rate_bump,
+        } = config;
+
+        let default_limit = PeerTxRateLimit::default();
+        let peer_tx_rate_limit = PeerTxRateLimit {
+            txs_per_sec: peer_max_txs_per_sec.unwrap_or(default_limit.txs_per_sec),
+            bytes_per_sec: peer_max_tx_bytes_per_sec.unwrap_or(default_limit.bytes_per_sec),
+            burst_period: peer_tx_burst_period
+                .map(Duration::from_secs)
+                .unwrap_or(default_limit.burst_period),
+        };
+
+        let default_config = MempoolConfig::default();
+        let enable_rbf = enable_rbf.unwrap_or(default_config.enable_rbf);
+        let rbf_fee_rate_bump = rbf_fee_rate_bump
+            .map(|atoms| FeeRate::new(Amount::from_atoms(atoms)))
+            .unwrap_or(default_config.rbf_fee_rate_bump);
+
+        Self {
+            peer_tx_rate_limit,
+            enable_rbf,
+            rbf_fee_rate_bump,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::SocketAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::RunOptions;
+
+/// The metrics endpoint configuration.
+#[must_use]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct MetricsConfigFile {
+    /// Address to bind the Prometheus metrics endpoint to; the endpoint is disabled if not set
+    pub prometheus_bind_address: Option<SocketAddr>,
+}
+
+impl MetricsConfigFile {
+    pub fn with_run_options(config: MetricsConfigFile, options: &RunOptions) -> MetricsConfigFile {
+        let MetricsConfigFile {
+            prometheus_bind_address,
+        } = config;
+
+        let prometheus_bind_address = options.prometheus_addr.or(prometheus_bind_address);
+
+        MetricsConfigFile {
+            prometheus_bind_address,
+        }
+    }
+}
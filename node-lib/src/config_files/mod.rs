@@ -16,14 +16,18 @@
 //! The node configuration.
 
 pub const DEFAULT_HTTP_RPC_ENABLED: bool = true;
+pub const DEFAULT_BLOCKPROD_ENABLED: bool = true;
 
 pub use self::{
-    chainstate_launcher::StorageBackendConfigFile, p2p::NodeTypeConfigFile, rpc::RpcConfigFile,
+    chainstate_launcher::StorageBackendConfigFile, mempool::MempoolConfigFile,
+    metrics::MetricsConfigFile, p2p::NodeTypeConfigFile, rpc::RpcConfigFile,
 };
 
 mod blockprod;
 mod chainstate;
 mod chainstate_launcher;
+mod mempool;
+mod metrics;
 mod p2p;
 mod rpc;
 
@@ -31,6 +35,8 @@ use std::{fs, path::Path};
 
 use ::chainstate_launcher::ChainConfig;
 use anyhow::{Context, Result};
+use logging::log;
+use p2p::types::socket_address::SocketAddress;
 use serde::{Deserialize, Serialize};
 
 use crate::RunOptions;
@@ -47,8 +53,10 @@ pub struct NodeConfigFile {
     // Subsystems configurations.
     pub blockprod: Option<BlockProdConfigFile>,
     pub chainstate: Option<ChainstateLauncherConfigFile>,
+    pub mempool: Option<MempoolConfigFile>,
     pub p2p: Option<P2pConfigFile>,
     pub rpc: Option<RpcConfigFile>,
+    pub metrics: Option<MetricsConfigFile>,
 }
 
 impl NodeConfigFile {
@@ -56,8 +64,10 @@ impl NodeConfigFile {
         Ok(Self {
             blockprod: None,
             chainstate: None,
+            mempool: None,
             p2p: None,
             rpc: None,
+            metrics: None,
         })
     }
 
@@ -80,24 +90,60 @@ impl NodeConfigFile {
         options: &RunOptions,
     ) -> Result<Self> {
         let config_as_str = Self::read_to_string_with_policy(config_path)?;
+        Self::from_str_with_options(chain_config, &config_as_str, options)
+    }
+
+    /// Returns the default configuration as a TOML document with every setting commented out.
+    pub fn default_commented(chain_config: &ChainConfig) -> Result<String> {
+        let config = Self::from_str_with_options(chain_config, "", &RunOptions::default())?;
+        let config_as_str =
+            toml::to_string(&config).context("Failed to serialize the default config")?;
+
+        let mut result = format!(
+            "# Mintlayer node configuration for {}.\n\
+             # All the settings are commented out and set to their default values.\n\
+             # Uncomment and edit a setting to change it. Command line options take precedence.\n",
+            chain_config.chain_type().name()
+        );
+        for line in config_as_str.lines() {
+            result.push('\n');
+            if !line.is_empty() {
+                result.push_str("# ");
+                result.push_str(line);
+            }
+        }
+        result.push('\n');
+        Ok(result)
+    }
 
+    fn from_str_with_options(
+        chain_config: &ChainConfig,
+        config_as_str: &str,
+        options: &RunOptions,
+    ) -> Result<Self> {
         let NodeConfigFile {
             blockprod,
             chainstate,
+            mempool,
             p2p,
             rpc,
-        } = toml::from_str(&config_as_str).context("Failed to parse config")?;
+            metrics,
+        } = toml::from_str(config_as_str).context("Failed to parse config")?;
 
         let blockprod = blockprod_config(blockprod.unwrap_or_default(), options);
-        let chainstate = chainstate_config(chainstate.unwrap_or_default(), options);
-        let p2p = p2p_config(p2p.unwrap_or_default(), options);
+        let chainstate = chainstate_config(chainstate.unwrap_or_default(), options)?;
+        let mempool = mempool_config(mempool.unwrap_or_default(), options);
+        let p2p = p2p_config(p2p.unwrap_or_default(), options)?;
         let rpc = RpcConfigFile::with_run_options(chain_config, rpc.unwrap_or_default(), options);
+        let metrics = MetricsConfigFile::with_run_options(metrics.unwrap_or_default(), options);
 
         Ok(Self {
             blockprod: Some(blockprod),
             chainstate: Some(chainstate),
+            mempool: Some(mempool),
             p2p: Some(p2p),
             rpc: Some(rpc),
+            metrics: Some(metrics),
         })
     }
 }
@@ -106,10 +152,13 @@ fn blockprod_config(config: BlockProdConfigFile, options: &RunOptions) -> BlockP
     const DEFAULT_MIN_PEERS_TO_PRODUCE_BLOCKS: usize = 3;
 
     let BlockProdConfigFile {
+        enabled,
         min_peers_to_produce_blocks,
         skip_ibd_check,
     } = config;
 
+    let enabled = options.blockprod_enabled.or(enabled).unwrap_or(DEFAULT_BLOCKPROD_ENABLED);
+
     let min_peers_to_produce_blocks = options
         .blockprod_min_peers_to_produce_blocks
         .or(min_peers_to_produce_blocks.or(Some(DEFAULT_MIN_PEERS_TO_PRODUCE_BLOCKS)));
@@ -117,6 +166,7 @@ fn blockprod_config(config: BlockProdConfigFile, options: &RunOptions) -> BlockP
     let skip_ibd_check = options.blockprod_skip_ibd_check.or(skip_ibd_check).unwrap_or(false);
 
     BlockProdConfigFile {
+        enabled: Some(enabled),
         min_peers_to_produce_blocks,
         skip_ibd_check: Some(skip_ibd_check),
     }
@@ -125,7 +175,7 @@ fn blockprod_config(config: BlockProdConfigFile, options: &RunOptions) -> BlockP
 fn chainstate_config(
     config: ChainstateLauncherConfigFile,
     options: &RunOptions,
-) -> ChainstateLauncherConfigFile {
+) -> Result<ChainstateLauncherConfigFile> {
     let ChainstateLauncherConfigFile {
         storage_backend,
         chainstate_config,
@@ -137,6 +187,9 @@ fn chainstate_config(
         min_max_bootstrap_import_buffer_sizes,
         tx_index_enabled,
         max_tip_age,
+        commit_batch_size,
+        prune_depth,
+        reindex_tx_index,
     } = chainstate_config;
 
     let storage_backend = options.storage_backend.clone().unwrap_or(storage_backend);
@@ -144,6 +197,15 @@ fn chainstate_config(
     let max_orphan_blocks = options.max_orphan_blocks.or(max_orphan_blocks);
     let tx_index_enabled = options.tx_index_enabled.or(tx_index_enabled);
     let max_tip_age = options.max_tip_age.or(max_tip_age);
+    let commit_batch_size = options.chainstate_commit_batch_size.or(commit_batch_size);
+    let prune_depth = options.chainstate_prune_depth.or(prune_depth);
+    let reindex_tx_index = options.reindex_tx_index.or(reindex_tx_index);
+
+    // Pruned block bodies can't be served from the transaction index, so the two are exclusive.
+    anyhow::ensure!(
+        prune_depth.is_none() || tx_index_enabled != Some(true),
+        "Block pruning (prune_depth) can't be used together with the transaction index (tx_index_enabled)"
+    );
 
     let chainstate_config = ChainstateConfigFile {
         max_db_commit_attempts,
@@ -151,14 +213,42 @@ fn chainstate_config(
         min_max_bootstrap_import_buffer_sizes,
         tx_index_enabled,
         max_tip_age,
+        commit_batch_size,
+        prune_depth,
+        reindex_tx_index,
     };
-    ChainstateLauncherConfigFile {
+    Ok(ChainstateLauncherConfigFile {
         storage_backend,
         chainstate_config,
+    })
+}
+
+fn mempool_config(config: MempoolConfigFile, options: &RunOptions) -> MempoolConfigFile {
+    let MempoolConfigFile {
+        peer_max_txs_per_sec,
+        peer_max_tx_bytes_per_sec,
+        peer_tx_burst_period,
+        enable_rbf,
+        rbf_fee_rate_bump,
+    } = config;
+
+    let peer_max_txs_per_sec = options.mempool_peer_max_txs_per_sec.or(peer_max_txs_per_sec);
+    let peer_max_tx_bytes_per_sec =
+        options.mempool_peer_max_tx_bytes_per_sec.or(peer_max_tx_bytes_per_sec);
+    let peer_tx_burst_period = options.mempool_peer_tx_burst_period.or(peer_tx_burst_period);
+    let enable_rbf = options.mempool_enable_rbf.or(enable_rbf);
+    let rbf_fee_rate_bump = options.mempool_rbf_fee_rate_bump.or(rbf_fee_rate_bump);
+
+    MempoolConfigFile {
+        peer_max_txs_per_sec,
+        peer_max_tx_bytes_per_sec,
+        peer_tx_burst_period,
+        enable_rbf,
+        rbf_fee_rate_bump,
     }
 }
 
-fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
+fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> Result<P2pConfigFile> {
     let P2pConfigFile {
         bind_addresses,
         socks5_proxy,
@@ -166,6 +256,7 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
         boot_nodes,
         reserved_nodes,
         max_inbound_connections,
+        max_outbound_connections,
         ban_threshold,
         ban_duration,
         max_clock_diff,
@@ -174,14 +265,28 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
         ping_timeout,
         sync_stalling_timeout,
         node_type,
+        tx_relay_enabled,
+        allow_discover_private_ips,
+        mdns_enable,
+        mdns_query_interval,
+        mdns_enable_ipv6_discovery,
+        reserved_node_reconnect_base_delay,
+        reserved_node_reconnect_max_delay,
     } = config;
 
-    let bind_addresses = options.p2p_addr.clone().or(bind_addresses);
+    let bind_addresses = options
+        .p2p_addr
+        .clone()
+        .or(bind_addresses)
+        .map(|addresses| normalize_bind_addresses(&addresses))
+        .transpose()?;
     let socks5_proxy = options.p2p_socks5_proxy.clone().or(socks5_proxy);
     let disable_noise = options.p2p_disable_noise.or(disable_noise);
     let boot_nodes = options.p2p_boot_node.clone().or(boot_nodes);
     let reserved_nodes = options.p2p_reserved_node.clone().or(reserved_nodes);
     let max_inbound_connections = options.p2p_max_inbound_connections.or(max_inbound_connections);
+    let max_outbound_connections =
+        options.p2p_max_outbound_connections.or(max_outbound_connections);
     let ban_threshold = options.p2p_ban_threshold.or(ban_threshold);
     let ping_check_period = options.p2p_ping_check_period.or(ping_check_period);
     let ping_timeout = options.p2p_ping_timeout.or(ping_timeout);
@@ -190,14 +295,28 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
         options.p2p_outbound_connection_timeout.or(outbound_connection_timeout);
     let sync_stalling_timeout = options.p2p_sync_stalling_timeout.or(sync_stalling_timeout);
     let node_type = options.node_type.or(node_type);
-
-    P2pConfigFile {
+    let tx_relay_enabled = options.p2p_tx_relay_enabled.or(tx_relay_enabled);
+    let allow_discover_private_ips =
+        options.p2p_allow_discover_private_ips.or(allow_discover_private_ips);
+    let mdns_enable = options.p2p_mdns_enable.or(mdns_enable);
+    let mdns_query_interval = options.p2p_mdns_query_interval.or(mdns_query_interval);
+    let mdns_enable_ipv6_discovery =
+        options.p2p_mdns_enable_ipv6_discovery.or(mdns_enable_ipv6_discovery);
+    let reserved_node_reconnect_base_delay = options
+        .p2p_reserved_node_reconnect_base_delay
+        .or(reserved_node_reconnect_base_delay);
+    let reserved_node_reconnect_max_delay = options
+        .p2p_reserved_node_reconnect_max_delay
+        .or(reserved_node_reconnect_max_delay);
+
+    Ok(P2pConfigFile {
         bind_addresses,
         socks5_proxy,
         disable_noise,
         boot_nodes,
         reserved_nodes,
         max_inbound_connections,
+        max_outbound_connections,
         ban_threshold,
         ban_duration,
         max_clock_diff,
@@ -206,7 +325,32 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
         ping_timeout,
         sync_stalling_timeout,
         node_type,
+        tx_relay_enabled,
+        allow_discover_private_ips,
+        mdns_enable,
+        mdns_query_interval,
+        mdns_enable_ipv6_discovery,
+        reserved_node_reconnect_base_delay,
+        reserved_node_reconnect_max_delay,
+    })
+}
+
+/// Parses the p2p bind addresses, so the malformed ones are reported before the node starts,
+/// and removes the duplicates.
+fn normalize_bind_addresses(addresses: &[String]) -> Result<Vec<String>> {
+    let mut result = Vec::with_capacity(addresses.len());
+    for address in addresses {
+        let parsed = address
+            .parse::<SocketAddress>()
+            .with_context(|| format!("Invalid p2p bind address \"{address}\""))?
+            .to_string();
+        if result.contains(&parsed) {
+            log::warn!("Duplicate p2p bind address \"{address}\" is ignored");
+        } else {
+            result.push(parsed);
+        }
     }
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -223,6 +367,7 @@ mod tests {
         let _config: BlockProdConfigFile = toml::from_str("").unwrap();
         let _config: ChainstateLauncherConfigFile = toml::from_str("").unwrap();
         let _config: ChainstateConfigFile = toml::from_str("").unwrap();
+        let _config: MempoolConfigFile = toml::from_str("").unwrap();
         let _config: P2pConfigFile = toml::from_str("").unwrap();
         let _config: RpcConfigFile = toml::from_str("").unwrap();
     }
@@ -262,4 +407,19 @@ mod tests {
 
         let _err = NodeConfigFile::read_to_string_with_policy(config_path).unwrap_err();
     }
+
+    #[test]
+    fn pruning_with_tx_index_rejected() {
+        let config: ChainstateLauncherConfigFile =
+            toml::from_str("prune_depth = 1000\ntx_index_enabled = true").unwrap();
+        let _err = chainstate_config(config.clone(), &RunOptions::default()).unwrap_err();
+
+        let options = RunOptions {
+            tx_index_enabled: Some(false),
+            ..Default::default()
+        };
+        let config = chainstate_config(config, &options).unwrap();
+        assert_eq!(config.chainstate_config.prune_depth, Some(1000));
+        assert_eq!(config.chainstate_config.tx_index_enabled, Some(false));
+    }
 }
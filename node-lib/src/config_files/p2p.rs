@@ -19,7 +19,7 @@ use common::primitives::user_agent::mintlayer_core_user_agent;
 use serde::{Deserialize, Serialize};
 
 use p2p::{
-    config::{NodeType, P2pConfig},
+    config::{MdnsConfig, NodeType, P2pConfig},
     types::ip_or_socket_address::IpOrSocketAddress,
 };
 
@@ -32,6 +32,9 @@ pub enum NodeTypeConfigFile {
     /// A node that only download blocks, but ignores transactions.
     #[serde(rename = "blocks-only-node", alias = "blocks")]
     BlocksOnlyNode,
+    /// A node that only exchanges block headers.
+    #[serde(rename = "headers-only-node", alias = "headers")]
+    HeadersOnlyNode,
 }
 
 impl From<NodeTypeConfigFile> for NodeType {
@@ -39,6 +42,7 @@ impl From<NodeTypeConfigFile> for NodeType {
         match t {
             NodeTypeConfigFile::FullNode => Self::Full,
             NodeTypeConfigFile::BlocksOnlyNode => Self::BlocksOnly,
+            NodeTypeConfigFile::HeadersOnlyNode => Self::HeadersOnly,
         }
     }
 }
@@ -57,6 +61,8 @@ impl FromStr for NodeTypeConfigFile {
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct P2pConfigFile {
     /// Address to bind P2P to.
+    ///
+    /// The addresses are validated and de-duplicated when the config is loaded.
     pub bind_addresses: Option<Vec<String>>,
     /// SOCKS5 proxy.
     pub socks5_proxy: Option<String>,
@@ -68,6 +74,8 @@ pub struct P2pConfigFile {
     pub reserved_nodes: Option<Vec<IpOrSocketAddress>>,
     /// Maximum allowed number of inbound connections.
     pub max_inbound_connections: Option<usize>,
+    /// Maximum number of automatic outbound connections, at most 11 (the default).
+    pub max_outbound_connections: Option<usize>,
     /// The score threshold after which a peer is banned.
     pub ban_threshold: Option<u32>,
     /// Duration of bans in seconds.
@@ -85,6 +93,21 @@ pub struct P2pConfigFile {
     pub sync_stalling_timeout: Option<NonZeroU64>,
     /// A node type.
     pub node_type: Option<NodeTypeConfigFile>,
+    /// Whether mempool transactions are relayed. If disabled, the node is a blocks-only node.
+    pub tx_relay_enabled: Option<bool>,
+    /// Allow the peers with private and local IP addresses to be discovered and connected to.
+    pub allow_discover_private_ips: Option<bool>,
+    /// Enable local peer discovery over multicast DNS (disabled by default).
+    /// Peers on a private network are only used if `allow_discover_private_ips` is set.
+    pub mdns_enable: Option<bool>,
+    /// How often the local network is queried for other nodes (in seconds).
+    pub mdns_query_interval: Option<NonZeroU64>,
+    /// Also use IPv6 multicast for local peer discovery.
+    pub mdns_enable_ipv6_discovery: Option<bool>,
+    /// The initial delay between reconnection attempts to a reserved node (in seconds).
+    pub reserved_node_reconnect_base_delay: Option<NonZeroU64>,
+    /// The maximum delay between reconnection attempts to a reserved node (in seconds).
+    pub reserved_node_reconnect_max_delay: Option<NonZeroU64>,
 }
 
 impl From<P2pConfigFile> for P2pConfig {
@@ -96,6 +119,7 @@ impl From<P2pConfigFile> for P2pConfig {
             boot_nodes: c.boot_nodes.clone().unwrap_or_default(),
             reserved_nodes: c.reserved_nodes.clone().unwrap_or_default(),
             max_inbound_connections: c.max_inbound_connections.into(),
+            max_outbound_connections: c.max_outbound_connections.into(),
             ban_threshold: c.ban_threshold.into(),
             ban_duration: c.ban_duration.map(Duration::from_secs).into(),
             max_clock_diff: c.max_clock_diff.map(Duration::from_secs).into(),
@@ -105,8 +129,12 @@ impl From<P2pConfigFile> for P2pConfig {
                 .into(),
             ping_check_period: c.ping_check_period.map(Duration::from_secs).into(),
             ping_timeout: c.ping_timeout.map(|t| Duration::from_secs(t.into())).into(),
-            node_type: c.node_type.map(Into::into).into(),
-            allow_discover_private_ips: Default::default(),
+            node_type: match c.tx_relay_enabled {
+                Some(false) => Some(NodeType::BlocksOnly),
+                Some(true) | None => c.node_type.map(Into::into),
+            }
+            .into(),
+            allow_discover_private_ips: c.allow_discover_private_ips.into(),
             msg_header_count_limit: Default::default(),
             msg_max_locator_count: Default::default(),
             max_request_blocks_count: Default::default(),
@@ -119,6 +147,29 @@ impl From<P2pConfigFile> for P2pConfig {
                 .map(|t| Duration::from_secs(t.into()))
                 .into(),
             enable_block_relay_peers: Default::default(),
+            sync_maintenance_interval: Default::default(),
+            parallel_chainstate_reads: Default::default(),
+            reserved_node_reconnect_base_delay: c
+                .reserved_node_reconnect_base_delay
+                .map(|t| Duration::from_secs(t.into()))
+                .into(),
+            reserved_node_reconnect_max_delay: c
+                .reserved_node_reconnect_max_delay
+                .map(|t| Duration::from_secs(t.into()))
+                .into(),
+            mdns_config: if c.mdns_enable.unwrap_or(false) {
+                MdnsConfig::Enabled {
+                    query_interval: c
+                        .mdns_query_interval
+                        .map(|t| Duration::from_secs(t.into()))
+                        .into(),
+                    enable_ipv6_mdns_discovery: c.mdns_enable_ipv6_discovery.unwrap_or(false),
+                }
+            } else {
+                MdnsConfig::Disabled
+            },
+            // Depends on the chainstate config, set by the node runner
+            blocks_pruned: Default::default(),
         }
     }
 }
@@ -39,6 +39,12 @@ pub struct RpcConfigFile {
 
     /// Custom file path for the RPC cookie file
     pub cookie_file: Option<String>,
+
+    /// Path to the PEM encoded certificate chain to serve RPC over TLS
+    pub tls_cert_file: Option<String>,
+
+    /// Path to the PEM encoded private key to serve RPC over TLS
+    pub tls_key_file: Option<String>,
 }
 
 impl RpcConfigFile {
@@ -60,6 +66,8 @@ impl RpcConfigFile {
             username,
             password,
             cookie_file,
+            tls_cert_file,
+            tls_key_file,
         } = config;
 
         let http_bind_address = options
@@ -72,6 +80,8 @@ impl RpcConfigFile {
         let username = username.or(options.rpc_username.clone());
         let password = password.or(options.rpc_password.clone());
         let cookie_file = cookie_file.or(options.rpc_cookie_file.clone());
+        let tls_cert_file = options.rpc_tls_cert.clone().or(tls_cert_file);
+        let tls_key_file = options.rpc_tls_key.clone().or(tls_key_file);
 
         RpcConfigFile {
             http_bind_address: Some(http_bind_address),
@@ -79,6 +89,8 @@ impl RpcConfigFile {
             username,
             password,
             cookie_file,
+            tls_cert_file,
+            tls_key_file,
         }
     }
 }
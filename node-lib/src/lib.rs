@@ -16,6 +16,7 @@
 //! Top-level node runner as a library
 
 mod config_files;
+mod metrics;
 mod mock_time;
 pub mod node_controller;
 mod options;
@@ -28,8 +29,8 @@ use chainstate_launcher::ChainConfig;
 pub use config_files::{
     NodeConfigFile, NodeTypeConfigFile, RpcConfigFile, StorageBackendConfigFile,
 };
-pub use options::{Command, Options, RunOptions};
-pub use runner::setup;
+pub use options::{CleanDataTarget, Command, GenerateConfigOptions, Network, Options, RunOptions};
+pub use runner::{setup, NodeSetupResult};
 
 pub fn default_rpc_config(chain_config: &ChainConfig) -> RpcConfigFile {
     RpcConfigFile::with_run_options(
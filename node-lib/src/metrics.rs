@@ -0,0 +1,235 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prometheus metrics endpoint
+
+use std::{
+    convert::Infallible,
+    fmt::Write,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::Context;
+use hyper::{
+    header,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use tokio::{sync::oneshot, task::JoinHandle};
+
+use chainstate::{ChainstateEvent, ChainstateHandle};
+use logging::log;
+use mempool::MempoolHandle;
+use p2p::P2pHandle;
+
+const METRICS_PATH: &str = "/metrics";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Handles to the subsystems the metrics are collected from
+#[derive(Clone)]
+struct Sources {
+    chainstate: ChainstateHandle,
+    mempool: MempoolHandle,
+    p2p: P2pHandle,
+    /// The number of blocks the mainchain has grown by since the node started
+    blocks_processed: Arc<AtomicU64>,
+}
+
+/// The metrics subsystem, serving the node metrics in the Prometheus text format
+pub struct Metrics {
+    bind_address: SocketAddr,
+    shutdown_sender: oneshot::Sender<()>,
+    server_task: JoinHandle<()>,
+}
+
+impl Metrics {
+    pub async fn new(
+        bind_address: SocketAddr,
+        chainstate: ChainstateHandle,
+        mempool: MempoolHandle,
+        p2p: P2pHandle,
+    ) -> anyhow::Result<Self> {
+        let blocks_processed = Arc::new(AtomicU64::new(0));
+        chainstate
+            .call_mut({
+                let blocks_processed = Arc::clone(&blocks_processed);
+                move |this| {
+                    // Note: one NewTip event is generated for a whole batch of blocks during
+                    // the initial block download, so the height difference is counted instead
+                    // of the events.
+                    let start_height = this.get_best_block_height()?.into_int();
+                    let max_tip_height = AtomicU64::new(start_height);
+                    this.subscribe_to_events(Arc::new(move |event| match event {
+                        ChainstateEvent::NewTip(_, height) => {
+                            let height = height.into_int();
+                            let prev_max_height =
+                                max_tip_height.fetch_max(height, Ordering::Relaxed);
+                            blocks_processed.fetch_add(
+                                height.saturating_sub(prev_max_height),
+                                Ordering::Relaxed,
+                            );
+                        }
+                    }));
+                    Ok::<_, chainstate::ChainstateError>(())
+                }
+            })
+            .await??;
+
+        let sources = Sources {
+            chainstate,
+            mempool,
+            p2p,
+            blocks_processed,
+        };
+
+        let server = Server::try_bind(&bind_address)
+            .with_context(|| format!("Failed to bind the metrics endpoint to {bind_address}"))?
+            .serve(make_service_fn(move |_conn| {
+                let sources = sources.clone();
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |request| {
+                        handle_request(sources.clone(), request)
+                    }))
+                }
+            }));
+        let bind_address = server.local_addr();
+
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel();
+        let server_task = tokio::spawn(async move {
+            let server = server.with_graceful_shutdown(async {
+                let _ = shutdown_receiver.await;
+            });
+            if let Err(e) = server.await {
+                log::error!("Metrics endpoint failed: {e}");
+            }
+        });
+
+        log::info!("Prometheus metrics endpoint listening on {bind_address}");
+
+        Ok(Self {
+            bind_address,
+            shutdown_sender,
+            server_task,
+        })
+    }
+
+    /// The address the metrics endpoint is actually bound to
+    pub fn bind_address(&self) -> &SocketAddr {
+        &self.bind_address
+    }
+}
+
+#[async_trait::async_trait]
+impl subsystem::Subsystem for Metrics {
+    type Interface = Self;
+
+    fn interface_ref(&self) -> &Self {
+        self
+    }
+
+    fn interface_mut(&mut self) -> &mut Self {
+        self
+    }
+
+    async fn shutdown(self) {
+        let _ = self.shutdown_sender.send(());
+        if let Err(e) = self.server_task.await {
+            log::error!("Metrics endpoint task failed: {e}");
+        }
+    }
+}
+
+async fn handle_request(
+    sources: Sources,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    if request.method() != Method::GET || request.uri().path() != METRICS_PATH {
+        return Ok(status_response(StatusCode::NOT_FOUND));
+    }
+
+    let response = match collect_metrics(&sources).await {
+        Ok(metrics) => Response::builder()
+            .header(header::CONTENT_TYPE, CONTENT_TYPE)
+            .body(Body::from(metrics))
+            .expect("Valid response"),
+        Err(e) => {
+            log::error!("Failed to collect metrics: {e:#}");
+            status_response(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    };
+    Ok(response)
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    Response::builder().status(status).body(Body::empty()).expect("Valid response")
+}
+
+async fn collect_metrics(sources: &Sources) -> anyhow::Result<String> {
+    let best_block_height = sources.chainstate.call(|this| this.get_best_block_height()).await??;
+    let connected_peers = sources.p2p.call_async(|this| this.get_peer_count()).await??;
+    let (mempool_transactions, mempool_memory_usage) = sources
+        .mempool
+        .call(|this| (this.transaction_count(), this.memory_usage()))
+        .await?;
+    let blocks_processed = sources.blocks_processed.load(Ordering::Relaxed);
+
+    let mut out = String::new();
+    write_metric(
+        &mut out,
+        "mintlayer_best_block_height",
+        "gauge",
+        "Height of the best block",
+        best_block_height.into_int(),
+    );
+    write_metric(
+        &mut out,
+        "mintlayer_connected_peers",
+        "gauge",
+        "Number of connected peers",
+        connected_peers as u64,
+    );
+    write_metric(
+        &mut out,
+        "mintlayer_mempool_transactions",
+        "gauge",
+        "Number of transactions in the mempool",
+        mempool_transactions as u64,
+    );
+    write_metric(
+        &mut out,
+        "mintlayer_mempool_memory_usage_bytes",
+        "gauge",
+        "Memory used by the mempool",
+        mempool_memory_usage as u64,
+    );
+    write_metric(
+        &mut out,
+        "mintlayer_blocks_processed_total",
+        "counter",
+        "Number of blocks the mainchain has grown by since the node started",
+        blocks_processed,
+    );
+    Ok(out)
+}
+
+fn write_metric(out: &mut String, name: &str, metric_type: &str, help: &str, value: u64) {
+    writeln!(out, "# HELP {name} {help}").expect("Writing to a string can't fail");
+    writeln!(out, "# TYPE {name} {metric_type}").expect("Writing to a string can't fail");
+    writeln!(out, "{name} {value}").expect("Writing to a string can't fail");
+}
@@ -25,7 +25,8 @@ pub struct NodeController {
     pub shutdown_trigger: ShutdownTrigger,
     pub chainstate: chainstate::ChainstateHandle,
     pub mempool: mempool::MempoolHandle,
-    pub block_prod: blockprod::BlockProductionHandle,
+    /// None if block production is disabled
+    pub block_prod: Option<blockprod::BlockProductionHandle>,
     pub p2p: p2p::P2pHandle,
 }
 
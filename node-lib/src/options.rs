@@ -17,7 +17,7 @@
 
 use std::{ffi::OsString, net::SocketAddr, num::NonZeroU64, path::PathBuf};
 
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use common::chain::config::{regtest_options::ChainConfigOptions, ChainType};
 use p2p::types::ip_or_socket_address::IpOrSocketAddress;
 use utils::default_data_dir::default_data_dir_common;
@@ -46,6 +46,36 @@ pub enum Command {
     Testnet(RunOptions),
     /// Run the regtest node.
     Regtest(Box<RegtestOptions>),
+    /// Write a default config file into the data directory without starting the node.
+    GenerateConfig(GenerateConfigOptions),
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct GenerateConfigOptions {
+    /// The network to generate the config for.
+    #[clap(long, value_enum, default_value = "testnet")]
+    pub network: Network,
+
+    /// Overwrite the config file if it already exists.
+    #[clap(long)]
+    pub force: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Regtest,
+}
+
+impl Network {
+    pub fn chain_type(&self) -> ChainType {
+        match self {
+            Network::Mainnet => ChainType::Mainnet,
+            Network::Testnet => ChainType::Testnet,
+            Network::Regtest => ChainType::Regtest,
+        }
+    }
 }
 
 #[derive(Args, Clone, Debug)]
@@ -56,12 +86,38 @@ pub struct RegtestOptions {
     pub chain_config: ChainConfigOptions,
 }
 
+/// A part of the data dir that can be cleaned separately.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CleanDataTarget {
+    /// The chainstate database
+    Chainstate,
+    /// The database of known p2p peers
+    Peerdb,
+}
+
 #[derive(Args, Clone, Debug, Default)]
 pub struct RunOptions {
-    /// Clean data dir before starting
+    /// Clean data dir before starting.
+    ///
+    /// The data is removed permanently, so a confirmation is required: either an interactive
+    /// one or with `--clean-data-force`.
     #[clap(long)]
     pub clean_data: Option<bool>,
 
+    /// Clean only the selected data before starting, keeping everything else in the data dir.
+    ///
+    /// Requires the same confirmation as `--clean-data`.
+    #[clap(long, value_enum, value_delimiter = ',')]
+    pub clean_data_only: Option<Vec<CleanDataTarget>>,
+
+    /// Confirm cleaning the data dir without the interactive prompt.
+    #[clap(long)]
+    pub clean_data_force: Option<bool>,
+
+    /// Enable/Disable the block production subsystem.
+    #[clap(long)]
+    pub blockprod_enabled: Option<bool>,
+
     /// Minimum number of connected peers to enable block production.
     #[clap(long)]
     pub blockprod_min_peers_to_produce_blocks: Option<usize>,
@@ -82,6 +138,9 @@ pub struct RunOptions {
     pub blockprod_skip_ibd_check: Option<bool>,
 
     /// Storage backend to use.
+    ///
+    /// Use "inmemory" to run an ephemeral node that doesn't touch the disk; with this backend,
+    /// all the chainstate data is lost on shutdown.
     #[clap(long)]
     pub storage_backend: Option<StorageBackendConfigFile>,
 
@@ -89,6 +148,11 @@ pub struct RunOptions {
     #[clap(long)]
     pub node_type: Option<NodeTypeConfigFile>,
 
+    /// Enable/Disable relaying mempool transactions to peers.
+    /// If disabled, the node runs as a blocks-only node.
+    #[clap(long)]
+    pub p2p_tx_relay_enabled: Option<bool>,
+
     /// Mock time used to initialize the node time at startup, in seconds (valid only for regtest).
     #[clap(long)]
     #[arg(hide = true)]
@@ -106,6 +170,13 @@ pub struct RunOptions {
     #[clap(long)]
     pub tx_index_enabled: Option<bool>,
 
+    /// Build the transaction index from the already stored blocks on startup.
+    ///
+    /// This is needed if the transaction index is enabled for a node that has already been
+    /// synced without it. The blocks are neither downloaded nor validated again.
+    #[clap(long)]
+    pub reindex_tx_index: Option<bool>,
+
     /// Address to bind P2P to.
     #[clap(long, value_name = "ADDR")]
     pub p2p_addr: Option<Vec<String>>,
@@ -131,6 +202,11 @@ pub struct RunOptions {
     #[clap(long)]
     pub p2p_max_inbound_connections: Option<usize>,
 
+    /// Maximum number of automatic outbound connections, at most 11 (the default).
+    /// Connections to the reserved nodes are not limited.
+    #[clap(long)]
+    pub p2p_max_outbound_connections: Option<usize>,
+
     /// The p2p score threshold after which a peer is baned.
     #[clap(long)]
     pub p2p_ban_threshold: Option<u32>,
@@ -157,6 +233,33 @@ pub struct RunOptions {
     #[clap(long)]
     pub p2p_max_clock_diff: Option<u64>,
 
+    /// Allow the peers with private and local IP addresses to be discovered and connected to
+    /// (disabled by default).
+    #[clap(long)]
+    pub p2p_allow_discover_private_ips: Option<bool>,
+
+    /// Enable local peer discovery over multicast DNS (disabled by default).
+    /// Peers on a private network are only used if `p2p_allow_discover_private_ips` is set.
+    #[clap(long)]
+    pub p2p_mdns_enable: Option<bool>,
+
+    /// How often the local network is queried for other nodes (in seconds).
+    #[clap(long)]
+    pub p2p_mdns_query_interval: Option<NonZeroU64>,
+
+    /// Also use IPv6 multicast for local peer discovery.
+    #[clap(long)]
+    pub p2p_mdns_enable_ipv6_discovery: Option<bool>,
+
+    /// The initial delay between reconnection attempts to a reserved node (in seconds).
+    /// The delay is doubled after every failed attempt.
+    #[clap(long)]
+    pub p2p_reserved_node_reconnect_base_delay: Option<NonZeroU64>,
+
+    /// The maximum delay between reconnection attempts to a reserved node (in seconds).
+    #[clap(long)]
+    pub p2p_reserved_node_reconnect_max_delay: Option<NonZeroU64>,
+
     /// A maximum tip age in seconds.
     ///
     /// The initial block download is finished if the difference between the current time and the
@@ -164,6 +267,43 @@ pub struct RunOptions {
     #[clap(long, overrides_with("max_tip_age"))]
     pub max_tip_age: Option<u64>,
 
+    /// The maximum number of blocks committed to the storage in a single transaction during
+    /// the initial block download.
+    #[clap(long)]
+    pub chainstate_commit_batch_size: Option<usize>,
+
+    /// Remove the bodies of the blocks buried under this many blocks to reduce the disk usage.
+    ///
+    /// Block headers and indices are kept, so the chain can still be validated, but the pruned
+    /// blocks can't be served to other peers anymore. Wallets and the API server can't scan
+    /// the pruned part of the chain, and the bootstrap export is disabled.
+    #[clap(long)]
+    pub chainstate_prune_depth: Option<u64>,
+
+    /// Maximum sustained number of transactions per second accepted from a single peer.
+    #[clap(long)]
+    pub mempool_peer_max_txs_per_sec: Option<u32>,
+
+    /// Maximum sustained number of transaction bytes per second accepted from a single peer.
+    #[clap(long)]
+    pub mempool_peer_max_tx_bytes_per_sec: Option<usize>,
+
+    /// The period (in seconds) the per-peer transaction burst allowance is computed from.
+    ///
+    /// A peer may send up to this many seconds' worth of transactions at once.
+    #[clap(long)]
+    pub mempool_peer_tx_burst_period: Option<u64>,
+
+    /// Enable/Disable replacing mempool transactions that signal replaceability by conflicting
+    /// transactions paying higher fees.
+    #[clap(long)]
+    pub mempool_enable_rbf: Option<bool>,
+
+    /// The minimum fee rate increase (in atoms per 1000 bytes) a replacement received from a peer
+    /// has to pay over the transactions it replaces.
+    #[clap(long)]
+    pub mempool_rbf_fee_rate_bump: Option<u128>,
+
     /// Address to bind http RPC to.
     #[clap(long, value_name = "ADDR")]
     pub http_rpc_addr: Option<SocketAddr>,
@@ -186,6 +326,22 @@ pub struct RunOptions {
     /// If not set, the cookie file is created in the data dir.
     #[clap(long)]
     pub rpc_cookie_file: Option<String>,
+
+    /// Path to the PEM encoded certificate chain to serve http RPC over TLS.
+    /// Must be set together with `--rpc-tls-key`.
+    /// Plain http RPC isn't served then, and batch requests aren't supported over TLS.
+    #[clap(long)]
+    pub rpc_tls_cert: Option<String>,
+
+    /// Path to the PEM encoded private key to serve http RPC over TLS.
+    /// Must be set together with `--rpc-tls-cert`.
+    #[clap(long)]
+    pub rpc_tls_key: Option<String>,
+
+    /// Address to bind the Prometheus metrics endpoint to.
+    /// The endpoint is disabled if not set.
+    #[clap(long, value_name = "ADDR")]
+    pub prometheus_addr: Option<SocketAddr>,
 }
 
 impl Options {
@@ -19,7 +19,36 @@ use std::{sync::Arc, time::Duration};
 
 use chainstate_launcher::ChainConfig;
 use rpc::Result as RpcResult;
-use subsystem::ShutdownTrigger;
+use subsystem::{HealthMonitor, ShutdownTrigger, SubsystemHealth, SubsystemState};
+
+/// A subsystem whose heartbeat is older than this is considered wedged.
+const MAX_HEARTBEAT_AGE: Duration =
+    Duration::from_secs(3 * subsystem::HEARTBEAT_INTERVAL.as_secs());
+
+/// Liveness of a single subsystem as reported over RPC
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SubsystemHealthInfo {
+    pub name: String,
+    pub state: String,
+    pub healthy: bool,
+    pub seconds_since_last_heartbeat: Option<u64>,
+}
+
+impl From<&SubsystemHealth> for SubsystemHealthInfo {
+    fn from(health: &SubsystemHealth) -> Self {
+        let state = match health.state() {
+            SubsystemState::Starting => "starting",
+            SubsystemState::Running => "running",
+            SubsystemState::Stopped => "stopped",
+        };
+        Self {
+            name: health.full_name().to_owned(),
+            state: state.to_owned(),
+            healthy: health.is_healthy(MAX_HEARTBEAT_AGE),
+            seconds_since_last_heartbeat: health.since_last_heartbeat().map(|d| d.as_secs()),
+        }
+    }
+}
 
 #[rpc::rpc(server, client, namespace = "node")]
 pub trait NodeRpc {
@@ -33,17 +62,27 @@ pub trait NodeRpc {
 
     #[method(name = "set_mock_time")]
     fn set_mock_time(&self, time: u64) -> RpcResult<()>;
+
+    /// Get the liveness of each subsystem
+    #[method(name = "subsystem_health")]
+    fn subsystem_health(&self) -> RpcResult<Vec<SubsystemHealthInfo>>;
 }
 
 struct NodeRpc {
     shutdown_trigger: ShutdownTrigger,
+    health_monitor: HealthMonitor,
     chain_config: Arc<ChainConfig>,
 }
 
 impl NodeRpc {
-    fn new(shutdown_trigger: ShutdownTrigger, chain_config: Arc<ChainConfig>) -> Self {
+    fn new(
+        shutdown_trigger: ShutdownTrigger,
+        health_monitor: HealthMonitor,
+        chain_config: Arc<ChainConfig>,
+    ) -> Self {
         Self {
             shutdown_trigger,
+            health_monitor,
             chain_config,
         }
     }
@@ -72,8 +111,16 @@ impl NodeRpcServer for NodeRpc {
         crate::mock_time::set_mock_time(*self.chain_config.chain_type(), time)?;
         Ok(())
     }
+
+    fn subsystem_health(&self) -> RpcResult<Vec<SubsystemHealthInfo>> {
+        Ok(self.health_monitor.report().iter().map(SubsystemHealthInfo::from).collect())
+    }
 }
 
-pub fn init(shutdown_trigger: ShutdownTrigger, chain_config: Arc<ChainConfig>) -> rpc::Methods {
-    NodeRpc::new(shutdown_trigger, chain_config).into_rpc().into()
+pub fn init(
+    shutdown_trigger: ShutdownTrigger,
+    health_monitor: HealthMonitor,
+    chain_config: Arc<ChainConfig>,
+) -> rpc::Methods {
+    NodeRpc::new(shutdown_trigger, health_monitor, chain_config).into_rpc().into()
 }
@@ -17,16 +17,19 @@
 
 use std::{
     fs::File,
+    io::IsTerminal,
     path::{Path, PathBuf},
     sync::Arc,
 };
 
 use anyhow::{anyhow, Context, Result};
 use blockprod::rpc::BlockProductionRpcServer;
-use chainstate_launcher::{ChainConfig, StorageBackendConfig};
+use chainstate_launcher::{ChainConfig, ChainstateLauncherConfig, StorageBackendConfig};
 use common::chain::config::{regtest_options::regtest_chain_config, Builder as ChainConfigBuilder};
 
-use chainstate::{rpc::ChainstateRpcServer, ChainstateError, InitializationError};
+use chainstate::{
+    rpc::ChainstateRpcServer, ChainstateError, InitializationError, StorageCompatibilityCheckError,
+};
 use common::chain::config::{assert_no_ignore_consensus_in_chain_config, ChainType};
 use logging::log;
 
@@ -34,20 +37,24 @@ use mempool::rpc::MempoolRpcServer;
 
 use test_rpc_functions::{empty::make_empty_rpc_test_functions, rpc::RpcTestFunctionsRpcServer};
 
-use p2p::{peer_manager::peerdb::storage_impl::PeerDbStorageImpl, rpc::P2pRpcServer};
-use rpc::rpc_creds::RpcCreds;
+use p2p::{
+    config::P2pConfig, peer_manager::peerdb::storage_impl::PeerDbStorageImpl, rpc::P2pRpcServer,
+};
+use rpc::{rpc_creds::RpcCreds, rpc_tls::RpcTlsConfig};
 use test_rpc_functions::make_rpc_test_functions;
 use utils::default_data_dir::prepare_data_dir;
 
 use crate::{
-    config_files::{NodeConfigFile, DEFAULT_HTTP_RPC_ENABLED},
+    config_files::{NodeConfigFile, DEFAULT_BLOCKPROD_ENABLED, DEFAULT_HTTP_RPC_ENABLED},
+    metrics::Metrics,
     mock_time::set_mock_time,
     node_controller::NodeController,
-    options::{default_data_dir, Command, Options, RunOptions},
+    options::{default_data_dir, CleanDataTarget, Command, Options, RunOptions},
     RpcConfigFile,
 };
 
 const LOCK_FILE_NAME: &str = ".lock";
+const PEERDB_SUBDIRECTORY: &str = "peerdb-lmdb";
 
 pub struct Node {
     manager: subsystem::Manager,
@@ -66,6 +73,14 @@ impl Node {
     }
 }
 
+/// The outcome of processing the command line options.
+pub enum NodeSetupResult {
+    /// The node is ready to run
+    Node(Node),
+    /// A default config file has been written to the given path; the node isn't started
+    ConfigGenerated(PathBuf),
+}
+
 /// Initialize the node, giving caller the opportunity to add more subsystems before start.
 async fn initialize(
     chain_config: ChainConfig,
@@ -82,16 +97,20 @@ async fn initialize(
     let mut manager = subsystem::Manager::new_with_config(manager_config);
 
     // Chainstate subsystem
+    let chainstate_config: ChainstateLauncherConfig =
+        node_config.chainstate.unwrap_or_default().into();
+    let blocks_pruned = chainstate_config.chainstate_config.prune_depth.is_some();
     let chainstate = chainstate_launcher::make_chainstate(
         &data_dir,
         Arc::clone(&chain_config),
-        node_config.chainstate.unwrap_or_default().into(),
+        chainstate_config,
     )?;
     let chainstate = manager.add_subsystem("chainstate", chainstate);
 
     // Mempool subsystem
     let mempool = mempool::make_mempool(
         Arc::clone(&chain_config),
+        node_config.mempool.unwrap_or_default().into(),
         subsystem::Handle::clone(&chainstate),
         Default::default(),
     );
@@ -100,14 +119,19 @@ async fn initialize(
     // P2P subsystem
     // TODO: Replace Lmdb with Sqlite backend when it's ready
     let peerdb_storage = PeerDbStorageImpl::new(storage_lmdb::Lmdb::new(
-        data_dir.join("peerdb-lmdb"),
+        data_dir.join(PEERDB_SUBDIRECTORY),
         Default::default(),
         Default::default(),
         Default::default(),
     ))?;
+    // Peers are told in the handshake not to request old blocks from a pruning node
+    let p2p_config = P2pConfig {
+        blocks_pruned: blocks_pruned.into(),
+        ..node_config.p2p.unwrap_or_default().into()
+    };
     let p2p = p2p::make_p2p(
         Arc::clone(&chain_config),
-        Arc::new(node_config.p2p.unwrap_or_default().into()),
+        Arc::new(p2p_config),
         subsystem::Handle::clone(&chainstate),
         subsystem::Handle::clone(&mempool),
         Default::default(),
@@ -116,17 +140,23 @@ async fn initialize(
     .add_to_manager("p2p", &mut manager);
 
     // Block production
-    let block_prod = manager.add_subsystem(
-        "blockprod",
-        blockprod::make_blockproduction(
-            Arc::clone(&chain_config),
-            Arc::new(node_config.blockprod.unwrap_or_default().into()),
-            subsystem::Handle::clone(&chainstate),
-            subsystem::Handle::clone(&mempool),
-            subsystem::Handle::clone(&p2p),
-            Default::default(),
-        )?,
-    );
+    let blockprod_config = node_config.blockprod.unwrap_or_default();
+    let block_prod = if blockprod_config.enabled.unwrap_or(DEFAULT_BLOCKPROD_ENABLED) {
+        Some(manager.add_subsystem(
+            "blockprod",
+            blockprod::make_blockproduction(
+                Arc::clone(&chain_config),
+                Arc::new(blockprod_config.into()),
+                subsystem::Handle::clone(&chainstate),
+                subsystem::Handle::clone(&mempool),
+                subsystem::Handle::clone(&p2p),
+                Default::default(),
+            )?,
+        ))
+    } else {
+        log::info!("Block production is disabled");
+        None
+    };
 
     // RPC Functions for tests
     let rpc_test_functions = if chain_config.chain_type() == &ChainType::Regtest {
@@ -150,7 +180,17 @@ async fn initialize(
             rpc_config.cookie_file.as_deref(),
         )?;
 
-        let rpc = rpc::Builder::new(
+        let rpc_tls = match (&rpc_config.tls_cert_file, &rpc_config.tls_key_file) {
+            (Some(cert_file), Some(key_file)) => Some(RpcTlsConfig::new(cert_file, key_file)),
+            (None, None) => None,
+            (Some(_), None) | (None, Some(_)) => {
+                return Err(anyhow!(
+                    "Both the RPC TLS certificate and key must be set to enable TLS for RPC"
+                ))
+            }
+        };
+
+        let mut rpc = rpc::Builder::new(
             rpc_config
                 .http_bind_address
                 .unwrap_or_else(|| RpcConfigFile::default_bind_address(&chain_config)),
@@ -158,19 +198,37 @@ async fn initialize(
         )
         .register(crate::rpc::init(
             manager.make_shutdown_trigger(),
+            manager.health_monitor(),
             chain_config,
         ))
-        .register(block_prod.clone().into_rpc())
         .register(chainstate.clone().into_rpc())
         .register(mempool.clone().into_rpc())
         .register(p2p.clone().into_rpc())
-        .register(rpc_test_functions.into_rpc())
-        .build();
+        .register(rpc_test_functions.into_rpc());
+        if let Some(block_prod) = &block_prod {
+            rpc = rpc.register(block_prod.clone().into_rpc());
+        }
+        if let Some(rpc_tls) = rpc_tls {
+            rpc = rpc.with_tls(rpc_tls);
+        }
 
-        let rpc = rpc.await?;
+        let rpc = rpc.build().await?;
         let _rpc = manager.add_subsystem("rpc", rpc);
     };
 
+    // Metrics subsystem
+    let metrics_config = node_config.metrics.unwrap_or_default();
+    if let Some(bind_address) = metrics_config.prometheus_bind_address {
+        let metrics = Metrics::new(
+            bind_address,
+            chainstate.clone(),
+            mempool.clone(),
+            p2p.clone(),
+        )
+        .await?;
+        let _metrics = manager.add_subsystem("metrics", metrics);
+    }
+
     let controller = NodeController {
         shutdown_trigger: manager.make_shutdown_trigger(),
         chainstate: chainstate.clone(),
@@ -183,7 +241,7 @@ async fn initialize(
 }
 
 /// Processes options and potentially runs the node.
-pub async fn setup(options: Options) -> Result<Node> {
+pub async fn setup(options: Options) -> Result<NodeSetupResult> {
     let command = options.command.clone().unwrap_or(Command::Testnet(RunOptions::default()));
     match command {
         Command::Mainnet(ref run_options) => {
@@ -195,6 +253,7 @@ pub async fn setup(options: Options) -> Result<Node> {
                 chain_config,
             )
             .await
+            .map(NodeSetupResult::Node)
         }
         Command::Testnet(ref run_options) => {
             let chain_config = ChainConfigBuilder::new(ChainType::Testnet).build();
@@ -205,6 +264,7 @@ pub async fn setup(options: Options) -> Result<Node> {
                 chain_config,
             )
             .await
+            .map(NodeSetupResult::Node)
         }
         Command::Regtest(ref regtest_options) => {
             let chain_config = regtest_chain_config(&regtest_options.chain_config)?;
@@ -215,10 +275,41 @@ pub async fn setup(options: Options) -> Result<Node> {
                 chain_config,
             )
             .await
+            .map(NodeSetupResult::Node)
+        }
+        Command::GenerateConfig(ref generate_options) => {
+            let chain_config =
+                ChainConfigBuilder::new(generate_options.network.chain_type()).build();
+            // The config is placed in the data directory, so make sure it exists
+            prepare_data_dir(
+                || default_data_dir(*chain_config.chain_type()),
+                &options.data_dir,
+            )?;
+            let config_path = options.config_path(*chain_config.chain_type());
+            generate_config(&chain_config, &config_path, generate_options.force)?;
+            log::info!("Config file written to {}", config_path.display());
+            Ok(NodeSetupResult::ConfigGenerated(config_path))
         }
     }
 }
 
+/// Writes the default config file for the given chain.
+/// Fails if the file already exists, unless `force` is set.
+fn generate_config(chain_config: &ChainConfig, config_path: &Path, force: bool) -> Result<()> {
+    if config_path.exists() && !force {
+        return Err(anyhow!(
+            "Config file {} already exists, use --force to overwrite it",
+            config_path.display()
+        ));
+    }
+
+    let config = NodeConfigFile::default_commented(chain_config)?;
+    std::fs::write(config_path, config)
+        .with_context(|| format!("Failed to write config file {}", config_path.display()))?;
+
+    Ok(())
+}
+
 /// Creates an exclusive lock file in the specified directory.
 /// Fails if the lock file cannot be created or is already locked.
 fn lock_data_dir(data_dir: &PathBuf) -> Result<std::fs::File> {
@@ -229,6 +320,72 @@ fn lock_data_dir(data_dir: &PathBuf) -> Result<std::fs::File> {
     Ok(lock)
 }
 
+/// Asks the user on the terminal, returns false if there is no terminal.
+fn ask_confirmation(question: &str) -> bool {
+    if !std::io::stdin().is_terminal() {
+        return false;
+    }
+
+    eprint!("{question} [y/N] ");
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
+/// Cleans the data dir as requested by the run options.
+/// Nothing is removed without a confirmation, either forced by the options or from `confirm`.
+fn clean_data(
+    data_dir: &Path,
+    run_options: &RunOptions,
+    storage_config: &StorageBackendConfig,
+    confirm: impl FnOnce(&str) -> bool,
+) -> Result<()> {
+    let clean_all = run_options.clean_data.unwrap_or(false);
+    let targets = run_options.clean_data_only.clone().unwrap_or_default();
+    if !clean_all && targets.is_empty() {
+        return Ok(());
+    }
+
+    let what = if clean_all {
+        "all the data".to_owned()
+    } else {
+        format!("the {targets:?} data")
+    };
+    let question = format!(
+        "This will permanently delete {what} in {}. Continue?",
+        data_dir.display()
+    );
+    if !run_options.clean_data_force.unwrap_or(false) && !confirm(&question) {
+        return Err(anyhow!(
+            "Cleaning the data dir is not confirmed, use --clean-data-force true to skip the confirmation"
+        ));
+    }
+
+    if clean_all {
+        log::warn!("Cleaning the data dir {}", data_dir.display());
+        return clean_data_dir(
+            data_dir,
+            std::slice::from_ref(&data_dir.join(LOCK_FILE_NAME).as_path()),
+        );
+    }
+
+    for target in targets {
+        let subdirectory = match target {
+            CleanDataTarget::Chainstate => storage_config.subdirectory_name(),
+            CleanDataTarget::Peerdb => Some(PEERDB_SUBDIRECTORY),
+        };
+        if let Some(path) = subdirectory.map(|subdirectory| data_dir.join(subdirectory)) {
+            if path.exists() {
+                log::warn!("Removing {}", path.display());
+                std::fs::remove_dir_all(path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 fn clean_data_dir(data_dir: &Path, exclude: &[&Path]) -> Result<()> {
     for entry in std::fs::read_dir(data_dir)? {
         let entry_path = entry?.path();
@@ -268,12 +425,9 @@ async fn start(
     .expect("Failed to prepare data directory");
     let lock_file = lock_data_dir(&data_dir)?;
 
-    if run_options.clean_data.unwrap_or(false) {
-        clean_data_dir(
-            &data_dir,
-            std::slice::from_ref(&data_dir.join(LOCK_FILE_NAME).as_path()),
-        )?;
-    }
+    let storage_config: StorageBackendConfig =
+        node_config.chainstate.clone().unwrap_or_default().storage_backend.into();
+    clean_data(&data_dir, run_options, &storage_config, ask_confirmation)?;
 
     log::info!("Starting with the following config:\n {node_config:#?}");
     let (manager, controller) = match initialize(
@@ -285,6 +439,12 @@ async fn start(
     {
         Ok((manager, controller)) => (manager, controller),
         Err(error) => match error.downcast_ref::<ChainstateError>() {
+            // Never wipe the data written by a newer node, the user may want to go back to it
+            Some(ChainstateError::FailedToInitializeChainstate(
+                InitializationError::StorageCompatibilityCheckError(
+                    StorageCompatibilityCheckError::ChainstateStorageVersionTooNew(_, _),
+                ),
+            )) => return Err(error),
             Some(ChainstateError::FailedToInitializeChainstate(
                 InitializationError::StorageCompatibilityCheckError(e),
             )) => {
@@ -323,6 +483,8 @@ mod test {
     use tempfile::TempDir;
 
     use super::*;
+    use crate::StorageBackendConfigFile;
+    use chainstate_launcher::SUBDIRECTORY_LMDB;
 
     fn test_file_data(file_path: &Path, expected_contents: &[u8]) {
         let mut file = std::fs::File::open(file_path).unwrap();
@@ -438,4 +600,342 @@ mod test {
 
         test_file_data(&file_path, &file_data);
     }
+
+    #[tokio::test]
+    async fn blockprod_disabled() {
+        let data_dir = TempDir::new().unwrap();
+        let chain_config = common::chain::config::create_regtest();
+        let options = RunOptions {
+            blockprod_enabled: Some(false),
+            storage_backend: Some(StorageBackendConfigFile::InMemory),
+            p2p_addr: Some(vec!["127.0.0.1:0".to_owned()]),
+            http_rpc_enabled: Some(false),
+            ..Default::default()
+        };
+        let node_config = NodeConfigFile::read(
+            &chain_config,
+            &data_dir.path().join("config.toml"),
+            &options,
+        )
+        .unwrap();
+
+        let (manager, controller) =
+            initialize(chain_config, data_dir.path().to_owned(), node_config).await.unwrap();
+        let shutdown_trigger = manager.make_shutdown_trigger();
+        let manager_task = manager.main_in_task();
+
+        // The block production subsystem isn't started, but the others are running
+        assert!(controller.block_prod.is_none());
+        let best_block_height = controller
+            .chainstate
+            .call(|chainstate| chainstate.get_best_block_height())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(best_block_height, 0.into());
+
+        shutdown_trigger.initiate();
+        manager_task.join().await;
+    }
+
+    async fn best_block_height(controller: &NodeController) -> common::primitives::BlockHeight {
+        controller
+            .chainstate
+            .call(|chainstate| chainstate.get_best_block_height())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    // The in-memory backend allows running a node that doesn't touch the disk,
+    // but nothing survives a restart
+    #[tokio::test]
+    async fn in_memory_storage_is_ephemeral() {
+        let data_dir = TempDir::new().unwrap();
+        let chain_config = common::chain::config::create_regtest();
+        let options = RunOptions {
+            blockprod_enabled: Some(true),
+            blockprod_min_peers_to_produce_blocks: Some(0),
+            blockprod_skip_ibd_check: Some(true),
+            storage_backend: Some(StorageBackendConfigFile::InMemory),
+            p2p_addr: Some(vec!["127.0.0.1:0".to_owned()]),
+            http_rpc_enabled: Some(false),
+            ..Default::default()
+        };
+        let node_config = NodeConfigFile::read(
+            &chain_config,
+            &data_dir.path().join("config.toml"),
+            &options,
+        )
+        .unwrap();
+
+        // Produce a block
+        {
+            let (manager, controller) = initialize(
+                chain_config.clone(),
+                data_dir.path().to_owned(),
+                node_config.clone(),
+            )
+            .await
+            .unwrap();
+            let shutdown_trigger = manager.make_shutdown_trigger();
+            let manager_task = manager.main_in_task();
+
+            let block = controller
+                .block_prod
+                .as_ref()
+                .unwrap()
+                .call_async_mut(|block_prod| {
+                    block_prod.generate_block(
+                        consensus::GenerateBlockInputData::None,
+                        vec![],
+                        vec![],
+                        mempool::tx_accumulator::PackingStrategy::LeaveEmptySpace,
+                    )
+                })
+                .await
+                .unwrap()
+                .unwrap();
+            controller
+                .chainstate
+                .call_mut(|chainstate| {
+                    chainstate.process_block(block, chainstate::BlockSource::Local)
+                })
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(best_block_height(&controller).await, 1.into());
+
+            shutdown_trigger.initiate();
+            manager_task.join().await;
+        }
+
+        // Restart the node; the block is gone
+        {
+            let (manager, controller) =
+                initialize(chain_config, data_dir.path().to_owned(), node_config).await.unwrap();
+            let shutdown_trigger = manager.make_shutdown_trigger();
+            let manager_task = manager.main_in_task();
+
+            assert_eq!(best_block_height(&controller).await, 0.into());
+
+            shutdown_trigger.initiate();
+            manager_task.join().await;
+        }
+    }
+
+    // Scraping the metrics endpoint returns well-formed Prometheus text
+    #[tokio::test]
+    async fn prometheus_metrics() {
+        let data_dir = TempDir::new().unwrap();
+        let chain_config = common::chain::config::create_regtest();
+        // Find a free port for the endpoint
+        let prometheus_addr =
+            std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let options = RunOptions {
+            blockprod_enabled: Some(false),
+            storage_backend: Some(StorageBackendConfigFile::InMemory),
+            p2p_addr: Some(vec!["127.0.0.1:0".to_owned()]),
+            http_rpc_enabled: Some(false),
+            prometheus_addr: Some(prometheus_addr),
+            ..Default::default()
+        };
+        let node_config = NodeConfigFile::read(
+            &chain_config,
+            &data_dir.path().join("config.toml"),
+            &options,
+        )
+        .unwrap();
+
+        let (manager, _controller) =
+            initialize(chain_config, data_dir.path().to_owned(), node_config).await.unwrap();
+        let shutdown_trigger = manager.make_shutdown_trigger();
+        let manager_task = manager.main_in_task();
+
+        let client = hyper::Client::new();
+
+        let response = client
+            .get(format!("http://{prometheus_addr}/metrics").parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), hyper::StatusCode::OK);
+        assert_eq!(
+            response.headers()[hyper::header::CONTENT_TYPE],
+            "text/plain; version=0.0.4"
+        );
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+
+        let mut samples = std::collections::BTreeMap::new();
+        for line in body.lines() {
+            if line.starts_with('#') {
+                let mut parts = line.splitn(4, ' ');
+                assert_eq!(parts.next(), Some("#"));
+                assert!(matches!(parts.next(), Some("HELP" | "TYPE")));
+                assert!(parts.next().is_some());
+                assert!(parts.next().is_some());
+            } else {
+                let (name, value) = line.split_once(' ').unwrap();
+                assert!(name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
+                assert!(body.contains(&format!("# TYPE {name} ")));
+                samples.insert(name.to_owned(), value.parse::<f64>().unwrap());
+            }
+        }
+        assert_eq!(samples["mintlayer_best_block_height"], 0.0);
+        assert_eq!(samples["mintlayer_connected_peers"], 0.0);
+        assert_eq!(samples["mintlayer_mempool_transactions"], 0.0);
+        assert_eq!(samples["mintlayer_blocks_processed_total"], 0.0);
+        assert!(samples.contains_key("mintlayer_mempool_memory_usage_bytes"));
+
+        let response = client
+            .get(format!("http://{prometheus_addr}/other").parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), hyper::StatusCode::NOT_FOUND);
+
+        shutdown_trigger.initiate();
+        manager_task.join().await;
+    }
+
+    #[test]
+    fn generate_config_overwrite() {
+        let data_dir = TempDir::new().unwrap();
+        let config_path = data_dir.path().join("config.toml");
+        let chain_config = common::chain::config::create_testnet();
+
+        generate_config(&chain_config, &config_path, false).unwrap();
+        let generated = std::fs::read_to_string(&config_path).unwrap();
+
+        // An existing file is kept intact unless forced
+        std::fs::write(&config_path, "[rpc]\n").unwrap();
+        generate_config(&chain_config, &config_path, false).unwrap_err();
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), "[rpc]\n");
+
+        generate_config(&chain_config, &config_path, true).unwrap();
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), generated);
+    }
+
+    fn make_data_dir() -> TempDir {
+        let data_dir = TempDir::new().unwrap();
+        for subdirectory in [SUBDIRECTORY_LMDB, PEERDB_SUBDIRECTORY] {
+            std::fs::create_dir(data_dir.path().join(subdirectory)).unwrap();
+        }
+        std::fs::write(data_dir.path().join("wallet.dat"), "wallet").unwrap();
+        data_dir
+    }
+
+    fn data_dir_entries(data_dir: &TempDir) -> Vec<String> {
+        let mut entries = std::fs::read_dir(data_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        entries.sort();
+        entries
+    }
+
+    #[test]
+    fn clean_data_requires_confirmation() {
+        let data_dir = make_data_dir();
+        let entries = data_dir_entries(&data_dir);
+        let options = RunOptions {
+            clean_data: Some(true),
+            ..Default::default()
+        };
+
+        clean_data(
+            data_dir.path(),
+            &options,
+            &StorageBackendConfig::Lmdb,
+            |_| false,
+        )
+        .unwrap_err();
+        assert_eq!(data_dir_entries(&data_dir), entries);
+
+        clean_data(
+            data_dir.path(),
+            &options,
+            &StorageBackendConfig::Lmdb,
+            |_| true,
+        )
+        .unwrap();
+        assert!(data_dir_entries(&data_dir).is_empty());
+    }
+
+    #[test]
+    fn clean_data_forced() {
+        let data_dir = make_data_dir();
+        let options = RunOptions {
+            clean_data: Some(true),
+            clean_data_force: Some(true),
+            ..Default::default()
+        };
+
+        clean_data(
+            data_dir.path(),
+            &options,
+            &StorageBackendConfig::Lmdb,
+            |_| panic!("no confirmation expected"),
+        )
+        .unwrap();
+        assert!(data_dir_entries(&data_dir).is_empty());
+    }
+
+    #[test]
+    fn clean_data_selective() {
+        for (targets, expected) in [
+            (
+                vec![CleanDataTarget::Chainstate],
+                vec![PEERDB_SUBDIRECTORY, "wallet.dat"],
+            ),
+            (
+                vec![CleanDataTarget::Peerdb],
+                vec![SUBDIRECTORY_LMDB, "wallet.dat"],
+            ),
+            (
+                vec![CleanDataTarget::Chainstate, CleanDataTarget::Peerdb],
+                vec!["wallet.dat"],
+            ),
+        ] {
+            let data_dir = make_data_dir();
+            let options = RunOptions {
+                clean_data_only: Some(targets),
+                clean_data_force: Some(true),
+                ..Default::default()
+            };
+
+            clean_data(
+                data_dir.path(),
+                &options,
+                &StorageBackendConfig::Lmdb,
+                |_| panic!("no confirmation expected"),
+            )
+            .unwrap();
+            assert_eq!(data_dir_entries(&data_dir), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn rpc_tls_requires_cert_and_key() {
+        let data_dir = TempDir::new().unwrap();
+        let chain_config = common::chain::config::create_regtest();
+        let options = RunOptions {
+            storage_backend: Some(StorageBackendConfigFile::InMemory),
+            p2p_addr: Some(vec!["127.0.0.1:0".to_owned()]),
+            http_rpc_addr: Some("127.0.0.1:0".parse().unwrap()),
+            rpc_tls_cert: Some("cert.pem".to_owned()),
+            ..Default::default()
+        };
+        let node_config = NodeConfigFile::read(
+            &chain_config,
+            &data_dir.path().join("config.toml"),
+            &options,
+        )
+        .unwrap();
+
+        let error = initialize(chain_config, data_dir.path().to_owned(), node_config)
+            .await
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("TLS certificate and key"));
+    }
 }
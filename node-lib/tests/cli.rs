@@ -15,7 +15,7 @@
 
 use std::{net::SocketAddr, num::NonZeroU64, path::Path, str::FromStr};
 
-use common::chain::config::create_testnet;
+use common::chain::config::{create_mainnet, create_regtest, create_testnet};
 use p2p::types::ip_or_socket_address::IpOrSocketAddress;
 use tempfile::TempDir;
 
@@ -91,57 +91,94 @@ fn read_config_override_values() {
     let blockprod_skip_ibd_check = true;
     let max_db_commit_attempts = 1;
     let max_orphan_blocks = 2;
-    let p2p_addr = "address";
+    let p2p_addr = "127.0.0.1:3031";
     let p2p_socks5_proxy = "socks5_proxy";
     let p2p_disable_noise = false;
     let p2p_boot_node: IpOrSocketAddress = "127.0.0.1".parse().unwrap();
     let p2p_reserved_node: IpOrSocketAddress = "127.0.0.1".parse().unwrap();
     let p2p_max_inbound_connections = 123;
+    let p2p_max_outbound_connections = 5;
     let p2p_ban_threshold = 3;
     let p2p_timeout = NonZeroU64::new(10000).unwrap();
     let p2p_ping_check_period = 30;
     let p2p_ping_timeout = NonZeroU64::new(60).unwrap();
     let p2p_sync_stalling_timeout = NonZeroU64::new(37).unwrap();
     let p2p_max_clock_diff = 15;
+    let p2p_mdns_query_interval = NonZeroU64::new(30).unwrap();
+    let p2p_reserved_node_reconnect_base_delay = NonZeroU64::new(5).unwrap();
+    let p2p_reserved_node_reconnect_max_delay = NonZeroU64::new(600).unwrap();
     let http_rpc_addr = SocketAddr::from_str("127.0.0.1:5432").unwrap();
     let backend_type = StorageBackendConfigFile::InMemory;
     let node_type = NodeTypeConfigFile::FullNode;
     let max_tip_age = 1000;
+    let chainstate_commit_batch_size = 100;
+    let chainstate_prune_depth = 1000;
+    let mempool_peer_max_txs_per_sec = 20;
+    let mempool_peer_max_tx_bytes_per_sec = 50_000;
+    let mempool_peer_tx_burst_period = 5;
+    let mempool_rbf_fee_rate_bump = 2000;
     let rpc_username = "username";
     let rpc_password = "password";
     let rpc_cookie_file = "cookie_file";
+    let rpc_tls_cert = "cert.pem";
+    let rpc_tls_key = "key.pem";
+    let prometheus_addr = SocketAddr::from_str("127.0.0.1:9100").unwrap();
 
     let options = RunOptions {
+        blockprod_enabled: Some(false),
         blockprod_min_peers_to_produce_blocks: Some(blockprod_min_peers_to_produce_blocks),
         blockprod_skip_ibd_check: Some(blockprod_skip_ibd_check),
         storage_backend: Some(backend_type.clone()),
         node_type: Some(node_type),
+        p2p_tx_relay_enabled: Some(true),
         mock_time: None,
         max_db_commit_attempts: Some(max_db_commit_attempts),
         max_orphan_blocks: Some(max_orphan_blocks),
         tx_index_enabled: Some(false),
+        reindex_tx_index: Some(false),
         p2p_addr: Some(vec![p2p_addr.to_owned()]),
         p2p_socks5_proxy: Some(p2p_socks5_proxy.to_owned()),
         p2p_disable_noise: Some(p2p_disable_noise),
         p2p_boot_node: Some(vec![p2p_boot_node.clone()]),
         p2p_reserved_node: Some(vec![p2p_reserved_node.clone()]),
         p2p_max_inbound_connections: Some(p2p_max_inbound_connections),
+        p2p_max_outbound_connections: Some(p2p_max_outbound_connections),
         p2p_ban_threshold: Some(p2p_ban_threshold),
         p2p_outbound_connection_timeout: Some(p2p_timeout),
         p2p_ping_check_period: Some(p2p_ping_check_period),
         p2p_ping_timeout: Some(p2p_ping_timeout),
         p2p_sync_stalling_timeout: Some(p2p_sync_stalling_timeout),
         p2p_max_clock_diff: Some(p2p_max_clock_diff),
+        p2p_allow_discover_private_ips: Some(true),
+        p2p_mdns_enable: Some(true),
+        p2p_mdns_query_interval: Some(p2p_mdns_query_interval),
+        p2p_mdns_enable_ipv6_discovery: Some(false),
+        p2p_reserved_node_reconnect_base_delay: Some(p2p_reserved_node_reconnect_base_delay),
+        p2p_reserved_node_reconnect_max_delay: Some(p2p_reserved_node_reconnect_max_delay),
         max_tip_age: Some(max_tip_age),
+        chainstate_commit_batch_size: Some(chainstate_commit_batch_size),
+        chainstate_prune_depth: Some(chainstate_prune_depth),
+        mempool_peer_max_txs_per_sec: Some(mempool_peer_max_txs_per_sec),
+        mempool_peer_max_tx_bytes_per_sec: Some(mempool_peer_max_tx_bytes_per_sec),
+        mempool_peer_tx_burst_period: Some(mempool_peer_tx_burst_period),
+        mempool_enable_rbf: Some(true),
+        mempool_rbf_fee_rate_bump: Some(mempool_rbf_fee_rate_bump),
         http_rpc_addr: Some(http_rpc_addr),
         http_rpc_enabled: Some(true),
         rpc_username: Some(rpc_username.to_owned()),
         rpc_password: Some(rpc_password.to_owned()),
         rpc_cookie_file: Some(rpc_cookie_file.to_owned()),
+        rpc_tls_cert: Some(rpc_tls_cert.to_owned()),
+        rpc_tls_key: Some(rpc_tls_key.to_owned()),
+        prometheus_addr: Some(prometheus_addr),
         clean_data: Some(false),
+        clean_data_only: None,
+        clean_data_force: None,
     };
     let config = NodeConfigFile::read(&chain_config, &config_path, &options).unwrap();
 
+    assert_eq!(config.blockprod.clone().unwrap().enabled, Some(false));
+
     assert_eq!(
         config.blockprod.clone().unwrap().min_peers_to_produce_blocks,
         Some(blockprod_min_peers_to_produce_blocks),
@@ -168,6 +205,32 @@ fn read_config_override_values() {
         config.chainstate.clone().unwrap().chainstate_config.max_tip_age,
         Some(max_tip_age)
     );
+    assert_eq!(
+        config.chainstate.clone().unwrap().chainstate_config.commit_batch_size,
+        Some(chainstate_commit_batch_size)
+    );
+    assert_eq!(
+        config.chainstate.clone().unwrap().chainstate_config.prune_depth,
+        Some(chainstate_prune_depth)
+    );
+
+    assert_eq!(
+        config.mempool.clone().unwrap().peer_max_txs_per_sec,
+        Some(mempool_peer_max_txs_per_sec)
+    );
+    assert_eq!(
+        config.mempool.clone().unwrap().peer_max_tx_bytes_per_sec,
+        Some(mempool_peer_max_tx_bytes_per_sec)
+    );
+    assert_eq!(
+        config.mempool.clone().unwrap().peer_tx_burst_period,
+        Some(mempool_peer_tx_burst_period)
+    );
+    assert_eq!(config.mempool.clone().unwrap().enable_rbf, Some(true));
+    assert_eq!(
+        config.mempool.clone().unwrap().rbf_fee_rate_bump,
+        Some(mempool_rbf_fee_rate_bump)
+    );
 
     assert_eq!(
         config.p2p.clone().unwrap().bind_addresses,
@@ -193,6 +256,10 @@ fn read_config_override_values() {
         config.p2p.clone().unwrap().max_inbound_connections,
         Some(p2p_max_inbound_connections)
     );
+    assert_eq!(
+        config.p2p.clone().unwrap().max_outbound_connections,
+        Some(p2p_max_outbound_connections)
+    );
     assert_eq!(
         config.p2p.clone().unwrap().ban_threshold,
         Some(p2p_ban_threshold)
@@ -218,6 +285,28 @@ fn read_config_override_values() {
         Some(p2p_max_clock_diff)
     );
     assert_eq!(config.p2p.clone().unwrap().node_type, Some(node_type));
+    assert_eq!(config.p2p.clone().unwrap().tx_relay_enabled, Some(true));
+    assert_eq!(
+        config.p2p.clone().unwrap().allow_discover_private_ips,
+        Some(true)
+    );
+    assert_eq!(config.p2p.clone().unwrap().mdns_enable, Some(true));
+    assert_eq!(
+        config.p2p.clone().unwrap().mdns_query_interval,
+        Some(p2p_mdns_query_interval)
+    );
+    assert_eq!(
+        config.p2p.clone().unwrap().mdns_enable_ipv6_discovery,
+        Some(false)
+    );
+    assert_eq!(
+        config.p2p.clone().unwrap().reserved_node_reconnect_base_delay,
+        Some(p2p_reserved_node_reconnect_base_delay)
+    );
+    assert_eq!(
+        config.p2p.clone().unwrap().reserved_node_reconnect_max_delay,
+        Some(p2p_reserved_node_reconnect_max_delay)
+    );
 
     assert_eq!(
         config.rpc.clone().unwrap().http_bind_address,
@@ -237,6 +326,88 @@ fn read_config_override_values() {
         config.rpc.as_ref().unwrap().cookie_file.as_deref(),
         Some(rpc_cookie_file)
     );
+    assert_eq!(
+        config.rpc.as_ref().unwrap().tls_cert_file.as_deref(),
+        Some(rpc_tls_cert)
+    );
+    assert_eq!(
+        config.rpc.as_ref().unwrap().tls_key_file.as_deref(),
+        Some(rpc_tls_key)
+    );
+
+    assert_eq!(
+        config.metrics.as_ref().unwrap().prometheus_bind_address,
+        Some(prometheus_addr)
+    );
 
     assert_eq!(config.chainstate.unwrap().storage_backend, backend_type);
 }
+
+// Check that the generated default config is parsed to the default values, both as is
+// and with all the settings uncommented.
+#[test]
+fn generated_config_round_trip() {
+    for chain_config in [create_mainnet(), create_testnet(), create_regtest()] {
+        let data_dir = TempDir::new().unwrap();
+        let config_path = data_dir.path().join(CONFIG_NAME);
+        let options = RunOptions::default();
+
+        let default_config = NodeConfigFile::read(&chain_config, &config_path, &options).unwrap();
+
+        let generated = NodeConfigFile::default_commented(&chain_config).unwrap();
+        std::fs::write(&config_path, &generated).unwrap();
+        let config = NodeConfigFile::read(&chain_config, &config_path, &options).unwrap();
+        assert_eq!(format!("{config:?}"), format!("{default_config:?}"));
+
+        // Skip the header and uncomment everything else
+        let uncommented = generated
+            .lines()
+            .skip_while(|line| !line.is_empty())
+            .map(|line| line.strip_prefix("# ").unwrap_or(line))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(uncommented.contains("[rpc]"));
+        std::fs::write(&config_path, &uncommented).unwrap();
+        let config = NodeConfigFile::read(&chain_config, &config_path, &options).unwrap();
+        assert_eq!(format!("{config:?}"), format!("{default_config:?}"));
+    }
+}
+
+fn read_bind_addresses(addresses: &[&str]) -> anyhow::Result<Option<Vec<String>>> {
+    let data_dir = TempDir::new().unwrap();
+    let config_path = data_dir.path().join(CONFIG_NAME);
+    let options = RunOptions {
+        p2p_addr: Some(addresses.iter().map(|a| (*a).to_owned()).collect()),
+        ..Default::default()
+    };
+    let config = NodeConfigFile::read(&create_testnet(), &config_path, &options)?;
+    Ok(config.p2p.unwrap().bind_addresses)
+}
+
+#[test]
+fn p2p_bind_addresses_valid() {
+    assert_eq!(
+        read_bind_addresses(&["127.0.0.1:3031", "[0:0:0:0:0:0:0:1]:3031"]).unwrap(),
+        Some(vec!["127.0.0.1:3031".to_owned(), "[::1]:3031".to_owned()])
+    );
+}
+
+#[test]
+fn p2p_bind_addresses_invalid() {
+    for address in ["/ip4/127.0.0.1/tcp/3031", "127.0.0.1", "localhost:3031", ""] {
+        let error = read_bind_addresses(&["127.0.0.1:3031", address]).unwrap_err();
+        assert!(
+            error.to_string().contains(&format!("\"{address}\"")),
+            "{error}"
+        );
+    }
+}
+
+#[test]
+fn p2p_bind_addresses_duplicates() {
+    assert_eq!(
+        read_bind_addresses(&["127.0.0.1:3031", "[::1]:3031", "127.0.0.1:3031", "[::0:1]:3031"])
+            .unwrap(),
+        Some(vec!["127.0.0.1:3031".to_owned(), "[::1]:3031".to_owned()])
+    );
+}
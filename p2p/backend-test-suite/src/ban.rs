@@ -114,7 +114,9 @@ where
     )
     .unwrap();
 
-    let sync1_handle = logging::spawn_in_current_span(async move { sync1.run().await });
+    let (sync1_shutdown_sender, sync1_shutdown_receiver) = oneshot::channel();
+    let sync1_handle =
+        logging::spawn_in_current_span(async move { sync1.run(sync1_shutdown_receiver).await });
 
     // spawn `sync2` into background and spam an orphan block on the network
     logging::spawn_in_current_span(async move {
@@ -122,6 +124,7 @@ where
             SyncingEvent::Connected {
                 peer_id,
                 common_services: _,
+                remote_services: _,
                 protocol_version: _,
                 sync_msg_rx,
             } => (peer_id, sync_msg_rx),
@@ -161,6 +164,7 @@ where
 
     shutdown.store(true);
     let _ = shutdown_sender.send(());
+    let _ = sync1_shutdown_sender.send(());
     let _ = sync1_handle.await.unwrap();
     shutdown_trigger.initiate();
     subsystem_manager_handle.join().await;
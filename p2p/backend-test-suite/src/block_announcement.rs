@@ -100,6 +100,7 @@ where
         SyncingEvent::Connected {
             peer_id: _,
             common_services: _,
+            remote_services: _,
             protocol_version: _,
             sync_msg_rx,
         } => sync_msg_rx,
@@ -136,6 +137,7 @@ where
         SyncingEvent::Connected {
             peer_id: _,
             common_services: _,
+            remote_services: _,
             protocol_version: _,
             sync_msg_rx,
         } => sync_msg_rx,
@@ -177,6 +179,7 @@ where
         boot_nodes: Vec::new(),
         reserved_nodes: Vec::new(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -193,6 +196,12 @@ where
         max_singular_unconnected_headers: Default::default(),
         sync_stalling_timeout: Default::default(),
         enable_block_relay_peers: Default::default(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
     });
     let shutdown = Arc::new(SeqCstAtomicBool::new(false));
     let (shutdown_sender_1, shutdown_receiver) = oneshot::channel();
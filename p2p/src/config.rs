@@ -22,6 +22,11 @@ use utils::make_config_setting;
 use crate::net::types::services::{Service, Services};
 
 make_config_setting!(MaxInboundConnections, usize, 128);
+make_config_setting!(
+    MaxOutboundConnections,
+    usize,
+    crate::peer_manager::OUTBOUND_FULL_AND_BLOCK_RELAY_COUNT
+);
 make_config_setting!(BanThreshold, u32, 100);
 make_config_setting!(BanDuration, Duration, Duration::from_secs(60 * 60 * 24));
 make_config_setting!(OutboundConnectionTimeout, Duration, Duration::from_secs(10));
@@ -38,6 +43,20 @@ make_config_setting!(MaxPeerTxAnnouncements, usize, 5000);
 make_config_setting!(MaxUnconnectedHeaders, usize, 10);
 make_config_setting!(SyncStallingTimeout, Duration, Duration::from_secs(5));
 make_config_setting!(BlockRelayPeers, bool, true);
+make_config_setting!(SyncMaintenanceInterval, Duration, Duration::from_secs(1));
+make_config_setting!(ParallelChainstateReads, bool, true);
+make_config_setting!(BlocksPruned, bool, false);
+make_config_setting!(MdnsQueryInterval, Duration, Duration::from_secs(60));
+make_config_setting!(
+    ReservedNodeReconnectBaseDelay,
+    Duration,
+    Duration::from_secs(10)
+);
+make_config_setting!(
+    ReservedNodeReconnectMaxDelay,
+    Duration,
+    Duration::from_secs(360)
+);
 
 /// A node type.
 #[derive(Debug, Copy, Clone)]
@@ -46,6 +65,10 @@ pub enum NodeType {
     Full,
     /// A node that only download blocks, but ignores transactions.
     BlocksOnly,
+    /// A node that only exchanges block headers, but neither downloads nor serves full blocks.
+    ///
+    /// This is useful for lightweight monitoring nodes.
+    HeadersOnly,
     /// A node interested only in network address announcements.
     DnsServer,
     /// A node that doesn't subscribe to any events.
@@ -61,12 +84,28 @@ impl From<NodeType> for Services {
                 .as_slice()
                 .into(),
             NodeType::BlocksOnly => [Service::Blocks, Service::PeerAddresses].as_slice().into(),
+            NodeType::HeadersOnly => [Service::Headers, Service::PeerAddresses].as_slice().into(),
             NodeType::DnsServer => [Service::PeerAddresses].as_slice().into(),
             NodeType::Inactive => [].as_slice().into(),
         }
     }
 }
 
+/// Local peer discovery over multicast DNS.
+#[derive(Debug, Clone, Default)]
+pub enum MdnsConfig {
+    /// Local peer discovery is disabled.
+    #[default]
+    Disabled,
+    /// Peers are announced and discovered on the local network.
+    Enabled {
+        /// How often the local network is queried for other nodes.
+        query_interval: MdnsQueryInterval,
+        /// Also use IPv6 multicast to discover peers.
+        enable_ipv6_mdns_discovery: bool,
+    },
+}
+
 /// The p2p subsystem configuration.
 // TODO: some of these "configuration options" should never be changed in production code,
 // because their values are a part of the protocol, e.g. this includes msg_header_count_limit and
@@ -92,6 +131,10 @@ pub struct P2pConfig {
     pub reserved_nodes: Vec<IpOrSocketAddress>,
     /// Maximum allowed number of inbound connections.
     pub max_inbound_connections: MaxInboundConnections,
+    /// Maximum number of automatic outbound connections.
+    /// Connections to the reserved nodes and the manually requested ones are not limited.
+    /// Can't be greater than the default value.
+    pub max_outbound_connections: MaxOutboundConnections,
     /// The score threshold after which a peer is banned.
     pub ban_threshold: BanThreshold,
     /// Duration of bans in seconds.
@@ -128,4 +171,21 @@ pub struct P2pConfig {
     pub sync_stalling_timeout: SyncStallingTimeout,
     /// Enable/disable block relay peers (only used in unit tests)
     pub enable_block_relay_peers: BlockRelayPeers,
+    /// How often the sync manager performs its periodic maintenance (zero disables it).
+    pub sync_maintenance_interval: SyncMaintenanceInterval,
+    /// Serve read-only chainstate queries of the sync manager from a storage snapshot instead
+    /// of going through the chainstate subsystem.
+    pub parallel_chainstate_reads: ParallelChainstateReads,
+    /// The delay before reconnecting to a reserved node after the first failed attempt.
+    /// The delay is doubled after every consecutive failure and reset once a connection succeeds.
+    pub reserved_node_reconnect_base_delay: ReservedNodeReconnectBaseDelay,
+    /// The maximum delay between reconnection attempts to a reserved node.
+    pub reserved_node_reconnect_max_delay: ReservedNodeReconnectMaxDelay,
+    /// Local peer discovery (mDNS) settings.
+    /// Discovered addresses are filtered the same way as the addresses received from the peers,
+    /// so private ones are only used if `allow_discover_private_ips` is set.
+    pub mdns_config: MdnsConfig,
+    /// The node prunes old block bodies and can't serve them to other peers.
+    /// This is advertised to peers in the handshake, so they don't request such blocks.
+    pub blocks_pruned: BlocksPruned,
 }
@@ -45,6 +45,8 @@ pub enum ProtocolError {
     HeadersLimitExceeded(usize, usize),
     #[error("A peer requested an unknown block ({0})")]
     UnknownBlockRequested(Id<Block>),
+    #[error("A peer requested a block that has been pruned ({0})")]
+    PrunedBlockRequested(Id<Block>),
     #[error("A peer tried to download same block ({0})")]
     DuplicatedBlockRequest(Id<Block>),
     #[error("Headers aren't connected")]
@@ -224,6 +226,8 @@ impl BanScore for ProtocolError {
             ProtocolError::BlocksRequestLimitExceeded(_, _) => 20,
             ProtocolError::HeadersLimitExceeded(_, _) => 20,
             ProtocolError::UnknownBlockRequested(_) => 20,
+            // The peer can't know which blocks are pruned
+            ProtocolError::PrunedBlockRequested(_) => 0,
             ProtocolError::DuplicatedBlockRequest(_) => 20,
             ProtocolError::DisconnectedHeaders => 20,
             ProtocolError::UnexpectedMessage(_) => 20,
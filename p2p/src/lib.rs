@@ -16,6 +16,7 @@
 pub mod config;
 pub mod error;
 pub mod interface;
+pub mod mdns;
 pub mod message;
 pub mod net;
 pub mod peer_manager;
@@ -56,7 +57,7 @@ use peer_manager::peerdb::storage::PeerDbStorage;
 use types::socket_address::SocketAddress;
 
 use crate::{
-    config::P2pConfig,
+    config::{MdnsConfig, P2pConfig},
     error::{ConversionError, P2pError},
     net::{
         default_backend::{
@@ -83,6 +84,7 @@ struct P2p<T: NetworkingService> {
     mempool_handle: MempoolHandle,
 
     backend_shutdown_sender: oneshot::Sender<()>,
+    sync_manager_shutdown_sender: oneshot::Sender<()>,
 
     // TODO: This flag is a workaround for graceful p2p termination.
     // See https://github.com/mintlayer/mintlayer-core/issues/888 for more details.
@@ -91,6 +93,7 @@ struct P2p<T: NetworkingService> {
     backend_task: JoinHandle<()>,
     peer_manager_task: JoinHandle<()>,
     sync_manager_task: JoinHandle<()>,
+    mdns_task: Option<JoinHandle<()>>,
 
     subscribers_sender: mpsc::UnboundedSender<P2pEventHandler>,
 
@@ -144,6 +147,42 @@ where
         // a `oneshot::channel` object that must be used to send the response.
         let (tx_peer_manager, rx_peer_manager) = mpsc::unbounded_channel();
 
+        let mdns_task = match &p2p_config.mdns_config {
+            MdnsConfig::Disabled => None,
+            MdnsConfig::Enabled {
+                query_interval,
+                enable_ipv6_mdns_discovery,
+            } => {
+                let ports = conn
+                    .local_addresses()
+                    .iter()
+                    .map(|address| address.socket_addr().port())
+                    .collect();
+                let mdns = mdns::MdnsService::new(
+                    &chain_config,
+                    ports,
+                    **query_interval,
+                    *enable_ipv6_mdns_discovery,
+                    time_getter.clone(),
+                    tx_peer_manager.clone(),
+                )?;
+                let shutdown_ = Arc::clone(&shutdown);
+                Some(logging::spawn_in_current_span(async move {
+                    match mdns.run().await {
+                        Ok(never) => match never {},
+                        // The channel can be closed during the shutdown process.
+                        Err(P2pError::ChannelClosed) if shutdown_.load() => {
+                            log::info!("mDNS is shut down");
+                        }
+                        // Local discovery is optional, so the node keeps running without it
+                        Err(e) => {
+                            log::error!("mDNS failed: {e:?}");
+                        }
+                    }
+                }))
+            }
+        };
+
         let peer_manager = peer_manager::PeerManager::<T, _>::new(
             Arc::clone(&chain_config),
             Arc::clone(&p2p_config),
@@ -177,10 +216,13 @@ where
             tx_peer_manager.clone(),
             time_getter,
         );
+        let (sync_manager_shutdown_sender, sync_manager_shutdown_receiver) = oneshot::channel();
         let shutdown_ = Arc::clone(&shutdown);
         let sync_manager_task = logging::spawn_in_current_span(async move {
-            match sync_manager.run().await {
-                Ok(never) => match never {},
+            match sync_manager.run(sync_manager_shutdown_receiver).await {
+                Ok(()) => {
+                    log::info!("Sync manager is shut down");
+                }
                 // The channel can be closed during the shutdown process.
                 Err(P2pError::ChannelClosed) if shutdown_.load() => {
                     log::info!("Sync manager is shut down");
@@ -197,9 +239,11 @@ where
             mempool_handle,
             shutdown,
             backend_shutdown_sender,
+            sync_manager_shutdown_sender,
             backend_task,
             peer_manager_task,
             sync_manager_task,
+            mdns_task,
             subscribers_sender,
             _phantom: PhantomData,
         })
@@ -369,6 +413,11 @@ where
     async fn shutdown(self) {
         self.shutdown.store(true);
         let _ = self.backend_shutdown_sender.send(());
+        let _ = self.sync_manager_shutdown_sender.send(());
+        // The mDNS task only waits for the incoming packets, so it's just stopped.
+        if let Some(mdns_task) = &self.mdns_task {
+            mdns_task.abort();
+        }
 
         // Wait for the tasks to shut down.
         futures::future::join_all([
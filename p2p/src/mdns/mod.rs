@@ -0,0 +1,392 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Local peer discovery over multicast DNS.
+//!
+//! Every node announces itself as an instance of the `_ml-<magic bytes>._tcp.local.` service,
+//! so only the nodes of the same network find each other. The node periodically queries
+//! the local network for the service and answers the queries of other nodes with its p2p ports.
+//! The address of a discovered peer is the source IP address of the response combined with
+//! the advertised port.
+//!
+//! Any host on the local network can send responses, so the number of the discovered addresses
+//! is limited, and the peer manager applies the same IP address filter as for the addresses
+//! received from the peers. A discovered address is forgotten if it isn't announced again
+//! within the record TTL, so that the limit is not exhausted by the nodes that have gone away.
+
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+use futures::never::Never;
+use tokio::{net::UdpSocket, sync::mpsc};
+use trust_dns_client::{
+    op::{Message, MessageType, OpCode, Query},
+    rr::{rdata::SRV, Name, RData, Record, RecordType},
+    serialize::binary::{BinDecodable, BinEncodable},
+};
+
+use common::{chain::ChainConfig, primitives::time::Time, time_getter::TimeGetter};
+use crypto::random::{make_pseudo_rng, Rng};
+use logging::log;
+
+use crate::{types::socket_address::SocketAddress, PeerManagerEvent};
+
+/// The standard mDNS port.
+pub const MDNS_PORT: u16 = 5353;
+
+const MDNS_IPV4_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+const MDNS_IPV6_GROUP: Ipv6Addr = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 0xfb);
+
+/// TTL of the announced records (the value recommended by RFC 6762 for SRV records).
+const RECORD_TTL: u32 = 120;
+
+/// Multicast DNS packets can't be larger than this (RFC 6762, section 17).
+const MAX_PACKET_SIZE: usize = 9000;
+
+/// Maximum number of the local peers that are reported to the peer manager.
+const MAX_DISCOVERED_PEERS: usize = 64;
+
+/// Maximum number of the ports accepted from a single host.
+const MAX_PORTS_PER_HOST: usize = 4;
+
+/// How often the query schedule is checked if no packets are received.
+const MAX_WAKEUP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long to wait before receiving again after a socket error, so a persistent error
+/// doesn't result in a busy loop.
+const RECV_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+struct MulticastSocket {
+    socket: UdpSocket,
+    group: SocketAddr,
+}
+
+impl MulticastSocket {
+    fn new(group: IpAddr, port: u16) -> std::io::Result<Self> {
+        let socket = socket2::Socket::new(
+            socket2::Domain::for_address(SocketAddr::new(group, port)),
+            socket2::Type::DGRAM,
+            Some(socket2::Protocol::UDP),
+        )?;
+
+        socket.set_nonblocking(true)?;
+        // Other mDNS responders (and other nodes) on the same host use the same port
+        socket.set_reuse_address(true)?;
+        #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+        socket.set_reuse_port(true)?;
+
+        match group {
+            IpAddr::V4(group) => {
+                socket.bind(&SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), port).into())?;
+                socket.join_multicast_v4(&group, &Ipv4Addr::UNSPECIFIED)?;
+                // Required to discover the nodes running on the same host
+                socket.set_multicast_loop_v4(true)?;
+            }
+            IpAddr::V6(group) => {
+                socket.set_only_v6(true)?;
+                socket.bind(&SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), port).into())?;
+                socket.join_multicast_v6(&group, 0)?;
+                socket.set_multicast_loop_v6(true)?;
+            }
+        }
+
+        Ok(Self {
+            socket: UdpSocket::from_std(socket.into())?,
+            group: SocketAddr::new(group, port),
+        })
+    }
+
+    /// Sends the message to the multicast group; the errors are only logged, because they
+    /// may be transient (e.g. the network interface is down for a moment).
+    async fn send(&self, message: &Message) {
+        let data = message.to_vec().expect("mDNS message encoding must succeed");
+        if let Err(e) = self.socket.send_to(&data, self.group).await {
+            log::warn!("Failed to send mDNS packet to {}: {e}", self.group);
+        }
+    }
+
+    async fn recv_from(
+        socket: Option<&Self>,
+        buf: &mut [u8],
+    ) -> std::io::Result<(usize, SocketAddr)> {
+        match socket {
+            Some(socket) => socket.socket.recv_from(buf).await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+/// The state that is updated when mDNS packets are received.
+struct Responder {
+    service_name: Name,
+    instance_name: Name,
+    ports: Vec<u16>,
+    /// The discovered ports of every host, along with the time when they expire.
+    discovered: BTreeMap<IpAddr, BTreeMap<u16, Time>>,
+    discovered_count: usize,
+    peer_manager_tx: mpsc::UnboundedSender<PeerManagerEvent>,
+}
+
+impl Responder {
+    fn make_query(&self) -> Message {
+        let mut message = Message::new();
+        message
+            .set_message_type(MessageType::Query)
+            .set_op_code(OpCode::Query)
+            .add_query(Query::query(self.service_name.clone(), RecordType::PTR));
+        message
+    }
+
+    fn make_response(&self) -> Message {
+        let mut message = Message::new();
+        message
+            .set_message_type(MessageType::Response)
+            .set_op_code(OpCode::Query)
+            .set_authoritative(true)
+            .add_answer(Record::from_rdata(
+                self.service_name.clone(),
+                RECORD_TTL,
+                RData::PTR(self.instance_name.clone()),
+            ));
+        for port in &self.ports {
+            message.add_answer(Record::from_rdata(
+                self.instance_name.clone(),
+                RECORD_TTL,
+                RData::SRV(SRV::new(0, 0, *port, self.instance_name.clone())),
+            ));
+        }
+        message
+    }
+
+    async fn handle_packet(
+        &mut self,
+        data: &[u8],
+        source: SocketAddr,
+        socket: &MulticastSocket,
+        now: Time,
+    ) -> crate::Result<()> {
+        let message = match Message::from_bytes(data) {
+            Ok(message) => message,
+            Err(e) => {
+                log::trace!("Ignoring invalid mDNS packet from {source}: {e}");
+                return Ok(());
+            }
+        };
+
+        match message.message_type() {
+            MessageType::Query => {
+                let is_our_service = message.queries().iter().any(|query| {
+                    query.name() == &self.service_name
+                        && matches!(query.query_type(), RecordType::PTR | RecordType::ANY)
+                });
+                if is_our_service {
+                    socket.send(&self.make_response()).await;
+                }
+            }
+            MessageType::Response => {
+                for record in message.answers().iter().chain(message.additionals()) {
+                    if record.name() == &self.instance_name
+                        || !self.service_name.zone_of(record.name())
+                    {
+                        continue;
+                    }
+                    if let Some(RData::SRV(srv)) = record.data() {
+                        self.peer_discovered(source.ip(), srv.port(), now)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn peer_discovered(&mut self, ip: IpAddr, port: u16, now: Time) -> crate::Result<()> {
+        if port == 0 {
+            return Ok(());
+        }
+        self.remove_expired(now);
+
+        let expiry_time =
+            (now + Duration::from_secs(RECORD_TTL.into())).expect("Time derived from local clock");
+        if let Some(expires_at) =
+            self.discovered.get_mut(&ip).and_then(|ports| ports.get_mut(&port))
+        {
+            *expires_at = expiry_time;
+            return Ok(());
+        }
+
+        if self.discovered_count >= MAX_DISCOVERED_PEERS {
+            return Ok(());
+        }
+        // Every new entry gets a port, so the map can't grow beyond the limit either
+        let ports = self.discovered.entry(ip).or_default();
+        if ports.len() >= MAX_PORTS_PER_HOST {
+            return Ok(());
+        }
+        ports.insert(port, expiry_time);
+        self.discovered_count += 1;
+
+        let address = SocketAddress::new(SocketAddr::new(ip, port));
+        log::debug!("Local peer discovered: {address}");
+        self.peer_manager_tx.send(PeerManagerEvent::LocalPeerDiscovered(address))?;
+        Ok(())
+    }
+
+    fn remove_expired(&mut self, now: Time) {
+        self.discovered.retain(|_ip, ports| {
+            ports.retain(|_port, expires_at| *expires_at > now);
+            !ports.is_empty()
+        });
+        self.discovered_count = self.discovered.values().map(BTreeMap::len).sum();
+    }
+}
+
+/// Announces the node and discovers the peers on the local network.
+pub struct MdnsService {
+    query_interval: Duration,
+    time_getter: TimeGetter,
+    socket_v4: MulticastSocket,
+    socket_v6: Option<MulticastSocket>,
+    responder: Responder,
+}
+
+impl MdnsService {
+    /// Creates a new mDNS service that advertises the given p2p ports.
+    ///
+    /// Discovered peers are reported to the peer manager as [`PeerManagerEvent::LocalPeerDiscovered`].
+    pub fn new(
+        chain_config: &ChainConfig,
+        ports: Vec<u16>,
+        query_interval: Duration,
+        enable_ipv6: bool,
+        time_getter: TimeGetter,
+        peer_manager_tx: mpsc::UnboundedSender<PeerManagerEvent>,
+    ) -> crate::Result<Self> {
+        Self::with_mdns_port(
+            chain_config,
+            ports,
+            query_interval,
+            enable_ipv6,
+            time_getter,
+            peer_manager_tx,
+            MDNS_PORT,
+        )
+    }
+
+    fn with_mdns_port(
+        chain_config: &ChainConfig,
+        mut ports: Vec<u16>,
+        query_interval: Duration,
+        enable_ipv6: bool,
+        time_getter: TimeGetter,
+        peer_manager_tx: mpsc::UnboundedSender<PeerManagerEvent>,
+        mdns_port: u16,
+    ) -> crate::Result<Self> {
+        ports.sort();
+        ports.dedup();
+
+        let service_name = format!(
+            "_ml-{}._tcp.local.",
+            hex::encode(chain_config.magic_bytes())
+        );
+        // The random instance name allows the node to recognize its own responses
+        let instance_name = format!("{:016x}.{service_name}", make_pseudo_rng().gen::<u64>());
+
+        let socket_v4 = MulticastSocket::new(MDNS_IPV4_GROUP.into(), mdns_port)?;
+        let socket_v6 = enable_ipv6
+            .then(|| MulticastSocket::new(MDNS_IPV6_GROUP.into(), mdns_port))
+            .transpose()?;
+
+        Ok(Self {
+            query_interval,
+            time_getter,
+            socket_v4,
+            socket_v6,
+            responder: Responder {
+                service_name: Name::from_ascii(service_name)
+                    .expect("mDNS service name must be valid"),
+                instance_name: Name::from_ascii(instance_name)
+                    .expect("mDNS instance name must be valid"),
+                ports,
+                discovered: BTreeMap::new(),
+                discovered_count: 0,
+                peer_manager_tx,
+            },
+        })
+    }
+
+    pub async fn run(self) -> crate::Result<Never> {
+        let Self {
+            query_interval,
+            time_getter,
+            socket_v4,
+            socket_v6,
+            mut responder,
+        } = self;
+
+        let mut buf_v4 = vec![0; MAX_PACKET_SIZE];
+        let mut buf_v6 = vec![0; MAX_PACKET_SIZE];
+        let mut next_query = time_getter.get_time();
+
+        loop {
+            let now = time_getter.get_time();
+            if now >= next_query {
+                let query = responder.make_query();
+                socket_v4.send(&query).await;
+                if let Some(socket_v6) = &socket_v6 {
+                    socket_v6.send(&query).await;
+                }
+                next_query = (now + query_interval).expect("Time derived from local clock");
+            }
+            // The time can be adjusted, so the schedule is checked at least every second
+            let wakeup_delay = (next_query - now).unwrap_or_default().min(MAX_WAKEUP_INTERVAL);
+
+            tokio::select! {
+                _ = tokio::time::sleep(wakeup_delay) => {}
+                res = socket_v4.socket.recv_from(&mut buf_v4) => {
+                    match res {
+                        Ok((len, source)) => {
+                            let now = time_getter.get_time();
+                            responder.handle_packet(&buf_v4[..len], source, &socket_v4, now).await?;
+                        }
+                        Err(e) => {
+                            log::warn!("Failed to receive mDNS packet over IPv4: {e}");
+                            tokio::time::sleep(RECV_ERROR_BACKOFF).await;
+                        }
+                    }
+                }
+                res = MulticastSocket::recv_from(socket_v6.as_ref(), &mut buf_v6) => {
+                    match res {
+                        Ok((len, source)) => {
+                            let socket_v6 = socket_v6.as_ref().expect("IPv6 socket must exist");
+                            let now = time_getter.get_time();
+                            responder.handle_packet(&buf_v6[..len], source, socket_v6, now).await?;
+                        }
+                        Err(e) => {
+                            log::warn!("Failed to receive mDNS packet over IPv6: {e}");
+                            tokio::time::sleep(RECV_ERROR_BACKOFF).await;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
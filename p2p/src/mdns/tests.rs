@@ -0,0 +1,200 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common::chain::config::create_unit_test_config;
+
+use super::*;
+
+async fn expect_discovered_port(rx: &mut mpsc::UnboundedReceiver<PeerManagerEvent>, port: u16) {
+    let event = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+        .await
+        .expect("peer must be discovered")
+        .expect("channel must be open");
+    match event {
+        PeerManagerEvent::LocalPeerDiscovered(address) => {
+            assert_eq!(address.socket_addr().port(), port);
+        }
+        event => panic!("unexpected event: {event:?}"),
+    }
+}
+
+// Two nodes running on the same host discover each other
+#[tokio::test]
+async fn two_nodes_discover_each_other() {
+    let chain_config = create_unit_test_config();
+    // Don't interfere with the mDNS responders that may run on the host
+    let mdns_port = make_pseudo_rng().gen_range(20000..60000);
+    let query_interval = Duration::from_millis(100);
+
+    let (tx1, mut rx1) = mpsc::unbounded_channel();
+    let mdns1 = MdnsService::with_mdns_port(
+        &chain_config,
+        vec![10001],
+        query_interval,
+        false,
+        TimeGetter::default(),
+        tx1,
+        mdns_port,
+    )
+    .unwrap();
+    let (tx2, mut rx2) = mpsc::unbounded_channel();
+    let mdns2 = MdnsService::with_mdns_port(
+        &chain_config,
+        vec![10002],
+        query_interval,
+        false,
+        TimeGetter::default(),
+        tx2,
+        mdns_port,
+    )
+    .unwrap();
+
+    let task1 = logging::spawn_in_current_span(mdns1.run());
+    let task2 = logging::spawn_in_current_span(mdns2.run());
+
+    expect_discovered_port(&mut rx1, 10002).await;
+    expect_discovered_port(&mut rx2, 10001).await;
+
+    task1.abort();
+    task2.abort();
+}
+
+// Nodes of different networks ignore each other
+#[tokio::test]
+async fn other_network_ignored() {
+    let chain_config1 = create_unit_test_config();
+    let chain_config2 = common::chain::config::create_regtest();
+    assert_ne!(chain_config1.magic_bytes(), chain_config2.magic_bytes());
+    let mdns_port = make_pseudo_rng().gen_range(20000..60000);
+    let query_interval = Duration::from_millis(100);
+
+    let (tx1, mut rx1) = mpsc::unbounded_channel();
+    let mdns1 = MdnsService::with_mdns_port(
+        &chain_config1,
+        vec![10001],
+        query_interval,
+        false,
+        TimeGetter::default(),
+        tx1,
+        mdns_port,
+    )
+    .unwrap();
+    let (tx2, _rx2) = mpsc::unbounded_channel();
+    let mdns2 = MdnsService::with_mdns_port(
+        &chain_config2,
+        vec![10002],
+        query_interval,
+        false,
+        TimeGetter::default(),
+        tx2,
+        mdns_port,
+    )
+    .unwrap();
+
+    let task1 = logging::spawn_in_current_span(mdns1.run());
+    let task2 = logging::spawn_in_current_span(mdns2.run());
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(rx1.try_recv().is_err());
+
+    task1.abort();
+    task2.abort();
+}
+
+// The number of the reported peers is limited, both per host and in total
+#[tokio::test]
+async fn discovered_peers_limited() {
+    let chain_config = create_unit_test_config();
+    let mdns_port = make_pseudo_rng().gen_range(20000..60000);
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut mdns = MdnsService::with_mdns_port(
+        &chain_config,
+        vec![10001],
+        Duration::from_secs(60),
+        false,
+        TimeGetter::default(),
+        tx,
+        mdns_port,
+    )
+    .unwrap();
+
+    let now = TimeGetter::default().get_time();
+    let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1));
+    for port in 1..=(MAX_PORTS_PER_HOST as u16 + 1) {
+        mdns.responder.peer_discovered(ip, port, now).unwrap();
+    }
+    let mut reported = std::iter::from_fn(|| rx.try_recv().ok()).count();
+    assert_eq!(reported, MAX_PORTS_PER_HOST);
+
+    for i in 0..MAX_DISCOVERED_PEERS {
+        let ip = IpAddr::V4(Ipv4Addr::new(10, 0, (i / 256) as u8, (i % 256) as u8));
+        mdns.responder.peer_discovered(ip, 10000, now).unwrap();
+    }
+    reported += std::iter::from_fn(|| rx.try_recv().ok()).count();
+    assert_eq!(reported, MAX_DISCOVERED_PEERS);
+}
+
+// The discovered peers that are not announced again expire, so new peers can be discovered
+#[tokio::test]
+async fn discovered_peers_expire() {
+    let chain_config = create_unit_test_config();
+    let mdns_port = make_pseudo_rng().gen_range(20000..60000);
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut mdns = MdnsService::with_mdns_port(
+        &chain_config,
+        vec![10001],
+        Duration::from_secs(60),
+        false,
+        TimeGetter::default(),
+        tx,
+        mdns_port,
+    )
+    .unwrap();
+
+    let ttl = Duration::from_secs(RECORD_TTL.into());
+    let start = TimeGetter::default().get_time();
+    for i in 0..MAX_DISCOVERED_PEERS {
+        let ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, i as u8));
+        mdns.responder.peer_discovered(ip, 10000, start).unwrap();
+    }
+    assert_eq!(
+        std::iter::from_fn(|| rx.try_recv().ok()).count(),
+        MAX_DISCOVERED_PEERS
+    );
+
+    // The first peer keeps announcing itself, so it doesn't expire and isn't reported again
+    let first_ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0));
+    let refresh_time = (start + ttl / 2).unwrap();
+    mdns.responder.peer_discovered(first_ip, 10000, refresh_time).unwrap();
+    assert!(rx.try_recv().is_err());
+
+    // The limit is reached
+    let new_ip = IpAddr::V4(Ipv4Addr::new(10, 0, 1, 0));
+    mdns.responder.peer_discovered(new_ip, 10000, refresh_time).unwrap();
+    assert!(rx.try_recv().is_err());
+
+    // The other peers expire
+    let later = (start + ttl).unwrap();
+    mdns.responder.peer_discovered(new_ip, 10000, later).unwrap();
+    expect_discovered_port(&mut rx, 10000).await;
+    assert_eq!(mdns.responder.discovered_count, 2);
+
+    mdns.responder.peer_discovered(first_ip, 10000, later).unwrap();
+    assert!(rx.try_recv().is_err());
+}
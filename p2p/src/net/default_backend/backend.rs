@@ -43,7 +43,7 @@ use crate::{
     net::{
         default_backend::{
             peer,
-            transport::{TransportListener, TransportSocket},
+            transport::{TrafficCounters, TransportListener, TransportSocket},
             types::{BackendEvent, Command, PeerEvent},
         },
         types::{services::Services, ConnectivityEvent, PeerInfo, SyncingEvent},
@@ -54,6 +54,7 @@ use crate::{
 };
 
 use super::{
+    handshake_nonces::OutboundHandshakeNonces,
     peer::ConnectionInfo,
     types::{HandshakeNonce, Message, P2pTimestamp},
 };
@@ -96,6 +97,12 @@ struct PeerContext {
     /// All services that will be enabled for this peer if it's accepted.
     /// The Peer Manager can disconnect the peer if some required services are missing.
     common_services: Services,
+
+    /// Services advertised by the peer.
+    remote_services: Services,
+
+    /// Bytes sent to and received from the peer, including the handshake.
+    traffic_counters: Arc<TrafficCounters>,
 }
 
 /// Pending peer data (until handshake message is received)
@@ -107,6 +114,8 @@ struct PendingPeerContext {
     connection_info: ConnectionInfo,
 
     backend_event_tx: mpsc::UnboundedSender<BackendEvent>,
+
+    traffic_counters: Arc<TrafficCounters>,
 }
 
 pub struct Backend<T: TransportSocket> {
@@ -133,6 +142,9 @@ pub struct Backend<T: TransportSocket> {
     /// Pending connections
     pending: HashMap<PeerId, PendingPeerContext>,
 
+    /// Nonces recently sent in outbound handshakes, used to detect self-connections
+    outbound_nonces: OutboundHandshakeNonces,
+
     /// Map of streams for receiving events from peers.
     peer_event_stream_map: StreamMap<PeerId, ReceiverStream<PeerEvent>>,
 
@@ -188,6 +200,7 @@ where
             syncing_event_tx,
             peers: HashMap::new(),
             pending: HashMap::new(),
+            outbound_nonces: OutboundHandshakeNonces::new(),
             peer_event_stream_map: StreamMap::new(),
             command_queue: FuturesUnordered::new(),
             shutdown,
@@ -208,10 +221,13 @@ where
         match connection_res {
             Ok(socket) => {
                 let handshake_nonce = make_pseudo_rng().gen();
+                let peer_id = PeerId::new();
+                self.outbound_nonces
+                    .insert(handshake_nonce, peer_id, self.time_getter.get_time());
 
                 self.create_pending_peer(
                     socket,
-                    PeerId::new(),
+                    peer_id,
                     ConnectionInfo::Outbound {
                         handshake_nonce,
                         local_services_override,
@@ -249,6 +265,7 @@ where
             SyncingEvent::Connected {
                 peer_id,
                 common_services: peer.common_services,
+                remote_services: peer.remote_services,
                 protocol_version: peer.protocol_version,
                 sync_msg_rx,
             },
@@ -369,6 +386,7 @@ where
             backend_event_rx,
             self.node_protocol_version,
         );
+        let traffic_counters = peer.traffic_counters();
         let shutdown = Arc::clone(&self.shutdown);
         let local_time = P2pTimestamp::from_time(self.time_getter.get_time());
         let handle = logging::spawn_in_current_span(async move {
@@ -386,6 +404,7 @@ where
                 address,
                 connection_info,
                 backend_event_tx,
+                traffic_counters,
             },
         );
 
@@ -407,6 +426,7 @@ where
             address,
             connection_info,
             backend_event_tx,
+            traffic_counters,
         } = match self.pending.remove(&peer_id) {
             Some(pending) => pending,
             // Could be removed if self-connection was detected earlier
@@ -418,6 +438,7 @@ where
         }
 
         let common_services = peer_info.common_services;
+        let remote_services = peer_info.remote_services;
         let protocol_version = peer_info.protocol_version;
         let inbound = connection_info == ConnectionInfo::Inbound;
         let user_agent = peer_info.user_agent.clone();
@@ -453,8 +474,10 @@ where
                 user_agent,
                 software_version,
                 common_services,
+                remote_services,
                 backend_event_tx,
                 was_accepted: SetFlag::new(),
+                traffic_counters,
             },
         );
 
@@ -493,41 +516,40 @@ where
         connection_info: ConnectionInfo,
         incoming_nonce: HandshakeNonce,
     ) -> crate::Result<bool> {
-        if connection_info == ConnectionInfo::Inbound {
-            // Look for own outbound connection with same nonce
-            let outbound_peer_id = self
-                .pending
-                .iter()
-                .find(|(_peer_id, pending)| match pending.connection_info {
-                    ConnectionInfo::Inbound => false,
-                    ConnectionInfo::Outbound {
-                        handshake_nonce,
-                        local_services_override: _,
-                    } => handshake_nonce == incoming_nonce,
-                })
-                .map(|(peer_id, _pending)| *peer_id);
-
-            if let Some(outbound_peer_id) = outbound_peer_id {
-                let outbound_pending =
-                    self.pending.remove(&outbound_peer_id).expect("peer must exist");
-
-                log::info!(
-                    "self-connection detected on address {:?}",
-                    outbound_pending.address
-                );
+        if connection_info != ConnectionInfo::Inbound {
+            return Ok(false);
+        }
 
-                // Report outbound connection failure
-                self.conn_event_tx.send(ConnectivityEvent::ConnectionError {
-                    address: outbound_pending.address,
-                    error: P2pError::DialError(DialError::AttemptToDialSelf),
-                })?;
+        // Only a nonce that we have recently sent ourselves means a self-connection
+        let outbound_peer_id =
+            match self.outbound_nonces.take(incoming_nonce, self.time_getter.get_time()) {
+                Some(peer_id) => peer_id,
+                None => return Ok(false),
+            };
+
+        if let Some(outbound_pending) = self.pending.remove(&outbound_peer_id) {
+            log::info!(
+                "self-connection detected on address {:?}",
+                outbound_pending.address
+            );
 
-                // Nothing else to do, just drop inbound connection
-                return Ok(true);
-            }
+            // Report outbound connection failure
+            self.conn_event_tx.send(ConnectivityEvent::ConnectionError {
+                address: outbound_pending.address,
+                error: P2pError::DialError(DialError::AttemptToDialSelf),
+            })?;
+        } else if let Some(outbound_peer) = self.peers.get(&outbound_peer_id) {
+            // The outbound side has already completed its handshake, close it too
+            log::info!(
+                "self-connection detected on address {:?}",
+                outbound_peer.address
+            );
+
+            self.destroy_peer(outbound_peer_id)?;
         }
 
-        Ok(false)
+        // Nothing else to do, just drop inbound connection
+        Ok(true)
     }
 
     fn handle_peer_event(&mut self, peer_id: PeerId, event: PeerEvent) -> crate::Result<()> {
@@ -536,6 +558,7 @@ where
                 protocol_version,
                 network,
                 common_services,
+                remote_services,
                 user_agent,
                 software_version,
                 receiver_address,
@@ -550,6 +573,7 @@ where
                     software_version,
                     user_agent,
                     common_services,
+                    remote_services,
                 },
                 receiver_address,
             ),
@@ -673,6 +697,10 @@ where
                     log::debug!("Failed to send request to peer {peer_id}: {e}")
                 }
             }
+            Command::GetPeerTraffic { peer_id, response } => {
+                let traffic = self.peers.get(&peer_id).map(|peer| peer.traffic_counters.get());
+                let _ = response.send(traffic);
+            }
         };
     }
 
@@ -0,0 +1,94 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{collections::HashMap, time::Duration};
+
+use common::primitives::time::Time;
+
+use crate::types::peer_id::PeerId;
+
+use super::types::HandshakeNonce;
+
+/// How long a nonce sent in an outbound handshake is remembered.
+///
+/// This is longer than the handshake timeout, so the nonce is still known when the inbound side
+/// of a self-connection receives it, even if the outbound side has finished its handshake first.
+/// Older nonces are forgotten, so a nonce replayed later by a genuine peer isn't mistaken
+/// for a self-connection.
+const OUTBOUND_NONCE_TTL: Duration = Duration::from_secs(60);
+
+/// Nonces that were recently sent in outbound handshakes.
+#[derive(Default)]
+pub struct OutboundHandshakeNonces {
+    nonces: HashMap<HandshakeNonce, (PeerId, Time)>,
+}
+
+impl OutboundHandshakeNonces {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember the nonce sent to the outbound peer
+    pub fn insert(&mut self, nonce: HandshakeNonce, peer_id: PeerId, now: Time) {
+        self.remove_expired(now);
+        let expires_at = now.saturating_duration_add(OUTBOUND_NONCE_TTL);
+        self.nonces.insert(nonce, (peer_id, expires_at));
+    }
+
+    /// If the nonce received in an inbound handshake was sent by us, returns the outbound peer
+    /// it was sent to. Such a connection is a self-connection.
+    pub fn take(&mut self, nonce: HandshakeNonce, now: Time) -> Option<PeerId> {
+        self.remove_expired(now);
+        self.nonces.remove(&nonce).map(|(peer_id, _expires_at)| peer_id)
+    }
+
+    fn remove_expired(&mut self, now: Time) {
+        self.nonces.retain(|_nonce, (_peer_id, expires_at)| *expires_at > now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn own_nonce_detected() {
+        let now = Time::from_secs_since_epoch(1_000_000);
+        let mut nonces = OutboundHandshakeNonces::new();
+        let peer_id = PeerId::new();
+        nonces.insert(123, peer_id, now);
+
+        // A genuine peer sends a different nonce
+        assert_eq!(nonces.take(456, now), None);
+
+        assert_eq!(nonces.take(123, now), Some(peer_id));
+        assert_eq!(nonces.take(123, now), None);
+    }
+
+    #[test]
+    fn expired_nonce_ignored() {
+        let now = Time::from_secs_since_epoch(1_000_000);
+        let mut nonces = OutboundHandshakeNonces::new();
+        let peer_id = PeerId::new();
+        nonces.insert(123, peer_id, now);
+
+        let later = now.saturating_duration_add(OUTBOUND_NONCE_TTL - Duration::from_secs(1));
+        assert_eq!(nonces.take(123, later), Some(peer_id));
+
+        nonces.insert(123, peer_id, now);
+        let expired = now.saturating_duration_add(OUTBOUND_NONCE_TTL);
+        assert_eq!(nonces.take(123, expired), None);
+    }
+}
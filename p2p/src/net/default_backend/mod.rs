@@ -15,6 +15,7 @@
 
 pub mod backend;
 mod default_networking_service;
+mod handshake_nonces;
 mod peer;
 pub mod transport;
 pub mod types;
@@ -22,7 +23,7 @@ pub mod types;
 use std::marker::PhantomData;
 
 use async_trait::async_trait;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
 use logging::log;
 use p2p_types::{services::Services, socket_address::SocketAddress};
@@ -67,6 +68,17 @@ impl<S: NetworkingService> ConnectivityHandle<S> {
             _marker: PhantomData,
         }
     }
+
+    /// Returns the number of bytes sent to and received from the peer,
+    /// or `None` if the peer is not connected.
+    pub async fn peer_traffic(
+        &self,
+        peer_id: PeerId,
+    ) -> crate::Result<Option<transport::PeerTraffic>> {
+        let (response, response_rx) = oneshot::channel();
+        self.cmd_tx.send(types::Command::GetPeerTraffic { peer_id, response })?;
+        Ok(response_rx.await?)
+    }
 }
 
 #[derive(Debug)]
@@ -15,7 +15,7 @@
 
 use std::{sync::Arc, time::Duration};
 
-use p2p_types::services::Services;
+use p2p_types::services::{Service, Services};
 use tokio::{sync::mpsc, time::timeout};
 
 use common::chain::ChainConfig;
@@ -37,7 +37,7 @@ use crate::{
 };
 
 use super::{
-    transport::BufferedTranscoder,
+    transport::{BufferedTranscoder, TrafficCounters},
     types::{CategorizedMessage, HandshakeMessage, HandshakeNonce, Message, P2pTimestamp},
 };
 
@@ -124,6 +124,11 @@ where
         }
     }
 
+    /// Returns the byte counters of the peer's socket.
+    pub fn traffic_counters(&self) -> Arc<TrafficCounters> {
+        Arc::clone(self.socket.traffic_counters())
+    }
+
     fn validate_peer_time(
         p2p_config: &P2pConfig,
         local_time: Duration,
@@ -142,6 +147,17 @@ where
         Ok(())
     }
 
+    /// The services sent to the peer in the handshake.
+    ///
+    /// The `PrunedBlocks` flag is only advertised, it's not a part of the common services.
+    fn advertised_services(p2p_config: &P2pConfig, local_services: Services) -> Services {
+        if *p2p_config.blocks_pruned {
+            local_services | [Service::PrunedBlocks].as_slice().into()
+        } else {
+            local_services
+        }
+    }
+
     async fn handshake(&mut self, local_time: P2pTimestamp) -> crate::Result<()> {
         match self.connection_info {
             ConnectionInfo::Inbound => {
@@ -167,7 +183,7 @@ where
 
                 let local_services: Services = (*self.p2p_config.node_type).into();
 
-                let common_services = local_services & remote_services;
+                let common_services = local_services.common_with(remote_services);
 
                 let common_protocol_version =
                     choose_common_protocol_version(protocol_version, self.node_protocol_version)?;
@@ -180,6 +196,7 @@ where
                         protocol_version: common_protocol_version,
                         network,
                         common_services,
+                        remote_services,
                         user_agent,
                         software_version,
                         receiver_address,
@@ -193,7 +210,7 @@ where
                         network: *self.chain_config.magic_bytes(),
                         user_agent: self.p2p_config.user_agent.clone(),
                         software_version: *self.chain_config.software_version(),
-                        services: (*self.p2p_config.node_type).into(),
+                        services: Self::advertised_services(&self.p2p_config, local_services),
                         receiver_address: self.receiver_address.clone(),
                         current_time: local_time,
                     }))
@@ -210,7 +227,7 @@ where
                     .send(Message::Handshake(HandshakeMessage::Hello {
                         protocol_version: self.node_protocol_version,
                         network: *self.chain_config.magic_bytes(),
-                        services: local_services,
+                        services: Self::advertised_services(&self.p2p_config, local_services),
                         user_agent: self.p2p_config.user_agent.clone(),
                         software_version: *self.chain_config.software_version(),
                         receiver_address: self.receiver_address.clone(),
@@ -238,7 +255,7 @@ where
                     remote_time.as_duration_since_epoch(),
                 )?;
 
-                let common_services = local_services & remote_services;
+                let common_services = local_services.common_with(remote_services);
 
                 let common_protocol_version =
                     choose_common_protocol_version(protocol_version, self.node_protocol_version)?;
@@ -248,6 +265,7 @@ where
                         protocol_version: common_protocol_version,
                         network,
                         common_services,
+                        remote_services,
                         user_agent,
                         software_version,
                         receiver_address,
@@ -441,6 +459,7 @@ mod tests {
                 protocol_version: TEST_PROTOCOL_VERSION,
                 network: *chain_config.magic_bytes(),
                 common_services: [Service::Blocks, Service::Transactions].as_slice().into(),
+                remote_services: [Service::Blocks, Service::Transactions].as_slice().into(),
                 user_agent: p2p_config.user_agent.clone(),
                 software_version: *chain_config.software_version(),
                 receiver_address: None,
@@ -521,6 +540,7 @@ mod tests {
                 protocol_version: TEST_PROTOCOL_VERSION,
                 network: *chain_config.magic_bytes(),
                 common_services: [Service::Blocks, Service::Transactions].as_slice().into(),
+                remote_services: [Service::Blocks, Service::Transactions].as_slice().into(),
                 user_agent: p2p_config.user_agent.clone(),
                 software_version: *chain_config.software_version(),
                 receiver_address: None,
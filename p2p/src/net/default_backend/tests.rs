@@ -18,6 +18,7 @@ use std::{fmt::Debug, sync::Arc, time::Duration};
 use tokio::{io::AsyncWriteExt, sync::oneshot, time::timeout};
 
 use common::time_getter::TimeGetter;
+use serialization::Encode;
 use utils::atomics::SeqCstAtomicBool;
 
 use super::transport::TransportListener;
@@ -29,7 +30,11 @@ use crate::net::default_backend::default_networking_service::get_preferred_proto
 use crate::{
     config::NodeType,
     error::DialError,
-    net::default_backend::transport::{MpscChannelTransport, TcpTransportSocket},
+    message::{PingRequest, PingResponse},
+    net::default_backend::{
+        transport::{MpscChannelTransport, TcpTransportSocket},
+        types::Message,
+    },
     protocol::{ProtocolVersion, SupportedProtocolVersion},
     testing_utils::{
         test_p2p_config, TestTransportChannel, TestTransportMaker, TestTransportNoise,
@@ -403,6 +408,82 @@ async fn self_connect_noise() {
     self_connect::<TestTransportNoise, NoiseTcpTransport>().await;
 }
 
+// Two nodes connecting to each other at the same time must not be mistaken for self-connections
+async fn connect_each_other<A, T>()
+where
+    A: TestTransportMaker<Transport = T>,
+    T: TransportSocket + Debug,
+{
+    let config = Arc::new(common::chain::config::create_mainnet());
+    let p2p_config = Arc::new(test_p2p_config());
+    let shutdown = Arc::new(SeqCstAtomicBool::new(false));
+    let time_getter = TimeGetter::default();
+
+    let (_shutdown_sender_1, shutdown_receiver) = oneshot::channel();
+    let (_subscribers_sender_1, subscribers_receiver) = mpsc::unbounded_channel();
+    let (mut conn1, _, _, _) = DefaultNetworkingService::<T>::start(
+        A::make_transport(),
+        vec![A::make_address()],
+        Arc::clone(&config),
+        Arc::clone(&p2p_config),
+        time_getter.clone(),
+        Arc::clone(&shutdown),
+        shutdown_receiver,
+        subscribers_receiver,
+    )
+    .await
+    .unwrap();
+
+    let (_shutdown_sender_2, shutdown_receiver) = oneshot::channel();
+    let (_subscribers_sender_2, subscribers_receiver) = mpsc::unbounded_channel();
+    let (mut conn2, _, _, _) = DefaultNetworkingService::<T>::start(
+        A::make_transport(),
+        vec![A::make_address()],
+        Arc::clone(&config),
+        Arc::clone(&p2p_config),
+        time_getter,
+        shutdown,
+        shutdown_receiver,
+        subscribers_receiver,
+    )
+    .await
+    .unwrap();
+
+    conn1.connect(conn2.local_addresses()[0], None).unwrap();
+    conn2.connect(conn1.local_addresses()[0], None).unwrap();
+
+    for conn in [&mut conn1, &mut conn2] {
+        let mut outbound_accepted = 0;
+        let mut inbound_accepted = 0;
+        for _ in 0..2 {
+            match conn.poll_next().await.unwrap() {
+                ConnectivityEvent::OutboundAccepted { .. } => outbound_accepted += 1,
+                ConnectivityEvent::InboundAccepted { .. } => inbound_accepted += 1,
+                event => panic!("unexpected event: {event:?}"),
+            }
+        }
+        assert_eq!((outbound_accepted, inbound_accepted), (1, 1));
+    }
+}
+
+#[tracing::instrument]
+#[tokio::test]
+async fn connect_each_other_tcp() {
+    connect_each_other::<TestTransportTcp, TcpTransportSocket>().await;
+}
+
+#[tracing::instrument]
+#[tokio::test]
+async fn connect_each_other_channels() {
+    connect_each_other::<TestTransportChannel, MpscChannelTransport>().await;
+}
+
+#[tracing::instrument]
+#[tokio::test]
+async fn connect_each_other_noise() {
+    connect_each_other::<TestTransportNoise, NoiseTcpTransport>().await;
+}
+
 async fn invalid_outbound_peer_connect<A, T>()
 where
     A: TestTransportMaker<Transport = T>,
@@ -606,3 +687,122 @@ async fn general_protocol_version_selection_channels() {
 async fn general_protocol_version_selection_noise() {
     general_protocol_version_selection::<TestTransportNoise, NoiseTcpTransport>().await;
 }
+
+async fn peer_traffic<A, T>()
+where
+    A: TestTransportMaker<Transport = T>,
+    T: TransportSocket,
+{
+    let config = Arc::new(common::chain::config::create_mainnet());
+    let p2p_config = Arc::new(test_p2p_config());
+    let shutdown = Arc::new(SeqCstAtomicBool::new(false));
+    let time_getter = TimeGetter::default();
+
+    let (_shutdown_sender, shutdown_receiver) = oneshot::channel();
+    let (_subscribers_sender, subscribers_receiver) = mpsc::unbounded_channel();
+    let (mut conn1, _, _, _) = DefaultNetworkingService::<T>::start(
+        A::make_transport(),
+        vec![A::make_address()],
+        Arc::clone(&config),
+        Arc::clone(&p2p_config),
+        time_getter.clone(),
+        Arc::clone(&shutdown),
+        shutdown_receiver,
+        subscribers_receiver,
+    )
+    .await
+    .unwrap();
+
+    let (_shutdown_sender, shutdown_receiver) = oneshot::channel();
+    let (_subscribers_sender, subscribers_receiver) = mpsc::unbounded_channel();
+    let (mut conn2, _, _, _) = DefaultNetworkingService::<T>::start(
+        A::make_transport(),
+        vec![A::make_address()],
+        config,
+        p2p_config,
+        time_getter,
+        shutdown,
+        shutdown_receiver,
+        subscribers_receiver,
+    )
+    .await
+    .unwrap();
+
+    conn1.connect(conn2.local_addresses()[0], None).unwrap();
+    let peer_id1 = match conn1.poll_next().await.unwrap() {
+        ConnectivityEvent::OutboundAccepted { peer_info, .. } => peer_info.peer_id,
+        _ => panic!("invalid event received, expected outgoing connection"),
+    };
+    let peer_id2 = match conn2.poll_next().await.unwrap() {
+        ConnectivityEvent::InboundAccepted { peer_info, .. } => peer_info.peer_id,
+        _ => panic!("invalid event received, expected incoming connection"),
+    };
+    conn1.accept(peer_id1).unwrap();
+    conn2.accept(peer_id2).unwrap();
+
+    // The handshake is already counted
+    let traffic1 = conn1.peer_traffic(peer_id1).await.unwrap().unwrap();
+    let traffic2 = conn2.peer_traffic(peer_id2).await.unwrap().unwrap();
+    assert_ne!(traffic1.bytes_sent, 0);
+    assert_ne!(traffic1.bytes_received, 0);
+    assert_ne!(traffic2.bytes_received, 0);
+
+    // Each message is prefixed with a 4-byte length header
+    let encoded_size =
+        |message: PeerManagerMessage| Message::from(message).encode().len() as u64 + 4;
+
+    let request = PeerManagerMessage::PingRequest(PingRequest { nonce: 1 });
+    let request_size = encoded_size(request.clone());
+    conn1.send_message(peer_id1, request.clone()).unwrap();
+    match conn2.poll_next().await.unwrap() {
+        ConnectivityEvent::Message { peer_id, message } => {
+            assert_eq!(peer_id, peer_id2);
+            assert_eq!(message, request);
+        }
+        _ => panic!("invalid event received, expected message"),
+    }
+
+    let response = PeerManagerMessage::PingResponse(PingResponse { nonce: 1 });
+    let response_size = encoded_size(response.clone());
+    conn2.send_message(peer_id2, response.clone()).unwrap();
+    match conn1.poll_next().await.unwrap() {
+        ConnectivityEvent::Message { peer_id, message } => {
+            assert_eq!(peer_id, peer_id1);
+            assert_eq!(message, response);
+        }
+        _ => panic!("invalid event received, expected message"),
+    }
+
+    let new_traffic1 = conn1.peer_traffic(peer_id1).await.unwrap().unwrap();
+    let new_traffic2 = conn2.peer_traffic(peer_id2).await.unwrap().unwrap();
+    assert_eq!(new_traffic1.bytes_sent - traffic1.bytes_sent, request_size);
+    assert_eq!(
+        new_traffic1.bytes_received - traffic1.bytes_received,
+        response_size
+    );
+    assert_eq!(
+        new_traffic2.bytes_received - traffic2.bytes_received,
+        request_size
+    );
+
+    // Unknown peers have no traffic
+    assert_eq!(conn1.peer_traffic(PeerId::new()).await.unwrap(), None);
+}
+
+#[tracing::instrument]
+#[tokio::test]
+async fn peer_traffic_tcp() {
+    peer_traffic::<TestTransportTcp, TcpTransportSocket>().await;
+}
+
+#[tracing::instrument]
+#[tokio::test]
+async fn peer_traffic_channels() {
+    peer_traffic::<TestTransportChannel, MpscChannelTransport>().await;
+}
+
+#[tracing::instrument]
+#[tokio::test]
+async fn peer_traffic_noise() {
+    peer_traffic::<TestTransportNoise, NoiseTcpTransport>().await;
+}
@@ -13,14 +13,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io;
+use std::{io, sync::Arc};
 
 use bytes::BytesMut;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio_util::codec::{Decoder, Encoder};
 
 use crate::{
-    net::default_backend::{transport::message_codec::MessageCodec, types::Message},
+    net::default_backend::{
+        transport::{message_codec::MessageCodec, TrafficCounters},
+        types::Message,
+    },
     Result,
 };
 
@@ -28,6 +31,7 @@ pub struct BufferedTranscoder<S> {
     stream: S,
     buffer: BytesMut,
     message_codec: MessageCodec<Message>,
+    traffic_counters: Arc<TrafficCounters>,
 }
 
 impl<S: AsyncWrite + AsyncRead + Unpin> BufferedTranscoder<S> {
@@ -37,14 +41,21 @@ impl<S: AsyncWrite + AsyncRead + Unpin> BufferedTranscoder<S> {
             stream,
             buffer: BytesMut::new(),
             message_codec,
+            traffic_counters: Arc::new(TrafficCounters::default()),
         }
     }
 
+    /// Returns the counters of the bytes written to and read from the stream.
+    pub fn traffic_counters(&self) -> &Arc<TrafficCounters> {
+        &self.traffic_counters
+    }
+
     pub async fn send(&mut self, msg: Message) -> Result<()> {
         let mut buf = BytesMut::new();
         self.message_codec.encode(msg, &mut buf)?;
         self.stream.write_all(&buf).await?;
         self.stream.flush().await?;
+        self.traffic_counters.add_sent(buf.len());
         Ok(())
     }
 
@@ -58,9 +69,11 @@ impl<S: AsyncWrite + AsyncRead + Unpin> BufferedTranscoder<S> {
         loop {
             match self.message_codec.decode(&mut self.buffer) {
                 Ok(None) => {
-                    if self.stream.read_buf(&mut self.buffer).await? == 0 {
+                    let bytes_read = self.stream.read_buf(&mut self.buffer).await?;
+                    if bytes_read == 0 {
                         return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
                     }
+                    self.traffic_counters.add_received(bytes_read);
                     continue;
                 }
                 Ok(Some(msg)) => return Ok(msg),
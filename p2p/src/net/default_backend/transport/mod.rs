@@ -16,6 +16,7 @@
 mod buffered_transcoder;
 mod impls;
 mod message_codec;
+mod traffic_counters;
 mod traits;
 
 use impls::{channel, socks5, stream_adapter, tcp};
@@ -29,6 +30,7 @@ pub use self::{
         wrapped_transport::wrapped_socket::WrappedTransportSocket,
     },
     tcp::TcpTransportSocket,
+    traffic_counters::{PeerTraffic, TrafficCounters},
     traits::{PeerStream, TransportListener, TransportSocket},
 };
 
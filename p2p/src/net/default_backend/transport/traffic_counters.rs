@@ -0,0 +1,50 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use utils::atomics::SeqCstAtomicU64;
+
+/// The number of bytes sent to and received from a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PeerTraffic {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+/// Byte counters of a single connection.
+///
+/// The counters are updated by the peer task that owns the socket and can be read concurrently
+/// by the backend.
+#[derive(Debug, Default)]
+pub struct TrafficCounters {
+    bytes_sent: SeqCstAtomicU64,
+    bytes_received: SeqCstAtomicU64,
+}
+
+impl TrafficCounters {
+    pub fn add_sent(&self, bytes: usize) {
+        self.bytes_sent.fetch_add(bytes as u64);
+    }
+
+    pub fn add_received(&self, bytes: usize) {
+        self.bytes_received.fetch_add(bytes as u64);
+    }
+
+    pub fn get(&self) -> PeerTraffic {
+        PeerTraffic {
+            bytes_sent: self.bytes_sent.load(),
+            bytes_received: self.bytes_received.load(),
+        }
+    }
+}
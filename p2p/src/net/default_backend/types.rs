@@ -21,7 +21,7 @@ use common::{
 };
 use p2p_types::socket_address::SocketAddress;
 use serialization::{Decode, Encode};
-use tokio::sync::mpsc::Sender;
+use tokio::sync::{mpsc::Sender, oneshot};
 
 use crate::{
     error::P2pError,
@@ -30,7 +30,7 @@ use crate::{
         HeaderList, HeaderListRequest, PeerManagerMessage, PingRequest, PingResponse, SyncMessage,
         TransactionResponse,
     },
-    net::types::services::Services,
+    net::{default_backend::transport::PeerTraffic, types::services::Services},
     protocol::{ProtocolVersion, SupportedProtocolVersion},
     types::{peer_address::PeerAddress, peer_id::PeerId},
 };
@@ -51,6 +51,10 @@ pub enum Command {
         peer_id: PeerId,
         message: Message,
     },
+    GetPeerTraffic {
+        peer_id: PeerId,
+        response: oneshot::Sender<Option<PeerTraffic>>,
+    },
 }
 
 /// Random nonce sent in outbound handshake.
@@ -87,6 +91,8 @@ pub enum PeerEvent {
         protocol_version: SupportedProtocolVersion,
         network: [u8; 4],
         common_services: Services,
+        /// All services advertised by the remote peer.
+        remote_services: Services,
         user_agent: UserAgent,
         software_version: SemVer,
         receiver_address: Option<PeerAddress>,
@@ -89,6 +89,9 @@ pub struct PeerInfo {
     /// All services that will be enabled for this peer if it's accepted.
     /// The Peer Manager can disconnect the peer if some required services are missing.
     pub common_services: Services,
+
+    /// All services advertised by the peer (including the ones not requested by us).
+    pub remote_services: Services,
 }
 
 impl PeerInfo {
@@ -183,6 +186,7 @@ pub enum SyncingEvent {
     Connected {
         peer_id: PeerId,
         common_services: Services,
+        remote_services: Services,
         protocol_version: SupportedProtocolVersion,
         sync_msg_rx: Receiver<SyncMessage>,
     },
@@ -83,7 +83,7 @@ const OUTBOUND_FULL_RELAY_COUNT: usize = 8;
 const OUTBOUND_BLOCK_RELAY_COUNT: usize = 3;
 
 /// Desired number of automatic outbound connections
-const OUTBOUND_FULL_AND_BLOCK_RELAY_COUNT: usize =
+pub(crate) const OUTBOUND_FULL_AND_BLOCK_RELAY_COUNT: usize =
     OUTBOUND_FULL_RELAY_COUNT + OUTBOUND_BLOCK_RELAY_COUNT;
 
 /// Lower bound for how often [`PeerManager::heartbeat()`] is called
@@ -229,6 +229,12 @@ where
         )?;
         assert!(!p2p_config.outbound_connection_timeout.is_zero());
         assert!(!p2p_config.ping_timeout.is_zero());
+        ensure!(
+            *p2p_config.max_outbound_connections <= OUTBOUND_FULL_AND_BLOCK_RELAY_COUNT,
+            P2pError::InvalidConfigurationValue(format!(
+                "max_outbound_connections can't be greater than {OUTBOUND_FULL_AND_BLOCK_RELAY_COUNT}"
+            ))
+        );
         Ok(PeerManager {
             chain_config,
             p2p_config,
@@ -1059,8 +1065,10 @@ where
         // Expired banned addresses are dropped here, keep this call!
         self.peerdb.heartbeat();
 
+        // Pending connections are counted too, so no new dials are made while the limit is reached
         let automatic_outbound = self.automatic_outbound_peers();
-        let count = OUTBOUND_FULL_AND_BLOCK_RELAY_COUNT.saturating_sub(automatic_outbound.len());
+        let count =
+            (*self.p2p_config.max_outbound_connections).saturating_sub(automatic_outbound.len());
         let new_automatic = self.peerdb.select_new_outbound_addresses(&automatic_outbound, count);
 
         let pending_outbound =
@@ -1287,6 +1295,15 @@ where
                 self.peerdb.unban(&address);
                 response.send(Ok(()));
             }
+            PeerManagerEvent::LocalPeerDiscovered(address) => {
+                // Any host on the local network can announce itself, so the addresses are
+                // filtered the same way as the addresses received from the peers.
+                if self.is_peer_address_valid(&address.as_peer_address()) {
+                    self.peerdb.peer_discovered(address);
+                } else {
+                    log::debug!("Ignoring locally discovered address {address}");
+                }
+            }
         }
     }
 
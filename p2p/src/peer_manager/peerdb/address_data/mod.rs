@@ -18,9 +18,6 @@ use std::time::Duration;
 use common::primitives::time::Time;
 use crypto::random::Rng;
 
-/// Maximum delay between reconnection attempts to reserved nodes
-const MAX_DELAY_RESERVED: Duration = Duration::from_secs(360);
-
 /// Maximum delay between reconnection attempts to previously reachable nodes
 const MAX_DELAY_REACHABLE: Duration = Duration::from_secs(3600);
 
@@ -42,6 +39,25 @@ const PURGE_REACHABLE_FAIL_COUNT: u32 =
 /// -ln(0.0000000000000035527136788) which is about 33.
 const MAX_DELAY_FACTOR: u32 = 30;
 
+/// Delays between reconnection attempts to reserved nodes
+#[derive(Copy, Clone, Debug)]
+pub struct ReservedReconnectDelays {
+    /// The delay after a disconnect or the first failed attempt
+    pub base: Duration,
+    /// The delay is doubled after every failed attempt, but never exceeds this value
+    pub max: Duration,
+}
+
+impl ReservedReconnectDelays {
+    fn delay(&self, fail_count: u32) -> Duration {
+        // base, 2 * base, 4 * base... up to max
+        std::cmp::min(
+            self.base.saturating_mul(2u32.saturating_pow(fail_count.saturating_sub(1))),
+            self.max,
+        )
+    }
+}
+
 pub enum AddressState {
     Connected {},
 
@@ -157,23 +173,29 @@ impl AddressData {
         matches!(self.state, AddressState::Unreachable { .. })
     }
 
-    fn next_connect_delay(fail_count: u32, reserved: bool) -> Duration {
-        let max_delay = if reserved {
-            MAX_DELAY_RESERVED
-        } else {
-            MAX_DELAY_REACHABLE
-        };
-
+    fn next_connect_delay(fail_count: u32) -> Duration {
         // 10, 20, 40, 80... seconds
         std::cmp::min(
             Duration::from_secs(10).saturating_mul(2u32.saturating_pow(fail_count)),
-            max_delay,
+            MAX_DELAY_REACHABLE,
         )
     }
 
-    fn next_connect_time(now: Time, fail_count: u32, reserved: bool, rng: &mut impl Rng) -> Time {
-        let factor = utils::exp_rand::exponential_rand(rng).clamp(0.0, MAX_DELAY_FACTOR as f64);
-        let offset = Self::next_connect_delay(fail_count, reserved).mul_f64(factor);
+    fn next_connect_time(
+        now: Time,
+        fail_count: u32,
+        reserved: bool,
+        reserved_delays: &ReservedReconnectDelays,
+        rng: &mut impl Rng,
+    ) -> Time {
+        let offset = if reserved {
+            // Reserved nodes are retried with a plain exponential backoff, so a node that is
+            // temporarily down is not hammered with connection attempts.
+            reserved_delays.delay(fail_count)
+        } else {
+            let factor = utils::exp_rand::exponential_rand(rng).clamp(0.0, MAX_DELAY_FACTOR as f64);
+            Self::next_connect_delay(fail_count).mul_f64(factor)
+        };
         (now + offset).expect("Unexpected time addition overflow")
     }
 
@@ -181,6 +203,7 @@ impl AddressData {
         &mut self,
         transition: AddressStateTransitionTo,
         now: Time,
+        reserved_delays: &ReservedReconnectDelays,
         rng: &mut impl Rng,
     ) {
         self.state = match transition {
@@ -200,7 +223,13 @@ impl AddressData {
             AddressStateTransitionTo::Disconnected => match self.state {
                 AddressState::Connected {} => AddressState::Disconnected {
                     fail_count: 0,
-                    next_connect_after: Self::next_connect_time(now, 0, self.reserved, rng),
+                    next_connect_after: Self::next_connect_time(
+                        now,
+                        0,
+                        self.reserved,
+                        reserved_delays,
+                        rng,
+                    ),
                     was_reachable: true,
                 },
                 AddressState::Disconnected {
@@ -225,6 +254,7 @@ impl AddressData {
                                 now,
                                 fail_count + 1,
                                 self.reserved,
+                                reserved_delays,
                                 rng,
                             ),
                             was_reachable,
@@ -243,6 +273,7 @@ impl AddressData {
                                 now,
                                 fail_count + 1,
                                 self.reserved,
+                                reserved_delays,
                                 rng,
                             ),
                             was_reachable,
@@ -272,13 +303,20 @@ impl AddressData {
                             now,
                             fail_count,
                             self.reserved,
+                            reserved_delays,
                             rng,
                         ),
                     },
                     // Reserved nodes should not be in the `Unreachable` state
                     AddressState::Unreachable { erase_after: _ } => AddressState::Disconnected {
                         fail_count: 0,
-                        next_connect_after: Self::next_connect_time(now, 0, self.reserved, rng),
+                        next_connect_after: Self::next_connect_time(
+                            now,
+                            0,
+                            self.reserved,
+                            reserved_delays,
+                            rng,
+                        ),
                         was_reachable: false,
                     },
                 }
@@ -23,6 +23,11 @@ use test_utils::random::{make_seedable_rng, Seed};
 
 use super::*;
 
+const TEST_RESERVED_DELAYS: ReservedReconnectDelays = ReservedReconnectDelays {
+    base: Duration::from_secs(10),
+    max: Duration::from_secs(360),
+};
+
 #[tracing::instrument(skip(seed))]
 #[rstest]
 #[trace]
@@ -53,7 +58,7 @@ fn randomized(#[case] seed: Seed) {
             };
 
             if is_valid_transition {
-                address_data.transition_to(transition, started_at, &mut rng);
+                address_data.transition_to(transition, started_at, &TEST_RESERVED_DELAYS, &mut rng);
             }
         }
     }
@@ -75,7 +80,12 @@ fn reachable_reconnects(#[case] seed: Seed) {
             break;
         }
         if address.connect_now(now) {
-            address.transition_to(AddressStateTransitionTo::ConnectionFailed, now, &mut rng);
+            address.transition_to(
+                AddressStateTransitionTo::ConnectionFailed,
+                now,
+                &TEST_RESERVED_DELAYS,
+                &mut rng,
+            );
             connection_attempts += 1;
         }
         now = (now + Duration::from_secs(60)).unwrap();
@@ -92,23 +102,25 @@ fn reachable_reconnects(#[case] seed: Seed) {
 }
 
 fn next_connect_time_test_impl(rng: &mut impl Rng) {
-    let limit_reserved = MAX_DELAY_RESERVED * MAX_DELAY_FACTOR;
+    let limit_reserved = TEST_RESERVED_DELAYS.max;
     let limit_reachable = MAX_DELAY_REACHABLE * MAX_DELAY_FACTOR;
 
     let start_time = Time::from_secs_since_epoch(0);
     let max_time_reserved = (start_time + limit_reserved).unwrap();
     let max_time_reachable = (start_time + limit_reachable).unwrap();
 
-    let time = AddressData::next_connect_time(start_time, 0, true, rng);
+    let time = AddressData::next_connect_time(start_time, 0, true, &TEST_RESERVED_DELAYS, rng);
     assert!(time <= max_time_reserved);
 
-    let time = AddressData::next_connect_time(start_time, 0, false, rng);
+    let time = AddressData::next_connect_time(start_time, 0, false, &TEST_RESERVED_DELAYS, rng);
     assert!(time <= max_time_reachable);
 
-    let time = AddressData::next_connect_time(start_time, u32::MAX, true, rng);
+    let time =
+        AddressData::next_connect_time(start_time, u32::MAX, true, &TEST_RESERVED_DELAYS, rng);
     assert!(time <= max_time_reserved);
 
-    let time = AddressData::next_connect_time(start_time, u32::MAX, false, rng);
+    let time =
+        AddressData::next_connect_time(start_time, u32::MAX, false, &TEST_RESERVED_DELAYS, rng);
     assert!(time <= max_time_reachable);
 }
 
@@ -120,3 +132,81 @@ fn next_connect_time() {
     let mut always_max_rng = StepRng::new(u64::MAX, 0);
     next_connect_time_test_impl(&mut always_max_rng);
 }
+
+#[tracing::instrument(skip(seed))]
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn reserved_reconnect_backoff(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let started_at = Time::from_secs_since_epoch(1600000000);
+    let mut now = started_at;
+    let mut address = AddressData::new(false, true, started_at);
+    let mut attempts = Vec::new();
+
+    while attempts.len() < 10 {
+        if address.connect_now(now) {
+            attempts.push(now);
+            address.transition_to(
+                AddressStateTransitionTo::ConnectionFailed,
+                now,
+                &TEST_RESERVED_DELAYS,
+                &mut rng,
+            );
+        }
+        now = (now + Duration::from_secs(1)).unwrap();
+    }
+
+    // Reserved nodes are never dropped, the delay is doubled after every failure up to the limit
+    assert!(!address.is_unreachable());
+    let intervals = attempts
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).unwrap().as_secs())
+        .collect::<Vec<_>>();
+    assert_eq!(intervals, [10, 20, 40, 80, 160, 320, 360, 360, 360]);
+
+    // A successful connection resets the backoff
+    address.transition_to(
+        AddressStateTransitionTo::Connected,
+        now,
+        &TEST_RESERVED_DELAYS,
+        &mut rng,
+    );
+    address.transition_to(
+        AddressStateTransitionTo::Disconnected,
+        now,
+        &TEST_RESERVED_DELAYS,
+        &mut rng,
+    );
+    assert!(!address.connect_now((now + Duration::from_secs(9)).unwrap()));
+    assert!(address.connect_now((now + Duration::from_secs(10)).unwrap()));
+    address.transition_to(
+        AddressStateTransitionTo::ConnectionFailed,
+        now,
+        &TEST_RESERVED_DELAYS,
+        &mut rng,
+    );
+    assert!(!address.connect_now((now + Duration::from_secs(9)).unwrap()));
+    assert!(address.connect_now((now + Duration::from_secs(10)).unwrap()));
+}
+
+// Failed connections to addresses that were never reachable are not retried
+#[tracing::instrument(skip(seed))]
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn unreachable_not_retried(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let now = Time::from_secs_since_epoch(1600000000);
+    let mut address = AddressData::new(false, false, now);
+
+    assert!(address.connect_now(now));
+    address.transition_to(
+        AddressStateTransitionTo::ConnectionFailed,
+        now,
+        &TEST_RESERVED_DELAYS,
+        &mut rng,
+    );
+    assert!(address.is_unreachable());
+    assert!(!address.connect_now((now + Duration::from_secs(3600 * 24)).unwrap()));
+}
@@ -43,7 +43,7 @@ use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress
 use crate::{config, error::P2pError};
 
 use self::{
-    address_data::{AddressData, AddressStateTransitionTo},
+    address_data::{AddressData, AddressStateTransitionTo, ReservedReconnectDelays},
     storage::{PeerDbStorage, PeerDbStorageWrite},
     storage_load::LoadedStorage,
 };
@@ -264,6 +264,10 @@ impl<S: PeerDbStorage> PeerDb<S> {
         transition: AddressStateTransitionTo,
     ) {
         let now = self.time_getter.get_time();
+        let reserved_delays = ReservedReconnectDelays {
+            base: *self.p2p_config.reserved_node_reconnect_base_delay,
+            max: *self.p2p_config.reserved_node_reconnect_max_delay,
+        };
 
         // Make sure the address always exists.
         // It's needed because unknown addresses may be reported after RPC connect requests.
@@ -280,7 +284,7 @@ impl<S: PeerDbStorage> PeerDb<S> {
             transition,
         );
 
-        address_data.transition_to(transition, now, &mut make_pseudo_rng());
+        address_data.transition_to(transition, now, &reserved_delays, &mut make_pseudo_rng());
 
         let is_persistent_new = address_data.is_persistent();
 
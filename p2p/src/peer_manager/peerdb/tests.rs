@@ -46,6 +46,7 @@ fn unban_peer() {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             max_inbound_connections: Default::default(),
+            max_outbound_connections: Default::default(),
             ban_threshold: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -62,6 +63,12 @@ fn unban_peer() {
             max_singular_unconnected_headers: Default::default(),
             sync_stalling_timeout: Default::default(),
             enable_block_relay_peers: Default::default(),
+            sync_maintenance_interval: Default::default(),
+            parallel_chainstate_reads: Default::default(),
+            reserved_node_reconnect_base_delay: Default::default(),
+            reserved_node_reconnect_max_delay: Default::default(),
+            mdns_config: Default::default(),
+            blocks_pruned: Default::default(),
         }),
         time_getter.get_time_getter(),
         db_store,
@@ -187,3 +194,56 @@ fn anchor_peers() {
     .unwrap();
     assert_eq!(*peerdb.anchors(), anchors);
 }
+
+// Failed connections to a reserved node are retried with the configured exponential backoff
+#[tracing::instrument]
+#[test]
+fn reserved_node_reconnect_backoff() {
+    let db_store = peerdb_inmemory_store();
+    let time_getter = P2pBasicTestTimeGetter::new();
+    let chain_config = create_unit_test_config();
+    let p2p_config = Arc::new(P2pConfig {
+        reserved_node_reconnect_base_delay: Duration::from_secs(5).into(),
+        reserved_node_reconnect_max_delay: Duration::from_secs(30).into(),
+        ..test_p2p_config()
+    });
+    let mut peerdb = PeerDb::new(
+        &chain_config,
+        p2p_config,
+        time_getter.get_time_getter(),
+        db_store,
+    )
+    .unwrap();
+
+    let address = TestAddressMaker::new_random_address();
+    peerdb.peer_discovered(address);
+    peerdb.add_reserved_node(address);
+
+    let mut elapsed = 0;
+    let mut attempts = Vec::new();
+    while attempts.len() < 6 {
+        if peerdb.select_reserved_outbound_addresses(&BTreeSet::new()) == [address] {
+            attempts.push(elapsed);
+            peerdb.report_outbound_failure(
+                address,
+                &P2pError::DialError(DialError::ConnectionRefusedOrTimedOut),
+            );
+        }
+        time_getter.advance_time(Duration::from_secs(1));
+        elapsed += 1;
+    }
+    let intervals = attempts.windows(2).map(|pair| pair[1] - pair[0]).collect::<Vec<_>>();
+    assert_eq!(intervals, [5, 10, 20, 30, 30]);
+
+    // The backoff is reset after a successful connection
+    peerdb.outbound_peer_connected(address);
+    assert!(peerdb.select_reserved_outbound_addresses(&BTreeSet::new()).is_empty());
+    peerdb.outbound_peer_disconnected(address);
+    time_getter.advance_time(Duration::from_secs(4));
+    assert!(peerdb.select_reserved_outbound_addresses(&BTreeSet::new()).is_empty());
+    time_getter.advance_time(Duration::from_secs(1));
+    assert_eq!(
+        peerdb.select_reserved_outbound_addresses(&BTreeSet::new()),
+        [address]
+    );
+}
@@ -73,6 +73,7 @@ where
         software_version: *config.software_version(),
         user_agent: mintlayer_core_user_agent(),
         common_services: NodeType::Full.into(),
+        remote_services: NodeType::Full.into(),
     };
     pm.accept_connection(address, Role::Inbound, peer_info, None);
     assert_eq!(pm.peers.len(), 1);
@@ -150,6 +151,7 @@ fn test_addr_list_handling_inbound() {
         software_version: *chain_config.software_version(),
         user_agent: mintlayer_core_user_agent(),
         common_services: NodeType::Full.into(),
+        remote_services: NodeType::Full.into(),
     };
     pm.accept_connection(
         TestAddressMaker::new_random_address(),
@@ -209,6 +211,12 @@ fn test_addr_list_handling_outbound() {
     let chain_config = Arc::new(config::create_mainnet());
     let p2p_config = Arc::new(P2pConfig {
         enable_block_relay_peers: false.into(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
 
         bind_addresses: Default::default(),
         socks5_proxy: Default::default(),
@@ -216,6 +224,7 @@ fn test_addr_list_handling_outbound() {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -259,6 +268,7 @@ fn test_addr_list_handling_outbound() {
         software_version: *chain_config.software_version(),
         user_agent: mintlayer_core_user_agent(),
         common_services: NodeType::Full.into(),
+        remote_services: NodeType::Full.into(),
     };
     pm.connect(peer_address, OutboundConnectType::Automatic);
 
@@ -359,6 +369,7 @@ async fn resend_own_addresses() {
             software_version: *chain_config.software_version(),
             user_agent: mintlayer_core_user_agent(),
             common_services: NodeType::Full.into(),
+            remote_services: NodeType::Full.into(),
         };
         pm.connect(peer_address, OutboundConnectType::Reserved);
 
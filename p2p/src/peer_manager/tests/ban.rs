@@ -260,6 +260,9 @@ where
                 common_services: [Service::Blocks, Service::Transactions, Service::PeerAddresses]
                     .as_slice()
                     .into(),
+                remote_services: [Service::Blocks, Service::Transactions, Service::PeerAddresses]
+                    .as_slice()
+                    .into(),
             },
             None,
         );
@@ -397,6 +400,7 @@ fn ban_and_disconnect() {
         software_version: *chain_config.software_version(),
         user_agent: mintlayer_core_user_agent(),
         common_services: NodeType::Full.into(),
+        remote_services: NodeType::Full.into(),
     };
     pm.accept_connection(address_1, Role::Inbound, peer_info, None);
     assert_eq!(pm.peers.len(), 1);
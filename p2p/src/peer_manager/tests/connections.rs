@@ -493,6 +493,7 @@ async fn inbound_connection_too_many_peers_tcp() {
                     software_version: *config.software_version(),
                     user_agent: mintlayer_core_user_agent(),
                     common_services: [Service::Blocks, Service::Transactions].as_slice().into(),
+                    remote_services: [Service::Blocks, Service::Transactions].as_slice().into(),
                 },
             )
         })
@@ -520,6 +521,7 @@ async fn inbound_connection_too_many_peers_channels() {
                     software_version: *config.software_version(),
                     user_agent: mintlayer_core_user_agent(),
                     common_services: [Service::Blocks, Service::Transactions].as_slice().into(),
+                    remote_services: [Service::Blocks, Service::Transactions].as_slice().into(),
                 },
             )
         })
@@ -547,6 +549,7 @@ async fn inbound_connection_too_many_peers_noise() {
                     software_version: *config.software_version(),
                     user_agent: mintlayer_core_user_agent(),
                     common_services: [Service::Blocks, Service::Transactions].as_slice().into(),
+                    remote_services: [Service::Blocks, Service::Transactions].as_slice().into(),
                 },
             )
         })
@@ -650,6 +653,7 @@ async fn connection_timeout_rpc_notified<T>(
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         ping_check_period: Default::default(),
@@ -666,6 +670,12 @@ async fn connection_timeout_rpc_notified<T>(
         max_singular_unconnected_headers: Default::default(),
         sync_stalling_timeout: Default::default(),
         enable_block_relay_peers: Default::default(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
     });
     let shutdown = Arc::new(SeqCstAtomicBool::new(false));
     let time_getter = TimeGetter::default();
@@ -766,6 +776,7 @@ where
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -783,6 +794,12 @@ where
         max_singular_unconnected_headers: Default::default(),
         sync_stalling_timeout: Default::default(),
         enable_block_relay_peers: Default::default(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
     });
     let (tx1, _shutdown_sender, _subscribers_sender) = run_peer_manager::<T>(
         A::make_transport(),
@@ -812,6 +829,7 @@ where
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -829,6 +847,12 @@ where
         max_singular_unconnected_headers: Default::default(),
         sync_stalling_timeout: Default::default(),
         enable_block_relay_peers: Default::default(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
     });
     let (tx1, _shutdown_sender, _subscribers_sender) = run_peer_manager::<T>(
         A::make_transport(),
@@ -895,6 +919,12 @@ where
     let p2p_config_1 = Arc::new(P2pConfig {
         allow_discover_private_ips: true.into(),
         enable_block_relay_peers: false.into(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
 
         bind_addresses: Default::default(),
         socks5_proxy: None,
@@ -902,6 +932,7 @@ where
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -943,12 +974,19 @@ where
         reserved_nodes,
         allow_discover_private_ips: true.into(),
         enable_block_relay_peers: false.into(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
 
         bind_addresses: Default::default(),
         socks5_proxy: None,
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -984,12 +1022,19 @@ where
         reserved_nodes,
         allow_discover_private_ips: true.into(),
         enable_block_relay_peers: false.into(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
 
         bind_addresses: Default::default(),
         socks5_proxy: None,
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -1063,3 +1108,111 @@ async fn discovered_node_noise() {
 async fn discovered_node_channel() {
     discovered_node::<TestTransportChannel, DefaultNetworkingService<MpscChannelTransport>>().await;
 }
+
+fn take_connect_commands(
+    cmd_rx: &mut mpsc::UnboundedReceiver<net::default_backend::types::Command>,
+) -> Vec<SocketAddress> {
+    std::iter::from_fn(|| cmd_rx.try_recv().ok())
+        .filter_map(|cmd| match cmd {
+            net::default_backend::types::Command::Connect {
+                address,
+                local_services_override: _,
+            } => Some(address),
+            _ => None,
+        })
+        .collect()
+}
+
+// Automatic outbound dials stop at the configured limit and resume once a slot is freed
+#[rstest::rstest]
+#[trace]
+#[case(1)]
+#[case(3)]
+#[case(peer_manager::OUTBOUND_FULL_AND_BLOCK_RELAY_COUNT - 1)]
+#[tracing::instrument]
+#[tokio::test]
+async fn max_outbound_connections(#[case] max_outbound_connections: usize) {
+    type TestNetworkingService = DefaultNetworkingService<TcpTransportSocket>;
+
+    let chain_config = Arc::new(config::create_mainnet());
+    let p2p_config = Arc::new(P2pConfig {
+        max_outbound_connections: max_outbound_connections.into(),
+        ..test_p2p_config()
+    });
+    let (cmd_tx, mut cmd_rx) = mpsc::unbounded_channel();
+    let (_conn_tx, conn_rx) = mpsc::unbounded_channel();
+    let (_peer_tx, peer_rx) = mpsc::unbounded_channel::<PeerManagerEvent>();
+    let time_getter = P2pBasicTestTimeGetter::new();
+    let connectivity_handle =
+        net::default_backend::ConnectivityHandle::<TestNetworkingService>::new(
+            vec![],
+            cmd_tx,
+            conn_rx,
+        );
+
+    let mut pm = peer_manager::PeerManager::<TestNetworkingService, _>::new(
+        Arc::clone(&chain_config),
+        Arc::clone(&p2p_config),
+        connectivity_handle,
+        peer_rx,
+        time_getter.get_time_getter(),
+        peerdb_inmemory_store(),
+    )
+    .unwrap();
+
+    // Addresses from different address groups, so any of them can be selected
+    for i in 0..20 {
+        pm.peerdb.peer_discovered(format!("1.{i}.0.1:3031").parse().unwrap());
+    }
+
+    pm.heartbeat();
+    let dialed = take_connect_commands(&mut cmd_rx);
+    assert_eq!(dialed.len(), max_outbound_connections);
+
+    // The limit is reached, no new dials
+    pm.heartbeat();
+    assert_eq!(take_connect_commands(&mut cmd_rx), Vec::new());
+
+    // A failed connection frees a slot
+    pm.handle_outbound_error(
+        dialed[0],
+        P2pError::DialError(DialError::ConnectionRefusedOrTimedOut),
+    );
+    pm.heartbeat();
+    let dialed_again = take_connect_commands(&mut cmd_rx);
+    assert_eq!(dialed_again.len(), 1);
+    assert!(!dialed.contains(&dialed_again[0]));
+}
+
+// A limit that can't be reached by the automatic outbound connections is rejected
+#[tracing::instrument]
+#[tokio::test]
+async fn max_outbound_connections_too_large() {
+    type TestNetworkingService = DefaultNetworkingService<TcpTransportSocket>;
+
+    let chain_config = Arc::new(config::create_mainnet());
+    let p2p_config = Arc::new(P2pConfig {
+        max_outbound_connections: (peer_manager::OUTBOUND_FULL_AND_BLOCK_RELAY_COUNT + 1).into(),
+        ..test_p2p_config()
+    });
+    let (cmd_tx, _cmd_rx) = mpsc::unbounded_channel();
+    let (_conn_tx, conn_rx) = mpsc::unbounded_channel();
+    let (_peer_tx, peer_rx) = mpsc::unbounded_channel::<PeerManagerEvent>();
+    let time_getter = P2pBasicTestTimeGetter::new();
+    let connectivity_handle =
+        net::default_backend::ConnectivityHandle::<TestNetworkingService>::new(
+            vec![],
+            cmd_tx,
+            conn_rx,
+        );
+
+    let res = peer_manager::PeerManager::<TestNetworkingService, _>::new(
+        chain_config,
+        p2p_config,
+        connectivity_handle,
+        peer_rx,
+        time_getter.get_time_getter(),
+        peerdb_inmemory_store(),
+    );
+    assert!(matches!(res, Err(P2pError::InvalidConfigurationValue(_))));
+}
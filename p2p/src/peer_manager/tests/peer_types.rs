@@ -41,7 +41,7 @@ use crate::{
 fn validate_services() {
     type TestNetworkingService = DefaultNetworkingService<TcpTransportSocket>;
 
-    for node_type in [NodeType::Full, NodeType::BlocksOnly] {
+    for node_type in [NodeType::Full, NodeType::BlocksOnly, NodeType::HeadersOnly] {
         let chain_config = Arc::new(config::create_mainnet());
         let p2p_config = Arc::new(P2pConfig {
             node_type: node_type.into(),
@@ -52,6 +52,7 @@ fn validate_services() {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             max_inbound_connections: Default::default(),
+            max_outbound_connections: Default::default(),
             ban_threshold: Default::default(),
             ban_duration: Default::default(),
             outbound_connection_timeout: Default::default(),
@@ -68,6 +69,12 @@ fn validate_services() {
             max_singular_unconnected_headers: Default::default(),
             sync_stalling_timeout: Default::default(),
             enable_block_relay_peers: Default::default(),
+            sync_maintenance_interval: Default::default(),
+            parallel_chainstate_reads: Default::default(),
+            reserved_node_reconnect_base_delay: Default::default(),
+            reserved_node_reconnect_max_delay: Default::default(),
+            mdns_config: Default::default(),
+            blocks_pruned: Default::default(),
         });
 
         let (cmd_tx, _cmd_rx) = tokio::sync::mpsc::unbounded_channel();
@@ -106,6 +113,7 @@ fn validate_services() {
                     software_version: *chain_config.software_version(),
                     user_agent: mintlayer_core_user_agent(),
                     common_services: services,
+                    remote_services: services,
                 };
 
                 let res = pm.validate_connection(
@@ -131,6 +139,9 @@ fn validate_services() {
                             NodeType::BlocksOnly => {
                                 Some([Service::Blocks, Service::PeerAddresses].as_slice().into())
                             }
+                            NodeType::HeadersOnly => {
+                                Some([Service::Headers, Service::PeerAddresses].as_slice().into())
+                            }
                             NodeType::DnsServer => unimplemented!(),
                             NodeType::Inactive => unimplemented!(),
                         },
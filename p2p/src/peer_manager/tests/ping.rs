@@ -54,6 +54,7 @@ async fn ping_timeout() {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -69,6 +70,12 @@ async fn ping_timeout() {
         max_singular_unconnected_headers: Default::default(),
         sync_stalling_timeout: Default::default(),
         enable_block_relay_peers: Default::default(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
     });
     let ping_check_period = *p2p_config.ping_check_period;
     let ping_timeout = *p2p_config.ping_timeout;
@@ -105,6 +112,7 @@ async fn ping_timeout() {
                 software_version: *chain_config.software_version(),
                 user_agent: p2p_config.user_agent.clone(),
                 common_services: NodeType::Full.into(),
+                remote_services: NodeType::Full.into(),
             },
             receiver_address: None,
         })
@@ -84,4 +84,7 @@ pub enum PeerManagerEvent {
     ListBanned(oneshot_nofail::Sender<Vec<BannableAddress>>),
     Ban(BannableAddress, oneshot_nofail::Sender<crate::Result<()>>),
     Unban(BannableAddress, oneshot_nofail::Sender<crate::Result<()>>),
+
+    /// A peer was discovered on the local network (mDNS).
+    LocalPeerDiscovered(SocketAddress),
 }
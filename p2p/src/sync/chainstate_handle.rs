@@ -16,7 +16,10 @@
 //! This module is responsible for both initial syncing and further blocks processing (the reaction
 //! to block announcement from peers and the announcement of blocks produced by this node).
 
-use chainstate::chainstate_interface::ChainstateInterface;
+use chainstate::{
+    chainstate_interface::ChainstateInterface, chainstate_read_interface::ChainstateReadInterface,
+    ChainstateReadHandle,
+};
 use utils::{atomics::AcqRelAtomicBool, sync::Arc};
 
 #[derive(Clone)]
@@ -25,6 +28,9 @@ pub struct ChainstateHandle {
 
     /// A cached result of the `ChainstateInterface::is_initial_block_download` call.
     is_initial_block_download: Arc<AcqRelAtomicBool>,
+
+    /// A handle for read-only queries that bypass the chainstate subsystem (if enabled).
+    reader: Option<ChainstateReadHandle>,
 }
 
 impl ChainstateHandle {
@@ -32,9 +38,16 @@ impl ChainstateHandle {
         ChainstateHandle {
             handle,
             is_initial_block_download: Arc::new(true.into()),
+            reader: None,
         }
     }
 
+    /// Makes `call_read` serve the queries concurrently with the chainstate subsystem.
+    pub async fn enable_parallel_reads(&mut self) -> crate::Result<()> {
+        self.reader = Some(self.handle.call(|cs| cs.read_handle()).await?);
+        Ok(())
+    }
+
     pub async fn call_mut<R: Send + 'static>(
         &self,
         func: impl FnOnce(&mut dyn ChainstateInterface) -> crate::Result<R> + Send + 'static,
@@ -49,6 +62,37 @@ impl ChainstateHandle {
         self.handle.call(move |cs| func(cs)).await?
     }
 
+    /// Performs a read-only query.
+    ///
+    /// All the reads made by `func` are served from a single storage snapshot, so they observe
+    /// the same state of the chainstate. If parallel reads are enabled, the query runs on
+    /// a blocking thread, so it doesn't have to wait for e.g. block processing to complete.
+    pub async fn call_read<R: Send + 'static>(
+        &self,
+        func: impl FnOnce(&dyn ChainstateReadInterface) -> crate::Result<R> + Send + 'static,
+    ) -> crate::Result<R> {
+        match &self.reader {
+            Some(reader) => {
+                let reader = Arc::clone(reader);
+                tokio::task::spawn_blocking(move || {
+                    let snapshot = reader.snapshot()?;
+                    func(&*snapshot)
+                })
+                .await
+                .expect("Chainstate read task panicked")
+            }
+            None => {
+                self.handle
+                    .call(move |cs| {
+                        let reader = cs.read_handle();
+                        let snapshot = reader.snapshot()?;
+                        func(&*snapshot)
+                    })
+                    .await?
+            }
+        }
+    }
+
     pub async fn is_initial_block_download(&self) -> crate::Result<bool> {
         // Note: is_initial_block_download can only go from true to false.
         if !self.is_initial_block_download.load() {
@@ -0,0 +1,281 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::min;
+
+use chainstate::Locator;
+use common::{
+    chain::{
+        block::{signed_block_header::SignedBlockHeader, timestamp::BlockTimestamp},
+        Block, GenBlock,
+    },
+    primitives::{Id, Idable},
+    Uint256,
+};
+use utils::sync::{Arc, Mutex, MutexGuard};
+
+/// Headers that have been received from peers during the initial block download, but whose
+/// blocks haven't been processed by the chainstate yet.
+///
+/// The headers are contiguous and the first one is connected to the chainstate. The checkpoint
+/// is shared between all peer tasks, so if a peer disconnects in the middle of the header sync,
+/// the next peer may continue from the last known header instead of re-sending all the headers
+/// starting from the current tip.
+///
+/// Since the headers are shared, only the ones that have passed the chainstate's header checks
+/// should be added. The number of stored headers is limited; the headers beyond the limit are
+/// dropped from the end, so that the remaining ones stay connected to the chainstate.
+#[derive(Clone)]
+pub struct HeaderCheckpoint {
+    max_len: usize,
+    headers: Arc<Mutex<Vec<SignedBlockHeader>>>,
+}
+
+impl HeaderCheckpoint {
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            max_len,
+            headers: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Returns the ids of the headers stored in the checkpoint.
+    pub fn block_ids(&self) -> Vec<Id<Block>> {
+        self.lock().iter().map(|header| header.block_id()).collect()
+    }
+
+    /// Returns the id of the block the first checkpoint header is connected to.
+    pub fn first_prev_block_id(&self) -> Option<Id<GenBlock>> {
+        self.lock().first().map(|header| *header.prev_block_id())
+    }
+
+    /// Replaces the stored headers.
+    pub fn set_headers(&self, mut headers: Vec<SignedBlockHeader>) {
+        headers.truncate(self.max_len);
+        *self.lock() = headers;
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Removes the leading headers that have the given ids (e.g. because their blocks are
+    /// already in the chainstate). Stops at the first header that doesn't match, in case
+    /// the checkpoint has been modified in the meantime.
+    pub fn remove_leading(&self, ids: &[Id<Block>]) {
+        let mut headers = self.lock();
+        let count = headers
+            .iter()
+            .zip(ids)
+            .take_while(|(header, id)| header.block_id() == **id)
+            .count();
+        headers.drain(..count);
+    }
+
+    /// Returns the headers from the beginning of the checkpoint up to and including
+    /// the header with the given id, or None if there is no such header.
+    pub fn headers_up_to(&self, id: &Id<GenBlock>) -> Option<Vec<SignedBlockHeader>> {
+        let headers = self.lock();
+        let pos = headers.iter().position(|header| Id::<GenBlock>::from(header.get_id()) == *id)?;
+        Some(headers[..=pos].to_vec())
+    }
+
+    /// Returns true if the checkpoint contains the header with the given id.
+    pub fn contains(&self, id: &Id<GenBlock>) -> bool {
+        self.lock().iter().any(|header| Id::<GenBlock>::from(header.get_id()) == *id)
+    }
+
+    /// Appends the headers to the checkpoint if the first one is connected to one of the
+    /// checkpoint headers. If there are headers after the connection point, they are replaced
+    /// only if the new headers have more chain trust.
+    ///
+    /// Returns false if the headers are not connected to the checkpoint or if the existing
+    /// headers are kept.
+    pub fn try_extend(&self, new_headers: &[SignedBlockHeader]) -> bool {
+        let first_prev_id = match new_headers.first() {
+            Some(header) => *header.prev_block_id(),
+            None => return true,
+        };
+
+        let mut headers = self.lock();
+        let connection_pos = headers
+            .iter()
+            .rposition(|header| Id::<GenBlock>::from(header.get_id()) == first_prev_id);
+        match connection_pos {
+            Some(pos) => {
+                let remaining_len = self.max_len.saturating_sub(pos + 1);
+                let new_headers = &new_headers[..min(remaining_len, new_headers.len())];
+
+                let prev_timestamp = headers[pos].timestamp();
+                let old_trust = chain_trust(prev_timestamp, &headers[pos + 1..]);
+                let new_trust = chain_trust(prev_timestamp, new_headers);
+                let has_more_trust = match (new_trust, old_trust) {
+                    (Some(new_trust), Some(old_trust)) => new_trust > old_trust,
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                };
+                if !has_more_trust {
+                    return false;
+                }
+
+                headers.truncate(pos + 1);
+                headers.extend(new_headers.iter().cloned());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Prepends the checkpoint headers to the given locator, so that a peer responds with
+    /// the headers that follow the last known one.
+    ///
+    /// Like the locator itself, the checkpoint headers are taken at exponentially increasing
+    /// distance from the last one.
+    pub fn extend_locator(&self, locator: Locator) -> Locator {
+        let headers = self.lock();
+        let mut entries = Vec::new();
+
+        let mut distance = 0;
+        while distance < headers.len() {
+            entries.push(headers[headers.len() - 1 - distance].block_id().into());
+            distance = if distance == 0 { 1 } else { distance * 2 };
+        }
+
+        entries.extend(locator.into_vec());
+        Locator::new(entries)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<SignedBlockHeader>> {
+        self.headers.lock().expect("Header checkpoint mutex poisoned")
+    }
+}
+
+/// The total block proof of the contiguous headers following a block with the given timestamp.
+fn chain_trust(
+    mut prev_timestamp: BlockTimestamp,
+    headers: &[SignedBlockHeader],
+) -> Option<Uint256> {
+    let mut trust = Uint256::ZERO;
+    for header in headers {
+        let block_proof =
+            header.consensus_data().get_block_proof(prev_timestamp, header.timestamp())?;
+        trust = (trust + block_proof)?;
+        prev_timestamp = header.timestamp();
+    }
+    Some(trust)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        chain::{
+            block::{timestamp::BlockTimestamp, BlockReward, ConsensusData},
+            config::create_unit_test_config,
+        },
+        primitives::H256,
+    };
+
+    use super::*;
+
+    fn make_headers(prev_block_id: Id<GenBlock>, count: usize) -> Vec<SignedBlockHeader> {
+        let mut prev_block_id = prev_block_id;
+        (0..count)
+            .map(|i| {
+                let block = Block::new(
+                    vec![],
+                    prev_block_id,
+                    BlockTimestamp::from_int_seconds(i as u64),
+                    ConsensusData::None,
+                    BlockReward::new(vec![]),
+                )
+                .unwrap();
+                prev_block_id = block.get_id().into();
+                block.header().clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bounded_length() {
+        let genesis_id = create_unit_test_config().genesis_block_id();
+        let headers = make_headers(genesis_id, 10);
+        let checkpoint = HeaderCheckpoint::new(4);
+
+        checkpoint.set_headers(headers[..3].to_vec());
+        assert!(checkpoint.try_extend(&headers[3..]));
+        // The first headers are kept, so that the checkpoint stays connected
+        assert_eq!(
+            checkpoint.block_ids(),
+            headers[..4].iter().map(|h| h.block_id()).collect::<Vec<_>>()
+        );
+
+        checkpoint.set_headers(headers.clone());
+        assert_eq!(checkpoint.block_ids().len(), 4);
+        assert_eq!(checkpoint.first_prev_block_id(), Some(genesis_id));
+
+        // Headers that aren't connected to the checkpoint are not added
+        let other_headers = make_headers(H256::zero().into(), 2);
+        assert!(!checkpoint.try_extend(&other_headers));
+        assert_eq!(checkpoint.block_ids().len(), 4);
+    }
+
+    #[test]
+    fn fork_replaces_suffix_only_with_more_trust() {
+        let genesis_id = create_unit_test_config().genesis_block_id();
+        let headers = make_headers(genesis_id, 5);
+        let ids = headers.iter().map(|h| h.block_id()).collect::<Vec<_>>();
+        let checkpoint = HeaderCheckpoint::new(10);
+        checkpoint.set_headers(headers.clone());
+
+        // Each header has the same block proof, so the trust is determined by the length
+        let fork_point = headers[1].block_id().into();
+        for len in 1..=3 {
+            let fork = make_headers(fork_point, len);
+            assert!(!checkpoint.try_extend(&fork));
+            assert_eq!(checkpoint.block_ids(), ids);
+        }
+
+        let fork = make_headers(fork_point, 4);
+        assert!(checkpoint.try_extend(&fork));
+        let expected_ids = ids[..2]
+            .iter()
+            .copied()
+            .chain(fork.iter().map(|h| h.block_id()))
+            .collect::<Vec<_>>();
+        assert_eq!(checkpoint.block_ids(), expected_ids);
+        assert!(checkpoint.contains(&fork_point));
+        assert!(!checkpoint.contains(&ids[2].into()));
+    }
+
+    #[test]
+    fn remove_leading() {
+        let genesis_id = create_unit_test_config().genesis_block_id();
+        let headers = make_headers(genesis_id, 5);
+        let ids = headers.iter().map(|h| h.block_id()).collect::<Vec<_>>();
+        let checkpoint = HeaderCheckpoint::new(10);
+        checkpoint.set_headers(headers.clone());
+
+        checkpoint.remove_leading(&ids[..2]);
+        assert_eq!(checkpoint.block_ids(), ids[2..].to_vec());
+
+        // Ids that don't match the leading headers are ignored
+        checkpoint.remove_leading(&ids[3..]);
+        assert_eq!(checkpoint.block_ids(), ids[2..].to_vec());
+
+        checkpoint.remove_leading(&ids[2..]);
+        assert_eq!(checkpoint.block_ids(), Vec::new());
+        assert_eq!(checkpoint.first_prev_block_id(), None);
+    }
+}
@@ -17,26 +17,29 @@
 //! to block announcement from peers and the announcement of blocks produced by this node).
 
 mod chainstate_handle;
+mod header_checkpoint;
 mod peer_common;
 mod peer_v1;
 mod peer_v2;
 mod types;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
-use futures::never::Never;
 use tokio::{
-    sync::mpsc::{self, Receiver, UnboundedReceiver, UnboundedSender},
+    sync::{
+        mpsc::{self, Receiver, UnboundedReceiver, UnboundedSender},
+        oneshot,
+    },
     task::JoinHandle,
 };
 
 use common::{
     chain::{config::ChainConfig, Block, Transaction},
-    primitives::Id,
+    primitives::{time::Time, Id},
     time_getter::TimeGetter,
 };
 use logging::log;
-use mempool::{event::TransactionProcessed, tx_origin::TxOrigin, MempoolHandle};
+use mempool::{event::TransactionProcessed, MempoolHandle};
 use utils::{sync::Arc, tap_error_log::LogError};
 
 use crate::{
@@ -52,17 +55,45 @@ use crate::{
     PeerManagerEvent, Result,
 };
 
-use self::chainstate_handle::ChainstateHandle;
+use self::{chainstate_handle::ChainstateHandle, header_checkpoint::HeaderCheckpoint};
+
+/// How often the event loop checks whether it's time to run the periodic maintenance.
+///
+/// The actual maintenance cadence is determined by `P2pConfig::sync_maintenance_interval` and
+/// is measured using `TimeGetter`, so intervals shorter than this value are effectively rounded up.
+const MAINTENANCE_CHECK_PERIOD: Duration = Duration::from_secs(1);
+
+/// How long the peer tasks are given to finish their current work when they are stopped,
+/// on shutdown or when the peer is disconnected, before they are aborted.
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(5);
 
 #[derive(Debug)]
 pub enum LocalEvent {
     ChainstateNewTip(Id<Block>),
     MempoolNewTx(Id<Transaction>),
+    /// The given transactions were evicted from the mempool by a replacement.
+    MempoolTxsReplaced(Vec<Id<Transaction>>),
+    /// The mempool subsystem is not reachable anymore, transaction relay must be stopped.
+    MempoolDead,
+}
+
+/// A notification about a peer being connected to or disconnected from the sync manager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncPeerEvent {
+    Connected {
+        peer_id: PeerId,
+        protocol_version: SupportedProtocolVersion,
+    },
+    Disconnected {
+        peer_id: PeerId,
+        protocol_version: SupportedProtocolVersion,
+    },
 }
 
 pub struct PeerContext {
     task: JoinHandle<()>,
     local_event_tx: UnboundedSender<LocalEvent>,
+    protocol_version: SupportedProtocolVersion,
 }
 
 /// Sync manager is responsible for syncing the local blockchain to the chain with most trust
@@ -83,10 +114,24 @@ pub struct BlockSyncManager<T: NetworkingService> {
     chainstate_handle: ChainstateHandle,
     mempool_handle: MempoolHandle,
 
+    /// Headers downloaded during the initial block download, shared between peers.
+    header_checkpoint: HeaderCheckpoint,
+
     /// The list of connected peers
     peers: HashMap<PeerId, PeerContext>,
 
+    /// Set when the mempool subsystem is not reachable anymore. Transaction relay is stopped for
+    /// good in this case, but block syncing continues.
+    mempool_dead: bool,
+
     time_getter: TimeGetter,
+
+    /// The subscribers for the peer connection events.
+    peer_event_subscribers: Vec<UnboundedSender<SyncPeerEvent>>,
+
+    /// A helper channel for unit testing (it receives the time of every maintenance call).
+    #[cfg(test)]
+    maintenance_observer: Option<UnboundedSender<Time>>,
 }
 
 /// Syncing manager
@@ -116,47 +161,121 @@ where
             peer_manager_sender,
             chainstate_handle: ChainstateHandle::new(chainstate_handle),
             mempool_handle,
+            header_checkpoint: HeaderCheckpoint::new(*p2p_config.msg_header_count_limit),
             peers: Default::default(),
+            mempool_dead: false,
             time_getter,
+            peer_event_subscribers: Vec::new(),
+            #[cfg(test)]
+            maintenance_observer: None,
         }
     }
 
+    /// Returns a receiver of the peer connect and disconnect events.
+    ///
+    /// Dropping the receiver simply cancels the subscription.
+    pub fn subscribe_peer_events(&mut self) -> UnboundedReceiver<SyncPeerEvent> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.peer_event_subscribers.push(sender);
+        receiver
+    }
+
+    fn notify_peer_event(&mut self, event: SyncPeerEvent) {
+        self.peer_event_subscribers.retain(|subscriber| subscriber.send(event).is_ok());
+    }
+
+    #[cfg(test)]
+    pub fn set_maintenance_observer(&mut self, observer: UnboundedSender<Time>) {
+        self.maintenance_observer = Some(observer);
+    }
+
     /// Runs the sync manager event loop.
-    pub async fn run(mut self) -> Result<Never> {
+    ///
+    /// The loop is stopped either on error or when the shutdown signal is received (or its sender
+    /// is dropped). In the latter case, all the peer tasks are stopped before returning.
+    pub async fn run(mut self, mut shutdown_receiver: oneshot::Receiver<()>) -> Result<()> {
         log::info!("Starting SyncManager");
 
+        if *self.p2p_config.parallel_chainstate_reads {
+            self.chainstate_handle.enable_parallel_reads().await?;
+        }
+
         let mut new_tip_receiver = subscribe_to_new_tip(&self.chainstate_handle).await?;
         let mut tx_processed_receiver = subscribe_to_tx_processed(&self.mempool_handle).await?;
 
+        let maintenance_enabled = !self.p2p_config.sync_maintenance_interval.is_zero();
+        let mut next_maintenance = self.next_maintenance_time(self.time_getter.get_time());
+        let mut maintenance_interval = tokio::time::interval(MAINTENANCE_CHECK_PERIOD);
+
         loop {
             tokio::select! {
+                _ = &mut shutdown_receiver => {
+                    log::info!("Shutting down SyncManager");
+                    self.unregister_all_peers().await;
+                    return Ok(());
+                },
+
                 block_id = new_tip_receiver.recv() => {
                     // This error can only occur when chainstate drops an events subscriber.
                     let block_id = block_id.expect("New tip sender was closed");
                     self.handle_new_tip(block_id).await?;
                 },
 
-                tx_proc = tx_processed_receiver.recv() => {
-                    let tx_proc = tx_proc.expect("Transaction processed sender closed");
-                    self.handle_transaction_processed(&tx_proc)?;
+                tx_proc = tx_processed_receiver.recv(), if !self.mempool_dead => {
+                    // The sender is only dropped together with the mempool.
+                    match tx_proc {
+                        Some(tx_proc) => self.handle_transaction_processed(&tx_proc)?,
+                        None => self.handle_mempool_dead(),
+                    }
                 },
 
                 event = self.syncing_event_receiver.poll_next() => {
                     self.handle_peer_event(event?).await;
                 },
+
+                _ = maintenance_interval.tick(), if maintenance_enabled => {
+                    let now = self.time_getter.get_time();
+                    if now >= next_maintenance {
+                        self.run_maintenance();
+                        next_maintenance = self.next_maintenance_time(now);
+                    }
+                },
             }
         }
     }
 
+    fn next_maintenance_time(&self, now: Time) -> Time {
+        (now + *self.p2p_config.sync_maintenance_interval)
+            .expect("Time derived from local clock; cannot fail")
+    }
+
+    /// Performs the periodic maintenance of the sync manager.
+    ///
+    /// Called every `P2pConfig::sync_maintenance_interval`, as measured by `TimeGetter`.
+    fn run_maintenance(&mut self) {
+        log::trace!(
+            "Running sync manager maintenance, peer count: {}",
+            self.peers.len()
+        );
+
+        #[cfg(test)]
+        if let Some(observer) = &self.maintenance_observer {
+            let _ = observer.send(self.time_getter.get_time());
+        }
+    }
+
     /// Starts a task for the new peer.
     pub fn register_peer(
         &mut self,
         peer_id: PeerId,
         common_services: Services,
+        remote_services: Services,
         protocol_version: SupportedProtocolVersion,
         sync_msg_rx: Receiver<SyncMessage>,
     ) {
-        log::debug!("Register peer {peer_id} to sync manager");
+        log::debug!(
+            "Register peer {peer_id} to sync manager, common services: {common_services:?}"
+        );
 
         let (local_event_tx, local_event_rx) = mpsc::unbounded_channel();
 
@@ -166,6 +285,7 @@ where
                     let mut peer = peer_v1::Peer::<T>::new(
                         peer_id,
                         common_services,
+                        remote_services,
                         Arc::clone(&self.chain_config),
                         Arc::clone(&self.p2p_config),
                         self.chainstate_handle.clone(),
@@ -186,6 +306,7 @@ where
                     let mut peer = peer_v2::Peer::<T>::new(
                         peer_id,
                         common_services,
+                        remote_services,
                         Arc::clone(&self.chain_config),
                         Arc::clone(&self.p2p_config),
                         self.chainstate_handle.clone(),
@@ -195,6 +316,7 @@ where
                         self.messaging_handle.clone(),
                         local_event_rx,
                         self.time_getter.clone(),
+                        self.header_checkpoint.clone(),
                     );
 
                     logging::spawn_in_current_span(async move {
@@ -204,24 +326,93 @@ where
             }
         };
 
+        if self.mempool_dead {
+            let _ = local_event_tx.send(LocalEvent::MempoolDead);
+        }
+
         let peer_context = PeerContext {
             task: peer_task,
             local_event_tx,
+            protocol_version,
         };
 
         let prev_task = self.peers.insert(peer_id, peer_context);
         assert!(prev_task.is_none(), "Registered duplicated peer: {peer_id}");
+
+        self.notify_peer_event(SyncPeerEvent::Connected {
+            peer_id,
+            protocol_version,
+        });
     }
 
     /// Stops the task of the given peer by closing the corresponding channel.
+    ///
+    /// As on shutdown, the task commits the blocks that have already been received from the peer
+    /// before stopping. It is aborted if it doesn't stop within `SHUTDOWN_GRACE_PERIOD`.
     fn unregister_peer(&mut self, peer_id: PeerId) {
         log::debug!("Unregister peer {peer_id} from sync manager");
-        let peer = self
+        let PeerContext {
+            mut task,
+            local_event_tx,
+            protocol_version,
+        } = self
             .peers
             .remove(&peer_id)
             .unwrap_or_else(|| panic!("Unregistering unknown peer: {peer_id}"));
-        // Call `abort` because the peer task may be sleeping for a long time in the `sync_clock` function
-        peer.task.abort();
+        drop(local_event_tx);
+
+        logging::spawn_in_current_span(async move {
+            if tokio::time::timeout(SHUTDOWN_GRACE_PERIOD, &mut task).await.is_err() {
+                log::warn!("Peer {peer_id} task didn't stop in time, aborting");
+                task.abort();
+            }
+        });
+
+        self.notify_peer_event(SyncPeerEvent::Disconnected {
+            peer_id,
+            protocol_version,
+        });
+    }
+
+    /// Stops the tasks of all the peers and waits for them to finish.
+    ///
+    /// Closing the local event channel makes a peer task exit after handling the messages it has
+    /// already received, so that a block being processed is not interrupted halfway. The tasks
+    /// that don't stop within `SHUTDOWN_GRACE_PERIOD` are aborted.
+    async fn unregister_all_peers(&mut self) {
+        let peers = self.peers.drain().collect::<Vec<_>>();
+
+        let mut tasks = Vec::with_capacity(peers.len());
+        for (peer_id, peer) in peers {
+            log::debug!("Unregister peer {peer_id} from sync manager");
+            let PeerContext {
+                task,
+                local_event_tx,
+                protocol_version,
+            } = peer;
+            drop(local_event_tx);
+            tasks.push((peer_id, task));
+
+            self.notify_peer_event(SyncPeerEvent::Disconnected {
+                peer_id,
+                protocol_version,
+            });
+        }
+
+        let deadline = tokio::time::Instant::now() + SHUTDOWN_GRACE_PERIOD;
+        for (peer_id, mut task) in tasks {
+            match tokio::time::timeout_at(deadline, &mut task).await {
+                // The task has stopped by itself
+                Ok(_join_result) => {}
+                Err(_elapsed) => {
+                    log::warn!("Peer {peer_id} task didn't stop in time, aborting");
+                    task.abort();
+                    // The task has been aborted, so the only possible error is
+                    // `JoinError::Cancelled` (or a panic that happened before).
+                    let _ = task.await;
+                }
+            }
+        }
     }
 
     /// Announces the header of a new block to peers.
@@ -243,6 +434,16 @@ where
 
         match tx_proc_event.result() {
             Ok(()) => {
+                let replaced = tx_proc_event.replaced_txs();
+                if !replaced.is_empty() {
+                    log::debug!("Transactions {replaced:?} were replaced by {tx_id}");
+                    for peer in self.peers.values_mut() {
+                        let _ = peer
+                            .local_event_tx
+                            .send(LocalEvent::MempoolTxsReplaced(replaced.to_vec()));
+                    }
+                }
+
                 if origin.should_propagate() {
                     log::info!("Broadcasting transaction {tx_id} originating in {origin}");
                     for peer in self.peers.values_mut() {
@@ -252,10 +453,11 @@ where
                     log::trace!("Not propagating transaction {tx_id} originating in {origin}");
                 }
             }
-            Err(_) => match origin {
-                TxOrigin::Remote(remote_origin) => {
-                    // Punish the original peer for submitting an invalid transaction according
-                    // to mempool ban score.
+            Err(_) => {
+                // Punish the original peer for submitting an invalid transaction according
+                // to mempool ban score. This also applies to orphans, which are attributed
+                // to the peer they were originally received from.
+                if let Some(remote_origin) = origin.remote_origin() {
                     let ban_score = tx_proc_event.ban_score();
                     if ban_score > 0 {
                         let (sx, _rx) = crate::utils::oneshot_nofail::channel();
@@ -266,8 +468,7 @@ where
                             .map_err(|_| P2pError::ChannelClosed)?;
                     }
                 }
-                TxOrigin::Local(_) => (),
-            },
+            }
         }
         Ok(())
     }
@@ -278,23 +479,52 @@ where
             SyncingEvent::Connected {
                 peer_id,
                 common_services,
+                remote_services,
+                protocol_version,
+                sync_msg_rx,
+            } => self.register_peer(
+                peer_id,
+                common_services,
+                remote_services,
                 protocol_version,
                 sync_msg_rx,
-            } => self.register_peer(peer_id, common_services, protocol_version, sync_msg_rx),
+            ),
             SyncingEvent::Disconnected { peer_id } => {
-                Self::notify_mempool_peer_disconnected(&self.mempool_handle, peer_id).await;
+                self.notify_mempool_peer_disconnected(peer_id).await;
                 self.unregister_peer(peer_id);
             }
         }
     }
 
-    async fn notify_mempool_peer_disconnected(mempool_handle: &MempoolHandle, peer_id: PeerId) {
-        mempool_handle
+    async fn notify_mempool_peer_disconnected(&mut self, peer_id: PeerId) {
+        if self.mempool_dead {
+            return;
+        }
+
+        let result = self
+            .mempool_handle
             .call_mut(move |mempool| mempool.notify_peer_disconnected(peer_id))
-            .await
-            .unwrap_or_else(|err| {
-                log::error!("Mempool dead upon peer {peer_id} disconnect: {err}");
-            })
+            .await;
+        if let Err(err) = result {
+            log::error!("Mempool dead upon peer {peer_id} disconnect: {err}");
+            self.handle_mempool_dead();
+        }
+    }
+
+    /// Stops transaction relay after the mempool subsystem has died.
+    ///
+    /// The node keeps syncing blocks, the mempool failure is reported by the subsystem health
+    /// monitor.
+    fn handle_mempool_dead(&mut self) {
+        if self.mempool_dead {
+            return;
+        }
+
+        log::error!("Mempool subsystem is dead, stopping transaction relay");
+        self.mempool_dead = true;
+        for peer in self.peers.values_mut() {
+            let _ = peer.local_event_tx.send(LocalEvent::MempoolDead);
+        }
     }
 
     pub fn chainstate(&self) -> &ChainstateHandle {
@@ -0,0 +1,215 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::mem;
+
+use chainstate::BlockSource;
+use common::{
+    chain::{Block, GenBlock},
+    primitives::{Id, Idable},
+};
+use logging::log;
+use p2p_types::PeerId;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{sync::chainstate_handle::ChainstateHandle, PeerManagerEvent, Result};
+
+use super::choose_peers_best_block;
+
+/// Blocks received from a peer during the initial block download that haven't been processed yet.
+///
+/// They are committed to the storage in batches of `ChainstateConfig::commit_batch_size`.
+/// Outside of the initial block download, each block is processed as soon as it's received.
+#[derive(Default)]
+pub struct BlockBatch {
+    blocks: Vec<Block>,
+}
+
+impl BlockBatch {
+    /// Handle a block received from the peer, either processing it immediately or adding
+    /// it to the batch.
+    ///
+    /// The batch is processed when it's full or when `more_blocks_expected` is false.
+    /// Returns the new value of `peers_best_block_that_we_have`.
+    pub async fn add_block(
+        &mut self,
+        chainstate_handle: &ChainstateHandle,
+        peer_manager_sender: &UnboundedSender<PeerManagerEvent>,
+        peer_id: PeerId,
+        peers_best_block_that_we_have: Option<Id<GenBlock>>,
+        block: Block,
+        more_blocks_expected: bool,
+    ) -> Result<Option<Id<GenBlock>>> {
+        let commit_batch_size = chainstate_handle
+            .call(|c| {
+                Ok(if c.is_initial_block_download() {
+                    *c.get_chainstate_config().commit_batch_size
+                } else {
+                    1
+                })
+            })
+            .await?;
+
+        if commit_batch_size == 1 && self.blocks.is_empty() {
+            return process_block(
+                chainstate_handle,
+                peer_manager_sender,
+                peer_id,
+                peers_best_block_that_we_have,
+                block,
+            )
+            .await;
+        }
+
+        // Note: the block checks are performed when the batch is processed, because
+        // the parent of the block may be in the same batch.
+        self.blocks.push(block);
+        if self.blocks.len() < commit_batch_size && more_blocks_expected {
+            return Ok(peers_best_block_that_we_have);
+        }
+
+        process_block_batch(
+            chainstate_handle,
+            peer_manager_sender,
+            peer_id,
+            peers_best_block_that_we_have,
+            mem::take(&mut self.blocks),
+        )
+        .await
+    }
+
+    /// Process the blocks in the batch, if there are any, e.g. when the peer task is stopping.
+    ///
+    /// Returns the new value of `peers_best_block_that_we_have`.
+    pub async fn flush(
+        &mut self,
+        chainstate_handle: &ChainstateHandle,
+        peer_manager_sender: &UnboundedSender<PeerManagerEvent>,
+        peer_id: PeerId,
+        peers_best_block_that_we_have: Option<Id<GenBlock>>,
+    ) -> Result<Option<Id<GenBlock>>> {
+        if self.blocks.is_empty() {
+            return Ok(peers_best_block_that_we_have);
+        }
+
+        process_block_batch(
+            chainstate_handle,
+            peer_manager_sender,
+            peer_id,
+            peers_best_block_that_we_have,
+            mem::take(&mut self.blocks),
+        )
+        .await
+    }
+}
+
+/// Process a single block and determine the new value for peers_best_block_that_we_have.
+async fn process_block(
+    chainstate_handle: &ChainstateHandle,
+    peer_manager_sender: &UnboundedSender<PeerManagerEvent>,
+    peer_id: PeerId,
+    old_peers_best_block_that_we_have: Option<Id<GenBlock>>,
+    block: Block,
+) -> Result<Option<Id<GenBlock>>> {
+    let block_id = block.get_id();
+    let block = chainstate_handle.call(|c| Ok(c.preliminary_block_check(block)?)).await?;
+
+    let (best_block, new_tip_received) = chainstate_handle
+        .call_mut(move |c| {
+            // If the block already exists in the block tree, skip it.
+            let new_tip_received = if c.has_block(&block_id)? {
+                log::debug!(
+                    "[peer id = {}] The peer sent a block that already exists ({})",
+                    peer_id,
+                    block_id
+                );
+                false
+            } else {
+                let block_index = c.process_block(block, BlockSource::Peer)?;
+                block_index.is_some()
+            };
+
+            let best_block = choose_peers_best_block(
+                c,
+                old_peers_best_block_that_we_have,
+                Some(block_id.into()),
+            )?;
+
+            Ok((best_block, new_tip_received))
+        })
+        .await?;
+
+    if new_tip_received {
+        peer_manager_sender.send(PeerManagerEvent::NewTipReceived { peer_id, block_id })?;
+    }
+
+    Ok(best_block)
+}
+
+/// Process the blocks accumulated during the initial block download, committing them
+/// to the storage at once, and determine the new value for peers_best_block_that_we_have.
+async fn process_block_batch(
+    chainstate_handle: &ChainstateHandle,
+    peer_manager_sender: &UnboundedSender<PeerManagerEvent>,
+    peer_id: PeerId,
+    old_peers_best_block_that_we_have: Option<Id<GenBlock>>,
+    blocks: Vec<Block>,
+) -> Result<Option<Id<GenBlock>>> {
+    let last_block_id = blocks.last().expect("the batch can't be empty").get_id();
+    log::debug!(
+        "[peer id = {}] Processing a batch of {} blocks, last block id = {}",
+        peer_id,
+        blocks.len(),
+        last_block_id
+    );
+
+    let (best_block, new_tip_received) = chainstate_handle
+        .call_mut(move |c| {
+            // Skip the blocks that already exist in the block tree.
+            let mut new_blocks = Vec::with_capacity(blocks.len());
+            for block in blocks {
+                if c.has_block(&block.get_id())? {
+                    log::debug!(
+                        "[peer id = {}] The peer sent a block that already exists ({})",
+                        peer_id,
+                        block.get_id()
+                    );
+                } else {
+                    new_blocks.push(block);
+                }
+            }
+
+            let new_tip_received = !new_blocks.is_empty()
+                && c.process_block_batch(new_blocks, BlockSource::Peer)?.is_some();
+
+            let best_block = choose_peers_best_block(
+                c,
+                old_peers_best_block_that_we_have,
+                Some(last_block_id.into()),
+            )?;
+
+            Ok((best_block, new_tip_received))
+        })
+        .await?;
+
+    if new_tip_received {
+        peer_manager_sender.send(PeerManagerEvent::NewTipReceived {
+            peer_id,
+            block_id: last_block_id,
+        })?;
+    }
+
+    Ok(best_block)
+}
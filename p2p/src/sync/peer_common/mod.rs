@@ -13,9 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod block_batch;
 mod known_transactions;
 
-use chainstate::{ban_score::BanScore, chainstate_interface::ChainstateInterface};
+use chainstate::{ban_score::BanScore, chainstate_read_interface::ChainstateReadInterface};
 use common::{chain::GenBlock, primitives::Id};
 use logging::log;
 use mempool::error::{Error as MempoolError, MempoolPolicyError};
@@ -28,6 +29,7 @@ use crate::{
     PeerManagerEvent, Result,
 };
 
+pub use block_batch::BlockBatch;
 pub use known_transactions::KnownTransactions;
 
 /// Handles a result of message processing.
@@ -115,8 +117,8 @@ pub async fn handle_message_processing_result(
 /// This function is used to update peers_best_block_that_we_have.
 /// The "better" block is the one that is on the main chain and has bigger height.
 /// In the case of a tie, new_block_id is preferred.
-pub fn choose_peers_best_block(
-    chainstate: &dyn ChainstateInterface,
+pub fn choose_peers_best_block<C: ChainstateReadInterface + ?Sized>(
+    chainstate: &C,
     old_block_id: Option<Id<GenBlock>>,
     new_block_id: Option<Id<GenBlock>>,
 ) -> Result<Option<Id<GenBlock>>> {
@@ -124,10 +126,9 @@ pub fn choose_peers_best_block(
         (None, None) => Ok(None),
         (Some(id), None) | (None, Some(id)) => Ok(Some(id)),
         (Some(old_id), Some(new_id)) => {
-            let old_height =
-                chainstate.get_block_height_in_main_chain(&old_id)?.unwrap_or(0.into());
-            let new_height =
-                chainstate.get_block_height_in_main_chain(&new_id)?.unwrap_or(0.into());
+            let heights = chainstate.get_block_heights_in_main_chain(&[old_id, new_id])?;
+            let old_height = heights[0].unwrap_or(0.into());
+            let new_height = heights[1].unwrap_or(0.into());
             if new_height >= old_height {
                 Ok(Some(new_id))
             } else {
@@ -24,13 +24,13 @@ use tokio::{
     time::MissedTickBehavior,
 };
 
-use chainstate::{chainstate_interface::ChainstateInterface, BlockIndex, BlockSource, Locator};
+use chainstate::{chainstate_interface::ChainstateInterface, Locator};
 use common::{
     chain::{
         block::{signed_block_header::SignedBlockHeader, timestamp::BlockTimestamp},
         Block, ChainConfig, GenBlock, Transaction,
     },
-    primitives::{time::Time, Id, Idable},
+    primitives::{time::Time, BlockHeight, Id, Idable},
     time_getter::TimeGetter,
 };
 use logging::log;
@@ -52,7 +52,8 @@ use crate::{
     peer_manager_event::PeerDisconnectionDbAction,
     sync::{
         peer_common::{
-            choose_peers_best_block, handle_message_processing_result, KnownTransactions,
+            choose_peers_best_block, handle_message_processing_result, BlockBatch,
+            KnownTransactions,
         },
         types::PeerActivity,
         LocalEvent,
@@ -62,7 +63,7 @@ use crate::{
     MessagingService, PeerManagerEvent, Result,
 };
 
-use super::chainstate_handle::ChainstateHandle;
+use super::{chainstate_handle::ChainstateHandle, header_checkpoint::HeaderCheckpoint};
 
 // TODO: Take into account the chain work when syncing.
 /// A peer context.
@@ -73,6 +74,8 @@ pub struct Peer<T: NetworkingService> {
     chain_config: Arc<ChainConfig>,
     p2p_config: Arc<P2pConfig>,
     common_services: Services,
+    /// Services advertised by the peer; used to detect peers that prune old blocks.
+    remote_services: Services,
     chainstate_handle: ChainstateHandle,
     mempool_handle: MempoolHandle,
     peer_manager_sender: UnboundedSender<PeerManagerEvent>,
@@ -80,6 +83,8 @@ pub struct Peer<T: NetworkingService> {
     sync_msg_rx: Receiver<SyncMessage>,
     local_event_rx: UnboundedReceiver<LocalEvent>,
     time_getter: TimeGetter,
+    /// Headers downloaded during the initial block download, shared with other peers.
+    header_checkpoint: HeaderCheckpoint,
     /// Incoming data state.
     incoming: IncomingDataState,
     /// Outgoing data state.
@@ -90,11 +95,16 @@ pub struct Peer<T: NetworkingService> {
     /// A list of transactions that have been announced by this peer. An entry is added when the
     /// identifier is announced and removed when the actual transaction or not found response is received.
     announced_transactions: BTreeSet<Id<Transaction>>,
+    /// Announced transactions that were requested from this peer, but have been replaced in the
+    /// mempool since then. The responses are still expected, but are not submitted to the mempool.
+    replaced_tx_requests: BTreeSet<Id<Transaction>>,
     /// Current activity with the peer.
     peer_activity: PeerActivity,
     /// If set, send the new tip notification when the tip moves.
     /// It's set when we know that the peer knows about all of our current mainchain headers.
     send_tip_updates: bool,
+    /// Set when the mempool subsystem is not reachable anymore, transactions are not relayed then.
+    mempool_dead: bool,
 }
 
 struct IncomingDataState {
@@ -107,13 +117,15 @@ struct IncomingDataState {
     /// This includes headers received by any means, e.g. via HeaderList messages, as part
     /// of a locator during peer's header requests, via block responses.
     peers_best_block_that_we_have: Option<Id<GenBlock>>,
+    /// Blocks received during the initial block download that haven't been processed yet.
+    blocks_to_commit: BlockBatch,
 }
 
 struct OutgoingDataState {
-    /// A queue of the blocks requested by this peer.
-    blocks_queue: VecDeque<Id<Block>>,
-    /// The index of the best block that we've sent to the peer.
-    best_sent_block: Option<BlockIndex>,
+    /// A queue of the blocks requested by this peer, together with their heights.
+    blocks_queue: VecDeque<(Id<Block>, BlockHeight)>,
+    /// The id and height of the best block that we've sent to the peer.
+    best_sent_block: Option<(Id<Block>, BlockHeight)>,
     /// The id of the best block header that we've sent to the peer.
     best_sent_block_header: Option<Id<GenBlock>>,
 }
@@ -127,6 +139,7 @@ where
     pub fn new(
         id: PeerId,
         common_services: Services,
+        remote_services: Services,
         chain_config: Arc<ChainConfig>,
         p2p_config: Arc<P2pConfig>,
         chainstate_handle: ChainstateHandle,
@@ -136,6 +149,7 @@ where
         messaging_handle: T::MessagingHandle,
         local_event_rx: UnboundedReceiver<LocalEvent>,
         time_getter: TimeGetter,
+        header_checkpoint: HeaderCheckpoint,
     ) -> Self {
         let known_transactions = KnownTransactions::new();
 
@@ -144,6 +158,7 @@ where
             chain_config,
             p2p_config,
             common_services,
+            remote_services,
             chainstate_handle,
             mempool_handle,
             peer_manager_sender,
@@ -151,10 +166,12 @@ where
             sync_msg_rx,
             local_event_rx,
             time_getter,
+            header_checkpoint,
             incoming: IncomingDataState {
                 pending_headers: Vec::new(),
                 requested_blocks: BTreeSet::new(),
                 peers_best_block_that_we_have: None,
+                blocks_to_commit: BlockBatch::default(),
             },
             outgoing: OutgoingDataState {
                 blocks_queue: VecDeque::new(),
@@ -163,8 +180,10 @@ where
             },
             known_transactions,
             announced_transactions: BTreeSet::new(),
+            replaced_tx_requests: BTreeSet::new(),
             peer_activity: PeerActivity::new(),
             send_tip_updates: false,
+            mempool_dead: false,
         }
     }
 
@@ -188,7 +207,7 @@ where
         // the first tick.
         stalling_interval.tick().await;
 
-        if self.common_services.has_service(Service::Blocks) {
+        if self.exchanges_headers() {
             log::debug!("[peer id = {}] Asking for headers initially", self.id());
             self.request_headers().await?;
         }
@@ -196,19 +215,35 @@ where
         loop {
             tokio::select! {
                 message = self.sync_msg_rx.recv() => {
-                    let message = message.ok_or(P2pError::ChannelClosed)?;
+                    let message = match message {
+                        Some(message) => message,
+                        None => {
+                            self.flush_blocks_to_commit().await?;
+                            return Err(P2pError::ChannelClosed);
+                        }
+                    };
                     self.handle_message(message).await?;
                 }
 
-                block_to_send_to_peer = async {
+                (block_id, block_height) = async {
                     self.outgoing.blocks_queue.pop_front().expect("The block queue is empty")
                 }, if !self.outgoing.blocks_queue.is_empty() => {
-                    self.send_block(block_to_send_to_peer).await?;
+                    self.send_block(block_id, block_height).await?;
                 }
 
                 event = self.local_event_rx.recv() => {
-                    let event = event.ok_or(P2pError::ChannelClosed)?;
-                    self.handle_local_event(event).await?;
+                    match event {
+                        Some(event) => self.handle_local_event(event).await?,
+                        None => {
+                            // The sync manager is shutting down. Handle the messages that have
+                            // already been received (e.g. blocks) before stopping.
+                            while let Ok(message) = self.sync_msg_rx.try_recv() {
+                                self.handle_message(message).await?;
+                            }
+                            self.flush_blocks_to_commit().await?;
+                            return Ok(());
+                        }
+                    }
                 }
 
                 _ = stalling_interval.tick(), if self.peer_activity.earliest_expected_activity_time().is_some() => {}
@@ -219,6 +254,25 @@ where
         }
     }
 
+    /// Returns true if block headers are exchanged with the peer.
+    fn exchanges_headers(&self) -> bool {
+        self.common_services.has_service(Service::Blocks)
+            || self.common_services.has_service(Service::Headers)
+    }
+
+    /// Returns true if the peer only exchanges block headers, but not full blocks.
+    fn is_headers_only(&self) -> bool {
+        !self.common_services.has_service(Service::Blocks)
+            && self.common_services.has_service(Service::Headers)
+    }
+
+    /// Returns true if the peer has pruned the bodies of old blocks.
+    ///
+    /// Blocks aren't requested from such peers, because they may be unable to serve them.
+    fn prunes_blocks(&self) -> bool {
+        self.remote_services.has_service(Service::PrunedBlocks)
+    }
+
     fn send_message(&mut self, message: SyncMessage) -> Result<()> {
         self.messaging_handle.send_message(self.id(), message)
     }
@@ -234,8 +288,7 @@ where
         // This function is not supposed to be called when in IBD.
         debug_assert!(!self.chainstate_handle.is_initial_block_download().await?);
 
-        let best_sent_block_id =
-            self.outgoing.best_sent_block.as_ref().map(|index| (*index.block_id()).into());
+        let best_sent_block_id = self.outgoing.best_sent_block.map(|(block_id, _)| block_id.into());
 
         log::debug!(
             concat!(
@@ -251,7 +304,7 @@ where
         );
 
         if self.send_tip_updates {
-            debug_assert!(self.common_services.has_service(Service::Blocks));
+            debug_assert!(self.exchanges_headers());
 
             if self.incoming.peers_best_block_that_we_have.is_some()
                 || best_sent_block_id.is_some()
@@ -347,11 +400,28 @@ where
                     Ok(())
                 }
             }
+            LocalEvent::MempoolTxsReplaced(tx_ids) => {
+                self.handle_replaced_transactions(&tx_ids);
+                Ok(())
+            }
+            LocalEvent::MempoolDead => {
+                self.handle_mempool_dead();
+                Ok(())
+            }
         }
     }
 
     async fn request_headers(&mut self) -> Result<()> {
         let locator = self.chainstate_handle.call(|this| Ok(this.get_locator()?)).await?;
+        let locator = if self.chainstate_handle.is_initial_block_download().await? {
+            // Continue from the last downloaded header, so that the peer doesn't have to re-send
+            // the headers that we've already got from other peers.
+            self.prune_header_checkpoint().await?;
+            self.header_checkpoint.extend_locator(locator)
+        } else {
+            self.header_checkpoint.clear();
+            locator
+        };
         if locator.len() > *self.p2p_config.msg_max_locator_count {
             // Note: msg_max_locator_count is not supposed to be configurable outside of tests,
             // so we should never get here in production code. Moreover, currently it's not
@@ -376,6 +446,41 @@ where
         Ok(())
     }
 
+    /// Removes the headers whose blocks are already in the chainstate from the header checkpoint.
+    async fn prune_header_checkpoint(&mut self) -> Result<()> {
+        let block_ids = self.header_checkpoint.block_ids();
+        let first_prev_block_id = match self.header_checkpoint.first_prev_block_id() {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+
+        let (block_ids, is_connected) = self
+            .chainstate_handle
+            .call_read(move |c| {
+                let mut known_count = 0;
+                for block_id in &block_ids {
+                    if c.get_block_index(block_id)?.is_none() {
+                        break;
+                    }
+                    known_count += 1;
+                }
+                // If the remaining headers are no longer connected to the chainstate
+                // (which is not supposed to happen), they should be dropped.
+                let is_connected =
+                    known_count > 0 || c.get_gen_block_index(&first_prev_block_id)?.is_some();
+                Ok((block_ids[..known_count].to_vec(), is_connected))
+            })
+            .await?;
+
+        if is_connected {
+            self.header_checkpoint.remove_leading(&block_ids);
+        } else {
+            self.header_checkpoint.clear();
+        }
+
+        Ok(())
+    }
+
     async fn handle_message(&mut self, message: SyncMessage) -> Result<()> {
         log::trace!(
             "[peer id = {}] Handling message from the peer: {message:?}",
@@ -417,7 +522,7 @@ where
         let old_peers_best_block_that_we_have = self.incoming.peers_best_block_that_we_have;
         let (headers, peers_best_block_that_we_have) = self
             .chainstate_handle
-            .call(move |c| {
+            .call_read(move |c| {
                 let headers = c.get_mainchain_headers_by_locator(&locator, header_count_limit)?;
                 let peers_best_block_that_we_have = if let Some(header) = headers.first() {
                     // If headers obtained from the locator are non-empty, the parent of
@@ -450,6 +555,12 @@ where
 
     /// Processes the blocks request.
     async fn handle_block_request(&mut self, block_ids: Vec<Id<Block>>) -> Result<()> {
+        if self.is_headers_only() {
+            return Err(P2pError::ProtocolError(ProtocolError::UnexpectedMessage(
+                "block list request from a headers-only peer".to_owned(),
+            )));
+        }
+
         utils::ensure!(
             !block_ids.is_empty(),
             P2pError::ProtocolError(ProtocolError::ZeroBlocksInRequest)
@@ -489,28 +600,49 @@ where
 
         // Check that all the blocks are known and haven't been already requested.
         let ids = block_ids.clone();
-        let best_sent_block = self.outgoing.best_sent_block.clone();
-        self.chainstate_handle
-            .call(move |c| {
-                for id in ids {
+        let best_sent_block = self.outgoing.best_sent_block;
+        let heights = self
+            .chainstate_handle
+            .call_read(move |c| {
+                let gen_ids = ids.iter().map(|id| (*id).into()).collect::<Vec<Id<GenBlock>>>();
+                let mainchain_heights = c.get_block_heights_in_main_chain(&gen_ids)?;
+                let mut heights = Vec::with_capacity(ids.len());
+
+                for (id, mainchain_height) in ids.into_iter().zip(mainchain_heights) {
                     // TODO: As it is mentioned in send_block, in the future it may be possible
                     // for previously existing blocks and block indices to get removed due to
                     // invalidation. P2p will need to handle this situation correctly. See issue
                     // #1033 for more details.
-                    let index = c.get_block_index(&id)?.ok_or(P2pError::ProtocolError(
-                        ProtocolError::UnknownBlockRequested(id),
-                    ))?;
+                    // Note: the peer may request blocks that are no longer in our main chain,
+                    // e.g. after a reorg, so those have to be looked up individually.
+                    let height = match mainchain_height {
+                        Some(height) => height,
+                        None => c
+                            .get_block_index(&id)?
+                            .ok_or(P2pError::ProtocolError(
+                                ProtocolError::UnknownBlockRequested(id),
+                            ))?
+                            .block_height(),
+                    };
+
+                    // The block index is kept for the pruned blocks, but the block itself is gone.
+                    utils::ensure!(
+                        c.get_block(id)?.is_some(),
+                        P2pError::ProtocolError(ProtocolError::PrunedBlockRequested(id))
+                    );
 
-                    if let Some(ref best_sent_block) = best_sent_block {
-                        if index.block_height() <= best_sent_block.block_height() {
+                    if let Some((best_sent_block_id, best_sent_block_height)) = best_sent_block {
+                        if height <= best_sent_block_height {
                             // This can be normal in case of reorg; ensure that the mainchain block
                             // at best_sent_block's height has a different id.
                             // Note that mainchain could have become shorter due to blocks
                             // invalidation, so no block at that height may be present at all.
                             if let Some(mainchain_block_id_at_height) =
-                                c.get_block_id_from_height(&best_sent_block.block_height())?
+                                c.get_block_id_from_height(&best_sent_block_height)?
                             {
-                                if &mainchain_block_id_at_height == best_sent_block.block_id() {
+                                if mainchain_block_id_at_height
+                                    == Id::<GenBlock>::from(best_sent_block_id)
+                                {
                                     return Err(P2pError::ProtocolError(
                                         ProtocolError::DuplicatedBlockRequest(id),
                                     ));
@@ -518,13 +650,15 @@ where
                             }
                         }
                     }
+
+                    heights.push(height);
                 }
 
-                Ok(())
+                Ok(heights)
             })
             .await?;
 
-        self.outgoing.blocks_queue.extend(block_ids.into_iter());
+        self.outgoing.blocks_queue.extend(block_ids.into_iter().zip(heights));
 
         Ok(())
     }
@@ -603,11 +737,17 @@ where
             .expect("Headers shouldn't be empty")
             .prev_block_id();
 
-        let first_header_is_connected_to_chainstate = self
+        // Note: the headers' connection to the chain can only be checked sequentially, but
+        // their internal consistency can be checked for the whole list at once, so that
+        // a malformed header is caught before its block is requested.
+        let (mut headers, mut first_header_is_connected_to_chainstate) = self
             .chainstate_handle
-            .call(move |c| Ok(c.get_gen_block_index(&first_header_prev_id)?))
-            .await?
-            .is_some();
+            .call(move |c| {
+                c.preliminary_header_check_batch(&headers)?;
+                let is_connected = c.get_gen_block_index(&first_header_prev_id)?.is_some();
+                Ok((headers, is_connected))
+            })
+            .await?;
 
         let first_header_is_connected_to_pending_headers = {
             // If the peer reorged, the new header list may not start where the previous one ended.
@@ -630,11 +770,51 @@ where
             .and_then(|id| self.incoming.requested_blocks.get(&id))
             .is_some();
 
+        let mut peer_may_have_more_headers =
+            headers.len() == *self.p2p_config.msg_header_count_limit;
+
         if !(first_header_is_connected_to_chainstate
             || first_header_is_connected_to_pending_headers
             || first_header_is_connected_to_requested_blocks)
         {
-            return Err(P2pError::ProtocolError(ProtocolError::DisconnectedHeaders));
+            // The peer may be continuing from the header checkpoint, in which case the
+            // checkpoint headers become the beginning of the list.
+            match self.header_checkpoint.headers_up_to(&first_header_prev_id) {
+                Some(checkpoint_headers) => {
+                    log::debug!(
+                        "[peer id = {}] Header list is connected to the header checkpoint ({} headers)",
+                        self.id(),
+                        checkpoint_headers.len()
+                    );
+                    // The combined list must still respect the per-message limit; the rest
+                    // of the peer's headers will be requested again.
+                    let remaining_len = (*self.p2p_config.msg_header_count_limit)
+                        .saturating_sub(checkpoint_headers.len());
+                    if headers.len() > remaining_len {
+                        headers.truncate(remaining_len);
+                        peer_may_have_more_headers = true;
+                    }
+                    headers = checkpoint_headers.into_iter().chain(headers).collect();
+                    first_header_is_connected_to_chainstate = true;
+                }
+                None => {
+                    return Err(P2pError::ProtocolError(ProtocolError::DisconnectedHeaders));
+                }
+            }
+        }
+
+        if self.is_headers_only() || self.prunes_blocks() {
+            // Blocks are never downloaded from headers-only peers or the peers that prune old
+            // blocks, so only the latest headers are kept, which allows the peer's next header
+            // list to be connected to them.
+            self.incoming.pending_headers.extend(headers);
+            let excess = self
+                .incoming
+                .pending_headers
+                .len()
+                .saturating_sub(*self.p2p_config.msg_header_count_limit);
+            self.incoming.pending_headers.drain(..excess);
+            return Ok(());
         }
 
         let already_downloading_blocks = if !self.incoming.requested_blocks.is_empty() {
@@ -657,14 +837,12 @@ where
             return Ok(());
         }
 
-        let peer_may_have_more_headers = headers.len() == *self.p2p_config.msg_header_count_limit;
-
         // Filter out any existing headers from "headers" and determine the new value for
         // peers_best_block_that_we_have.
         let old_peers_best_block_that_we_have = self.incoming.peers_best_block_that_we_have;
         let (new_block_headers, peers_best_block_that_we_have) = self
             .chainstate_handle
-            .call(move |c| {
+            .call_read(move |c| {
                 let (existing_block_headers, new_block_headers) =
                     c.split_off_leading_known_headers(headers)?;
                 let peers_best_block_that_we_have = choose_peers_best_block(
@@ -702,6 +880,16 @@ where
                 .await?;
         }
 
+        // Only the headers that have been checked by the chainstate (the first one is now
+        // known to be connected to it) are shared with other peers. Headers that are connected
+        // to the checkpoint but don't extend it with more chain trust are not stored.
+        if self.chainstate_handle.is_initial_block_download().await?
+            && !self.header_checkpoint.try_extend(&new_block_headers)
+            && !self.header_checkpoint.contains(new_block_headers[0].prev_block_id())
+        {
+            self.header_checkpoint.set_headers(new_block_headers.clone());
+        }
+
         self.request_blocks(new_block_headers)
     }
 
@@ -723,44 +911,19 @@ where
             )));
         }
 
-        let block = self.chainstate_handle.call(|c| Ok(c.preliminary_block_check(block)?)).await?;
-
-        // Process the block and also determine the new value for peers_best_block_that_we_have.
-        let peer_id = self.id();
-        let old_peers_best_block_that_we_have = self.incoming.peers_best_block_that_we_have;
-        let (best_block, new_tip_received) = self
-            .chainstate_handle
-            .call_mut(move |c| {
-                // If the block already exists in the block tree, skip it.
-                let new_tip_received = if c.get_block_index(&block.get_id())?.is_some() {
-                    log::debug!(
-                        "[peer id = {}] The peer sent a block that already exists ({})",
-                        peer_id,
-                        block_id
-                    );
-                    false
-                } else {
-                    let block_index = c.process_block(block, BlockSource::Peer)?;
-                    block_index.is_some()
-                };
-
-                let best_block = choose_peers_best_block(
-                    c,
-                    old_peers_best_block_that_we_have,
-                    Some(block_id.into()),
-                )?;
-
-                Ok((best_block, new_tip_received))
-            })
+        let more_blocks_expected = !self.incoming.requested_blocks.is_empty();
+        self.incoming.peers_best_block_that_we_have = self
+            .incoming
+            .blocks_to_commit
+            .add_block(
+                &self.chainstate_handle,
+                &self.peer_manager_sender,
+                self.id(),
+                self.incoming.peers_best_block_that_we_have,
+                block,
+                more_blocks_expected,
+            )
             .await?;
-        self.incoming.peers_best_block_that_we_have = best_block;
-
-        if new_tip_received {
-            self.peer_manager_sender.send(PeerManagerEvent::NewTipReceived {
-                peer_id: self.id(),
-                block_id,
-            })?;
-        }
 
         if self.incoming.requested_blocks.is_empty() {
             let headers = mem::take(&mut self.incoming.pending_headers);
@@ -771,7 +934,7 @@ where
                 headers
             } else {
                 self.chainstate_handle
-                    .call(|c| Ok(c.split_off_leading_known_headers(headers)?))
+                    .call_read(|c| Ok(c.split_off_leading_known_headers(headers)?))
                     .await?
                     .1
             };
@@ -791,6 +954,25 @@ where
         Ok(())
     }
 
+    /// Processes the blocks that have been received from the peer, but not committed yet
+    /// because they are part of an incomplete batch.
+    async fn flush_blocks_to_commit(&mut self) -> Result<()> {
+        let res = self
+            .incoming
+            .blocks_to_commit
+            .flush(
+                &self.chainstate_handle,
+                &self.peer_manager_sender,
+                self.id(),
+                self.incoming.peers_best_block_that_we_have,
+            )
+            .await
+            .map(|peers_best_block_that_we_have| {
+                self.incoming.peers_best_block_that_we_have = peers_best_block_that_we_have;
+            });
+        handle_message_processing_result(&self.peer_manager_sender, self.id(), res).await
+    }
+
     async fn handle_transaction_request(&mut self, id: Id<Transaction>) -> Result<()> {
         if !self.common_services.has_service(Service::Transactions) {
             return Err(P2pError::ProtocolError(ProtocolError::UnexpectedMessage(
@@ -798,7 +980,17 @@ where
             )));
         }
 
-        let tx = self.mempool_handle.call(move |m| m.transaction(&id)).await?;
+        let tx = if self.mempool_dead {
+            None
+        } else {
+            match self.mempool_handle.call(move |m| m.transaction(&id)).await {
+                Ok(tx) => tx,
+                Err(_) => {
+                    self.handle_mempool_dead();
+                    None
+                }
+            }
+        };
         let res = match tx {
             Some(tx) => TransactionResponse::Found(tx),
             None => TransactionResponse::NotFound(id),
@@ -810,12 +1002,24 @@ where
     }
 
     async fn handle_transaction_response(&mut self, resp: TransactionResponse) -> Result<()> {
+        // Requests sent before the mempool died may still be answered.
+        if self.mempool_dead {
+            return Ok(());
+        }
+
         let (id, tx) = match resp {
             TransactionResponse::NotFound(id) => (id, None),
             TransactionResponse::Found(tx) => (tx.transaction().get_id(), Some(tx)),
         };
 
         if self.announced_transactions.take(&id).is_none() {
+            if self.replaced_tx_requests.remove(&id) {
+                log::debug!(
+                    "[peer id = {}] Ignoring response for replaced transaction {id}",
+                    self.id()
+                );
+                return Ok(());
+            }
             return Err(P2pError::ProtocolError(ProtocolError::UnexpectedMessage(
                 "Unexpected transaction response".to_owned(),
             )));
@@ -824,10 +1028,17 @@ where
         if let Some(transaction) = tx {
             let origin = mempool::tx_origin::RemoteTxOrigin::new(self.id());
             let txid = transaction.transaction().get_id();
-            let tx_status = self
+            let tx_status = match self
                 .mempool_handle
                 .call_mut(move |m| m.add_transaction_remote(transaction, origin))
-                .await??;
+                .await
+            {
+                Ok(tx_status) => tx_status?,
+                Err(_) => {
+                    self.handle_mempool_dead();
+                    return Ok(());
+                }
+            };
             match tx_status {
                 mempool::TxStatus::InMempool => {
                     self.peer_manager_sender.send(
@@ -848,6 +1059,32 @@ where
         self.known_transactions.insert(&txid);
     }
 
+    /// Stops waiting for the given transactions, which were replaced in the mempool, to be
+    /// submitted by this peer.
+    ///
+    /// The transactions stay in `known_transactions`: entries can't be removed from a rolling
+    /// filter, and keeping them prevents announcing the replaced versions back to the peer.
+    fn handle_replaced_transactions(&mut self, tx_ids: &[Id<Transaction>]) {
+        for tx_id in tx_ids {
+            if self.announced_transactions.remove(tx_id) {
+                self.replaced_tx_requests.insert(*tx_id);
+            }
+        }
+    }
+
+    /// Stops transaction relay with this peer after the mempool subsystem has died.
+    fn handle_mempool_dead(&mut self) {
+        if !self.mempool_dead {
+            log::warn!(
+                "[peer id = {}] Mempool is dead, stopping transaction relay",
+                self.id()
+            );
+            self.mempool_dead = true;
+            self.announced_transactions.clear();
+            self.replaced_tx_requests.clear();
+        }
+    }
+
     // TODO: This can be optimized, see https://github.com/mintlayer/mintlayer-core/issues/829
     // for details.
     async fn handle_transaction_announcement(&mut self, tx: Id<Transaction>) -> Result<()> {
@@ -871,7 +1108,17 @@ where
             )));
         }
 
-        if self.announced_transactions.len() >= *self.p2p_config.max_peer_tx_announcements {
+        if self.mempool_dead {
+            log::debug!(
+                "[peer id = {}] Ignoring transaction announcement because the mempool is dead",
+                self.id()
+            );
+            return Ok(());
+        }
+
+        if self.announced_transactions.len() + self.replaced_tx_requests.len()
+            >= *self.p2p_config.max_peer_tx_announcements
+        {
             return Err(P2pError::ProtocolError(
                 ProtocolError::TransactionAnnouncementLimitExceeded(
                     *self.p2p_config.max_peer_tx_announcements,
@@ -885,7 +1132,15 @@ where
             ));
         }
 
-        if !(self.mempool_handle.call(move |m| m.contains_transaction(&tx)).await?) {
+        let contains_transaction =
+            match self.mempool_handle.call(move |m| m.contains_transaction(&tx)).await {
+                Ok(contains_transaction) => contains_transaction,
+                Err(_) => {
+                    self.handle_mempool_dead();
+                    return Ok(());
+                }
+            };
+        if !contains_transaction {
             self.send_message(SyncMessage::TransactionRequest(tx))?;
             assert!(self.announced_transactions.insert(tx));
         }
@@ -930,23 +1185,17 @@ where
         Ok(())
     }
 
-    async fn send_block(&mut self, id: Id<Block>) -> Result<()> {
-        let (block, index) = self
-            .chainstate_handle
-            .call(move |c| {
-                let index = c.get_block_index(&id);
-                let block = c.get_block(id);
-                Ok((block, index))
-            })
-            .await?;
+    async fn send_block(&mut self, id: Id<Block>, height: BlockHeight) -> Result<()> {
+        // The height has already been obtained while processing `BlockListRequest`.
+        let block = self.chainstate_handle.call_read(move |c| Ok(c.get_block(id)?)).await?;
         // All requested blocks are already checked while processing `BlockListRequest`.
         // TODO: in the future, when block invalidation gets merged in and/if we implement
         // bad blocks purging, a block that once existed may not exist anymore.
         // Moreover, its block index may no longer exist (e.g. there was a suggestion
         // to delete block indices of missing blocks when resetting their failure flags).
         // P2p should handle such situations correctly (see issue #1033 for more details).
-        let block = block?.unwrap_or_else(|| panic!("Unknown block requested: {id}"));
-        self.outgoing.best_sent_block = index?;
+        let block = block.unwrap_or_else(|| panic!("Unknown block requested: {id}"));
+        self.outgoing.best_sent_block = Some((id, height));
 
         log::debug!(
             "[peer id = {}] Sending block with id = {} to the peer",
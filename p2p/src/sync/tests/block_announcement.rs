@@ -26,6 +26,7 @@ use common::{
     primitives::{user_agent::mintlayer_core_user_agent, Idable},
 };
 use consensus::ConsensusVerificationError;
+use crypto::random::Rng;
 use logging::log;
 use p2p_test_utils::P2pBasicTestTimeGetter;
 use test_utils::random::Seed;
@@ -72,6 +73,7 @@ async fn single_header_with_unknown_prev_block_v1(#[case] seed: Seed) {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -88,6 +90,12 @@ async fn single_header_with_unknown_prev_block_v1(#[case] seed: Seed) {
         max_peer_tx_announcements: Default::default(),
         sync_stalling_timeout: Default::default(),
         enable_block_relay_peers: Default::default(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
     });
 
     let mut node = TestNode::builder(protocol_version)
@@ -124,6 +132,63 @@ async fn single_header_with_unknown_prev_block_v1(#[case] seed: Seed) {
     node.join_subsystem_manager().await;
 }
 
+// V1: the number of singular unconnected headers that a peer may send in a row is bounded
+// by the configured max_singular_unconnected_headers; exceeding it increases the ban score.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn single_header_with_unknown_prev_block_configurable_limit_v1(#[case] seed: Seed) {
+    let protocol_version = SupportedProtocolVersion::V1.into();
+
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .build();
+    let block_1 = tf.make_block_builder().build();
+    let block_2 = tf.make_block_builder().with_parent(block_1.get_id().into()).build();
+
+    let max_singular_unconnected_headers: usize = rng.gen_range(2..=5);
+    let p2p_config = Arc::new(P2pConfig {
+        max_singular_unconnected_headers: max_singular_unconnected_headers.into(),
+        ..test_p2p_config()
+    });
+
+    let mut node = TestNode::builder(protocol_version)
+        .with_chain_config(chain_config)
+        .with_p2p_config(Arc::clone(&p2p_config))
+        .with_chainstate(tf.into_chainstate())
+        .build()
+        .await;
+
+    let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+    // Each of the allowed unconnected headers should only trigger HeaderListRequest.
+    for _ in 0..max_singular_unconnected_headers {
+        peer.send_headers(vec![block_2.header().clone()]).await;
+
+        let (sent_to, message) = node.get_sent_message().await;
+        assert_eq!(sent_to, peer.get_id());
+        assert!(matches!(message, SyncMessage::HeaderListRequest(_)));
+    }
+    node.assert_no_peer_manager_event().await;
+
+    // One more unconnected header should increase the ban score.
+    peer.send_headers(vec![block_2.header().clone()]).await;
+
+    node.assert_peer_score_adjustment(
+        peer.get_id(),
+        P2pError::ProtocolError(ProtocolError::DisconnectedHeaders).ban_score(),
+    )
+    .await;
+    node.assert_no_event().await;
+
+    node.join_subsystem_manager().await;
+}
+
 // Same as single_header_with_unknown_prev_block_v1, but here a connected header list is sent
 // in between the two attempts to send unconnected ones. This should reset the number of
 // singular unconnected headers, so no error should be generated.
@@ -160,6 +225,7 @@ async fn single_header_with_unknown_prev_block_with_intermittent_connected_heade
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -176,6 +242,12 @@ async fn single_header_with_unknown_prev_block_with_intermittent_connected_heade
         max_peer_tx_announcements: Default::default(),
         sync_stalling_timeout: Default::default(),
         enable_block_relay_peers: Default::default(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
     });
 
     let mut node = TestNode::builder(protocol_version)
@@ -15,11 +15,14 @@
 
 use std::{iter, sync::Arc};
 
-use chainstate::{ban_score::BanScore, BlockSource};
+use chainstate::{ban_score::BanScore, BlockSource, ChainstateConfig};
 use chainstate_test_framework::TestFramework;
 use common::{
-    chain::{config::create_unit_test_config, Block},
-    primitives::{Id, Idable},
+    chain::{
+        config::{create_unit_test_config, Builder, ChainType},
+        Block, Destination, NetUpgrades,
+    },
+    primitives::{BlockDistance, Id, Idable},
 };
 use crypto::random::Rng;
 use p2p_test_utils::create_n_blocks;
@@ -232,3 +235,66 @@ async fn request_same_block_twice(#[case] seed: Seed) {
     })
     .await;
 }
+
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pruned_blocks(#[case] seed: Seed) {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let chain_config = Arc::new(
+            Builder::new(ChainType::Mainnet)
+                .net_upgrades(NetUpgrades::unit_tests())
+                .genesis_unittest(Destination::AnyoneCanSpend)
+                .max_depth_for_reorg(BlockDistance::new(1))
+                .build(),
+        );
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .with_chainstate_config(ChainstateConfig::new().with_prune_depth(Some(1)))
+            .build();
+        // The bodies of all the blocks except the last two are pruned.
+        let num_blocks = rng.gen_range(3..10);
+        let blocks = create_n_blocks(&mut tf, num_blocks);
+        for block in blocks.clone() {
+            tf.process_block(block, BlockSource::Local).unwrap().unwrap();
+        }
+
+        let mut node = TestNode::builder(protocol_version)
+            .with_chain_config(chain_config)
+            .with_chainstate(tf.into_chainstate())
+            .build()
+            .await;
+
+        let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+        // A pruned block can't be served, but the peer isn't punished for asking.
+        let pruned_block_id = blocks[rng.gen_range(0..num_blocks - 2)].get_id();
+        peer.send_message(SyncMessage::BlockListRequest(BlockListRequest::new(vec![
+            pruned_block_id,
+        ])))
+        .await;
+        node.assert_no_event().await;
+        node.assert_no_peer_manager_event().await;
+
+        // The recent blocks are still available.
+        let block = blocks.last().unwrap().clone();
+        peer.send_message(SyncMessage::BlockListRequest(BlockListRequest::new(vec![
+            block.get_id(),
+        ])))
+        .await;
+        let (sent_to, message) = node.get_sent_message().await;
+        assert_eq!(peer.get_id(), sent_to);
+        assert_eq!(
+            message,
+            SyncMessage::BlockResponse(BlockResponse::new(block))
+        );
+
+        node.assert_no_error().await;
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
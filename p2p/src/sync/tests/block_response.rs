@@ -152,6 +152,7 @@ async fn disconnect(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             max_inbound_connections: Default::default(),
+            max_outbound_connections: Default::default(),
             ban_threshold: Default::default(),
             ban_duration: Default::default(),
             outbound_connection_timeout: Default::default(),
@@ -168,6 +169,12 @@ async fn disconnect(#[case] seed: Seed) {
             max_peer_tx_announcements: Default::default(),
             max_singular_unconnected_headers: Default::default(),
             enable_block_relay_peers: Default::default(),
+            sync_maintenance_interval: Default::default(),
+            parallel_chainstate_reads: Default::default(),
+            reserved_node_reconnect_base_delay: Default::default(),
+            reserved_node_reconnect_max_delay: Default::default(),
+            mdns_config: Default::default(),
+            blocks_pruned: Default::default(),
         });
         let mut node = TestNode::builder(protocol_version)
             .with_chain_config(chain_config)
@@ -224,6 +231,7 @@ async fn slow_response(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             max_inbound_connections: Default::default(),
+            max_outbound_connections: Default::default(),
             ban_threshold: Default::default(),
             ban_duration: Default::default(),
             outbound_connection_timeout: Default::default(),
@@ -240,6 +248,12 @@ async fn slow_response(#[case] seed: Seed) {
             max_peer_tx_announcements: Default::default(),
             max_singular_unconnected_headers: Default::default(),
             enable_block_relay_peers: Default::default(),
+            sync_maintenance_interval: Default::default(),
+            parallel_chainstate_reads: Default::default(),
+            reserved_node_reconnect_base_delay: Default::default(),
+            reserved_node_reconnect_max_delay: Default::default(),
+            mdns_config: Default::default(),
+            blocks_pruned: Default::default(),
         });
 
         let mut tf = TestFramework::builder(&mut rng)
@@ -156,6 +156,7 @@ async fn allow_peer_to_ignore_header_requests_when_asking_for_blocks(
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -171,6 +172,12 @@ async fn allow_peer_to_ignore_header_requests_when_asking_for_blocks(
         max_peer_tx_announcements: Default::default(),
         max_singular_unconnected_headers: Default::default(),
         enable_block_relay_peers: Default::default(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
     });
 
     let blocks = make_new_blocks(
@@ -251,6 +258,7 @@ async fn respond_with_empty_header_list_when_in_ibd(#[case] protocol_version: Pr
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -266,6 +274,12 @@ async fn respond_with_empty_header_list_when_in_ibd(#[case] protocol_version: Pr
         max_peer_tx_announcements: Default::default(),
         max_singular_unconnected_headers: Default::default(),
         enable_block_relay_peers: Default::default(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
     });
 
     let mut node = TestNode::builder(protocol_version)
@@ -15,15 +15,21 @@
 
 use std::{iter, sync::Arc, time::Duration};
 
-use chainstate::ban_score::BanScore;
+use chainstate::{ban_score::BanScore, BlockError, ChainstateError, CheckBlockError};
 use chainstate_test_framework::TestFramework;
-use common::{chain::config::create_unit_test_config, primitives::Idable};
+use common::{
+    chain::{block::timestamp::BlockTimestamp, config::create_unit_test_config, GenBlock},
+    primitives::{Id, Idable},
+};
 use p2p_test_utils::create_n_blocks;
+use p2p_types::services::{Service, Services};
 use test_utils::random::Seed;
 
 use crate::{
+    config::NodeType,
     error::ProtocolError,
     message::{BlockListRequest, HeaderList, SyncMessage},
+    protocol::SupportedProtocolVersion,
     sync::tests::helpers::TestNode,
     testing_utils::{for_each_protocol_version, test_p2p_config},
     types::peer_id::PeerId,
@@ -201,6 +207,102 @@ async fn valid_headers(#[case] seed: Seed) {
     .await;
 }
 
+// Blocks aren't requested from a peer that prunes old blocks.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn headers_from_pruned_peer(#[case] seed: Seed) {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let chain_config = Arc::new(create_unit_test_config());
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .build();
+        let blocks = create_n_blocks(&mut tf, 3);
+
+        let mut node = TestNode::builder(protocol_version)
+            .with_chain_config(chain_config)
+            .with_chainstate(tf.into_chainstate())
+            .build()
+            .await;
+
+        let remote_services =
+            Services::from(NodeType::Full) | [Service::PrunedBlocks].as_slice().into();
+        let peer =
+            node.try_connect_peer_with_services(PeerId::new(), protocol_version, remote_services);
+        let (sent_to, message) = node.get_sent_message().await;
+        assert_eq!(peer.get_id(), sent_to);
+        assert!(matches!(message, SyncMessage::HeaderListRequest(_)));
+
+        let headers = blocks.iter().map(|b| b.header().clone()).collect();
+        peer.send_message(SyncMessage::HeaderList(HeaderList::new(headers))).await;
+
+        node.assert_no_event().await;
+        node.assert_no_error().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
+// A header list is rejected if any of the headers is internally invalid, even if it's not
+// the first one.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn invalid_header_in_the_middle(#[case] seed: Seed) {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let chain_config = Arc::new(create_unit_test_config());
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .build();
+
+        let future_timestamp = BlockTimestamp::from_int_seconds(
+            tf.current_time().as_secs_since_epoch()
+                + chain_config.max_future_block_time_offset().as_secs()
+                + 1,
+        );
+        let block_1 = tf.make_block_builder().build();
+        let block_2 = tf
+            .make_block_builder()
+            .with_parent(block_1.get_id().into())
+            .with_timestamp(future_timestamp)
+            .build();
+        let block_3 = tf.make_block_builder().with_parent(block_2.get_id().into()).build();
+
+        let mut node = TestNode::builder(protocol_version)
+            .with_chain_config(chain_config)
+            .with_chainstate(tf.into_chainstate())
+            .build()
+            .await;
+
+        let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+        let headers = [block_1, block_2, block_3].iter().map(|b| b.header().clone()).collect();
+        peer.send_message(SyncMessage::HeaderList(HeaderList::new(headers))).await;
+
+        node.assert_peer_score_adjustment(
+            peer.get_id(),
+            P2pError::ChainstateError(ChainstateError::ProcessBlockError(
+                BlockError::CheckBlockFailed(CheckBlockError::BlockFromTheFuture),
+            ))
+            .ban_score(),
+        )
+        .await;
+        node.assert_no_event().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
 #[tracing::instrument]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn disconnect() {
@@ -214,6 +316,7 @@ async fn disconnect() {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             max_inbound_connections: Default::default(),
+            max_outbound_connections: Default::default(),
             ban_threshold: Default::default(),
             ban_duration: Default::default(),
             outbound_connection_timeout: Default::default(),
@@ -230,6 +333,12 @@ async fn disconnect() {
             max_peer_tx_announcements: Default::default(),
             max_singular_unconnected_headers: Default::default(),
             enable_block_relay_peers: Default::default(),
+            sync_maintenance_interval: Default::default(),
+            parallel_chainstate_reads: Default::default(),
+            reserved_node_reconnect_base_delay: Default::default(),
+            reserved_node_reconnect_max_delay: Default::default(),
+            mdns_config: Default::default(),
+            blocks_pruned: Default::default(),
         });
         let mut node = TestNode::builder(protocol_version)
             .with_p2p_config(Arc::clone(&p2p_config))
@@ -245,3 +354,77 @@ async fn disconnect() {
     })
     .await;
 }
+
+// If a peer disconnects in the middle of the initial header sync, the next peer should be asked
+// for the headers that follow the last downloaded one, and the already downloaded headers
+// should be reused.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn resume_header_sync_from_checkpoint(#[case] seed: Seed) {
+    let protocol_version = SupportedProtocolVersion::V2.into();
+
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .build();
+    let blocks = create_n_blocks(&mut tf, 5);
+    let (old_blocks, new_blocks) = blocks.split_at(3);
+
+    let mut node = TestNode::builder(protocol_version)
+        .with_chain_config(chain_config)
+        .with_chainstate(tf.into_chainstate())
+        .build()
+        .await;
+
+    let peer1 = node.connect_peer(PeerId::new(), protocol_version).await;
+    peer1
+        .send_headers(old_blocks.iter().map(|b| b.header().clone()).collect())
+        .await;
+
+    let (sent_to, message) = node.get_sent_message().await;
+    assert_eq!(peer1.get_id(), sent_to);
+    assert_eq!(
+        message,
+        SyncMessage::BlockListRequest(BlockListRequest::new(
+            old_blocks.iter().map(|b| b.get_id()).collect()
+        ))
+    );
+
+    // The peer disconnects before sending any blocks.
+    node.disconnect_peer(peer1.get_id());
+
+    // The new peer is asked for the headers after the last downloaded one.
+    let peer2 = node.try_connect_peer(PeerId::new(), protocol_version);
+    let (sent_to, message) = node.get_sent_message().await;
+    assert_eq!(peer2.get_id(), sent_to);
+    let locator = match message {
+        SyncMessage::HeaderListRequest(request) => request.into_locator(),
+        message => panic!("Unexpected message: {message:?}"),
+    };
+    let checkpoint_tip: Id<GenBlock> = old_blocks.last().unwrap().get_id().into();
+    assert_eq!(locator[0], checkpoint_tip);
+
+    // The new peer only sends the headers that follow the checkpoint, but all the blocks
+    // are requested from it.
+    peer2
+        .send_headers(new_blocks.iter().map(|b| b.header().clone()).collect())
+        .await;
+
+    let (sent_to, message) = node.get_sent_message().await;
+    assert_eq!(peer2.get_id(), sent_to);
+    assert_eq!(
+        message,
+        SyncMessage::BlockListRequest(BlockListRequest::new(
+            blocks.iter().map(|b| b.get_id()).collect()
+        ))
+    );
+
+    node.assert_no_error().await;
+
+    node.join_subsystem_manager().await;
+}
@@ -0,0 +1,172 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use chainstate::ban_score::BanScore;
+use chainstate_test_framework::TestFramework;
+use common::{chain::config::create_unit_test_config, primitives::Idable};
+use p2p_test_utils::create_n_blocks;
+use test_utils::random::Seed;
+
+use crate::{
+    config::NodeType,
+    error::ProtocolError,
+    message::{BlockListRequest, SyncMessage},
+    sync::tests::helpers::TestNode,
+    testing_utils::{for_each_protocol_version, test_p2p_config},
+    types::peer_id::PeerId,
+    P2pConfig, P2pError,
+};
+
+// A headers-only peer receives headers, including the tip updates, but never requests blocks.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn headers_only_peer_syncs_headers(#[case] seed: Seed) {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let chain_config = Arc::new(create_unit_test_config());
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .build();
+        let mut headers: Vec<_> =
+            create_n_blocks(&mut tf, 4).into_iter().map(|b| b.header().clone()).collect();
+        let tip_header = headers.pop().unwrap();
+
+        let p2p_config = Arc::new(P2pConfig {
+            node_type: NodeType::HeadersOnly.into(),
+            ..test_p2p_config()
+        });
+        let mut node = TestNode::builder(protocol_version)
+            .with_chain_config(chain_config)
+            .with_p2p_config(p2p_config)
+            .build()
+            .await;
+
+        let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+        peer.send_headers(headers).await;
+        node.assert_no_event().await;
+        node.assert_no_peer_manager_event().await;
+
+        // The tip update is connected to the previously received headers.
+        peer.send_headers(vec![tip_header]).await;
+        node.assert_no_event().await;
+        node.assert_no_peer_manager_event().await;
+        node.assert_no_error().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
+// A headers-only peer isn't allowed to request blocks.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn headers_only_peer_block_request(#[case] seed: Seed) {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let chain_config = Arc::new(create_unit_test_config());
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .build();
+        let block = create_n_blocks(&mut tf, 1).pop().unwrap();
+
+        let p2p_config = Arc::new(P2pConfig {
+            node_type: NodeType::HeadersOnly.into(),
+            ..test_p2p_config()
+        });
+        let mut node = TestNode::builder(protocol_version)
+            .with_chain_config(chain_config)
+            .with_p2p_config(p2p_config)
+            .with_chainstate(tf.into_chainstate())
+            .build()
+            .await;
+
+        let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+        peer.send_message(SyncMessage::BlockListRequest(BlockListRequest::new(vec![
+            block.get_id(),
+        ])))
+        .await;
+
+        let (adjusted_peer, score) = node.receive_adjust_peer_score_event().await;
+        assert_eq!(peer.get_id(), adjusted_peer);
+        assert_eq!(
+            score,
+            P2pError::ProtocolError(ProtocolError::UnexpectedMessage("".to_owned())).ban_score()
+        );
+        node.assert_no_event().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
+// A headers-only node exchanges headers with a full node, which doesn't advertise the `Headers`
+// service, because it's implied by `Blocks`.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn headers_only_node_full_peer(#[case] seed: Seed) {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let chain_config = Arc::new(create_unit_test_config());
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .build();
+        let headers: Vec<_> =
+            create_n_blocks(&mut tf, 2).into_iter().map(|b| b.header().clone()).collect();
+
+        let p2p_config = Arc::new(P2pConfig {
+            node_type: NodeType::HeadersOnly.into(),
+            ..test_p2p_config()
+        });
+        let mut node = TestNode::builder(protocol_version)
+            .with_chain_config(chain_config)
+            .with_p2p_config(p2p_config)
+            .build()
+            .await;
+
+        let peer = node.try_connect_peer_with_services(
+            PeerId::new(),
+            protocol_version,
+            NodeType::Full.into(),
+        );
+        let (sent_to, message) = node.get_sent_message().await;
+        assert_eq!(peer.get_id(), sent_to);
+        assert!(matches!(message, SyncMessage::HeaderListRequest(_)));
+
+        // The headers are accepted, but no blocks are requested.
+        peer.send_headers(headers).await;
+        node.assert_no_event().await;
+        node.assert_no_peer_manager_event().await;
+        node.assert_no_error().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
@@ -18,7 +18,7 @@ use std::{panic, sync::Arc};
 use async_trait::async_trait;
 use crypto::random::Rng;
 use p2p_test_utils::{expect_future_val, expect_no_recv, expect_recv, SHORT_TIMEOUT};
-use p2p_types::socket_address::SocketAddress;
+use p2p_types::{services::Services, socket_address::SocketAddress};
 use test_utils::random::Seed;
 use tokio::{
     sync::{
@@ -45,7 +45,7 @@ use common::{
         Block, ChainConfig, Destination, GenBlock, SignedTransaction, Transaction, TxInput,
         TxOutput,
     },
-    primitives::{Amount, BlockHeight, Id, Idable, H256},
+    primitives::{time::Time, Amount, BlockHeight, Id, Idable, H256},
     time_getter::TimeGetter,
 };
 use mempool::MempoolHandle;
@@ -56,7 +56,7 @@ use crate::{
     message::{HeaderList, SyncMessage},
     net::{default_backend::transport::TcpTransportSocket, types::SyncingEvent},
     protocol::{choose_common_protocol_version, ProtocolVersion},
-    sync::{subscribe_to_new_tip, BlockSyncManager},
+    sync::{subscribe_to_new_tip, BlockSyncManager, SyncPeerEvent},
     testing_utils::test_p2p_config,
     types::peer_id::PeerId,
     MessagingService, NetworkingService, P2pConfig, P2pError, P2pEventHandler, PeerManagerEvent,
@@ -76,12 +76,14 @@ pub struct TestNode {
     syncing_event_sender: UnboundedSender<SyncingEvent>,
     sync_msg_receiver: UnboundedReceiver<(PeerId, SyncMessage)>,
     error_receiver: UnboundedReceiver<P2pError>,
+    sync_manager_shutdown_sender: Option<oneshot::Sender<()>>,
     sync_manager_handle: JoinHandle<()>,
     shutdown_trigger: ShutdownTrigger,
     subsystem_manager_handle: ManagerJoinHandle,
     chainstate_handle: ChainstateHandle,
     mempool_handle: MempoolHandle,
     _new_tip_receiver: UnboundedReceiver<Id<Block>>,
+    peer_event_receiver: Option<UnboundedReceiver<SyncPeerEvent>>,
     protocol_version: ProtocolVersion,
 }
 
@@ -106,6 +108,7 @@ impl TestNode {
         subsystem_manager_handle: ManagerJoinHandle,
         time_getter: TimeGetter,
         protocol_version: ProtocolVersion,
+        maintenance_observer: Option<UnboundedSender<Time>>,
     ) -> Self {
         let (peer_manager_event_sender, peer_manager_event_receiver) = mpsc::unbounded_channel();
 
@@ -116,7 +119,7 @@ impl TestNode {
             events_receiver: syncing_event_receiver,
         };
 
-        let sync_manager = BlockSyncManager::<NetworkingServiceStub>::new(
+        let mut sync_manager = BlockSyncManager::<NetworkingServiceStub>::new(
             chain_config,
             Arc::clone(&p2p_config),
             messaging_handle,
@@ -126,13 +129,19 @@ impl TestNode {
             peer_manager_event_sender,
             time_getter,
         );
+        if let Some(observer) = maintenance_observer {
+            sync_manager.set_maintenance_observer(observer);
+        }
+        let peer_event_receiver = sync_manager.subscribe_peer_events();
 
         let sync_manager_chainstate_handle = sync_manager.chainstate().clone();
 
         let (error_sender, error_receiver) = mpsc::unbounded_channel();
+        let (sync_manager_shutdown_sender, sync_manager_shutdown_receiver) = oneshot::channel();
         let sync_manager_handle = logging::spawn_in_current_span(async move {
-            let e = sync_manager.run().await.unwrap_err();
-            let _ = error_sender.send(e);
+            if let Err(e) = sync_manager.run(sync_manager_shutdown_receiver).await {
+                let _ = error_sender.send(e);
+            }
         });
 
         let new_tip_receiver = subscribe_to_new_tip(&sync_manager_chainstate_handle).await.unwrap();
@@ -144,12 +153,14 @@ impl TestNode {
             syncing_event_sender,
             sync_msg_receiver,
             error_receiver,
+            sync_manager_shutdown_sender: Some(sync_manager_shutdown_sender),
             sync_manager_handle,
             shutdown_trigger,
             subsystem_manager_handle,
             chainstate_handle,
             mempool_handle,
             _new_tip_receiver: new_tip_receiver,
+            peer_event_receiver: Some(peer_event_receiver),
             protocol_version,
         }
     }
@@ -168,6 +179,18 @@ impl TestNode {
         &mut self,
         peer_id: PeerId,
         protocol_version: ProtocolVersion,
+    ) -> TestPeer {
+        let remote_services = (*self.p2p_config.node_type).into();
+        self.try_connect_peer_with_services(peer_id, protocol_version, remote_services)
+    }
+
+    /// Same as `try_connect_peer`, but the peer advertises the specified services.
+    #[must_use]
+    pub fn try_connect_peer_with_services(
+        &mut self,
+        peer_id: PeerId,
+        protocol_version: ProtocolVersion,
+        remote_services: Services,
     ) -> TestPeer {
         let (sync_msg_tx, sync_msg_rx) = mpsc::channel(20);
         let common_protocol_version =
@@ -175,7 +198,9 @@ impl TestNode {
         self.syncing_event_sender
             .send(SyncingEvent::Connected {
                 peer_id,
-                common_services: (*self.p2p_config.node_type).into(),
+                common_services: Services::from(*self.p2p_config.node_type)
+                    .common_with(remote_services),
+                remote_services,
                 protocol_version: common_protocol_version,
                 sync_msg_rx,
             })
@@ -217,6 +242,16 @@ impl TestNode {
         }
     }
 
+    /// Receives a peer connection event reported by the sync manager.
+    pub async fn receive_peer_event(&mut self) -> SyncPeerEvent {
+        expect_recv!(self.peer_event_receiver.as_mut().expect("Unsubscribed from peer events"))
+    }
+
+    /// Drops the receiver of the peer connection events.
+    pub fn unsubscribe_peer_events(&mut self) {
+        self.peer_event_receiver = None;
+    }
+
     /// Panics if the sync manager returns an error.
     pub async fn assert_no_error(&mut self) {
         expect_no_recv!(self.error_receiver);
@@ -275,7 +310,8 @@ impl TestNode {
                     | PeerManagerEvent::RemoveReserved(_, _)
                     | PeerManagerEvent::ListBanned(_)
                     | PeerManagerEvent::Ban(_, _)
-                    | PeerManagerEvent::Unban(_, _) => {
+                    | PeerManagerEvent::Unban(_, _)
+                    | PeerManagerEvent::LocalPeerDiscovered(_) => {
                         panic!("Unexpected peer manager event: {peer_event:?}");
                     }
                     PeerManagerEvent::NewTipReceived { .. }
@@ -312,10 +348,19 @@ impl TestNode {
         panic::resume_unwind(err.into_panic());
     }
 
+    /// Sends the shutdown signal to the sync manager and waits for it to stop.
+    pub async fn shutdown_sync_manager(&mut self) {
+        let sender = self.sync_manager_shutdown_sender.take().expect("Already shut down");
+        sender.send(()).unwrap();
+        expect_future_val!(&mut self.sync_manager_handle).unwrap();
+    }
+
     pub async fn join_subsystem_manager(self) {
-        // Shutdown sync manager first
+        // Shutdown sync manager first (unless it has already been shut down)
         drop(self.syncing_event_sender);
-        let _ = self.sync_manager_handle.await;
+        if self.sync_manager_shutdown_sender.is_some() {
+            let _ = self.sync_manager_handle.await;
+        }
 
         // Shutdown remaining subsystems
         self.shutdown_trigger.initiate();
@@ -361,6 +406,11 @@ impl TestPeer {
     pub async fn send_headers(&self, headers: Vec<SignedBlockHeader>) {
         self.send_message(SyncMessage::HeaderList(HeaderList::new(headers))).await;
     }
+
+    /// Returns true if the corresponding peer task in the sync manager has been stopped.
+    pub fn is_unregistered(&self) -> bool {
+        self.sync_msg_tx.is_closed()
+    }
 }
 
 pub struct TestNodeBuilder {
@@ -370,6 +420,7 @@ pub struct TestNodeBuilder {
     time_getter: TimeGetter,
     blocks: Vec<Block>,
     protocol_version: ProtocolVersion,
+    maintenance_observer: Option<UnboundedSender<Time>>,
 }
 
 impl TestNodeBuilder {
@@ -381,6 +432,7 @@ impl TestNodeBuilder {
             time_getter: TimeGetter::default(),
             blocks: Vec::new(),
             protocol_version,
+            maintenance_observer: None,
         }
     }
 
@@ -409,6 +461,11 @@ impl TestNodeBuilder {
         self
     }
 
+    pub fn with_maintenance_observer(mut self, observer: UnboundedSender<Time>) -> Self {
+        self.maintenance_observer = Some(observer);
+        self
+    }
+
     pub async fn build(self) -> TestNode {
         let TestNodeBuilder {
             chain_config,
@@ -417,6 +474,7 @@ impl TestNodeBuilder {
             time_getter,
             blocks,
             protocol_version,
+            maintenance_observer,
         } = self;
 
         let mut manager = subsystem::Manager::new("p2p-sync-test-manager");
@@ -456,6 +514,7 @@ impl TestNodeBuilder {
             manager_handle,
             time_getter,
             protocol_version,
+            maintenance_observer,
         )
         .await
     }
@@ -524,7 +583,16 @@ pub fn make_new_block(
     rng: &mut impl Rng,
 ) -> Block {
     let random_bytes = get_random_bytes(rng);
-    let timestamp = BlockTimestamp::from_time(time_getter.get_time());
+
+    // The timestamp must be strictly greater than the median time past of the ancestors,
+    // which is guaranteed if every block is at least a second later than its parent.
+    let prev_timestamp = prev_block.map_or(chain_config.genesis_block().timestamp(), |block| {
+        block.timestamp()
+    });
+    let timestamp = std::cmp::max(
+        BlockTimestamp::from_time(time_getter.get_time()),
+        prev_timestamp.add_int_seconds(1).expect("timestamp overflow"),
+    );
 
     let input = match prev_block {
         None => common::chain::OutPointSourceId::BlockReward(chain_config.genesis_block_id()),
@@ -329,7 +329,8 @@ impl TestNodeGroup {
                         | PeerManagerEvent::RemoveReserved(_, _)
                         | PeerManagerEvent::ListBanned(_)
                         | PeerManagerEvent::Ban(_, _)
-                        | PeerManagerEvent::Unban(_, _) => {
+                        | PeerManagerEvent::Unban(_, _)
+                        | PeerManagerEvent::LocalPeerDiscovered(_) => {
                             panic!("Unexpected peer manager event: {peer_event:?}");
                         }
                         PeerManagerEvent::NewTipReceived { .. }
@@ -0,0 +1,101 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{sync::Arc, time::Duration};
+
+use p2p_test_utils::{expect_no_recv, expect_recv, P2pBasicTestTimeGetter};
+use tokio::sync::mpsc;
+
+use crate::{
+    config::P2pConfig,
+    sync::tests::helpers::TestNode,
+    testing_utils::{for_each_protocol_version, test_p2p_config},
+    types::peer_id::PeerId,
+};
+
+// Check that the maintenance is performed with the configured interval, measured by
+// the time getter.
+#[tracing::instrument]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn maintenance_cadence() {
+    for_each_protocol_version(|protocol_version| async move {
+        let maintenance_interval = Duration::from_secs(10);
+        let p2p_config = Arc::new(P2pConfig {
+            sync_maintenance_interval: maintenance_interval.into(),
+            parallel_chainstate_reads: Default::default(),
+            ..test_p2p_config()
+        });
+        let time_getter = P2pBasicTestTimeGetter::new();
+        let (maintenance_tx, mut maintenance_rx) = mpsc::unbounded_channel();
+        let mut node = TestNode::builder(protocol_version)
+            .with_p2p_config(Arc::clone(&p2p_config))
+            .with_time_getter(time_getter.get_time_getter())
+            .with_maintenance_observer(maintenance_tx)
+            .build()
+            .await;
+
+        // Make sure the event loop is running before changing the time.
+        let _peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+        // The time hasn't changed, so there should be no maintenance.
+        expect_no_recv!(maintenance_rx);
+
+        for _ in 0..3 {
+            time_getter.advance_time(maintenance_interval / 2);
+            expect_no_recv!(maintenance_rx);
+
+            time_getter.advance_time(maintenance_interval / 2);
+            let maintenance_time = expect_recv!(maintenance_rx);
+            assert_eq!(maintenance_time, time_getter.get_time_getter().get_time());
+        }
+
+        // A big time jump results in a single maintenance call.
+        time_getter.advance_time(maintenance_interval * 5);
+        expect_recv!(maintenance_rx);
+        expect_no_recv!(maintenance_rx);
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
+// Check that the maintenance isn't performed if the interval is zero.
+#[tracing::instrument]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn maintenance_disabled() {
+    for_each_protocol_version(|protocol_version| async move {
+        let p2p_config = Arc::new(P2pConfig {
+            sync_maintenance_interval: Duration::ZERO.into(),
+            parallel_chainstate_reads: Default::default(),
+            ..test_p2p_config()
+        });
+        let time_getter = P2pBasicTestTimeGetter::new();
+        let (maintenance_tx, mut maintenance_rx) = mpsc::unbounded_channel();
+        let mut node = TestNode::builder(protocol_version)
+            .with_p2p_config(Arc::clone(&p2p_config))
+            .with_time_getter(time_getter.get_time_getter())
+            .with_maintenance_observer(maintenance_tx)
+            .build()
+            .await;
+
+        let _peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+        time_getter.advance_time(Duration::from_secs(60 * 60));
+        expect_no_recv!(maintenance_rx);
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
@@ -0,0 +1,121 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use chainstate_test_framework::TestFramework;
+use common::{
+    chain::{config::create_unit_test_config, SignedTransaction, Transaction},
+    primitives::Idable,
+    time_getter::TimeGetter,
+};
+use test_utils::random::Seed;
+
+use crate::{
+    message::{SyncMessage, TransactionResponse},
+    sync::tests::helpers::TestNode,
+    testing_utils::{for_each_protocol_version, test_p2p_config},
+    types::peer_id::PeerId,
+};
+
+// When the mempool subsystem dies, transaction relay is stopped and the sync manager keeps
+// running instead of failing later.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn mempool_dead(#[case] seed: Seed) {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let chain_config = Arc::new(create_unit_test_config());
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .build();
+        // Process a block to finish the initial block download.
+        tf.make_block_builder().build_and_process().unwrap().unwrap();
+        let time_getter = TimeGetter::default();
+
+        let mut manager = subsystem::Manager::new("p2p-sync-test-manager");
+        let shutdown_trigger = manager.make_shutdown_trigger();
+        let chainstate = manager.add_subsystem("p2p-sync-test-chainstate", tf.into_chainstate());
+
+        // The mempool runs under its own manager, so it can be killed separately.
+        let mut mempool_manager = subsystem::Manager::new("p2p-sync-test-mempool-manager");
+        let mempool_shutdown_trigger = mempool_manager.make_shutdown_trigger();
+        let mempool = mempool::make_mempool(
+            Arc::clone(&chain_config),
+            chainstate.clone(),
+            time_getter.clone(),
+        );
+        let mempool =
+            mempool_manager.add_custom_subsystem("p2p-sync-test-mempool", |h| mempool.init(h));
+        let mempool_manager_handle = mempool_manager.main_in_task();
+
+        let mut node = TestNode::start_with_params(
+            chain_config,
+            Arc::new(test_p2p_config()),
+            chainstate,
+            mempool,
+            shutdown_trigger,
+            manager.main_in_task(),
+            time_getter,
+            protocol_version,
+            None,
+        )
+        .await;
+
+        let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+        mempool_shutdown_trigger.initiate();
+        mempool_manager_handle.join().await;
+
+        let tx = Transaction::new(0x00, vec![], vec![]).unwrap();
+        let tx = SignedTransaction::new(tx, vec![]).unwrap();
+        let tx_id = tx.transaction().get_id();
+
+        // Transaction announcements are ignored.
+        peer.send_message(SyncMessage::NewTransaction(tx_id)).await;
+        node.assert_no_event().await;
+
+        // Transaction requests are answered, but nothing is found.
+        peer.send_message(SyncMessage::TransactionRequest(tx_id)).await;
+        let (sent_to, message) = node.get_sent_message().await;
+        assert_eq!(peer.get_id(), sent_to);
+        assert_eq!(
+            message,
+            SyncMessage::TransactionResponse(TransactionResponse::NotFound(tx_id))
+        );
+
+        // Late transaction responses are not a misbehavior.
+        peer.send_message(SyncMessage::TransactionResponse(
+            TransactionResponse::Found(tx),
+        ))
+        .await;
+        node.assert_no_event().await;
+
+        // New peers are served too, disconnecting doesn't touch the mempool.
+        let peer2 = node.connect_peer(PeerId::new(), protocol_version).await;
+        node.disconnect_peer(peer.get_id());
+        node.disconnect_peer(peer2.get_id());
+
+        node.assert_no_peer_manager_event().await;
+        node.assert_no_error().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
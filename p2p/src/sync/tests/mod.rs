@@ -19,7 +19,10 @@ mod block_list_request;
 mod block_response;
 mod header_list_request;
 mod header_list_response;
+mod headers_only;
 mod helpers;
+mod maintenance;
+mod mempool_failure;
 mod network_sync;
 mod peer_events;
 mod tx_announcement;
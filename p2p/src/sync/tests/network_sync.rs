@@ -53,6 +53,7 @@ async fn basic(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             max_inbound_connections: Default::default(),
+            max_outbound_connections: Default::default(),
             ban_threshold: Default::default(),
             ban_duration: Default::default(),
             outbound_connection_timeout: Default::default(),
@@ -68,6 +69,12 @@ async fn basic(#[case] seed: Seed) {
             max_singular_unconnected_headers: Default::default(),
             sync_stalling_timeout: Default::default(),
             enable_block_relay_peers: Default::default(),
+            sync_maintenance_interval: Default::default(),
+            parallel_chainstate_reads: Default::default(),
+            reserved_node_reconnect_base_delay: Default::default(),
+            reserved_node_reconnect_max_delay: Default::default(),
+            mdns_config: Default::default(),
+            blocks_pruned: Default::default(),
         });
 
         let blocks = make_new_blocks(
@@ -288,6 +295,7 @@ async fn block_announcement_disconnected_headers(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             max_inbound_connections: Default::default(),
+            max_outbound_connections: Default::default(),
             ban_threshold: Default::default(),
             ban_duration: Default::default(),
             outbound_connection_timeout: Default::default(),
@@ -303,6 +311,12 @@ async fn block_announcement_disconnected_headers(#[case] seed: Seed) {
             max_singular_unconnected_headers: Default::default(),
             sync_stalling_timeout: Default::default(),
             enable_block_relay_peers: Default::default(),
+            sync_maintenance_interval: Default::default(),
+            parallel_chainstate_reads: Default::default(),
+            reserved_node_reconnect_base_delay: Default::default(),
+            reserved_node_reconnect_max_delay: Default::default(),
+            mdns_config: Default::default(),
+            blocks_pruned: Default::default(),
         });
 
         let initial_block_count = rng.gen_range(1..=MAX_REQUEST_BLOCKS_COUNT);
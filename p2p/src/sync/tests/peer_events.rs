@@ -15,17 +15,20 @@
 
 use std::sync::Arc;
 
-use chainstate::BlockSource;
+use chainstate::{BlockSource, ChainstateConfig};
 use chainstate_test_framework::TestFramework;
-use common::chain::{block::timestamp::BlockTimestamp, config::create_unit_test_config};
-use p2p_test_utils::P2pBasicTestTimeGetter;
+use common::{
+    chain::{block::timestamp::BlockTimestamp, config::create_unit_test_config, GenBlock},
+    primitives::{Id, Idable},
+};
+use p2p_test_utils::{create_n_blocks, P2pBasicTestTimeGetter};
 use test_utils::random::Seed;
 
 use crate::{
-    message::{HeaderList, SyncMessage},
+    message::{BlockListRequest, BlockResponse, HeaderList, SyncMessage},
     protocol::ProtocolVersion,
     protocol::SupportedProtocolVersion,
-    sync::tests::helpers::TestNode,
+    sync::{tests::helpers::TestNode, SyncPeerEvent},
     testing_utils::{for_each_protocol_version, test_p2p_config},
     types::peer_id::PeerId,
 };
@@ -96,6 +99,143 @@ async fn disconnect_peer() {
     .await;
 }
 
+// Check that the subscribers are notified about connected and disconnected peers.
+#[tracing::instrument]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn peer_event_subscription() {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut node = TestNode::start(protocol_version).await;
+        let negotiated_version: SupportedProtocolVersion = protocol_version.try_into().unwrap();
+
+        let peer_id = PeerId::new();
+        let _ = node.connect_peer(peer_id, protocol_version).await;
+        assert_eq!(
+            node.receive_peer_event().await,
+            SyncPeerEvent::Connected {
+                peer_id,
+                protocol_version: negotiated_version
+            }
+        );
+
+        node.disconnect_peer(peer_id);
+        assert_eq!(
+            node.receive_peer_event().await,
+            SyncPeerEvent::Disconnected {
+                peer_id,
+                protocol_version: negotiated_version
+            }
+        );
+        node.assert_no_error().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
+// Check that dropping the peer event receiver doesn't affect the sync manager.
+#[tracing::instrument]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn peer_event_subscription_dropped() {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut node = TestNode::start(protocol_version).await;
+        node.unsubscribe_peer_events();
+
+        let peer1 = PeerId::new();
+        let _ = node.connect_peer(peer1, protocol_version).await;
+        node.disconnect_peer(peer1);
+
+        let peer2 = node.connect_peer(PeerId::new(), protocol_version).await;
+        node.assert_no_error().await;
+
+        node.shutdown_sync_manager().await;
+        assert!(peer2.is_unregistered());
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
+// Check that the shutdown signal stops the sync manager cleanly, after unregistering all peers.
+#[tracing::instrument]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn shutdown() {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut node = TestNode::start(protocol_version).await;
+
+        let peer1 = node.connect_peer(PeerId::new(), protocol_version).await;
+        let peer2 = node.connect_peer(PeerId::new(), protocol_version).await;
+        assert!(!peer1.is_unregistered());
+        assert!(!peer2.is_unregistered());
+
+        node.shutdown_sync_manager().await;
+
+        assert!(peer1.is_unregistered());
+        assert!(peer2.is_unregistered());
+        node.assert_no_error().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
+// Check that a block that has been received right before the shutdown signal is still processed,
+// including when it's part of an incomplete batch of blocks downloaded during the initial block
+// download.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy(), 1)]
+#[case(Seed::from_entropy(), 10)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn shutdown_with_block_in_flight(#[case] seed: Seed, #[case] commit_batch_size: usize) {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let chain_config = Arc::new(create_unit_test_config());
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .with_chainstate_config(
+                ChainstateConfig::new().with_commit_batch_size(commit_batch_size),
+            )
+            .build();
+        // Only the first block is sent, so the second one is still expected from the peer
+        let blocks = create_n_blocks(&mut tf, 2);
+        let block = blocks[0].clone();
+        let block_id = block.get_id();
+
+        let mut node = TestNode::builder(protocol_version)
+            .with_chain_config(chain_config)
+            .with_chainstate(tf.into_chainstate())
+            .build()
+            .await;
+
+        let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+        let headers = blocks.iter().map(|b| b.header().clone()).collect();
+        peer.send_message(SyncMessage::HeaderList(HeaderList::new(headers))).await;
+
+        let (sent_to, message) = node.get_sent_message().await;
+        assert_eq!(peer.get_id(), sent_to);
+        let ids = blocks.iter().map(|b| b.get_id()).collect();
+        assert_eq!(
+            message,
+            SyncMessage::BlockListRequest(BlockListRequest::new(ids))
+        );
+
+        peer.send_message(SyncMessage::BlockResponse(BlockResponse::new(block))).await;
+        node.shutdown_sync_manager().await;
+
+        assert!(peer.is_unregistered());
+        let best_block_id =
+            node.chainstate().call(|cs| cs.get_best_block_id().unwrap()).await.unwrap();
+        assert_eq!(best_block_id, Id::<GenBlock>::from(block_id));
+        node.assert_no_error().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
 #[tracing::instrument(skip(seed))]
 #[rstest::rstest]
 #[trace]
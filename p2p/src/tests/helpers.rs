@@ -67,6 +67,7 @@ where
     local_address: SocketAddress,
     shutdown: Arc<SeqCstAtomicBool>,
     shutdown_sender: oneshot::Sender<()>,
+    _sync_mgr_shutdown_sender: oneshot::Sender<()>,
     _subscribers_sender: mpsc::UnboundedSender<P2pEventHandler>,
     peer_mgr_join_handle: JoinHandle<(PeerMgr<TTM>, P2pError)>,
     sync_mgr_join_handle: JoinHandle<P2pError>,
@@ -157,10 +158,11 @@ where
             peer_mgr_event_tx.clone(),
             time_getter.get_time_getter(),
         );
+        let (sync_mgr_shutdown_sender, sync_mgr_shutdown_receiver) = oneshot::channel();
         let sync_mgr_join_handle = logging::spawn_in_current_span(async move {
-            match sync_mgr.run().await {
+            match sync_mgr.run(sync_mgr_shutdown_receiver).await {
                 Err(err) => err,
-                Ok(never) => match never {},
+                Ok(()) => panic!("Sync manager is not expected to be shut down explicitly"),
             }
         });
 
@@ -170,6 +172,7 @@ where
             local_address,
             shutdown,
             shutdown_sender,
+            _sync_mgr_shutdown_sender: sync_mgr_shutdown_sender,
             _subscribers_sender: subscribers_sender,
             peer_mgr_join_handle,
             sync_mgr_join_handle,
@@ -21,10 +21,26 @@ pub enum Service {
     Transactions = 1 << 0,
     Blocks = 1 << 1,
     PeerAddresses = 1 << 2,
+    /// Block headers are exchanged, but full blocks are not.
+    ///
+    /// `Blocks` implies this flag, so it's only advertised by the nodes that don't exchange
+    /// full blocks, and the nodes that don't know about it still exchange headers with them.
+    Headers = 1 << 3,
+    /// The bodies of old blocks are pruned, only the blocks that can still be reorged are served.
+    ///
+    /// Unlike the other flags, this one only describes the node that advertises it and is never
+    /// a part of the common services.
+    PrunedBlocks = 1 << 4,
 }
 
 impl Service {
-    pub const ALL: [Service; 3] = [Service::Transactions, Service::Blocks, Service::PeerAddresses];
+    pub const ALL: [Service; 5] = [
+        Service::Transactions,
+        Service::Blocks,
+        Service::PeerAddresses,
+        Service::Headers,
+        Service::PrunedBlocks,
+    ];
 }
 
 #[derive(Eq, PartialEq, Clone, Copy, Debug, Encode, Decode)]
@@ -42,6 +58,26 @@ impl Services {
     pub fn is_empty(&self) -> bool {
         self.0 == 0
     }
+
+    /// The services that can be used with a peer that advertises the `remote` services.
+    ///
+    /// `Headers` is a part of the result only if `Blocks` isn't.
+    pub fn common_with(self, remote: Services) -> Services {
+        let common = self.with_implied() & remote.with_implied();
+        if common.has_service(Service::Blocks) {
+            Services(common.0 & !(Service::Headers as u64))
+        } else {
+            common
+        }
+    }
+
+    fn with_implied(self) -> Services {
+        if self.has_service(Service::Blocks) {
+            self | [Service::Headers].as_slice().into()
+        } else {
+            self
+        }
+    }
 }
 
 impl From<&[Service]> for Services {
@@ -73,10 +109,34 @@ pub mod test {
 
     #[test]
     fn test_service_flags() {
-        let all_flags = vec![Service::Transactions, Service::Blocks, Service::PeerAddresses];
+        let all_flags = Service::ALL.to_vec();
         let services: Services = all_flags.as_slice().into();
         for flag in all_flags {
             assert!(services.has_service(flag));
         }
     }
+
+    #[test]
+    fn test_common_services() {
+        let full: Services = [Service::Transactions, Service::Blocks, Service::PeerAddresses]
+            .as_slice()
+            .into();
+        let blocks_only: Services = [Service::Blocks, Service::PeerAddresses].as_slice().into();
+        let headers_only: Services = [Service::Headers, Service::PeerAddresses].as_slice().into();
+        let blocks_and_headers: Services = [Service::Blocks, Service::Headers].as_slice().into();
+
+        assert_eq!(full.common_with(full), full);
+        assert_eq!(full.common_with(blocks_only), blocks_only);
+        assert_eq!(full.common_with(headers_only), headers_only);
+        assert_eq!(headers_only.common_with(blocks_only), headers_only);
+        assert_eq!(headers_only.common_with(headers_only), headers_only);
+        assert_eq!(
+            blocks_and_headers.common_with(full),
+            [Service::Blocks].as_slice().into()
+        );
+        assert_eq!(
+            headers_only.common_with([Service::Transactions].as_slice().into()),
+            [].as_slice().into()
+        );
+    }
 }
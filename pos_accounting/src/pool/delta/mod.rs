@@ -13,13 +13,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use accounting::{DeltaAmountCollection, DeltaDataUndoCollection};
+use accounting::{DeltaAmountCollection, DeltaDataUndoCollection, GetDataResult};
 use common::{
     chain::{DelegationId, PoolId},
     primitives::{signed_amount::SignedAmount, Amount, H256},
 };
+use crypto::vrf::VRFPublicKey;
 use serialization::{Decode, Encode};
 
 use crate::error::Error;
@@ -35,6 +36,9 @@ mod view_impl;
 pub struct PoSAccountingDelta<P> {
     parent: P,
     data: PoSAccountingDeltaData,
+    /// Pools whose data has been present in the delta at some point, by VRF key.
+    /// The VRF key of a pool never changes, but the pools may have been deleted since.
+    pools_by_vrf_key: BTreeMap<VRFPublicKey, BTreeSet<PoolId>>,
 }
 
 /// All the operations we have to do with the accounting state to undo a delta
@@ -64,11 +68,19 @@ impl<P: PoSAccountingView> PoSAccountingDelta<P> {
         Self {
             parent,
             data: PoSAccountingDeltaData::new(),
+            pools_by_vrf_key: BTreeMap::new(),
         }
     }
 
     pub fn from_data(parent: P, data: PoSAccountingDeltaData) -> Self {
-        Self { parent, data }
+        let pool_ids = data.pool_data.data().keys().copied().collect::<Vec<_>>();
+        let mut delta = Self {
+            parent,
+            data,
+            pools_by_vrf_key: BTreeMap::new(),
+        };
+        delta.index_pools_vrf_keys(pool_ids);
+        delta
     }
 
     pub fn consume(self) -> PoSAccountingDeltaData {
@@ -98,11 +110,30 @@ impl<P: PoSAccountingView> PoSAccountingDelta<P> {
         &mut self,
         other: PoSAccountingDeltaData,
     ) -> Result<DeltaMergeUndo, Error> {
-        self.data.merge_with_delta(other)
+        let pool_ids = other.pool_data.data().keys().copied().collect::<Vec<_>>();
+        let undo = self.data.merge_with_delta(other)?;
+        self.index_pools_vrf_keys(pool_ids);
+        Ok(undo)
     }
 
     pub fn undo_delta_merge(&mut self, undo_data: DeltaMergeUndo) -> Result<(), Error> {
-        self.data.undo_delta_merge(undo_data)
+        let pool_ids = undo_data.pool_data_undo.data().keys().copied().collect::<Vec<_>>();
+        self.data.undo_delta_merge(undo_data)?;
+        self.index_pools_vrf_keys(pool_ids);
+        Ok(())
+    }
+
+    /// Must be called after the data of the pools has been changed, so that they can be found
+    /// by their VRF keys
+    fn index_pools_vrf_keys(&mut self, pool_ids: impl IntoIterator<Item = PoolId>) {
+        for pool_id in pool_ids {
+            if let GetDataResult::Present(data) = self.data.pool_data.get_data(&pool_id) {
+                self.pools_by_vrf_key
+                    .entry(data.vrf_public_key().clone())
+                    .or_default()
+                    .insert(pool_id);
+            }
+        }
     }
 
     fn add_to_delegation_balance(
@@ -60,6 +60,7 @@ impl<P: PoSAccountingView> PoSAccountingOperations for PoSAccountingDelta<P> {
             .data
             .pool_data
             .merge_delta_data_element(pool_id, DataDelta::new(None, Some(pool_data)))?;
+        self.index_pools_vrf_keys([pool_id]);
 
         Ok(PoSAccountingUndo::CreatePool(CreatePoolUndo {
             pool_id,
@@ -112,6 +113,7 @@ impl<P: PoSAccountingView> PoSAccountingOperations for PoSAccountingDelta<P> {
             pool_id,
             DataDelta::new(Some(pool_data), Some(new_pool_data)),
         )?;
+        self.index_pools_vrf_keys([pool_id]);
 
         Ok(PoSAccountingUndo::IncreasePledgeAmount(
             IncreasePledgeAmountUndo {
@@ -298,6 +300,7 @@ impl<P: PoSAccountingView> PoSAccountingDelta<P> {
 
         self.data.pool_balances.add_unsigned(undo.pool_id, undo.pool_balance)?;
         self.data.pool_data.undo_merge_delta_data_element(undo.pool_id, undo_data)?;
+        self.index_pools_vrf_keys([undo.pool_id]);
 
         Ok(())
     }
@@ -388,6 +391,7 @@ impl<P: PoSAccountingView> PoSAccountingDelta<P> {
         };
 
         self.data.pool_data.undo_merge_delta_data_element(undo.pool_id, undo_data)?;
+        self.index_pools_vrf_keys([undo.pool_id]);
         self.sub_balance_from_pool(undo.pool_id, undo.amount_added)?;
 
         Ok(())
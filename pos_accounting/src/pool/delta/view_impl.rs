@@ -20,6 +20,7 @@ use common::{
     chain::{DelegationId, PoolId},
     primitives::{signed_amount::SignedAmount, Amount},
 };
+use crypto::vrf::VRFPublicKey;
 
 use crate::{
     error::Error,
@@ -89,6 +90,36 @@ impl<P: PoSAccountingView> PoSAccountingView for PoSAccountingDelta<P> {
         }
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, Self::Error> {
+        let local_pool_id =
+            self.pools_by_vrf_key.get(vrf_public_key).into_iter().flatten().copied().find(
+                |pool_id| {
+                    matches!(
+                        self.data.pool_data.get_data(pool_id),
+                        accounting::GetDataResult::Present(_)
+                    )
+                },
+            );
+        if local_pool_id.is_some() {
+            return Ok(local_pool_id);
+        }
+
+        // A pool found in the parent could have been deleted or modified in this delta
+        let parent_pool_id = self
+            .parent
+            .get_pool_id_by_vrf_public_key(vrf_public_key)
+            .map_err(|_| Error::ViewFail)?;
+        Ok(parent_pool_id.filter(|pool_id| {
+            matches!(
+                self.data.pool_data.get_data(pool_id),
+                accounting::GetDataResult::Missing
+            )
+        }))
+    }
+
     fn get_pool_delegations_shares(
         &self,
         pool_id: PoolId,
@@ -19,6 +19,7 @@ use common::{
     chain::{DelegationId, PoolId},
     primitives::Amount,
 };
+use crypto::vrf::VRFPublicKey;
 
 use crate::{
     error::Error,
@@ -47,6 +48,13 @@ impl<S: PoSAccountingStorageRead<T>, T: StorageTag> PoSAccountingView for PoSAcc
         self.store.get_pool_data(pool_id).map_err(Error::from)
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, Error> {
+        self.store.get_pool_id_by_vrf_public_key(vrf_public_key).map_err(Error::from)
+    }
+
     fn get_pool_delegations_shares(
         &self,
         pool_id: PoolId,
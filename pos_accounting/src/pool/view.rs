@@ -19,6 +19,7 @@ use common::{
     chain::{DelegationId, PoolId},
     primitives::Amount,
 };
+use crypto::vrf::VRFPublicKey;
 
 use crate::{error::Error, DeltaMergeUndo};
 
@@ -33,6 +34,12 @@ pub trait PoSAccountingView {
 
     fn get_pool_data(&self, pool_id: PoolId) -> Result<Option<PoolData>, Self::Error>;
 
+    /// Find the pool whose data references the given VRF public key
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, Self::Error>;
+
     fn get_pool_delegations_shares(
         &self,
         pool_id: PoolId,
@@ -78,6 +85,13 @@ where
         self.deref().get_pool_data(pool_id)
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, Self::Error> {
+        self.deref().get_pool_id_by_vrf_public_key(vrf_public_key)
+    }
+
     fn get_pool_delegations_shares(
         &self,
         pool_id: PoolId,
@@ -20,6 +20,7 @@ use common::{
     chain::{DelegationId, PoolId},
     primitives::{Amount, H256},
 };
+use crypto::vrf::VRFPublicKey;
 
 use crate::pool::{delegation::DelegationData, pool_data::PoolData};
 
@@ -175,6 +176,17 @@ impl PoSAccountingStorageRead for InMemoryPoSAccounting {
     fn get_pool_data(&self, pool_id: PoolId) -> Result<Option<PoolData>, Error> {
         Ok(self.pool_data.get(&pool_id).cloned())
     }
+
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, Error> {
+        Ok(self
+            .pool_data
+            .iter()
+            .find(|(_, data)| data.vrf_public_key() == vrf_public_key)
+            .map(|(pool_id, _)| *pool_id))
+    }
 }
 
 impl PoSAccountingStorageWrite for InMemoryPoSAccounting {
@@ -22,6 +22,7 @@ use common::{
     chain::{DelegationId, PoolId},
     primitives::Amount,
 };
+use crypto::vrf::VRFPublicKey;
 
 use crate::pool::{delegation::DelegationData, pool_data::PoolData};
 
@@ -39,6 +40,11 @@ pub trait PoSAccountingStorageRead<Tag: StorageTag = DefaultStorageTag> {
 
     fn get_pool_data(&self, pool_id: PoolId) -> Result<Option<PoolData>, storage_result::Error>;
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, storage_result::Error>;
+
     fn get_delegation_balance(
         &self,
         delegation_id: DelegationId,
@@ -130,6 +136,13 @@ where
         self.deref().get_pool_data(pool_id)
     }
 
+    fn get_pool_id_by_vrf_public_key(
+        &self,
+        vrf_public_key: &VRFPublicKey,
+    ) -> Result<Option<PoolId>, storage_result::Error> {
+        self.deref().get_pool_id_by_vrf_public_key(vrf_public_key)
+    }
+
     fn get_delegation_balance(
         &self,
         delegation_id: DelegationId,
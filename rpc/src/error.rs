@@ -15,6 +15,10 @@
 
 //! Error handling machinery for RPC
 
+use jsonrpsee::types::error::{
+    CallError, ErrorObject, CALL_EXECUTION_FAILED_CODE, INVALID_PARAMS_CODE,
+};
+
 /// RPC error
 pub use jsonrpsee::core::Error;
 
@@ -65,3 +69,53 @@ where
         self.map_err(Error::to_call_error).and_then(T::handle_result)
     }
 }
+
+/// Error code reported when the requested object doesn't exist
+pub const NOT_FOUND_ERROR_CODE: i32 = -32001;
+
+/// The kind of an error, determines the error code reported to RPC clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The requested object doesn't exist
+    NotFound,
+    /// The request is invalid, e.g. contains malformed or inconsistent data
+    BadRequest,
+    /// The request couldn't be handled for reasons unrelated to its contents
+    Internal,
+}
+
+impl ErrorKind {
+    pub const fn code(self) -> i32 {
+        match self {
+            ErrorKind::NotFound => NOT_FOUND_ERROR_CODE,
+            ErrorKind::BadRequest => INVALID_PARAMS_CODE,
+            ErrorKind::Internal => CALL_EXECUTION_FAILED_CODE,
+        }
+    }
+}
+
+/// Errors that can tell which [ErrorKind] they are
+pub trait ClassifyError: std::error::Error {
+    fn error_kind(&self) -> ErrorKind;
+}
+
+/// Convert an error into an RPC error carrying the code of its kind
+pub fn classified_error<E: ClassifyError>(error: E) -> Error {
+    Error::Call(CallError::Custom(ErrorObject::owned(
+        error.error_kind().code(),
+        error.to_string(),
+        None::<()>,
+    )))
+}
+
+/// Same as [handle_result] for the results of subsystem calls returning a [ClassifyError], but
+/// the inner errors are reported with the codes of their kinds.
+pub fn handle_classified_result<R, E, C>(
+    res: core::result::Result<core::result::Result<R, E>, C>,
+) -> self::Result<R>
+where
+    E: ClassifyError,
+    C: 'static + Sync + Send + std::error::Error,
+{
+    res.map_err(Error::to_call_error)?.map_err(classified_error)
+}
@@ -16,6 +16,7 @@
 mod error;
 mod rpc_auth;
 pub mod rpc_creds;
+pub mod rpc_tls;
 
 use std::{net::SocketAddr, path::PathBuf};
 
@@ -28,11 +29,16 @@ use jsonrpsee::{
 
 use logging::log;
 
-pub use error::{handle_result, Error, Result};
+pub use error::{
+    classified_error, handle_classified_result, handle_result, ClassifyError, Error, ErrorKind,
+    Result, NOT_FOUND_ERROR_CODE,
+};
 
 pub use jsonrpsee::{core::server::Methods, proc_macros::rpc};
 use rpc_auth::RpcAuth;
 use rpc_creds::RpcCreds;
+use rpc_tls::RpcTlsConfig;
+use tokio::task::JoinHandle;
 use tower_http::{
     set_header::{MakeHeaderValue, SetRequestHeader, SetRequestHeaderLayer},
     validate_request::ValidateRequestHeaderLayer,
@@ -44,6 +50,7 @@ pub struct Builder {
     http_bind_address: SocketAddr,
     methods: Methods,
     creds: Option<RpcCreds>,
+    tls: Option<RpcTlsConfig>,
 }
 
 impl Builder {
@@ -55,9 +62,16 @@ impl Builder {
             http_bind_address,
             methods: Methods::new(),
             creds,
+            tls: None,
         }
     }
 
+    /// Serve RPC over HTTPS using the given certificate and key
+    pub fn with_tls(mut self, tls: RpcTlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
     /// Add methods handlers to the RPC server
     pub fn register(mut self, methods: impl Into<Methods>) -> Self {
         self.methods.merge(methods).expect("Duplicate RPC methods");
@@ -66,13 +80,20 @@ impl Builder {
 
     /// Build the RPC server and get the RPC object
     pub async fn build(self) -> anyhow::Result<Rpc> {
-        Rpc::new(&self.http_bind_address, self.methods, self.creds).await
+        Rpc::new(&self.http_bind_address, self.methods, self.creds, self.tls).await
     }
 }
 
+enum HttpServer {
+    Plain(ServerHandle),
+    // Serves RPC over TLS, the jsonrpsee server isn't started in this case
+    Tls(JoinHandle<()>),
+}
+
 /// The RPC subsystem
 pub struct Rpc {
-    http: (SocketAddr, ServerHandle),
+    http_address: SocketAddr,
+    http_server: HttpServer,
     // Stored here to remove the cookie file when the node is stopped
     _creds: Option<RpcCreds>,
 }
@@ -81,36 +102,46 @@ impl Rpc {
     /// Rpc constructor.
     ///
     /// If `creds` is set, basic HTTP authentication is required.
+    /// If `tls` is set, RPC is served over HTTPS.
     async fn new(
         http_bind_addr: &SocketAddr,
         methods: Methods,
         creds: Option<RpcCreds>,
+        tls: Option<RpcTlsConfig>,
     ) -> anyhow::Result<Self> {
-        let auth_layer = creds.as_ref().map(|creds| {
-            ValidateRequestHeaderLayer::custom(RpcAuth::new(creds.username(), creds.password()))
-        });
-
-        let middleware = tower::ServiceBuilder::new().layer(tower::util::option_layer(auth_layer));
-
-        let http = {
-            let http_server = ServerBuilder::new()
-                .set_middleware(middleware.clone())
-                .http_only()
-                .build(http_bind_addr)
-                .await?;
-            let http_address = http_server.local_addr()?;
-            let http_handle = http_server.start(methods.clone())?;
-            (http_address, http_handle)
+        let auth = creds.as_ref().map(|creds| RpcAuth::new(creds.username(), creds.password()));
+
+        let (http_address, http_server) = match tls {
+            Some(tls) => {
+                let (http_address, tls_server) =
+                    rpc_tls::start_tls_server(&tls, http_bind_addr, methods, auth).await?;
+                (http_address, HttpServer::Tls(tls_server))
+            }
+            None => {
+                let auth_layer = auth.map(ValidateRequestHeaderLayer::custom);
+                let middleware =
+                    tower::ServiceBuilder::new().layer(tower::util::option_layer(auth_layer));
+
+                let http_server = ServerBuilder::new()
+                    .set_middleware(middleware)
+                    .http_only()
+                    .build(http_bind_addr)
+                    .await?;
+                let http_address = http_server.local_addr()?;
+                let http_handle = http_server.start(methods)?;
+                (http_address, HttpServer::Plain(http_handle))
+            }
         };
 
         Ok(Self {
-            http,
+            http_address,
+            http_server,
             _creds: creds,
         })
     }
 
     pub fn http_address(&self) -> &SocketAddr {
-        &self.http.0
+        &self.http_address
     }
 }
 
@@ -127,9 +158,16 @@ impl subsystem::Subsystem for Rpc {
     }
 
     async fn shutdown(self) {
-        match self.http.1.stop() {
-            Ok(()) => self.http.1.stopped().await,
-            Err(e) => log::error!("Http RPC stop handle acquisition failed: {}", e),
+        match self.http_server {
+            HttpServer::Plain(handle) => match handle.stop() {
+                Ok(()) => handle.stopped().await,
+                Err(e) => log::error!("Http RPC stop handle acquisition failed: {}", e),
+            },
+            HttpServer::Tls(task) => {
+                // Wait for the task to be dropped, so the open connections are closed as well
+                task.abort();
+                let _ = task.await;
+            }
         }
     }
 }
@@ -361,4 +399,188 @@ mod tests {
 
         subsystem::Subsystem::shutdown(rpc).await;
     }
+
+    const PROTOCOL_VERSION_REQUEST: &str =
+        r#"{"jsonrpc":"2.0","id":1,"method":"example_server_protocol_version","params":[]}"#;
+
+    // Opens a TLS connection to the server, trusting only the given certificate
+    async fn https_connect(
+        rpc: &Rpc,
+        cert: &rcgen::Certificate,
+    ) -> anyhow::Result<hyper::client::conn::SendRequest<hyper::Body>> {
+        use tokio_rustls::rustls;
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(&rustls::Certificate(cert.serialize_der()?))?;
+        let client_config = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(std::sync::Arc::new(client_config));
+
+        let stream = tokio::net::TcpStream::connect(rpc.http_address()).await?;
+        let stream = connector.connect(rustls::ServerName::try_from("localhost")?, stream).await?;
+
+        let (sender, connection) = hyper::client::conn::handshake(stream).await?;
+        tokio::spawn(connection);
+        Ok(sender)
+    }
+
+    async fn send_https_request(
+        sender: &mut hyper::client::conn::SendRequest<hyper::Body>,
+        rpc_auth: &RpcAuthData,
+        body: &'static str,
+    ) -> anyhow::Result<hyper::Response<hyper::Body>> {
+        let mut request =
+            hyper::Request::post("/").header(header::CONTENT_TYPE, "application/json");
+        if let Some(auth) = rpc_auth.get_header() {
+            request = request.header(header::AUTHORIZATION, auth);
+        }
+        let request = request.body(hyper::Body::from(body))?;
+        Ok(sender.send_request(request).await?)
+    }
+
+    // Sends a JSON-RPC request over a new TLS connection
+    async fn https_request(
+        rpc: &Rpc,
+        cert: &rcgen::Certificate,
+        rpc_auth: RpcAuthData,
+    ) -> anyhow::Result<hyper::Response<hyper::Body>> {
+        let mut sender = https_connect(rpc, cert).await?;
+        send_https_request(&mut sender, &rpc_auth, PROTOCOL_VERSION_REQUEST).await
+    }
+
+    fn make_tls_files(dir: &std::path::Path) -> (rcgen::Certificate, RpcTlsConfig) {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_owned()]).unwrap();
+        let cert_file = dir.join("cert.pem");
+        let key_file = dir.join("key.pem");
+        std::fs::write(&cert_file, cert.serialize_pem().unwrap()).unwrap();
+        std::fs::write(&key_file, cert.serialize_private_key_pem()).unwrap();
+        (cert, RpcTlsConfig::new(cert_file, key_file))
+    }
+
+    #[tokio::test]
+    async fn rpc_server_tls() {
+        let tls_dir = tempfile::TempDir::new().unwrap();
+        let (cert, tls_config) = make_tls_files(tls_dir.path());
+
+        let http_bind_address = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+        let rpc = Builder::new(
+            http_bind_address,
+            Some(
+                RpcCreds::new(
+                    tls_dir.path(),
+                    Some("username"),
+                    Some("password"),
+                    Option::<String>::None,
+                )
+                .unwrap(),
+            ),
+        )
+        .with_tls(tls_config)
+        .register(RpcInfo.into_rpc())
+        .build()
+        .await
+        .unwrap();
+
+        // Basic auth works over TLS
+        let response = https_request(
+            &rpc,
+            &cert,
+            RpcAuthData::Basic {
+                username: "username".to_owned(),
+                password: "password".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), hyper::StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert!(String::from_utf8(body.to_vec()).unwrap().contains("version1"));
+
+        let response = https_request(&rpc, &cert, RpcAuthData::None).await.unwrap();
+        assert_eq!(response.status(), hyper::StatusCode::UNAUTHORIZED);
+
+        // Batches are rejected with a JSON-RPC error
+        let mut sender = https_connect(&rpc, &cert).await.unwrap();
+        let batch = concat!(
+            r#"[{"jsonrpc":"2.0","id":1,"method":"example_server_protocol_version","params":[]},"#,
+            r#"{"jsonrpc":"2.0","id":2,"method":"example_server_protocol_version","params":[]}]"#
+        );
+        let response = send_https_request(
+            &mut sender,
+            &RpcAuthData::Basic {
+                username: "username".to_owned(),
+                password: "password".to_owned(),
+            },
+            batch,
+        )
+        .await
+        .unwrap();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert!(String::from_utf8(body.to_vec()).unwrap().contains("-32600"));
+
+        // Plain HTTP isn't served
+        http_request(
+            &rpc,
+            RpcAuthData::Basic {
+                username: "username".to_owned(),
+                password: "password".to_owned(),
+            },
+        )
+        .await
+        .unwrap_err();
+
+        subsystem::Subsystem::shutdown(rpc).await;
+    }
+
+    #[tokio::test]
+    async fn rpc_server_tls_shutdown_closes_connections() {
+        let tls_dir = tempfile::TempDir::new().unwrap();
+        let (cert, tls_config) = make_tls_files(tls_dir.path());
+
+        let http_bind_address = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+        let rpc = Builder::new(http_bind_address, None)
+            .with_tls(tls_config)
+            .register(RpcInfo.into_rpc())
+            .build()
+            .await
+            .unwrap();
+
+        let mut sender = https_connect(&rpc, &cert).await.unwrap();
+        let response =
+            send_https_request(&mut sender, &RpcAuthData::None, PROTOCOL_VERSION_REQUEST)
+                .await
+                .unwrap();
+        assert_eq!(response.status(), hyper::StatusCode::OK);
+        hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+        subsystem::Subsystem::shutdown(rpc).await;
+
+        // The keep-alive connection isn't served after the shutdown
+        send_https_request(&mut sender, &RpcAuthData::None, PROTOCOL_VERSION_REQUEST)
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn rpc_server_tls_bad_files() {
+        let tls_dir = tempfile::TempDir::new().unwrap();
+        let cert_file = tls_dir.path().join("cert.pem");
+        std::fs::write(&cert_file, "not a certificate").unwrap();
+
+        let http_bind_address = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+        let res = Builder::new(http_bind_address, None)
+            .with_tls(RpcTlsConfig::new(
+                &cert_file,
+                tls_dir.path().join("key.pem"),
+            ))
+            .register(RpcInfo.into_rpc())
+            .build()
+            .await;
+        assert!(matches!(
+            res.err().unwrap().downcast_ref::<rpc_tls::RpcTlsError>(),
+            Some(rpc_tls::RpcTlsError::NoCertificates(_))
+        ));
+    }
 }
@@ -0,0 +1,248 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! TLS support for the RPC server.
+//!
+//! The jsonrpsee server (as of version 0.17) can only serve plain TCP connections it accepts
+//! itself, so with TLS enabled the RPC requests are served here instead and dispatched to the
+//! registered methods directly. No plain HTTP listener is opened in that case, not even on
+//! the loopback interface.
+
+use std::{
+    convert::Infallible,
+    io::BufReader,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use hyper::{
+    body::HttpBody, header, server::conn::Http, Body, Method, Request, Response, StatusCode,
+};
+use jsonrpsee::core::server::Methods;
+use logging::log;
+use tokio::{
+    net::TcpListener,
+    task::{JoinHandle, JoinSet},
+};
+use tokio_rustls::{
+    rustls::{Certificate, PrivateKey, ServerConfig},
+    TlsAcceptor,
+};
+use tower_http::validate_request::ValidateRequestHeaderLayer;
+
+use crate::rpc_auth::RpcAuth;
+
+/// Same as the default limit of the jsonrpsee server
+const MAX_REQUEST_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+/// Same as the default limit of the jsonrpsee server
+const MAX_CONNECTIONS: usize = 100;
+
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connections are closed after this time, so slow or idle clients can't hold them forever.
+/// Clients open new connections as needed.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Accepting fails repeatedly e.g. when the process runs out of file descriptors,
+/// so wait a bit instead of retrying immediately
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_secs(1);
+
+const PARSE_ERROR_RESPONSE: &str =
+    r#"{"jsonrpc":"2.0","error":{"code":-32700,"message":"Parse error"},"id":null}"#;
+
+const BATCH_NOT_SUPPORTED_RESPONSE: &str = concat!(
+    r#"{"jsonrpc":"2.0","error":{"code":-32600,"#,
+    r#""message":"Batch requests are not supported"},"id":null}"#
+);
+
+#[derive(thiserror::Error, Debug)]
+pub enum RpcTlsError {
+    #[error("Failed to read TLS file {0}: {1}")]
+    FileIoError(PathBuf, std::io::Error),
+    #[error("No certificates found in {0}")]
+    NoCertificates(PathBuf),
+    #[error("No private key found in {0}")]
+    NoPrivateKey(PathBuf),
+    #[error("Invalid TLS certificate or key: {0}")]
+    InvalidCertificate(#[from] tokio_rustls::rustls::Error),
+}
+
+/// PEM encoded certificate chain and private key used to serve RPC over HTTPS
+#[derive(Clone, Debug)]
+pub struct RpcTlsConfig {
+    cert_file: PathBuf,
+    key_file: PathBuf,
+}
+
+impl RpcTlsConfig {
+    pub fn new(cert_file: impl Into<PathBuf>, key_file: impl Into<PathBuf>) -> Self {
+        Self {
+            cert_file: cert_file.into(),
+            key_file: key_file.into(),
+        }
+    }
+
+    fn make_acceptor(&self) -> Result<TlsAcceptor, RpcTlsError> {
+        let certs = rustls_pemfile::certs(&mut open_file(&self.cert_file)?)
+            .map_err(|e| RpcTlsError::FileIoError(self.cert_file.clone(), e))?
+            .into_iter()
+            .map(Certificate)
+            .collect::<Vec<_>>();
+        utils::ensure!(
+            !certs.is_empty(),
+            RpcTlsError::NoCertificates(self.cert_file.clone())
+        );
+
+        let key = rustls_pemfile::read_all(&mut open_file(&self.key_file)?)
+            .map_err(|e| RpcTlsError::FileIoError(self.key_file.clone(), e))?
+            .into_iter()
+            .find_map(|item| match item {
+                rustls_pemfile::Item::RSAKey(key)
+                | rustls_pemfile::Item::PKCS8Key(key)
+                | rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+                _ => None,
+            })
+            .ok_or_else(|| RpcTlsError::NoPrivateKey(self.key_file.clone()))?;
+
+        let config = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)?;
+
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+}
+
+fn open_file(path: &Path) -> Result<BufReader<std::fs::File>, RpcTlsError> {
+    std::fs::File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| RpcTlsError::FileIoError(path.to_owned(), e))
+}
+
+/// Accepts TLS connections on `bind_addr` and serves the RPC `methods` over them.
+///
+/// Only single JSON-RPC calls over HTTP POST are supported, batches and subscriptions aren't.
+/// Returns the address the listener is bound to and the task serving the connections.
+/// Aborting the task closes all the connections as well.
+pub(crate) async fn start_tls_server(
+    tls_config: &RpcTlsConfig,
+    bind_addr: &SocketAddr,
+    methods: Methods,
+    auth: Option<RpcAuth>,
+) -> anyhow::Result<(SocketAddr, JoinHandle<()>)> {
+    let acceptor = tls_config.make_acceptor()?;
+    let listener = TcpListener::bind(bind_addr).await?;
+    let local_addr = listener.local_addr()?;
+
+    let task = tokio::spawn(async move {
+        // The connection tasks are aborted when the set is dropped together with this task
+        let mut connections = JoinSet::new();
+
+        loop {
+            let accepted = tokio::select! {
+                accepted = listener.accept(), if connections.len() < MAX_CONNECTIONS => accepted,
+                Some(_) = connections.join_next() => continue,
+            };
+            let (stream, peer_addr) = match accepted {
+                Ok(conn) => conn,
+                Err(e) => {
+                    log::error!("Accepting RPC TLS connection failed: {e}");
+                    tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                    continue;
+                }
+            };
+
+            let acceptor = acceptor.clone();
+            let service = tower::ServiceBuilder::new()
+                .layer(tower::util::option_layer(
+                    auth.clone().map(ValidateRequestHeaderLayer::custom),
+                ))
+                .service_fn({
+                    let methods = methods.clone();
+                    move |request| handle_request(methods.clone(), request)
+                });
+
+            connections.spawn(async move {
+                let result = async {
+                    let tls_stream =
+                        tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream))
+                            .await??;
+                    tokio::time::timeout(
+                        CONNECTION_TIMEOUT,
+                        Http::new().http1_only(true).serve_connection(tls_stream, service),
+                    )
+                    .await??;
+                    Ok::<_, anyhow::Error>(())
+                }
+                .await;
+                if let Err(e) = result {
+                    log::debug!("RPC TLS connection from {peer_addr} failed: {e}");
+                }
+            });
+        }
+    });
+
+    Ok((local_addr, task))
+}
+
+async fn handle_request(
+    methods: Methods,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    if request.method() != Method::POST {
+        return Ok(status_response(StatusCode::METHOD_NOT_ALLOWED));
+    }
+
+    let body = match read_body(request.into_body()).await {
+        Ok(body) => body,
+        Err(status) => return Ok(status_response(status)),
+    };
+
+    let result = match std::str::from_utf8(&body) {
+        Ok(request) if request.trim_start().starts_with('[') => {
+            BATCH_NOT_SUPPORTED_RESPONSE.to_owned()
+        }
+        Ok(request) => match methods.raw_json_request(request, 1).await {
+            Ok((response, _subscription_rx)) => response.result,
+            Err(_) => PARSE_ERROR_RESPONSE.to_owned(),
+        },
+        Err(_) => PARSE_ERROR_RESPONSE.to_owned(),
+    };
+
+    Ok(Response::builder()
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(result))
+        .expect("Valid response"))
+}
+
+async fn read_body(mut body: Body) -> Result<Vec<u8>, StatusCode> {
+    let mut result = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|_| StatusCode::BAD_REQUEST)?;
+        utils::ensure!(
+            result.len() + chunk.len() <= MAX_REQUEST_BODY_SIZE,
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+        result.extend_from_slice(&chunk);
+    }
+    Ok(result)
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    Response::builder().status(status).body(Body::empty()).expect("Valid response")
+}
@@ -74,6 +74,15 @@ pub fn prefix_iter<DbMap: schema::DbMap, Tx: ReadOps>(
     })
 }
 
+pub fn prefix_iter_raw<DbMap: schema::DbMap, Tx: ReadOps>(
+    dbtx: &Tx,
+    map_id: DbMapId,
+    prefix: Vec<u8>,
+) -> crate::Result<impl '_ + Iterator<Item = (DbMap::Key, Vec<u8>)>> {
+    dbtx.prefix_iter(map_id, prefix)
+        .map(|iter| iter.map(|(k, v)| (Encoded::from_bytes_unchecked(k).decode(), v)))
+}
+
 pub fn prefix_iter_keys<DbMap: schema::DbMap, Tx: ReadOps>(
     dbtx: &Tx,
     map_id: DbMapId,
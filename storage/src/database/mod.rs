@@ -272,6 +272,21 @@ where
     {
         internal::prefix_iter(self.dbtx, self.map_id, prefix.encode())
     }
+
+    /// Iterator over entries with key starting with given prefix, values are left undecoded.
+    ///
+    /// Unlike [Self::prefix_iter], the values are not required to be valid encodings of
+    /// `DbMap::Value`, e.g. when they are being migrated from an older format.
+    pub fn prefix_iter_raw<Pfx>(
+        &self,
+        prefix: &Pfx,
+    ) -> crate::Result<impl '_ + Iterator<Item = (DbMap::Key, Vec<u8>)>>
+    where
+        Pfx: Encode,
+        DbMap::Key: HasPrefix<Pfx>,
+    {
+        internal::prefix_iter_raw::<DbMap, _>(self.dbtx, self.map_id, prefix.encode())
+    }
 }
 
 impl<Tx: TxImpl, DbMap: schema::DbMap> MapMut<'_, Tx, DbMap>
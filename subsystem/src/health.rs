@@ -0,0 +1,158 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Subsystem liveness tracking
+//!
+//! Each subsystem task records a heartbeat every time its event loop goes around. An idle
+//! subsystem is woken up periodically to keep the heartbeat fresh, so a stale heartbeat means
+//! the event loop is stuck (e.g. a call that never completes).
+
+use std::time::{Duration, Instant};
+
+use utils::sync::{Arc, Mutex};
+
+/// How often an idle subsystem refreshes its heartbeat.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Lifecycle state of a subsystem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubsystemState {
+    /// The subsystem is registered but its initialization has not finished yet.
+    Starting,
+    /// The subsystem event loop is running.
+    Running,
+    /// The subsystem has terminated (or failed to initialize).
+    Stopped,
+}
+
+/// Health information about a single subsystem.
+#[derive(Clone, Debug)]
+pub struct SubsystemHealth {
+    full_name: String,
+    state: SubsystemState,
+    last_heartbeat: Option<Instant>,
+}
+
+impl SubsystemHealth {
+    fn new(full_name: String) -> Self {
+        Self {
+            full_name,
+            state: SubsystemState::Starting,
+            last_heartbeat: None,
+        }
+    }
+
+    /// Full name of the subsystem.
+    pub fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    /// Current lifecycle state.
+    pub fn state(&self) -> SubsystemState {
+        self.state
+    }
+
+    /// Time elapsed since the last heartbeat, `None` if there has been none yet.
+    pub fn since_last_heartbeat(&self) -> Option<Duration> {
+        self.last_heartbeat.map(|hb| hb.elapsed())
+    }
+
+    /// The subsystem is running and its last heartbeat is not older than `max_age`.
+    pub fn is_healthy(&self, max_age: Duration) -> bool {
+        self.state == SubsystemState::Running
+            && self.since_last_heartbeat().map_or(false, |age| age <= max_age)
+    }
+}
+
+/// Shared view of the health of all subsystems registered with a manager.
+#[derive(Clone)]
+pub struct HealthMonitor {
+    entries: Arc<Mutex<Vec<SubsystemHealth>>>,
+}
+
+impl HealthMonitor {
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Register a new subsystem and obtain a heartbeat handle for it.
+    pub(crate) fn register(&self, full_name: String) -> Heartbeat {
+        let mut entries = self.entries.lock().expect("poisoned mutex");
+        let index = entries.len();
+        entries.push(SubsystemHealth::new(full_name));
+        Heartbeat {
+            monitor: self.clone(),
+            index,
+        }
+    }
+
+    /// Snapshot of the health of all subsystems, in the order of registration.
+    pub fn report(&self) -> Vec<SubsystemHealth> {
+        self.entries.lock().expect("poisoned mutex").clone()
+    }
+
+    fn update(&self, index: usize, f: impl FnOnce(&mut SubsystemHealth)) {
+        f(&mut self.entries.lock().expect("poisoned mutex")[index])
+    }
+}
+
+/// Used by a subsystem task to report its liveness.
+pub(crate) struct Heartbeat {
+    monitor: HealthMonitor,
+    index: usize,
+}
+
+impl Heartbeat {
+    /// Record that the event loop is alive.
+    pub fn beat(&self) {
+        self.monitor.update(self.index, |health| {
+            health.state = SubsystemState::Running;
+            health.last_heartbeat = Some(Instant::now());
+        })
+    }
+
+    /// Record that the subsystem has terminated.
+    pub fn stopped(&self) {
+        self.monitor.update(self.index, |health| health.state = SubsystemState::Stopped)
+    }
+}
+
+/// Wakes up an idle subsystem event loop so it can refresh its heartbeat.
+pub(crate) struct HeartbeatTimer {
+    #[cfg(all(feature = "time", not(loom)))]
+    interval: tokio::time::Interval,
+}
+
+impl HeartbeatTimer {
+    pub fn new() -> Self {
+        Self {
+            #[cfg(all(feature = "time", not(loom)))]
+            interval: tokio::time::interval(HEARTBEAT_INTERVAL),
+        }
+    }
+
+    /// Wait for the next tick. Never completes if timer support is not compiled in.
+    pub async fn tick(&mut self) {
+        cfg_if::cfg_if! {
+            if #[cfg(all(feature = "time", not(loom)))] {
+                self.interval.tick().await;
+            } else {
+                std::future::pending::<()>().await
+            }
+        }
+    }
+}
@@ -34,6 +34,7 @@
 //! 3. The main task waits for all subsystems to terminate.
 
 mod calls;
+mod health;
 mod manager;
 mod subsystem;
 mod task;
@@ -43,6 +44,7 @@ pub mod error;
 
 pub use crate::{
     calls::{blocking, CallResponse, CallResult, Handle, SubmitOnlyHandle},
+    health::{HealthMonitor, SubsystemHealth, SubsystemState, HEARTBEAT_INTERVAL},
     manager::{Manager, ManagerConfig, ManagerJoinHandle, ShutdownTrigger},
     subsystem::Subsystem,
 };
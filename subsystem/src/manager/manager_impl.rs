@@ -24,7 +24,7 @@ use tokio::{
 use logging::log;
 use utils::{const_value::ConstValue, shallow_clone::ShallowClone};
 
-use crate::{task, Handle, ManagerConfig, SubmitOnlyHandle, Subsystem};
+use crate::{health::HealthMonitor, task, Handle, ManagerConfig, SubmitOnlyHandle, Subsystem};
 
 use super::shutdown_signal::shutdown_signal;
 
@@ -46,6 +46,9 @@ pub struct Manager {
 
     // List of subsystem tasks
     subsystems: Vec<SubsystemData<BoxFuture<'static, ()>>>,
+
+    // Liveness of the subsystems
+    health_monitor: HealthMonitor,
 }
 
 impl Manager {
@@ -65,6 +68,7 @@ impl Manager {
             shutting_down_tx,
             shutting_down_rx,
             subsystems,
+            health_monitor: HealthMonitor::new(),
         }
     }
 
@@ -98,6 +102,8 @@ impl Manager {
 
         log::info!("Registering subsystem {full_name}");
 
+        let heartbeat = self.health_monitor.register(full_name.clone());
+
         let task = Box::pin(task::subsystem(
             full_name.clone(),
             subsys_init,
@@ -105,6 +111,7 @@ impl Manager {
             action_rx,
             shutdown_rx,
             self.shutting_down_tx.clone(),
+            heartbeat,
         ));
 
         self.subsystems.push(SubsystemData {
@@ -134,6 +141,11 @@ impl Manager {
         self.add_subsystem(name, crate::wrappers::Direct::new(subsys))
     }
 
+    /// Get a monitor reporting the liveness of the subsystems managed by this manager.
+    pub fn health_monitor(&self) -> HealthMonitor {
+        self.health_monitor.clone()
+    }
+
     /// Create a trigger object that can be used to shut down the system
     pub fn make_shutdown_trigger(&self) -> ShutdownTrigger {
         ShutdownTrigger::new(&self.shutting_down_tx)
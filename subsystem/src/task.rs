@@ -24,7 +24,11 @@ use tracing::Instrument;
 use logging::log;
 use utils::{once_destructor::OnceDestructor, sync::Arc};
 
-use crate::{calls::Action, SubmitOnlyHandle, Subsystem};
+use crate::{
+    calls::Action,
+    health::{Heartbeat, HeartbeatTimer},
+    SubmitOnlyHandle, Subsystem,
+};
 
 /// Handle a task completion result
 pub fn handle_result(full_name: &str, task_type: &str, res: Result<(), tokio::task::JoinError>) {
@@ -45,6 +49,7 @@ pub async fn subsystem<S, IF, SF, E>(
     mut action_rx: mpsc::UnboundedReceiver<Action<S::Interface>>,
     mut shutdown_rx: oneshot::Receiver<()>,
     shutting_down_tx: mpsc::UnboundedSender<()>,
+    heartbeat: Heartbeat,
 ) where
     IF: FnOnce(SubmitOnlyHandle<S::Interface>) -> SF + Send + 'static,
     SF: std::future::IntoFuture<Output = Result<S, E>> + Send,
@@ -57,7 +62,9 @@ pub async fn subsystem<S, IF, SF, E>(
     // Make sure that we send the shutdown signal even in case of a panic.
     let _shutdown_sender = OnceDestructor::new({
         let full_name = &full_name;
+        let heartbeat = &heartbeat;
         move || {
+            heartbeat.stopped();
             let _ = shutting_down_tx.send(());
             log::info!("Subsystem {full_name} terminated");
         }
@@ -84,8 +91,13 @@ pub async fn subsystem<S, IF, SF, E>(
 
     log::info!("Subsystem {full_name} started");
 
+    // Wakes the event loop up periodically to keep the heartbeat fresh while idle.
+    let mut heartbeat_timer = HeartbeatTimer::new();
+
     // Main event loop
     loop {
+        heartbeat.beat();
+
         tokio::select! {
             // Process events in pre-determined order.
             biased;
@@ -122,6 +134,9 @@ pub async fn subsystem<S, IF, SF, E>(
             () = background_work_signal() => {
                 subsys.write().await.perform_background_work_unit();
             }
+
+            // Nothing to do, just refresh the heartbeat.
+            () = heartbeat_timer.tick() => {}
         }
     }
 
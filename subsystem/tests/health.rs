@@ -0,0 +1,97 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod helpers;
+mod sample_subsystems;
+
+use std::time::Duration;
+
+use sample_subsystems::Counter;
+use subsystem::{SubsystemState, HEARTBEAT_INTERVAL};
+
+const MAX_HEARTBEAT_AGE: Duration = Duration::from_secs(3 * HEARTBEAT_INTERVAL.as_secs());
+
+#[derive(Debug, thiserror::Error)]
+#[error("Initialization failed")]
+struct InitError;
+
+// A subsystem that never gets past initialization.
+struct Broken;
+
+#[async_trait::async_trait]
+impl subsystem::Subsystem for Broken {
+    type Interface = Self;
+
+    fn interface_ref(&self) -> &Self {
+        self
+    }
+
+    fn interface_mut(&mut self) -> &mut Self {
+        self
+    }
+}
+
+#[test]
+fn running_and_stopped_subsystems() {
+    let runtime = helpers::init_test_runtime();
+    runtime.block_on(async {
+        let mut app = subsystem::Manager::new("app");
+        let monitor = app.health_monitor();
+        let shutdown = app.make_shutdown_trigger();
+
+        let counter = app.add_direct_subsystem("counter", Counter::new());
+
+        let report = monitor.report();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].full_name(), "app/counter");
+        assert_eq!(report[0].state(), SubsystemState::Starting);
+        assert!(!report[0].is_healthy(MAX_HEARTBEAT_AGE));
+
+        let app = app.main_in_task();
+
+        // Once the call is served, the event loop is up and running.
+        assert_eq!(counter.call(|c| c.get()).await.unwrap(), 13);
+        let report = monitor.report();
+        assert_eq!(report[0].state(), SubsystemState::Running);
+        assert!(report[0].is_healthy(MAX_HEARTBEAT_AGE));
+
+        shutdown.initiate();
+        app.join().await;
+
+        let report = monitor.report();
+        assert_eq!(report[0].state(), SubsystemState::Stopped);
+        assert!(!report[0].is_healthy(MAX_HEARTBEAT_AGE));
+    })
+}
+
+#[test]
+fn failed_initialization_is_unhealthy() {
+    let runtime = helpers::init_test_runtime();
+    runtime.block_on(async {
+        let mut app = subsystem::Manager::new("app");
+        let monitor = app.health_monitor();
+
+        app.add_direct_subsystem("counter", Counter::new());
+        app.add_custom_subsystem("broken", |_| async { Result::<Broken, _>::Err(InitError) });
+
+        // The failed subsystem brings the whole manager down.
+        app.main().await;
+
+        let report = monitor.report();
+        assert_eq!(report.len(), 2);
+        assert!(report.iter().all(|health| health.state() == SubsystemState::Stopped));
+        assert!(report.iter().all(|health| !health.is_healthy(MAX_HEARTBEAT_AGE)));
+    })
+}
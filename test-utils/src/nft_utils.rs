@@ -36,10 +36,11 @@ pub fn random_token_issuance(chain_config: Arc<ChainConfig>, rng: &mut impl Rng)
     let max_ticker_len = chain_config.token_max_ticker_len();
     let max_dec_count = chain_config.token_max_dec_count();
     let max_uri_len = chain_config.token_max_uri_len();
+    let max_supply = chain_config.token_max_supply();
 
     TokenIssuance {
         token_ticker: random_string(rng, 1..max_ticker_len).as_bytes().to_vec(),
-        amount_to_issue: Amount::from_atoms(rng.gen_range(1..u128::MAX)),
+        amount_to_issue: Amount::from_atoms(rng.gen_range(1..=max_supply)),
         number_of_decimals: rng.gen_range(1..max_dec_count),
         metadata_uri: random_string(rng, 1..max_uri_len).as_bytes().to_vec(),
     }
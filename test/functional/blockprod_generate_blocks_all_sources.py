@@ -54,7 +54,7 @@ class GenerateBlocksFromAllSourcesTest(BitcoinTestFramework):
             [1000 for _ in range(20)],
         )
 
-        block = node.blockprod_generate_block(block_input_data, [generate_utxos], [], "LeaveEmptySpace")
+        block = node.blockprod_generate_block(block_input_data, [generate_utxos], [], "LeaveEmptySpace")['block']
         node.chainstate_submit_block(block)
         utxos = [(utxo_id, i) for i in range(20)]
 
@@ -145,7 +145,7 @@ class GenerateBlocksFromAllSourcesTest(BitcoinTestFramework):
                         transactions,
                         transaction_ids,
                         packing_strategy
-                    )
+                    )['block']
 
                     block = ScaleDecoder.get_decoder_class('BlockV1', ScaleBytes(bytearray.fromhex(block_hex))).decode()
 
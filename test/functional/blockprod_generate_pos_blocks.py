@@ -75,10 +75,10 @@ class GeneratePoSBlocksTest(BitcoinTestFramework):
         # Block production may fail if the Job Manager found a new tip, so try and sleep
         for _ in range(5):
             try:
-                block_hex = node.blockprod_generate_block(block_input_data, transactions, [], "LeaveEmptySpace")
+                block_hex = node.blockprod_generate_block(block_input_data, transactions, [], "LeaveEmptySpace")['block']
                 break
             except JSONRPCException:
-                block_hex = node.blockprod_generate_block(block_input_data, transactions, [], "LeaveEmptySpace")
+                block_hex = node.blockprod_generate_block(block_input_data, transactions, [], "LeaveEmptySpace")['block']
                 time.sleep(1)
 
         block_hex_array = bytearray.fromhex(block_hex)
@@ -84,7 +84,7 @@ class BlockprodIBDGenesisFailsButSkipSucceedsTest(BitcoinTestFramework):
 
         # Blockprod should succeed because we're skipping the initial block download check
 
-        block = node.blockprod_generate_block(block_input_data, [], [], "FillSpaceFromMempool")
+        block = node.blockprod_generate_block(block_input_data, [], [], "FillSpaceFromMempool")['block']
 
         assert(genesis_id != node.chainstate_best_block_id())
 
@@ -113,7 +113,7 @@ class BlockprodIBDGenesisSucceedsTest(BitcoinTestFramework):
 
         # Blockprod should succeed because we're no longer in initial block download
 
-        block = node.blockprod_generate_block(block_input_data, [], [], "FillSpaceFromMempool")
+        block = node.blockprod_generate_block(block_input_data, [], [], "FillSpaceFromMempool")['block']
         node.chainstate_submit_block(block)
 
         assert(genesis_id != node.chainstate_best_block_id())
@@ -84,11 +84,11 @@ class StakingIncentivesTest(BitcoinTestFramework):
         for _ in range(5):
             try:
                 block_hex = self.nodes[node_index].blockprod_generate_block(
-                    block_input_data, transactions)
+                    block_input_data, transactions)['block']
                 break
             except JSONRPCException:
                 block_hex = self.nodes[node_index].blockprod_generate_block(
-                    block_input_data, transactions)
+                    block_input_data, transactions)['block']
                 time.sleep(1)
         return block_hex
 
@@ -37,7 +37,7 @@ class RestartWithDifferentMagicBytes(BitcoinTestFramework):
             {"PoW": {"reward_destination": "AnyoneCanSpend"}}
         ).to_hex()[2:]
 
-        block = self.nodes[0].blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")
+        block = self.nodes[0].blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")['block']
         self.nodes[0].chainstate_submit_block(block)
 
         tip_height = self.nodes[0].chainstate_best_block_height()
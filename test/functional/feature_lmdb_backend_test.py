@@ -66,12 +66,12 @@ class ExampleTest(BitcoinTestFramework):
         ).to_hex()[2:]
 
         # add two blocks
-        block = node.blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")
+        block = node.blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")['block']
         blocks.append(block)
         node.chainstate_submit_block(blocks[0])
         self.wait_until(lambda: node.mempool_local_best_block_id() == node.chainstate_best_block_id(), timeout = 5)
 
-        block = node.blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")
+        block = node.blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")['block']
         blocks.append(block)
         node.chainstate_submit_block(blocks[1])
         self.wait_until(lambda: node.mempool_local_best_block_id() == node.chainstate_best_block_id(), timeout = 5)
@@ -88,7 +88,7 @@ class ExampleTest(BitcoinTestFramework):
 
         # Add three more blocks
         for i in range(2, 5):
-            block = node.blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")
+            block = node.blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")['block']
             blocks.append(block)
             node.chainstate_submit_block(blocks[i])
             self.wait_until(lambda: node.mempool_local_best_block_id() == node.chainstate_best_block_id(), timeout = 5)
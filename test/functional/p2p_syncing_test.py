@@ -74,7 +74,7 @@ class SyncingTest(BitcoinTestFramework):
         ).to_hex()[2:]
 
         # add first block
-        block = self.nodes[0].blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")
+        block = self.nodes[0].blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")['block']
         blocks.append(block)
         self.submit_block(blocks[0])
         assert_equal(self.block_height(0), 1)
@@ -82,7 +82,7 @@ class SyncingTest(BitcoinTestFramework):
         self.assert_tip(0, blocks[0])
 
         # add second block
-        block = self.nodes[0].blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")
+        block = self.nodes[0].blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")['block']
         blocks.append(block)
         self.submit_block(blocks[1])
         assert_equal(self.block_height(0), 2)
@@ -105,14 +105,14 @@ class SyncingTest(BitcoinTestFramework):
         self.assert_tip(1, blocks[1])
 
         # submit third block
-        block = self.nodes[0].blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")
+        block = self.nodes[0].blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")['block']
         blocks.append(block)
         self.submit_block(blocks[2])
         assert_equal(self.block_height(0), 3)
         self.assert_tip(0, blocks[2])
 
         # submit final block
-        block = self.nodes[0].blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")
+        block = self.nodes[0].blockprod_generate_block(block_input_data, [], [], "LeaveEmptySpace")['block']
         blocks.append(block)
         self.submit_block(blocks[3])
         assert_equal(self.block_height(0), 4)
@@ -103,10 +103,10 @@ class WalletSubmitTransaction(BitcoinTestFramework):
         # Block production may fail if the Job Manager found a new tip, so try and sleep
         for _ in range(5):
             try:
-                block_hex = self.nodes[0].blockprod_generate_block(block_input_data, transactions, [], fill_mode)
+                block_hex = self.nodes[0].blockprod_generate_block(block_input_data, transactions, [], fill_mode)['block']
                 break
             except JSONRPCException:
-                block_hex = self.nodes[0].blockprod_generate_block(block_input_data, transactions, [], fill_mode)
+                block_hex = self.nodes[0].blockprod_generate_block(block_input_data, transactions, [], fill_mode)['block']
                 time.sleep(1)
 
         block_hex_array = bytearray.fromhex(block_hex)
@@ -54,7 +54,7 @@ class WalletGetAddressUsage(BitcoinTestFramework):
         block_input_data = block_input_data_obj.encode(block_input_data).to_hex()[2:]
 
         # create a new block, taking transactions from mempool
-        block = node.blockprod_generate_block(block_input_data, [], [], "FillSpaceFromMempool")
+        block = node.blockprod_generate_block(block_input_data, [], [], "FillSpaceFromMempool")['block']
         node.chainstate_submit_block(block)
         block_id = node.chainstate_best_block_id()
 
@@ -19,7 +19,9 @@ use std::env;
 async fn main() -> Result<(), node_lib::Error> {
     let opts = node_lib::Options::from_args(env::args_os());
     node_lib::init_logging(&opts);
-    let node = node_lib::setup(opts).await?;
-    node.main().await;
+    match node_lib::setup(opts).await? {
+        node_lib::NodeSetupResult::Node(node) => node.main().await,
+        node_lib::NodeSetupResult::ConfigGenerated(_) => {}
+    }
     Ok(())
 }
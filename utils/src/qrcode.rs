@@ -23,6 +23,55 @@ pub enum QrCodeError {
     DataTooLong(usize),
     #[error("Border scaling failed. Possible very large border size: {0}")]
     BorderScalingFailed(u32),
+    #[error("The image scale must be at least 1")]
+    ZeroImageScale,
+    #[error("The image is too large for scale {0} and quiet zone {1}")]
+    ImageTooLarge(usize, usize),
+    #[error("PNG encoding failed: {0}")]
+    PngEncodingFailed(String),
+    #[error("Chunk size {0} is too small, it must be larger than the chunk header size {1}")]
+    ChunkSizeTooSmall(usize, usize),
+    #[error("Data of size {0} needs more than the maximum of {1} chunks")]
+    TooManyChunks(usize, usize),
+    #[error("No chunks given for reassembly")]
+    NoChunks,
+    #[error("Chunk of size {0} is too short to contain a header")]
+    ChunkTooShort(usize),
+    #[error("Chunk index {0} is out of range for a total of {1} chunks")]
+    ChunkIndexOutOfRange(u16, u16),
+    #[error("Chunks belong to different data")]
+    InconsistentChunks,
+    #[error("Chunk {0} is missing")]
+    MissingChunk(u16),
+    #[error("Checksum of the reassembled data doesn't match")]
+    ChecksumMismatch,
+}
+
+/// The error correction level of a QR code.
+///
+/// Higher levels allow recovering the data from a more damaged code,
+/// at the cost of a lower data capacity.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
+pub enum QrCodeErrorCorrection {
+    /// Tolerates about 7% erroneous codewords
+    Low,
+    /// Tolerates about 15% erroneous codewords
+    Medium,
+    /// Tolerates about 25% erroneous codewords
+    Quartile,
+    /// Tolerates about 30% erroneous codewords
+    High,
+}
+
+impl From<QrCodeErrorCorrection> for qrcodegen::QrCodeEcc {
+    fn from(ecc: QrCodeErrorCorrection) -> Self {
+        match ecc {
+            QrCodeErrorCorrection::Low => qrcodegen::QrCodeEcc::Low,
+            QrCodeErrorCorrection::Medium => qrcodegen::QrCodeEcc::Medium,
+            QrCodeErrorCorrection::Quartile => qrcodegen::QrCodeEcc::Quartile,
+            QrCodeErrorCorrection::High => qrcodegen::QrCodeEcc::High,
+        }
+    }
 }
 
 pub trait QrCode {
@@ -119,12 +168,14 @@ pub trait QrCode {
             "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" viewBox=\"0 0 {0} {0}\" stroke=\"none\">\n", dimension);
         result += "\t<rect width=\"100%\" height=\"100%\" fill=\"#FFFFFF\"/>\n";
         result += "\t<path d=\"";
+        let mut first_module = true;
         for y in 0..self.side_length() {
             for x in 0..self.side_length() {
                 if self.pixel_or_false(x, y) {
-                    if x != 0 || y != 0 {
+                    if !first_module {
                         result += " ";
                     }
+                    first_module = false;
                     result += &format!("M{},{}h1v1h-1z", x + border_size, y + border_size);
                 }
             }
@@ -133,6 +184,55 @@ pub trait QrCode {
         result += "</svg>\n";
         result
     }
+
+    /// Render the QR code to a grayscale PNG image, returned as the bytes of the PNG file.
+    ///
+    /// Every module becomes a square of `scale` x `scale` pixels, and the code is surrounded
+    /// by `quiet_zone` empty modules on each side (the QR specification recommends 4).
+    #[cfg(feature = "qrcode-png")]
+    fn to_png(&self, scale: usize, quiet_zone: usize) -> Result<Vec<u8>, QrCodeError> {
+        crate::ensure!(scale > 0, QrCodeError::ZeroImageScale);
+
+        let image_too_large = || QrCodeError::ImageTooLarge(scale, quiet_zone);
+        let modules = quiet_zone
+            .checked_mul(2)
+            .and_then(|border| border.checked_add(self.side_length()))
+            .ok_or_else(image_too_large)?;
+        let dimension = modules.checked_mul(scale).ok_or_else(image_too_large)?;
+        let dimension_u32 = u32::try_from(dimension).map_err(|_| image_too_large())?;
+        let pixel_count = dimension.checked_mul(dimension).ok_or_else(image_too_large)?;
+
+        const FILLED_PIXEL: u8 = 0x00;
+        const EMPTY_PIXEL: u8 = 0xFF;
+
+        let mut pixels = Vec::with_capacity(pixel_count);
+        for row in 0..dimension {
+            for column in 0..dimension {
+                // Coordinates in the quiet zone wrap around to huge values, which are out of bounds
+                let x = (column / scale).wrapping_sub(quiet_zone);
+                let y = (row / scale).wrapping_sub(quiet_zone);
+                pixels.push(if self.pixel_or_false(x, y) {
+                    FILLED_PIXEL
+                } else {
+                    EMPTY_PIXEL
+                });
+            }
+        }
+
+        let mut result = Vec::new();
+        let mut encoder = png::Encoder::new(&mut result, dimension_u32, dimension_u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| QrCodeError::PngEncodingFailed(e.to_string()))?;
+        writer
+            .write_image_data(&pixels)
+            .map_err(|e| QrCodeError::PngEncodingFailed(e.to_string()))?;
+        writer.finish().map_err(|e| QrCodeError::PngEncodingFailed(e.to_string()))?;
+
+        Ok(result)
+    }
 }
 
 impl QrCode for qrcodegen::QrCode {
@@ -149,11 +249,198 @@ impl QrCode for qrcodegen::QrCode {
     }
 }
 
-/// Constructs QR Code from a string
+/// Returns the maximum number of bytes of text that fit in a QR code with the given
+/// error correction level.
+///
+/// This is the byte mode capacity of the largest QR code version (40). Text consisting only
+/// of digits or upper case alphanumeric characters is encoded more compactly, so it may fit
+/// even if it's longer than this.
+#[must_use]
+pub fn max_capacity_for(ecc: QrCodeErrorCorrection) -> usize {
+    match ecc {
+        QrCodeErrorCorrection::Low => 2953,
+        QrCodeErrorCorrection::Medium => 2331,
+        QrCodeErrorCorrection::Quartile => 1663,
+        QrCodeErrorCorrection::High => 1273,
+    }
+}
+
+/// Returns how many more bytes of text can be added to text of `current_len` bytes
+/// before it no longer fits in a QR code with the given error correction level.
+///
+/// See [max_capacity_for] for the details.
+#[must_use]
+pub fn remaining_capacity(current_len: usize, ecc: QrCodeErrorCorrection) -> usize {
+    max_capacity_for(ecc).saturating_sub(current_len)
+}
+
+/// The size of the header that starts every chunk created by [data_to_chunks]:
+/// the chunk index and the total number of chunks as big endian `u16`s,
+/// followed by a checksum of the whole data.
+pub const CHUNK_HEADER_SIZE: usize = 8;
+
+const MAX_CHUNK_COUNT: usize = u16::MAX as usize;
+
+fn chunked_data_checksum(data: &[u8]) -> [u8; 4] {
+    let hash = crypto::hash::hash::<crypto::hash::Sha256, _>(data);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+struct ChunkHeader {
+    index: u16,
+    total: u16,
+    checksum: [u8; 4],
+}
+
+impl ChunkHeader {
+    fn encode(&self) -> [u8; CHUNK_HEADER_SIZE] {
+        let mut result = [0; CHUNK_HEADER_SIZE];
+        result[0..2].copy_from_slice(&self.index.to_be_bytes());
+        result[2..4].copy_from_slice(&self.total.to_be_bytes());
+        result[4..8].copy_from_slice(&self.checksum);
+        result
+    }
+
+    fn decode(chunk: &[u8]) -> Result<(Self, &[u8]), QrCodeError> {
+        crate::ensure!(
+            chunk.len() >= CHUNK_HEADER_SIZE,
+            QrCodeError::ChunkTooShort(chunk.len())
+        );
+        let (header, payload) = chunk.split_at(CHUNK_HEADER_SIZE);
+        let header = Self {
+            index: u16::from_be_bytes([header[0], header[1]]),
+            total: u16::from_be_bytes([header[2], header[3]]),
+            checksum: [header[4], header[5], header[6], header[7]],
+        };
+        crate::ensure!(
+            header.index < header.total,
+            QrCodeError::ChunkIndexOutOfRange(header.index, header.total)
+        );
+        Ok((header, payload))
+    }
+}
+
+/// Splits the data into chunks of at most `max_chunk` bytes each, including the header
+/// of [CHUNK_HEADER_SIZE] bytes, so that they can be transferred one by one
+/// (for example, as an animated sequence of QR codes) and joined with [reassemble_chunks].
+pub fn data_to_chunks(data: &[u8], max_chunk: usize) -> Result<Vec<Vec<u8>>, QrCodeError> {
+    crate::ensure!(
+        max_chunk > CHUNK_HEADER_SIZE,
+        QrCodeError::ChunkSizeTooSmall(max_chunk, CHUNK_HEADER_SIZE)
+    );
+    let payload_size = max_chunk - CHUNK_HEADER_SIZE;
+
+    // Empty data still produces a single chunk, so that it can be transferred too
+    let total = std::cmp::max((data.len() + payload_size - 1) / payload_size, 1);
+    let total = u16::try_from(total)
+        .map_err(|_| QrCodeError::TooManyChunks(data.len(), MAX_CHUNK_COUNT))?;
+    let checksum = chunked_data_checksum(data);
+
+    let chunks = (0..total)
+        .map(|index| {
+            let start = index as usize * payload_size;
+            let end = std::cmp::min(start + payload_size, data.len());
+            let header = ChunkHeader {
+                index,
+                total,
+                checksum,
+            };
+            [header.encode().as_slice(), &data[start..end]].concat()
+        })
+        .collect();
+
+    Ok(chunks)
+}
+
+/// Joins the chunks created by [data_to_chunks] back into the original data.
+///
+/// The chunks may be given in any order and may repeat, as happens when scanning
+/// a looping animated QR code, but all of them must be present.
+pub fn reassemble_chunks<I, T>(chunks: I) -> Result<Vec<u8>, QrCodeError>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut expected = None;
+    let mut payloads: Vec<Option<Vec<u8>>> = Vec::new();
+
+    for chunk in chunks {
+        let (header, payload) = ChunkHeader::decode(chunk.as_ref())?;
+
+        let (total, checksum) = *expected.get_or_insert((header.total, header.checksum));
+        crate::ensure!(
+            header.total == total && header.checksum == checksum,
+            QrCodeError::InconsistentChunks
+        );
+
+        if payloads.is_empty() {
+            payloads.resize(total as usize, None);
+        }
+        let slot = &mut payloads[header.index as usize];
+        if slot.is_none() {
+            *slot = Some(payload.to_vec());
+        }
+        crate::ensure!(
+            slot.as_deref() == Some(payload),
+            QrCodeError::InconsistentChunks
+        );
+    }
+
+    let (_, checksum) = expected.ok_or(QrCodeError::NoChunks)?;
+
+    let mut data = Vec::new();
+    for (index, payload) in payloads.into_iter().enumerate() {
+        let payload = payload.ok_or(QrCodeError::MissingChunk(index as u16))?;
+        data.extend_from_slice(&payload);
+    }
+
+    crate::ensure!(
+        chunked_data_checksum(&data) == checksum,
+        QrCodeError::ChecksumMismatch
+    );
+
+    Ok(data)
+}
+
+/// Constructs a sequence of QR codes from binary data that may be too large for a single
+/// QR code, using the medium error correction level.
+///
+/// Every QR code contains a chunk of at most `max_chunk` bytes, see [data_to_chunks].
+/// The data from the scanned QR codes can be joined with [reassemble_chunks].
+pub fn qrcodes_from_data_chunked(
+    data: &[u8],
+    max_chunk: usize,
+) -> Result<Vec<impl QrCode>, QrCodeError> {
+    qrcodes_from_data_chunked_with_ecc(data, max_chunk, QrCodeErrorCorrection::Medium)
+}
+
+/// Constructs a sequence of QR codes from binary data that may be too large for a single
+/// QR code, using the given error correction level
+pub fn qrcodes_from_data_chunked_with_ecc(
+    data: &[u8],
+    max_chunk: usize,
+    ecc: QrCodeErrorCorrection,
+) -> Result<Vec<impl QrCode>, QrCodeError> {
+    data_to_chunks(data, max_chunk)?
+        .into_iter()
+        .map(|chunk| {
+            qrcodegen::QrCode::encode_binary(&chunk, ecc.into())
+                .map_err(|_| QrCodeError::DataTooLong(chunk.len()))
+        })
+        .collect()
+}
+
+/// Constructs QR Code from a string, using the medium error correction level
 pub fn qrcode_from_str<S: AsRef<str>>(s: S) -> Result<impl QrCode, QrCodeError> {
-    let errcorlvl = qrcodegen::QrCodeEcc::Medium; // Error correction level
+    qrcode_from_str_with_ecc(s, QrCodeErrorCorrection::Medium)
+}
 
-    let qr = qrcodegen::QrCode::encode_text(s.as_ref(), errcorlvl)
+/// Constructs QR Code from a string, using the given error correction level
+pub fn qrcode_from_str_with_ecc<S: AsRef<str>>(
+    s: S,
+    ecc: QrCodeErrorCorrection,
+) -> Result<impl QrCode, QrCodeError> {
+    let qr = qrcodegen::QrCode::encode_text(s.as_ref(), ecc.into())
         .map_err(|_| QrCodeError::DataTooLong(s.as_ref().len()))?;
 
     Ok(qr)
@@ -246,10 +533,293 @@ mod tests {
         test_string_qrcode(&qr);
     }
 
+    #[test]
+    fn ecc_levels() {
+        let text =
+            "abandon ability able about above absent absorb abstract absurd abuse access accident";
+
+        let side_lengths = [
+            QrCodeErrorCorrection::Low,
+            QrCodeErrorCorrection::Medium,
+            QrCodeErrorCorrection::Quartile,
+            QrCodeErrorCorrection::High,
+        ]
+        .into_iter()
+        .map(|ecc| super::qrcode_from_str_with_ecc(text, ecc).unwrap().side_length())
+        .collect::<Vec<_>>();
+
+        assert!(side_lengths.windows(2).all(|w| w[0] <= w[1]));
+        assert!(side_lengths[0] < side_lengths[3]);
+
+        assert_eq!(
+            super::qrcode_from_str(text).unwrap().as_vec(),
+            super::qrcode_from_str_with_ecc(text, QrCodeErrorCorrection::Medium)
+                .unwrap()
+                .as_vec()
+        );
+    }
+
+    #[test]
+    fn ecc_data_too_long() {
+        // The maximum capacity in byte mode is 2953 bytes for the low level and 1273 bytes
+        // for the high level
+        let text = "a".repeat(2000);
+        let _ = super::qrcode_from_str_with_ecc(&text, QrCodeErrorCorrection::Low).unwrap();
+        assert_eq!(
+            super::qrcode_from_str_with_ecc(&text, QrCodeErrorCorrection::High).err(),
+            Some(QrCodeError::DataTooLong(text.len()))
+        );
+    }
+
+    #[test]
+    fn max_capacity() {
+        for ecc in [
+            QrCodeErrorCorrection::Low,
+            QrCodeErrorCorrection::Medium,
+            QrCodeErrorCorrection::Quartile,
+            QrCodeErrorCorrection::High,
+        ] {
+            let capacity = super::max_capacity_for(ecc);
+
+            // Lower case letters are encoded in byte mode
+            let text = "a".repeat(capacity);
+            let _ = super::qrcode_from_str_with_ecc(&text, ecc).unwrap();
+
+            let text = "a".repeat(capacity + 1);
+            assert_eq!(
+                super::qrcode_from_str_with_ecc(&text, ecc).err(),
+                Some(QrCodeError::DataTooLong(text.len()))
+            );
+        }
+
+        assert!(
+            super::max_capacity_for(QrCodeErrorCorrection::Low)
+                > super::max_capacity_for(QrCodeErrorCorrection::High)
+        );
+    }
+
+    #[test]
+    fn remaining_capacity() {
+        let ecc = QrCodeErrorCorrection::Medium;
+        let capacity = super::max_capacity_for(ecc);
+
+        assert_eq!(super::remaining_capacity(0, ecc), capacity);
+        assert_eq!(super::remaining_capacity(capacity - 12, ecc), 12);
+        assert_eq!(super::remaining_capacity(capacity, ecc), 0);
+        assert_eq!(super::remaining_capacity(capacity + 1, ecc), 0);
+
+        // The reported remaining capacity can be filled up exactly
+        let text = "memo: ".to_owned();
+        let remaining = super::remaining_capacity(text.len(), ecc);
+        let text = text + &"x".repeat(remaining);
+        let _ = super::qrcode_from_str_with_ecc(&text, ecc).unwrap();
+        assert!(super::qrcode_from_str_with_ecc(text + "x", ecc).is_err());
+    }
+
+    #[test]
+    fn chunked_data_round_trip() {
+        let data = (0..10_000u32).map(|i| (i * 7 % 251) as u8).collect::<Vec<_>>();
+        let max_chunk = 500;
+
+        let chunks = super::data_to_chunks(&data, max_chunk).unwrap();
+        assert_eq!(
+            chunks.len(),
+            (data.len() + max_chunk - CHUNK_HEADER_SIZE - 1) / (max_chunk - CHUNK_HEADER_SIZE)
+        );
+        assert!(chunks.iter().all(|chunk| chunk.len() <= max_chunk));
+        assert_eq!(super::reassemble_chunks(&chunks).unwrap(), data);
+
+        // Order doesn't matter and repeated chunks are ignored
+        let shuffled = chunks.iter().rev().chain(chunks.iter().take(3));
+        assert_eq!(super::reassemble_chunks(shuffled).unwrap(), data);
+
+        // Every chunk fits in a QR code
+        let qrs = super::qrcodes_from_data_chunked(&data, max_chunk).unwrap();
+        assert_eq!(qrs.len(), chunks.len());
+
+        // Data larger than a single QR code can be transferred in chunks
+        let capacity = super::max_capacity_for(QrCodeErrorCorrection::High);
+        let large_data = vec![0xAB; 3 * capacity];
+        let qrs = super::qrcodes_from_data_chunked_with_ecc(
+            &large_data,
+            capacity,
+            QrCodeErrorCorrection::High,
+        )
+        .unwrap();
+        assert_eq!(qrs.len(), 4);
+    }
+
+    #[test]
+    fn chunked_data_edge_cases() {
+        let chunks = super::data_to_chunks(&[], 100).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(super::reassemble_chunks(&chunks).unwrap(), Vec::<u8>::new());
+
+        let data = [1, 2, 3];
+        let chunks = super::data_to_chunks(&data, CHUNK_HEADER_SIZE + 3).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(super::reassemble_chunks(&chunks).unwrap(), data);
+
+        assert_eq!(
+            super::data_to_chunks(&data, CHUNK_HEADER_SIZE),
+            Err(QrCodeError::ChunkSizeTooSmall(
+                CHUNK_HEADER_SIZE,
+                CHUNK_HEADER_SIZE
+            ))
+        );
+        let large_data = vec![0; MAX_CHUNK_COUNT + 1];
+        assert_eq!(
+            super::data_to_chunks(&large_data, CHUNK_HEADER_SIZE + 1),
+            Err(QrCodeError::TooManyChunks(
+                large_data.len(),
+                MAX_CHUNK_COUNT
+            ))
+        );
+    }
+
+    #[test]
+    fn chunked_data_reassembly_errors() {
+        let data = (0..100u8).collect::<Vec<_>>();
+        let chunks = super::data_to_chunks(&data, CHUNK_HEADER_SIZE + 10).unwrap();
+
+        assert_eq!(
+            super::reassemble_chunks(Vec::<Vec<u8>>::new()),
+            Err(QrCodeError::NoChunks)
+        );
+        assert_eq!(
+            super::reassemble_chunks([&chunks[0][..CHUNK_HEADER_SIZE - 1]]),
+            Err(QrCodeError::ChunkTooShort(CHUNK_HEADER_SIZE - 1))
+        );
+        assert_eq!(
+            super::reassemble_chunks(&chunks[1..]),
+            Err(QrCodeError::MissingChunk(0))
+        );
+
+        let mut out_of_range = chunks[0].clone();
+        out_of_range[0..2].copy_from_slice(&10u16.to_be_bytes());
+        assert_eq!(
+            super::reassemble_chunks([out_of_range]),
+            Err(QrCodeError::ChunkIndexOutOfRange(10, 10))
+        );
+
+        let other_chunks = super::data_to_chunks(&[0; 100], CHUNK_HEADER_SIZE + 10).unwrap();
+        assert_eq!(
+            super::reassemble_chunks(chunks.iter().take(5).chain(other_chunks.iter().skip(5))),
+            Err(QrCodeError::InconsistentChunks)
+        );
+
+        let mut corrupted = chunks.clone();
+        corrupted[3][CHUNK_HEADER_SIZE] ^= 1;
+        assert_eq!(
+            super::reassemble_chunks(&corrupted),
+            Err(QrCodeError::ChecksumMismatch)
+        );
+    }
+
+    struct TestQrCode {
+        side_length: usize,
+        pixels: Vec<bool>,
+    }
+
+    impl QrCode for TestQrCode {
+        fn side_length(&self) -> usize {
+            self.side_length
+        }
+
+        fn pixel(&self, x: usize, y: usize) -> Option<bool> {
+            if x < self.side_length && y < self.side_length {
+                Some(self.pixels[y * self.side_length + x])
+            } else {
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn svg_known_code() {
+        let qr = TestQrCode {
+            side_length: 2,
+            pixels: vec![false, true, true, true],
+        };
+
+        let expected = concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<!DOCTYPE svg PUBLIC \"-//W3C//DTD SVG 1.1//EN\" \"http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd\">\n",
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" viewBox=\"0 0 8 8\" stroke=\"none\">\n",
+            "\t<rect width=\"100%\" height=\"100%\" fill=\"#FFFFFF\"/>\n",
+            "\t<path d=\"M4,3h1v1h-1z M3,4h1v1h-1z M4,4h1v1h-1z\" fill=\"#000000\"/>\n",
+            "</svg>\n",
+        );
+        assert_eq!(qr.encode_to_svg_string(3), expected);
+    }
+
     #[test]
     fn svg_attempt_str() {
         let text: &'static str = "Hello, world!";
         let qr = super::qrcode_from_str(text).unwrap();
         let _svg = qr.encode_to_svg_string(20);
     }
+
+    #[cfg(feature = "qrcode-png")]
+    #[rstest::rstest]
+    #[case(1, 0)]
+    #[case(1, 4)]
+    #[case(3, 0)]
+    #[case(5, 2)]
+    fn png_decodes_back_to_the_same_code(#[case] scale: usize, #[case] quiet_zone: usize) {
+        let text: &'static str = "Hello, world!";
+        let qr = super::qrcode_from_str(text).unwrap();
+        let png_bytes = qr.to_png(scale, quiet_zone).unwrap();
+
+        let decoder = png::Decoder::new(png_bytes.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(info.color_type, png::ColorType::Grayscale);
+        assert_eq!(info.bit_depth, png::BitDepth::Eight);
+
+        let modules = qr.side_length() + 2 * quiet_zone;
+        let dimension = modules * scale;
+        assert_eq!(info.width as usize, dimension);
+        assert_eq!(info.height as usize, dimension);
+
+        // Every pixel of a module must have the same color
+        let module_at = |x: usize, y: usize| {
+            let color = pixels[y * scale * dimension + x * scale];
+            for dy in 0..scale {
+                for dx in 0..scale {
+                    let pixel = pixels[(y * scale + dy) * dimension + x * scale + dx];
+                    assert_eq!(pixel, color, "module ({x}, {y}) is not uniform");
+                }
+            }
+            color == 0x00
+        };
+
+        // The quiet zone is empty
+        for y in 0..modules {
+            for x in 0..modules {
+                let in_code = (quiet_zone..quiet_zone + qr.side_length()).contains(&x)
+                    && (quiet_zone..quiet_zone + qr.side_length()).contains(&y);
+                if !in_code {
+                    assert!(
+                        !module_at(x, y),
+                        "module ({x}, {y}) in the quiet zone is filled"
+                    );
+                }
+            }
+        }
+
+        let decoded = (quiet_zone..quiet_zone + qr.side_length())
+            .flat_map(|y| (quiet_zone..quiet_zone + qr.side_length()).map(move |x| (x, y)))
+            .map(|(x, y)| module_at(x, y))
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, super::qrcode_from_str(text).unwrap().as_vec());
+    }
+
+    #[cfg(feature = "qrcode-png")]
+    #[test]
+    fn png_zero_scale() {
+        let qr = super::qrcode_from_str("Hello, world!").unwrap();
+        assert_eq!(qr.to_png(0, 4), Err(QrCodeError::ZeroImageScale));
+    }
 }
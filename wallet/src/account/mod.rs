@@ -23,6 +23,7 @@ use common::chain::AccountSpending::{self, Delegation};
 use common::primitives::id::WithId;
 use common::primitives::Idable;
 use common::Uint256;
+use crypto::key::extended::{ExtendedPrivateKey, ExtendedPublicKey};
 use crypto::key::hdkd::child_number::ChildNumber;
 use mempool::FeeRate;
 use utils::ensure;
@@ -390,6 +391,29 @@ impl Account {
         Ok(tx)
     }
 
+    /// Same as `process_send_request` but the transaction is returned without signatures
+    pub fn process_send_request_unsigned(
+        &mut self,
+        db_tx: &mut impl WalletStorageWriteLocked,
+        request: SendRequest,
+        inputs: Vec<UtxoOutPoint>,
+        median_time: BlockTimestamp,
+        current_fee_rate: FeeRate,
+        consolidate_fee_rate: FeeRate,
+    ) -> WalletResult<Transaction> {
+        let request = self.select_inputs_for_send_request(
+            request,
+            inputs,
+            db_tx,
+            median_time,
+            current_fee_rate,
+            consolidate_fee_rate,
+        )?;
+
+        let (tx, _utxos) = request.into_transaction_and_utxos()?;
+        Ok(tx)
+    }
+
     pub fn decommission_stake_pool(
         &mut self,
         db_tx: &mut impl WalletStorageWriteUnlocked,
@@ -726,6 +750,17 @@ impl Account {
     }
 
     /// Get the id of this account
+    pub fn account_public_key(&self) -> &ExtendedPublicKey {
+        self.key_chain.account_public_key()
+    }
+
+    pub fn account_private_key(
+        &self,
+        db_tx: &impl WalletStorageReadUnlocked,
+    ) -> WalletResult<ExtendedPrivateKey> {
+        Ok(self.key_chain.derive_account_private_key(db_tx)?)
+    }
+
     pub fn get_account_id(&self) -> AccountId {
         self.key_chain.get_account_id()
     }
@@ -1230,7 +1265,10 @@ fn group_outputs<T, Grouped: Clone>(
 
                         combiner(total_token_amount, &output, token_transfer.amount)?;
                     }
-                    TokenData::TokenIssuance(_) | TokenData::NftIssuance(_) => {}
+                    TokenData::TokenIssuance(_)
+                    | TokenData::NftIssuance(_)
+                    | TokenData::TokenFreezeV1(_)
+                    | TokenData::TokenUnfreezeV1(_) => {}
                 }
             }
         }
@@ -1296,6 +1334,7 @@ fn group_utxos_for_input<T, Grouped: Clone>(
 
                         combiner(total_token_amount, &output, Amount::from_atoms(1))?;
                     }
+                    TokenData::TokenFreezeV1(_) | TokenData::TokenUnfreezeV1(_) => {}
                 }
             }
         }
@@ -74,6 +74,7 @@ impl OutputGroup {
                     TokenData::TokenTransfer(token_transfer) => token_transfer.amount,
                     TokenData::TokenIssuance(token_issuance) => token_issuance.amount_to_issue,
                     TokenData::NftIssuance(_) => Amount::from_atoms(1),
+                    TokenData::TokenFreezeV1(_) | TokenData::TokenUnfreezeV1(_) => Amount::ZERO,
                 }
             }
         };
@@ -29,6 +29,7 @@ use crypto::vrf::ExtendedVRFPrivateKey;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use utils::const_value::ConstValue;
+use utils::ensure;
 use wallet_storage::{
     WalletStorageReadLocked, WalletStorageReadUnlocked, WalletStorageWriteLocked,
 };
@@ -65,7 +66,31 @@ impl AccountKeyChain {
 
         let account_privkey = root_key.derive_absolute_path(&account_path)?;
 
-        let account_pubkey = account_privkey.to_public_key();
+        Self::new_from_public_key(
+            chain_config,
+            db_tx,
+            account_privkey.to_public_key(),
+            lookahead_size,
+        )
+    }
+
+    /// Create a key chain from an account public key without access to any private keys.
+    /// The account index is taken from the derivation path of the key.
+    pub fn new_from_public_key(
+        chain_config: Arc<ChainConfig>,
+        db_tx: &mut impl WalletStorageWriteLocked,
+        account_pubkey: ExtendedPublicKey,
+        lookahead_size: u32,
+    ) -> KeyChainResult<AccountKeyChain> {
+        let account_path = account_pubkey.get_derivation_path().clone();
+        let account_index = match account_path.as_slice() {
+            [_, _, account_index] => U31::from_u32_with_msb(account_index.into_encoded_index()).0,
+            _ => return Err(KeyChainError::InvalidBip44DerivationPath(account_path)),
+        };
+        ensure!(
+            account_path == make_account_path(&chain_config, account_index),
+            KeyChainError::InvalidBip44DerivationPath(account_path)
+        );
 
         let account_id = AccountId::new_from_xpub(&account_pubkey);
 
@@ -104,7 +129,7 @@ impl AccountKeyChain {
         Ok(new_account)
     }
 
-    fn derive_account_private_key(
+    pub fn derive_account_private_key(
         &self,
         db_tx: &impl WalletStorageReadUnlocked,
     ) -> KeyChainResult<ExtendedPrivateKey> {
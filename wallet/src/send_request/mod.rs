@@ -16,6 +16,7 @@
 use common::address::Address;
 use common::chain::output_value::OutputValue;
 use common::chain::stakelock::StakePoolData;
+use common::chain::timelock::OutputTimeLock;
 use common::chain::timelock::OutputTimeLock::ForBlockCount;
 use common::chain::tokens::{
     Metadata, NftIssuance, TokenData, TokenId, TokenIssuance, TokenTransfer,
@@ -54,6 +55,22 @@ pub fn make_address_output(
     Ok(TxOutput::Transfer(OutputValue::Coin(amount), destination))
 }
 
+/// Make an output that sends coins to the address, which can only be spent after the timelock
+pub fn make_address_output_with_timelock(
+    chain_config: &ChainConfig,
+    address: Address<Destination>,
+    amount: Amount,
+    timelock: OutputTimeLock,
+) -> WalletResult<TxOutput> {
+    let destination = address.decode_object(chain_config)?;
+
+    Ok(TxOutput::LockThenTransfer(
+        OutputValue::Coin(amount),
+        destination,
+        timelock,
+    ))
+}
+
 pub fn make_address_output_token(
     chain_config: &ChainConfig,
     address: Address<Destination>,
@@ -75,6 +92,7 @@ pub fn make_issue_token_outputs(
     address: Address<Destination>,
     token_issuance: TokenIssuance,
     chain_config: &ChainConfig,
+    block_height: BlockHeight,
 ) -> WalletResult<Vec<TxOutput>> {
     let destination = address.decode_object(chain_config)?;
 
@@ -84,6 +102,7 @@ pub fn make_issue_token_outputs(
         &token_issuance.amount_to_issue,
         &token_issuance.number_of_decimals,
         &token_issuance.metadata_uri,
+        block_height,
     )?;
 
     let issuance_output = TxOutput::Transfer(
@@ -101,12 +120,13 @@ pub fn make_issue_nft_outputs(
     address: Address<Destination>,
     nft_metadata: Metadata,
     chain_config: &ChainConfig,
+    block_height: BlockHeight,
 ) -> WalletResult<Vec<TxOutput>> {
     let destination = address.decode_object(chain_config)?;
     let nft_issuance = Box::new(NftIssuance {
         metadata: nft_metadata,
     });
-    chainstate::check_nft_issuance_data(chain_config, &nft_issuance)?;
+    chainstate::check_nft_issuance_data(chain_config, &nft_issuance, block_height)?;
 
     let issuance_output = TxOutput::Transfer(
         OutputValue::Token(Box::new(TokenData::NftIssuance(nft_issuance))),
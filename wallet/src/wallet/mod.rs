@@ -19,7 +19,7 @@ use std::sync::Arc;
 
 use crate::account::transaction_list::TransactionList;
 use crate::account::{Currency, DelegationData, UtxoSelectorError};
-use crate::key_chain::{KeyChainError, MasterKeyChain};
+use crate::key_chain::{AccountKeyChain, KeyChainError, MasterKeyChain, LOOKAHEAD_SIZE};
 use crate::send_request::{
     make_issue_nft_outputs, make_issue_token_outputs, StakePoolDataArguments,
 };
@@ -37,6 +37,7 @@ use common::chain::{
 use common::primitives::id::WithId;
 use common::primitives::{Amount, BlockHeight, Id};
 use consensus::PoSGenerateBlockInputData;
+use crypto::key::extended::{ExtendedPrivateKey, ExtendedPublicKey};
 use crypto::key::hdkd::child_number::ChildNumber;
 use crypto::key::hdkd::u31::U31;
 use crypto::key::PublicKey;
@@ -137,6 +138,10 @@ pub enum WalletError {
     ConsumedUtxo(UtxoOutPoint),
     #[error("Selected UTXO is still locked")]
     LockedUtxo(UtxoOutPoint),
+    #[error("The wallet is watch-only and has no private keys")]
+    WatchOnlyWallet,
+    #[error("The wallet password is required")]
+    PasswordRequired,
 }
 
 /// Result type used for the wallet
@@ -148,13 +153,16 @@ pub struct Wallet<B: storage::Backend> {
     key_chain: MasterKeyChain,
     accounts: BTreeMap<U31, Account>,
     latest_median_time: BlockTimestamp,
-    next_unused_account: (U31, Account),
+    /// The next account that is scanned for transactions before it is used.
+    /// Watch-only wallets can't derive new accounts, so they don't have one.
+    next_unused_account: Option<(U31, Account)>,
+    watch_only: bool,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
 pub struct WalletSyncingState {
     pub account_best_blocks: BTreeMap<U31, (Id<GenBlock>, BlockHeight)>,
-    pub unused_account_best_block: (Id<GenBlock>, BlockHeight),
+    pub unused_account_best_block: Option<(Id<GenBlock>, BlockHeight)>,
 }
 
 pub fn open_or_create_wallet_file<P: AsRef<Path>>(path: P) -> WalletResult<Store<DefaultBackend>> {
@@ -238,7 +246,49 @@ impl<B: storage::Backend> Wallet<B> {
             key_chain,
             accounts: [default_account].into(),
             latest_median_time,
-            next_unused_account,
+            next_unused_account: Some(next_unused_account),
+            watch_only: false,
+        };
+
+        Ok(wallet)
+    }
+
+    /// Create a wallet that watches the account of the provided account public key.
+    /// The wallet doesn't store any private keys, so it can compute balances and create
+    /// unsigned transactions, but it can't sign them.
+    /// The blockchain needs to be scanned from the genesis to find the account's transactions.
+    pub fn create_watch_only_wallet(
+        chain_config: Arc<ChainConfig>,
+        db: Store<B>,
+        account_public_key: ExtendedPublicKey,
+    ) -> WalletResult<Self> {
+        let mut db_tx = db.transaction_rw(None)?;
+
+        db_tx.set_storage_version(CURRENT_WALLET_VERSION)?;
+        db_tx.set_chain_info(&ChainInfo::new(chain_config.as_ref()))?;
+        db_tx.set_watch_only(true)?;
+
+        let key_chain = MasterKeyChain::new_from_existing_database(chain_config.clone(), &db_tx)?;
+
+        let account_key_chain = AccountKeyChain::new_from_public_key(
+            chain_config.clone(),
+            &mut db_tx,
+            account_public_key,
+            LOOKAHEAD_SIZE,
+        )?;
+        let account = Account::new(chain_config.clone(), &mut db_tx, account_key_chain, None)?;
+
+        db_tx.commit()?;
+
+        let latest_median_time = chain_config.genesis_block().timestamp();
+        let wallet = Wallet {
+            chain_config,
+            db,
+            key_chain,
+            accounts: [(account.account_index(), account)].into(),
+            latest_median_time,
+            next_unused_account: None,
+            watch_only: true,
         };
 
         Ok(wallet)
@@ -407,9 +457,16 @@ impl<B: storage::Backend> Wallet<B> {
         let latest_median_time =
             db_tx.get_median_time()?.unwrap_or(chain_config.genesis_block().timestamp());
 
+        let watch_only = db_tx.is_watch_only()?;
+
         db_tx.close();
 
-        let next_unused_account = accounts.pop_last().ok_or(WalletError::WalletNotInitialized)?;
+        let next_unused_account = if watch_only {
+            ensure!(!accounts.is_empty(), WalletError::WalletNotInitialized);
+            None
+        } else {
+            Some(accounts.pop_last().ok_or(WalletError::WalletNotInitialized)?)
+        };
 
         Ok(Wallet {
             chain_config,
@@ -418,6 +475,7 @@ impl<B: storage::Backend> Wallet<B> {
             accounts,
             latest_median_time,
             next_unused_account,
+            watch_only,
         })
     }
 
@@ -441,6 +499,15 @@ impl<B: storage::Backend> Wallet<B> {
         self.db.is_locked()
     }
 
+    pub fn is_watch_only(&self) -> bool {
+        self.watch_only
+    }
+
+    fn ensure_not_watch_only(&self) -> WalletResult<()> {
+        ensure!(!self.watch_only, WalletError::WatchOnlyWallet);
+        Ok(())
+    }
+
     pub fn encrypt_wallet(&mut self, password: &Option<String>) -> WalletResult<()> {
         self.db.encrypt_private_keys(password).map_err(WalletError::from)
     }
@@ -504,10 +571,13 @@ impl<B: storage::Backend> Wallet<B> {
             WalletError::EmptyAccountName
         );
 
-        let next_account_index =
-            self.next_unused_account.0.plus_one().map_err(|_| {
-                WalletError::AbsoluteMaxNumAccountsExceeded(self.next_unused_account.0)
-            })?;
+        let unused_account =
+            self.next_unused_account.as_mut().ok_or(WalletError::WatchOnlyWallet)?;
+
+        let next_account_index = unused_account
+            .0
+            .plus_one()
+            .map_err(|_| WalletError::AbsoluteMaxNumAccountsExceeded(unused_account.0))?;
 
         let mut db_tx = self.db.transaction_rw_unlocked(None)?;
 
@@ -519,19 +589,17 @@ impl<B: storage::Backend> Wallet<B> {
             None,
         )?;
 
-        self.next_unused_account.1.set_name(name.clone(), &mut db_tx)?;
-        std::mem::swap(&mut self.next_unused_account, &mut next_unused_account);
+        unused_account.1.set_name(name.clone(), &mut db_tx)?;
+        std::mem::swap(unused_account, &mut next_unused_account);
         let (next_account_index, next_account) = next_unused_account;
 
         // no need to rescan the blockchain from the start for the next unused account as we have been
         // scanning for addresses of the previous next unused account and it is not allowed to create a gap in
         // the account indexes
         let (best_block_id, best_block_height) = next_account.best_block();
-        self.next_unused_account.1.update_best_block(
-            &mut db_tx,
-            best_block_height,
-            best_block_id,
-        )?;
+        unused_account
+            .1
+            .update_best_block(&mut db_tx, best_block_height, best_block_id)?;
 
         db_tx.commit()?;
 
@@ -720,7 +788,28 @@ impl<B: storage::Backend> Wallet<B> {
         Ok(account.get_addresses_usage())
     }
 
+    pub fn account_public_key(&self, account_index: U31) -> WalletResult<&ExtendedPublicKey> {
+        Ok(self.get_account(account_index)?.account_public_key())
+    }
+
+    /// Returns the extended private key of the account, so it can be exported.
+    /// If the wallet is encrypted, the password is checked even if the wallet is unlocked
+    pub fn export_account_private_key(
+        &self,
+        account_index: U31,
+        password: Option<&String>,
+    ) -> WalletResult<ExtendedPrivateKey> {
+        self.ensure_not_watch_only()?;
+        if self.is_encrypted() {
+            let password = password.ok_or(WalletError::PasswordRequired)?;
+            self.db.check_private_keys_password(password)?;
+        }
+        let db_tx = self.db.transaction_ro_unlocked()?;
+        self.get_account(account_index)?.account_private_key(&db_tx)
+    }
+
     pub fn get_vrf_public_key(&mut self, account_index: U31) -> WalletResult<VRFPublicKey> {
+        self.ensure_not_watch_only()?;
         let db_tx = self.db.transaction_ro_unlocked()?;
         self.get_account(account_index)?.get_vrf_public_key(&db_tx)
     }
@@ -749,6 +838,7 @@ impl<B: storage::Backend> Wallet<B> {
         current_fee_rate: FeeRate,
         consolidate_fee_rate: FeeRate,
     ) -> WalletResult<SignedTransaction> {
+        self.ensure_not_watch_only()?;
         let request = SendRequest::new().with_outputs(outputs);
         let latest_median_time = self.latest_median_time;
         self.for_account_rw_unlocked(account_index, |account, db_tx| {
@@ -764,6 +854,31 @@ impl<B: storage::Backend> Wallet<B> {
         })
     }
 
+    /// Same as `create_transaction_to_addresses` but the transaction is returned without
+    /// signatures, so it can also be created by watch-only wallets
+    pub fn create_unsigned_transaction_to_addresses(
+        &mut self,
+        account_index: U31,
+        outputs: impl IntoIterator<Item = TxOutput>,
+        inputs: impl IntoIterator<Item = UtxoOutPoint>,
+        current_fee_rate: FeeRate,
+        consolidate_fee_rate: FeeRate,
+    ) -> WalletResult<Transaction> {
+        let request = SendRequest::new().with_outputs(outputs);
+        let latest_median_time = self.latest_median_time;
+        self.for_account_rw_unlocked(account_index, |account, db_tx| {
+            let inputs = inputs.into_iter().collect();
+            account.process_send_request_unsigned(
+                db_tx,
+                request,
+                inputs,
+                latest_median_time,
+                current_fee_rate,
+                consolidate_fee_rate,
+            )
+        })
+    }
+
     pub fn create_transaction_to_addresses_from_delegation(
         &mut self,
         account_index: U31,
@@ -773,6 +888,7 @@ impl<B: storage::Backend> Wallet<B> {
         delegation_share: Amount,
         current_fee_rate: FeeRate,
     ) -> WalletResult<SignedTransaction> {
+        self.ensure_not_watch_only()?;
         self.for_account_rw_unlocked(account_index, |account, db_tx| {
             account.spend_from_delegation(
                 db_tx,
@@ -818,8 +934,13 @@ impl<B: storage::Backend> Wallet<B> {
         current_fee_rate: FeeRate,
         consolidate_fee_rate: FeeRate,
     ) -> WalletResult<(TokenId, SignedTransaction)> {
-        let outputs =
-            make_issue_token_outputs(address, token_issuance, self.chain_config.as_ref())?;
+        let (_, best_block_height) = self.get_best_block_for_account(account_index)?;
+        let outputs = make_issue_token_outputs(
+            address,
+            token_issuance,
+            self.chain_config.as_ref(),
+            best_block_height.next_height(),
+        )?;
 
         let tx = self.create_transaction_to_addresses(
             account_index,
@@ -840,7 +961,13 @@ impl<B: storage::Backend> Wallet<B> {
         current_fee_rate: FeeRate,
         consolidate_fee_rate: FeeRate,
     ) -> WalletResult<(TokenId, SignedTransaction)> {
-        let outputs = make_issue_nft_outputs(address, metadata, self.chain_config.as_ref())?;
+        let (_, best_block_height) = self.get_best_block_for_account(account_index)?;
+        let outputs = make_issue_nft_outputs(
+            address,
+            metadata,
+            self.chain_config.as_ref(),
+            best_block_height.next_height(),
+        )?;
 
         let tx = self.create_transaction_to_addresses(
             account_index,
@@ -861,6 +988,7 @@ impl<B: storage::Backend> Wallet<B> {
         consolidate_fee_rate: FeeRate,
         stake_pool_arguments: StakePoolDataArguments,
     ) -> WalletResult<SignedTransaction> {
+        self.ensure_not_watch_only()?;
         let latest_median_time = self.latest_median_time;
         self.for_account_rw_unlocked(account_index, |account, db_tx| {
             account.create_stake_pool_tx(
@@ -881,6 +1009,7 @@ impl<B: storage::Backend> Wallet<B> {
         pool_balance: Amount,
         current_fee_rate: FeeRate,
     ) -> WalletResult<SignedTransaction> {
+        self.ensure_not_watch_only()?;
         self.for_account_rw_unlocked(account_index, |account, db_tx| {
             account.decommission_stake_pool(db_tx, pool_id, pool_balance, current_fee_rate)
         })
@@ -891,6 +1020,7 @@ impl<B: storage::Backend> Wallet<B> {
         account_index: U31,
         pool_id: PoolId,
     ) -> WalletResult<PoSGenerateBlockInputData> {
+        self.ensure_not_watch_only()?;
         let db_tx = self.db.transaction_ro_unlocked()?;
         self.get_account(account_index)?.get_pos_gen_block_data(
             &db_tx,
@@ -923,7 +1053,10 @@ impl<B: storage::Backend> Wallet<B> {
     pub fn get_syncing_state(&self) -> WalletSyncingState {
         WalletSyncingState {
             account_best_blocks: self.get_best_block(),
-            unused_account_best_block: self.next_unused_account.1.best_block(),
+            unused_account_best_block: self
+                .next_unused_account
+                .as_ref()
+                .map(|(_, account)| account.best_block()),
         }
     }
 
@@ -960,9 +1093,9 @@ impl<B: storage::Backend> Wallet<B> {
         blocks: Vec<Block>,
         wallet_events: &impl WalletEvents,
     ) -> WalletResult<()> {
-        loop {
+        while let Some((_, unused_account)) = &mut self.next_unused_account {
             let mut db_tx = self.db.transaction_rw(None)?;
-            let added_new_tx_in_unused_acc = self.next_unused_account.1.scan_new_blocks(
+            let added_new_tx_in_unused_acc = unused_account.scan_new_blocks(
                 &mut db_tx,
                 wallet_events,
                 common_block_height,
@@ -995,11 +1128,9 @@ impl<B: storage::Backend> Wallet<B> {
             account.update_best_block(&mut db_tx, best_block_height, best_block_id)?;
         }
 
-        self.next_unused_account.1.update_best_block(
-            &mut db_tx,
-            best_block_height,
-            best_block_id,
-        )?;
+        if let Some((_, unused_account)) = &mut self.next_unused_account {
+            unused_account.update_best_block(&mut db_tx, best_block_height, best_block_id)?;
+        }
 
         db_tx.commit()?;
 
@@ -15,7 +15,9 @@
 
 use crate::{
     key_chain::{make_account_path, LOOKAHEAD_SIZE},
-    send_request::{make_address_output, make_create_delegation_output},
+    send_request::{
+        make_address_output, make_address_output_with_timelock, make_create_delegation_output,
+    },
     wallet_events::WalletEventsNoOp,
     DefaultWallet,
 };
@@ -413,6 +415,63 @@ fn wallet_seed_phrase_retrieval(#[case] seed: Seed) {
     assert!(seed_phrase.is_none());
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn export_account_private_key(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+    let account_public_key = wallet.account_public_key(DEFAULT_ACCOUNT_INDEX).unwrap().clone();
+
+    // Without a password set, no password is needed
+    let private_key = wallet.export_account_private_key(DEFAULT_ACCOUNT_INDEX, None).unwrap();
+    assert_eq!(private_key.to_public_key(), account_public_key);
+
+    let password = gen_random_password(&mut rng);
+    wallet.encrypt_wallet(&Some(password.clone())).unwrap();
+
+    // A password-protected wallet refuses to export the key without the correct password,
+    // even if it's unlocked
+    assert_eq!(
+        wallet.export_account_private_key(DEFAULT_ACCOUNT_INDEX, None),
+        Err(WalletError::PasswordRequired)
+    );
+    let wrong_password = format!("{password}x");
+    assert_eq!(
+        wallet.export_account_private_key(DEFAULT_ACCOUNT_INDEX, Some(&wrong_password)),
+        Err(WalletError::DatabaseError(
+            wallet_storage::Error::WalletInvalidPassword
+        ))
+    );
+    let private_key = wallet
+        .export_account_private_key(DEFAULT_ACCOUNT_INDEX, Some(&password))
+        .unwrap();
+    assert_eq!(private_key.to_public_key(), account_public_key);
+
+    // The key can't be exported while the wallet is locked
+    wallet.lock_wallet().unwrap();
+    assert_eq!(
+        wallet.export_account_private_key(DEFAULT_ACCOUNT_INDEX, Some(&password)),
+        Err(WalletError::DatabaseError(
+            wallet_storage::Error::WalletLocked
+        ))
+    );
+
+    // Watch-only wallets have no private keys to export
+    let watch_only_wallet = Wallet::create_watch_only_wallet(
+        chain_config,
+        create_wallet_in_memory().unwrap(),
+        account_public_key,
+    )
+    .unwrap();
+    assert_eq!(
+        watch_only_wallet.export_account_private_key(DEFAULT_ACCOUNT_INDEX, None),
+        Err(WalletError::WatchOnlyWallet)
+    );
+}
+
 #[test]
 fn wallet_balance_genesis() {
     let chain_type = ChainType::Mainnet;
@@ -1104,6 +1163,113 @@ fn locked_wallet_cant_sign_transaction(#[case] seed: Seed) {
     }
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn watch_only_wallet_cant_sign_transaction(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let wallet = create_wallet(chain_config.clone());
+    let account_public_key = wallet.account_public_key(DEFAULT_ACCOUNT_INDEX).unwrap().clone();
+
+    // Only account public keys can be watched
+    let leaf_public_key = account_public_key.clone().derive_child(ChildNumber::ZERO).unwrap();
+    assert_eq!(
+        Wallet::create_watch_only_wallet(
+            chain_config.clone(),
+            create_wallet_in_memory().unwrap(),
+            leaf_public_key.clone(),
+        )
+        .err(),
+        Some(WalletError::KeyChainError(
+            KeyChainError::InvalidBip44DerivationPath(
+                leaf_public_key.get_derivation_path().clone()
+            )
+        ))
+    );
+
+    let mut wallet = Wallet::create_watch_only_wallet(
+        chain_config.clone(),
+        create_wallet_in_memory().unwrap(),
+        account_public_key,
+    )
+    .unwrap();
+    assert!(wallet.is_watch_only());
+    test_wallet_accounts(&chain_config, &wallet, vec![DEFAULT_ACCOUNT_INDEX]);
+
+    // Generate a new block which sends reward to the wallet
+    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 1..NETWORK_FEE + 10000));
+    let address = get_address(
+        &chain_config,
+        MNEMONIC,
+        DEFAULT_ACCOUNT_INDEX,
+        KeyPurpose::ReceiveFunds,
+        0.try_into().unwrap(),
+    );
+    let block1 = Block::new(
+        vec![],
+        chain_config.genesis_block_id(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![make_address_output(
+            chain_config.as_ref(),
+            address,
+            block1_amount,
+        )
+        .unwrap()]),
+    )
+    .unwrap();
+
+    scan_wallet(&mut wallet, BlockHeight::new(0), vec![block1]);
+
+    // The balance is known without the private keys, also after reloading the wallet
+    verify_wallet_balance(&chain_config, &wallet, block1_amount);
+    let wallet_copy =
+        Wallet::load_wallet(Arc::clone(&chain_config), wallet.db.clone(), None).unwrap();
+    assert!(wallet_copy.is_watch_only());
+
+    let new_output = TxOutput::Transfer(
+        OutputValue::Coin(Amount::from_atoms(
+            rng.gen_range(1..=block1_amount.into_atoms() - NETWORK_FEE),
+        )),
+        Destination::AnyoneCanSpend,
+    );
+
+    assert_eq!(
+        wallet.create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [new_output.clone()],
+            [],
+            FeeRate::new(Amount::ZERO),
+            FeeRate::new(Amount::ZERO),
+        ),
+        Err(WalletError::WatchOnlyWallet)
+    );
+    assert_eq!(
+        wallet.get_vrf_public_key(DEFAULT_ACCOUNT_INDEX),
+        Err(WalletError::WatchOnlyWallet)
+    );
+    // New accounts can't be derived from the account public key
+    assert_eq!(
+        wallet.create_next_account(None),
+        Err(WalletError::WatchOnlyWallet)
+    );
+
+    // An unsigned transaction can still be created
+    let tx = wallet
+        .create_unsigned_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [new_output.clone()],
+            [],
+            FeeRate::new(Amount::ZERO),
+            FeeRate::new(Amount::ZERO),
+        )
+        .unwrap();
+    assert_eq!(tx.inputs().len(), 1);
+    assert!(tx.outputs().contains(&new_output));
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -2465,6 +2631,143 @@ fn lock_then_transfer(#[case] seed: Seed) {
     );
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn send_to_address_with_timelock(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let get_coin_balance = |wallet: &DefaultWallet, with_locked: WithLocked| {
+        wallet
+            .get_balance(
+                DEFAULT_ACCOUNT_INDEX,
+                UtxoType::Transfer | UtxoType::LockThenTransfer,
+                UtxoState::Confirmed.into(),
+                with_locked,
+            )
+            .unwrap()
+            .get(&Currency::Coin)
+            .copied()
+            .unwrap_or(Amount::ZERO)
+    };
+
+    // Generate a new block which sends reward to the wallet
+    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    let address = get_address(
+        &chain_config,
+        MNEMONIC,
+        DEFAULT_ACCOUNT_INDEX,
+        KeyPurpose::ReceiveFunds,
+        0.try_into().unwrap(),
+    );
+    let mut timestamp = chain_config.genesis_block().timestamp().add_int_seconds(10).unwrap();
+    let block1 = Block::new(
+        vec![],
+        chain_config.genesis_block_id(),
+        timestamp,
+        ConsensusData::None,
+        BlockReward::new(vec![make_address_output(
+            chain_config.as_ref(),
+            address,
+            block1_amount,
+        )
+        .unwrap()]),
+    )
+    .unwrap();
+    let mut prev_block_id = block1.get_id();
+    scan_wallet(&mut wallet, BlockHeight::new(0), vec![block1]);
+    assert_eq!(
+        get_coin_balance(&wallet, WithLocked::Unlocked),
+        block1_amount
+    );
+
+    // Send some coins to the wallet itself, locked until some future block height
+    let lock_height = rng.gen_range(4..10);
+    let amount_to_lock =
+        Amount::from_atoms(rng.gen_range(1..(block1_amount.into_atoms() - NETWORK_FEE) / 10));
+    let address2 = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let locked_output = make_address_output_with_timelock(
+        chain_config.as_ref(),
+        address2,
+        amount_to_lock,
+        OutputTimeLock::UntilHeight(BlockHeight::new(lock_height)),
+    )
+    .unwrap();
+    assert!(matches!(
+        locked_output,
+        TxOutput::LockThenTransfer(_, _, OutputTimeLock::UntilHeight(_))
+    ));
+
+    let transaction = wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [locked_output],
+            [],
+            FeeRate::new(Amount::ZERO),
+            FeeRate::new(Amount::ZERO),
+        )
+        .unwrap();
+    wallet.add_unconfirmed_tx(transaction.clone(), &WalletEventsNoOp).unwrap();
+
+    timestamp = timestamp.add_int_seconds(10).unwrap();
+    let block2 = Block::new(
+        vec![transaction],
+        prev_block_id.into(),
+        timestamp,
+        ConsensusData::None,
+        BlockReward::new(vec![]),
+    )
+    .unwrap();
+    prev_block_id = block2.get_id();
+    scan_wallet(&mut wallet, BlockHeight::new(1), vec![block2]);
+
+    // The locked output is excluded from the spendable balance before maturity
+    let balance_without_locked = (block1_amount - amount_to_lock).unwrap();
+    for height in 3..lock_height {
+        assert_eq!(
+            get_coin_balance(&wallet, WithLocked::Unlocked),
+            balance_without_locked
+        );
+        assert_eq!(
+            get_coin_balance(&wallet, WithLocked::Locked),
+            amount_to_lock
+        );
+
+        timestamp = timestamp.add_int_seconds(10).unwrap();
+        let block = Block::new(
+            vec![],
+            prev_block_id.into(),
+            timestamp,
+            ConsensusData::None,
+            BlockReward::new(vec![]),
+        )
+        .unwrap();
+        prev_block_id = block.get_id();
+        scan_wallet(&mut wallet, BlockHeight::new(height - 1), vec![block]);
+    }
+
+    // Once the lock height is reached the whole amount is spendable
+    timestamp = timestamp.add_int_seconds(10).unwrap();
+    let block = Block::new(
+        vec![],
+        prev_block_id.into(),
+        timestamp,
+        ConsensusData::None,
+        BlockReward::new(vec![]),
+    )
+    .unwrap();
+    scan_wallet(&mut wallet, BlockHeight::new(lock_height - 1), vec![block]);
+
+    assert_eq!(
+        get_coin_balance(&wallet, WithLocked::Unlocked),
+        block1_amount
+    );
+    assert_eq!(get_coin_balance(&wallet, WithLocked::Locked), Amount::ZERO);
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -145,6 +145,18 @@ impl<B: storage::Backend> Store<B> {
         Ok(())
     }
 
+    /// Checks if the provided password can decrypt all of the stored private keys,
+    /// without changing the encryption state
+    /// Returns WalletInvalidPassword if it can't or if no password is set
+    pub fn check_private_keys_password(&self, password: &String) -> crate::Result<()> {
+        let kdf_challenge = self
+            .transaction_ro()?
+            .get_encryption_key_kdf_challenge()?
+            .ok_or(crate::Error::WalletInvalidPassword)?;
+        let sym_key = challenge_to_sym_key(password, kdf_challenge)?;
+        self.transaction_ro()?.check_can_decrypt_all_root_keys(&sym_key)
+    }
+
     /// Drops the encryption_key and sets the state to Locked
     /// Returns an error if no password is set
     pub fn lock_private_keys(&mut self) -> crate::Result<()> {
@@ -254,6 +266,7 @@ impl<B: storage::Backend> WalletStorageReadLocked for Store<B> {
     delegate_to_transaction! {
         fn get_storage_version(&self) -> crate::Result<u32>;
         fn get_chain_info(&self) -> crate::Result<ChainInfo>;
+        fn is_watch_only(&self) -> crate::Result<bool>;
         fn get_transaction(&self, id: &AccountWalletTxId) -> crate::Result<Option<WalletTx>>;
         fn get_transactions(&self, account_id: &AccountId) -> crate::Result<Vec<(AccountWalletTxId, WalletTx)>>;
         fn get_user_transactions(&self) -> crate::Result<Vec<SignedTransaction>>;
@@ -274,6 +287,7 @@ impl<B: storage::Backend> WalletStorageWriteLocked for Store<B> {
     delegate_to_transaction! {
         fn set_storage_version(&mut self, version: u32) -> crate::Result<()>;
         fn set_chain_info(&mut self, chain_info: &ChainInfo) -> crate::Result<()>;
+        fn set_watch_only(&mut self, watch_only: bool) -> crate::Result<()>;
         fn set_transaction(&mut self, id: &AccountWalletTxId, tx: &WalletTx) -> crate::Result<()>;
         fn del_transaction(&mut self, id: &AccountWalletTxId) -> crate::Result<()>;
         fn clear_transactions(&mut self) -> crate::Result<()>;
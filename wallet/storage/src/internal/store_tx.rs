@@ -69,6 +69,7 @@ mod well_known {
     declare_entry!(EncryptionKeyKdfChallenge: KdfChallenge);
     declare_entry!(MedianTime: BlockTimestamp);
     declare_entry!(StoreChainInfo: ChainInfo);
+    declare_entry!(WatchOnly: bool);
 }
 
 #[derive(PartialEq, Clone)]
@@ -179,7 +180,13 @@ macro_rules! impl_read_ops {
                     .map(Iterator::collect)
             }
 
+            fn is_watch_only(&self) -> crate::Result<bool> {
+                self.read_value::<well_known::WatchOnly>().map(|v| v.unwrap_or_default())
+            }
+
             fn check_root_keys_sanity(&self) -> crate::Result<()> {
+                // Watch-only wallets don't have any root keys
+                let expected_count = if self.is_watch_only()? { 0 } else { 1 };
                 self.storage
                     .get::<db::DBRootKeys, _>()
                     .prefix_iter_decoded(&())
@@ -187,8 +194,11 @@ macro_rules! impl_read_ops {
                     .map(Iterator::count)
                     .and_then(|count| {
                         ensure!(
-                            count == 1,
-                            crate::Error::WalletSanityErrorInvalidRootKeyCount(count)
+                            count == expected_count,
+                            crate::Error::WalletSanityErrorInvalidRootKeyCount(
+                                expected_count,
+                                count
+                            )
                         );
                         Ok(())
                     })
@@ -358,6 +368,10 @@ macro_rules! impl_write_ops {
                 self.write_value::<well_known::StoreChainInfo>(chain_info)
             }
 
+            fn set_watch_only(&mut self, watch_only: bool) -> crate::Result<()> {
+                self.write_value::<well_known::WatchOnly>(&watch_only)
+            }
+
             fn set_transaction(
                 &mut self,
                 id: &AccountWalletTxId,
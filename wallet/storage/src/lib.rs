@@ -48,8 +48,8 @@ pub enum Error {
     WalletAlreadyUnlocked,
     #[error("Cannot lock the wallet without setting a password")]
     WalletLockedWithoutAPassword,
-    #[error("Wallet file corrupted root keys expected 1 got {0}")]
-    WalletSanityErrorInvalidRootKeyCount(usize),
+    #[error("Wallet file corrupted root keys expected {0} got {1}")]
+    WalletSanityErrorInvalidRootKeyCount(usize, usize),
     #[error("Cannot decode address from DB {0}")]
     CannotDecodeAddress(#[from] AddressError),
     #[error("Wallet DB is not in a consistent state")]
@@ -64,6 +64,8 @@ pub trait WalletStorageReadLocked {
     /// Get storage version
     fn get_storage_version(&self) -> Result<u32>;
     fn get_chain_info(&self) -> Result<ChainInfo>;
+    /// Whether the wallet only watches an account public key and has no private keys
+    fn is_watch_only(&self) -> Result<bool>;
     fn get_transaction(&self, id: &AccountWalletTxId) -> Result<Option<WalletTx>>;
     fn get_transactions(
         &self,
@@ -111,6 +113,7 @@ pub trait WalletStorageWriteLocked: WalletStorageReadLocked {
     /// Set storage version
     fn set_storage_version(&mut self, version: u32) -> Result<()>;
     fn set_chain_info(&mut self, chain_info: &ChainInfo) -> Result<()>;
+    fn set_watch_only(&mut self, watch_only: bool) -> Result<()>;
     fn set_transaction(&mut self, id: &AccountWalletTxId, tx: &WalletTx) -> Result<()>;
     fn del_transaction(&mut self, id: &AccountWalletTxId) -> Result<()>;
     fn clear_transactions(&mut self) -> Result<()>;
@@ -0,0 +1,553 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use common::{
+    address::Address,
+    chain::{
+        output_value::OutputValue,
+        signature::inputsig::InputWitness,
+        timelock::OutputTimeLock,
+        tokens::{get_tokens_issuance_count, token_id, RPCTokenInfo, TokenData, TokenId},
+        AccountSpending, ChainConfig, OutPointSourceId, PoolId, SignedTransaction, TxInput,
+        TxOutput, UtxoOutPoint,
+    },
+    primitives::{Amount, Idable},
+};
+use serialization::hex::HexDecode;
+use wallet_controller::{NodeInterface, NodeRpcClient};
+
+use crate::errors::WalletCliError;
+
+use super::{print_coin_amount, print_token_amount};
+
+pub fn parse_transaction(transaction_hex: &str) -> Result<SignedTransaction, WalletCliError> {
+    SignedTransaction::hex_decode_all(transaction_hex)
+        .map_err(|e| WalletCliError::InvalidInput(format!("Invalid transaction: {e}")))
+}
+
+/// What the node knows about the chain state a transaction refers to
+#[derive(Default)]
+pub struct NodeInfo {
+    /// The outputs spent by the utxo inputs, if they are unspent in the mainchain
+    utxos: BTreeMap<UtxoOutPoint, TxOutput>,
+    /// The balances of the pools that are decommissioned by spending their outputs
+    pool_balances: BTreeMap<PoolId, Amount>,
+    /// The number of decimals of the tokens that are transferred, frozen or unfrozen
+    token_decimals: BTreeMap<TokenId, u8>,
+}
+
+impl NodeInfo {
+    pub async fn fetch(
+        rpc_client: &NodeRpcClient,
+        tx: &SignedTransaction,
+    ) -> Result<Self, WalletCliError> {
+        let mut info = Self::default();
+
+        for outpoint in tx.inputs().iter().filter_map(TxInput::utxo_outpoint) {
+            let output =
+                rpc_client.get_utxo(outpoint.clone()).await.map_err(WalletCliError::RpcError)?;
+            if let Some(output) = output {
+                info.utxos.insert(outpoint.clone(), output);
+            }
+        }
+
+        let pool_ids: BTreeSet<PoolId> = info.utxos.values().filter_map(pool_id_of).collect();
+        for pool_id in pool_ids {
+            let balance = rpc_client
+                .get_stake_pool_balance(pool_id)
+                .await
+                .map_err(WalletCliError::RpcError)?;
+            if let Some(balance) = balance {
+                info.pool_balances.insert(pool_id, balance);
+            }
+        }
+
+        let token_ids: BTreeSet<TokenId> =
+            info.utxos.values().chain(tx.outputs()).filter_map(token_id_of).collect();
+        for token_id in token_ids {
+            let token_info =
+                rpc_client.get_token_info(token_id).await.map_err(WalletCliError::RpcError)?;
+            let decimals = match token_info {
+                Some(RPCTokenInfo::FungibleToken(token_info)) => token_info.number_of_decimals,
+                Some(RPCTokenInfo::NonFungibleToken(_)) => 0,
+                None => continue,
+            };
+            info.token_decimals.insert(token_id, decimals);
+        }
+
+        Ok(info)
+    }
+}
+
+fn output_value(output: &TxOutput) -> Option<&OutputValue> {
+    match output {
+        TxOutput::Transfer(value, _)
+        | TxOutput::LockThenTransfer(value, _, _)
+        | TxOutput::Burn(value) => Some(value),
+        TxOutput::CreateStakePool(_, _)
+        | TxOutput::ProduceBlockFromStake(_, _)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::DelegateStaking(_, _) => None,
+    }
+}
+
+/// The pool that is decommissioned when the output is spent
+fn pool_id_of(output: &TxOutput) -> Option<PoolId> {
+    match output {
+        TxOutput::CreateStakePool(pool_id, _) | TxOutput::ProduceBlockFromStake(_, pool_id) => {
+            Some(*pool_id)
+        }
+        TxOutput::Transfer(_, _)
+        | TxOutput::LockThenTransfer(_, _, _)
+        | TxOutput::Burn(_)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::DelegateStaking(_, _) => None,
+    }
+}
+
+/// The existing token the output refers to
+fn token_id_of(output: &TxOutput) -> Option<TokenId> {
+    match output_value(output)? {
+        OutputValue::Coin(_) => None,
+        OutputValue::Token(token_data) => match token_data.as_ref() {
+            TokenData::TokenTransfer(transfer) => Some(transfer.token_id),
+            TokenData::TokenFreezeV1(freeze) => Some(freeze.token_id),
+            TokenData::TokenUnfreezeV1(unfreeze) => Some(unfreeze.token_id),
+            TokenData::TokenIssuance(_) | TokenData::NftIssuance(_) => None,
+        },
+    }
+}
+
+/// The coins that become available by spending the output, if known
+fn spent_coins(output: &TxOutput, node_info: &NodeInfo) -> Option<Amount> {
+    match output {
+        TxOutput::Transfer(value, _) | TxOutput::LockThenTransfer(value, _, _) => {
+            Some(value.coin_amount().unwrap_or(Amount::ZERO))
+        }
+        TxOutput::CreateStakePool(pool_id, _) | TxOutput::ProduceBlockFromStake(_, pool_id) => {
+            node_info.pool_balances.get(pool_id).copied()
+        }
+        TxOutput::Burn(_)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::DelegateStaking(_, _) => None,
+    }
+}
+
+/// The coins that are locked, burned or transferred by the output
+fn created_coins(output: &TxOutput) -> Amount {
+    match output {
+        TxOutput::Transfer(value, _)
+        | TxOutput::LockThenTransfer(value, _, _)
+        | TxOutput::Burn(value) => value.coin_amount().unwrap_or(Amount::ZERO),
+        TxOutput::CreateStakePool(_, pool_data) => pool_data.value(),
+        TxOutput::DelegateStaking(amount, _) => *amount,
+        TxOutput::ProduceBlockFromStake(_, _) | TxOutput::CreateDelegationId(_, _) => Amount::ZERO,
+    }
+}
+
+fn format_outpoint(outpoint: &UtxoOutPoint) -> String {
+    // The same format that is accepted by the commands that take an outpoint
+    match outpoint.source_id() {
+        OutPointSourceId::Transaction(id) => {
+            format!("tx({:x},{})", id.to_hash(), outpoint.output_index())
+        }
+        OutPointSourceId::BlockReward(id) => {
+            format!("block({:x},{})", id.to_hash(), outpoint.output_index())
+        }
+    }
+}
+
+fn format_timelock(timelock: &OutputTimeLock) -> String {
+    match timelock {
+        OutputTimeLock::UntilHeight(height) => format!("until height {height}"),
+        OutputTimeLock::UntilTime(timestamp) => format!("until {timestamp}"),
+        OutputTimeLock::ForBlockCount(count) => format!("for {count} blocks"),
+        OutputTimeLock::ForSeconds(seconds) => format!("for {seconds} seconds"),
+    }
+}
+
+fn format_token_amount(
+    chain_config: &ChainConfig,
+    token_id: TokenId,
+    amount: Amount,
+    node_info: &NodeInfo,
+) -> Result<String, WalletCliError> {
+    let token_address = Address::new(chain_config, &token_id)?;
+    let result = match node_info.token_decimals.get(&token_id) {
+        Some(decimals) => format!(
+            "{} of token {token_address}",
+            print_token_amount(*decimals, amount)
+        ),
+        None => format!("{} atoms of token {token_address}", amount.into_atoms()),
+    };
+    Ok(result)
+}
+
+fn format_value(
+    chain_config: &ChainConfig,
+    value: &OutputValue,
+    node_info: &NodeInfo,
+) -> Result<String, WalletCliError> {
+    let result = match value {
+        OutputValue::Coin(amount) => format!(
+            "{} {}",
+            print_coin_amount(chain_config, *amount),
+            chain_config.coin_ticker()
+        ),
+        OutputValue::Token(token_data) => match token_data.as_ref() {
+            TokenData::TokenTransfer(transfer) => {
+                format_token_amount(chain_config, transfer.token_id, transfer.amount, node_info)?
+            }
+            TokenData::TokenIssuance(issuance) => format!(
+                "{} of a new token {}",
+                print_token_amount(issuance.number_of_decimals, issuance.amount_to_issue),
+                String::from_utf8_lossy(&issuance.token_ticker)
+            ),
+            TokenData::NftIssuance(issuance) => format!(
+                "a new NFT {}",
+                String::from_utf8_lossy(&issuance.metadata.ticker)
+            ),
+            TokenData::TokenFreezeV1(freeze) => format!(
+                "a freeze of token {}",
+                Address::new(chain_config, &freeze.token_id)?
+            ),
+            TokenData::TokenUnfreezeV1(unfreeze) => format!(
+                "an unfreeze of token {}",
+                Address::new(chain_config, &unfreeze.token_id)?
+            ),
+        },
+    };
+    Ok(result)
+}
+
+fn format_output(
+    chain_config: &ChainConfig,
+    output: &TxOutput,
+    node_info: &NodeInfo,
+) -> Result<String, WalletCliError> {
+    let result = match output {
+        TxOutput::Transfer(value, destination) => format!(
+            "Transfer {} to {}",
+            format_value(chain_config, value, node_info)?,
+            Address::new(chain_config, destination)?
+        ),
+        TxOutput::LockThenTransfer(value, destination, timelock) => format!(
+            "Transfer {} to {}, locked {}",
+            format_value(chain_config, value, node_info)?,
+            Address::new(chain_config, destination)?,
+            format_timelock(timelock)
+        ),
+        TxOutput::Burn(value) => {
+            format!("Burn {}", format_value(chain_config, value, node_info)?)
+        }
+        TxOutput::CreateStakePool(pool_id, pool_data) => format!(
+            "Create stake pool {} with pledge {} {}, staker {}, decommission key {}",
+            Address::new(chain_config, pool_id)?,
+            print_coin_amount(chain_config, pool_data.value()),
+            chain_config.coin_ticker(),
+            Address::new(chain_config, pool_data.staker())?,
+            Address::new(chain_config, pool_data.decommission_key())?
+        ),
+        TxOutput::ProduceBlockFromStake(destination, pool_id) => format!(
+            "Produce a block from the stake of pool {} with staker {}",
+            Address::new(chain_config, pool_id)?,
+            Address::new(chain_config, destination)?
+        ),
+        TxOutput::CreateDelegationId(destination, pool_id) => format!(
+            "Create a delegation to pool {} owned by {}",
+            Address::new(chain_config, pool_id)?,
+            Address::new(chain_config, destination)?
+        ),
+        TxOutput::DelegateStaking(amount, delegation_id) => format!(
+            "Delegate {} {} to delegation {}",
+            print_coin_amount(chain_config, *amount),
+            chain_config.coin_ticker(),
+            Address::new(chain_config, delegation_id)?
+        ),
+    };
+    Ok(result)
+}
+
+fn format_token_operation(
+    chain_config: &ChainConfig,
+    tx: &SignedTransaction,
+    output: &TxOutput,
+    node_info: &NodeInfo,
+) -> Result<Option<String>, WalletCliError> {
+    let token_data = match output_value(output) {
+        Some(OutputValue::Token(token_data)) => token_data,
+        Some(OutputValue::Coin(_)) | None => return Ok(None),
+    };
+    let new_token_address = || -> Result<String, WalletCliError> {
+        match token_id(tx.transaction()) {
+            Some(token_id) => Ok(Address::new(chain_config, &token_id)?.to_string()),
+            None => Ok("unknown".to_owned()),
+        }
+    };
+
+    let result = match token_data.as_ref() {
+        TokenData::TokenIssuance(issuance) => format!(
+            "Issue {} of token {} with {} decimals and metadata URI '{}', token id {}",
+            print_token_amount(issuance.number_of_decimals, issuance.amount_to_issue),
+            String::from_utf8_lossy(&issuance.token_ticker),
+            issuance.number_of_decimals,
+            String::from_utf8_lossy(&issuance.metadata_uri),
+            new_token_address()?
+        ),
+        TokenData::NftIssuance(issuance) => format!(
+            "Issue NFT {} named '{}', token id {}",
+            String::from_utf8_lossy(&issuance.metadata.ticker),
+            String::from_utf8_lossy(&issuance.metadata.name),
+            new_token_address()?
+        ),
+        TokenData::TokenTransfer(transfer) => {
+            let verb = match output {
+                TxOutput::Burn(_) => "Burn",
+                _ => "Transfer",
+            };
+            format!(
+                "{verb} {}",
+                format_token_amount(chain_config, transfer.token_id, transfer.amount, node_info)?
+            )
+        }
+        TokenData::TokenFreezeV1(freeze) => format!(
+            "Freeze token {}",
+            Address::new(chain_config, &freeze.token_id)?
+        ),
+        TokenData::TokenUnfreezeV1(unfreeze) => format!(
+            "Unfreeze token {}",
+            Address::new(chain_config, &unfreeze.token_id)?
+        ),
+    };
+    Ok(Some(result))
+}
+
+/// Describes the inputs, outputs, token operations and fee of a transaction,
+/// along with the issues that would make the transaction invalid
+pub fn describe_transaction(
+    chain_config: &ChainConfig,
+    tx: &SignedTransaction,
+    node_info: &NodeInfo,
+) -> Result<String, WalletCliError> {
+    let mut lines = vec![format!("Transaction id: {:x}", tx.transaction().get_id().to_hash())];
+    let mut issues = Vec::new();
+
+    if let Err(e) = tx.check_structure() {
+        issues.push(e.to_string());
+    }
+
+    lines.push("Inputs:".to_owned());
+    let mut input_coins = Some(Amount::ZERO);
+    for (index, (input, witness)) in tx.inputs().iter().zip(tx.signatures()).enumerate() {
+        let (description, coins) = match input {
+            TxInput::Utxo(outpoint) => match node_info.utxos.get(outpoint) {
+                Some(output) => (
+                    format!(
+                        "{} spending: {}",
+                        format_outpoint(outpoint),
+                        format_output(chain_config, output, node_info)?
+                    ),
+                    spent_coins(output, node_info),
+                ),
+                None => {
+                    issues.push(format!(
+                        "Input {index} is not an unspent output known to the node"
+                    ));
+                    (format!("{} (unknown)", format_outpoint(outpoint)), None)
+                }
+            },
+            TxInput::Account(outpoint) => match outpoint.account() {
+                AccountSpending::Delegation(delegation_id, amount) => (
+                    format!(
+                        "Withdraw {} {} from delegation {} with nonce {}",
+                        print_coin_amount(chain_config, *amount),
+                        chain_config.coin_ticker(),
+                        Address::new(chain_config, delegation_id)?,
+                        outpoint.nonce()
+                    ),
+                    Some(*amount),
+                ),
+            },
+        };
+        let signed = match witness {
+            InputWitness::NoSignature(_) => " (not signed)",
+            InputWitness::Standard(_) => "",
+        };
+        lines.push(format!("  {index}: {description}{signed}"));
+        input_coins = input_coins.zip(coins).and_then(|(total, coins)| total + coins);
+    }
+
+    lines.push("Outputs:".to_owned());
+    for (index, output) in tx.outputs().iter().enumerate() {
+        lines.push(format!(
+            "  {index}: {}",
+            format_output(chain_config, output, node_info)?
+        ));
+        if let TxOutput::ProduceBlockFromStake(_, _) = output {
+            issues.push(format!("Output {index} can only be used in a block reward"));
+        }
+    }
+
+    let token_operations = tx
+        .outputs()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, output)| {
+            format_token_operation(chain_config, tx, output, node_info)
+                .transpose()
+                .map(|operation| operation.map(|operation| format!("  {index}: {operation}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if !token_operations.is_empty() {
+        lines.push("Token operations:".to_owned());
+        lines.extend(token_operations);
+    }
+    if get_tokens_issuance_count(tx.outputs()) > 1 {
+        issues.push("More than one token is issued".to_owned());
+    }
+
+    let output_coins: Option<Amount> = tx.outputs().iter().map(created_coins).sum();
+    match (input_coins, output_coins) {
+        (Some(input_coins), Some(output_coins)) => match input_coins - output_coins {
+            Some(fee) => lines.push(format!(
+                "Fee: {} {}",
+                print_coin_amount(chain_config, fee),
+                chain_config.coin_ticker()
+            )),
+            None => {
+                lines.push("Fee: none".to_owned());
+                issues.push(format!(
+                    "Coin outputs exceed coin inputs by {} {}",
+                    print_coin_amount(chain_config, output_coins.abs_diff(input_coins)),
+                    chain_config.coin_ticker()
+                ));
+            }
+        },
+        _ => lines.push("Fee: unknown, not all inputs are known to the node".to_owned()),
+    }
+
+    if issues.is_empty() {
+        lines.push("No issues found".to_owned());
+    } else {
+        lines.push("Issues:".to_owned());
+        lines.extend(issues.into_iter().map(|issue| format!("  - {issue}")));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use common::chain::{
+        config::create_regtest, tokens::TokenTransfer, AccountNonce, AccountOutPoint, DelegationId,
+        Destination, Transaction,
+    };
+    use common::primitives::{Id, H256};
+    use serialization::hex::HexEncode;
+
+    use super::*;
+
+    fn make_tx(inputs: Vec<TxInput>, outputs: Vec<TxOutput>) -> SignedTransaction {
+        let witnesses = vec![InputWitness::NoSignature(None); inputs.len()];
+        SignedTransaction::new(Transaction::new(0, inputs, outputs).unwrap(), witnesses).unwrap()
+    }
+
+    #[test]
+    fn invalid_hex() {
+        assert!(matches!(
+            parse_transaction("not hex"),
+            Err(WalletCliError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            parse_transaction("0102"),
+            Err(WalletCliError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn resolved_inputs() {
+        let chain_config = create_regtest();
+        let outpoint = UtxoOutPoint::new(Id::<Transaction>::new(H256::zero()).into(), 1);
+        let delegation_id = DelegationId::new(H256::zero());
+        let tx = make_tx(
+            vec![
+                TxInput::Utxo(outpoint.clone()),
+                TxInput::Account(AccountOutPoint::new(
+                    AccountNonce::new(0),
+                    AccountSpending::Delegation(delegation_id, Amount::from_atoms(50)),
+                )),
+            ],
+            vec![TxOutput::Transfer(
+                OutputValue::Coin(Amount::from_atoms(120)),
+                Destination::AnyoneCanSpend,
+            )],
+        );
+        let tx = parse_transaction(&tx.hex_encode()).unwrap();
+
+        let node_info = NodeInfo {
+            utxos: [(
+                outpoint,
+                TxOutput::Transfer(
+                    OutputValue::Coin(Amount::from_atoms(100)),
+                    Destination::AnyoneCanSpend,
+                ),
+            )]
+            .into(),
+            ..Default::default()
+        };
+        let description = describe_transaction(&chain_config, &tx, &node_info).unwrap();
+        let fee = print_coin_amount(&chain_config, Amount::from_atoms(30));
+        assert!(description.contains(&format!("Fee: {fee} ")));
+        assert!(description.contains("No issues found"));
+
+        // The fee isn't known without the spent utxo
+        let description = describe_transaction(&chain_config, &tx, &NodeInfo::default()).unwrap();
+        assert!(description.contains("Fee: unknown"));
+        assert!(description.contains("Input 0 is not an unspent output known to the node"));
+    }
+
+    #[test]
+    fn token_operations_and_issues() {
+        let chain_config = create_regtest();
+        let delegation_id = DelegationId::new(H256::zero());
+        let token_id = TokenId::new(H256::zero());
+        let tx = make_tx(
+            vec![TxInput::Account(AccountOutPoint::new(
+                AccountNonce::new(0),
+                AccountSpending::Delegation(delegation_id, Amount::from_atoms(10)),
+            ))],
+            vec![
+                TxOutput::Transfer(
+                    OutputValue::Token(Box::new(TokenData::TokenTransfer(TokenTransfer {
+                        token_id,
+                        amount: Amount::from_atoms(5),
+                    }))),
+                    Destination::AnyoneCanSpend,
+                ),
+                TxOutput::Burn(OutputValue::Coin(Amount::from_atoms(11))),
+            ],
+        );
+
+        let description = describe_transaction(&chain_config, &tx, &NodeInfo::default()).unwrap();
+        let token_address = Address::new(&chain_config, &token_id).unwrap();
+        assert!(description.contains(&format!(
+            "Token operations:\n  0: Transfer 5 atoms of token {token_address}"
+        )));
+        assert!(description.contains("Fee: none"));
+        assert!(description.contains("Coin outputs exceed coin inputs by"));
+    }
+}
@@ -13,9 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{fmt::Display, str::FromStr};
+use std::{collections::BTreeMap, fmt::Display, num::NonZeroUsize, str::FromStr};
 
 use clap::ValueEnum;
+use mempool::FeeRateEstimate;
 use wallet_controller::{UtxoState, UtxoStates, UtxoType, UtxoTypes};
 
 use common::{
@@ -131,6 +132,58 @@ pub fn format_delegation_info(
     )
 }
 
+pub fn format_fee_rate_estimate(
+    estimate: FeeRateEstimate,
+    target_blocks: NonZeroUsize,
+    chain_config: &ChainConfig,
+) -> String {
+    let fee_rate = format!(
+        "{} {} per kB",
+        Amount::from_atoms(estimate.fee_rate().atoms_per_kb())
+            .into_fixedpoint_str(chain_config.coin_decimals()),
+        chain_config.coin_ticker(),
+    );
+    if estimate.is_fallback() {
+        format!(
+            "Not enough mempool data to estimate the fee rate for confirmation within {target_blocks} block(s), \
+            using the minimum relay fee rate: {fee_rate}"
+        )
+    } else {
+        format!("Estimated fee rate for confirmation within {target_blocks} block(s): {fee_rate}")
+    }
+}
+
+pub fn format_staking_status(
+    is_staking: bool,
+    pool_balances: &BTreeMap<PoolId, Amount>,
+    chain_config: &ChainConfig,
+) -> String {
+    let status = if is_staking { "Staking" } else { "Not staking" };
+    if pool_balances.is_empty() {
+        return format!("{status}, no staking pools found");
+    }
+
+    let total = pool_balances
+        .values()
+        .try_fold(Amount::ZERO, |total, balance| total + *balance)
+        .expect("Pool balances cannot overflow");
+    let pools = pool_balances.iter().map(|(pool_id, balance)| {
+        format!(
+            "Pool Id: {}, Balance: {}",
+            Address::new(chain_config, pool_id).expect("Encoding pool id should never fail"),
+            balance.into_fixedpoint_str(chain_config.coin_decimals()),
+        )
+    });
+
+    std::iter::once(format!(
+        "{status}, total pool balance: {}",
+        total.into_fixedpoint_str(chain_config.coin_decimals())
+    ))
+    .chain(pools)
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum CliWithLocked {
     Any,
@@ -244,4 +297,62 @@ mod tests {
             check(format!("{id}({h256:x},{idx})"), is_tx, idx, h256);
         }
     }
+
+    #[test]
+    fn test_format_fee_rate_estimate() {
+        let chain_config = common::chain::config::create_regtest();
+        let fee_rate = mempool::FeeRate::new(Amount::from_atoms(
+            10u128.pow(chain_config.coin_decimals() as u32) / 2,
+        ));
+        let target_blocks = NonZeroUsize::new(3).unwrap();
+        let ticker = chain_config.coin_ticker();
+
+        assert_eq!(
+            format_fee_rate_estimate(
+                FeeRateEstimate::from_mempool(fee_rate),
+                target_blocks,
+                &chain_config
+            ),
+            format!("Estimated fee rate for confirmation within 3 block(s): 0.5 {ticker} per kB"),
+        );
+
+        let fallback = format_fee_rate_estimate(
+            FeeRateEstimate::fallback(fee_rate),
+            target_blocks,
+            &chain_config,
+        );
+        assert!(fallback.starts_with("Not enough mempool data"));
+        assert!(fallback.ends_with(&format!("minimum relay fee rate: 0.5 {ticker} per kB")));
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn test_format_staking_status(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = common::chain::config::create_regtest();
+        let coin = 10u128.pow(chain_config.coin_decimals() as u32);
+
+        assert_eq!(
+            format_staking_status(false, &BTreeMap::new(), &chain_config),
+            "Not staking, no staking pools found"
+        );
+
+        let pool_id = PoolId::new(H256::random_using(&mut rng));
+        let pool_balances = BTreeMap::from([(pool_id, Amount::from_atoms(coin * 3 / 2))]);
+        let pool_address = Address::new(&chain_config, &pool_id).unwrap();
+        assert_eq!(
+            format_staking_status(true, &pool_balances, &chain_config),
+            format!("Staking, total pool balance: 1.5\nPool Id: {pool_address}, Balance: 1.5")
+        );
+
+        let other_pool_id = PoolId::new(H256::random_using(&mut rng));
+        let pool_balances = BTreeMap::from([
+            (pool_id, Amount::from_atoms(coin)),
+            (other_pool_id, Amount::from_atoms(coin * 2)),
+        ]);
+        let status = format_staking_status(false, &pool_balances, &chain_config);
+        assert!(status.starts_with("Not staking, total pool balance: 3\n"));
+        assert_eq!(status.lines().count(), 3);
+    }
 }
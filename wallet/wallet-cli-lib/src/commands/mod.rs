@@ -13,21 +13,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod decode_transaction;
 mod helper_types;
 
-use std::{path::PathBuf, str::FromStr, sync::Arc};
+use std::{num::NonZeroUsize, path::PathBuf, str::FromStr, sync::Arc};
 
 use chainstate::TokenIssuanceError;
 use clap::Parser;
 use common::{
     address::Address,
     chain::{
+        timelock::OutputTimeLock,
         tokens::{Metadata, TokenCreator, TokenId},
         Block, ChainConfig, Destination, PoolId, SignedTransaction, Transaction, UtxoOutPoint,
     },
     primitives::{per_thousand::PerThousand, Amount, BlockHeight, Id, H256},
 };
-use crypto::key::{hdkd::u31::U31, PublicKey};
+use crypto::key::{extended::ExtendedPublicKey, hdkd::u31::U31, PublicKey};
 use mempool::tx_accumulator::PackingStrategy;
 use p2p_types::{bannable_address::BannableAddress, ip_or_socket_address::IpOrSocketAddress};
 use serialization::{hex::HexEncode, hex_encoded::HexEncoded};
@@ -43,8 +45,8 @@ use wallet_controller::{
 use crate::{errors::WalletCliError, CliController};
 
 use self::helper_types::{
-    format_delegation_info, format_pool_info, parse_utxo_outpoint, CliStoreSeedPhrase,
-    CliUtxoState, CliUtxoTypes, CliWithLocked,
+    format_delegation_info, format_fee_rate_estimate, format_pool_info, format_staking_status,
+    parse_utxo_outpoint, CliStoreSeedPhrase, CliUtxoState, CliUtxoTypes, CliWithLocked,
 };
 
 #[derive(Debug, Parser)]
@@ -66,6 +68,16 @@ pub enum WalletCommand {
         mnemonic: Option<String>,
     },
 
+    /// Create a watch-only wallet for the account with the given extended public key.
+    /// The wallet can show balances and create unsigned transactions, but it can't sign them.
+    CreateWatchOnly {
+        /// File path
+        wallet_path: PathBuf,
+
+        /// Hex encoded extended public key of the account, as printed by 'showaccountpublickey'
+        account_public_key: HexEncoded<ExtendedPublicKey>,
+    },
+
     /// Open exiting wallet
     OpenWallet {
         /// File path
@@ -95,6 +107,21 @@ pub enum WalletCommand {
     // Locks the private keys so they can't be used until they are unlocked again
     LockPrivateKeys,
 
+    /// Show the extended public key of the selected account, which can be used to create
+    /// a watch-only wallet
+    ShowAccountPublicKey,
+
+    /// Print the extended private key of the selected account, e.g. to migrate it to another wallet.
+    /// Without '--confirm' only a warning is shown. Anyone who sees the key can spend the funds
+    ExportPrivateKeys {
+        /// The existing password, required if the wallet is encrypted
+        password: Option<String>,
+
+        /// Confirm that the private key should be printed on the screen
+        #[arg(long)]
+        confirm: bool,
+    },
+
     /// Returns the node chainstate
     ChainstateInfo,
 
@@ -148,8 +175,12 @@ pub enum WalletCommand {
     /// Start staking
     StartStaking,
 
+    /// Stop staking
     StopStaking,
 
+    /// Show whether the selected account is staking and the balances of its staking pools
+    StakingStatus,
+
     StakePoolBalance {
         pool_id: String,
     },
@@ -174,6 +205,20 @@ pub enum WalletCommand {
         transaction: HexEncoded<SignedTransaction>,
     },
 
+    /// Decode a hex encoded transaction and show its inputs, outputs, token operations and fee.
+    /// The spent outputs are looked up in the node to calculate the fee
+    DecodeTransaction {
+        /// Hex encoded transaction
+        transaction: String,
+    },
+
+    /// Estimate the fee rate needed for a transaction to get confirmed within the given number of blocks.
+    /// If the mempool doesn't have enough data, the minimum relay fee rate is shown instead
+    EstimateFee {
+        /// The number of blocks the transaction should be confirmed within
+        target_blocks: NonZeroUsize,
+    },
+
     /// Abandon an unconfirmed transaction, and make the consumed inputs available to be used again
     AbandonTransaction {
         transaction_id: HexEncoded<Id<Transaction>>,
@@ -244,6 +289,21 @@ pub enum WalletCommand {
         amount: String,
         #[arg(default_values_t = Vec::<String>::new())]
         utxos: Vec<String>,
+        /// Lock the sent coins until the given block height
+        #[arg(long, conflicts_with = "lock_seconds")]
+        lock_until_height: Option<BlockHeight>,
+        /// Lock the sent coins for the given number of seconds after the transaction is included in a block
+        #[arg(long)]
+        lock_seconds: Option<u64>,
+    },
+
+    /// Create a transaction that sends coins to the address, and print it without signing
+    /// or broadcasting it
+    CreateUnsignedTransaction {
+        address: String,
+        amount: String,
+        #[arg(default_values_t = Vec::<String>::new())]
+        utxos: Vec<String>,
     },
 
     SendTokensToAddress {
@@ -484,6 +544,17 @@ impl CommandHandler {
             .map_err(WalletCliError::Controller)
     }
 
+    /// Same as `get_synced_controller` but fails early if the wallet can't sign transactions
+    async fn get_synced_controller_for_signing(
+        &mut self,
+    ) -> Result<SyncedController<'_, NodeRpcClient, WalletEventsNoOp>, WalletCliError> {
+        ensure!(
+            !self.controller()?.is_watch_only(),
+            WalletCliError::WatchOnlyWallet
+        );
+        self.get_synced_controller().await
+    }
+
     fn get_readonly_controller(
         &mut self,
     ) -> Result<ReadOnlyController<'_, NodeRpcClient>, WalletCliError> {
@@ -579,6 +650,39 @@ impl CommandHandler {
                 })
             }
 
+            WalletCommand::CreateWatchOnly {
+                wallet_path,
+                account_public_key,
+            } => {
+                utils::ensure!(self.state.is_none(), WalletCliError::WalletFileAlreadyOpen);
+
+                let wallet = CliController::create_watch_only_wallet(
+                    Arc::clone(chain_config),
+                    wallet_path,
+                    account_public_key.take(),
+                )
+                .map_err(WalletCliError::Controller)?;
+
+                self.state = Some((
+                    CliController::new(
+                        Arc::clone(chain_config),
+                        rpc_client.clone(),
+                        wallet,
+                        WalletEventsNoOp,
+                    )
+                    .await
+                    .map_err(WalletCliError::Controller)?,
+                    CliWalletState {
+                        selected_account: DEFAULT_ACCOUNT_INDEX,
+                    },
+                ));
+
+                Ok(ConsoleCommand::SetStatus {
+                    status: self.repl_status(),
+                    print_message: "New watch-only wallet created successfully".to_owned(),
+                })
+            }
+
             WalletCommand::OpenWallet {
                 wallet_path,
                 password,
@@ -656,6 +760,36 @@ impl CommandHandler {
                 ))
             }
 
+            WalletCommand::ShowAccountPublicKey => {
+                let public_key = self
+                    .get_readonly_controller()?
+                    .account_public_key()
+                    .map_err(WalletCliError::Controller)?;
+                Ok(ConsoleCommand::Print(public_key.hex_encode()))
+            }
+
+            WalletCommand::ExportPrivateKeys { password, confirm } => {
+                ensure!(
+                    !self.controller()?.is_watch_only(),
+                    WalletCliError::WatchOnlyWallet
+                );
+
+                if !confirm {
+                    return Ok(ConsoleCommand::Print(
+                        "WARNING: This will print the private key of the account. Anyone who sees it can spend all of the funds of the account. Run the command again with '--confirm' to proceed.".to_owned(),
+                    ));
+                }
+
+                let private_key = self
+                    .get_readonly_controller()?
+                    .export_account_private_key(password.as_ref())
+                    .map_err(WalletCliError::Controller)?;
+                Ok(ConsoleCommand::Print(format!(
+                    "SENSITIVE: Keep the following private key secret and don't share it with anyone\n{}",
+                    private_key.hex_encode()
+                )))
+            }
+
             WalletCommand::ChainstateInfo => {
                 let info = rpc_client.chainstate_info().await.map_err(WalletCliError::RpcError)?;
                 Ok(ConsoleCommand::Print(format!("{info:#?}")))
@@ -695,6 +829,10 @@ impl CommandHandler {
             }
 
             WalletCommand::GenerateBlock { transactions } => {
+                ensure!(
+                    !self.controller()?.is_watch_only(),
+                    WalletCliError::WatchOnlyWallet
+                );
                 let transactions = transactions.into_iter().map(HexEncoded::take).collect();
                 let (controller, selected_account) = self.get_controller_and_selected_acc()?;
                 let block = controller
@@ -711,6 +849,10 @@ impl CommandHandler {
             }
 
             WalletCommand::GenerateBlocks { block_count } => {
+                ensure!(
+                    !self.controller()?.is_watch_only(),
+                    WalletCliError::WatchOnlyWallet
+                );
                 let (controller, selected_account) = self.get_controller_and_selected_acc()?;
                 controller
                     .generate_blocks(selected_account, block_count)
@@ -740,7 +882,7 @@ impl CommandHandler {
             }
 
             WalletCommand::StartStaking => {
-                self.get_synced_controller()
+                self.get_synced_controller_for_signing()
                     .await?
                     .start_staking()
                     .map_err(WalletCliError::Controller)?;
@@ -755,6 +897,20 @@ impl CommandHandler {
                 Ok(ConsoleCommand::Print("Success".to_owned()))
             }
 
+            WalletCommand::StakingStatus => {
+                let (controller, selected_account) = self.get_controller_and_selected_acc()?;
+                let is_staking = controller.is_staking(selected_account);
+                let pool_balances = controller
+                    .get_stake_pool_balances(selected_account)
+                    .await
+                    .map_err(WalletCliError::Controller)?;
+                Ok(ConsoleCommand::Print(format_staking_status(
+                    is_staking,
+                    &pool_balances,
+                    chain_config,
+                )))
+            }
+
             WalletCommand::StakePoolBalance { pool_id } => {
                 let pool_id = parse_pool_id(chain_config, pool_id.as_str())?;
                 let balance_opt = rpc_client
@@ -781,6 +937,26 @@ impl CommandHandler {
                 Self::broadcast_transaction(rpc_client, transaction.take()).await
             }
 
+            WalletCommand::DecodeTransaction { transaction } => {
+                let tx = decode_transaction::parse_transaction(&transaction)?;
+                let node_info = decode_transaction::NodeInfo::fetch(rpc_client, &tx).await?;
+                let description =
+                    decode_transaction::describe_transaction(chain_config, &tx, &node_info)?;
+                Ok(ConsoleCommand::Print(description))
+            }
+
+            WalletCommand::EstimateFee { target_blocks } => {
+                let estimate = rpc_client
+                    .mempool_estimate_fee_rate(target_blocks)
+                    .await
+                    .map_err(WalletCliError::RpcError)?;
+                Ok(ConsoleCommand::Print(format_fee_rate_estimate(
+                    estimate,
+                    target_blocks,
+                    chain_config,
+                )))
+            }
+
             WalletCommand::AbandonTransaction { transaction_id } => {
                 self.get_synced_controller()
                     .await?
@@ -801,7 +977,10 @@ impl CommandHandler {
                 ensure!(
                     number_of_decimals <= chain_config.token_max_dec_count(),
                     WalletCliError::Controller(ControllerError::WalletError(
-                        WalletError::TokenIssuance(TokenIssuanceError::IssueErrorTooManyDecimals),
+                        WalletError::TokenIssuance(TokenIssuanceError::IssueErrorTooManyDecimals(
+                            number_of_decimals,
+                            chain_config.token_max_dec_count(),
+                        )),
                     ))
                 );
 
@@ -809,7 +988,7 @@ impl CommandHandler {
                 let destination_address = parse_address(chain_config, &destination_address)?;
 
                 let token_id = self
-                    .get_synced_controller()
+                    .get_synced_controller_for_signing()
                     .await?
                     .issue_new_token(
                         destination_address,
@@ -854,7 +1033,7 @@ impl CommandHandler {
                 };
 
                 let token_id = self
-                    .get_synced_controller()
+                    .get_synced_controller_for_signing()
                     .await?
                     .issue_new_nft(destination_address, metadata)
                     .await
@@ -966,7 +1145,7 @@ impl CommandHandler {
 
             WalletCommand::GetVrfPublicKey => {
                 let vrf_public_key = self
-                    .get_synced_controller()
+                    .get_synced_controller_for_signing()
                     .await?
                     .get_vrf_public_key()
                     .map_err(WalletCliError::Controller)?;
@@ -977,6 +1156,8 @@ impl CommandHandler {
                 address,
                 amount,
                 utxos,
+                lock_until_height,
+                lock_seconds,
             } => {
                 let utxos: Vec<UtxoOutPoint> = utxos
                     .into_iter()
@@ -984,14 +1165,39 @@ impl CommandHandler {
                     .collect::<Result<Vec<_>, WalletCliError>>()?;
                 let amount = parse_coin_amount(chain_config, &amount)?;
                 let address = parse_address(chain_config, &address)?;
-                self.get_synced_controller()
+                let timelock = match (lock_until_height, lock_seconds) {
+                    (Some(height), _) => Some(OutputTimeLock::UntilHeight(height)),
+                    (None, Some(seconds)) => Some(OutputTimeLock::ForSeconds(seconds)),
+                    (None, None) => None,
+                };
+                self.get_synced_controller_for_signing()
                     .await?
-                    .send_to_address(address, amount, utxos)
+                    .send_to_address(address, amount, utxos, timelock)
                     .await
                     .map_err(WalletCliError::Controller)?;
                 Ok(Self::tx_submitted_command())
             }
 
+            WalletCommand::CreateUnsignedTransaction {
+                address,
+                amount,
+                utxos,
+            } => {
+                let utxos: Vec<UtxoOutPoint> = utxos
+                    .into_iter()
+                    .map(parse_utxo_outpoint)
+                    .collect::<Result<Vec<_>, WalletCliError>>()?;
+                let amount = parse_coin_amount(chain_config, &amount)?;
+                let address = parse_address(chain_config, &address)?;
+                let tx = self
+                    .get_synced_controller()
+                    .await?
+                    .create_unsigned_send_to_address(address, amount, utxos)
+                    .await
+                    .map_err(WalletCliError::Controller)?;
+                Ok(ConsoleCommand::Print(tx.hex_encode()))
+            }
+
             WalletCommand::SendTokensToAddress {
                 token_id,
                 address,
@@ -1008,7 +1214,7 @@ impl CommandHandler {
                     parse_token_amount(token_number_of_decimals, &amount)?
                 };
 
-                self.get_synced_controller()
+                self.get_synced_controller_for_signing()
                     .await?
                     .send_tokens_to_address(token_id, address, amount)
                     .await
@@ -1021,7 +1227,7 @@ impl CommandHandler {
                 let pool_id_address = Address::from_str(chain_config, &pool_id)?;
 
                 let delegation_id = self
-                    .get_synced_controller()
+                    .get_synced_controller_for_signing()
                     .await?
                     .create_delegation(address, pool_id_address.decode_object(chain_config)?)
                     .await
@@ -1039,7 +1245,7 @@ impl CommandHandler {
                 let amount = parse_coin_amount(chain_config, &amount)?;
                 let delegation_id_address = Address::from_str(chain_config, &delegation_id)?;
 
-                self.get_synced_controller()
+                self.get_synced_controller_for_signing()
                     .await?
                     .delegate_staking(amount, delegation_id_address.decode_object(chain_config)?)
                     .await
@@ -1058,7 +1264,7 @@ impl CommandHandler {
                 let amount = parse_coin_amount(chain_config, &amount)?;
                 let delegation_id_address = Address::from_str(chain_config, &delegation_id)?;
                 let address = parse_address(chain_config, &address)?;
-                self.get_synced_controller()
+                self.get_synced_controller_for_signing()
                     .await?
                     .send_to_address_from_delegation(
                         address,
@@ -1083,7 +1289,7 @@ impl CommandHandler {
                 let cost_per_block = parse_coin_amount(chain_config, &cost_per_block)?;
                 let margin_ratio_per_thousand =
                     to_per_thousand(&margin_ratio_per_thousand, "margin ratio")?;
-                self.get_synced_controller()
+                self.get_synced_controller_for_signing()
                     .await?
                     .create_stake_pool_tx(
                         amount,
@@ -1099,7 +1305,7 @@ impl CommandHandler {
 
             WalletCommand::DecommissionStakePool { pool_id } => {
                 let pool_id = parse_pool_id(chain_config, pool_id.as_str())?;
-                self.get_synced_controller()
+                self.get_synced_controller_for_signing()
                     .await?
                     .decommission_stake_pool(pool_id)
                     .await
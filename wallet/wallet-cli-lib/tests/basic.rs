@@ -33,6 +33,10 @@ async fn wallet_cli_basic(#[case] seed: Seed) {
     let output = test.exec("bestblockheight");
     assert_eq!(output, "0");
 
+    // Decoding doesn't need an open wallet
+    let output = test.exec("decodetransaction 0102");
+    assert!(output.starts_with("Invalid input: Invalid transaction"));
+
     test.shutdown().await;
 }
 
@@ -82,3 +86,73 @@ async fn produce_blocks(#[case] seed: Seed) {
 
     test.shutdown().await;
 }
+
+#[rstest]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn watch_only_wallet(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let test = CliTestFramework::setup(&mut rng).await;
+
+    test.create_genesis_wallet();
+    let account_public_key = test.exec("showaccountpublickey");
+    assert_eq!(test.exec("closewallet"), "Successfully closed the wallet.");
+
+    let file_name = test
+        .test_root
+        .fresh_test_dir("watch only dir")
+        .as_ref()
+        .join("watch_only_wallet")
+        .to_str()
+        .unwrap()
+        .to_owned();
+    assert_eq!(
+        test.exec(&format!(
+            "createwatchonly \"{file_name}\" {account_public_key}"
+        )),
+        "New watch-only wallet created successfully"
+    );
+
+    // The balance of the genesis wallet is visible without the private keys
+    assert_eq!(test.exec("getbalance"), "Coins amount: 99960000");
+
+    let address = test.exec("newaddress");
+    assert_eq!(
+        test.exec(&format!("sendtoaddress {address} 1")),
+        "The wallet is watch-only and cannot sign transactions"
+    );
+    assert_eq!(
+        test.exec("startstaking"),
+        "The wallet is watch-only and cannot sign transactions"
+    );
+    assert_eq!(
+        test.exec("exportprivatekeys --confirm"),
+        "The wallet is watch-only and cannot sign transactions"
+    );
+
+    test.shutdown().await;
+}
+
+#[rstest]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn export_private_keys(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let test = CliTestFramework::setup(&mut rng).await;
+
+    test.create_genesis_wallet();
+    assert_eq!(
+        test.exec("encryptprivatekeys password"),
+        "Successfully encrypted the private keys of the wallet."
+    );
+
+    // Nothing sensitive is printed without the confirmation
+    assert!(test.exec("exportprivatekeys password").starts_with("WARNING: "));
+
+    assert!(test
+        .exec("exportprivatekeys wrong_password --confirm")
+        .ends_with("Invalid wallet password"));
+    assert!(test.exec("exportprivatekeys password --confirm").starts_with("SENSITIVE: "));
+
+    test.shutdown().await;
+}
@@ -172,6 +172,7 @@ async fn start_node(chain_config: Arc<ChainConfig>) -> (subsystem::Manager, Sock
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -189,6 +190,12 @@ async fn start_node(chain_config: Arc<ChainConfig>) -> (subsystem::Manager, Sock
         max_singular_unconnected_headers: Default::default(),
         sync_stalling_timeout: Default::default(),
         enable_block_relay_peers: Default::default(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
     };
     let rpc_creds = RpcCreds::basic(RPC_USERNAME, RPC_PASSWORD).unwrap();
 
@@ -216,6 +223,7 @@ async fn start_node(chain_config: Arc<ChainConfig>) -> (subsystem::Manager, Sock
 
     let mempool = mempool::make_mempool(
         Arc::clone(&chain_config),
+        mempool::MempoolConfig::default(),
         chainstate.clone(),
         Default::default(),
     );
@@ -250,6 +258,7 @@ async fn start_node(chain_config: Arc<ChainConfig>) -> (subsystem::Manager, Sock
     let rpc = rpc::Builder::new(http_bind_address, Some(rpc_creds))
         .register(node_lib::rpc::init(
             manager.make_shutdown_trigger(),
+            manager.health_monitor(),
             chain_config,
         ))
         .register(block_prod.clone().into_rpc())
@@ -299,11 +308,14 @@ impl CliTestFramework {
                     chain_max_block_header_size: None,
                     chain_max_block_size_with_standard_txs: None,
                     chain_max_block_size_with_smart_contracts: None,
+                    chain_max_block_size: None,
+                    chain_max_block_tx_count: None,
                     chain_initial_difficulty: None,
                     chain_pos_netupgrades: None,
                     chain_pos_netupgrades_v0_to_v1: None,
                     chain_genesis_block_timestamp: None,
                     chain_genesis_staking_settings: GenesisStakingSettings::default(),
+                    chain_genesis_file: None,
                 },
                 run_options: wallet_cli_lib::config::CliArgs {
                     wallet_file: None,
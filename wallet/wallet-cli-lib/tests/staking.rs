@@ -66,3 +66,61 @@ async fn staking_locked_wallet(#[case] seed: Seed) {
 
     test.shutdown().await;
 }
+
+#[rstest]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn staking_status(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let test = CliTestFramework::setup(&mut rng).await;
+
+    test.create_genesis_wallet();
+
+    // The genesis wallet owns the genesis staking pool
+    let status = test.exec("stakingstatus");
+    assert!(
+        status.starts_with("Not staking, total pool balance: 40000\nPool Id: "),
+        "{status}"
+    );
+
+    assert_eq!(test.exec("startstaking"), "Staking started successfully");
+    assert!(test.exec("stakingstatus").starts_with("Staking, total pool balance: "));
+
+    assert_eq!(test.exec("stopstaking"), "Success");
+    assert!(test.exec("stakingstatus").starts_with("Not staking, total pool balance: "));
+
+    test.shutdown().await;
+}
+
+#[rstest]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn staking_without_pool(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let test = CliTestFramework::setup(&mut rng).await;
+
+    test.create_genesis_wallet();
+
+    assert_eq!(
+        test.exec("createnewaccount"),
+        "Success, the new account index is: 1"
+    );
+    assert_eq!(test.exec("selectaccount 1"), "Success");
+
+    assert_eq!(
+        test.exec("stakingstatus"),
+        "Not staking, no staking pools found"
+    );
+    assert_eq!(
+        test.exec("startstaking"),
+        "Controller error: No staking pool found"
+    );
+    assert_eq!(
+        test.exec("stakingstatus"),
+        "Not staking, no staking pools found"
+    );
+
+    test.shutdown().await;
+}
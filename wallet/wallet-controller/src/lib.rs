@@ -52,7 +52,7 @@ use common::{
 };
 use consensus::GenerateBlockInputData;
 use crypto::{
-    key::hdkd::u31::U31,
+    key::{extended::ExtendedPublicKey, hdkd::u31::U31},
     random::{make_pseudo_rng, Rng},
 };
 use logging::log;
@@ -87,6 +87,8 @@ pub enum ControllerError<T: NodeInterface> {
     WalletIsLocked,
     #[error("Cannot lock wallet because staking is running")]
     StakingRunning,
+    #[error("Staking is not supported by the chain at height {0}")]
+    StakingNotSupported(BlockHeight),
 }
 
 #[derive(Clone, Copy)]
@@ -216,6 +218,31 @@ impl<T: NodeInterface + Clone + Send + Sync + 'static, W: WalletEvents> Controll
         Ok(wallet)
     }
 
+    pub fn create_watch_only_wallet(
+        chain_config: Arc<ChainConfig>,
+        file_path: impl AsRef<Path>,
+        account_public_key: ExtendedPublicKey,
+    ) -> Result<DefaultWallet, ControllerError<T>> {
+        utils::ensure!(
+            !file_path.as_ref().exists(),
+            ControllerError::WalletFileError(
+                file_path.as_ref().to_owned(),
+                "File already exists".to_owned()
+            )
+        );
+
+        let db = wallet::wallet::open_or_create_wallet_file(file_path)
+            .map_err(ControllerError::WalletError)?;
+        let wallet = wallet::Wallet::create_watch_only_wallet(
+            Arc::clone(&chain_config),
+            db,
+            account_public_key,
+        )
+        .map_err(ControllerError::WalletError)?;
+
+        Ok(wallet)
+    }
+
     fn make_backup_wallet_file(file_path: impl AsRef<Path>) -> Result<(), ControllerError<T>> {
         let backup_name = file_path
             .as_ref()
@@ -340,6 +367,11 @@ impl<T: NodeInterface + Clone + Send + Sync + 'static, W: WalletEvents> Controll
         self.wallet.lock_wallet().map_err(ControllerError::WalletError)
     }
 
+    /// Returns true if the wallet only watches an account and can't sign transactions
+    pub fn is_watch_only(&self) -> bool {
+        self.wallet.is_watch_only()
+    }
+
     pub fn account_names(&self) -> impl Iterator<Item = &Option<String>> {
         self.wallet.account_names()
     }
@@ -459,6 +491,10 @@ impl<T: NodeInterface + Clone + Send + Sync + 'static, W: WalletEvents> Controll
         Ok(())
     }
 
+    pub fn is_staking(&self, account_index: U31) -> bool {
+        self.staking_started.contains(&account_index)
+    }
+
     pub fn best_block(&self) -> (Id<GenBlock>, BlockHeight) {
         *self
             .wallet
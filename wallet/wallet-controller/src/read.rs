@@ -22,7 +22,10 @@ use common::{
     chain::{ChainConfig, DelegationId, Destination, PoolId, Transaction, TxOutput, UtxoOutPoint},
     primitives::{id::WithId, Amount},
 };
-use crypto::key::hdkd::{child_number::ChildNumber, u31::U31};
+use crypto::key::{
+    extended::{ExtendedPrivateKey, ExtendedPublicKey},
+    hdkd::{child_number::ChildNumber, u31::U31},
+};
 use futures::{stream::FuturesUnordered, TryStreamExt};
 use node_comm::node_traits::NodeInterface;
 use utils::tap_error_log::LogError;
@@ -64,6 +67,22 @@ impl<'a, T: NodeInterface> ReadOnlyController<'a, T> {
         self.account_index
     }
 
+    pub fn account_public_key(&self) -> Result<&'a ExtendedPublicKey, ControllerError<T>> {
+        self.wallet
+            .account_public_key(self.account_index)
+            .map_err(ControllerError::WalletError)
+    }
+
+    /// The extended private key of the account; the password is required if the wallet is encrypted
+    pub fn export_account_private_key(
+        &self,
+        password: Option<&String>,
+    ) -> Result<ExtendedPrivateKey, ControllerError<T>> {
+        self.wallet
+            .export_account_private_key(self.account_index, password)
+            .map_err(ControllerError::WalletError)
+    }
+
     pub fn get_balance(
         &self,
         utxo_states: UtxoStates,
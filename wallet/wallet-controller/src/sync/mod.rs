@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{cmp::Reverse, collections::BTreeMap, iter};
+use std::{cmp::Reverse, collections::BTreeMap};
 
 use common::{
     chain::{block::timestamp::BlockTimestamp, Block, ChainConfig, GenBlock},
@@ -127,7 +127,7 @@ pub async fn sync_once<T: NodeInterface>(
         } = wallet.syncing_state();
         if account_best_blocks
             .values()
-            .chain(iter::once(&unused_account_best_block))
+            .chain(unused_account_best_block.iter())
             .all(|wallet_best_block| chain_info.best_block_id == wallet_best_block.0)
         {
             // if all accounts are on the latest tip nothing to sync
@@ -319,7 +319,7 @@ async fn group_accounts_by_mainchain_blocks<T: NodeInterface>(
     node_block_id: Id<GenBlock>,
     node_block_height: BlockHeight,
     account_best_blocks: BTreeMap<U31, (Id<GenBlock>, BlockHeight)>,
-    unused_account_best_block: (Id<GenBlock>, BlockHeight),
+    unused_account_best_block: Option<(Id<GenBlock>, BlockHeight)>,
 ) -> Result<Vec<(NextBlockInfo, Vec<AccountType>)>, ControllerError<T>> {
     let mut accounts_grouped: BTreeMap<(Id<GenBlock>, BlockHeight), Vec<AccountType>> =
         BTreeMap::new();
@@ -329,10 +329,12 @@ async fn group_accounts_by_mainchain_blocks<T: NodeInterface>(
             .or_default()
             .push(AccountType::Account(*account));
     }
-    accounts_grouped
-        .entry(unused_account_best_block)
-        .or_default()
-        .push(AccountType::UnusedAccount);
+    if let Some(unused_account_best_block) = unused_account_best_block {
+        accounts_grouped
+            .entry(unused_account_best_block)
+            .or_default()
+            .push(AccountType::UnusedAccount);
+    }
 
     let mut accounts_by_common_block = Vec::new();
     for ((acc_block_id, acc_block_height), acc) in accounts_grouped {
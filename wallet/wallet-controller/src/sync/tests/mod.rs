@@ -14,6 +14,7 @@
 // limitations under the License.
 
 use std::{
+    num::NonZeroUsize,
     sync::{Arc, Mutex},
     time::Duration,
 };
@@ -23,7 +24,7 @@ use chainstate_test_framework::TestFramework;
 use common::{
     chain::{
         tokens::{RPCTokenInfo, TokenId},
-        DelegationId, PoolId, SignedTransaction, Transaction,
+        DelegationId, PoolId, SignedTransaction, Transaction, TxOutput, UtxoOutPoint,
     },
     primitives::Amount,
 };
@@ -31,7 +32,7 @@ use consensus::GenerateBlockInputData;
 use crypto::random::{seq::IteratorRandom, CryptoRng, Rng};
 use futures::executor::block_on;
 use logging::log;
-use mempool::{tx_accumulator::PackingStrategy, FeeRate};
+use mempool::{tx_accumulator::PackingStrategy, FeeRate, FeeRateEstimate};
 use node_comm::{
     node_traits::{ConnectedPeer, PeerId},
     rpc_client::NodeRpcError,
@@ -92,10 +93,10 @@ impl SyncingWallet for MockWallet {
                 DEFAULT_ACCOUNT_INDEX,
                 (self.get_best_block_id(), self.get_block_height()),
             )]),
-            unused_account_best_block: (
+            unused_account_best_block: Some((
                 self.get_unused_acc_best_block_id(),
                 self.get_unused_acc_block_height(),
-            ),
+            )),
         }
     }
 
@@ -267,6 +268,10 @@ impl NodeInterface for MockNode {
         unreachable!()
     }
 
+    async fn get_utxo(&self, _outpoint: UtxoOutPoint) -> Result<Option<TxOutput>, Self::Error> {
+        unreachable!()
+    }
+
     async fn generate_block(
         &self,
         _input_data: GenerateBlockInputData,
@@ -324,6 +329,13 @@ impl NodeInterface for MockNode {
     async fn mempool_get_fee_rate(&self, _in_top_x_mb: usize) -> Result<FeeRate, Self::Error> {
         Ok(FeeRate::new(Amount::ZERO))
     }
+
+    async fn mempool_estimate_fee_rate(
+        &self,
+        _target_blocks: NonZeroUsize,
+    ) -> Result<FeeRateEstimate, Self::Error> {
+        Ok(FeeRateEstimate::fallback(FeeRate::new(Amount::ZERO)))
+    }
 }
 
 fn create_chain(node: &MockNode, rng: &mut (impl Rng + CryptoRng), parent: u64, count: usize) {
@@ -18,9 +18,10 @@ use std::collections::BTreeSet;
 use common::{
     address::Address,
     chain::{
+        timelock::OutputTimeLock,
         tokens::{Metadata, TokenId, TokenIssuance},
-        ChainConfig, DelegationId, Destination, PoolId, SignedTransaction, Transaction, TxOutput,
-        UtxoOutPoint,
+        ChainConfig, DelegationId, Destination, PoolId, RequiredConsensus, SignedTransaction,
+        Transaction, TxOutput, UtxoOutPoint,
     },
     primitives::{per_thousand::PerThousand, Amount, Id},
 };
@@ -35,8 +36,8 @@ use logging::log;
 use node_comm::node_traits::NodeInterface;
 use wallet::{
     send_request::{
-        make_address_output, make_address_output_token, make_create_delegation_output,
-        StakePoolDataArguments,
+        make_address_output, make_address_output_token, make_address_output_with_timelock,
+        make_create_delegation_output, StakePoolDataArguments,
     },
     wallet_events::WalletEvents,
     DefaultWallet, WalletError,
@@ -158,14 +159,22 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
         Ok(token_id)
     }
 
+    /// Send coins to the address, if a timelock is specified the coins can only be spent
+    /// by the receiver after it expires
     pub async fn send_to_address(
         &mut self,
         address: Address<Destination>,
         amount: Amount,
         selected_utxos: Vec<UtxoOutPoint>,
+        timelock: Option<OutputTimeLock>,
     ) -> Result<(), ControllerError<T>> {
-        let output = make_address_output(self.chain_config, address, amount)
-            .map_err(ControllerError::WalletError)?;
+        let output = match timelock {
+            Some(timelock) => {
+                make_address_output_with_timelock(self.chain_config, address, amount, timelock)
+            }
+            None => make_address_output(self.chain_config, address, amount),
+        }
+        .map_err(ControllerError::WalletError)?;
         let (current_fee_rate, consolidate_fee_rate) =
             self.get_current_and_consolidation_fee_rate().await?;
 
@@ -183,6 +192,30 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
         self.broadcast_to_mempool(tx).await
     }
 
+    /// Create a transaction like `send_to_address` but return it without signatures and
+    /// without broadcasting it, so it can be signed elsewhere
+    pub async fn create_unsigned_send_to_address(
+        &mut self,
+        address: Address<Destination>,
+        amount: Amount,
+        selected_utxos: Vec<UtxoOutPoint>,
+    ) -> Result<Transaction, ControllerError<T>> {
+        let output = make_address_output(self.chain_config, address, amount)
+            .map_err(ControllerError::WalletError)?;
+        let (current_fee_rate, consolidate_fee_rate) =
+            self.get_current_and_consolidation_fee_rate().await?;
+
+        self.wallet
+            .create_unsigned_transaction_to_addresses(
+                self.account_index,
+                [output],
+                selected_utxos,
+                current_fee_rate,
+                consolidate_fee_rate,
+            )
+            .map_err(ControllerError::WalletError)
+    }
+
     pub async fn create_delegation(
         &mut self,
         address: Address<Destination>,
@@ -351,6 +384,10 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
     }
 
     pub fn start_staking(&mut self) -> Result<(), ControllerError<T>> {
+        utils::ensure!(
+            !self.wallet.is_watch_only(),
+            ControllerError::WalletError(WalletError::WatchOnlyWallet)
+        );
         utils::ensure!(!self.wallet.is_locked(), ControllerError::WalletIsLocked);
         // Make sure that account_index is valid and that pools exist
         let pool_ids = self
@@ -358,6 +395,19 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
             .get_pool_ids(self.account_index)
             .map_err(ControllerError::WalletError)?;
         utils::ensure!(!pool_ids.is_empty(), ControllerError::NoStakingPool);
+        // Make sure that the next block can actually be produced with PoS
+        let (_, best_block_height) = self
+            .wallet
+            .get_best_block_for_account(self.account_index)
+            .map_err(ControllerError::WalletError)?;
+        let next_block_height = best_block_height.next_height();
+        utils::ensure!(
+            matches!(
+                self.chain_config.net_upgrades().consensus_status(next_block_height),
+                RequiredConsensus::PoS(_)
+            ),
+            ControllerError::StakingNotSupported(next_block_height)
+        );
         log::info!("Start staking, account_index: {}", self.account_index);
         self.staking_started.insert(self.account_index);
         Ok(())
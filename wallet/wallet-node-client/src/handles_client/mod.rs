@@ -13,17 +13,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::num::NonZeroUsize;
+
 use blockprod::{BlockProductionError, BlockProductionHandle};
 use chainstate::{BlockSource, ChainInfo, ChainstateError, ChainstateHandle};
 use common::{
     chain::{
         tokens::{RPCTokenInfo, TokenId},
-        Block, DelegationId, GenBlock, PoolId, SignedTransaction, Transaction,
+        Block, DelegationId, GenBlock, PoolId, SignedTransaction, Transaction, TxOutput,
+        UtxoOutPoint,
     },
     primitives::{Amount, BlockHeight, Id},
 };
 use consensus::GenerateBlockInputData;
-use mempool::{tx_accumulator::PackingStrategy, FeeRate, MempoolHandle};
+use mempool::{tx_accumulator::PackingStrategy, FeeRate, FeeRateEstimate, MempoolHandle};
 use p2p::{
     error::P2pError,
     interface::types::ConnectedPeer,
@@ -185,6 +188,11 @@ impl NodeInterface for WalletHandlesClient {
         Ok(result)
     }
 
+    async fn get_utxo(&self, outpoint: UtxoOutPoint) -> Result<Option<TxOutput>, Self::Error> {
+        let result = self.chainstate.call(move |this| this.utxo(&outpoint)).await??;
+        Ok(result.map(|utxo| utxo.take_output()))
+    }
+
     async fn generate_block(
         &self,
         input_data: GenerateBlockInputData,
@@ -268,4 +276,12 @@ impl NodeInterface for WalletHandlesClient {
         let res = self.mempool.call(move |this| this.get_fee_rate(in_top_x_mb)).await??;
         Ok(res)
     }
+
+    async fn mempool_estimate_fee_rate(
+        &self,
+        target_blocks: NonZeroUsize,
+    ) -> Result<FeeRateEstimate, Self::Error> {
+        let res = self.mempool.call(move |this| this.estimate_fee_rate(target_blocks)).await??;
+        Ok(res)
+    }
 }
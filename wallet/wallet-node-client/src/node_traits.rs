@@ -13,17 +13,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::num::NonZeroUsize;
+
 use chainstate::ChainInfo;
 use common::{
     chain::{
         tokens::{RPCTokenInfo, TokenId},
-        Block, DelegationId, GenBlock, PoolId, SignedTransaction, Transaction,
+        Block, DelegationId, GenBlock, PoolId, SignedTransaction, Transaction, TxOutput,
+        UtxoOutPoint,
     },
     primitives::{Amount, BlockHeight, Id},
 };
 
 use consensus::GenerateBlockInputData;
-use mempool::{tx_accumulator::PackingStrategy, FeeRate};
+use mempool::{tx_accumulator::PackingStrategy, FeeRate, FeeRateEstimate};
 use p2p::types::{bannable_address::BannableAddress, ip_or_socket_address::IpOrSocketAddress};
 pub use p2p::{interface::types::ConnectedPeer, types::peer_id::PeerId};
 
@@ -57,6 +60,7 @@ pub trait NodeInterface {
         delegation_id: DelegationId,
     ) -> Result<Option<Amount>, Self::Error>;
     async fn get_token_info(&self, token_id: TokenId) -> Result<Option<RPCTokenInfo>, Self::Error>;
+    async fn get_utxo(&self, outpoint: UtxoOutPoint) -> Result<Option<TxOutput>, Self::Error>;
     async fn generate_block(
         &self,
         input_data: GenerateBlockInputData,
@@ -82,4 +86,8 @@ pub trait NodeInterface {
         -> Result<(), Self::Error>;
 
     async fn mempool_get_fee_rate(&self, in_top_x_mb: usize) -> Result<FeeRate, Self::Error>;
+    async fn mempool_estimate_fee_rate(
+        &self,
+        target_blocks: NonZeroUsize,
+    ) -> Result<FeeRateEstimate, Self::Error>;
 }
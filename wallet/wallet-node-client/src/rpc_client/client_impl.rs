@@ -13,17 +13,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::num::NonZeroUsize;
+
 use blockprod::rpc::BlockProductionRpcClient;
 use chainstate::{rpc::ChainstateRpcClient, ChainInfo};
 use common::{
     chain::{
         tokens::{RPCTokenInfo, TokenId},
-        Block, DelegationId, GenBlock, PoolId, SignedTransaction, Transaction,
+        Block, DelegationId, GenBlock, PoolId, SignedTransaction, Transaction, TxOutput,
+        UtxoOutPoint,
     },
     primitives::{Amount, BlockHeight, Id},
 };
 use consensus::GenerateBlockInputData;
-use mempool::{rpc::MempoolRpcClient, tx_accumulator::PackingStrategy, FeeRate};
+use mempool::{rpc::MempoolRpcClient, tx_accumulator::PackingStrategy, FeeRate, FeeRateEstimate};
 use p2p::{
     interface::types::ConnectedPeer,
     rpc::P2pRpcClient,
@@ -128,6 +131,13 @@ impl NodeInterface for NodeRpcClient {
             .map_err(NodeRpcError::ResponseError)
     }
 
+    async fn get_utxo(&self, outpoint: UtxoOutPoint) -> Result<Option<TxOutput>, Self::Error> {
+        ChainstateRpcClient::get_utxo(&self.http_client, outpoint.into())
+            .await
+            .map(|output| output.map(HexEncoded::take))
+            .map_err(NodeRpcError::ResponseError)
+    }
+
     async fn generate_block(
         &self,
         input_data: GenerateBlockInputData,
@@ -144,7 +154,7 @@ impl NodeInterface for NodeRpcClient {
             packing_strategy,
         )
         .await
-        .map(HexEncoded::take)
+        .map(|generated_block| generated_block.block.take())
         .map_err(NodeRpcError::ResponseError)
     }
 
@@ -227,4 +237,13 @@ impl NodeInterface for NodeRpcClient {
             .await
             .map_err(NodeRpcError::ResponseError)
     }
+
+    async fn mempool_estimate_fee_rate(
+        &self,
+        target_blocks: NonZeroUsize,
+    ) -> Result<FeeRateEstimate, Self::Error> {
+        MempoolRpcClient::estimate_fee_rate(&self.http_client, target_blocks)
+            .await
+            .map_err(NodeRpcError::ResponseError)
+    }
 }
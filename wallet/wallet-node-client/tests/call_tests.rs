@@ -57,6 +57,7 @@ pub async fn start_subsystems(
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         max_inbound_connections: Default::default(),
+        max_outbound_connections: Default::default(),
         ban_threshold: Default::default(),
         ban_duration: Default::default(),
         outbound_connection_timeout: Default::default(),
@@ -74,6 +75,12 @@ pub async fn start_subsystems(
         max_singular_unconnected_headers: Default::default(),
         sync_stalling_timeout: Default::default(),
         enable_block_relay_peers: Default::default(),
+        sync_maintenance_interval: Default::default(),
+        parallel_chainstate_reads: Default::default(),
+        reserved_node_reconnect_base_delay: Default::default(),
+        reserved_node_reconnect_max_delay: Default::default(),
+        mdns_config: Default::default(),
+        blocks_pruned: Default::default(),
     };
 
     let chainstate = make_chainstate(
@@ -90,6 +97,7 @@ pub async fn start_subsystems(
 
     let mempool = mempool::make_mempool(
         Arc::clone(&chain_config),
+        mempool::MempoolConfig::default(),
         chainstate_handle.clone(),
         Default::default(),
     );
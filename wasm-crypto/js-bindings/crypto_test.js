@@ -13,6 +13,10 @@ import {
   make_default_account_pubkey,
   make_receiving_address,
   pubkey_to_string,
+  derive_child,
+  derive_path,
+  public_key_to_address,
+  address_to_public_key_hash,
   Network,
 } from "../pkg/wasm_crypto.js";
 
@@ -109,5 +113,63 @@ export async function run_test() {
     if (address != "tmt1q9dn5m4svn8sds3fcy09kpxrefnu75xekgr5wa3n") {
       throw new Error("Incorrect address generated");
     }
+
+    const same_address = public_key_to_address(receiving_pubkey, Network.Testnet);
+    if (same_address != address) {
+      throw new Error("Incorrect address generated");
+    }
+    const pubkey_hash = address_to_public_key_hash(address, Network.Testnet);
+    console.log(`pubkey hash = ${pubkey_hash}`);
+
+    try {
+      address_to_public_key_hash(address, Network.Mainnet);
+      throw new Error("Address of a different network worked somehow!");
+    } catch (e) {
+      if (!e.includes("Invalid address")) {
+        throw e;
+      }
+      console.log("Tested decoding address of a different network successfully");
+    }
+  }
+
+  {
+    // BIP32 derivation from the master key of the mnemonic "abandon ... about"
+    const master_key = hex_to_bytes(
+      "00007923408dadd3c7b56eed15567707ae5e5dca089de972e07f3b860450e2a3b70e1837c1be8e2995ec11cda2b066151be2cfb48adf9e47b151d46adab3a21cdf67"
+    );
+    const expected_key =
+      "00038000002c80000000800000003da4bc190a2680111d31fadfdc905f2a7f6ce77c6f109919116f253d43445219fe64af825b5b78554c33a28b23085fc082f691b3c712cc1d4e66e133297da87a";
+
+    const path_key = derive_path(master_key, "m/44'/0'/0'");
+    if (bytes_to_hex(path_key) != expected_key) {
+      throw new Error("Incorrect key derived from path");
+    }
+
+    let child_key = master_key;
+    for (const index of [44, 0, 0]) {
+      child_key = derive_child(child_key, index, true);
+    }
+    if (bytes_to_hex(child_key) != expected_key) {
+      throw new Error("Incorrect key derived from children");
+    }
+    console.log("Tested BIP32 key derivation successfully");
+
+    try {
+      derive_child(master_key, 1 << 31, false);
+      throw new Error("Invalid child index worked somehow!");
+    } catch (e) {
+      if (!e.includes("Invalid key index, MSB bit set")) {
+        throw e;
+      }
+      console.log("Tested invalid child index with set MSB bit successfully");
+    }
   }
 }
+
+function hex_to_bytes(hex) {
+  return Uint8Array.from(hex.match(/../g).map((byte) => parseInt(byte, 16)));
+}
+
+function bytes_to_hex(bytes) {
+  return Array.from(bytes, (byte) => byte.toString(16).padStart(2, "0")).join("");
+}
@@ -29,6 +29,12 @@ pub enum Error {
     InvalidMnemonic,
     #[error("Invalid key index, MSB bit set")]
     InvalidKeyIndex,
+    #[error("Invalid derivation path")]
+    InvalidDerivationPath,
+    #[error("Key derivation failed")]
+    KeyDerivationFailed,
+    #[error("Invalid address")]
+    InvalidAddress,
 }
 
 // This is required to make an error readable in JavaScript
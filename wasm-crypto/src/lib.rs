@@ -13,6 +13,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::str::FromStr;
+
 pub use bip39::{Language, Mnemonic};
 use common::{
     address::{pubkeyhash::PublicKeyHash, Address},
@@ -23,7 +25,12 @@ use common::{
 };
 use crypto::key::{
     extended::{ExtendedKeyKind, ExtendedPrivateKey, ExtendedPublicKey},
-    hdkd::{child_number::ChildNumber, derivable::Derivable, u31::U31},
+    hdkd::{
+        child_number::ChildNumber,
+        derivable::{Derivable, DerivationError},
+        derivation_path::DerivationPath,
+        u31::U31,
+    },
     KeyKind, PrivateKey, PublicKey, Signature,
 };
 use error::Error;
@@ -33,6 +40,7 @@ use wasm_bindgen::prelude::*;
 pub mod error;
 
 #[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
 pub enum Network {
     Mainnet,
     Testnet,
@@ -103,8 +111,63 @@ pub fn make_receiving_address(public_key_bytes: &[u8], key_index: u32) -> Result
     Ok(public_key.encode())
 }
 
+impl From<DerivationError> for Error {
+    fn from(value: DerivationError) -> Self {
+        match value {
+            DerivationError::InvalidChildNumber(_) => Error::InvalidKeyIndex,
+            DerivationError::InvalidChildNumberFormat
+            | DerivationError::InvalidDerivationPathFormat
+            | DerivationError::PathTooLong
+            | DerivationError::CannotDerivePath(_) => Error::InvalidDerivationPath,
+            DerivationError::UnsupportedKeyType
+            | DerivationError::KeyDerivationError
+            | DerivationError::CannotDeriveHardenedKeyFromPublicKey(_) => {
+                Error::KeyDerivationFailed
+            }
+        }
+    }
+}
+
+/// Derive the child with the given index from an encoded extended private key and return the
+/// encoded extended private key of the child
+#[wasm_bindgen]
+pub fn derive_child(
+    extended_private_key: &[u8],
+    key_index: u32,
+    hardened: bool,
+) -> Result<Vec<u8>, Error> {
+    let key = ExtendedPrivateKey::decode_all(&mut &extended_private_key[..])
+        .map_err(|_| Error::InvalidPrivateKeyEncoding)?;
+
+    let index = U31::from_u32(key_index).ok_or(Error::InvalidKeyIndex)?;
+    let child_number = if hardened {
+        ChildNumber::from_hardened(index)
+    } else {
+        ChildNumber::from_normal(index)
+    };
+
+    Ok(key.derive_child(child_number)?.encode())
+}
+
+/// Derive a key from an encoded extended private key given an absolute derivation path, such as
+/// `m/44'/19788'/0'/0/1`. The path has to start with the derivation path of the given key.
+#[wasm_bindgen]
+pub fn derive_path(extended_private_key: &[u8], path: &str) -> Result<Vec<u8>, Error> {
+    let key = ExtendedPrivateKey::decode_all(&mut &extended_private_key[..])
+        .map_err(|_| Error::InvalidPrivateKeyEncoding)?;
+    let path = DerivationPath::from_str(path)?;
+
+    Ok(key.derive_absolute_path(&path)?.encode())
+}
+
 #[wasm_bindgen]
 pub fn pubkey_to_string(public_key_bytes: &[u8], network: Network) -> Result<String, Error> {
+    public_key_to_address(public_key_bytes, network)
+}
+
+/// Encode the public key hash of the given public key as an address of the given network
+#[wasm_bindgen]
+pub fn public_key_to_address(public_key_bytes: &[u8], network: Network) -> Result<String, Error> {
     let public_key = PublicKey::decode_all(&mut &public_key_bytes[..])
         .map_err(|_| Error::InvalidPublicKeyEncoding)?;
     let chain_config = Builder::new(network.into()).build();
@@ -119,6 +182,25 @@ pub fn pubkey_to_string(public_key_bytes: &[u8], network: Network) -> Result<Str
     )
 }
 
+/// Decode the encoded public key hash from an address. The network is required to check that the
+/// address has the right prefix.
+#[wasm_bindgen]
+pub fn address_to_public_key_hash(address: &str, network: Network) -> Result<Vec<u8>, Error> {
+    let chain_config = Builder::new(network.into()).build();
+
+    let destination = Address::<Destination>::from_str(&chain_config, address)
+        .and_then(|address| address.decode_object(&chain_config))
+        .map_err(|_| Error::InvalidAddress)?;
+
+    match destination {
+        Destination::Address(public_key_hash) => Ok(public_key_hash.encode()),
+        Destination::AnyoneCanSpend
+        | Destination::PublicKey(_)
+        | Destination::ScriptHash(_)
+        | Destination::ClassicMultisig(_) => Err(Error::InvalidAddress),
+    }
+}
+
 #[wasm_bindgen]
 pub fn public_key_from_private_key(private_key: &[u8]) -> Result<Vec<u8>, Error> {
     let private_key = PrivateKey::decode_all(&mut &private_key[..])
@@ -153,7 +235,10 @@ pub fn verify_signature(
 mod tests {
     use crypto::random::Rng;
     use rstest::rstest;
-    use test_utils::random::{make_seedable_rng, Seed};
+    use test_utils::{
+        assert_encoded_eq,
+        random::{make_seedable_rng, Seed},
+    };
 
     use super::*;
 
@@ -206,4 +291,139 @@ mod tests {
             assert!(!verification_result);
         }
     }
+
+    fn bip32_test_master_key() -> Vec<u8> {
+        let mnemonic_str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mnemonic = Mnemonic::parse_in(Language::English, mnemonic_str).unwrap();
+        let master_key = ExtendedPrivateKey::new_master(
+            &mnemonic.to_seed(""),
+            ExtendedKeyKind::Secp256k1Schnorr,
+        )
+        .unwrap();
+        master_key.encode()
+    }
+
+    #[rstest]
+    #[case(
+        "m/44'/0'/0'",
+        "038000002c8000000080000000",
+        "3da4bc190a2680111d31fadfdc905f2a7f6ce77c6f109919116f253d43445219",
+        "fe64af825b5b78554c33a28b23085fc082f691b3c712cc1d4e66e133297da87a"
+    )]
+    #[case(
+        "m/84'/0'/0'",
+        "03800000548000000080000000",
+        "4a53a0ab21b9dc95869c4e92a161194e03c0ef3ff5014ac692f433c4765490fc",
+        "e14f274d16ca0d91031b98b162618061d03930fa381af6d4caf44b01819ab6d4"
+    )]
+    #[case(
+        "m/0'/1'/2'/3'/4'/5'/6'/7'/8'/9'",
+        "0a80000000800000018000000280000003800000048000000580000006800000078000000880000009",
+        "0c61a6e40d45b7b04ac86ed5a75ebd32fca2d06ff3c1eaaef43f985920ef873f",
+        "1754f94b8f5bfbacfbbc6b71bc6b2a2aefa3bb31a5579b2da7a0543451041c6a"
+    )]
+    fn derive_bip32_vectors(
+        #[case] path: &str,
+        #[case] path_encoded: &str,
+        #[case] chain_code: &str,
+        #[case] secret: &str,
+    ) {
+        let master_key = bip32_test_master_key();
+        let expected = format!("00{path_encoded}{chain_code}{secret}");
+
+        let key = derive_path(&master_key, path).unwrap();
+        assert_encoded_eq(
+            &ExtendedPrivateKey::decode_all(&mut &key[..]).unwrap(),
+            &expected,
+        );
+
+        // Deriving the children one by one yields the same key
+        let key = DerivationPath::from_str(path)
+            .unwrap()
+            .into_iter()
+            .try_fold(master_key, |key, num| {
+                derive_child(&key, num.get_index().into_u32(), num.is_hardened())
+            });
+        assert_encoded_eq(
+            &ExtendedPrivateKey::decode_all(&mut &key.unwrap()[..]).unwrap(),
+            &expected,
+        );
+    }
+
+    #[test]
+    fn derive_invalid_input() {
+        let master_key = bip32_test_master_key();
+
+        assert!(matches!(
+            derive_child(&master_key, 1 << 31, false),
+            Err(Error::InvalidKeyIndex)
+        ));
+        assert!(matches!(
+            derive_path(&master_key, "m/0/2147483648"),
+            Err(Error::InvalidKeyIndex)
+        ));
+        assert!(matches!(
+            derive_path(&master_key, "0/1"),
+            Err(Error::InvalidDerivationPath)
+        ));
+        assert!(matches!(
+            derive_child(b"bad", 0, false),
+            Err(Error::InvalidPrivateKeyEncoding)
+        ));
+
+        // The path must start with the path of the given key
+        let child_key = derive_path(&master_key, "m/1").unwrap();
+        assert!(matches!(
+            derive_path(&child_key, "m/2/3"),
+            Err(Error::InvalidDerivationPath)
+        ));
+        assert!(derive_path(&child_key, "m/1/3").is_ok());
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy(), Network::Mainnet)]
+    #[case(Seed::from_entropy(), Network::Testnet)]
+    fn address_round_trip(#[case] seed: Seed, #[case] network: Network) {
+        let mut rng = make_seedable_rng(seed);
+
+        let (_private_key, public_key) =
+            PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+        let expected_prefix = match network {
+            Network::Mainnet => "mtc1",
+            Network::Testnet => "tmt1",
+            Network::Regtest | Network::Signet => unreachable!(),
+        };
+        let other_network = match network {
+            Network::Mainnet => Network::Testnet,
+            _ => Network::Mainnet,
+        };
+
+        let address = public_key_to_address(&public_key.encode(), network).unwrap();
+        assert!(address.starts_with(expected_prefix));
+
+        let public_key_hash = address_to_public_key_hash(&address, network).unwrap();
+        assert_eq!(public_key_hash, PublicKeyHash::from(&public_key).encode());
+
+        // The prefix of a different network is rejected
+        assert!(matches!(
+            address_to_public_key_hash(&address, other_network),
+            Err(Error::InvalidAddress)
+        ));
+
+        // Corrupting the address breaks its checksum
+        let mut corrupted = address.into_bytes();
+        let last = corrupted.last_mut().unwrap();
+        *last = if *last == b'q' { b'p' } else { b'q' };
+        let corrupted = String::from_utf8(corrupted).unwrap();
+        assert!(matches!(
+            address_to_public_key_hash(&corrupted, network),
+            Err(Error::InvalidAddress)
+        ));
+
+        assert!(matches!(
+            public_key_to_address(b"bad", network),
+            Err(Error::InvalidPublicKeyEncoding)
+        ));
+    }
 }